@@ -0,0 +1,71 @@
+//! Records the commit, branch, build time and compiler for `build_info::BuildInfo`
+//!
+//! Outside a git checkout, e.g. in a service generated from this template before its first
+//! commit, the commit and branch are `unknown`.
+
+use std::{env, path::Path, process::Command};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+const UNKNOWN: &str = "unknown";
+
+fn main() {
+    let git_sha = git(&["rev-parse", "HEAD"]);
+    let git_branch = git(&["rev-parse", "--abbrev-ref", "HEAD"]);
+    println!(
+        "cargo:rustc-env=BUILD_GIT_SHA={}",
+        git_sha.as_deref().unwrap_or(UNKNOWN)
+    );
+    println!(
+        "cargo:rustc-env=BUILD_GIT_BRANCH={}",
+        git_branch.as_deref().unwrap_or(UNKNOWN)
+    );
+    println!("cargo:rustc-env=BUILD_TIMESTAMP={}", build_timestamp());
+    println!("cargo:rustc-env=BUILD_RUSTC_VERSION={}", rustc_version());
+
+    // Rebuild when HEAD moves to another commit or branch, rather than on every build
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    if let Some(git_dir) = git(&["rev-parse", "--git-dir"]) {
+        let git_dir = Path::new(&git_dir);
+        println!("cargo:rerun-if-changed={}", git_dir.join("HEAD").display());
+        if let Some(head_ref) = git(&["symbolic-ref", "-q", "HEAD"]) {
+            println!(
+                "cargo:rerun-if-changed={}",
+                git_dir.join(head_ref).display()
+            );
+        }
+    }
+}
+
+/// Trimmed output of a git command, `None` when git is missing or the command fails
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let output = String::from_utf8(output.stdout).ok()?;
+    Some(output.trim().to_string()).filter(|output| !output.is_empty())
+}
+
+/// Now, or `SOURCE_DATE_EPOCH` for reproducible builds
+fn build_timestamp() -> String {
+    env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse().ok())
+        .and_then(|epoch| DateTime::<Utc>::from_timestamp(epoch, 0))
+        .unwrap_or_else(Utc::now)
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn rustc_version() -> String {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty())
+        .unwrap_or_else(|| UNKNOWN.to_string())
+}
@@ -0,0 +1,4 @@
+{{- define "rust-service-template.labels" -}}
+app.kubernetes.io/name: {{ .Chart.Name }}
+app.kubernetes.io/instance: {{ .Release.Name }}
+{{- end }}
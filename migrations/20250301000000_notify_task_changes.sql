@@ -0,0 +1,32 @@
+-- Publish every row change on the `task_changes` channel. NOTIFY is transactional, so
+-- listeners only hear about committed changes. The payload carries ids only: NOTIFY
+-- payloads are limited to 8000 bytes and a long description would fail the write.
+CREATE FUNCTION notify_task_change() RETURNS trigger AS $$
+DECLARE
+    changed tasks%ROWTYPE;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        changed := OLD;
+    ELSE
+        changed := NEW;
+    END IF;
+
+    PERFORM pg_notify(
+        'task_changes',
+        json_build_object(
+            'event_type', CASE TG_OP
+                WHEN 'INSERT' THEN 'Created'
+                WHEN 'UPDATE' THEN 'Updated'
+                ELSE 'Deleted'
+            END,
+            'task_id', changed.id,
+            'user_id', changed.user_id
+        )::text
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER tasks_notify_change
+    AFTER INSERT OR UPDATE OR DELETE ON tasks
+    FOR EACH ROW EXECUTE FUNCTION notify_task_change();
@@ -0,0 +1,10 @@
+-- Owners of tasks. Emails are stored lowercase, so the unique constraint ignores case.
+-- The foreign key from tasks.user_id is not created here: it follows the `user_ids`
+-- setting and is added or dropped after migrating (see `infrastructure::migrations`).
+CREATE TABLE users (
+    id UUID PRIMARY KEY,
+    email TEXT NOT NULL,
+    display_name TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT users_email_key UNIQUE (email)
+);
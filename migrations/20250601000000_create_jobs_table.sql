@@ -0,0 +1,20 @@
+-- Background jobs, claimed by workers with `FOR UPDATE SKIP LOCKED` (see `infrastructure::jobs`).
+-- A job is deleted once it succeeds. `status` is `pending` until a worker claims it,
+-- `running` while a worker holds it, and `dead` once it has used up its attempts.
+CREATE TABLE jobs (
+    id UUID PRIMARY KEY,
+    kind TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    run_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    locked_by TEXT,
+    locked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT jobs_status_check CHECK (status IN ('pending', 'running', 'dead'))
+);
+
+-- Due jobs are found in run order without scanning running or dead ones
+CREATE INDEX idx_jobs_due ON jobs(run_at) WHERE status = 'pending';
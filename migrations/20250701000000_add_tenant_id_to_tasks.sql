@@ -0,0 +1,40 @@
+-- Partition tasks by tenant. Existing rows belong to the default (nil) tenant, the only
+-- tenant in single-tenant mode. The default is dropped once they are backfilled, so
+-- every insert has to name its tenant.
+ALTER TABLE tasks ADD COLUMN tenant_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000';
+ALTER TABLE tasks ALTER COLUMN tenant_id DROP DEFAULT;
+
+-- Every query filters on the tenant first
+DROP INDEX idx_tasks_user_id;
+CREATE INDEX idx_tasks_tenant_id_user_id ON tasks(tenant_id, user_id, created_at DESC);
+
+DROP INDEX idx_tasks_status;
+CREATE INDEX idx_tasks_tenant_id_status ON tasks(tenant_id, status);
+
+-- Include the tenant in change notifications, so listeners can filter by it
+CREATE OR REPLACE FUNCTION notify_task_change() RETURNS trigger AS $$
+DECLARE
+    changed tasks%ROWTYPE;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        changed := OLD;
+    ELSE
+        changed := NEW;
+    END IF;
+
+    PERFORM pg_notify(
+        'task_changes',
+        json_build_object(
+            'event_type', CASE TG_OP
+                WHEN 'INSERT' THEN 'Created'
+                WHEN 'UPDATE' THEN 'Updated'
+                ELSE 'Deleted'
+            END,
+            'task_id', changed.id,
+            'tenant_id', changed.tenant_id,
+            'user_id', changed.user_id
+        )::text
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
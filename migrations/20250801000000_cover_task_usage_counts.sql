@@ -0,0 +1,4 @@
+-- Quota checks count a user's open tasks and the tasks they created this month before
+-- every create. With the status in the index, both counts come from an index-only scan.
+DROP INDEX idx_tasks_tenant_id_user_id;
+CREATE INDEX idx_tasks_tenant_id_user_id ON tasks(tenant_id, user_id, created_at DESC) INCLUDE (status);
@@ -0,0 +1,33 @@
+-- Files attached to tasks. The files live in the object store under `object_key`; only
+-- their metadata is kept here.
+CREATE TABLE attachments (
+    id UUID PRIMARY KEY,
+    task_id UUID NOT NULL REFERENCES tasks (id) ON DELETE CASCADE,
+    filename TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size BIGINT NOT NULL CHECK (size > 0),
+    object_key TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_attachments_task_id ON attachments(task_id, created_at);
+
+-- Object keys of deleted attachments whose objects may still be stored. Rows are added
+-- by a trigger, so attachments deleted along with their task are covered too, and
+-- removed once the object is gone.
+CREATE TABLE orphaned_objects (
+    object_key TEXT PRIMARY KEY,
+    orphaned_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE FUNCTION record_orphaned_object() RETURNS trigger AS $$
+BEGIN
+    INSERT INTO orphaned_objects (object_key) VALUES (OLD.object_key)
+    ON CONFLICT (object_key) DO NOTHING;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER attachments_record_orphaned_object
+    AFTER DELETE ON attachments
+    FOR EACH ROW EXECUTE FUNCTION record_orphaned_object();
@@ -0,0 +1,14 @@
+-- Audit trail of task priority changes. A row is written in the same transaction as the
+-- task update, so every stored priority is explained by the change that set it.
+CREATE TABLE priority_changes (
+    id BIGSERIAL PRIMARY KEY,
+    task_id UUID NOT NULL REFERENCES tasks (id) ON DELETE CASCADE,
+    tenant_id UUID NOT NULL,
+    from_priority task_priority NOT NULL,
+    to_priority task_priority NOT NULL CHECK (to_priority <> from_priority),
+    reason TEXT,
+    changed_by UUID NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_priority_changes_task_id ON priority_changes(tenant_id, task_id, changed_at);
@@ -0,0 +1,8 @@
+-- Webhooks belong to a tenant and only receive that tenant's events. Existing webhooks
+-- belong to the default (nil) tenant, like the tasks before them; the default is dropped
+-- once they are backfilled, so every insert has to name its tenant.
+ALTER TABLE webhooks ADD COLUMN tenant_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000';
+ALTER TABLE webhooks ALTER COLUMN tenant_id DROP DEFAULT;
+
+-- Listing and dispatching both filter on the tenant first
+CREATE INDEX idx_webhooks_tenant_id ON webhooks(tenant_id, created_at);
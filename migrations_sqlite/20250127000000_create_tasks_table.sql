@@ -0,0 +1,20 @@
+-- SQLite counterpart of migrations/20250127000000_create_tasks_table.sql.
+-- Enums become CHECK-constrained TEXT, UUIDs are stored as 16-byte BLOBs and
+-- timestamps as RFC 3339 TEXT.
+CREATE TABLE tasks (
+    id BLOB PRIMARY KEY NOT NULL,
+    user_id BLOB NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    status TEXT NOT NULL DEFAULT 'PENDING'
+        CHECK (status IN ('PENDING', 'IN_PROGRESS', 'COMPLETED', 'CANCELLED')),
+    priority TEXT NOT NULL DEFAULT 'MEDIUM'
+        CHECK (priority IN ('LOW', 'MEDIUM', 'HIGH', 'CRITICAL')),
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    completed_at TEXT
+);
+
+CREATE INDEX idx_tasks_user_id ON tasks(user_id);
+
+CREATE INDEX idx_tasks_status ON tasks(status);
@@ -0,0 +1,10 @@
+-- SQLite counterpart of migrations/20250401000000_create_users_table.sql.
+-- There is no foreign key from tasks.user_id: SQLite cannot add one to an existing
+-- table, so with `user_ids = registered` only the check before creating a task applies.
+CREATE TABLE users (
+    id BLOB PRIMARY KEY NOT NULL,
+    email TEXT NOT NULL,
+    display_name TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    CONSTRAINT users_email_key UNIQUE (email)
+);
@@ -0,0 +1,29 @@
+-- SQLite counterpart of migrations/20250501000000_create_webhooks_table.sql.
+-- `event_types` is JSON stored as TEXT and booleans are 0/1 INTEGERs. Deliveries are
+-- removed with their webhook because sqlx turns foreign keys on for every connection.
+
+CREATE TABLE webhooks (
+    id BLOB PRIMARY KEY NOT NULL,
+    url TEXT NOT NULL,
+    secret TEXT NOT NULL,
+    event_types TEXT NOT NULL,
+    active INTEGER NOT NULL DEFAULT 1,
+    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE TABLE webhook_deliveries (
+    id BLOB PRIMARY KEY NOT NULL,
+    webhook_id BLOB NOT NULL REFERENCES webhooks (id) ON DELETE CASCADE,
+    event_id BLOB NOT NULL,
+    event_type TEXT NOT NULL,
+    attempt INTEGER NOT NULL,
+    status_code INTEGER,
+    error TEXT,
+    succeeded INTEGER NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    attempted_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE INDEX idx_webhook_deliveries_webhook_id ON webhook_deliveries(webhook_id, attempted_at);
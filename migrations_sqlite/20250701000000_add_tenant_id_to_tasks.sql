@@ -0,0 +1,9 @@
+-- SQLite counterpart of migrations/20250701000000_add_tenant_id_to_tasks.sql.
+-- SQLite cannot drop a column default, so the nil tenant stays the default here.
+ALTER TABLE tasks ADD COLUMN tenant_id BLOB NOT NULL DEFAULT X'00000000000000000000000000000000';
+
+DROP INDEX idx_tasks_user_id;
+CREATE INDEX idx_tasks_tenant_id_user_id ON tasks(tenant_id, user_id, created_at DESC);
+
+DROP INDEX idx_tasks_status;
+CREATE INDEX idx_tasks_tenant_id_status ON tasks(tenant_id, status);
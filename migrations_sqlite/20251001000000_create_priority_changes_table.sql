@@ -0,0 +1,15 @@
+-- SQLite counterpart of migrations/20251001000000_create_priority_changes_table.sql.
+CREATE TABLE priority_changes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id BLOB NOT NULL REFERENCES tasks (id) ON DELETE CASCADE,
+    tenant_id BLOB NOT NULL,
+    from_priority TEXT NOT NULL
+        CHECK (from_priority IN ('LOW', 'MEDIUM', 'HIGH', 'CRITICAL')),
+    to_priority TEXT NOT NULL
+        CHECK (to_priority IN ('LOW', 'MEDIUM', 'HIGH', 'CRITICAL') AND to_priority <> from_priority),
+    reason TEXT,
+    changed_by BLOB NOT NULL,
+    changed_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE INDEX idx_priority_changes_task_id ON priority_changes(tenant_id, task_id, changed_at);
@@ -0,0 +1,5 @@
+-- SQLite counterpart of migrations/20251101000000_add_tenant_id_to_webhooks.sql.
+-- SQLite cannot drop a column default, so the nil tenant stays the default here.
+ALTER TABLE webhooks ADD COLUMN tenant_id BLOB NOT NULL DEFAULT X'00000000000000000000000000000000';
+
+CREATE INDEX idx_webhooks_tenant_id ON webhooks(tenant_id, created_at);
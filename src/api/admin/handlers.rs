@@ -0,0 +1,421 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::Arc;
+
+// <feature:swagger>
+use crate::api::tenant::TenantHeader;
+// </feature:swagger>
+use crate::{
+    api::{
+        // <feature:auth>
+        auth::JwtExtractor,
+        // </feature:auth>
+        error::{ApiErrorResponse, ErrorCode},
+        extractors::{AppJson, AppPath, AppQuery, PathParam},
+        models::{
+            admin::{FeatureFlagsResponse, JobResponse, ListJobsQuery, LogLevel, SearchTasksQuery},
+            // <feature:swagger>
+            examples,
+            // </feature:swagger>
+            page::Page,
+            tasks::TaskResponse,
+        },
+        tenant::TenantExtractor,
+    },
+    config::{AppState, SanitizedConfig},
+    domain::{
+        interfaces::job_repository::JobRepository,
+        job::{
+            models::JobId,
+            operations::{discard_job, list_jobs, retry_job},
+        },
+        task::operations::search_tasks,
+    },
+    infrastructure::log_level::{LogLevelError, LogLevelHandle},
+};
+
+fn log_level_handle(state: &AppState) -> Result<&LogLevelHandle, ApiErrorResponse> {
+    state.log_level.as_ref().ok_or_else(|| {
+        ApiErrorResponse::with_message(
+            ErrorCode::ServiceUnavailable,
+            "Runtime log level changes are not available",
+        )
+    })
+}
+
+impl From<LogLevelError> for ApiErrorResponse {
+    fn from(error: LogLevelError) -> Self {
+        match error {
+            LogLevelError::InvalidDirective(_) => {
+                Self::with_message(ErrorCode::BadRequest, error.to_string())
+            }
+            LogLevelError::Reload(_) => {
+                tracing::error!(
+                    error_type = "LogLevelReloadError",
+                    error_message = %error,
+                    "Failed to access log filter"
+                );
+                Self::from(ErrorCode::InternalServerError)
+            }
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/log-level",
+    tag = "admin",
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "Active log filter", body = LogLevel),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        // </feature:auth>
+        (status = 503, description = "Log level reloading is not available", body = ApiErrorResponse)
+    )
+)]
+pub async fn get_log_level_handler(
+    State(state): State<Arc<AppState>>,
+    // <feature:auth>
+    JwtExtractor(_claims): JwtExtractor,
+    // </feature:auth>
+) -> Result<Json<LogLevel>, ApiErrorResponse> {
+    let directives = log_level_handle(&state)?.current()?;
+
+    Ok(Json(LogLevel { directives }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/admin/log-level",
+    tag = "admin",
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    request_body = LogLevel,
+    responses(
+        (status = 200, description = "Log filter updated", body = LogLevel),
+        (status = 400, description = "Invalid log directive", body = ApiErrorResponse),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        // </feature:auth>
+        (status = 503, description = "Log level reloading is not available", body = ApiErrorResponse)
+    )
+)]
+pub async fn set_log_level_handler(
+    State(state): State<Arc<AppState>>,
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    AppJson(request): AppJson<LogLevel>,
+) -> Result<Json<LogLevel>, ApiErrorResponse> {
+    let handle = log_level_handle(&state)?;
+    let previous = handle.set(&request.directives)?;
+
+    tracing::warn!(
+        // <feature:auth>
+        user_id = claims.sub.as_deref().unwrap_or("<none>"),
+        session_id = claims.session_id(),
+        // </feature:auth>
+        previous = %previous,
+        directives = %request.directives,
+        "Log level changed"
+    );
+
+    Ok(Json(LogLevel {
+        directives: handle.current()?,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/config",
+    tag = "admin",
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "Loaded configuration with secrets redacted", body = serde_json::Value),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        ))
+        // </feature:auth>
+    )
+)]
+pub async fn get_config_handler(
+    State(state): State<Arc<AppState>>,
+    // <feature:auth>
+    JwtExtractor(_claims): JwtExtractor,
+    // </feature:auth>
+) -> Response {
+    Json(SanitizedConfig(&state.env)).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/features",
+    tag = "admin",
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "Current value of every feature flag", body = FeatureFlagsResponse),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        ))
+        // </feature:auth>
+    )
+)]
+pub async fn get_features_handler(
+    State(state): State<Arc<AppState>>,
+    // <feature:auth>
+    JwtExtractor(_claims): JwtExtractor,
+    // </feature:auth>
+) -> Json<FeatureFlagsResponse> {
+    let features = state
+        .feature_flags
+        .snapshot()
+        .into_iter()
+        .map(|(name, enabled)| (name.to_string(), enabled))
+        .collect();
+
+    Json(FeatureFlagsResponse { features })
+}
+
+fn job_repository(state: &AppState) -> Result<Arc<dyn JobRepository>, ApiErrorResponse> {
+    state.job_repository.clone().ok_or_else(|| {
+        ApiErrorResponse::with_message(
+            ErrorCode::ServiceUnavailable,
+            "Background jobs need DATABASE_KIND postgres",
+        )
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/jobs",
+    tag = "admin",
+    params(ListJobsQuery),
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "A page of background jobs, oldest first", body = Page<JobResponse>,
+            example = json!(examples::job_page())),
+        (status = 400, description = "Unknown status, or limit outside 1 to 100", body = ApiErrorResponse),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Token lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+        (status = 503, description = "The database backend has no job queue", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub async fn list_jobs_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    State(state): State<Arc<AppState>>,
+    AppQuery(query): AppQuery<ListJobsQuery>,
+) -> Result<Json<Page<JobResponse>>, ApiErrorResponse> {
+    // <feature:auth>
+    claims.authorize_admin()?;
+    // </feature:auth>
+
+    let (limit, offset) = (query.limit(), query.offset());
+    let page = list_jobs(&query.filter(), limit, offset, job_repository(&state)?).await?;
+
+    Ok(Json(Page::new(page.jobs, page.total, limit, offset)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/jobs/{id}/retry",
+    tag = "admin",
+    params(
+        ("id" = Uuid, Path, description = "Job ID")
+    ),
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "Dead job due again, with its attempts starting over", body = JobResponse),
+        (status = 400, description = "Job ID is not a valid UUID", body = ApiErrorResponse),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Token lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 404, description = "Job not found, or it already succeeded", body = ApiErrorResponse,
+            example = json!(examples::not_found_error())),
+        (status = 409, description = "Job is not dead", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+        (status = 503, description = "The database backend has no job queue", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(job_id = %id))]
+pub async fn retry_job_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    AppPath(PathParam(id)): AppPath<PathParam<JobId>>,
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<JobResponse>, ApiErrorResponse> {
+    // <feature:auth>
+    claims.authorize_admin()?;
+    // </feature:auth>
+
+    let job = retry_job(id, job_repository(&state)?).await?;
+
+    tracing::warn!(
+        // <feature:auth>
+        user_id = claims.sub.as_deref().unwrap_or("<none>"),
+        session_id = claims.session_id(),
+        // </feature:auth>
+        job_id = %job.id,
+        kind = %job.kind,
+        "Dead job retried"
+    );
+
+    Ok(Json(job.into()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/jobs/{id}",
+    tag = "admin",
+    params(
+        ("id" = Uuid, Path, description = "Job ID")
+    ),
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 204, description = "Job discarded; it will not run again"),
+        (status = 400, description = "Job ID is not a valid UUID", body = ApiErrorResponse),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Token lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 404, description = "Job not found, or it already succeeded", body = ApiErrorResponse,
+            example = json!(examples::not_found_error())),
+        (status = 409, description = "Job is running", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+        (status = 503, description = "The database backend has no job queue", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(job_id = %id))]
+pub async fn discard_job_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    AppPath(PathParam(id)): AppPath<PathParam<JobId>>,
+    State(state): State<Arc<AppState>>,
+) -> Result<StatusCode, ApiErrorResponse> {
+    // <feature:auth>
+    claims.authorize_admin()?;
+    // </feature:auth>
+
+    let job = discard_job(id, job_repository(&state)?).await?;
+
+    tracing::warn!(
+        // <feature:auth>
+        user_id = claims.sub.as_deref().unwrap_or("<none>"),
+        session_id = claims.session_id(),
+        // </feature:auth>
+        job_id = %job.id,
+        kind = %job.kind,
+        status = job.status.as_str(),
+        attempts = job.attempts,
+        "Job discarded"
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/tasks",
+    tag = "admin",
+    params(SearchTasksQuery, TenantHeader),
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "A page of the tasks of every user in the tenant", body = Page<TaskResponse>,
+            example = json!(examples::task_page())),
+        (status = 400, description = "Malformed or unknown filter values, an empty date range, an id fragment that is not hex, or limit outside 1 to 100", body = ApiErrorResponse,
+            example = json!(examples::validation_error())),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Token lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+        (status = 504, description = "Database query timed out", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub async fn search_tasks_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    TenantExtractor(tenant): TenantExtractor,
+    State(state): State<Arc<AppState>>,
+    AppQuery(query): AppQuery<SearchTasksQuery>,
+) -> Result<Json<Page<TaskResponse>>, ApiErrorResponse> {
+    // <feature:auth>
+    claims.authorize_admin()?;
+    // </feature:auth>
+
+    let search = query.search()?;
+    let page = search_tasks(tenant, &search, state.task_repository.clone()).await?;
+
+    // Every search is logged, since it reads tasks of users other than the caller
+    tracing::info!(
+        // <feature:auth>
+        user_id = claims.sub.as_deref().unwrap_or("<none>"),
+        session_id = claims.session_id(),
+        // </feature:auth>
+        tenant_id = %tenant,
+        filter = ?search.filter(),
+        sort = ?search.sort(),
+        limit = search.limit(),
+        offset = search.offset(),
+        total = page.total,
+        "Admin task search"
+    );
+
+    Ok(Json(Page::from_search(page, &search)))
+}
@@ -0,0 +1,254 @@
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header::CONTENT_TYPE, HeaderMap, StatusCode},
+    Json,
+};
+use std::{sync::Arc, time::Duration};
+
+// <feature:swagger>
+use crate::api::tenant::TenantHeader;
+// </feature:swagger>
+use crate::{
+    api::{
+        // <feature:auth>
+        auth::JwtExtractor,
+        // </feature:auth>
+        error::{ApiErrorResponse, ErrorCode},
+        extractors::{AppJson, AppPath, AppQuery, PathParam},
+        models::{
+            attachments::{AttachmentResponse, AttachmentUploadResponse, CreateAttachmentRequest},
+            // <feature:swagger>
+            examples,
+            // </feature:swagger>
+        },
+        tenant::TenantExtractor,
+    },
+    config::AppState,
+    domain::{
+        attachment::{
+            models::{Attachment, AttachmentId},
+            operations::{delete_attachment, list_attachments, request_upload},
+        },
+        interfaces::attachment_repository::AttachmentRepository,
+        task::{models::TaskId, operations::get_task},
+    },
+    infrastructure::filesystem_object_store::UploadGrant,
+};
+
+fn attachment_repository(
+    state: &AppState,
+) -> Result<Arc<dyn AttachmentRepository>, ApiErrorResponse> {
+    state.attachment_repository.clone().ok_or_else(|| {
+        ApiErrorResponse::with_message(
+            ErrorCode::ServiceUnavailable,
+            "Attachments need DATABASE_KIND postgres",
+        )
+    })
+}
+
+#[utoipa::path(
+    post,
+    path = "/tasks/{id}/attachments",
+    tag = "attachments",
+    params(
+        ("id" = Uuid, Path, description = "Task to attach the file to"),
+        TenantHeader
+    ),
+    request_body = CreateAttachmentRequest,
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 201, description = "Attachment created; upload its file with one `PUT` to `upload.url` before `upload.expires_at`",
+            body = AttachmentUploadResponse),
+        (status = 400, description = "Invalid filename, a content type that is not accepted, or a size outside the limit", body = ApiErrorResponse,
+            example = json!(examples::invalid_attachment_error())),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Task belongs to another user and the token lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 404, description = "Task not found", body = ApiErrorResponse,
+            example = json!(examples::not_found_error())),
+        (status = 415, description = "Missing JSON content type", body = ApiErrorResponse),
+        (status = 422, description = "Request body does not match the schema", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+        (status = 503, description = "Attachments need Postgres", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(task_id = %id, attachment_id = tracing::field::Empty))]
+pub async fn create_attachment_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    TenantExtractor(tenant): TenantExtractor,
+    AppPath(PathParam(id)): AppPath<PathParam<TaskId>>,
+    State(state): State<Arc<AppState>>,
+    AppJson(request): AppJson<CreateAttachmentRequest>,
+) -> Result<(StatusCode, Json<AttachmentUploadResponse>), ApiErrorResponse> {
+    let repo = attachment_repository(&state)?;
+    let task = get_task(tenant, id, state.task_repository.clone()).await?;
+    // <feature:auth>
+    claims.authorize_user(task.user_id.into_inner())?;
+    // </feature:auth>
+
+    let attachments = &state.env.attachment_config;
+    let attachment = Attachment::new(
+        task.id,
+        &request.filename,
+        &request.content_type,
+        request.size,
+        &attachments.limits(),
+    )?;
+    tracing::Span::current().record("attachment_id", tracing::field::display(attachment.id));
+
+    let (attachment, upload) = request_upload(
+        attachment,
+        Duration::from_secs(attachments.upload_url_ttl_secs),
+        repo,
+        state.object_store.clone(),
+    )
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(AttachmentUploadResponse {
+            attachment: attachment.into(),
+            upload: upload.into(),
+        }),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/tasks/{id}/attachments",
+    tag = "attachments",
+    params(
+        ("id" = Uuid, Path, description = "Task whose attachments to list"),
+        TenantHeader
+    ),
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "The task's attachments, oldest first", body = Vec<AttachmentResponse>),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Task belongs to another user and the token lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 404, description = "Task not found", body = ApiErrorResponse,
+            example = json!(examples::not_found_error())),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+        (status = 503, description = "Attachments need Postgres", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(task_id = %id))]
+pub async fn list_attachments_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    TenantExtractor(tenant): TenantExtractor,
+    AppPath(PathParam(id)): AppPath<PathParam<TaskId>>,
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<AttachmentResponse>>, ApiErrorResponse> {
+    let repo = attachment_repository(&state)?;
+    let task = get_task(tenant, id, state.task_repository.clone()).await?;
+    // <feature:auth>
+    claims.authorize_user(task.user_id.into_inner())?;
+    // </feature:auth>
+
+    let attachments = list_attachments(task.id, repo).await?;
+
+    Ok(Json(attachments.into_iter().map(Into::into).collect()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/tasks/{id}/attachments/{attachment_id}",
+    tag = "attachments",
+    params(
+        ("id" = Uuid, Path, description = "Task the attachment belongs to"),
+        ("attachment_id" = Uuid, Path, description = "Attachment to delete"),
+        TenantHeader
+    ),
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 204, description = "Attachment and its file deleted"),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Task belongs to another user and the token lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 404, description = "Task or attachment not found", body = ApiErrorResponse,
+            example = json!(examples::not_found_error())),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+        (status = 503, description = "Attachments need Postgres", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(task_id = %id, attachment_id = %attachment_id))]
+pub async fn delete_attachment_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    TenantExtractor(tenant): TenantExtractor,
+    AppPath((PathParam(id), PathParam(attachment_id))): AppPath<(
+        PathParam<TaskId>,
+        PathParam<AttachmentId>,
+    )>,
+    State(state): State<Arc<AppState>>,
+) -> Result<StatusCode, ApiErrorResponse> {
+    let repo = attachment_repository(&state)?;
+    let task = get_task(tenant, id, state.task_repository.clone()).await?;
+    // <feature:auth>
+    claims.authorize_user(task.user_id.into_inner())?;
+    // </feature:auth>
+
+    delete_attachment(task.id, attachment_id, repo, state.object_store.clone()).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Receive a file uploaded to a URL presigned by the filesystem store
+///
+/// Stands in for the store's own upload endpoint, so it is left out of the API docs: the
+/// signed query string authorizes the request instead of a token.
+#[tracing::instrument(skip_all, fields(object_key = %key))]
+pub async fn receive_upload_handler(
+    AppPath(key): AppPath<String>,
+    AppQuery(grant): AppQuery<UploadGrant>,
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<StatusCode, ApiErrorResponse> {
+    let store = state
+        .local_uploads
+        .clone()
+        .ok_or_else(|| ApiErrorResponse::from(ErrorCode::NotFound))?;
+    let limit = usize::try_from(state.env.attachment_config.max_size_bytes).unwrap_or(usize::MAX);
+    let body = axum::body::to_bytes(body, limit).await.map_err(|_| {
+        ApiErrorResponse::with_message(
+            ErrorCode::BadRequest,
+            "Upload is larger than the attachment size limit",
+        )
+    })?;
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+
+    store.receive(&key, &grant, content_type, &body).await?;
+
+    Ok(StatusCode::OK)
+}
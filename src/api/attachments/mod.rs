@@ -0,0 +1,29 @@
+pub mod handlers;
+
+use std::sync::Arc;
+
+use axum::{
+    routing::{delete, get, put},
+    Router,
+};
+
+use crate::config::AppState;
+
+/// Routes of the attachments API, merged into the application router
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/tasks/{id}/attachments",
+            get(handlers::list_attachments_handler).post(handlers::create_attachment_handler),
+        )
+        .route(
+            "/tasks/{id}/attachments/{attachment_id}",
+            delete(handlers::delete_attachment_handler),
+        )
+}
+
+/// The upload URLs of the filesystem store, under its `UPLOADS_PATH`, merged only while
+/// it is the store in use
+pub fn upload_routes() -> Router<Arc<AppState>> {
+    Router::new().route("/uploads/{*key}", put(handlers::receive_upload_handler))
+}
@@ -8,18 +8,23 @@ use axum_extra::{
 use jsonwebtoken::{decode, DecodingKey, Validation};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+// <feature:swagger>
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify,
+};
+// </feature:swagger>
 use uuid::Uuid;
 
 use crate::{
     api::error::{ApiErrorResponse, ErrorCode},
-    config::AppState,
+    common::UserId,
+    config::{AppState, MIN_JWT_SECRET_LENGTH},
+    infrastructure::error_reporting,
 };
 
-/// Minimum length required for JWT secret
-const MIN_SECRET_LENGTH: usize = 32;
-
 fn get_keys(secret: &str) -> Result<Keys, ApiErrorResponse> {
-    if secret.len() < MIN_SECRET_LENGTH {
+    if secret.len() < MIN_JWT_SECRET_LENGTH {
         return Err(ApiErrorResponse::from(ErrorCode::InternalServerError));
     }
     Ok(Keys::new(secret.as_bytes()))
@@ -66,6 +71,11 @@ impl axum::extract::FromRequestParts<Arc<AppState>> for JwtExtractor {
 
         tracing::info!("Token decoded successfully");
 
+        if let Some(user_id) = claims.sub.as_deref() {
+            tracing::Span::current().record("user_id", user_id);
+            error_reporting::set_user_id(user_id);
+        }
+
         Ok(Self(claims))
     }
 }
@@ -89,16 +99,54 @@ pub struct JwtClaims {
     pub exp: usize,
     pub iss: Option<String>,
     pub session_id: Option<String>,
+    /// Roles granted by the identity provider; [`ADMIN_ROLE`] may act for any user
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<String>,
+    /// Tenant the token acts for in multi-tenant mode; takes precedence over the
+    /// `X-Tenant-Id` header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<Uuid>,
 }
 
+/// Role allowed to read and change any user's resources
+pub const ADMIN_ROLE: &str = "admin";
+
 impl JwtClaims {
     /// Get the session ID if present
     pub fn session_id(&self) -> Option<&str> {
         self.session_id.as_deref()
     }
 
+    /// Whether the token grants [`ADMIN_ROLE`]
+    pub fn is_admin(&self) -> bool {
+        self.roles.iter().any(|role| role == ADMIN_ROLE)
+    }
+
+    /// Allow access to resources that belong to no user, such as webhooks, to admins only
+    pub fn authorize_admin(&self) -> Result<(), ApiErrorResponse> {
+        if self.is_admin() {
+            return Ok(());
+        }
+        tracing::warn!("Token without the {ADMIN_ROLE} role used for an admin-only resource");
+        Err(ApiErrorResponse::from(ErrorCode::Forbidden))
+    }
+
+    /// Allow access to `user_id`'s resources to that user and to admins
+    pub fn authorize_user(&self, user_id: Uuid) -> Result<(), ApiErrorResponse> {
+        if self.is_admin() {
+            return Ok(());
+        }
+        self.validate_user_id(user_id)
+    }
+
+    /// The subject claim as a user id, when it is a UUID
+    #[must_use]
+    pub fn subject_user_id(&self) -> Option<UserId> {
+        self.sub.as_deref()?.parse::<Uuid>().ok().map(UserId::from)
+    }
+
     /// Validate that the `user_id` from the path matches the subject claim in the JWT token.
-    /// Returns an error if the claims don't have a subject or if it doesn't match the `user_id`.
+    /// Returns 401 if the claims don't have a usable subject, and 403 if it is another user.
     pub fn validate_user_id(&self, user_id: Uuid) -> Result<(), ApiErrorResponse> {
         let claims_user_id = self
             .sub
@@ -119,9 +167,91 @@ impl JwtClaims {
                 claims_user_id,
                 user_id
             );
-            return Err(ApiErrorResponse::from(ErrorCode::Unauthorized));
+            return Err(ApiErrorResponse::from(ErrorCode::Forbidden));
         }
 
         Ok(())
     }
 }
+
+// <feature:swagger>
+/// Name of the JWT bearer scheme referenced by `security(("bearer" = []))` on protected paths
+pub const BEARER_SECURITY_SCHEME: &str = "bearer";
+
+/// Registers the JWT bearer scheme, which also enables Swagger UI's "Authorize" button
+///
+/// There is deliberately no top-level requirement: health, readiness and docs stay public,
+/// and each protected path declares its own.
+pub struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                BEARER_SECURITY_SCHEME,
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .bearer_format("JWT")
+                        .build(),
+                ),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utoipa::OpenApi;
+
+    use super::BEARER_SECURITY_SCHEME;
+    use crate::api::docs::ApiDoc;
+
+    #[test]
+    fn test_openapi_declares_bearer_scheme_for_protected_paths_only() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+        let scheme = &spec["components"]["securitySchemes"][BEARER_SECURITY_SCHEME];
+        assert_eq!(scheme["type"], "http");
+        assert_eq!(scheme["scheme"], "bearer");
+        assert_eq!(scheme["bearerFormat"], "JWT");
+        assert!(spec.get("security").is_none(), "No global requirement");
+
+        let requirement = serde_json::json!([{ BEARER_SECURITY_SCHEME: [] }]);
+        for (path, method) in [
+            ("/admin/log-level", "get"),
+            ("/admin/log-level", "put"),
+            ("/admin/config", "get"),
+            ("/users/{user_id}/tasks", "get"),
+            ("/users/{user_id}/stats", "get"),
+            ("/webhooks", "get"),
+            ("/webhooks", "post"),
+            ("/webhooks/{id}", "get"),
+            ("/webhooks/{id}", "put"),
+            ("/webhooks/{id}", "delete"),
+            ("/webhooks/{id}/deliveries", "get"),
+        ] {
+            let operation = &spec["paths"][path][method];
+            assert_eq!(operation["security"], requirement, "{method} {path}");
+            assert_eq!(
+                operation["responses"]["401"]["content"]["application/json"]["examples"]
+                    ["Missing token"]["value"]["code"],
+                "TokenNotFound",
+                "{method} {path}"
+            );
+        }
+        for (path, method) in [("/health", "get"), ("/ready", "get"), ("/tasks", "get")] {
+            let operation = &spec["paths"][path][method];
+            assert!(
+                operation.is_object(),
+                "{method} {path} should be documented"
+            );
+            assert!(
+                operation.get("security").is_none(),
+                "{method} {path} should stay public"
+            );
+        }
+    }
+}
+// </feature:swagger>
@@ -0,0 +1,160 @@
+//! Conditional GETs: weak `ETag`s on responses and 304 for a matching `If-None-Match`
+//!
+//! Handlers tag a response with [`Tagged`]; [`conditional_get`] then answers requests whose
+//! `If-None-Match` names that tag with an empty 304, so any GET endpoint gets revalidation
+//! by tagging its response. Tags are weak, so they stay valid when a compression layer or
+//! proxy re-encodes the body; keep `conditional_get` inside any compression layer so it
+//! compares tags before a body is encoded.
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{
+        header::{CACHE_CONTROL, ETAG, IF_NONE_MATCH, VARY},
+        HeaderValue, Method, StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+
+/// Bytes of the digest kept in a tag
+const TAG_BYTES: usize = 16;
+
+/// Weak entity tag, as sent in the `ETag` header (`W/"<hex>"`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ETag(String);
+
+impl ETag {
+    /// Start a tag over the parts that identify a representation
+    #[must_use]
+    pub fn builder() -> ETagBuilder {
+        ETagBuilder(Sha256::new())
+    }
+
+    /// The tag as sent in the `ETag` header
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether an `If-None-Match` header names this tag, by weak comparison, or is `*`
+    #[must_use]
+    pub fn matches(&self, if_none_match: &str) -> bool {
+        let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+        let own = opaque(&self.0);
+        if_none_match.trim() == "*" || if_none_match.split(',').any(|tag| opaque(tag) == own)
+    }
+}
+
+/// Hashes the parts of an [`ETag`], in order
+pub struct ETagBuilder(Sha256);
+
+impl ETagBuilder {
+    /// Add a part; parts are delimited, so `["ab", "c"]` and `["a", "bc"]` differ
+    #[must_use]
+    pub fn part(mut self, part: impl AsRef<[u8]>) -> Self {
+        let part = part.as_ref();
+        self.0.update((part.len() as u64).to_be_bytes());
+        self.0.update(part);
+        self
+    }
+
+    #[must_use]
+    pub fn weak(self) -> ETag {
+        let hex: String = self.0.finalize()[..TAG_BYTES]
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        ETag(format!("W/\"{hex}\""))
+    }
+}
+
+/// Response sent with an `ETag` header, which [`conditional_get`] turns into a 304 when
+/// the request already has it
+#[derive(Debug)]
+pub struct Tagged<R> {
+    pub etag: ETag,
+    pub response: R,
+}
+
+impl<R> Tagged<R> {
+    pub fn new(etag: ETag, response: R) -> Self {
+        Self { etag, response }
+    }
+}
+
+impl<R: IntoResponse> IntoResponse for Tagged<R> {
+    fn into_response(self) -> Response {
+        let mut response = self.response.into_response();
+        let etag = HeaderValue::from_str(self.etag.as_str())
+            .expect("ETags are quoted hex, a valid header value");
+        response.headers_mut().insert(ETAG, etag);
+        response
+    }
+}
+
+/// Replace a 200 GET or HEAD response with an empty 304 when the request's `If-None-Match`
+/// names its `ETag`
+///
+/// The 304 keeps the headers a cache needs to refresh its copy: `ETag`, `Vary` and
+/// `Cache-Control`. Responses without a tag pass through unchanged.
+pub async fn conditional_get(request: Request, next: Next) -> Response {
+    if !matches!(*request.method(), Method::GET | Method::HEAD) {
+        return next.run(request).await;
+    }
+    let if_none_match = request
+        .headers()
+        .get(IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let response = next.run(request).await;
+
+    let Some(if_none_match) = if_none_match else {
+        return response;
+    };
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok());
+    if response.status() != StatusCode::OK
+        || !etag.is_some_and(|etag| ETag(etag.to_string()).matches(&if_none_match))
+    {
+        return response;
+    }
+
+    let mut not_modified = Response::new(Body::empty());
+    *not_modified.status_mut() = StatusCode::NOT_MODIFIED;
+    for name in [ETAG, VARY, CACHE_CONTROL] {
+        for value in response.headers().get_all(&name) {
+            not_modified.headers_mut().append(&name, value.clone());
+        }
+    }
+    not_modified
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_etag_matches_by_weak_comparison() {
+        let etag = ETag::builder().part("task").part("2025-03-01").weak();
+        let opaque = etag.as_str().trim_start_matches("W/");
+
+        assert!(etag.as_str().starts_with("W/\""), "Tags should be weak");
+        assert!(etag.matches(etag.as_str()));
+        assert!(
+            etag.matches(opaque),
+            "A strong form of the tag should match"
+        );
+        assert!(etag.matches(&format!("W/\"other\", {}", etag.as_str())));
+        assert!(etag.matches("*"));
+        assert!(!etag.matches("W/\"other\""));
+        assert_ne!(
+            ETag::builder().part("ab").part("c").weak(),
+            ETag::builder().part("a").part("bc").weak(),
+            "Parts should be delimited"
+        );
+    }
+}
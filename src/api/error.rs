@@ -1,53 +1,236 @@
 use axum::{
+    extract::{
+        path::ErrorKind,
+        rejection::{JsonRejection, PathRejection, QueryRejection},
+    },
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::domain::errors::DomainError;
+use crate::{
+    api::middleware::REQUEST_ID_HEADER,
+    domain::errors::{DomainError, ExternalSystem, FieldError},
+    infrastructure::error_reporting,
+};
 
 /// API error response returned to clients
-#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+#[schema(example = crate::api::models::examples::missing_user_id_error)]
 pub struct ApiErrorResponse {
     #[schema(value_type = String)]
     pub code: ErrorCode,
+    /// Human-readable detail, only set when the client can act on it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Every invalid field of the request, on validation errors naming fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<FieldErrorResponse>>,
+}
+
+/// One invalid field of a request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FieldErrorResponse {
+    pub field: String,
+    /// Machine-readable reason: `required`, `too_long`, `invalid_characters`, or `invalid`
+    /// when no more specific one applies
+    pub code: String,
+    pub message: String,
+}
+
+impl From<FieldError> for FieldErrorResponse {
+    fn from(error: FieldError) -> Self {
+        Self {
+            field: error.field,
+            code: error.code.to_string(),
+            message: error.message,
+        }
+    }
 }
 
 /// Error codes returned in API responses
-#[derive(Debug, Serialize, Clone, utoipa::ToSchema)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, utoipa::ToSchema)]
 pub enum ErrorCode {
     NotFound,
+    MethodNotAllowed,
+    UnsupportedMediaType,
     ValidationError,
     BadRequest,
+    Conflict,
     Unauthorized,
+    Forbidden,
     InvalidToken,
     TokenNotFound,
     InternalServerError,
     DatabaseError,
     UnprocessableEntity,
+    ServiceUnavailable,
+    GatewayTimeout,
+    QuotaExceeded,
+}
+
+impl ErrorCode {
+    /// Every code, in declaration order, as listed by the error catalogue
+    pub const ALL: [Self; 16] = [
+        Self::NotFound,
+        Self::MethodNotAllowed,
+        Self::UnsupportedMediaType,
+        Self::ValidationError,
+        Self::BadRequest,
+        Self::Conflict,
+        Self::Unauthorized,
+        Self::Forbidden,
+        Self::InvalidToken,
+        Self::TokenNotFound,
+        Self::InternalServerError,
+        Self::DatabaseError,
+        Self::UnprocessableEntity,
+        Self::ServiceUnavailable,
+        Self::GatewayTimeout,
+        Self::QuotaExceeded,
+    ];
+
+    /// Status of the responses carrying this code
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
+            Self::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::ValidationError | Self::BadRequest => StatusCode::BAD_REQUEST,
+            Self::Conflict => StatusCode::CONFLICT,
+            Self::UnprocessableEntity => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::Unauthorized | Self::TokenNotFound | Self::InvalidToken => {
+                StatusCode::UNAUTHORIZED
+            }
+            Self::Forbidden | Self::QuotaExceeded => StatusCode::FORBIDDEN,
+            Self::InternalServerError | Self::DatabaseError => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::ServiceUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+            Self::GatewayTimeout => StatusCode::GATEWAY_TIMEOUT,
+        }
+    }
+
+    /// What the code means, for the people reading the error catalogue
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::NotFound => "The resource does not exist, or belongs to another user or tenant",
+            Self::MethodNotAllowed => "The path exists but does not accept this HTTP method",
+            Self::UnsupportedMediaType => "The request body is neither JSON nor MessagePack",
+            Self::ValidationError => {
+                "Values of the request break validation rules; `errors` lists every invalid \
+                 field when the rule concerns fields"
+            }
+            Self::BadRequest => {
+                "The request cannot be read: a malformed path or query parameter, header or \
+                 JSON body"
+            }
+            Self::Conflict => "The resource already exists or was changed concurrently",
+            Self::Unauthorized => "The request is not authenticated",
+            Self::Forbidden => "The token is valid but does not allow this action",
+            Self::InvalidToken => "The bearer token is malformed, expired or wrongly signed",
+            Self::TokenNotFound => "No bearer token was sent",
+            Self::InternalServerError => {
+                "The server failed unexpectedly; quote the response's X-Request-Id when \
+                 reporting it"
+            }
+            Self::DatabaseError => {
+                "The database failed to handle the request; quote the response's X-Request-Id \
+                 when reporting it"
+            }
+            Self::UnprocessableEntity => {
+                "The body is well-formed but does not match the request schema"
+            }
+            Self::ServiceUnavailable => {
+                "The service is overloaded or a dependency is down; retry after the \
+                 `Retry-After` delay when there is one"
+            }
+            Self::GatewayTimeout => "The request or one of its database queries took too long",
+            Self::QuotaExceeded => "The action would go past a hard quota",
+        }
+    }
+
+    /// Whether the same request may succeed when sent again unchanged
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::DatabaseError | Self::ServiceUnavailable | Self::GatewayTimeout
+        )
+    }
+}
+
+/// One entry of the error catalogue served at `/api-docs/errors`
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ErrorCatalogueEntry {
+    #[schema(value_type = String, example = "ValidationError")]
+    pub code: ErrorCode,
+    #[schema(example = 400)]
+    pub status: u16,
+    pub description: String,
+    /// Whether the same request may succeed when sent again unchanged
+    pub retryable: bool,
+}
+
+impl From<&ErrorCode> for ErrorCatalogueEntry {
+    fn from(code: &ErrorCode) -> Self {
+        Self {
+            code: code.clone(),
+            status: code.status().as_u16(),
+            description: code.description().to_string(),
+            retryable: code.is_retryable(),
+        }
+    }
+}
+
+/// Every error code the API returns, served at `/api-docs/errors`
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ErrorCatalogue {
+    /// Response header identifying the request, to quote when reporting an error
+    #[schema(example = "x-request-id")]
+    pub request_id_header: String,
+    pub errors: Vec<ErrorCatalogueEntry>,
+}
+
+/// The catalogue of [`ErrorCode::ALL`]
+pub fn error_catalogue() -> ErrorCatalogue {
+    ErrorCatalogue {
+        request_id_header: REQUEST_ID_HEADER.to_string(),
+        errors: ErrorCode::ALL.iter().map(Into::into).collect(),
+    }
+}
+
+impl ApiErrorResponse {
+    /// Error response carrying a detail message for the client
+    pub fn with_message(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: Some(message.into()),
+            errors: None,
+        }
+    }
+
+    /// Validation error response listing every invalid field
+    pub fn with_field_errors(errors: Vec<FieldError>) -> Self {
+        Self {
+            code: ErrorCode::ValidationError,
+            message: None,
+            errors: Some(errors.into_iter().map(Into::into).collect()),
+        }
+    }
 }
 
 impl From<ErrorCode> for ApiErrorResponse {
     fn from(code: ErrorCode) -> Self {
-        Self { code }
+        Self {
+            code,
+            message: None,
+            errors: None,
+        }
     }
 }
 
 impl IntoResponse for ApiErrorResponse {
     fn into_response(self) -> Response {
-        let status_code = match self.code {
-            ErrorCode::NotFound => StatusCode::NOT_FOUND,
-            ErrorCode::ValidationError | ErrorCode::BadRequest => StatusCode::BAD_REQUEST,
-            ErrorCode::UnprocessableEntity => StatusCode::UNPROCESSABLE_ENTITY,
-            ErrorCode::Unauthorized | ErrorCode::TokenNotFound | ErrorCode::InvalidToken => {
-                StatusCode::UNAUTHORIZED
-            }
-            ErrorCode::InternalServerError | ErrorCode::DatabaseError => {
-                StatusCode::INTERNAL_SERVER_ERROR
-            }
-        };
-        (status_code, Json(self)).into_response()
+        (self.code.status(), Json(self)).into_response()
     }
 }
 
@@ -70,7 +253,22 @@ impl From<DomainError> for ApiErrorResponse {
                     error_message = %message,
                     "Validation error"
                 );
-                ErrorCode::ValidationError
+                match field {
+                    Some(field) => {
+                        return Self::with_field_errors(vec![FieldError::new(
+                            field, "invalid", message,
+                        )]);
+                    }
+                    None => ErrorCode::ValidationError,
+                }
+            }
+            DomainError::InvalidFields { errors } => {
+                tracing::error!(
+                    error_type = "ValidationError",
+                    field_errors = ?errors,
+                    "Validation error"
+                );
+                return Self::with_field_errors(errors);
             }
             DomainError::BusinessRuleViolation { message, rule } => {
                 tracing::error!(
@@ -79,19 +277,40 @@ impl From<DomainError> for ApiErrorResponse {
                     error_message = %message,
                     "Business rule violation"
                 );
-                ErrorCode::BadRequest
+                // Rules are stated for the client, which cannot tell why otherwise
+                return Self::with_message(ErrorCode::BadRequest, message);
+            }
+            DomainError::Conflict {
+                message,
+                constraint,
+            } => {
+                tracing::warn!(
+                    error_type = "Conflict",
+                    constraint = ?constraint,
+                    error_message = %message,
+                    "Conflict"
+                );
+                ErrorCode::Conflict
             }
-            DomainError::ExternalError { message, source } => {
+            DomainError::ExternalError {
+                system,
+                message,
+                source,
+            } => {
                 tracing::error!(
                     error_type = "ExternalError",
+                    external_system = %system,
                     error_message = %message,
                     has_source = source.is_some(),
                     "External system error"
                 );
-                if message.contains("Database") {
-                    ErrorCode::DatabaseError
-                } else {
-                    ErrorCode::InternalServerError
+                error_reporting::report_error("ExternalError", &message);
+                match system {
+                    ExternalSystem::Database => ErrorCode::DatabaseError,
+                    ExternalSystem::Kafka
+                    | ExternalSystem::Http
+                    | ExternalSystem::ObjectStore
+                    | ExternalSystem::Other => ErrorCode::InternalServerError,
                 }
             }
             DomainError::Unauthorized { message } => {
@@ -102,7 +321,132 @@ impl From<DomainError> for ApiErrorResponse {
                 );
                 ErrorCode::Unauthorized
             }
+            DomainError::Timeout { operation } => {
+                tracing::error!(
+                    error_type = "Timeout",
+                    operation = %operation,
+                    "Operation timed out"
+                );
+                error_reporting::report_error("Timeout", &operation);
+                ErrorCode::GatewayTimeout
+            }
+            DomainError::QuotaExceeded { quota, limit } => {
+                tracing::warn!(
+                    error_type = "QuotaExceeded",
+                    quota = %quota,
+                    limit,
+                    "Quota exceeded"
+                );
+                return Self::with_message(
+                    ErrorCode::QuotaExceeded,
+                    format!("{quota} is limited to {limit}"),
+                );
+            }
+        };
+        Self::from(code)
+    }
+}
+
+impl From<JsonRejection> for ApiErrorResponse {
+    fn from(rejection: JsonRejection) -> Self {
+        tracing::warn!(
+            error_type = "JsonRejection",
+            error_message = %rejection.body_text(),
+            "Rejected request body"
+        );
+        let code = match rejection {
+            JsonRejection::JsonDataError(_) => ErrorCode::UnprocessableEntity,
+            JsonRejection::MissingJsonContentType(_) => ErrorCode::UnsupportedMediaType,
+            _ => ErrorCode::BadRequest,
         };
-        Self { code }
+        Self::from(code)
+    }
+}
+
+impl From<QueryRejection> for ApiErrorResponse {
+    fn from(rejection: QueryRejection) -> Self {
+        tracing::warn!(
+            error_type = "QueryRejection",
+            error_message = %rejection.body_text(),
+            "Rejected query string"
+        );
+        Self::with_message(ErrorCode::BadRequest, rejection.body_text())
+    }
+}
+
+/// Longest part of a rejected path parameter repeated in the error message
+const MAX_ECHOED_PARAM_CHARS: usize = 40;
+
+impl From<PathRejection> for ApiErrorResponse {
+    fn from(rejection: PathRejection) -> Self {
+        match &rejection {
+            PathRejection::FailedToDeserializePathParams(error) => {
+                tracing::warn!(
+                    error_type = "PathRejection",
+                    error_message = %rejection.body_text(),
+                    "Rejected path parameters"
+                );
+                let message = match error.kind() {
+                    // Raised by `PathParam`, whose message says what was expected
+                    ErrorKind::DeserializeError {
+                        key,
+                        value,
+                        message,
+                    } => {
+                        let mut echoed: String =
+                            value.chars().take(MAX_ECHOED_PARAM_CHARS).collect();
+                        if echoed.len() < value.len() {
+                            echoed.push('…');
+                        }
+                        format!("Path parameter `{key}` {message}, got {echoed:?}")
+                    }
+                    _ => rejection.body_text(),
+                };
+                Self::with_message(ErrorCode::BadRequest, message)
+            }
+            // Only happens when a handler extracts a path on a route without parameters
+            _ => {
+                tracing::error!(
+                    error_type = "PathRejection",
+                    error_message = %rejection.body_text(),
+                    "Path extractor used on a route without matching parameters"
+                );
+                Self::from(ErrorCode::InternalServerError)
+            }
+        }
+    }
+}
+
+// <feature:swagger>
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use utoipa::PartialSchema;
+
+    use super::*;
+
+    #[test]
+    fn test_catalogue_lists_every_error_code_once() {
+        // The schema is derived from the enum, so it names every variant
+        let schema = serde_json::to_value(ErrorCode::schema()).unwrap();
+        let mut variants: Vec<String> =
+            serde_json::from_value(schema["enum"].clone()).expect("ErrorCode schema enum");
+        variants.sort();
+
+        let mut listed: BTreeMap<String, usize> = BTreeMap::new();
+        for entry in error_catalogue().errors {
+            let name = serde_json::to_value(&entry.code).unwrap();
+            *listed
+                .entry(name.as_str().unwrap().to_string())
+                .or_default() += 1;
+        }
+
+        assert!(
+            listed.values().all(|&count| count == 1),
+            "listed more than once: {listed:?}"
+        );
+        assert_eq!(listed.into_keys().collect::<Vec<_>>(), variants);
     }
 }
+// </feature:swagger>
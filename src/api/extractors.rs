@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use axum::extract::{FromRequest, FromRequestParts, Request};
+use serde::{
+    de::{self, DeserializeOwned, Visitor},
+    Deserialize, Deserializer,
+};
+use serde_ignored::Path;
+use std::{fmt, marker::PhantomData};
+use uuid::Uuid;
+
+use crate::{
+    api::error::{ApiErrorResponse, ErrorCode},
+    config::AppState,
+};
+
+/// JSON body extractor that rejects with our `ApiErrorResponse` envelope
+///
+/// Wraps `axum::Json` so malformed or mistyped bodies produce the same JSON error shape
+/// as every other failure instead of axum's plain-text rejection.
+///
+/// With `strict_json` on, a body with fields the target type does not have, at any depth,
+/// is rejected as `UnprocessableEntity` naming them, as `#[serde(deny_unknown_fields)]`
+/// would; otherwise they are ignored so clients may send fields of newer versions.
+#[derive(Debug)]
+pub struct AppJson<T>(pub T);
+
+impl<T: DeserializeOwned> FromRequest<Arc<AppState>> for AppJson<T> {
+    type Rejection = ApiErrorResponse;
+
+    async fn from_request(
+        request: Request,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        if !state.env.strict_json {
+            let axum::Json(value) = axum::Json::from_request(request, state).await?;
+            return Ok(Self(value));
+        }
+
+        // Going through `Value` keeps content type and syntax errors the same in both modes
+        let axum::Json(body) =
+            axum::Json::<serde_json::Value>::from_request(request, state).await?;
+        deserialize_strict(body).map(Self)
+    }
+}
+
+/// Deserialize a parsed body into `T`, rejecting fields `T` does not have
+pub(crate) fn deserialize_strict<T: DeserializeOwned>(
+    body: serde_json::Value,
+) -> Result<T, ApiErrorResponse> {
+    let mut unknown = Vec::new();
+    let value = serde_ignored::deserialize(body, |path| {
+        let mut segments = Vec::new();
+        field_path(&path, &mut segments);
+        unknown.push(format!("`{}`", segments.join(".")));
+    })
+    .map_err(|error| {
+        tracing::warn!(
+            error_type = "JsonRejection",
+            error_message = %error,
+            "Rejected request body"
+        );
+        ApiErrorResponse::from(ErrorCode::UnprocessableEntity)
+    })?;
+
+    if !unknown.is_empty() {
+        let message = match unknown.as_slice() {
+            [field] => format!("Unknown field {field}"),
+            fields => format!("Unknown fields {}", fields.join(", ")),
+        };
+        tracing::warn!(
+            error_type = "JsonRejection",
+            error_message = %message,
+            "Rejected request body"
+        );
+        return Err(ApiErrorResponse::with_message(
+            ErrorCode::UnprocessableEntity,
+            message,
+        ));
+    }
+    Ok(value)
+}
+
+/// Keys and indexes leading to an ignored field, e.g. `["tags", "0", "colour"]`
+fn field_path(path: &Path<'_>, segments: &mut Vec<String>) {
+    match path {
+        Path::Root => {}
+        Path::Seq { parent, index } => {
+            field_path(parent, segments);
+            segments.push(index.to_string());
+        }
+        Path::Map { parent, key } => {
+            field_path(parent, segments);
+            segments.push(key.clone());
+        }
+        Path::Some { parent }
+        | Path::NewtypeStruct { parent }
+        | Path::NewtypeVariant { parent } => field_path(parent, segments),
+    }
+}
+
+/// Query string extractor that rejects with our `ApiErrorResponse` envelope
+///
+/// Missing or malformed parameters come back as `BadRequest` with the parse error as message.
+#[derive(Debug, FromRequestParts)]
+#[from_request(via(axum::extract::Query), rejection(ApiErrorResponse))]
+pub struct AppQuery<T>(pub T);
+
+/// Path parameter extractor that rejects with our `ApiErrorResponse` envelope
+///
+/// Parameters that fail to parse come back as `BadRequest`; wrap them in [`PathParam`] for
+/// a message naming the parameter, e.g. ``Path parameter `id` must be a UUID, got "42"``.
+#[derive(Debug, FromRequestParts)]
+#[from_request(via(axum::extract::Path), rejection(ApiErrorResponse))]
+pub struct AppPath<T>(pub T);
+
+/// Value a path parameter can hold
+pub trait FromPathParam: Sized {
+    /// What the parameter must be, completing "must be", e.g. `a UUID`
+    const EXPECTED: &'static str;
+
+    fn from_param(value: &str) -> Option<Self>;
+}
+
+impl<T: From<Uuid>> FromPathParam for T {
+    const EXPECTED: &'static str = "a UUID";
+
+    fn from_param(value: &str) -> Option<Self> {
+        Uuid::parse_str(value).ok().map(Self::from)
+    }
+}
+
+/// Path parameter parsed as `T`, e.g. `AppPath<PathParam<TaskId>>`
+///
+/// A value `T` rejects fails with a message saying what was expected, which the
+/// [`AppPath`] rejection completes with the parameter's name and value.
+#[derive(Debug, Clone, Copy)]
+pub struct PathParam<T>(pub T);
+
+impl<'de, T: FromPathParam> Deserialize<'de> for PathParam<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ParamVisitor<T>(PhantomData<T>);
+
+        impl<T: FromPathParam> Visitor<'_> for ParamVisitor<T> {
+            type Value = PathParam<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str(T::EXPECTED)
+            }
+
+            // axum adds the parameter's name and value to errors raised here
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                T::from_param(value)
+                    .map(PathParam)
+                    .ok_or_else(|| E::custom(format!("must be {}", T::EXPECTED)))
+            }
+        }
+
+        deserializer.deserialize_str(ParamVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Tag {
+        #[allow(dead_code)]
+        name: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Body {
+        #[allow(dead_code)]
+        tags: Option<Vec<Tag>>,
+    }
+
+    #[test]
+    fn test_field_path_names_nested_fields_without_option_markers() {
+        let body = serde_json::json!({
+            "tags": [{ "name": "a" }, { "name": "b", "colour": "red" }],
+            "extra": true,
+        });
+        let mut unknown = Vec::new();
+
+        let _: Body = serde_ignored::deserialize(body, |path| {
+            let mut segments = Vec::new();
+            field_path(&path, &mut segments);
+            unknown.push(segments.join("."));
+        })
+        .unwrap();
+
+        assert_eq!(unknown, ["extra", "tags.1.colour"]);
+    }
+}
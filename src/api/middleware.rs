@@ -0,0 +1,287 @@
+use std::{
+    any::Any,
+    backtrace::Backtrace,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::{MatchedPath, State},
+    http::{
+        header::{CONTENT_TYPE, RETRY_AFTER},
+        Request,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+    BoxError,
+};
+use http_body::{Frame, SizeHint};
+use tower::{load_shed::error::Overloaded, timeout::error::Elapsed};
+
+use crate::{
+    api::error::{ApiErrorResponse, ErrorCode},
+    config::RequestLoggingConfig,
+    infrastructure::{
+        error_reporting::{self, RequestContext},
+        telemetry,
+    },
+};
+
+/// Header carrying the per-request correlation id
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Build the root tracing span for a request, tagged with its request id
+///
+/// The id is set by `SetRequestIdLayer` before the span is created, so every log line
+/// emitted while handling the request (including panic logs) carries it. An incoming
+/// `traceparent` header makes the span a child of the caller's trace. `user_id` is filled in
+/// once the request is authenticated.
+///
+/// `path` is the route template (`/tasks/{id}`) so ids don't explode log cardinality;
+/// unmatched requests fall back to the raw path.
+pub fn make_request_span<B>(request: &Request<B>) -> tracing::Span {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| request.uri().path(), MatchedPath::as_str);
+
+    let span = tracing::info_span!(
+        "request",
+        method = %request.method(),
+        path = %path,
+        request_id = %request_id,
+        user_id = tracing::field::Empty,
+    );
+    telemetry::set_parent_from_headers(&span, request.headers());
+
+    span
+}
+
+/// Convert a panic caught by `CatchPanicLayer` into a JSON `InternalServerError` response
+///
+/// The payload and backtrace are logged by the hook installed with [`install_panic_hook`],
+/// which runs at the panic site; this handler only records that the panic was converted,
+/// and counts it in `http_panics_total`.
+pub fn handle_panic(payload: Box<dyn Any + Send + 'static>) -> Response {
+    let message = panic_message(payload.as_ref());
+    metrics::counter!("http_panics_total").increment(1);
+    tracing::error!(
+        error_type = "Panic",
+        panic_message = %message,
+        "Handler panicked, responding with 500"
+    );
+    error_reporting::report_error("Panic", message);
+
+    ApiErrorResponse::from(ErrorCode::InternalServerError).into_response()
+}
+
+/// Install a process-wide panic hook that logs panics through `tracing`
+///
+/// The hook runs on the panicking thread before unwinding, so the current span (and its
+/// `request_id` field) is still entered and the captured backtrace points at the panic site.
+pub fn install_panic_hook() {
+    std::panic::set_hook(Box::new(|info| {
+        let location = info
+            .location()
+            .map(|location| location.to_string())
+            .unwrap_or_default();
+
+        tracing::error!(
+            error_type = "Panic",
+            panic_message = %panic_message(info.payload()),
+            panic_location = %location,
+            backtrace = %Backtrace::force_capture(),
+            "Panic occurred"
+        );
+    }));
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic payload")
+}
+
+/// Convert errors from the load-shedding stack into API responses
+///
+/// `LoadShedLayer` turns "no concurrency permit available" into an immediate `Overloaded`
+/// error instead of queueing, which becomes a 503 with a `Retry-After` header. A request
+/// that ran past the request timeout becomes a 504.
+pub fn handle_overload(error: &BoxError, retry_after: u64) -> Response {
+    if error.is::<Overloaded>() {
+        tracing::warn!(
+            error_type = "Overloaded",
+            "Concurrency limit reached, shedding request"
+        );
+        return (
+            [(RETRY_AFTER, retry_after.to_string())],
+            ApiErrorResponse::from(ErrorCode::ServiceUnavailable),
+        )
+            .into_response();
+    }
+
+    if error.is::<Elapsed>() {
+        tracing::error!(error_type = "Timeout", "Request timed out, abandoning it");
+        error_reporting::report_error("Timeout", "Request timed out");
+        return ApiErrorResponse::from(ErrorCode::GatewayTimeout).into_response();
+    }
+
+    tracing::error!(
+        error_type = "MiddlewareError",
+        error_message = %error,
+        "Unhandled middleware error"
+    );
+    ApiErrorResponse::from(ErrorCode::InternalServerError).into_response()
+}
+
+/// Make the request id, route and (later) user id available to error reports
+///
+/// Skipped entirely when no error reporter is installed.
+pub async fn error_reporting_context(request: Request<Body>, next: Next) -> Response {
+    if !error_reporting::is_enabled() {
+        return next.run(request).await;
+    }
+
+    let context = RequestContext {
+        request_id: request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        route: request
+            .extensions()
+            .get::<MatchedPath>()
+            .map(|path| path.as_str().to_string()),
+        ..Default::default()
+    };
+
+    error_reporting::with_request_context(context, next.run(request)).await
+}
+
+/// Log the outcome of a request: info for 2xx/3xx, warn for 4xx, error for 5xx
+///
+/// Runs inside the request span, so method, path, request id and user id are attached.
+pub fn log_response<B>(response: &Response<B>, latency: Duration, _span: &tracing::Span) {
+    let status = response.status();
+    let latency_ms = u64::try_from(latency.as_millis()).unwrap_or(u64::MAX);
+
+    if status.is_server_error() {
+        tracing::error!(status = status.as_u16(), latency_ms, "Request failed");
+    } else if status.is_client_error() {
+        tracing::warn!(status = status.as_u16(), latency_ms, "Request rejected");
+    } else {
+        tracing::info!(status = status.as_u16(), latency_ms, "Request completed");
+    }
+}
+
+/// Log request and response bodies at debug level, truncated to `max_body_bytes`
+///
+/// A no-op unless `log_bodies` is enabled. Bodies stream through unchanged while their
+/// first `max_body_bytes` are copied aside, and each is logged once it has been read to
+/// the end, so neither is held in memory whole. Event streams never end and are not logged.
+pub async fn log_bodies(
+    State(config): State<RequestLoggingConfig>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    if !config.log_bodies {
+        return next.run(request).await;
+    }
+
+    let request = request.map(|body| LoggedBody::wrap(body, "Request body", &config));
+    let response = next.run(request).await;
+
+    let is_event_stream = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .is_some_and(|content_type| content_type.as_bytes().starts_with(b"text/event-stream"));
+    if is_event_stream {
+        return response;
+    }
+
+    response.map(|body| LoggedBody::wrap(body, "Response body", &config))
+}
+
+/// Body passing its frames through while keeping a copy of at most `max_bytes` of data
+///
+/// Logs the copy when dropped, in the span of the request it belongs to.
+struct LoggedBody {
+    inner: Body,
+    message: &'static str,
+    head: Vec<u8>,
+    max_bytes: usize,
+    total_bytes: usize,
+    span: tracing::Span,
+}
+
+impl LoggedBody {
+    fn wrap(inner: Body, message: &'static str, config: &RequestLoggingConfig) -> Body {
+        Body::new(Self {
+            inner,
+            message,
+            head: Vec::new(),
+            max_bytes: config.max_body_bytes,
+            total_bytes: 0,
+            span: tracing::Span::current(),
+        })
+    }
+}
+
+impl HttpBody for LoggedBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, axum::Error>>> {
+        let polled = Pin::new(&mut self.inner).poll_frame(cx);
+        if let Poll::Ready(Some(Ok(frame))) = &polled {
+            if let Some(data) = frame.data_ref() {
+                let kept = self
+                    .max_bytes
+                    .saturating_sub(self.head.len())
+                    .min(data.len());
+                self.head.extend_from_slice(&data[..kept]);
+                self.total_bytes += data.len();
+            }
+        }
+        polled
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl Drop for LoggedBody {
+    fn drop(&mut self) {
+        let body = truncate_body(&self.head, self.total_bytes);
+        self.span
+            .in_scope(|| tracing::debug!(body = %body, "{}", self.message));
+    }
+}
+
+/// The logged part of a body of `total_bytes`, noting the size when some was left out
+fn truncate_body(head: &[u8], total_bytes: usize) -> String {
+    let body = String::from_utf8_lossy(head);
+    if total_bytes > head.len() {
+        format!("{body}... ({total_bytes} bytes total)")
+    } else {
+        body.into_owned()
+    }
+}
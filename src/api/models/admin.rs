@@ -0,0 +1,223 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+// <feature:swagger>
+use utoipa::ToSchema;
+// </feature:swagger>
+
+// <feature:swagger>
+use crate::api::models::{
+    examples,
+    tasks::{TaskPrioritySchema, TaskStatusSchema},
+};
+// </feature:swagger>
+use crate::{
+    common::UserId,
+    domain::{
+        errors::DomainError,
+        job::{
+            models::{Job, JobFilter, JobStatus},
+            operations::DEFAULT_JOB_PAGE_SIZE,
+        },
+        task::models::{
+            search::DEFAULT_TASK_PAGE_SIZE, SortDirection, TaskFilter, TaskPriority, TaskSearch,
+            TaskSort, TaskSortField, TaskStatus,
+        },
+    },
+};
+
+/// Active log filter, in `RUST_LOG` syntax
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LogLevel {
+    #[schema(example = "rust_service_template=debug,tower_http=info,sqlx=warn")]
+    pub directives: String,
+}
+
+/// Current value of every feature flag
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FeatureFlagsResponse {
+    /// Flags by name, with their default when the configuration does not set them
+    #[schema(example = json!({"enforce_quotas": true, "reject_duplicate_titles": false, "warn_duplicate_titles": false}))]
+    pub features: BTreeMap<String, bool>,
+}
+
+/// Statuses background jobs are listed by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatusFilter {
+    Pending,
+    Running,
+    Dead,
+    /// Jobs whose latest attempt failed, whether waiting for a retry or dead
+    Failed,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListJobsQuery {
+    /// `failed` lists jobs whose latest attempt failed, whether waiting for a retry or dead
+    #[param(value_type = Option<JobStatusFilter>)]
+    pub status: Option<JobStatusFilter>,
+    /// Kind of the jobs to list, e.g. `send_reminder`
+    pub kind: Option<String>,
+    /// Jobs per page, 1 to 100; 50 by default
+    pub limit: Option<u32>,
+    /// Jobs skipped before the page, oldest first; 0 by default
+    pub offset: Option<u64>,
+}
+
+impl ListJobsQuery {
+    #[must_use]
+    pub fn filter(&self) -> JobFilter {
+        let (status, failed) = match self.status {
+            None => (None, false),
+            Some(JobStatusFilter::Pending) => (Some(JobStatus::Pending), false),
+            Some(JobStatusFilter::Running) => (Some(JobStatus::Running), false),
+            Some(JobStatusFilter::Dead) => (Some(JobStatus::Dead), false),
+            Some(JobStatusFilter::Failed) => (None, true),
+        };
+        JobFilter {
+            status,
+            failed,
+            kind: self.kind.clone(),
+        }
+    }
+
+    #[must_use]
+    pub fn limit(&self) -> u32 {
+        self.limit.unwrap_or(DEFAULT_JOB_PAGE_SIZE)
+    }
+
+    #[must_use]
+    pub fn offset(&self) -> u64 {
+        self.offset.unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = examples::job)]
+pub struct JobResponse {
+    pub id: String,
+    pub kind: String,
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+    /// `pending`, `running` or `dead`; jobs that succeeded are deleted
+    #[schema(value_type = String)]
+    pub status: JobStatus,
+    /// Not run before this time
+    #[schema(format = DateTime)]
+    pub run_at: DateTime<Utc>,
+    /// Attempts started so far
+    pub attempts: u32,
+    /// Why the latest attempt failed, when it did
+    pub last_error: Option<String>,
+    /// Worker running the job
+    pub locked_by: Option<String>,
+    #[schema(format = DateTime)]
+    pub created_at: DateTime<Utc>,
+    #[schema(format = DateTime)]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Job> for JobResponse {
+    fn from(job: Job) -> Self {
+        Self {
+            id: job.id.to_string(),
+            kind: job.kind,
+            payload: job.payload,
+            status: job.status,
+            run_at: job.run_at,
+            attempts: job.attempts,
+            last_error: job.last_error,
+            locked_by: job.locked_by,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+        }
+    }
+}
+
+/// Fields tasks are sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSortFieldSchema {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    /// From `Low` to `Critical`
+    Priority,
+    /// By code point, so `Z` comes before `a`
+    Title,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchTasksQuery {
+    /// Owner of the tasks
+    #[param(value_type = Option<String>, format = Uuid)]
+    pub user_id: Option<UserId>,
+    #[param(value_type = Option<TaskStatusSchema>)]
+    pub status: Option<TaskStatus>,
+    #[param(value_type = Option<TaskPrioritySchema>)]
+    pub priority: Option<TaskPriority>,
+    /// Tasks created at or after this time, e.g. `2025-03-01T00:00:00Z`
+    #[param(value_type = Option<String>, format = DateTime)]
+    pub created_from: Option<DateTime<Utc>>,
+    /// Tasks created before this time
+    #[param(value_type = Option<String>, format = DateTime)]
+    pub created_before: Option<DateTime<Utc>>,
+    /// Part of the title, matched ignoring case
+    pub title: Option<String>,
+    /// Part of the task id, e.g. `5b3c8f4e`
+    pub id: Option<String>,
+    /// `created_at` by default
+    #[param(value_type = Option<TaskSortFieldSchema>)]
+    pub sort: Option<TaskSortFieldSchema>,
+    /// `desc` by default, ties broken by id
+    #[param(value_type = Option<SortOrder>)]
+    pub order: Option<SortOrder>,
+    /// Tasks per page, 1 to 100; 50 by default
+    pub limit: Option<u32>,
+    /// Tasks skipped before the page; 0 by default
+    pub offset: Option<u64>,
+}
+
+impl SearchTasksQuery {
+    /// The search this query asks for, with every invalid field reported at once
+    pub fn search(self) -> Result<TaskSearch, DomainError> {
+        let filter = TaskFilter {
+            user_id: self.user_id,
+            status: self.status,
+            priority: self.priority,
+            created_from: self.created_from,
+            created_before: self.created_before,
+            title: self.title,
+            id_fragment: self.id,
+        };
+        let sort = TaskSort {
+            field: match self.sort.unwrap_or_default() {
+                TaskSortFieldSchema::CreatedAt => TaskSortField::CreatedAt,
+                TaskSortFieldSchema::UpdatedAt => TaskSortField::UpdatedAt,
+                TaskSortFieldSchema::Priority => TaskSortField::Priority,
+                TaskSortFieldSchema::Title => TaskSortField::Title,
+            },
+            direction: match self.order.unwrap_or_default() {
+                SortOrder::Asc => SortDirection::Asc,
+                SortOrder::Desc => SortDirection::Desc,
+            },
+        };
+        TaskSearch::new(
+            filter,
+            sort,
+            self.limit.unwrap_or(DEFAULT_TASK_PAGE_SIZE),
+            self.offset.unwrap_or_default(),
+        )
+    }
+}
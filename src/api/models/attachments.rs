@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+// <feature:swagger>
+use utoipa::ToSchema;
+// </feature:swagger>
+
+// <feature:swagger>
+use crate::api::models::examples;
+// </feature:swagger>
+use crate::domain::attachment::models::{Attachment, PresignedUpload};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = examples::create_attachment_request)]
+pub struct CreateAttachmentRequest {
+    /// Name of the file, without directories
+    pub filename: String,
+    /// Media type of the file, one of the types the service accepts
+    pub content_type: String,
+    /// Exact size of the file in bytes
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = examples::attachment)]
+pub struct AttachmentResponse {
+    pub id: String,
+    pub task_id: String,
+    pub filename: String,
+    pub content_type: String,
+    pub size: u64,
+    #[schema(format = DateTime)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Attachment> for AttachmentResponse {
+    fn from(attachment: Attachment) -> Self {
+        Self {
+            id: attachment.id.to_string(),
+            task_id: attachment.task_id.to_string(),
+            filename: attachment.filename,
+            content_type: attachment.content_type,
+            size: attachment.size,
+            created_at: attachment.created_at,
+        }
+    }
+}
+
+/// How to upload the file: one `PUT` of its bytes to `url`, sending `headers`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UploadInstructions {
+    pub url: String,
+    /// Always `PUT`
+    pub method: String,
+    /// Headers the request must carry with exactly these values
+    pub headers: BTreeMap<String, String>,
+    /// When `url` stops accepting the upload
+    #[schema(format = DateTime)]
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<PresignedUpload> for UploadInstructions {
+    fn from(upload: PresignedUpload) -> Self {
+        Self {
+            url: upload.url,
+            method: "PUT".to_string(),
+            headers: upload.headers.into_iter().collect(),
+            expires_at: upload.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = examples::attachment_upload)]
+pub struct AttachmentUploadResponse {
+    pub attachment: AttachmentResponse,
+    pub upload: UploadInstructions,
+}
@@ -0,0 +1,62 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+// <feature:swagger>
+use utoipa::ToSchema;
+// </feature:swagger>
+
+use crate::{build_info::BuildInfo, infrastructure::health_history::ProbeResult};
+
+/// State of the service's dependencies, for operators rather than probes
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DetailedHealthResponse {
+    /// `ok`, or `degraded` when the database is down or a circuit breaker is not closed
+    pub status: String,
+    pub database: ComponentHealth,
+    /// State of every circuit breaker used so far by name, e.g. `kafka` or `webhook:{id}`:
+    /// `closed`, `open` or `half_open`
+    pub circuit_breakers: BTreeMap<String, String>,
+    /// What this instance is running
+    pub build: BuildInfo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ComponentHealth {
+    /// `up` or `down`
+    pub status: String,
+    /// Why the component is down
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Recent readiness probes, for explaining why a pod was restarted
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct HealthHistoryResponse {
+    /// Share of the recorded probes that succeeded, from 0 to 1; `null` before the first
+    pub success_rate: Option<f64>,
+    /// Whether the success rate is below `HEALTH_HISTORY_CONFIG__MIN_SUCCESS_RATE`
+    pub flapping: bool,
+    /// Newest first
+    pub probes: Vec<ProbeResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProbeResponse {
+    #[schema(format = DateTime)]
+    pub at: DateTime<Utc>,
+    pub latency_ms: u64,
+    /// Why the probe failed; absent when it succeeded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<ProbeResult> for ProbeResponse {
+    fn from(probe: ProbeResult) -> Self {
+        Self {
+            at: probe.at,
+            latency_ms: u64::try_from(probe.latency.as_millis()).unwrap_or(u64::MAX),
+            error: probe.error,
+        }
+    }
+}
@@ -1,5 +1,14 @@
 // Request/Response DTOs go here
-// Example:
-// pub mod user;
 
+pub mod admin;
+pub mod attachments;
+// <feature:swagger>
+pub mod examples;
+// </feature:swagger>
+pub mod health;
+pub mod page;
 pub mod tasks;
+pub mod usage;
+pub mod users;
+pub mod warnings;
+pub mod webhooks;
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+// <feature:swagger>
+use utoipa::ToSchema;
+// </feature:swagger>
+
+use crate::{
+    api::models::tasks::TaskResponse,
+    domain::task::models::{TaskPage, TaskSearch},
+};
+
+/// One page of a listing, with enough to request the next one
+///
+/// Every paged list endpoint returns this envelope. It is documented once per item type,
+/// e.g. as `Page_TaskResponse`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Items matching the filters, on every page
+    pub total: u64,
+    pub limit: u32,
+    /// Items skipped before this page
+    pub offset: u64,
+    /// `offset` of the next page, absent on the last one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<u64>,
+}
+
+impl<T> Page<T> {
+    /// The page of `total` items starting at `offset`, converting each of `items`
+    #[must_use]
+    pub fn new<U: Into<T>>(
+        items: impl IntoIterator<Item = U>,
+        total: u64,
+        limit: u32,
+        offset: u64,
+    ) -> Self {
+        let items: Vec<T> = items.into_iter().map(Into::into).collect();
+        let end = offset.saturating_add(items.len() as u64);
+        Self {
+            next_offset: (end < total && !items.is_empty()).then_some(end),
+            items,
+            total,
+            limit,
+            offset,
+        }
+    }
+}
+
+impl Page<TaskResponse> {
+    /// The page of a task search
+    #[must_use]
+    pub fn from_search(page: TaskPage, search: &TaskSearch) -> Self {
+        Self::new(page.tasks, page.total, search.limit(), search.offset())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_offset_follows_the_page_until_the_last_one() {
+        let first: Page<u64> = Page::new([1_u32, 2], 5, 2, 0);
+        let last: Page<u64> = Page::new([5_u32], 5, 2, 4);
+        let past_the_end: Page<u64> = Page::new(Vec::<u32>::new(), 5, 2, 10);
+
+        assert_eq!(first.next_offset, Some(2));
+        assert_eq!(first.items, vec![1, 2]);
+        assert_eq!(last.next_offset, None);
+        assert_eq!(past_the_end.next_offset, None);
+    }
+}
@@ -1,8 +1,28 @@
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+// <feature:swagger>
 use utoipa::ToSchema;
+// </feature:swagger>
 
-use crate::domain::task::models::{Task, TaskPriority, TaskStatus};
+use super::warnings::WarningResponse;
+use crate::{
+    api::extractors::FromPathParam,
+    // <feature:swagger>
+    api::models::examples,
+    // </feature:swagger>
+    common::UserId,
+    domain::{
+        errors::DomainError,
+        task::models::{
+            search::DEFAULT_TASK_PAGE_SIZE, DailyTaskCount, Task, TaskChange, TaskEventType,
+            TaskFilter, TaskId, TaskPriority, TaskRef, TaskSearch, TaskSort, TaskStats, TaskStatus,
+        },
+        warnings::DomainWarning,
+    },
+};
 
+// <feature:swagger>
 // Schema types for OpenAPI documentation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[schema(as = TaskStatus)]
@@ -22,7 +42,17 @@ pub enum TaskPrioritySchema {
     Critical,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = TaskChangeType)]
+pub enum TaskChangeTypeSchema {
+    Created,
+    Updated,
+    Deleted,
+}
+// </feature:swagger>
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = examples::task)]
 pub struct TaskResponse {
     pub id: String,
     pub user_id: String,
@@ -32,9 +62,28 @@ pub struct TaskResponse {
     pub status: TaskStatus,
     #[schema(value_type = TaskPrioritySchema)]
     pub priority: TaskPriority,
-    pub created_at: String,
-    pub updated_at: String,
-    pub completed_at: Option<String>,
+    /// Serialized as RFC 3339 in UTC, e.g. `2024-01-15T09:30:00.123456Z`
+    #[schema(format = DateTime)]
+    pub created_at: DateTime<Utc>,
+    #[schema(format = DateTime)]
+    pub updated_at: DateTime<Utc>,
+    #[schema(format = DateTime)]
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Concerns about the write that returned the task, in the verbose representation only
+    /// (requested with `Prefer: return=verbose`); an empty array when there are none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<Vec<WarningResponse>>,
+}
+
+impl TaskResponse {
+    /// The verbose representation, listing `warnings`
+    #[must_use]
+    pub fn with_warnings(self, warnings: &[DomainWarning]) -> Self {
+        Self {
+            warnings: Some(warnings.iter().map(WarningResponse::from).collect()),
+            ..self
+        }
+    }
 }
 
 impl From<Task> for TaskResponse {
@@ -46,25 +95,213 @@ impl From<Task> for TaskResponse {
             description: task.description,
             status: task.status,
             priority: task.priority,
-            created_at: task.created_at.to_rfc3339(),
-            updated_at: task.updated_at.to_rfc3339(),
-            completed_at: task
-                .completed_at
-                .map(|dt: chrono::DateTime<chrono::Utc>| dt.to_rfc3339()),
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            completed_at: task.completed_at,
+            warnings: None,
         }
     }
 }
 
-#[derive(Debug, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = examples::create_task_request)]
 pub struct CreateTaskRequest {
+    /// Owner of the task; required unless user ids are external, when a missing one is
+    /// generated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, format = Uuid)]
+    pub user_id: Option<UserId>,
     pub title: String,
+    /// Up to 2000 characters once trimmed
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[schema(value_type = TaskPrioritySchema)]
     pub priority: Option<TaskPriority>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = examples::change_priority_request)]
+pub struct ChangePriorityRequest {
+    #[schema(value_type = TaskPrioritySchema)]
+    pub priority: TaskPriority,
+    /// Why the priority changes, kept in its audit trail; required when escalating to
+    /// `Critical`, up to 500 characters once trimmed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// User making the change
+    // <feature:auth>
+    ///
+    /// Defaults to the token's subject; naming another user needs the `admin` role.
+    // </feature:auth>
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>, format = Uuid)]
+    pub changed_by: Option<UserId>,
+}
+
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListTasksQuery {
-    pub user_id: Option<String>,
+    /// Owner of the tasks to list
+    #[param(value_type = String, format = Uuid)]
+    pub user_id: UserId,
+}
+
+/// Filters and page of `GET /api/v1/users/{user_id}/tasks`
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskPageQuery {
+    #[param(value_type = Option<TaskStatusSchema>)]
+    pub status: Option<TaskStatus>,
+    #[param(value_type = Option<TaskPrioritySchema>)]
+    pub priority: Option<TaskPriority>,
+    /// Tasks per page, 1 to 100; 50 by default
+    pub limit: Option<u32>,
+    /// Tasks skipped before the page; 0 by default
+    pub offset: Option<u64>,
+}
+
+impl TaskPageQuery {
+    /// The search for the tasks of `user_id` this query asks for, newest first
+    pub fn search(self, user_id: UserId) -> Result<TaskSearch, DomainError> {
+        let filter = TaskFilter {
+            user_id: Some(user_id),
+            status: self.status,
+            priority: self.priority,
+            ..TaskFilter::default()
+        };
+        TaskSearch::new(
+            filter,
+            TaskSort::default(),
+            self.limit.unwrap_or(DEFAULT_TASK_PAGE_SIZE),
+            self.offset.unwrap_or_default(),
+        )
+    }
+}
+
+/// `{id}` of `GET /tasks/{id}`: a task ID, or `latest` for the newest task of a user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskIdParam {
+    Id(TaskId),
+    Latest,
+}
+
+impl TaskIdParam {
+    /// The task this names, taking the user of `latest` from `user_id`
+    pub fn resolve(self, user_id: Option<UserId>) -> Result<TaskRef, DomainError> {
+        match (self, user_id) {
+            (Self::Id(id), _) => Ok(TaskRef::Id(id)),
+            (Self::Latest, Some(user_id)) => Ok(TaskRef::Latest(user_id)),
+            (Self::Latest, None) => Err(DomainError::field_validation_error(
+                "user_id",
+                "user_id is required with `latest`",
+            )),
+        }
+    }
+}
+
+impl FromPathParam for TaskIdParam {
+    const EXPECTED: &'static str = "a UUID or `latest`";
+
+    fn from_param(value: &str) -> Option<Self> {
+        if value == "latest" {
+            return Some(Self::Latest);
+        }
+        TaskId::from_param(value).map(Self::Id)
+    }
+}
+
+impl fmt::Display for TaskIdParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Id(id) => id.fmt(f),
+            Self::Latest => f.write_str("latest"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetTaskQuery {
+    /// Owner of the task `latest` names; required with `latest`, ignored otherwise
+    #[param(value_type = Option<String>, format = Uuid)]
+    pub user_id: Option<UserId>,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskStatsQuery {
+    /// First day counted, e.g. `2025-03-01`
+    #[param(value_type = String, format = Date)]
+    pub from: NaiveDate,
+    /// Last day counted, included; at most 366 days after `from`, counting both
+    #[param(value_type = String, format = Date)]
+    pub to: NaiveDate,
+    /// IANA time zone the days start at midnight in, e.g. `Europe/Warsaw`; UTC by default
+    pub tz: Option<String>,
+}
+
+/// Tasks created and completed per day, for burn-down charts
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = examples::task_stats)]
+pub struct TaskStatsResponse {
+    #[schema(format = Date)]
+    pub from: NaiveDate,
+    #[schema(format = Date)]
+    pub to: NaiveDate,
+    /// Time zone the days were counted in
+    pub tz: String,
+    /// One entry per day from `from` to `to`, including days without tasks
+    pub days: Vec<DailyTaskCountResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DailyTaskCountResponse {
+    #[schema(format = Date)]
+    pub date: NaiveDate,
+    /// Tasks created that day
+    pub created: i64,
+    /// Tasks completed that day, whenever they were created
+    pub completed: i64,
+}
+
+impl From<DailyTaskCount> for DailyTaskCountResponse {
+    fn from(day: DailyTaskCount) -> Self {
+        Self {
+            date: day.date,
+            created: day.created,
+            completed: day.completed,
+        }
+    }
+}
+
+impl From<TaskStats> for TaskStatsResponse {
+    fn from(stats: TaskStats) -> Self {
+        Self {
+            from: stats.range.from(),
+            to: stats.range.to(),
+            tz: stats.range.time_zone().name().to_string(),
+            days: stats.days.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Data of a `task_change` event on `GET /tasks/stream`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = examples::task_change)]
+pub struct TaskChangeResponse {
+    #[schema(value_type = TaskChangeTypeSchema)]
+    pub event_type: TaskEventType,
+    pub task_id: String,
+    pub user_id: String,
+}
+
+impl From<TaskChange> for TaskChangeResponse {
+    fn from(change: TaskChange) -> Self {
+        Self {
+            event_type: change.event_type,
+            task_id: change.task_id.to_string(),
+            user_id: change.user_id.to_string(),
+        }
+    }
 }
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+// <feature:swagger>
+use utoipa::ToSchema;
+// </feature:swagger>
+
+use crate::{
+    // <feature:swagger>
+    api::models::examples,
+    // </feature:swagger>
+    common::UserId,
+    config::QuotaConfig,
+    domain::quota::models::{Quota, Usage},
+};
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UsageQuery {
+    /// User whose usage to report
+    // <feature:auth>
+    ///
+    /// Defaults to the token's subject; reporting on another user needs the `admin` role.
+    // </feature:auth>
+    #[param(value_type = Option<String>, format = Uuid)]
+    pub user_id: Option<UserId>,
+}
+
+/// Usage of one quota; an unset limit does not apply
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct QuotaUsageResponse {
+    pub used: u64,
+    /// Going past it is allowed, with an `X-Quota-Warning` header on the response
+    pub soft: Option<u64>,
+    /// Writes that would go past it fail with `QuotaExceeded`
+    pub hard: Option<u64>,
+}
+
+impl QuotaUsageResponse {
+    const fn new(used: u64, quota: Quota) -> Self {
+        Self {
+            used,
+            soft: quota.soft,
+            hard: quota.hard,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = examples::usage)]
+pub struct UsageResponse {
+    /// Pending and in-progress tasks of the user
+    pub open_tasks: QuotaUsageResponse,
+    /// Tasks the user created since the start of the month, in UTC
+    pub tasks_this_month: QuotaUsageResponse,
+    /// Webhooks of the tenant
+    pub webhooks: QuotaUsageResponse,
+}
+
+impl UsageResponse {
+    #[must_use]
+    pub const fn new(usage: Usage, quotas: &QuotaConfig) -> Self {
+        Self {
+            open_tasks: QuotaUsageResponse::new(usage.tasks.open, quotas.open_tasks),
+            tasks_this_month: QuotaUsageResponse::new(usage.tasks.created, quotas.monthly_tasks),
+            webhooks: QuotaUsageResponse::new(usage.webhooks, quotas.webhooks),
+        }
+    }
+}
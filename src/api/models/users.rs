@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+// <feature:swagger>
+use utoipa::ToSchema;
+// </feature:swagger>
+
+use crate::{
+    // <feature:swagger>
+    api::models::examples,
+    // </feature:swagger>
+    domain::user::models::User,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = examples::user)]
+pub struct UserResponse {
+    pub id: String,
+    /// Lowercased on registration
+    pub email: String,
+    pub display_name: String,
+    /// Serialized as RFC 3339 in UTC, e.g. `2024-01-15T09:30:00.123456Z`
+    #[schema(format = DateTime)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<User> for UserResponse {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id.to_string(),
+            email: user.email.into_inner(),
+            display_name: user.display_name,
+            created_at: user.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = examples::create_user_request)]
+pub struct CreateUserRequest {
+    pub email: String,
+    pub display_name: String,
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+// <feature:swagger>
+use utoipa::ToSchema;
+// </feature:swagger>
+
+use crate::domain::warnings::DomainWarning;
+
+/// A concern about a write that succeeded anyway
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct WarningResponse {
+    /// Machine-readable name of the warning, as listed in `X-Warnings`, e.g.
+    /// `PossibleDuplicateTitle`
+    pub code: String,
+    pub message: String,
+}
+
+impl From<&DomainWarning> for WarningResponse {
+    fn from(warning: &DomainWarning) -> Self {
+        Self {
+            code: warning.code().to_string(),
+            message: warning.to_string(),
+        }
+    }
+}
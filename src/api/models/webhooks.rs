@@ -0,0 +1,120 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+// <feature:swagger>
+use utoipa::ToSchema;
+// </feature:swagger>
+
+use crate::{
+    // <feature:swagger>
+    api::models::{examples, tasks::TaskChangeTypeSchema},
+    // </feature:swagger>
+    domain::{
+        task::models::TaskEventType,
+        webhook::models::{Webhook, WebhookDelivery},
+    },
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = examples::create_webhook_request)]
+pub struct CreateWebhookRequest {
+    /// Absolute `http://` or `https://` URL events are POSTed to
+    pub url: String,
+    /// Key for the `X-Webhook-Signature` HMAC, 16 to 256 characters; generated when omitted
+    pub secret: Option<String>,
+    /// Event types to deliver; every type when omitted or empty
+    #[schema(value_type = Option<Vec<TaskChangeTypeSchema>>)]
+    pub event_types: Option<Vec<TaskEventType>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = examples::update_webhook_request)]
+pub struct UpdateWebhookRequest {
+    pub url: String,
+    /// Event types to deliver; every type when empty
+    #[schema(value_type = Vec<TaskChangeTypeSchema>)]
+    pub event_types: Vec<TaskEventType>,
+    /// Reactivating a webhook starts its failure count over
+    pub active: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = examples::webhook)]
+pub struct WebhookResponse {
+    pub id: String,
+    pub url: String,
+    /// Signing secret, only returned when the webhook is created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    #[schema(value_type = Vec<TaskChangeTypeSchema>)]
+    pub event_types: Vec<TaskEventType>,
+    /// `false` once the webhook was deactivated, by hand or after repeated failures
+    pub active: bool,
+    /// Events in a row that could not be delivered
+    pub consecutive_failures: u32,
+    #[schema(format = DateTime)]
+    pub created_at: DateTime<Utc>,
+    #[schema(format = DateTime)]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookResponse {
+    /// Response to `POST /webhooks`, the only one that reveals the secret
+    #[must_use]
+    pub fn with_secret(webhook: Webhook) -> Self {
+        let secret = webhook.secret.clone();
+        Self {
+            secret: Some(secret),
+            ..webhook.into()
+        }
+    }
+}
+
+impl From<Webhook> for WebhookResponse {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            id: webhook.id.to_string(),
+            url: webhook.url.into_inner(),
+            secret: None,
+            event_types: webhook.event_types,
+            active: webhook.active,
+            consecutive_failures: webhook.consecutive_failures,
+            created_at: webhook.created_at,
+            updated_at: webhook.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(example = examples::webhook_delivery)]
+pub struct WebhookDeliveryResponse {
+    pub id: String,
+    /// Same for every attempt to deliver one event, and sent as `X-Webhook-Delivery`
+    pub event_id: String,
+    #[schema(value_type = TaskChangeTypeSchema)]
+    pub event_type: TaskEventType,
+    /// Attempt number for this event, starting at 1
+    pub attempt: u32,
+    /// Status the endpoint answered with; absent when no response arrived
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+    pub succeeded: bool,
+    pub duration_ms: u64,
+    #[schema(format = DateTime)]
+    pub attempted_at: DateTime<Utc>,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryResponse {
+    fn from(delivery: WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id.to_string(),
+            event_id: delivery.event_id.to_string(),
+            event_type: delivery.event_type,
+            attempt: delivery.attempt,
+            status_code: delivery.status_code,
+            error: delivery.error,
+            succeeded: delivery.succeeded,
+            duration_ms: delivery.duration_ms,
+            attempted_at: delivery.attempted_at,
+        }
+    }
+}
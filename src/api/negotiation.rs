@@ -0,0 +1,316 @@
+use std::{convert::Infallible, sync::Arc};
+
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, FromRequestParts, Request},
+    http::{header, request::Parts, HeaderMap},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    api::{
+        error::{ApiErrorResponse, ErrorCode},
+        extractors::{deserialize_strict, AppJson},
+    },
+    config::AppState,
+};
+
+/// Media types read as MessagePack; responses use the first
+pub const MSGPACK_MEDIA_TYPES: [&str; 2] = ["application/msgpack", "application/x-msgpack"];
+
+/// Encoding of a request or response body
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BodyFormat {
+    #[default]
+    Json,
+    MessagePack,
+}
+
+impl BodyFormat {
+    /// Content type of responses in this format
+    #[must_use]
+    pub fn media_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::MessagePack => MSGPACK_MEDIA_TYPES[0],
+        }
+    }
+}
+
+/// Whether an `Accept` header ranks one of `media_types` above JSON
+///
+/// Compares quality values only; on a tie, or when neither is listed, JSON wins.
+pub fn prefers(accept: &str, media_types: &[&str]) -> bool {
+    let quality = |media_types: &[&str]| {
+        accept
+            .split(',')
+            .filter_map(|item| {
+                let mut parts = item.split(';').map(str::trim);
+                let media_type = parts.next()?;
+                if !media_types
+                    .iter()
+                    .any(|candidate| candidate.eq_ignore_ascii_case(media_type))
+                {
+                    return None;
+                }
+                let q = parts
+                    .find_map(|param| param.strip_prefix("q="))
+                    .map_or(Some(1.0), |q| q.parse::<f32>().ok())?;
+                Some(q)
+            })
+            .fold(0.0_f32, f32::max)
+    };
+
+    quality(media_types) > quality(&["application/json"])
+}
+
+/// Whether a request's `Content-Type` is one of `media_types`, parameters aside
+fn has_content_type(headers: &HeaderMap, media_types: &[&str]) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|media_type| {
+            media_types
+                .iter()
+                .any(|candidate| candidate.eq_ignore_ascii_case(media_type.trim()))
+        })
+}
+
+/// The response format a request's `Accept` header asks for
+///
+/// MessagePack when the header ranks it above JSON, JSON otherwise, including for media
+/// types the service cannot produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Accepts(pub BodyFormat);
+
+impl<S: Send + Sync> FromRequestParts<S> for Accepts {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let accept = parts
+            .headers
+            .get(header::ACCEPT)
+            .and_then(|accept| accept.to_str().ok())
+            .unwrap_or_default();
+        Ok(Self(if prefers(accept, &MSGPACK_MEDIA_TYPES) {
+            BodyFormat::MessagePack
+        } else {
+            BodyFormat::Json
+        }))
+    }
+}
+
+/// Header of the preferences a client asks the server to apply (RFC 7240)
+pub const PREFER_HEADER: &str = "prefer";
+
+/// Header confirming which `Prefer` preferences the response honours
+pub const PREFERENCE_APPLIED_HEADER: &str = "preference-applied";
+
+/// Whether a `Prefer` header asks for `return=verbose`
+///
+/// Preferences are comma-separated and may carry parameters after `;`; the value may be
+/// quoted and is compared ignoring case. Preferences the service does not know are ignored.
+fn prefers_verbose(prefer: &str) -> bool {
+    prefer.split(',').any(|preference| {
+        let preference = preference.split(';').next().unwrap_or_default();
+        let Some((name, value)) = preference.split_once('=') else {
+            return false;
+        };
+        name.trim().eq_ignore_ascii_case("return")
+            && value
+                .trim()
+                .trim_matches('"')
+                .eq_ignore_ascii_case("verbose")
+    })
+}
+
+/// Whether the client asked for the verbose representation with `Prefer: return=verbose`
+///
+/// Verbose responses add details clients do not need by default, such as the warnings of
+/// a write, and confirm it with `Preference-Applied: return=verbose`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PreferVerbose(pub bool);
+
+impl<S: Send + Sync> FromRequestParts<S> for PreferVerbose {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self(
+            parts
+                .headers
+                .get_all(PREFER_HEADER)
+                .iter()
+                .filter_map(|prefer| prefer.to_str().ok())
+                .any(prefers_verbose),
+        ))
+    }
+}
+
+/// Response body written in the format the client accepts, sent with `Vary: Accept`
+///
+/// MessagePack bodies encode structs as maps and ids and timestamps as strings, so they
+/// carry the same fields and values as JSON.
+#[derive(Debug)]
+pub struct Negotiated<T> {
+    pub format: BodyFormat,
+    pub body: T,
+}
+
+impl<T> Negotiated<T> {
+    pub fn new(Accepts(format): Accepts, body: T) -> Self {
+        Self { format, body }
+    }
+}
+
+impl<T: Serialize> IntoResponse for Negotiated<T> {
+    fn into_response(self) -> Response {
+        let vary = [(header::VARY, header::ACCEPT.as_str())];
+        match self.format {
+            BodyFormat::Json => (vary, Json(self.body)).into_response(),
+            BodyFormat::MessagePack => match encode_msgpack(&self.body) {
+                Ok(bytes) => (
+                    vary,
+                    [(header::CONTENT_TYPE, self.format.media_type())],
+                    bytes,
+                )
+                    .into_response(),
+                Err(error) => {
+                    tracing::error!(
+                        error_type = "MessagePackEncode",
+                        error_message = %error,
+                        "Failed to encode the response as MessagePack"
+                    );
+                    ApiErrorResponse::from(ErrorCode::InternalServerError).into_response()
+                }
+            },
+        }
+    }
+}
+
+/// MessagePack encoding of `body` in the shape of its JSON encoding
+fn encode_msgpack<T: Serialize>(body: &T) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+    let mut bytes = Vec::new();
+    body.serialize(
+        &mut rmp_serde::Serializer::new(&mut bytes)
+            .with_struct_map()
+            .with_human_readable(),
+    )?;
+    Ok(bytes)
+}
+
+/// `T` decoded from MessagePack in the shape of its JSON encoding
+fn decode_msgpack<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, ApiErrorResponse> {
+    T::deserialize(&mut rmp_serde::Deserializer::new(bytes).with_human_readable())
+        .map_err(msgpack_rejection)
+}
+
+/// Request body extractor reading MessagePack when `Content-Type` names it, JSON otherwise
+///
+/// JSON bodies go through [`AppJson`], so other content types are still rejected with
+/// `UnsupportedMediaType`. MessagePack bodies that do not decode are `BadRequest`, and
+/// those that do not match `T` are `UnprocessableEntity`, as for JSON; `strict_json`
+/// applies to both.
+#[derive(Debug)]
+pub struct AppBody<T>(pub T);
+
+impl<T: DeserializeOwned> FromRequest<Arc<AppState>> for AppBody<T> {
+    type Rejection = ApiErrorResponse;
+
+    async fn from_request(
+        request: Request,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        if !has_content_type(request.headers(), &MSGPACK_MEDIA_TYPES) {
+            let AppJson(value) = AppJson::from_request(request, state).await?;
+            return Ok(Self(value));
+        }
+
+        let bytes = Bytes::from_request(request, state)
+            .await
+            .map_err(|rejection| {
+                tracing::warn!(
+                    error_type = "BytesRejection",
+                    error_message = %rejection.body_text(),
+                    "Rejected request body"
+                );
+                ApiErrorResponse::with_message(ErrorCode::BadRequest, rejection.body_text())
+            })?;
+        if state.env.strict_json {
+            return deserialize_strict(decode_msgpack(&bytes)?).map(Self);
+        }
+        decode_msgpack(&bytes).map(Self)
+    }
+}
+
+/// `UnprocessableEntity` for MessagePack that does not match the request type, and
+/// `BadRequest` for bytes that are not MessagePack at all
+fn msgpack_rejection(error: rmp_serde::decode::Error) -> ApiErrorResponse {
+    tracing::warn!(
+        error_type = "MessagePackRejection",
+        error_message = %error,
+        "Rejected request body"
+    );
+    match error {
+        rmp_serde::decode::Error::Syntax(_) => {
+            ApiErrorResponse::from(ErrorCode::UnprocessableEntity)
+        }
+        _ => ApiErrorResponse::from(ErrorCode::BadRequest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_prefers_msgpack_follows_accept_quality() {
+        for (accept, expected) in [
+            ("", false),
+            ("*/*", false),
+            ("application/json", false),
+            ("application/msgpack", true),
+            ("Application/X-MsgPack", true),
+            ("application/json, application/msgpack", false),
+            ("application/json;q=0.5, application/msgpack", true),
+            ("application/msgpack;q=0", false),
+        ] {
+            assert_eq!(
+                prefers(accept, &MSGPACK_MEDIA_TYPES),
+                expected,
+                "Accept: {accept}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_prefers_verbose_reads_the_return_preference() {
+        for (prefer, expected) in [
+            ("", false),
+            ("return=verbose", true),
+            ("Return=\"Verbose\"", true),
+            ("respond-async, wait=10, return=verbose", true),
+            ("return=minimal", false),
+            ("return=representation", false),
+            ("verbose", false),
+        ] {
+            assert_eq!(prefers_verbose(prefer), expected, "Prefer: {prefer}");
+        }
+    }
+
+    #[test]
+    fn test_content_type_ignores_parameters_and_case() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("Application/MsgPack; charset=binary"),
+        );
+
+        assert!(has_content_type(&headers, &MSGPACK_MEDIA_TYPES));
+        assert!(!has_content_type(&HeaderMap::new(), &MSGPACK_MEDIA_TYPES));
+    }
+}
@@ -1,21 +1,63 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
     Json,
 };
+use chrono::Utc;
 use std::sync::Arc;
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    Stream, StreamExt,
+};
+use uuid::Uuid;
 
+// <feature:auth>
+use crate::domain::task::operations::get_task;
+// </feature:auth>
+// <feature:swagger>
+use crate::api::tenant::TenantHeader;
+// </feature:swagger>
 use crate::{
     api::{
-        error::{ApiErrorResponse, ErrorCode},
-        models::tasks::{CreateTaskRequest, ListTasksQuery, TaskResponse},
+        // <feature:auth>
+        auth::JwtExtractor,
+        // </feature:auth>
+        caching::{ETag, Tagged},
+        error::ApiErrorResponse,
+        extractors::{AppJson, AppPath, AppQuery, PathParam},
+        models::{
+            // <feature:swagger>
+            examples,
+            // </feature:swagger>
+            page::Page,
+            tasks::{
+                ChangePriorityRequest, CreateTaskRequest, GetTaskQuery, ListTasksQuery,
+                TaskChangeResponse, TaskIdParam, TaskPageQuery, TaskResponse, TaskStatsQuery,
+                TaskStatsResponse,
+            },
+        },
+        negotiation::{Accepts, AppBody, BodyFormat, Negotiated, PreferVerbose},
+        tenant::TenantExtractor,
+        usage::quota_warning_headers,
+        warnings::warning_headers,
     },
-    common::UserId,
-    config::AppState,
-    domain::task::{
-        models::Task,
-        operations::{create_task, get_task, list_tasks_by_user},
+    common::{TenantId, UserId},
+    config::{AppState, UserIdMode},
+    domain::{
+        errors::DomainError,
+        quota::{models::QuotaWarning, operations::check_task_quotas},
+        task::{
+            models::{ChangedPriority, StatsRange, Task, TaskEvent, TaskId},
+            operations::{
+                change_priority, create_task, create_task_within_quotas, find_task,
+                list_tasks_by_user, search_tasks, task_stats,
+            },
+        },
+        user::operations::ensure_user_exists,
+        warnings::DomainWarning,
     },
+    infrastructure::error_reporting::current_request_id,
 };
 
 #[utoipa::path(
@@ -23,85 +65,537 @@ use crate::{
     path = "/tasks/{id}",
     tag = "tasks",
     params(
-        ("id" = String, Path, description = "Task ID")
+        ("id" = String, Path, description = "Task ID, or `latest` for the newest task of `user_id`"),
+        GetTaskQuery,
+        ("If-None-Match" = Option<String>, Header, description = "`ETag` of a copy the client already has"),
+        TenantHeader
     ),
     responses(
-        (status = 200, description = "Task found", body = TaskResponse),
-        (status = 404, description = "Task not found", body = ApiErrorResponse),
-        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+        (status = 200, description = "Task found, as MessagePack when `Accept` prefers it", content(
+            (TaskResponse = "application/json", example = json!(examples::task())),
+            (TaskResponse = "application/msgpack")
+        ), headers(("ETag" = String, description = "Weak tag of the task, changed by every write"))),
+        (status = 304, description = "Task unchanged since the `If-None-Match` tag"),
+        (status = 400, description = "Task ID is neither a UUID nor `latest`, or `latest` lacks `user_id`", body = ApiErrorResponse,
+            example = json!(examples::invalid_task_id_error())),
+        (status = 404, description = "Task not found, or the user of `latest` has no tasks", body = ApiErrorResponse,
+            example = json!(examples::not_found_error())),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+        (status = 504, description = "Database query timed out", body = ApiErrorResponse)
     )
 )]
+#[tracing::instrument(skip_all, fields(task_id = %id))]
 pub async fn get_task_handler(
-    Path(id): Path<String>,
+    TenantExtractor(tenant): TenantExtractor,
+    accepts: Accepts,
+    AppPath(PathParam(id)): AppPath<PathParam<TaskIdParam>>,
+    AppQuery(query): AppQuery<GetTaskQuery>,
     State(state): State<Arc<AppState>>,
-) -> Result<Json<TaskResponse>, ApiErrorResponse> {
-    let task_id =
-        uuid::Uuid::parse_str(&id).map_err(|_| ApiErrorResponse::from(ErrorCode::BadRequest))?;
-
-    let task = get_task(task_id.into(), state.task_repository.clone())
+) -> Result<Tagged<Negotiated<TaskResponse>>, ApiErrorResponse> {
+    let task_ref = id.resolve(query.user_id)?;
+    let task: TaskResponse = find_task(tenant, task_ref, state.task_repository.clone())
         .await
-        .map_err(ApiErrorResponse::from)?;
+        .map_err(ApiErrorResponse::from)?
+        .into();
+
+    Ok(Tagged::new(
+        task_etag(&task, accepts.0),
+        Negotiated::new(accepts, task),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/tasks",
+    tag = "tasks",
+    params(
+        ("user_id" = Uuid, Path, description = "Owner of the tasks to list"),
+        ("If-None-Match" = Option<String>, Header, description = "`ETag` of a copy the client already has"),
+        TenantHeader
+    ),
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "List of tasks, as MessagePack when `Accept` prefers it", content(
+            (Vec<TaskResponse> = "application/json", example = json!(examples::task_list())),
+            (Vec<TaskResponse> = "application/msgpack")
+        ), headers(("ETag" = String, description = "Weak tag of the list, changed when a task is added, removed or written"))),
+        (status = 304, description = "List unchanged since the `If-None-Match` tag"),
+        (status = 400, description = "User ID is not a valid UUID", body = ApiErrorResponse),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Token is for another user and lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+        (status = 504, description = "Database query timed out", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(user_id = %user_id))]
+pub async fn list_user_tasks_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    TenantExtractor(tenant): TenantExtractor,
+    accepts: Accepts,
+    AppPath(PathParam(user_id)): AppPath<PathParam<UserId>>,
+    State(state): State<Arc<AppState>>,
+) -> Result<Tagged<Negotiated<Vec<TaskResponse>>>, ApiErrorResponse> {
+    // <feature:auth>
+    claims.authorize_user(user_id.into_inner())?;
+    // </feature:auth>
+
+    let tasks = list_tasks(tenant, user_id, &state).await?;
+
+    Ok(Tagged::new(
+        task_list_etag(&tasks, accepts.0),
+        Negotiated::new(accepts, tasks),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{user_id}/tasks",
+    tag = "tasks",
+    params(
+        ("user_id" = Uuid, Path, description = "Owner of the tasks to list"),
+        TaskPageQuery,
+        TenantHeader
+    ),
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "A page of the user's tasks, newest first", body = Page<TaskResponse>,
+            example = json!(examples::task_page())),
+        (status = 400, description = "User ID is not a valid UUID, malformed filter values, or limit outside 1 to 100", body = ApiErrorResponse,
+            example = json!(examples::validation_error())),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Token is for another user and lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+        (status = 504, description = "Database query timed out", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(user_id = %user_id))]
+pub async fn list_user_task_page_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    TenantExtractor(tenant): TenantExtractor,
+    AppPath(PathParam(user_id)): AppPath<PathParam<UserId>>,
+    AppQuery(query): AppQuery<TaskPageQuery>,
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Page<TaskResponse>>, ApiErrorResponse> {
+    // <feature:auth>
+    claims.authorize_user(user_id.into_inner())?;
+    // </feature:auth>
+    let search = query.search(user_id)?;
+
+    let page = search_tasks(tenant, &search, state.task_repository.clone()).await?;
+
+    Ok(Json(Page::from_search(page, &search)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/stats",
+    tag = "tasks",
+    params(
+        ("user_id" = Uuid, Path, description = "Owner of the tasks to count"),
+        TaskStatsQuery,
+        TenantHeader
+    ),
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "Tasks created and completed per day", body = TaskStatsResponse),
+        (status = 400, description = "Malformed dates, `from` after `to`, a range over 366 days or an unknown time zone", body = ApiErrorResponse),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Token is for another user and lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+        (status = 504, description = "Database query timed out", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(user_id = %user_id))]
+pub async fn task_stats_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    TenantExtractor(tenant): TenantExtractor,
+    AppPath(PathParam(user_id)): AppPath<PathParam<UserId>>,
+    AppQuery(query): AppQuery<TaskStatsQuery>,
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<TaskStatsResponse>, ApiErrorResponse> {
+    // <feature:auth>
+    claims.authorize_user(user_id.into_inner())?;
+    // </feature:auth>
+    let range = StatsRange::new(query.from, query.to, query.tz.as_deref())?;
+
+    let stats = task_stats(tenant, user_id, range, state.task_repository.clone()).await?;
 
-    Ok(Json(task.into()))
+    Ok(Json(stats.into()))
 }
 
+/// Deprecated in favor of `GET /users/{user_id}/tasks`; served while `legacy_routes` is on
 #[utoipa::path(
     get,
     path = "/tasks",
     tag = "tasks",
-    params(ListTasksQuery),
+    params(
+        ListTasksQuery,
+        ("If-None-Match" = Option<String>, Header, description = "`ETag` of a copy the client already has"),
+        TenantHeader
+    ),
     responses(
-        (status = 200, description = "List of tasks", body = Vec<TaskResponse>),
-        (status = 400, description = "Invalid request", body = ApiErrorResponse),
-        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+        (status = 200, description = "List of tasks, as MessagePack when `Accept` prefers it", content(
+            (Vec<TaskResponse> = "application/json", example = json!(examples::task_list())),
+            (Vec<TaskResponse> = "application/msgpack")
+        ), headers(("ETag" = String, description = "Weak tag of the list, changed when a task is added, removed or written"))),
+        (status = 304, description = "List unchanged since the `If-None-Match` tag"),
+        (status = 400, description = "Missing or malformed user_id", body = ApiErrorResponse,
+            example = json!(examples::missing_user_id_error())),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+        (status = 504, description = "Database query timed out", body = ApiErrorResponse)
     )
 )]
+#[tracing::instrument(skip_all, fields(user_id = tracing::field::Empty))]
 pub async fn list_tasks_handler(
-    Query(query): Query<ListTasksQuery>,
+    TenantExtractor(tenant): TenantExtractor,
+    accepts: Accepts,
+    AppQuery(query): AppQuery<ListTasksQuery>,
     State(state): State<Arc<AppState>>,
-) -> Result<Json<Vec<TaskResponse>>, ApiErrorResponse> {
-    let user_id = query
-        .user_id
-        .ok_or_else(|| ApiErrorResponse::from(ErrorCode::BadRequest))?;
+) -> Result<Tagged<Negotiated<Vec<TaskResponse>>>, ApiErrorResponse> {
+    let user_id = query.user_id;
+    tracing::Span::current().record("user_id", tracing::field::display(user_id));
+    let tasks = list_tasks(tenant, user_id, &state).await?;
 
-    let user_id = uuid::Uuid::parse_str(&user_id)
-        .map_err(|_| ApiErrorResponse::from(ErrorCode::BadRequest))?;
+    Ok(Tagged::new(
+        task_list_etag(&tasks, accepts.0),
+        Negotiated::new(accepts, tasks),
+    ))
+}
 
-    let tasks = list_tasks_by_user(user_id.into(), state.task_repository.clone())
+/// The tasks of `user_id` in `tenant`, shared by the nested and the legacy listing routes
+async fn list_tasks(
+    tenant: TenantId,
+    user_id: UserId,
+    state: &AppState,
+) -> Result<Vec<TaskResponse>, ApiErrorResponse> {
+    let tasks = list_tasks_by_user(tenant, user_id, state.task_repository.clone())
         .await
         .map_err(ApiErrorResponse::from)?;
 
-    Ok(Json(tasks.into_iter().map(|t: Task| t.into()).collect()))
+    Ok(tasks.into_iter().map(|t: Task| t.into()).collect())
 }
 
 #[utoipa::path(
     post,
     path = "/tasks",
     tag = "tasks",
-    request_body = CreateTaskRequest,
+    params(
+        TenantHeader,
+        ("Prefer" = Option<String>, Header, description = "`return=verbose` to list the warnings of the write in the body")
+    ),
+    request_body(content(
+        (CreateTaskRequest = "application/json"),
+        (CreateTaskRequest = "application/msgpack")
+    )),
     responses(
-        (status = 201, description = "Task created", body = TaskResponse),
-        (status = 400, description = "Invalid request", body = ApiErrorResponse),
-        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+        (status = 201, description = "Task created, as MessagePack when `Accept` prefers it", content(
+            (TaskResponse = "application/json", example = json!(examples::task())),
+            (TaskResponse = "application/msgpack")
+        ), headers(
+            ("X-Quota-Warning" = String, description = "Soft quotas the new task goes past, when it does"),
+            ("X-Warnings" = String, description = "Codes of the warnings of the write, e.g. `PossibleDuplicateTitle`, when there are any"),
+            ("Preference-Applied" = String, description = "`return=verbose` when the body is the verbose representation, listing `warnings`")
+        )),
+        (status = 400, description = "Invalid request, or `user_id` missing while user ids are registered", body = ApiErrorResponse,
+            example = json!(examples::validation_error())),
+        (status = 403, description = "The task would go past a hard quota of the user", body = ApiErrorResponse,
+            example = json!(examples::quota_exceeded_error())),
+        (status = 404, description = "No registered user has `user_id`", body = ApiErrorResponse,
+            example = json!(examples::not_found_error())),
+        (status = 409, description = "Task already exists", body = ApiErrorResponse),
+        (status = 415, description = "Content type is neither JSON nor MessagePack", body = ApiErrorResponse),
+        (status = 422, description = "Request body does not match the schema", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+        (status = 504, description = "Database query timed out", body = ApiErrorResponse)
     )
 )]
+#[tracing::instrument(skip_all, fields(user_id = tracing::field::Empty))]
 pub async fn create_task_handler(
+    TenantExtractor(tenant): TenantExtractor,
+    accepts: Accepts,
+    verbose: PreferVerbose,
     State(state): State<Arc<AppState>>,
-    Json(request): Json<CreateTaskRequest>,
-) -> Result<(StatusCode, Json<TaskResponse>), ApiErrorResponse> {
-    let user_id = UserId::new();
+    AppBody(request): AppBody<CreateTaskRequest>,
+) -> Result<(StatusCode, HeaderMap, Negotiated<TaskResponse>), ApiErrorResponse> {
+    let user_id = task_owner(request.user_id, &state).await?;
+    tracing::Span::current().record("user_id", tracing::field::display(user_id));
 
     let task = Task::new(
+        tenant,
         user_id,
         request.title,
         request.description,
         request.priority.unwrap_or_default(),
     )
     .map_err(ApiErrorResponse::from)?;
+    let (created, warnings, quota_warnings) = create_task_checking_quotas(task, &state).await?;
+    if state.env.quota_config.limits_tasks() {
+        state
+            .usage_cache
+            .record_created(tenant, user_id, created.created_at)
+            .await;
+    }
 
-    let created = create_task(task, state.task_repository.clone())
-        .await
-        .map_err(ApiErrorResponse::from)?;
+    let mut headers = quota_warning_headers(&quota_warnings);
+    headers.extend(warning_headers(&warnings, verbose));
+    let mut response = TaskResponse::from(created);
+    if verbose.0 {
+        response = response.with_warnings(&warnings);
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        headers,
+        Negotiated::new(accepts, response),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/tasks/{id}/priority",
+    tag = "tasks",
+    params(
+        ("id" = Uuid, Path, description = "Task whose priority to change"),
+        TenantHeader
+    ),
+    request_body = ChangePriorityRequest,
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "Priority changed and recorded in the task's audit trail", body = TaskResponse,
+            example = json!(examples::task())),
+        (status = 400, description = "The task already has this priority, escalation to `Critical` without a reason, a reason over 500 characters, or `changed_by` missing", body = ApiErrorResponse, examples(
+            ("Unchanged priority" = (value = json!(examples::unchanged_priority_error()))),
+            ("Missing reason" = (value = json!(examples::missing_reason_error())))
+        )),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Task belongs to another user, or `changed_by` names one, and the token lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 404, description = "Task not found", body = ApiErrorResponse,
+            example = json!(examples::not_found_error())),
+        (status = 415, description = "Missing JSON content type", body = ApiErrorResponse),
+        (status = 422, description = "Request body does not match the schema", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+        (status = 504, description = "Database query timed out", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(task_id = %id, changed_by = tracing::field::Empty))]
+pub async fn change_priority_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    TenantExtractor(tenant): TenantExtractor,
+    AppPath(PathParam(id)): AppPath<PathParam<TaskId>>,
+    State(state): State<Arc<AppState>>,
+    AppJson(request): AppJson<ChangePriorityRequest>,
+) -> Result<Json<TaskResponse>, ApiErrorResponse> {
+    let changed_by = request.changed_by;
+    // <feature:auth>
+    let task = get_task(tenant, id, state.task_repository.clone()).await?;
+    claims.authorize_user(task.user_id.into_inner())?;
+    let changed_by = match changed_by {
+        Some(changed_by) => {
+            claims.authorize_user(changed_by.into_inner())?;
+            Some(changed_by)
+        }
+        None => claims.subject_user_id(),
+    };
+    // </feature:auth>
+    let changed_by = changed_by.ok_or_else(|| {
+        DomainError::field_validation_error("changed_by", "changed_by is required")
+    })?;
+    tracing::Span::current().record("changed_by", tracing::field::display(changed_by));
+
+    let ChangedPriority {
+        before,
+        after,
+        change,
+    } = change_priority(
+        tenant,
+        id,
+        request.priority,
+        request.reason,
+        changed_by,
+        state.task_repository.clone(),
+    )
+    .await?;
+
+    let event = TaskEvent::new_updated(
+        (&after).into(),
+        (&before).into(),
+        current_request_id().unwrap_or_else(|| Uuid::new_v4().to_string()),
+    )
+    .with_reason(change.reason);
+    // The change is committed; a sink that is down must not fail the request
+    if let Err(error) = state.event_producer.publish_task_event(event).await {
+        tracing::warn!(error = %error, "Failed to publish the priority change");
+    }
+
+    Ok(Json(after.into()))
+}
+
+/// Weak tag of a task in `format`; `updated_at` changes with every write
+fn task_etag(task: &TaskResponse, format: BodyFormat) -> ETag {
+    ETag::builder()
+        .part(format.media_type())
+        .part(&task.id)
+        .part(task.updated_at.to_rfc3339())
+        .weak()
+}
+
+/// Weak tag of a task list in `format`, from its ids and latest `updated_at`
+fn task_list_etag(tasks: &[TaskResponse], format: BodyFormat) -> ETag {
+    let latest = tasks.iter().map(|task| task.updated_at).max();
+    tasks
+        .iter()
+        .fold(ETag::builder().part(format.media_type()), |etag, task| {
+            etag.part(&task.id)
+        })
+        .part(latest.map(|latest| latest.to_rfc3339()).unwrap_or_default())
+        .weak()
+}
+
+/// Create `task`, with the soft quotas its user goes past, failing past a hard one
+///
+/// Hard quotas are checked against a count taken under the user's quota lock, in the unit of
+/// work creating the task; soft quotas alone are checked against the usage cache. Nothing is
+/// checked while the `enforce_quotas` feature is off.
+async fn create_task_checking_quotas(
+    task: Task,
+    state: &AppState,
+) -> Result<(Task, Vec<DomainWarning>, Vec<QuotaWarning>), DomainError> {
+    let quotas = &state.env.quota_config;
+    let features = state.feature_flags.as_ref();
+    let repo = state.task_repository.clone();
+    if !quotas.limits_tasks() || !features.enforces_quotas() {
+        let (created, warnings) = create_task(task, features, repo).await?;
+        return Ok((created, warnings, Vec::new()));
+    }
+    if quotas.hard_limits_tasks() {
+        return create_task_within_quotas(
+            task,
+            quotas.open_tasks,
+            quotas.monthly_tasks,
+            features,
+            repo,
+        )
+        .await;
+    }
+    let usage = state
+        .usage_cache
+        .task_usage(task.tenant_id, task.user_id, Utc::now(), repo.as_ref())
+        .await?;
+    let quota_warnings = check_task_quotas(usage, quotas.open_tasks, quotas.monthly_tasks)?;
+    let (created, warnings) = create_task(task, features, repo).await?;
+    Ok((created, warnings, quota_warnings))
+}
+
+/// The user a new task belongs to, checked against the registered users unless user ids
+/// are external
+async fn task_owner(user_id: Option<UserId>, state: &AppState) -> Result<UserId, DomainError> {
+    match (state.env.user_ids, user_id) {
+        (UserIdMode::Registered, Some(user_id)) => {
+            ensure_user_exists(user_id, state.user_repository.clone()).await?;
+            Ok(user_id)
+        }
+        (UserIdMode::Registered, None) => Err(DomainError::field_validation_error(
+            "user_id",
+            "user_id is required",
+        )),
+        (UserIdMode::External, user_id) => Ok(user_id.unwrap_or_default()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/tasks/stream",
+    tag = "tasks",
+    params(ListTasksQuery, TenantHeader),
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "Server-Sent Events: a `task_change` event per committed change to the user's tasks, and `resync` when changes were missed and the client should refetch", content_type = "text/event-stream", body = TaskChangeResponse),
+        (status = 400, description = "Missing or malformed user_id", body = ApiErrorResponse,
+            example = json!(examples::missing_user_id_error())),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Token is for another user and lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error()))
+        // </feature:auth>
+    )
+)]
+#[tracing::instrument(skip_all, fields(user_id = tracing::field::Empty))]
+pub async fn stream_task_changes_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    TenantExtractor(tenant): TenantExtractor,
+    AppQuery(query): AppQuery<ListTasksQuery>,
+    State(state): State<Arc<AppState>>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ApiErrorResponse> {
+    let user_id = query.user_id;
+    tracing::Span::current().record("user_id", tracing::field::display(user_id));
+    // <feature:auth>
+    claims.authorize_user(user_id.into_inner())?;
+    // </feature:auth>
+
+    let changes =
+        BroadcastStream::new(state.task_changes.subscribe()).filter_map(
+            move |change| match change {
+                Ok(change) if change.tenant_id == tenant && change.user_id == user_id => Some(
+                    Event::default()
+                        .event("task_change")
+                        .json_data(TaskChangeResponse::from(change)),
+                ),
+                Ok(_) => None,
+                Err(BroadcastStreamRecvError::Lagged(missed)) => {
+                    tracing::warn!(%user_id, missed, "Task change stream fell behind");
+                    Some(Ok(Event::default()
+                        .event("resync")
+                        .data(missed.to_string())))
+                }
+            },
+        );
 
-    Ok((StatusCode::CREATED, Json(created.into())))
+    Ok(Sse::new(changes).keep_alive(KeepAlive::default()))
 }
@@ -0,0 +1,119 @@
+use axum::http::request::Parts;
+// <feature:auth>
+use axum::RequestPartsExt;
+use axum_extra::{
+    headers::{authorization::Bearer, Authorization},
+    TypedHeader,
+};
+// </feature:auth>
+use std::sync::Arc;
+use uuid::Uuid;
+
+// <feature:auth>
+use crate::api::auth::extract_jwt_claims;
+// </feature:auth>
+use crate::{
+    api::error::{ApiErrorResponse, ErrorCode},
+    common::TenantId,
+    config::{AppState, TenancyMode},
+};
+
+/// Header naming the tenant a request acts for in multi-tenant mode
+pub const TENANT_HEADER: &str = "x-tenant-id";
+
+// <feature:swagger>
+/// The `X-Tenant-Id` header, as documented on every tenant-scoped operation
+#[derive(utoipa::IntoParams)]
+#[into_params(parameter_in = Header)]
+pub struct TenantHeader {
+    /// Tenant the request acts for; required in multi-tenant mode for requests without a
+    /// bearer token, ignored in single-tenant mode
+    #[param(rename = "X-Tenant-Id")]
+    pub tenant_id: Option<Uuid>,
+}
+// </feature:swagger>
+
+/// The tenant a request acts for, to be passed to every repository call
+///
+/// In single-tenant mode this is always [`TenantId::DEFAULT`], whatever the request says.
+/// In multi-tenant mode a bearer token must carry a `tenant_id` claim, and an `X-Tenant-Id`
+/// header naming another tenant is forbidden; only requests without a token, such as
+/// service-internal calls, name their tenant with the header alone.
+#[derive(Debug, Clone, Copy)]
+pub struct TenantExtractor(pub TenantId);
+
+impl axum::extract::FromRequestParts<Arc<AppState>> for TenantExtractor {
+    type Rejection = ApiErrorResponse;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        if state.env.tenancy == TenancyMode::Single {
+            return Ok(Self(TenantId::DEFAULT));
+        }
+
+        let header = tenant_header(parts)?;
+        // <feature:auth>
+        if let Some(claimed) = tenant_claim(parts, state).await? {
+            if header.is_some_and(|header| header != claimed) {
+                tracing::warn!(
+                    %claimed,
+                    "{TENANT_HEADER} header names another tenant than the token"
+                );
+                return Err(ApiErrorResponse::from(ErrorCode::Forbidden));
+            }
+            return Ok(Self(claimed));
+        }
+        // </feature:auth>
+
+        header.map(Self).ok_or_else(|| {
+            ApiErrorResponse::with_message(
+                ErrorCode::BadRequest,
+                format!("{TENANT_HEADER} header is required"),
+            )
+        })
+    }
+}
+
+/// The tenant named by the `X-Tenant-Id` header, if the request has one
+fn tenant_header(parts: &Parts) -> Result<Option<TenantId>, ApiErrorResponse> {
+    let Some(value) = parts.headers.get(TENANT_HEADER) else {
+        return Ok(None);
+    };
+    value
+        .to_str()
+        .ok()
+        .and_then(|value| Uuid::parse_str(value.trim()).ok())
+        .map(|uuid| Some(TenantId::from(uuid)))
+        .ok_or_else(|| {
+            ApiErrorResponse::with_message(
+                ErrorCode::BadRequest,
+                format!("{TENANT_HEADER} header must be a UUID"),
+            )
+        })
+}
+
+// <feature:auth>
+/// The `tenant_id` claim of the request's bearer token
+///
+/// Requests without a bearer token have no claim. A token that does not validate, or
+/// carries no claim, is rejected rather than ignored, so it cannot fall back to a header
+/// of the caller's choice.
+async fn tenant_claim(
+    parts: &mut Parts,
+    state: &AppState,
+) -> Result<Option<TenantId>, ApiErrorResponse> {
+    let Ok(TypedHeader(Authorization(bearer))) =
+        parts.extract::<TypedHeader<Authorization<Bearer>>>().await
+    else {
+        return Ok(None);
+    };
+    let claims = extract_jwt_claims(bearer.token(), &state.env.jwt_secret)?;
+    let Some(tenant_id) = claims.tenant_id else {
+        tracing::warn!("Bearer token has no tenant_id claim in multi-tenant mode");
+        return Err(ApiErrorResponse::from(ErrorCode::Forbidden));
+    };
+    Ok(Some(TenantId::from(tenant_id)))
+}
+// </feature:auth>
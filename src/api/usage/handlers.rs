@@ -0,0 +1,81 @@
+use axum::{extract::State, Json};
+use chrono::Utc;
+use std::sync::Arc;
+
+// <feature:swagger>
+use crate::api::tenant::TenantHeader;
+// </feature:swagger>
+use crate::{
+    api::{
+        // <feature:auth>
+        auth::JwtExtractor,
+        // </feature:auth>
+        error::{ApiErrorResponse, ErrorCode},
+        extractors::AppQuery,
+        models::{
+            // <feature:swagger>
+            examples,
+            // </feature:swagger>
+            usage::{UsageQuery, UsageResponse},
+        },
+        tenant::TenantExtractor,
+    },
+    config::AppState,
+    domain::quota::{models::Usage, operations::count_webhooks},
+};
+
+#[utoipa::path(
+    get,
+    path = "/usage",
+    tag = "usage",
+    params(UsageQuery, TenantHeader),
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "The user's usage of every quota, with its limits", body = UsageResponse,
+            example = json!(examples::usage())),
+        (status = 400, description = "Missing or malformed user_id", body = ApiErrorResponse),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Token is for another user and lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 500, description = "Internal server error", body = ApiErrorResponse),
+        (status = 504, description = "Database query timed out", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(user_id = tracing::field::Empty))]
+pub async fn usage_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    TenantExtractor(tenant): TenantExtractor,
+    AppQuery(query): AppQuery<UsageQuery>,
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<UsageResponse>, ApiErrorResponse> {
+    let user_id = query.user_id;
+    // <feature:auth>
+    let user_id = user_id.or_else(|| claims.subject_user_id());
+    // </feature:auth>
+    let user_id = user_id.ok_or_else(|| {
+        ApiErrorResponse::with_message(ErrorCode::BadRequest, "user_id is required")
+    })?;
+    // <feature:auth>
+    claims.authorize_user(user_id.into_inner())?;
+    // </feature:auth>
+    tracing::Span::current().record("user_id", tracing::field::display(user_id));
+
+    let usage = Usage {
+        tasks: state
+            .usage_cache
+            .task_usage(tenant, user_id, Utc::now(), state.task_repository.as_ref())
+            .await?,
+        webhooks: count_webhooks(tenant, state.webhook_repository.clone()).await?,
+    };
+
+    Ok(Json(UsageResponse::new(usage, &state.env.quota_config)))
+}
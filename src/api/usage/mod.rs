@@ -0,0 +1,38 @@
+pub mod handlers;
+
+use std::sync::Arc;
+
+use axum::{
+    http::{HeaderMap, HeaderValue},
+    routing::get,
+    Router,
+};
+
+use crate::{config::AppState, domain::quota::models::QuotaWarning};
+
+/// Header listing the soft quotas a successful write went past, e.g.
+/// `open_tasks; used=81; soft=80`, comma-separated when there are several
+pub const QUOTA_WARNING_HEADER: &str = "x-quota-warning";
+
+/// Routes of the usage API, merged into the application router
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new().route("/usage", get(handlers::usage_handler))
+}
+
+/// Response headers reporting `warnings`; empty when there are none
+pub fn quota_warning_headers(warnings: &[QuotaWarning]) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if warnings.is_empty() {
+        return headers;
+    }
+    let value = warnings
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    // Metric names and numbers are always valid header characters
+    if let Ok(value) = HeaderValue::from_str(&value) {
+        headers.insert(QUOTA_WARNING_HEADER, value);
+    }
+    headers
+}
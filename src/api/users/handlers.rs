@@ -0,0 +1,74 @@
+use axum::{extract::State, http::StatusCode, Json};
+use std::sync::Arc;
+
+use crate::{
+    api::{
+        error::ApiErrorResponse,
+        extractors::{AppJson, AppPath, PathParam},
+        models::{
+            // <feature:swagger>
+            examples,
+            // </feature:swagger>
+            users::{CreateUserRequest, UserResponse},
+        },
+    },
+    common::UserId,
+    config::AppState,
+    domain::user::{
+        models::User,
+        operations::{get_user, register_user},
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/users/{id}",
+    tag = "users",
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "User found", body = UserResponse),
+        (status = 400, description = "User ID is not a valid UUID", body = ApiErrorResponse),
+        (status = 404, description = "User not found", body = ApiErrorResponse,
+            example = json!(examples::not_found_error())),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(user_id = %id))]
+pub async fn get_user_handler(
+    AppPath(PathParam(id)): AppPath<PathParam<UserId>>,
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<UserResponse>, ApiErrorResponse> {
+    let user = get_user(id, state.user_repository.clone()).await?;
+
+    Ok(Json(user.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/users",
+    tag = "users",
+    request_body = CreateUserRequest,
+    responses(
+        (status = 201, description = "User registered", body = UserResponse),
+        (status = 400, description = "Invalid email or display name", body = ApiErrorResponse,
+            example = json!(examples::invalid_email_error())),
+        (status = 409, description = "Email already registered", body = ApiErrorResponse),
+        (status = 415, description = "Missing JSON content type", body = ApiErrorResponse),
+        (status = 422, description = "Request body does not match the schema", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(user_id = tracing::field::Empty))]
+pub async fn create_user_handler(
+    State(state): State<Arc<AppState>>,
+    AppJson(request): AppJson<CreateUserRequest>,
+) -> Result<(StatusCode, Json<UserResponse>), ApiErrorResponse> {
+    let user = User::new(request.email, request.display_name)?;
+    tracing::Span::current().record("user_id", tracing::field::display(user.id));
+
+    let registered = register_user(user, state.user_repository.clone()).await?;
+
+    Ok((StatusCode::CREATED, Json(registered.into())))
+}
@@ -0,0 +1,17 @@
+pub mod handlers;
+
+use std::sync::Arc;
+
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::config::AppState;
+
+/// Routes of the users API, merged into the application router
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/users", post(handlers::create_user_handler))
+        .route("/users/{id}", get(handlers::get_user_handler))
+}
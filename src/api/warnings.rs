@@ -0,0 +1,39 @@
+use axum::http::{HeaderMap, HeaderValue};
+
+use crate::{
+    api::negotiation::{PreferVerbose, PREFERENCE_APPLIED_HEADER},
+    domain::warnings::DomainWarning,
+};
+
+/// Header listing the codes of the warnings of a successful write, e.g.
+/// `PossibleDuplicateTitle`, comma-separated when there are several
+pub const WARNINGS_HEADER: &str = "x-warnings";
+
+/// Response headers reporting `warnings`, and confirming the verbose representation when
+/// the client asked for it; empty otherwise
+///
+/// The header carries codes only: the verbose body has the messages.
+pub fn warning_headers(
+    warnings: &[DomainWarning],
+    PreferVerbose(verbose): PreferVerbose,
+) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if !warnings.is_empty() {
+        let value = warnings
+            .iter()
+            .map(DomainWarning::code)
+            .collect::<Vec<_>>()
+            .join(", ");
+        // Warning codes are always valid header characters
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(WARNINGS_HEADER, value);
+        }
+    }
+    if verbose {
+        headers.insert(
+            PREFERENCE_APPLIED_HEADER,
+            HeaderValue::from_static("return=verbose"),
+        );
+    }
+    headers
+}
@@ -0,0 +1,342 @@
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use std::sync::Arc;
+
+// <feature:swagger>
+use crate::api::tenant::TenantHeader;
+// </feature:swagger>
+use crate::{
+    api::{
+        // <feature:auth>
+        auth::JwtExtractor,
+        // </feature:auth>
+        error::ApiErrorResponse,
+        extractors::{AppJson, AppPath, PathParam},
+        models::{
+            // <feature:swagger>
+            examples,
+            // </feature:swagger>
+            webhooks::{
+                CreateWebhookRequest, UpdateWebhookRequest, WebhookDeliveryResponse,
+                WebhookResponse,
+            },
+        },
+        tenant::TenantExtractor,
+        usage::quota_warning_headers,
+    },
+    config::AppState,
+    domain::{
+        quota::operations::check_webhook_quota,
+        webhook::{
+            models::{Webhook, WebhookId},
+            operations::{
+                create_webhook, delete_webhook, get_webhook, list_deliveries, list_webhooks,
+                update_webhook,
+            },
+        },
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = "webhooks",
+    params(TenantHeader),
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "Every webhook of the tenant, oldest first", body = Vec<WebhookResponse>),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Token lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub async fn list_webhooks_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    TenantExtractor(tenant): TenantExtractor,
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<WebhookResponse>>, ApiErrorResponse> {
+    // <feature:auth>
+    claims.authorize_admin()?;
+    // </feature:auth>
+
+    let webhooks = list_webhooks(tenant, state.webhook_repository.clone()).await?;
+
+    Ok(Json(webhooks.into_iter().map(Into::into).collect()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    tag = "webhooks",
+    params(TenantHeader),
+    request_body = CreateWebhookRequest,
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 201, description = "Webhook created; the response is the only one that shows the secret",
+            headers(("X-Quota-Warning" = String, description = "The webhook soft quota, when the new webhook goes past it")),
+            body = WebhookResponse),
+        (status = 400, description = "Invalid URL or secret", body = ApiErrorResponse,
+            example = json!(examples::invalid_webhook_url_error())),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        // </feature:auth>
+        (status = 403, description = "Token lacks the admin role, or the webhook would go past the hard quota", body = ApiErrorResponse, examples(
+            // <feature:auth>
+            ("Not an admin" = (value = json!(examples::forbidden_error()))),
+            // </feature:auth>
+            ("Quota exceeded" = (value = json!({"code": "QuotaExceeded", "message": "webhooks is limited to 20"})))
+        )),
+        (status = 415, description = "Missing JSON content type", body = ApiErrorResponse),
+        (status = 422, description = "Request body does not match the schema", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(webhook_id = tracing::field::Empty))]
+pub async fn create_webhook_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    TenantExtractor(tenant): TenantExtractor,
+    State(state): State<Arc<AppState>>,
+    AppJson(request): AppJson<CreateWebhookRequest>,
+) -> Result<(StatusCode, HeaderMap, Json<WebhookResponse>), ApiErrorResponse> {
+    // <feature:auth>
+    claims.authorize_admin()?;
+    // </feature:auth>
+
+    let webhook = Webhook::new(
+        tenant,
+        request.url,
+        request.secret,
+        request.event_types.unwrap_or_default(),
+    )?;
+    tracing::Span::current().record("webhook_id", tracing::field::display(webhook.id));
+    let warning = check_webhook_quota(
+        tenant,
+        state.env.quota_config.webhooks,
+        state.feature_flags.as_ref(),
+        state.webhook_repository.clone(),
+    )
+    .await?;
+
+    let created = create_webhook(webhook, state.webhook_repository.clone()).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        quota_warning_headers(warning.as_slice()),
+        Json(WebhookResponse::with_secret(created)),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(
+        ("id" = Uuid, Path, description = "Webhook ID"),
+        TenantHeader
+    ),
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "Webhook found", body = WebhookResponse),
+        (status = 400, description = "Webhook ID is not a valid UUID", body = ApiErrorResponse),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Token lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 404, description = "Webhook not found in the tenant", body = ApiErrorResponse,
+            example = json!(examples::not_found_error())),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(webhook_id = %id))]
+pub async fn get_webhook_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    TenantExtractor(tenant): TenantExtractor,
+    AppPath(PathParam(id)): AppPath<PathParam<WebhookId>>,
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<WebhookResponse>, ApiErrorResponse> {
+    // <feature:auth>
+    claims.authorize_admin()?;
+    // </feature:auth>
+
+    let webhook = get_webhook(tenant, id, state.webhook_repository.clone()).await?;
+
+    Ok(Json(webhook.into()))
+}
+
+#[utoipa::path(
+    put,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(
+        ("id" = Uuid, Path, description = "Webhook ID"),
+        TenantHeader
+    ),
+    request_body = UpdateWebhookRequest,
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "Webhook updated; the secret is kept", body = WebhookResponse),
+        (status = 400, description = "Invalid webhook ID or URL", body = ApiErrorResponse,
+            example = json!(examples::invalid_webhook_url_error())),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Token lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 404, description = "Webhook not found in the tenant", body = ApiErrorResponse,
+            example = json!(examples::not_found_error())),
+        (status = 415, description = "Missing JSON content type", body = ApiErrorResponse),
+        (status = 422, description = "Request body does not match the schema", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(webhook_id = %id))]
+pub async fn update_webhook_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    TenantExtractor(tenant): TenantExtractor,
+    AppPath(PathParam(id)): AppPath<PathParam<WebhookId>>,
+    State(state): State<Arc<AppState>>,
+    AppJson(request): AppJson<UpdateWebhookRequest>,
+) -> Result<Json<WebhookResponse>, ApiErrorResponse> {
+    // <feature:auth>
+    claims.authorize_admin()?;
+    // </feature:auth>
+
+    let webhook = update_webhook(
+        tenant,
+        id,
+        request.url,
+        request.event_types,
+        request.active,
+        state.webhook_repository.clone(),
+    )
+    .await?;
+
+    Ok(Json(webhook.into()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(
+        ("id" = Uuid, Path, description = "Webhook ID"),
+        TenantHeader
+    ),
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 204, description = "Webhook and its delivery history deleted"),
+        (status = 400, description = "Webhook ID is not a valid UUID", body = ApiErrorResponse),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Token lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 404, description = "Webhook not found in the tenant", body = ApiErrorResponse,
+            example = json!(examples::not_found_error())),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(webhook_id = %id))]
+pub async fn delete_webhook_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    TenantExtractor(tenant): TenantExtractor,
+    AppPath(PathParam(id)): AppPath<PathParam<WebhookId>>,
+    State(state): State<Arc<AppState>>,
+) -> Result<StatusCode, ApiErrorResponse> {
+    // <feature:auth>
+    claims.authorize_admin()?;
+    // </feature:auth>
+
+    delete_webhook(tenant, id, state.webhook_repository.clone()).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}/deliveries",
+    tag = "webhooks",
+    params(
+        ("id" = Uuid, Path, description = "Webhook ID"),
+        TenantHeader
+    ),
+    // <feature:auth>
+    security(("bearer" = [])),
+    // </feature:auth>
+    responses(
+        (status = 200, description = "The last 100 delivery attempts, newest first",
+            body = Vec<WebhookDeliveryResponse>),
+        (status = 400, description = "Webhook ID is not a valid UUID", body = ApiErrorResponse),
+        // <feature:auth>
+        (status = 401, description = "Missing or invalid token", body = ApiErrorResponse, examples(
+            ("Missing token" = (value = json!({"code": "TokenNotFound"}))),
+            ("Invalid token" = (value = json!({"code": "InvalidToken"})))
+        )),
+        (status = 403, description = "Token lacks the admin role", body = ApiErrorResponse,
+            example = json!(examples::forbidden_error())),
+        // </feature:auth>
+        (status = 404, description = "Webhook not found in the tenant", body = ApiErrorResponse,
+            example = json!(examples::not_found_error())),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields(webhook_id = %id))]
+pub async fn list_webhook_deliveries_handler(
+    // <feature:auth>
+    JwtExtractor(claims): JwtExtractor,
+    // </feature:auth>
+    TenantExtractor(tenant): TenantExtractor,
+    AppPath(PathParam(id)): AppPath<PathParam<WebhookId>>,
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<WebhookDeliveryResponse>>, ApiErrorResponse> {
+    // <feature:auth>
+    claims.authorize_admin()?;
+    // </feature:auth>
+
+    let deliveries = list_deliveries(tenant, id, state.webhook_repository.clone()).await?;
+
+    Ok(Json(deliveries.into_iter().map(Into::into).collect()))
+}
@@ -0,0 +1,26 @@
+pub mod handlers;
+
+use std::sync::Arc;
+
+use axum::{routing::get, Router};
+
+use crate::config::AppState;
+
+/// Routes of the webhooks API, merged into the application router
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/webhooks",
+            get(handlers::list_webhooks_handler).post(handlers::create_webhook_handler),
+        )
+        .route(
+            "/webhooks/{id}",
+            get(handlers::get_webhook_handler)
+                .put(handlers::update_webhook_handler)
+                .delete(handlers::delete_webhook_handler),
+        )
+        .route(
+            "/webhooks/{id}/deliveries",
+            get(handlers::list_webhook_deliveries_handler),
+        )
+}
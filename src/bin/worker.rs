@@ -0,0 +1,12 @@
+#![warn(clippy::pedantic)]
+#![warn(clippy::nursery)]
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::missing_panics_doc)]
+
+use anyhow::Result;
+use rust_service_template::{service, worker};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    service::run(|app_state, _config| worker::run(app_state)).await
+}
@@ -0,0 +1,516 @@
+use std::sync::Arc;
+// <feature:postgres>
+use std::time::{Duration, Instant};
+// </feature:postgres>
+
+use anyhow::Context;
+// <feature:postgres>
+use sqlx::{postgres::PgPoolOptions, Executor, PgPool};
+use tokio::task::JoinSet;
+// </feature:postgres>
+use tokio::sync::broadcast;
+
+// <feature:sqlite>
+#[cfg(feature = "sqlite")]
+use crate::infrastructure::{
+    sqlite_task::SqliteTaskRepository, sqlite_user::SqliteUserRepository,
+    sqlite_webhook::SqliteWebhookRepository,
+};
+// </feature:sqlite>
+use crate::{
+    config::{AppConfig, AppState, DatabaseKind, ObjectStoreKind},
+    domain::{
+        // <feature:kafka>
+        errors::ExternalSystem,
+        // </feature:kafka>
+        interfaces::{
+            attachment_repository::AttachmentRepository, event_producer::EventProducer,
+            feature_flags::FeatureFlags, job_repository::JobRepository, object_store::ObjectStore,
+            task_repository::TaskRepository, user_repository::UserRepository,
+            webhook_repository::WebhookRepository,
+        },
+    },
+    infrastructure::{
+        cached_task::{CachedTaskRepository, MokaTaskCache},
+        circuit_breaker::CircuitBreakers,
+        // <feature:kafka>
+        circuit_breaker_producer::CircuitBreakerProducer,
+        // </feature:kafka>
+        composite_event_producer::CompositeEventProducer,
+        feature_flags::StaticFeatureFlags,
+        filesystem_object_store::FilesystemObjectStore,
+        health_history::HealthHistory,
+        instrumented::InstrumentedTaskRepository,
+        // <feature:kafka>
+        kafka_producer::KafkaEventService,
+        // </feature:kafka>
+        log_level::LogLevelHandle,
+        noop_event_producer::NoopEventProducer,
+        s3_object_store::S3ObjectStore,
+        scheduler::Scheduler,
+        usage_cache::UsageCache,
+        webhook_dispatcher::WebhookDispatcher,
+    },
+};
+// <feature:postgres>
+use crate::{
+    config::{DatabasePoolConfig, JobsConfig, SchedulerConfig},
+    domain::attachment::operations::delete_orphaned_objects,
+    infrastructure::{
+        attachment::PostgresAttachmentRepository,
+        jobs::{JobRunner, PostgresJobQueue},
+        migrations,
+        retry::RetryPolicy,
+        task::PostgresTaskRepository,
+        user::PostgresUserRepository,
+        webhook::PostgresWebhookRepository,
+    },
+};
+// </feature:postgres>
+
+/// The object store in use, and the filesystem store again when it is that one
+type ObjectStores = (Arc<dyn ObjectStore>, Option<Arc<FilesystemObjectStore>>);
+
+// <feature:postgres>
+/// Most orphaned objects one `delete_orphaned_objects` run deletes; the rest wait for
+/// the next run
+const ORPHANED_OBJECTS_PER_RUN: u32 = 1000;
+// </feature:postgres>
+
+/// Task changes buffered per subscriber before the slowest one starts missing them
+const TASK_CHANGES_CAPACITY: usize = 1024;
+
+/// Repositories of one database backend
+type Repositories = (
+    // <feature:postgres>
+    Option<PgPool>,
+    // </feature:postgres>
+    Arc<dyn TaskRepository>,
+    Arc<dyn UserRepository>,
+    Arc<dyn WebhookRepository>,
+    Option<Arc<dyn JobRepository>>,
+    Option<Arc<dyn AttachmentRepository>>,
+);
+
+/// Build a fully populated [`AppState`] from configuration
+///
+/// Connects the database selected by `database_kind`, runs its migrations and wires the
+/// task, user and webhook repositories and the event producer. Both the binary and the integration
+/// tests start from here so the two cannot drift apart. `log_level` is only available when
+/// the caller built the tracing subscriber with a reloadable filter.
+pub async fn bootstrap(
+    config: AppConfig,
+    log_level: Option<LogLevelHandle>,
+) -> anyhow::Result<Arc<AppState>> {
+    let (
+        // <feature:postgres>
+        db_pool,
+        // </feature:postgres>
+        task_repository,
+        user_repository,
+        webhook_repository,
+        job_repository,
+        attachment_repository,
+    ): Repositories = match config.database_kind {
+        // <feature:sqlite>
+        #[cfg(feature = "sqlite")]
+        DatabaseKind::Sqlite => {
+            tracing::info!("Opening SQLite database...");
+            let task_repository =
+                SqliteTaskRepository::connect(&config.database_url, &config.pool_config).await?;
+            tracing::info!("SQLite database ready");
+            let user_repository = SqliteUserRepository::new(task_repository.pool().clone());
+            let webhook_repository = SqliteWebhookRepository::new(task_repository.pool().clone());
+            (
+                // <feature:postgres>
+                None,
+                // </feature:postgres>
+                Arc::new(task_repository),
+                Arc::new(user_repository),
+                Arc::new(webhook_repository),
+                // Both need Postgres
+                None,
+                None,
+            )
+        }
+        #[cfg(not(feature = "sqlite"))]
+        DatabaseKind::Sqlite => {
+            anyhow::bail!("database_kind = sqlite requires building with the `sqlite` feature")
+        }
+        // </feature:sqlite>
+        // <feature:postgres>
+        DatabaseKind::Postgres => postgres_repositories(&config).await?,
+        // </feature:postgres>
+    };
+
+    // Inside the cache, so cache hits are not counted as repository calls
+    let task_repository: Arc<dyn TaskRepository> = if config.pool_config.repository_metrics {
+        Arc::new(InstrumentedTaskRepository::task(task_repository))
+    } else {
+        task_repository
+    };
+
+    let task_repository: Arc<dyn TaskRepository> = if config.cache_config.enabled {
+        tracing::info!(
+            "Caching task reads for {}s (up to {} tasks)",
+            config.cache_config.ttl,
+            config.cache_config.max_capacity
+        );
+        Arc::new(CachedTaskRepository::new(
+            task_repository,
+            Arc::new(MokaTaskCache::from_config(&config.cache_config)),
+        ))
+    } else {
+        task_repository
+    };
+
+    let circuit_breakers = CircuitBreakers::new(&config.circuit_breaker_config);
+
+    // Every enabled sink receives each event; with none enabled, events are dropped
+    let mut sinks: Vec<Arc<dyn EventProducer>> = Vec::new();
+    // <feature:kafka>
+    if config.kafka_config.enabled {
+        tracing::info!("Initializing Kafka event producer...");
+        let producer: Arc<dyn EventProducer> = Arc::new(
+            KafkaEventService::new(&config.kafka_config)
+                .context("Failed to initialize Kafka producer")?,
+        );
+        tracing::info!("Kafka event producer initialized successfully");
+        sinks.push(match circuit_breakers.get("kafka") {
+            Some(breaker) => Arc::new(CircuitBreakerProducer::new(
+                producer,
+                breaker,
+                ExternalSystem::Kafka,
+            )),
+            None => producer,
+        });
+    }
+    // </feature:kafka>
+    if config.webhook_config.enabled {
+        tracing::info!("Delivering task events to webhooks");
+        sinks.push(Arc::new(
+            WebhookDispatcher::new(
+                webhook_repository.clone(),
+                &config.http_client_config,
+                &config.webhook_config,
+                circuit_breakers.clone(),
+            )
+            .context("Failed to initialize webhook dispatcher")?,
+        ));
+    }
+    let event_producer: Arc<dyn EventProducer> = match sinks.len() {
+        0 => {
+            tracing::info!("No event sink enabled, task events will not be published");
+            Arc::new(NoopEventProducer)
+        }
+        1 => sinks.remove(0),
+        _ => {
+            tracing::info!(
+                sinks = ?sinks.iter().map(|sink| sink.name()).collect::<Vec<_>>(),
+                policy = ?config.event_publish_policy,
+                "Publishing task events to several sinks"
+            );
+            Arc::new(CompositeEventProducer::new(
+                sinks,
+                config.event_publish_policy,
+            ))
+        }
+    };
+
+    let (task_changes, _) = broadcast::channel(TASK_CHANGES_CAPACITY);
+
+    let (object_store, local_uploads) = object_store(&config)?;
+
+    let usage_cache = UsageCache::from_config(&config.quota_config);
+    let health_history = HealthHistory::new(&config.health_history_config);
+
+    let feature_flags = StaticFeatureFlags::new(config.features.clone());
+    tracing::info!(features = ?feature_flags.snapshot(), "Feature flags loaded");
+
+    Ok(Arc::new(AppState {
+        // <feature:postgres>
+        db_pool,
+        // </feature:postgres>
+        env: config,
+        task_repository,
+        user_repository,
+        webhook_repository,
+        job_repository,
+        attachment_repository,
+        object_store,
+        local_uploads,
+        event_producer,
+        circuit_breakers,
+        health_history,
+        feature_flags: Arc::new(feature_flags),
+        task_changes,
+        usage_cache,
+        log_level,
+    }))
+}
+
+/// The object store selected by `attachment_config.store`, and the same store again when
+/// it is the filesystem one, whose uploads the service receives itself
+pub fn object_store(config: &AppConfig) -> anyhow::Result<ObjectStores> {
+    let attachments = &config.attachment_config;
+    match attachments.store {
+        ObjectStoreKind::Filesystem => {
+            let base_url = config
+                .public_base_url
+                .clone()
+                .unwrap_or_else(|| format!("http://{}:{}", config.server_host, config.server_port));
+            tracing::info!(
+                root = %attachments.filesystem_root.display(),
+                "Keeping attachments on the local filesystem"
+            );
+            let store = Arc::new(FilesystemObjectStore::new(
+                &attachments.filesystem_root,
+                &base_url,
+            ));
+            Ok((store.clone(), Some(store)))
+        }
+        ObjectStoreKind::S3 => {
+            let store =
+                S3ObjectStore::new(attachments).context("Failed to initialize the S3 store")?;
+            tracing::info!(bucket = %attachments.s3_bucket, "Keeping attachments in S3");
+            Ok((Arc::new(store), None))
+        }
+    }
+}
+
+// <feature:postgres>
+/// The background job runner, with a handler for every kind of job the service enqueues
+pub fn job_runner(db_pool: PgPool, config: &JobsConfig) -> JobRunner {
+    // The service's job handlers go here, e.g. `.with_handler(Arc::new(SendReminder))`
+    JobRunner::new(db_pool, config)
+}
+// </feature:postgres>
+
+/// The scheduler of recurring jobs, with the built-in maintenance jobs enabled in `config`
+///
+/// Fails when a cron expression does not parse, so a bad schedule stops startup instead
+/// of never running.
+pub fn scheduler(
+    // <feature:postgres>
+    db_pool: Option<&PgPool>,
+    object_store: Arc<dyn ObjectStore>,
+    config: &SchedulerConfig,
+    // </feature:postgres>
+) -> anyhow::Result<Scheduler> {
+    // The service's recurring jobs go here, e.g.
+    // `.register("send_digest", "0 0 8 * * Mon", || async { ... })?`
+    let scheduler = Scheduler::new();
+    // <feature:postgres>
+    let scheduler = maintenance_jobs(scheduler, db_pool, object_store, config)?;
+    // </feature:postgres>
+
+    Ok(scheduler)
+}
+
+// <feature:postgres>
+/// `scheduler` with the built-in maintenance jobs enabled in `config`
+fn maintenance_jobs(
+    mut scheduler: Scheduler,
+    db_pool: Option<&PgPool>,
+    object_store: Arc<dyn ObjectStore>,
+    config: &SchedulerConfig,
+) -> anyhow::Result<Scheduler> {
+    // The built-in jobs need Postgres, which config validation enforces
+    let Some(db_pool) = db_pool else {
+        return Ok(scheduler);
+    };
+
+    if config.purge_dead_jobs {
+        let queue = PostgresJobQueue::new(db_pool.clone());
+        let retention = chrono::Duration::days(i64::from(config.dead_job_retention_days));
+        scheduler =
+            scheduler.register("purge_dead_jobs", &config.purge_dead_jobs_cron, move || {
+                let queue = queue.clone();
+                async move {
+                    let purged = queue.purge_dead(chrono::Utc::now() - retention).await?;
+                    tracing::info!(purged, "Purged dead background jobs");
+                    Ok(())
+                }
+            })?;
+    }
+    if config.purge_webhook_deliveries {
+        let webhooks = PostgresWebhookRepository::new(db_pool.clone());
+        let retention = chrono::Duration::days(i64::from(config.webhook_delivery_retention_days));
+        scheduler = scheduler.register(
+            "purge_webhook_deliveries",
+            &config.purge_webhook_deliveries_cron,
+            move || {
+                let webhooks = webhooks.clone();
+                async move {
+                    let purged = webhooks
+                        .purge_deliveries(chrono::Utc::now() - retention)
+                        .await?;
+                    tracing::info!(purged, "Purged webhook deliveries");
+                    Ok(())
+                }
+            },
+        )?;
+    }
+    if config.delete_orphaned_objects {
+        let attachments: Arc<dyn AttachmentRepository> =
+            Arc::new(PostgresAttachmentRepository::new(db_pool.clone()));
+        scheduler = scheduler.register(
+            "delete_orphaned_objects",
+            &config.delete_orphaned_objects_cron,
+            move || {
+                let attachments = attachments.clone();
+                let object_store = object_store.clone();
+                async move {
+                    let deleted = delete_orphaned_objects(
+                        ORPHANED_OBJECTS_PER_RUN,
+                        attachments,
+                        object_store,
+                    )
+                    .await?;
+                    tracing::info!(deleted, "Deleted orphaned attachment objects");
+                    Ok(())
+                }
+            },
+        )?;
+    }
+
+    Ok(scheduler)
+}
+
+/// Connect to Postgres and build its repositories
+async fn postgres_repositories(config: &AppConfig) -> anyhow::Result<Repositories> {
+    let db_pool = connect_postgres(config).await?;
+    let task_repository = PostgresTaskRepository::new(db_pool.clone())
+        .with_slow_query_threshold(Duration::from_millis(
+            config.pool_config.slow_query_threshold_ms,
+        ))
+        .with_retry_policy(RetryPolicy::from_config(&config.pool_config))
+        .with_statement_timeout(
+            (config.pool_config.statement_timeout_ms > 0)
+                .then(|| Duration::from_millis(config.pool_config.statement_timeout_ms)),
+        );
+    let user_repository = PostgresUserRepository::new(db_pool.clone());
+    let webhook_repository = PostgresWebhookRepository::new(db_pool.clone());
+    let job_repository = PostgresJobQueue::new(db_pool.clone());
+    let attachment_repository = PostgresAttachmentRepository::new(db_pool.clone());
+    Ok((
+        Some(db_pool),
+        Arc::new(task_repository),
+        Arc::new(user_repository),
+        Arc::new(webhook_repository),
+        Some(Arc::new(job_repository)),
+        Some(Arc::new(attachment_repository)),
+    ))
+}
+
+/// Connect to Postgres and, unless `migrate_on_startup` is off, run the migrations in
+/// `migrations/`
+///
+/// Unless `lazy_db_connect` is on, the pool is warmed up first, so an unreachable database
+/// fails startup rather than the first requests.
+async fn connect_postgres(config: &AppConfig) -> anyhow::Result<PgPool> {
+    let db_pool = pool_options(&config.pool_config)
+        .connect_lazy(&config.database_url)
+        .context("Failed to create database pool")?;
+    if config.lazy_db_connect {
+        tracing::info!("Lazy database connection, connecting on the first query");
+    } else {
+        tracing::info!("Connecting to database...");
+        warm_up_pool(
+            &db_pool,
+            config.pool_config.min_connections,
+            Duration::from_millis(config.pool_config.warm_up_timeout_ms),
+        )
+        .await
+        .context("Failed to connect to the database")?;
+    }
+    tracing::info!(
+        "Database pool created with pool config: {:?}",
+        config.pool_config
+    );
+
+    if config.migrate_on_startup {
+        tracing::info!("Running migrations...");
+        migrations::run(&db_pool)
+            .await
+            .context("Failed to run migrations")?;
+        migrations::apply_user_id_mode(&db_pool, config.user_ids)
+            .await
+            .context("Failed to apply the user id mode")?;
+        tracing::info!("Migrations finished");
+    } else {
+        tracing::info!("Startup migrations disabled, expecting the schema to be up to date");
+    }
+
+    Ok(db_pool)
+}
+
+/// Create a Postgres pool without running migrations
+pub async fn connect_pool(database_url: &str, config: &DatabasePoolConfig) -> sqlx::Result<PgPool> {
+    pool_options(config).connect(database_url).await
+}
+
+fn pool_options(config: &DatabasePoolConfig) -> PgPoolOptions {
+    let statement_timeout_ms = config.statement_timeout_ms;
+
+    PgPoolOptions::new()
+        .max_connections(config.max_connections)
+        .min_connections(config.min_connections)
+        .acquire_timeout(Duration::from_secs(config.acquire_timeout))
+        .acquire_slow_threshold(Duration::from_millis(config.acquire_wait_limit_ms))
+        .idle_timeout(Duration::from_secs(config.idle_timeout))
+        .max_lifetime(Duration::from_secs(config.max_lifetime))
+        .after_connect(move |conn, _meta| {
+            Box::pin(async move {
+                // Also applies to migrations, which run on this pool
+                if statement_timeout_ms > 0 {
+                    conn.execute(
+                        format!("SET statement_timeout = {statement_timeout_ms}").as_str(),
+                    )
+                    .await?;
+                }
+                Ok(())
+            })
+        })
+}
+
+/// Open `connections` connections of `pool` at once, at least one, and run `SELECT 1` on
+/// each, failing if they do not all answer within `timeout`
+///
+/// The connections are held until every one answered, so each is a separate connection
+/// left idle in the pool for the first requests.
+pub async fn warm_up_pool(
+    pool: &PgPool,
+    connections: u32,
+    timeout: Duration,
+) -> anyhow::Result<()> {
+    let started = Instant::now();
+    let connections = connections.max(1);
+
+    let mut opening = JoinSet::new();
+    for _ in 0..connections {
+        let pool = pool.clone();
+        opening.spawn(async move {
+            let mut connection = pool.acquire().await?;
+            sqlx::query("SELECT 1").execute(&mut *connection).await?;
+            Ok::<_, sqlx::Error>(connection)
+        });
+    }
+    let warmed = tokio::time::timeout(timeout, async {
+        let mut warmed = Vec::new();
+        while let Some(opened) = opening.join_next().await {
+            warmed.push(opened.context("Warm-up task panicked")??);
+        }
+        anyhow::Ok(warmed)
+    })
+    .await
+    .map_err(|_| anyhow::anyhow!("Database did not answer within {timeout:?}"))??;
+
+    tracing::info!(
+        connections = warmed.len(),
+        duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        "Database pool warmed up"
+    );
+    Ok(())
+}
+// </feature:postgres>
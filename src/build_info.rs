@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+// <feature:swagger>
+use utoipa::ToSchema;
+// </feature:swagger>
+
+/// What was built and from which commit, recorded at compile time by `build.rs`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct BuildInfo {
+    pub name: String,
+    pub version: String,
+    /// Commit built, `unknown` outside a git checkout
+    pub git_sha: String,
+    /// Branch built, `HEAD` for a detached checkout and `unknown` outside a git checkout
+    pub git_branch: String,
+    /// When the build script last ran, or `SOURCE_DATE_EPOCH` when set, e.g.
+    /// `2026-03-01T12:00:00Z`
+    pub build_timestamp: String,
+    /// e.g. `rustc 1.90.0 (1159e78c4 2025-09-14)`
+    pub rustc_version: String,
+}
+
+impl BuildInfo {
+    /// Build information of this binary
+    #[must_use]
+    pub fn current() -> Self {
+        Self {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_sha: env!("BUILD_GIT_SHA").to_string(),
+            git_branch: env!("BUILD_GIT_BRANCH").to_string(),
+            build_timestamp: env!("BUILD_TIMESTAMP").to_string(),
+            rustc_version: env!("BUILD_RUSTC_VERSION").to_string(),
+        }
+    }
+}
@@ -1,4 +1,8 @@
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::cli::auth::Token;
 
 #[derive(Parser, Debug)]
 #[command(name = "rsc")]
@@ -7,6 +11,27 @@ use clap::{Args, Parser, Subcommand};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Show debug output, such as every file copied and every GitHub request
+    #[arg(short, long, global = true)]
+    pub verbose: bool,
+
+    /// Show errors only
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// With `json`, print a summary of `create` or `scaffold` as JSON on stdout, and
+    /// everything else on stderr
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub output_format: OutputFormat,
+}
+
+/// How command results are printed
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
 }
 
 #[derive(Subcommand, Debug)]
@@ -15,6 +40,91 @@ pub enum Commands {
     Create(CreateArgs),
     /// Scaffold a new service locally without creating a GitHub repository
     Scaffold(ScaffoldArgs),
+    /// Add code to a service generated by rsc
+    Generate(GenerateArgs),
+    /// Merge changes from a newer template into a service generated by rsc
+    Upgrade(UpgradeArgs),
+    /// Check a GitHub token and save it to the rsc config file for `create`
+    Auth(AuthArgs),
+}
+
+/// Template a service is generated from
+#[derive(Args, Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateArgs {
+    /// Git URL or local directory of the template [default: the repository of this CLI]
+    #[arg(long, value_name = "URL")]
+    pub template: Option<String>,
+
+    /// Tag, branch or commit of the template [default: the release matching this CLI]
+    #[arg(long, value_name = "REF")]
+    pub template_ref: Option<String>,
+
+    /// Fetch the template again instead of reusing the cached copy
+    #[arg(long, conflicts_with = "offline")]
+    pub no_cache: bool,
+
+    /// Never use the network: take a local template or the cached copy, and fail early
+    /// for anything that would need to fetch or call GitHub
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Leave template paths matching this gitignore-style pattern out of the service, on
+    /// top of the template's .rscignore; repeatable
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+
+    /// Copy template paths matching this pattern even if .rscignore or --exclude leaves
+    /// them out; repeatable
+    #[arg(long, value_name = "GLOB")]
+    pub include: Vec<String>,
+}
+
+/// Commands run in the generated service before its initial commit
+#[derive(Args, Debug, Clone, Default, PartialEq, Eq)]
+pub struct HookArgs {
+    /// Shell command to run in the service after the template's rsc.toml hooks, with
+    /// RSC_PROJECT_NAME and the selected features in RSC_* variables; repeatable
+    #[arg(long, value_name = "CMD")]
+    pub post_hook: Vec<String>,
+
+    /// Run neither the template's hooks nor --post-hook
+    #[arg(long)]
+    pub no_hooks: bool,
+}
+
+/// Entity the generated service manages instead of tasks
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct EntityArgs {
+    /// Snake case name replacing `task` in types, files, tables and routes, e.g. `order`
+    #[arg(long, value_name = "NAME", default_value = "task")]
+    pub entity: String,
+
+    /// Plural of --entity, for irregular nouns [default: English rules for regular nouns]
+    #[arg(long, value_name = "NAME")]
+    pub entity_plural: Option<String>,
+}
+
+/// What the generated service runs
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceKind {
+    /// HTTP API server
+    #[default]
+    Api,
+    /// Long-running worker serving only health probes, without the HTTP API, JWT
+    /// authentication or OpenAPI docs
+    Worker,
+    /// Library crate without a binary
+    Lib,
+}
+
+/// Database backend kept in the generated service
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseBackend {
+    #[default]
+    Postgres,
+    Sqlite,
 }
 
 #[derive(Args, Debug)]
@@ -31,8 +141,85 @@ pub struct CreateArgs {
     #[arg(short, long, value_name = "DESC")]
     pub description: Option<String>,
 
+    /// GitHub token, instead of GITHUB_TOKEN, `gh auth token`, the config file or a prompt
+    #[arg(long, value_name = "TOKEN")]
+    pub token: Option<Token>,
+
     #[arg(long)]
     pub without_kafka: bool,
+
+    /// Leave out JWT authentication; admin endpoints are then unauthenticated
+    #[arg(long)]
+    pub without_auth: bool,
+
+    /// Leave out the OpenAPI spec, Swagger UI and all utoipa annotations
+    #[arg(long)]
+    pub without_swagger: bool,
+
+    /// Add Kubernetes manifests (Deployment, Service, HPA) in deploy/k8s
+    #[arg(long)]
+    pub with_k8s: bool,
+
+    /// Add a Helm chart in deploy/helm
+    #[arg(long)]
+    pub with_helm: bool,
+
+    #[arg(long, value_enum, default_value_t = ServiceKind::Api)]
+    pub kind: ServiceKind,
+
+    #[arg(long, value_enum, default_value_t = DatabaseBackend::Postgres)]
+    pub database: DatabaseBackend,
+
+    /// Host port docker-compose publishes Postgres on [default: derived from NAME]
+    #[arg(long, value_name = "PORT", value_parser = clap::value_parser!(u16).range(1..))]
+    pub postgres_port: Option<u16>,
+
+    /// Host port docker-compose publishes Kafka on [default: derived from NAME]
+    #[arg(long, value_name = "PORT", value_parser = clap::value_parser!(u16).range(1..))]
+    pub kafka_port: Option<u16>,
+
+    #[command(flatten)]
+    pub entity: EntityArgs,
+
+    #[command(flatten)]
+    pub template: TemplateArgs,
+
+    #[command(flatten)]
+    pub hooks: HookArgs,
+
+    /// Print the plan and exit without calling GitHub, running git or writing files
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Prompt for the name, owner, visibility, description and features, and confirm
+    /// before creating anything; implied on a terminal when NAME or --github-user is missing
+    #[arg(long)]
+    pub interactive: bool,
+
+    /// Skip running `cargo check` on the generated service before creating the repository
+    #[arg(long)]
+    pub no_verify: bool,
+
+    /// Also run `cargo fmt --check` on the generated service
+    #[arg(long, conflicts_with = "no_verify")]
+    pub verify_fmt: bool,
+
+    /// Keep the GitHub repository when pushing to it fails, instead of deleting it
+    #[arg(long)]
+    pub keep_on_failure: bool,
+
+    /// Leave the repository without the `rust` and `microservice` topics
+    #[arg(long)]
+    pub no_topics: bool,
+
+    /// Keep GitHub's merge settings instead of allowing squash merges only and deleting
+    /// merged branches
+    #[arg(long)]
+    pub keep_merge_settings: bool,
+
+    /// Require an approved pull request to change the default branch
+    #[arg(long)]
+    pub protect_branch: bool,
 }
 
 #[derive(Args, Debug)]
@@ -45,6 +232,134 @@ pub struct ScaffoldArgs {
 
     #[arg(long)]
     pub without_kafka: bool,
+
+    /// Leave out JWT authentication; admin endpoints are then unauthenticated
+    #[arg(long)]
+    pub without_auth: bool,
+
+    /// Leave out the OpenAPI spec, Swagger UI and all utoipa annotations
+    #[arg(long)]
+    pub without_swagger: bool,
+
+    /// Add Kubernetes manifests (Deployment, Service, HPA) in deploy/k8s
+    #[arg(long)]
+    pub with_k8s: bool,
+
+    /// Add a Helm chart in deploy/helm
+    #[arg(long)]
+    pub with_helm: bool,
+
+    #[arg(long, value_enum, default_value_t = ServiceKind::Api)]
+    pub kind: ServiceKind,
+
+    #[arg(long, value_enum, default_value_t = DatabaseBackend::Postgres)]
+    pub database: DatabaseBackend,
+
+    /// Host port docker-compose publishes Postgres on [default: derived from NAME]
+    #[arg(long, value_name = "PORT", value_parser = clap::value_parser!(u16).range(1..))]
+    pub postgres_port: Option<u16>,
+
+    /// Host port docker-compose publishes Kafka on [default: derived from NAME]
+    #[arg(long, value_name = "PORT", value_parser = clap::value_parser!(u16).range(1..))]
+    pub kafka_port: Option<u16>,
+
+    #[command(flatten)]
+    pub entity: EntityArgs,
+
+    #[command(flatten)]
+    pub template: TemplateArgs,
+
+    #[command(flatten)]
+    pub hooks: HookArgs,
+
+    /// Print the plan and exit without calling GitHub, running git or writing files
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// With --dry-run, also print a unified diff from the existing output directory
+    #[arg(long, requires = "dry_run")]
+    pub diff: bool,
+
+    /// Run `cargo check` on the generated service and skip the initial commit if it fails
+    #[arg(long, conflicts_with = "dry_run")]
+    pub verify: bool,
+
+    /// With --verify, also run `cargo fmt --check`
+    #[arg(long, requires = "verify")]
+    pub verify_fmt: bool,
+
+    /// Generate into an existing output directory, overwriting files that differ
+    #[arg(long)]
+    pub force: bool,
+
+    /// Generate into an output directory holding only .git, README.md, LICENSE or
+    /// .gitignore, backing up files that differ to `<file>.rsc.bak` unless --force is given
+    #[arg(long)]
+    pub allow_non_empty: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct AuthArgs {
+    /// Token to save [default: GITHUB_TOKEN, `gh auth token`, the config file or a prompt]
+    #[arg(long, value_name = "TOKEN")]
+    pub token: Option<Token>,
+}
+
+#[derive(Args, Debug)]
+pub struct GenerateArgs {
+    #[command(subcommand)]
+    pub target: GenerateTarget,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum GenerateTarget {
+    /// Add an entity with CRUD endpoints, a Postgres table and OpenAPI docs
+    Entity(GenerateEntityArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct GenerateEntityArgs {
+    /// Snake case name of the entity, e.g. `invoice`
+    #[arg(value_name = "NAME")]
+    pub name: String,
+
+    /// Plural of NAME, for irregular nouns [default: English rules for regular nouns]
+    #[arg(long, value_name = "NAME")]
+    pub plural: Option<String>,
+
+    /// Root directory of the service
+    #[arg(long, value_name = "PATH", default_value = ".")]
+    pub path: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct UpgradeArgs {
+    /// Git URL or local directory of the template [default: the one the service was
+    /// generated from]
+    #[arg(long, value_name = "URL")]
+    pub template: Option<String>,
+
+    /// Tag, branch or commit of the template to upgrade to [default: the release matching
+    /// this CLI]
+    #[arg(long, value_name = "REF")]
+    pub template_ref: Option<String>,
+
+    /// Fetch the templates again instead of reusing the cached copies
+    #[arg(long, conflicts_with = "offline")]
+    pub no_cache: bool,
+
+    /// Never use the network: take local templates or the cached copies, and fail early
+    /// for any that would need to be fetched
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Print the changes as a diff without touching the service
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Root directory of the service
+    #[arg(long, value_name = "PATH", default_value = ".")]
+    pub path: PathBuf,
 }
 
 #[cfg(test)]
@@ -64,7 +379,30 @@ mod tests {
             github_user: "myuser".to_string(),
             private: true,
             description: Some("A test service".to_string()),
+            token: None,
             without_kafka: true,
+            without_auth: true,
+            without_swagger: true,
+            with_k8s: true,
+            with_helm: false,
+            kind: ServiceKind::Worker,
+            database: DatabaseBackend::Sqlite,
+            postgres_port: None,
+            kafka_port: None,
+            entity: EntityArgs {
+                entity: "order".to_string(),
+                entity_plural: None,
+            },
+            template: TemplateArgs::default(),
+            hooks: HookArgs::default(),
+            dry_run: true,
+            interactive: false,
+            no_verify: false,
+            verify_fmt: false,
+            keep_on_failure: false,
+            no_topics: false,
+            keep_merge_settings: false,
+            protect_branch: false,
         };
 
         assert_eq!(args.name, "my-service");
@@ -72,6 +410,11 @@ mod tests {
         assert!(args.private);
         assert_eq!(args.description, Some("A test service".to_string()));
         assert!(args.without_kafka);
+        assert!(args.without_auth);
+        assert!(args.without_swagger);
+        assert_eq!(args.database, DatabaseBackend::Sqlite);
+        assert_eq!(args.kind, ServiceKind::Worker);
+        assert!(args.dry_run);
     }
 
     #[test]
@@ -80,10 +423,228 @@ mod tests {
             name: "my-service".to_string(),
             output: Some("/tmp/output".to_string()),
             without_kafka: false,
+            without_auth: false,
+            without_swagger: false,
+            with_k8s: false,
+            with_helm: true,
+            kind: ServiceKind::Api,
+            database: DatabaseBackend::Postgres,
+            postgres_port: None,
+            kafka_port: None,
+            entity: EntityArgs {
+                entity: "task".to_string(),
+                entity_plural: None,
+            },
+            template: TemplateArgs::default(),
+            hooks: HookArgs::default(),
+            dry_run: false,
+            diff: false,
+            verify: false,
+            verify_fmt: false,
+            force: false,
+            allow_non_empty: false,
         };
 
         assert_eq!(args.name, "my-service");
         assert_eq!(args.output, Some("/tmp/output".to_string()));
         assert!(!args.without_kafka);
+        assert!(!args.without_auth);
+        assert!(!args.without_swagger);
+        assert_eq!(args.database, DatabaseBackend::Postgres);
+        assert!(!args.dry_run);
+        assert!(!args.diff);
+    }
+
+    #[test]
+    fn test_compose_port_flags() {
+        let cli = Cli::try_parse_from([
+            "rsc",
+            "scaffold",
+            "my-service",
+            "--postgres-port",
+            "5446",
+            "--kafka-port",
+            "9093",
+        ])
+        .unwrap();
+        let Commands::Scaffold(args) = cli.command else {
+            panic!("Expected scaffold command");
+        };
+        assert_eq!(args.postgres_port, Some(5446));
+        assert_eq!(args.kafka_port, Some(9093));
+
+        // Negative test: port 0 would let docker pick one nothing else knows about
+        for port in ["0", "65536", "postgres"] {
+            assert!(
+                Cli::try_parse_from(["rsc", "scaffold", "my-service", "--postgres-port", port])
+                    .is_err(),
+                "{port}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_output_flags_are_global() {
+        let cli = Cli::try_parse_from([
+            "rsc",
+            "scaffold",
+            "my-service",
+            "-q",
+            "--output-format",
+            "json",
+        ])
+        .unwrap();
+
+        assert!(cli.quiet && !cli.verbose);
+        assert_eq!(cli.output_format, OutputFormat::Json);
+        // Negative test: quiet and verbose contradict each other
+        let error = Cli::try_parse_from(["rsc", "-q", "-v", "scaffold", "my-service"]).unwrap_err();
+        assert_eq!(error.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn test_database_defaults_to_postgres() {
+        let cli = Cli::try_parse_from(["rsc", "scaffold", "my-service"]).unwrap();
+        let Commands::Scaffold(args) = cli.command else {
+            panic!("Expected scaffold command");
+        };
+        assert_eq!(args.database, DatabaseBackend::Postgres);
+        assert_eq!(args.entity.entity, "task");
+
+        let cli =
+            Cli::try_parse_from(["rsc", "scaffold", "my-service", "--database", "sqlite"]).unwrap();
+        let Commands::Scaffold(args) = cli.command else {
+            panic!("Expected scaffold command");
+        };
+        assert_eq!(args.database, DatabaseBackend::Sqlite);
+    }
+
+    #[test]
+    fn test_diff_requires_dry_run() {
+        assert!(Cli::try_parse_from(["rsc", "scaffold", "my-service", "--diff"]).is_err());
+
+        let cli =
+            Cli::try_parse_from(["rsc", "scaffold", "my-service", "--dry-run", "--diff"]).unwrap();
+        let Commands::Scaffold(args) = cli.command else {
+            panic!("Expected scaffold command");
+        };
+        assert!(args.dry_run && args.diff);
+    }
+
+    #[test]
+    fn test_verification_defaults() {
+        let cli = Cli::try_parse_from(["rsc", "create", "my-service", "-g", "myuser"]).unwrap();
+        let Commands::Create(args) = cli.command else {
+            panic!("Expected create command");
+        };
+        assert!(!args.no_verify, "create verifies unless told not to");
+
+        let cli = Cli::try_parse_from(["rsc", "scaffold", "my-service"]).unwrap();
+        let Commands::Scaffold(args) = cli.command else {
+            panic!("Expected scaffold command");
+        };
+        assert!(!args.verify, "scaffold verifies only when asked to");
+
+        // Negative test: a format check needs the compile check it follows
+        assert!(Cli::try_parse_from(["rsc", "scaffold", "my-service", "--verify-fmt"]).is_err());
+        assert!(Cli::try_parse_from([
+            "rsc",
+            "create",
+            "my-service",
+            "-g",
+            "myuser",
+            "--no-verify",
+            "--verify-fmt",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_token_parsing() {
+        let cli = Cli::try_parse_from(["rsc", "auth", "--token", "ghp_secret"]).unwrap();
+        let Commands::Auth(args) = cli.command else {
+            panic!("Expected auth command");
+        };
+        assert_eq!(args.token.unwrap().expose(), "ghp_secret");
+
+        let cli = Cli::try_parse_from(["rsc", "create", "svc", "-g", "acme", "--token", "ghp_x"])
+            .unwrap();
+        assert!(
+            !format!("{cli:?}").contains("ghp_x"),
+            "Debug output shows the token"
+        );
+
+        // Negative test: an empty token is rejected while parsing
+        assert!(Cli::try_parse_from(["rsc", "auth", "--token", ""]).is_err());
+    }
+
+    #[test]
+    fn test_template_options_parse_on_both_commands() {
+        let cli = Cli::try_parse_from([
+            "rsc",
+            "create",
+            "my-service",
+            "--github-user",
+            "myuser",
+            "--template",
+            "https://github.com/acme/service-template.git",
+            "--template-ref",
+            "v1.2.0",
+            "--no-cache",
+            "--exclude",
+            "docs/internal/",
+            "--exclude",
+            "*.scratch",
+            "--include",
+            "docs/internal/public/",
+        ])
+        .unwrap();
+        let Commands::Create(args) = cli.command else {
+            panic!("Expected create command");
+        };
+        assert_eq!(
+            args.template,
+            TemplateArgs {
+                template: Some("https://github.com/acme/service-template.git".to_string()),
+                template_ref: Some("v1.2.0".to_string()),
+                no_cache: true,
+                offline: false,
+                exclude: vec!["docs/internal/".to_string(), "*.scratch".to_string()],
+                include: vec!["docs/internal/public/".to_string()],
+            }
+        );
+
+        let cli = Cli::try_parse_from(["rsc", "scaffold", "my-service"]).unwrap();
+        let Commands::Scaffold(args) = cli.command else {
+            panic!("Expected scaffold command");
+        };
+        assert_eq!(args.template, TemplateArgs::default());
+
+        let cli = Cli::try_parse_from(["rsc", "scaffold", "my-service", "--offline"]).unwrap();
+        let Commands::Scaffold(args) = cli.command else {
+            panic!("Expected scaffold command");
+        };
+        assert!(args.template.offline);
+        // Negative test: fetching again is the one thing offline cannot do
+        assert!(
+            Cli::try_parse_from(["rsc", "scaffold", "my-service", "--offline", "--no-cache"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_generate_entity_parsing() {
+        let cli = Cli::try_parse_from(["rsc", "generate", "entity", "invoice"]).unwrap();
+        let Commands::Generate(GenerateArgs {
+            target: GenerateTarget::Entity(args),
+        }) = cli.command
+        else {
+            panic!("Expected generate entity command");
+        };
+        assert_eq!(args.name, "invoice");
+        assert_eq!(args.plural, None);
+        assert_eq!(args.path, PathBuf::from("."));
+
+        assert!(Cli::try_parse_from(["rsc", "generate", "entity"]).is_err());
     }
 }
@@ -0,0 +1,312 @@
+//! Finding the GitHub token and keeping it in the rsc config file
+//!
+//! `create` and `auth` look for a token in order: `--token`, `GITHUB_TOKEN`, the GitHub
+//! CLI, the config file, then a hidden prompt on a terminal.
+
+use anyhow::{Context, Result};
+use dialoguer::Password;
+use std::{
+    env, fmt, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+};
+
+const TOKEN_KEY: &str = "github_token";
+
+/// A GitHub token, shown as `***` by `Debug` so it stays out of logs
+#[derive(Clone, PartialEq, Eq)]
+pub struct Token(String);
+
+impl Token {
+    /// The token itself, for the `Authorization` header or a push URL
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Token {
+    type Err = String;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let token = token.trim();
+        if token.is_empty() {
+            return Err("GitHub token cannot be empty".to_string());
+        }
+        Ok(Self(token.to_string()))
+    }
+}
+
+impl fmt::Debug for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Token(***)")
+    }
+}
+
+/// Where a token was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenSource {
+    Flag,
+    Environment,
+    GhCli,
+    ConfigFile,
+    Prompt,
+}
+
+impl TokenSource {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Flag => "--token",
+            Self::Environment => "GITHUB_TOKEN",
+            Self::GhCli => "`gh auth token`",
+            Self::ConfigFile => "the rsc config file",
+            Self::Prompt => "the prompt",
+        }
+    }
+}
+
+/// The places a token is looked for, in order
+pub struct TokenLookup {
+    flag: Option<Token>,
+    environment: Option<String>,
+    /// The GitHub CLI, asked with `gh auth token`
+    gh: PathBuf,
+    config: Option<PathBuf>,
+    terminal: bool,
+}
+
+impl TokenLookup {
+    /// Look behind `flag` in `GITHUB_TOKEN`, `gh`, [`config_path`] and, on a
+    /// `terminal`, a prompt
+    pub fn new(flag: Option<Token>, terminal: bool) -> Self {
+        Self {
+            flag,
+            environment: env::var("GITHUB_TOKEN").ok(),
+            gh: PathBuf::from("gh"),
+            config: config_path(),
+            terminal,
+        }
+    }
+
+    pub fn find(self) -> Result<(Token, TokenSource)> {
+        if let Some(token) = self.flag {
+            return Ok((token, TokenSource::Flag));
+        }
+        if let Some(token) = self.environment.and_then(|token| token.parse().ok()) {
+            return Ok((token, TokenSource::Environment));
+        }
+        if let Some(token) = gh_token(&self.gh) {
+            return Ok((token, TokenSource::GhCli));
+        }
+        if let Some(path) = &self.config {
+            if let Some(token) = read_token(path)? {
+                return Ok((token, TokenSource::ConfigFile));
+            }
+        }
+        if self.terminal {
+            let token = Password::new()
+                .with_prompt("GitHub token")
+                .validate_with(|token: &String| token.parse::<Token>().map(|_| ()))
+                .interact()
+                .context("Failed to read the GitHub token")?;
+            let token = token.parse().map_err(anyhow::Error::msg)?;
+            return Ok((token, TokenSource::Prompt));
+        }
+
+        anyhow::bail!(
+            "No GitHub token found: pass --token, set GITHUB_TOKEN, log in with `gh auth login` \
+             or save one with `rsc auth`"
+        )
+    }
+}
+
+/// `$XDG_CONFIG_HOME/rsc/config.toml`, or `~/.config/rsc/config.toml`
+pub fn config_path() -> Option<PathBuf> {
+    let non_empty = |name| env::var_os(name).filter(|value| !value.is_empty());
+    non_empty("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|config| config.join("rsc").join("config.toml"))
+}
+
+/// The token the GitHub CLI is logged in with, if it is installed and logged in
+fn gh_token(gh: &Path) -> Option<Token> {
+    let output = Command::new(gh).args(["auth", "token"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()?.parse().ok()
+}
+
+fn read_config(path: &Path) -> Result<toml::Table> {
+    if !path.exists() {
+        return Ok(toml::Table::new());
+    }
+    let content = fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+    content
+        .parse()
+        .with_context(|| format!("Invalid TOML in {:?}", path))
+}
+
+/// The token saved in the config file at `path`, if any
+pub fn read_token(path: &Path) -> Result<Option<Token>> {
+    Ok(read_config(path)?
+        .get(TOKEN_KEY)
+        .and_then(toml::Value::as_str)
+        .and_then(|token| token.parse().ok()))
+}
+
+/// Save `token` to the config file at `path`, readable by the owner only
+///
+/// Other settings in the file are kept.
+pub fn save_token(path: &Path, token: &Token) -> Result<()> {
+    let mut config = read_config(path)?;
+    config.insert(TOKEN_KEY.to_string(), token.expose().into());
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // The mode only applies to new files; tighten one saved by hand too
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+                .with_context(|| format!("Failed to restrict {:?}", path))?;
+        }
+    }
+
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(config.to_string().as_bytes()))
+        .with_context(|| format!("Failed to write {:?}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(
+        flag: Option<&str>,
+        environment: Option<&str>,
+        config: Option<PathBuf>,
+    ) -> TokenLookup {
+        TokenLookup {
+            flag: flag.map(|token| token.parse().unwrap()),
+            environment: environment.map(String::from),
+            // Never the real `gh`, which may be logged in on the machine running the tests
+            gh: PathBuf::from("/nonexistent/gh"),
+            config,
+            terminal: false,
+        }
+    }
+
+    #[test]
+    fn test_token_is_redacted_in_debug_output() {
+        let token: Token = " ghp_secret\n".parse().unwrap();
+
+        assert_eq!(token.expose(), "ghp_secret");
+        assert_eq!(format!("{token:?}"), "Token(***)");
+        assert!(!format!("{:?}", Some(token)).contains("ghp_secret"));
+        // Negative test: an empty token is rejected where it is given
+        assert!("  ".parse::<Token>().is_err());
+    }
+
+    #[test]
+    fn test_lookup_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("rsc/config.toml");
+        save_token(&config, &"ghp_saved".parse().unwrap()).unwrap();
+
+        let found = |lookup: TokenLookup| {
+            let (token, source) = lookup.find().unwrap();
+            (token.expose().to_string(), source)
+        };
+        assert_eq!(
+            found(lookup(
+                Some("ghp_flag"),
+                Some("ghp_env"),
+                Some(config.clone())
+            )),
+            ("ghp_flag".to_string(), TokenSource::Flag)
+        );
+        assert_eq!(
+            found(lookup(None, Some("ghp_env"), Some(config.clone()))),
+            ("ghp_env".to_string(), TokenSource::Environment)
+        );
+        // An empty GITHUB_TOKEN, as CI leaves an unset secret, falls through
+        assert_eq!(
+            found(lookup(None, Some(""), Some(config))),
+            ("ghp_saved".to_string(), TokenSource::ConfigFile)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_lookup_asks_the_github_cli_before_the_config_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let gh = dir.path().join("gh");
+        fs::write(
+            &gh,
+            "#!/bin/sh\n[ \"$*\" = \"auth token\" ] && echo ghp_gh\n",
+        )
+        .unwrap();
+        fs::set_permissions(&gh, fs::Permissions::from_mode(0o755)).unwrap();
+        let config = dir.path().join("config.toml");
+        save_token(&config, &"ghp_saved".parse().unwrap()).unwrap();
+
+        let (token, source) = TokenLookup {
+            gh,
+            ..lookup(None, None, Some(config))
+        }
+        .find()
+        .unwrap();
+
+        assert_eq!(token.expose(), "ghp_gh");
+        assert_eq!(source, TokenSource::GhCli);
+    }
+
+    #[test]
+    fn test_lookup_without_token_or_terminal_fails() {
+        // Negative test: CI must fail with directions instead of waiting on a prompt
+        let dir = tempfile::tempdir().unwrap();
+
+        let error = lookup(None, None, Some(dir.path().join("config.toml")))
+            .find()
+            .unwrap_err();
+
+        assert!(error.to_string().contains("rsc auth"), "{error}");
+    }
+
+    #[test]
+    fn test_save_token_keeps_other_settings_and_restricts_access() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "editor = \"vim\"\ngithub_token = \"ghp_old\"\n").unwrap();
+
+        save_token(&path, &"ghp_new".parse().unwrap()).unwrap();
+
+        let config = read_config(&path).unwrap();
+        assert_eq!(config["editor"].as_str(), Some("vim"));
+        assert_eq!(
+            read_token(&path)
+                .unwrap()
+                .map(|token| token.expose().to_string()),
+            Some("ghp_new".to_string())
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
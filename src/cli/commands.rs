@@ -1,16 +1,50 @@
 use anyhow::{Context, Result};
-use std::{env, path::Path};
+use std::{
+    env, fs,
+    path::{Component, Path, PathBuf},
+    time::Instant,
+};
 use tempfile::TempDir;
+use tracing::{error, info, warn};
 
 use crate::cli::{
-    args::{CreateArgs, ScaffoldArgs},
-    generator::{self, ProjectGenerator},
-    github::{get_github_token, GitHubClient},
+    args::{
+        AuthArgs, CreateArgs, DatabaseBackend, EntityArgs, GenerateArgs, GenerateEntityArgs,
+        GenerateTarget, HookArgs, ScaffoldArgs, ServiceKind, TemplateArgs, UpgradeArgs,
+    },
+    auth::{self, TokenLookup},
+    entity::EntityName,
+    entity_generator::EntityGenerator,
+    exclusions::Exclusions,
+    generator::{
+        self, validate_service_name, Conflicts, FileChange, GenerationPlan, Merged,
+        ProjectGenerator,
+    },
+    github::{CreateRepoResponse, Deletion, GitHubClient, REPOSITORY_TOPICS},
+    hooks,
+    manifest::{Manifest, TemplateSource},
+    prompt,
+    summary::Summary,
+    template::{resolve_template, Template},
+    upgrade::{self, Outcome},
+    verify::Verifier,
 };
 
 fn validate_output_path(path: &Path) -> Result<()> {
-    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
     let current = std::env::current_dir()?.canonicalize()?;
+    let absolute = current.join(path);
+    // A path that does not exist yet cannot be canonicalized; its nearest existing
+    // ancestor can, and the rest must not climb back out of it
+    let canonical = absolute
+        .ancestors()
+        .find_map(|ancestor| {
+            let rest = absolute.strip_prefix(ancestor).ok()?;
+            if rest.components().any(|c| c == Component::ParentDir) {
+                return None;
+            }
+            Some(ancestor.canonicalize().ok()?.join(rest))
+        })
+        .with_context(|| format!("Cannot resolve output path {}", path.display()))?;
 
     if !canonical.starts_with(&current) {
         anyhow::bail!("Output path must be within the current directory");
@@ -19,61 +53,493 @@ fn validate_output_path(path: &Path) -> Result<()> {
     Ok(())
 }
 
-pub async fn execute_create(args: CreateArgs) -> Result<()> {
-    let github_token = get_github_token()
-        .context("GITHUB_TOKEN environment variable is required. Please set it and try again.")?;
+/// What a fresh clone of a new GitHub repository may hold for `--allow-non-empty`
+const CLONE_FILES: &[&str] = &[".git", "README.md", "LICENSE", ".gitignore"];
 
-    println!("Creating GitHub repository '{}'...", args.name);
+/// How `scaffold` writes into `dir` if it exists already, or why it may not
+fn existing_output(dir: &Path, force: bool, allow_non_empty: bool) -> Result<Option<Conflicts>> {
+    if !dir.exists() {
+        return Ok(None);
+    }
+    if !dir.is_dir() {
+        anyhow::bail!("Output path '{}' is not a directory", dir.display());
+    }
+    if !force && !allow_non_empty {
+        anyhow::bail!(
+            "Output directory '{}' already exists. Please remove it or choose a different location, \
+             or pass --allow-non-empty or --force to generate into it.",
+            dir.display()
+        );
+    }
 
-    let github = GitHubClient::new(&github_token)?;
+    if allow_non_empty {
+        let mut others = Vec::new();
+        for entry in
+            fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?
+        {
+            let name = entry?.file_name().to_string_lossy().into_owned();
+            if !CLONE_FILES.contains(&name.as_str()) {
+                others.push(name);
+            }
+        }
+        if !others.is_empty() {
+            others.sort();
+            anyhow::bail!(
+                "Output directory '{}' holds more than {}: {}. Pass --force without \
+                 --allow-non-empty to generate into it anyway.",
+                dir.display(),
+                CLONE_FILES.join(", "),
+                others.join(", ")
+            );
+        }
+    }
 
-    let repo = github
-        .create_repository(
-            &args.name,
-            args.description.as_deref(),
-            args.private,
-            &args.github_user,
-        )
-        .await
-        .context("Failed to create GitHub repository")?;
+    Ok(Some(if force {
+        Conflicts::Overwrite
+    } else {
+        Conflicts::Backup
+    }))
+}
+
+/// What `scaffold` did with each file it wrote into an existing directory
+fn print_merged(merged: &[(PathBuf, Merged)]) {
+    info!("Files:");
+    for (path, outcome) in merged {
+        match outcome {
+            Merged::Overwritten(Some(backup)) => info!(
+                "   {:<12}{} (the old file is in {})",
+                outcome.label(),
+                path.display(),
+                backup.display()
+            ),
+            Merged::Skipped => info!(
+                "   {:<12}{} (already up to date)",
+                outcome.label(),
+                path.display()
+            ),
+            _ => info!("   {:<12}{}", outcome.label(), path.display()),
+        }
+    }
+
+    let count = |label| merged.iter().filter(|(_, o)| o.label() == label).count();
+    info!(
+        "✓ {} created, {} overwritten, {} skipped",
+        count("created"),
+        count("overwritten"),
+        count("skipped")
+    );
+}
+
+fn load_template(args: &TemplateArgs) -> Result<Template> {
+    let template = resolve_template(args)?;
+    info!("Using template {}", template.source);
+    Ok(template)
+}
+
+/// Paths of `template` left out by its `.rscignore`, --exclude and --include
+fn exclusions(template: &Template, args: &TemplateArgs) -> Result<Exclusions> {
+    Exclusions::load(template.path(), &args.exclude, &args.include)
+}
+
+/// Hooks to run in the service: the template's, then `--post-hook`, or none with
+/// `--no-hooks`
+fn post_generate_hooks(template: &Template, args: &HookArgs) -> Result<Vec<String>> {
+    if args.no_hooks {
+        return Ok(Vec::new());
+    }
+    hooks::load(template.path(), &args.post_hook)
+}
+
+/// Hooks a dry run would have run
+fn print_hooks(hooks: &[String]) {
+    if hooks.is_empty() {
+        return;
+    }
+    info!("\nHooks, run before the initial commit ({}):", hooks.len());
+    for hook in hooks {
+        info!("   {hook}");
+    }
+}
+
+/// Say what a service of `kind` leaves out of the template
+fn print_kind(kind: ServiceKind) {
+    match kind {
+        ServiceKind::Api => {}
+        ServiceKind::Worker => info!("✓ Generated a worker without the HTTP API"),
+        ServiceKind::Lib => info!("✓ Generated a library without a binary"),
+    }
+}
+
+/// Files below `dir`, besides those in `.git`
+fn count_files(dir: &Path) -> Result<usize> {
+    Ok(generator::relative_files(dir, |path| path == Path::new(".git"))?.len())
+}
+
+/// Say which crate name the code will use when it is more than `name` with underscores
+fn report_crate_name(name: &str) {
+    let crate_name = generator::crate_name(name);
+    if crate_name != name.replace('-', "_") {
+        info!(
+            "Crate name: {} (Rust code refers to '{}' by it)",
+            crate_name, name
+        );
+    }
+}
+
+fn entity_name(args: &EntityArgs) -> Result<EntityName> {
+    EntityName::new(&args.entity, args.entity_plural.as_deref())
+}
+
+/// Features left out of the service, for the dry-run summary
+fn excluded_features(without_kafka: bool, without_auth: bool, without_swagger: bool) -> String {
+    let excluded: Vec<&str> = [
+        (without_kafka, "Kafka"),
+        (without_auth, "JWT authentication"),
+        (without_swagger, "OpenAPI docs and Swagger UI"),
+    ]
+    .into_iter()
+    .filter_map(|(excluded, feature)| excluded.then_some(feature))
+    .collect();
+
+    if excluded.is_empty() {
+        "none".to_string()
+    } else {
+        excluded.join(", ")
+    }
+}
 
-    println!("✓ Created repository: {}", repo.html_url);
+/// List every file of the plan with what generation does to it
+fn print_plan(plan: &GenerationPlan, database: DatabaseBackend) {
+    info!(
+        "\nFiles ({} copied, {} modified, {} removed, {} added), {} database backend:",
+        plan.count(FileChange::Copied),
+        plan.count(FileChange::Modified),
+        plan.count(FileChange::Removed),
+        plan.count(FileChange::Added),
+        format!("{:?}", database).to_lowercase()
+    );
+    for (path, change) in &plan.files {
+        info!("   {:<9}{}", change.label(), path.display());
+    }
+
+    info!("\nLeft out of the template ({}):", plan.excluded.len());
+    for (path, exclusion) in &plan.excluded {
+        info!("   {} ({})", path.display(), exclusion);
+    }
+}
+
+/// `create --dry-run`: validate the arguments and print what would be created
+fn plan_create(args: &CreateArgs, github_token: &str, template: &Template) -> Result<()> {
+    let github = GitHubClient::new(github_token)?;
+
+    // Never written: the plan generates into its own scratch directory
+    let generator = ProjectGenerator::new(
+        template.path().to_path_buf(),
+        env::temp_dir().join(&args.name),
+        args.without_kafka,
+        args.without_auth,
+        args.without_swagger,
+        args.database,
+        args.name.clone(),
+    )
+    .context("Failed to create project generator")?
+    .with_entity(entity_name(&args.entity)?)
+    .with_exclusions(exclusions(template, &args.template)?)
+    .with_k8s_manifests(args.with_k8s)
+    .with_helm_chart(args.with_helm)
+    .with_kind(args.kind)
+    .with_ports(args.postgres_port, args.kafka_port)
+    .with_template_source(TemplateSource::of(template));
+    let plan = generator
+        .plan()
+        .context("Failed to generate service files")?;
+
+    info!("Dry run: no repository, commit or push will be made.\n");
+    info!("Repository:");
+    info!(
+        "   Request: POST {}",
+        github.repositories_url(&args.github_user)
+    );
+    print_repository_settings(args)?;
+    let ports = generator.ports();
+    info!(
+        "   Ports: Postgres {}, Kafka {}",
+        ports.postgres, ports.kafka
+    );
+    print_plan(&plan, args.database);
+    print_hooks(&post_generate_hooks(template, &args.hooks)?);
+    info!(
+        "\nDestination: https://github.com/{}/{}.git",
+        args.github_user, args.name
+    );
+
+    Ok(())
+}
+
+/// Settings of the repository `create` makes, for the dry run and the confirmation
+fn print_repository_settings(args: &CreateArgs) -> Result<()> {
+    info!("   Name: {}", args.name);
+    info!(
+        "   Visibility: {}",
+        if args.private { "private" } else { "public" }
+    );
+    info!(
+        "   Description: {}",
+        args.description.as_deref().unwrap_or("(none)")
+    );
+    info!("   Kind: {}", format!("{:?}", args.kind).to_lowercase());
+    info!(
+        "   Left out: {}",
+        excluded_features(args.without_kafka, args.without_auth, args.without_swagger)
+    );
+    let entity = entity_name(&args.entity)?;
+    info!("   Entity: {} ({})", entity.singular(), entity.plural());
+
+    let mut settings = Vec::new();
+    if !args.no_topics {
+        settings.push(format!("topics {}", REPOSITORY_TOPICS.join(", ")));
+    }
+    if !args.keep_merge_settings {
+        settings.push("squash merges only, deleting merged branches".to_string());
+    }
+    if args.protect_branch {
+        settings.push("protected default branch".to_string());
+    }
+    if settings.is_empty() {
+        settings.push("GitHub's defaults".to_string());
+    }
+    info!("   Settings: {}", settings.join("; "));
+    Ok(())
+}
+
+/// Check the generated service in `dir`, naming the generation steps behind any failure
+///
+/// With `offline`, cargo builds from the dependencies it already has.
+async fn verify(
+    generator: &ProjectGenerator,
+    dir: &Path,
+    format: bool,
+    offline: bool,
+) -> Result<()> {
+    let mut verifier = Verifier::new(dir.to_path_buf(), format);
+    if offline {
+        verifier = verifier.with_env("CARGO_NET_OFFLINE", "true");
+    }
+    let Some(failure) = verifier.run().await? else {
+        return Ok(());
+    };
+
+    error!("\n✗ {} failed", failure.check.label());
+    if !failure.files.is_empty() {
+        info!("Generation steps (in src/cli/generator.rs) that changed the reported files:");
+        let files: Vec<PathBuf> = failure.files.into_iter().collect();
+        for (file, steps) in generator.trace(&files)? {
+            let steps = match steps {
+                None => "none, the generator did not write it".to_string(),
+                Some(steps) if steps.is_empty() => "none, copied from the template".to_string(),
+                Some(steps) => steps.join(", "),
+            };
+            info!("   {}: {}", file.display(), steps);
+        }
+    }
+
+    anyhow::bail!("Generated service failed {}", failure.check.label())
+}
+
+/// Create the repository and push the service, returning a summary unless nothing was
+/// created
+pub async fn execute_create(mut args: CreateArgs) -> Result<Option<Summary>> {
+    let started = Instant::now();
+    if args.template.offline {
+        anyhow::bail!(
+            "--offline: `create` needs the network to create the GitHub repository; use \
+             `rsc scaffold --offline` to generate the service locally"
+        );
+    }
+    if args.interactive {
+        if !prompt::is_terminal() {
+            anyhow::bail!("--interactive needs a terminal to prompt on");
+        }
+        prompt::complete_create_args(&mut args)?;
+    }
+
+    let (token, source) = TokenLookup::new(args.token.clone(), prompt::is_terminal()).find()?;
+    info!("Using the GitHub token from {}", source.label());
+    let github_token = token.expose();
+
+    validate_service_name(&args.name)?;
+    report_crate_name(&args.name);
+    entity_name(&args.entity)?;
+    // Before the repository is created, so a failed fetch leaves nothing behind on GitHub
+    let template = load_template(&args.template)?;
+
+    if args.dry_run {
+        return plan_create(&args, github_token, &template).map(|()| None);
+    }
+
+    let github = GitHubClient::new(github_token)?;
+    // Before generation, so a token or owner GitHub would refuse fails without delay
+    info!("Checking access to GitHub...");
+    for warning in github
+        .check_can_create(&args.github_user, &args.name, args.private)
+        .await?
+    {
+        warn!("⚠ Warning: {}", warning);
+    }
+    info!("✓ The repository can be created");
+
+    if args.interactive {
+        info!("\nRepository:");
+        print_repository_settings(&args)?;
+        info!(
+            "\nDestination: https://github.com/{}/{}.git\n",
+            args.github_user, args.name
+        );
+        if !prompt::confirm("Create the repository and push the service?", false)? {
+            info!("Cancelled; nothing was created.");
+            return Ok(None);
+        }
+    }
 
     let temp_dir = TempDir::new().context("Failed to create temporary directory")?;
     let temp_path = temp_dir.path();
 
-    println!("Generating service files...");
-
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
+    info!("Generating service files...");
 
     let generator = ProjectGenerator::new(
-        current_dir,
+        template.path().to_path_buf(),
         temp_path.to_path_buf(),
         args.without_kafka,
+        args.without_auth,
+        args.without_swagger,
+        args.database,
         args.name.clone(),
     )
-    .context("Failed to create project generator")?;
+    .context("Failed to create project generator")?
+    .with_entity(entity_name(&args.entity)?)
+    .with_exclusions(exclusions(&template, &args.template)?)
+    .with_k8s_manifests(args.with_k8s)
+    .with_helm_chart(args.with_helm)
+    .with_kind(args.kind)
+    .with_ports(args.postgres_port, args.kafka_port)
+    .with_template_source(TemplateSource::of(&template));
     generator
         .generate()
         .context("Failed to generate service files")?;
 
     if args.without_kafka {
-        println!("✓ Generated service without Kafka support");
+        info!("✓ Generated service without Kafka support");
     } else {
-        println!("✓ Generated service with Kafka support");
+        info!("✓ Generated service with Kafka support");
+    }
+    if args.without_auth {
+        info!("✓ Generated service without JWT authentication");
+    }
+    if args.without_swagger {
+        info!("✓ Generated service without OpenAPI docs or Swagger UI");
+    }
+    if args.with_k8s {
+        info!("✓ Added Kubernetes manifests in deploy/k8s");
+    }
+    if args.with_helm {
+        info!("✓ Added a Helm chart in deploy/helm");
+    }
+    print_kind(args.kind);
+    info!(
+        "✓ Using the {} database backend",
+        format!("{:?}", args.database).to_lowercase()
+    );
+
+    hooks::run(
+        &post_generate_hooks(&template, &args.hooks)?,
+        temp_path,
+        &generator.hook_env(),
+    )
+    .context("Nothing was created on GitHub; rerun with --no-hooks to skip the hooks")?;
+    // Before verification, which leaves a `target` directory behind
+    let files_created = count_files(temp_path)?;
+
+    // Before the repository is created, so a broken service never reaches GitHub
+    if !args.no_verify {
+        verify(
+            &generator,
+            temp_path,
+            args.verify_fmt,
+            args.template.offline,
+        )
+        .await
+        .context("Nothing was created on GitHub; rerun with --no-verify to push anyway")?;
+    }
+
+    info!("Creating GitHub repository '{}'...", args.name);
+
+    let repo = github
+        .create_repository(
+            &args.name,
+            args.description.as_deref(),
+            args.private,
+            &args.github_user,
+        )
+        .await
+        .context("Failed to create GitHub repository")?;
+
+    info!("✓ Created repository: {}", repo.html_url);
+
+    // Empty until the push, so a failure from here on would leave an orphan behind
+    let branch = match push_service(&args, temp_path, &repo, github_token) {
+        Ok(branch) => branch,
+        Err(error) => {
+            if args.keep_on_failure {
+                info!("\nKept {} as --keep-on-failure asks", repo.html_url);
+            } else {
+                roll_back(&github, &repo).await;
+            }
+            return Err(error);
+        }
+    };
+
+    configure_repository(&github, &args, &repo, &branch).await;
+
+    info!("\n✅ Success! Repository created and pushed to GitHub.");
+    info!("   Repository URL: {}", repo.html_url);
+    info!("   Clone URL: {}", repo.ssh_url);
+
+    if args.without_kafka {
+        info!("\nNote: Kafka support has been excluded from this service.");
+    }
+    if args.without_auth {
+        info!(
+            "\nNote: JWT authentication has been excluded; protect the admin endpoints at the network level if you enable them."
+        );
     }
 
-    println!("Initializing git repository...");
-    generator::init_git_repo(temp_path).context("Failed to initialize git repository")?;
+    Ok(Some(Summary::new(
+        Some(repo.html_url),
+        None,
+        files_created,
+        &generator.features(),
+        started.elapsed(),
+    )))
+}
+
+/// Commit the generated service in `dir` and push it to `repo` over HTTPS with `token`,
+/// returning the branch pushed
+fn push_service(
+    args: &CreateArgs,
+    dir: &Path,
+    repo: &CreateRepoResponse,
+    token: &str,
+) -> Result<String> {
+    info!("Initializing git repository...");
+    generator::init_git_repo(dir).context("Failed to initialize git repository")?;
 
-    let remote_url = format!("https://github.com/{}/{}.git", args.github_user, args.name);
-    generator::git_add_remote(temp_path, "origin", &remote_url)
+    generator::git_add_remote(dir, "origin", &repo.clone_url)
         .context("Failed to add git remote")?;
 
-    generator::git_add_all(temp_path).context("Failed to stage files")?;
+    generator::git_add_all(dir).context("Failed to stage files")?;
 
     generator::git_commit(
-        temp_path,
+        dir,
         if args.without_kafka {
             "feat: initial commit without Kafka"
         } else {
@@ -87,57 +553,100 @@ pub async fn execute_create(args: CreateArgs) -> Result<()> {
     // Verify commit was created
     let output = std::process::Command::new("git")
         .args(["log", "--oneline", "-1"])
-        .current_dir(temp_path)
+        .current_dir(dir)
         .output();
 
     match output {
         Ok(output) if output.status.success() => {
-            println!(
+            info!(
                 "✓ Commit created: {}",
                 String::from_utf8_lossy(&output.stdout).trim()
             );
         }
         _ => {
-            println!("⚠ Warning: Could not verify commit");
+            warn!("⚠ Warning: Could not verify commit");
         }
     }
 
-    // Check current branch
-    let output = std::process::Command::new("git")
-        .args(["branch", "--show-current"])
-        .current_dir(temp_path)
-        .output();
+    let branch = generator::git_current_branch(dir).context("Failed to read the branch")?;
+    info!("✓ Current branch: {}", branch);
 
-    match output {
-        Ok(output) if output.status.success() => {
-            let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            println!("✓ Current branch: {}", branch);
+    info!("Pushing to GitHub...");
+    generator::git_push(dir, &repo.clone_url, &branch, token).with_context(|| {
+        format!(
+            "Failed to push to {}. Make sure GITHUB_TOKEN may push to it",
+            repo.full_name
+        )
+    })?;
+
+    Ok(branch)
+}
+
+/// Apply the repository settings `args` ask for to `repo`, whose `branch` was pushed
+///
+/// The service is on GitHub by now, so a failure only warns.
+async fn configure_repository(
+    github: &GitHubClient,
+    args: &CreateArgs,
+    repo: &CreateRepoResponse,
+    branch: &str,
+) {
+    let (owner, name) = (repo.owner.login.as_str(), repo.name.as_str());
 
-            println!("Pushing to GitHub...");
-            generator::git_push(temp_path, "origin", &branch)
-                .context("Failed to push to remote. Make sure you have SSH access to GitHub.")?;
+    if !args.no_topics {
+        match github.set_topics(owner, name, REPOSITORY_TOPICS).await {
+            Ok(()) => info!("✓ Topics: {}", REPOSITORY_TOPICS.join(", ")),
+            Err(error) => warn!("⚠ Warning: Could not set the topics: {:#}", error),
         }
-        _ => {
-            println!("Pushing to GitHub...");
-            generator::git_push(temp_path, "origin", "main")
-                .or_else(|_| generator::git_push(temp_path, "origin", "master"))
-                .context("Failed to push to remote. Make sure you have SSH access to GitHub.")?;
+    }
+    if !args.keep_merge_settings {
+        match github.set_squash_merge_only(owner, name).await {
+            Ok(()) => info!("✓ Squash merges only, deleting merged branches"),
+            Err(error) => warn!(
+                "⚠ Warning: Could not change the merge settings: {:#}",
+                error
+            ),
         }
     }
+    if args.protect_branch {
+        match github.protect_branch(owner, name, branch).await {
+            Ok(()) => info!("✓ Protected branch: {}", branch),
+            Err(error) => warn!("⚠ Warning: Could not protect {}: {:#}", branch, error),
+        }
+    }
+}
 
-    println!("\n✅ Success! Repository created and pushed to GitHub.");
-    println!("   Repository URL: {}", repo.html_url);
-    println!("   Clone URL: {}", repo.ssh_url);
+/// Delete the repository `create` could not push to, or tell how to do it by hand
+async fn roll_back(github: &GitHubClient, repo: &CreateRepoResponse) {
+    info!("\nRolling back: deleting {}...", repo.full_name);
+    let manual = format!(
+        "   Delete it at {}/settings or with `gh repo delete {} --yes`",
+        repo.html_url, repo.full_name
+    );
 
-    if args.without_kafka {
-        println!("\nNote: Kafka support has been excluded from this service.");
+    match github
+        .delete_repository(&repo.owner.login, &repo.name)
+        .await
+    {
+        Ok(Deletion::Deleted) => info!("✓ Deleted repository: {}", repo.html_url),
+        Ok(Deletion::Forbidden) => {
+            warn!(
+                "⚠ GitHub refused to delete {}: the token needs the `delete_repo` scope",
+                repo.full_name
+            );
+            info!("{manual}");
+        }
+        Err(error) => {
+            warn!("⚠ Could not delete {}: {:#}", repo.full_name, error);
+            info!("{manual}");
+        }
     }
-
-    Ok(())
 }
 
-pub fn execute_scaffold(args: ScaffoldArgs) -> Result<()> {
-    let output_dir = match args.output {
+/// Generate the service locally, returning a summary unless it was a dry run
+pub async fn execute_scaffold(args: ScaffoldArgs) -> Result<Option<Summary>> {
+    let started = Instant::now();
+    let output_dir = match &args.output {
         Some(path) => std::path::PathBuf::from(path),
         None => {
             let current_dir = env::current_dir().context("Failed to get current directory")?;
@@ -147,36 +656,136 @@ pub fn execute_scaffold(args: ScaffoldArgs) -> Result<()> {
 
     validate_output_path(&output_dir)?;
 
-    if output_dir.exists() {
-        anyhow::bail!(
-            "Output directory '{}' already exists. Please remove it or choose a different location.",
-            output_dir.display()
-        );
-    }
+    let conflicts = if args.diff {
+        if !output_dir.exists() {
+            anyhow::bail!(
+                "Nothing to diff: output directory '{}' does not exist",
+                output_dir.display()
+            );
+        }
+        None
+    } else {
+        existing_output(&output_dir, args.force, args.allow_non_empty)?
+    };
 
-    println!("Scaffolding service '{}'...", args.name);
+    validate_service_name(&args.name)?;
+    report_crate_name(&args.name);
+    entity_name(&args.entity)?;
+    let template = load_template(&args.template)?;
 
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
+    if args.dry_run {
+        return plan_scaffold(&args, output_dir, &template).map(|()| None);
+    }
+
+    info!("Scaffolding service '{}'...", args.name);
 
     let generator = ProjectGenerator::new(
-        current_dir,
+        template.path().to_path_buf(),
         output_dir.clone(),
         args.without_kafka,
+        args.without_auth,
+        args.without_swagger,
+        args.database,
         args.name.clone(),
     )
-    .context("Failed to create project generator")?;
-    generator
-        .generate()
-        .context("Failed to generate service files")?;
+    .context("Failed to create project generator")?
+    .with_entity(entity_name(&args.entity)?)
+    .with_exclusions(exclusions(&template, &args.template)?)
+    .with_k8s_manifests(args.with_k8s)
+    .with_helm_chart(args.with_helm)
+    .with_kind(args.kind)
+    .with_ports(args.postgres_port, args.kafka_port)
+    .with_template_source(TemplateSource::of(&template));
+    let mut backed_up = false;
+    // Counted as generated: hooks may add files to it
+    let written = match conflicts {
+        None => {
+            generator
+                .generate()
+                .context("Failed to generate service files")?;
+            None
+        }
+        Some(conflicts) => {
+            let merged = generator
+                .merge(conflicts)
+                .context("Failed to generate service files")?;
+            print_merged(&merged);
+            backed_up = merged
+                .iter()
+                .any(|(_, outcome)| matches!(outcome, Merged::Overwritten(Some(_))));
+            Some(
+                merged
+                    .iter()
+                    .filter(|(_, outcome)| *outcome != Merged::Skipped)
+                    .count(),
+            )
+        }
+    };
 
     if args.without_kafka {
-        println!("✓ Generated service without Kafka support");
+        info!("✓ Generated service without Kafka support");
     } else {
-        println!("✓ Generated service with Kafka support");
+        info!("✓ Generated service with Kafka support");
+    }
+    if args.without_auth {
+        info!("✓ Generated service without JWT authentication");
+    }
+    if args.without_swagger {
+        info!("✓ Generated service without OpenAPI docs or Swagger UI");
+    }
+    if args.with_k8s {
+        info!("✓ Added Kubernetes manifests in deploy/k8s");
+    }
+    if args.with_helm {
+        info!("✓ Added a Helm chart in deploy/helm");
+    }
+    print_kind(args.kind);
+    info!(
+        "✓ Using the {} database backend",
+        format!("{:?}", args.database).to_lowercase()
+    );
+
+    hooks::run(
+        &post_generate_hooks(&template, &args.hooks)?,
+        &output_dir,
+        &generator.hook_env(),
+    )
+    .with_context(|| {
+        format!(
+            "The service is in '{}', without its initial commit",
+            output_dir.display()
+        )
+    })?;
+
+    // Before verification, which leaves a `target` directory behind
+    let files_created = match written {
+        Some(written) => written,
+        None => count_files(&output_dir)?,
+    };
+
+    if args.verify {
+        verify(
+            &generator,
+            &output_dir,
+            args.verify_fmt,
+            args.template.offline,
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "The service is in '{}', without its initial commit",
+                output_dir.display()
+            )
+        })?;
     }
 
-    println!("Initializing git repository...");
+    info!("Initializing git repository...");
     generator::init_git_repo(&output_dir).context("Failed to initialize git repository")?;
+    if backed_up {
+        // Kept for the user to compare, not for the initial commit
+        generator::git_exclude(&output_dir, "*.rsc.bak")
+            .context("Failed to exclude the backups from git")?;
+    }
 
     generator::git_add_all(&output_dir).context("Failed to stage files")?;
 
@@ -192,19 +801,320 @@ pub fn execute_scaffold(args: ScaffoldArgs) -> Result<()> {
     )
     .context("Failed to commit changes")?;
 
-    println!("\n✅ Success! Service scaffolded locally.");
-    println!("   Location: {}", output_dir.canonicalize()?.display());
-    println!("\nNext steps:");
-    println!(
+    info!("\n✅ Success! Service scaffolded locally.");
+    info!("   Location: {}", output_dir.canonicalize()?.display());
+    info!("\nNext steps:");
+    info!(
         "   cd {}",
         output_dir.file_name().unwrap().to_string_lossy()
     );
-    println!("   docker-compose up -d");
-    println!("   cargo run");
+    for step in generator.next_steps() {
+        info!("   {step}");
+    }
+    info!("\nThese are also under Getting Started in the README.");
 
     if args.without_kafka {
-        println!("\nNote: Kafka support has been excluded from this service.");
+        info!("\nNote: Kafka support has been excluded from this service.");
     }
+    if args.without_auth {
+        info!(
+            "\nNote: JWT authentication has been excluded; protect the admin endpoints at the network level if you enable them."
+        );
+    }
+
+    Ok(Some(Summary::new(
+        None,
+        Some(output_dir.canonicalize()?),
+        files_created,
+        &generator.features(),
+        started.elapsed(),
+    )))
+}
+
+pub fn execute_upgrade(args: UpgradeArgs) -> Result<()> {
+    let manifest = Manifest::read(&args.path)?;
+    let recorded = &manifest.template;
+    let Some(commit) = &recorded.commit else {
+        anyhow::bail!(
+            "The manifest records no template commit, as {} was not a git checkout when the \
+             service was generated, so there is nothing to compare the new template with",
+            recorded.url
+        );
+    };
+
+    info!("Fetching the template the service was generated from...");
+    let base_template = resolve_template(&TemplateArgs {
+        template: Some(recorded.url.clone()),
+        template_ref: Some(commit.clone()),
+        no_cache: args.no_cache,
+        offline: args.offline,
+        ..TemplateArgs::default()
+    })?;
+    info!("Fetching the template to upgrade to...");
+    let template = resolve_template(&TemplateArgs {
+        template: Some(
+            args.template
+                .clone()
+                .unwrap_or_else(|| recorded.url.clone()),
+        ),
+        template_ref: args.template_ref.clone(),
+        no_cache: args.no_cache,
+        offline: args.offline,
+        ..TemplateArgs::default()
+    })?;
+
+    let base_dir = TempDir::new().context("Failed to create temporary directory")?;
+    let upgraded_dir = TempDir::new().context("Failed to create temporary directory")?;
+    upgrade::regenerate(&manifest.options, &base_template, base_dir.path())?;
+    let generator = upgrade::regenerate(&manifest.options, &template, upgraded_dir.path())?;
+    let changes = upgrade::plan(&args.path, base_dir.path(), upgraded_dir.path())?;
+
+    info!(
+        "\nUpgrading '{}' from {} at {} to {}",
+        manifest.options.name,
+        recorded.url,
+        &commit[..commit.len().min(12)],
+        template.source
+    );
+    if changes.is_empty() {
+        info!("✓ Already up to date");
+    } else {
+        info!("\nFiles ({}):", changes.len());
+        for change in &changes {
+            info!("   {} ({})", change.path.display(), change.outcome.label());
+        }
+    }
+
+    if args.dry_run {
+        let diff = upgrade::diff(&changes);
+        if !diff.is_empty() {
+            info!("\n{diff}");
+        }
+        info!("Dry run: nothing was written.");
+        return Ok(());
+    }
+
+    upgrade::apply(&args.path, &changes)?;
+    generator.manifest()?.write(&args.path)?;
+
+    let conflicted: Vec<_> = changes
+        .iter()
+        .filter(|change| change.outcome == Outcome::Conflicted)
+        .collect();
+    info!("\n✅ Upgraded to {}", template.source);
+    if !conflicted.is_empty() {
+        warn!(
+            "\n⚠ Warning: {} file(s) have conflicts; resolve the <<<<<<< markers before committing:",
+            conflicted.len()
+        );
+        for change in conflicted {
+            info!("   {}", change.path.display());
+        }
+    }
+    if changes.iter().any(|change| change.outcome == Outcome::Kept) {
+        info!(
+            "\nFiles marked kept were changed in the template but have local changes it \
+             cannot merge; compare them with the template by hand."
+        );
+    }
+    info!("\nReview the changes with `git diff`, then commit them.");
+
+    Ok(())
+}
+
+pub fn execute_generate(args: GenerateArgs) -> Result<()> {
+    match args.target {
+        GenerateTarget::Entity(args) => generate_entity(&args),
+    }
+}
+
+fn generate_entity(args: &GenerateEntityArgs) -> Result<()> {
+    let entity = EntityName::additional(&args.name, args.plural.as_deref())?;
+    let plural = entity.plural().to_string();
+    let generator = EntityGenerator::new(args.path.clone(), entity)?;
+
+    info!("Adding entity '{}'...", args.name);
+    let generated = generator
+        .generate()
+        .context("Failed to generate entity files")?;
+    for path in &generated.created {
+        info!("   created  {}", path.display());
+    }
+    for path in &generated.modified {
+        info!("   modified {}", path.display());
+    }
+    if let Err(e) = generator.format(&generated) {
+        info!("\nNote: the new files were not formatted ({e:#}); run `cargo fmt`.");
+    }
+
+    info!("\n✅ Success! /{plural} is served with create, read, update and delete.");
+    info!("\nNext steps:");
+    info!("   cargo build");
+    if args.path.join("openapi.json").exists() {
+        info!("   cargo run -- openapi --out openapi.json");
+        info!("   cargo insta test --accept");
+    }
+    info!("   Add fields to the model, the migration and the request and response models");
 
     Ok(())
 }
+
+/// `scaffold --dry-run`: print what would be written, and with `--diff` how the output
+/// directory would change
+fn plan_scaffold(args: &ScaffoldArgs, output_dir: PathBuf, template: &Template) -> Result<()> {
+    let generator = ProjectGenerator::new(
+        template.path().to_path_buf(),
+        output_dir.clone(),
+        args.without_kafka,
+        args.without_auth,
+        args.without_swagger,
+        args.database,
+        args.name.clone(),
+    )
+    .context("Failed to create project generator")?
+    .with_entity(entity_name(&args.entity)?)
+    .with_exclusions(exclusions(template, &args.template)?)
+    .with_k8s_manifests(args.with_k8s)
+    .with_helm_chart(args.with_helm)
+    .with_kind(args.kind)
+    .with_ports(args.postgres_port, args.kafka_port)
+    .with_template_source(TemplateSource::of(template));
+    let plan = generator
+        .plan()
+        .context("Failed to generate service files")?;
+
+    info!("Dry run: nothing will be written.\n");
+    info!("Service: {}", args.name);
+    info!("   Kind: {}", format!("{:?}", args.kind).to_lowercase());
+    let ports = generator.ports();
+    info!(
+        "   Ports: Postgres {}, Kafka {}",
+        ports.postgres, ports.kafka
+    );
+    info!(
+        "   Left out: {}",
+        excluded_features(args.without_kafka, args.without_auth, args.without_swagger)
+    );
+    let entity = entity_name(&args.entity)?;
+    info!("   Entity: {} ({})", entity.singular(), entity.plural());
+    print_plan(&plan, args.database);
+    print_hooks(&post_generate_hooks(template, &args.hooks)?);
+    info!("\nDestination: {}", output_dir.display());
+
+    if args.diff {
+        let diff = plan
+            .diff_against(&output_dir)
+            .with_context(|| format!("Failed to diff against {}", output_dir.display()))?;
+        if diff.is_empty() {
+            info!("\nNo differences from {}", output_dir.display());
+        } else {
+            info!("\n{diff}");
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn execute_auth(args: AuthArgs) -> Result<()> {
+    let (token, source) = TokenLookup::new(args.token, prompt::is_terminal()).find()?;
+    info!("Checking the GitHub token from {}...", source.label());
+
+    let user = GitHubClient::new(token.expose())?
+        .get_authenticated_user()
+        .await
+        .context("Failed to check the token with GitHub")?;
+    info!("✓ Authenticated as {}", user.login);
+
+    let path = auth::config_path()
+        .context("Cannot find a config directory: set HOME or XDG_CONFIG_HOME")?;
+    auth::save_token(&path, &token)?;
+    info!("✓ Saved the token to {}", path.display());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A directory holding `files`, with `.git` as a directory like in a clone
+    fn directory_with(files: &[&str]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for file in files {
+            if *file == ".git" {
+                fs::create_dir(dir.path().join(file)).unwrap();
+            } else {
+                fs::write(dir.path().join(file), "").unwrap();
+            }
+        }
+        dir
+    }
+
+    #[test]
+    fn test_no_hooks_skips_template_and_flag_hooks() {
+        let dir = directory_with(&[]);
+        fs::write(
+            dir.path().join(hooks::RSC_TOML),
+            "[hooks]\npost_generate = [\"cargo fmt\"]\n",
+        )
+        .unwrap();
+        let template = resolve_template(&TemplateArgs {
+            template: Some(dir.path().display().to_string()),
+            ..TemplateArgs::default()
+        })
+        .unwrap();
+        let args = HookArgs {
+            post_hook: vec!["touch CODEOWNERS".to_string()],
+            no_hooks: false,
+        };
+
+        assert_eq!(
+            post_generate_hooks(&template, &args).unwrap(),
+            ["cargo fmt", "touch CODEOWNERS"]
+        );
+        let skipped = HookArgs {
+            no_hooks: true,
+            ..args
+        };
+        assert!(post_generate_hooks(&template, &skipped).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_existing_output_accepts_clones_with_a_flag() {
+        let clone = directory_with(&[".git", "README.md", "LICENSE", ".gitignore"]);
+        let check = |force, allow_non_empty| existing_output(clone.path(), force, allow_non_empty);
+
+        assert_eq!(check(false, true).unwrap(), Some(Conflicts::Backup));
+        assert_eq!(check(true, false).unwrap(), Some(Conflicts::Overwrite));
+        assert_eq!(check(true, true).unwrap(), Some(Conflicts::Overwrite));
+        // Negative test: an existing directory is never written into unasked
+        let error = check(false, false).unwrap_err();
+        assert!(error.to_string().contains("already exists"), "{error}");
+
+        let missing = clone.path().join("new-svc");
+        assert_eq!(existing_output(&missing, false, false).unwrap(), None);
+    }
+
+    #[test]
+    fn test_existing_output_with_other_files_needs_force_alone() {
+        let dir = directory_with(&[".git", "README.md", "notes.txt", "Cargo.toml"]);
+
+        // Negative test: --allow-non-empty refuses anything a fresh clone would not hold
+        for force in [false, true] {
+            let error = existing_output(dir.path(), force, true).unwrap_err();
+            assert!(
+                error.to_string().contains(": Cargo.toml, notes.txt."),
+                "{error}"
+            );
+        }
+        assert_eq!(
+            existing_output(dir.path(), true, false).unwrap(),
+            Some(Conflicts::Overwrite)
+        );
+
+        // Negative test: a file cannot become the service's directory
+        let file = dir.path().join("notes.txt");
+        let error = existing_output(&file, true, false).unwrap_err();
+        assert!(error.to_string().contains("is not a directory"), "{error}");
+    }
+}
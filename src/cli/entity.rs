@@ -0,0 +1,322 @@
+//! Renaming the template's `Task` entity to the domain of the generated service
+//!
+//! The entity appears in identifiers, paths, SQL and docs in four forms, all derived
+//! from the singular and plural snake case names: `task`/`tasks`, `Task`/`Tasks` and
+//! `TASK`/`TASKS`. A form is only replaced where it starts and ends a word, so
+//! `multitasking` stays, while `task_id`, `PostgresTaskRepository` and `TASK_ID` change.
+
+use anyhow::{bail, Result};
+
+/// Singular name of the entity the template manages
+pub const TEMPLATE_ENTITY: &str = "task";
+
+/// Words the template already uses next to the entity; renaming to one of them would
+/// make two identifiers collide, like `task_id` and `user_id` in the task events
+const RESERVED_NAMES: &[&str] = &[
+    "user",
+    "event",
+    "change",
+    "status",
+    "priority",
+    "title",
+    "description",
+    "id",
+    "cache",
+    "health",
+    "config",
+    "error",
+    "option",
+    "result",
+    "string",
+    "vec",
+];
+
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Name of the entity a generated service manages, e.g. `order` or `line_item`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityName {
+    singular: String,
+    plural: String,
+}
+
+impl EntityName {
+    /// Validate a snake case name; the plural defaults to the English rules for regular nouns
+    pub fn new(singular: &str, plural: Option<&str>) -> Result<Self> {
+        let entity = Self::additional(singular, plural)?;
+        for name in [entity.singular(), entity.plural()] {
+            if RESERVED_NAMES
+                .iter()
+                .any(|reserved| name == *reserved || name == pluralize(reserved))
+            {
+                bail!("Entity name `{name}` is already used by the template");
+            }
+        }
+        Ok(entity)
+    }
+
+    /// Validate the name of an entity added next to the existing ones
+    ///
+    /// Unlike a rename, this may reuse the words of the template, such as `event`.
+    pub fn additional(singular: &str, plural: Option<&str>) -> Result<Self> {
+        let plural = plural.map_or_else(|| pluralize(singular), str::to_string);
+
+        for name in [singular, plural.as_str()] {
+            validate_name(name)?;
+        }
+        if singular == plural {
+            bail!("Entity plural `{plural}` must differ from the singular");
+        }
+
+        Ok(Self {
+            singular: singular.to_string(),
+            plural,
+        })
+    }
+
+    pub fn singular(&self) -> &str {
+        &self.singular
+    }
+
+    pub fn plural(&self) -> &str {
+        &self.plural
+    }
+
+    /// Type name of the entity, e.g. `LineItem`
+    pub fn singular_pascal(&self) -> String {
+        pascal_case(&self.singular)
+    }
+
+    pub fn plural_pascal(&self) -> String {
+        pascal_case(&self.plural)
+    }
+
+    /// Whether this is the template's own entity, so there is nothing to rename
+    pub fn is_template(&self) -> bool {
+        self.singular == TEMPLATE_ENTITY && self.plural == pluralize(TEMPLATE_ENTITY)
+    }
+
+    /// Replace every form of the template entity in `text`, leaving `protected` as it is
+    ///
+    /// Done in one pass, so a new name containing `task` is never renamed again.
+    pub fn rename(&self, text: &str, protected: &[&str]) -> String {
+        let template = pluralize(TEMPLATE_ENTITY);
+        let replacements = [
+            (
+                Case::Pascal,
+                pascal_case(&template),
+                pascal_case(&self.plural),
+            ),
+            (
+                Case::Pascal,
+                pascal_case(TEMPLATE_ENTITY),
+                pascal_case(&self.singular),
+            ),
+            (Case::Lower, template.clone(), self.plural.clone()),
+            (
+                Case::Lower,
+                TEMPLATE_ENTITY.to_string(),
+                self.singular.clone(),
+            ),
+            (
+                Case::Upper,
+                template.to_uppercase(),
+                self.plural.to_uppercase(),
+            ),
+            (
+                Case::Upper,
+                TEMPLATE_ENTITY.to_uppercase(),
+                self.singular.to_uppercase(),
+            ),
+        ];
+
+        let mut renamed = String::with_capacity(text.len());
+        let mut rest = text;
+        'scan: while let Some(c) = rest.chars().next() {
+            if let Some(token) = protected.iter().find(|token| rest.starts_with(**token)) {
+                renamed.push_str(token);
+                rest = &rest[token.len()..];
+                continue;
+            }
+
+            let previous = renamed.chars().next_back();
+            for (case, from, to) in &replacements {
+                if rest.starts_with(from.as_str())
+                    && case.starts_word(previous)
+                    && case.ends_word(rest[from.len()..].chars().next())
+                {
+                    renamed.push_str(to);
+                    rest = &rest[from.len()..];
+                    continue 'scan;
+                }
+            }
+
+            renamed.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+        renamed
+    }
+}
+
+/// How a form of the entity is delimited from the surrounding word
+#[derive(Clone, Copy)]
+enum Case {
+    /// `task`: starts after a non-letter, as in `create_task`
+    Lower,
+    /// `Task`: starts anywhere, as in `PostgresTaskRepository`
+    Pascal,
+    /// `TASK`: delimited by anything but capitals, as in `TASK_ID`
+    Upper,
+}
+
+impl Case {
+    fn starts_word(self, previous: Option<char>) -> bool {
+        match self {
+            Self::Lower => !previous.is_some_and(|c| c.is_ascii_alphabetic()),
+            Self::Pascal => true,
+            Self::Upper => !previous.is_some_and(|c| c.is_ascii_uppercase()),
+        }
+    }
+
+    fn ends_word(self, next: Option<char>) -> bool {
+        match self {
+            Self::Lower | Self::Pascal => !next.is_some_and(|c| c.is_ascii_lowercase()),
+            Self::Upper => !next.is_some_and(|c| c.is_ascii_uppercase()),
+        }
+    }
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    let is_snake_case = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && !name.contains("__")
+        && !name.ends_with('_');
+    if !is_snake_case {
+        bail!("Entity name `{name}` must be snake case, like `order` or `line_item`");
+    }
+    if RUST_KEYWORDS.contains(&name) {
+        bail!("Entity name `{name}` is a Rust keyword");
+    }
+    Ok(())
+}
+
+/// Plural of a regular English noun: `order` → `orders`, `box` → `boxes`,
+/// `category` → `categories`
+fn pluralize(singular: &str) -> String {
+    if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|suffix| singular.ends_with(suffix))
+    {
+        return format!("{singular}es");
+    }
+    if let Some(stem) = singular.strip_suffix('y') {
+        if !stem.ends_with(['a', 'e', 'i', 'o', 'u']) {
+            return format!("{stem}ies");
+        }
+    }
+    format!("{singular}s")
+}
+
+/// `line_item` → `LineItem`
+fn pascal_case(snake: &str) -> String {
+    snake
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_ascii_uppercase().to_string() + chars.as_str()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_replaces_every_form_at_word_boundaries() {
+        let entity = EntityName::new("line_item", None).unwrap();
+
+        let renamed = entity.rename(
+            "struct PostgresTaskRepository; fn create_task(task_id: TaskId) -> Tasks {}\n\
+             CREATE TABLE tasks; const TASK_CHANGES: &str = \"/tasks/{id}\"; task1 taskId\n\
+             multitasking subtasks Taskbar TASKS",
+            &[],
+        );
+
+        assert_eq!(
+            renamed,
+            "struct PostgresLineItemRepository; fn create_line_item(line_item_id: LineItemId) -> LineItems {}\n\
+             CREATE TABLE line_items; const LINE_ITEM_CHANGES: &str = \"/line_items/{id}\"; line_item1 line_itemId\n\
+             multitasking subtasks Taskbar LINE_ITEMS"
+        );
+    }
+
+    #[test]
+    fn test_rename_keeps_protected_tokens_and_does_not_rename_twice() {
+        let entity = EntityName::new("task_item", None).unwrap();
+
+        let renamed = entity.rename(
+            "use tokio::task::JoinHandle; tokio::task_local! {} my_task",
+            &["tokio::task", "task_local!", "my_task"],
+        );
+
+        assert_eq!(
+            renamed,
+            "use tokio::task::JoinHandle; tokio::task_local! {} my_task"
+        );
+        assert_eq!(entity.rename("task tasks", &[]), "task_item task_items");
+    }
+
+    #[test]
+    fn test_plural_follows_english_rules_unless_given() {
+        for (singular, plural) in [
+            ("order", "orders"),
+            ("invoice", "invoices"),
+            ("address", "addresses"),
+            ("box_batch", "box_batches"),
+            ("category", "categories"),
+            ("survey", "surveys"),
+        ] {
+            assert_eq!(EntityName::new(singular, None).unwrap().plural(), plural);
+        }
+        assert_eq!(
+            EntityName::new("person", Some("people")).unwrap().plural(),
+            "people"
+        );
+        assert!(EntityName::new("task", None).unwrap().is_template());
+        assert_eq!(
+            EntityName::additional("user", None)
+                .unwrap()
+                .plural_pascal(),
+            "Users"
+        );
+    }
+
+    #[test]
+    fn test_invalid_entity_names_are_rejected() {
+        // Negative test: Not snake case, keywords, and names the template already uses
+        for (singular, plural, error) in [
+            ("Order", None, "must be snake case"),
+            ("line-item", None, "must be snake case"),
+            ("2fa", None, "must be snake case"),
+            ("line__item", None, "must be snake case"),
+            ("type", None, "is a Rust keyword"),
+            ("user", None, "already used by the template"),
+            ("stat", Some("status"), "already used by the template"),
+            ("sheep", Some("sheep"), "must differ from the singular"),
+        ] {
+            let message = EntityName::new(singular, plural).unwrap_err().to_string();
+            assert!(message.contains(error), "{singular}: {message}");
+        }
+    }
+}
@@ -0,0 +1,536 @@
+//! Adding an entity to a generated service
+//!
+//! The new entity follows the layers of the template's tasks with a name and a description:
+//! model and operations, repository trait, Postgres repository, CRUD handlers, request and
+//! response models, and a migration. Its modules are declared in sorted order; its routes
+//! and OpenAPI entries go above the `// <generate:NAME>` markers the template leaves in
+//! `src/api/mod.rs` and `src/api/docs.rs`.
+
+use anyhow::{bail, Context, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::cli::{
+    entity::EntityName,
+    generator::{
+        strip_attributes, strip_derives, strip_feature_regions, SWAGGER_ATTRIBUTES, SWAGGER_DERIVES,
+    },
+};
+
+/// Layout of generated services this version can add code to
+///
+/// Bump when a generated service no longer has a file or marker the generator relies on.
+pub const PROJECT_LAYOUT: u32 = 1;
+
+/// `Cargo.toml` section marking a service generated by rsc
+const METADATA_SECTION: &str = "[package.metadata.rsc]";
+
+/// Source of the OpenAPI spec, left out of services generated without Swagger
+const DOCS_FILE: &str = "src/api/docs.rs";
+
+/// Files of a new entity, as (path, template); both name the entity with placeholders
+const FILE_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "src/domain/{{entity}}/mod.rs",
+        include_str!("templates/entity/domain_mod.rs"),
+    ),
+    (
+        "src/domain/{{entity}}/models.rs",
+        include_str!("templates/entity/domain_models.rs"),
+    ),
+    (
+        "src/domain/{{entity}}/operations.rs",
+        include_str!("templates/entity/domain_operations.rs"),
+    ),
+    (
+        "src/domain/interfaces/{{entity}}_repository.rs",
+        include_str!("templates/entity/repository.rs"),
+    ),
+    (
+        "src/infrastructure/{{entity}}.rs",
+        include_str!("templates/entity/postgres.rs"),
+    ),
+    (
+        "src/api/{{entities}}/mod.rs",
+        include_str!("templates/entity/api_mod.rs"),
+    ),
+    (
+        "src/api/{{entities}}/handlers.rs",
+        include_str!("templates/entity/api_handlers.rs"),
+    ),
+    (
+        "src/api/models/{{entities}}.rs",
+        include_str!("templates/entity/api_models.rs"),
+    ),
+];
+
+const MIGRATION_TEMPLATE: &str = include_str!("templates/entity/migration.sql");
+
+/// Modules of a new entity, as (parent module file, module)
+const MODULE_DECLARATIONS: &[(&str, &str)] = &[
+    ("src/domain/mod.rs", "{{entity}}"),
+    ("src/domain/interfaces/mod.rs", "{{entity}}_repository"),
+    ("src/infrastructure/mod.rs", "{{entity}}"),
+    ("src/api/mod.rs", "{{entities}}"),
+    ("src/api/models/mod.rs", "{{entities}}"),
+];
+
+/// Lines registering a new entity, as (file, marker, line)
+///
+/// Each goes above the `// <generate:MARKER>` line, at its indentation, so entities keep
+/// the order they were added in.
+const MARKED_LINES: &[(&str, &str, &str)] = &[
+    (
+        "src/api/mod.rs",
+        "routes",
+        "let api_routes = api_routes.merge({{entities}}::routes());",
+    ),
+    (
+        DOCS_FILE,
+        "paths",
+        "crate::api::{{entities}}::handlers::get_{{entity}}_handler,",
+    ),
+    (
+        DOCS_FILE,
+        "paths",
+        "crate::api::{{entities}}::handlers::list_{{entities}}_handler,",
+    ),
+    (
+        DOCS_FILE,
+        "paths",
+        "crate::api::{{entities}}::handlers::create_{{entity}}_handler,",
+    ),
+    (
+        DOCS_FILE,
+        "paths",
+        "crate::api::{{entities}}::handlers::update_{{entity}}_handler,",
+    ),
+    (
+        DOCS_FILE,
+        "paths",
+        "crate::api::{{entities}}::handlers::delete_{{entity}}_handler,",
+    ),
+    (
+        DOCS_FILE,
+        "schemas",
+        "crate::api::models::{{entities}}::{{Entity}}Response,",
+    ),
+    (
+        DOCS_FILE,
+        "schemas",
+        "crate::api::models::{{entities}}::{{Entity}}Request,",
+    ),
+    (
+        DOCS_FILE,
+        "tags",
+        "(name = \"{{entities}}\", description = \"{{Entity}} management endpoints\"),",
+    ),
+];
+
+/// Section appended to the `Cargo.toml` of a generated service
+pub fn project_metadata() -> String {
+    format!("\n{METADATA_SECTION}\n# Checked by `rsc generate`\nlayout = {PROJECT_LAYOUT}\n")
+}
+
+/// Files written by [`EntityGenerator::generate`], relative to the project
+#[derive(Debug, Default)]
+pub struct GeneratedEntity {
+    pub created: Vec<PathBuf>,
+    pub modified: Vec<PathBuf>,
+}
+
+pub struct EntityGenerator {
+    project_dir: PathBuf,
+    entity: EntityName,
+    /// Whether the service documents its API, so the entity is added to the spec
+    with_swagger: bool,
+    /// Version of the migration, e.g. `20250127000000`
+    migration_version: String,
+}
+
+impl EntityGenerator {
+    /// Check `project_dir` is a service this version of rsc can add `entity` to
+    pub fn new(project_dir: PathBuf, entity: EntityName) -> Result<Self> {
+        check_layout(&project_dir)?;
+
+        Ok(Self {
+            with_swagger: project_dir.join(DOCS_FILE).exists(),
+            migration_version: chrono::Utc::now().format("%Y%m%d%H%M%S").to_string(),
+            project_dir,
+            entity,
+        })
+    }
+
+    /// Write the entity's files and register them
+    ///
+    /// Every change is prepared before anything is written, so an existing module or a
+    /// missing marker leaves the project untouched.
+    pub fn generate(&self) -> Result<GeneratedEntity> {
+        let mut modified: Vec<(PathBuf, String)> = Vec::new();
+        for (file, module) in MODULE_DECLARATIONS {
+            let path = PathBuf::from(file);
+            let module = self.render(module);
+            let parent = path.parent().unwrap_or(Path::new(""));
+            for existing in [parent.join(format!("{module}.rs")), parent.join(&module)] {
+                if self.project_dir.join(&existing).exists() {
+                    bail!(
+                        "{} already exists; remove it or choose another entity name",
+                        existing.display()
+                    );
+                }
+            }
+
+            let content = self.current(&modified, &path)?;
+            let declared = declare_module(&content, &module)
+                .with_context(|| format!("Cannot declare module in {}", path.display()))?;
+            set(&mut modified, path, declared);
+        }
+
+        let mut created: Vec<(PathBuf, String)> = Vec::new();
+        for (path, template) in FILE_TEMPLATES {
+            let content = self.source(&self.render(template))?;
+            created.push((PathBuf::from(self.render(path)), content));
+        }
+        created.push((self.migration_path()?, self.render(MIGRATION_TEMPLATE)));
+
+        for (file, marker, line) in MARKED_LINES {
+            let path = PathBuf::from(file);
+            if *file == DOCS_FILE && !self.with_swagger {
+                continue;
+            }
+            let content = self.current(&modified, &path)?;
+            let inserted = insert_above_marker(&content, marker, &self.render(line))
+                .with_context(|| format!("Cannot register the entity in {}", path.display()))?;
+            set(&mut modified, path, inserted);
+        }
+
+        for (path, content) in created.iter().chain(&modified) {
+            let path = self.project_dir.join(path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create directory {:?}", parent))?;
+            }
+            fs::write(&path, content).with_context(|| format!("Failed to write {:?}", path))?;
+        }
+
+        Ok(GeneratedEntity {
+            created: created.into_iter().map(|(path, _)| path).collect(),
+            modified: modified.into_iter().map(|(path, _)| path).collect(),
+        })
+    }
+
+    /// Format the created Rust files with rustfmt
+    ///
+    /// The templates are formatted for a short name; a longer one can move line breaks.
+    /// Only new files are passed, so the rest of the project keeps its formatting.
+    pub fn format(&self, generated: &GeneratedEntity) -> Result<()> {
+        let files: Vec<&PathBuf> = generated
+            .created
+            .iter()
+            .filter(|path| path.extension().is_some_and(|extension| extension == "rs"))
+            .collect();
+        let output = Command::new("rustfmt")
+            .args(["--edition", &self.edition()?])
+            .args(files)
+            .current_dir(&self.project_dir)
+            .output()
+            .context("Failed to execute rustfmt")?;
+
+        if !output.status.success() {
+            bail!(
+                "rustfmt failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+
+    /// Fill the entity's names into a template
+    fn render(&self, template: &str) -> String {
+        let words = |name: &str| name.replace('_', " ");
+        template
+            .replace("{{entity}}", self.entity.singular())
+            .replace("{{entities}}", self.entity.plural())
+            .replace("{{Entity}}", &self.entity.singular_pascal())
+            .replace("{{Entities}}", &self.entity.plural_pascal())
+            .replace("{{entity_words}}", &words(self.entity.singular()))
+            .replace("{{entities_words}}", &words(self.entity.plural()))
+    }
+
+    /// Keep or drop the Swagger parts of a rendered Rust template
+    fn source(&self, rendered: &str) -> Result<String> {
+        if self.with_swagger {
+            return strip_feature_regions(rendered, &[]);
+        }
+        let source = strip_feature_regions(rendered, &["swagger"])?;
+        Ok(strip_derives(
+            &strip_attributes(&source, SWAGGER_ATTRIBUTES),
+            SWAGGER_DERIVES,
+        ))
+    }
+
+    /// `migrations/<version>_create_<entities>_table.sql`, unless the table is created already
+    fn migration_path(&self) -> Result<PathBuf> {
+        let suffix = format!("_create_{}_table.sql", self.entity.plural());
+        let migrations_dir = self.project_dir.join("migrations");
+        if !migrations_dir.is_dir() {
+            bail!(
+                "No migrations directory: entities are stored in Postgres, \
+                 so services generated with `--database sqlite` cannot add them"
+            );
+        }
+        for entry in fs::read_dir(&migrations_dir)
+            .with_context(|| format!("Failed to read {:?}", migrations_dir))?
+        {
+            let name = entry.context("Failed to read directory entry")?.file_name();
+            if name.to_string_lossy().ends_with(&suffix) {
+                bail!(
+                    "migrations/{} already creates the `{}` table",
+                    name.to_string_lossy(),
+                    self.entity.plural()
+                );
+            }
+        }
+
+        Ok(PathBuf::from("migrations").join(format!("{}{suffix}", self.migration_version)))
+    }
+
+    /// Content of `path` with the changes prepared so far
+    fn current(&self, modified: &[(PathBuf, String)], path: &Path) -> Result<String> {
+        if let Some((_, content)) = modified.iter().find(|(modified, _)| modified == path) {
+            return Ok(content.clone());
+        }
+        let path = self.project_dir.join(path);
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))
+    }
+
+    /// Rust edition of the project, for rustfmt
+    fn edition(&self) -> Result<String> {
+        let cargo_toml = fs::read_to_string(self.project_dir.join("Cargo.toml"))
+            .context("Failed to read Cargo.toml")?;
+        Ok(cargo_toml
+            .lines()
+            .find_map(|line| line.strip_prefix("edition = "))
+            .map_or("2021", |edition| edition.trim_matches('"'))
+            .to_string())
+    }
+}
+
+fn set(modified: &mut Vec<(PathBuf, String)>, path: PathBuf, content: String) {
+    match modified.iter_mut().find(|(modified, _)| *modified == path) {
+        Some((_, existing)) => *existing = content,
+        None => modified.push((path, content)),
+    }
+}
+
+/// Fail unless `project_dir` was generated with a layout this version knows
+fn check_layout(project_dir: &Path) -> Result<()> {
+    let cargo_toml_path = project_dir.join("Cargo.toml");
+    let cargo_toml = fs::read_to_string(&cargo_toml_path).with_context(|| {
+        format!(
+            "No Cargo.toml in {}; run rsc generate from the root of a generated service",
+            project_dir.display()
+        )
+    })?;
+
+    let layout = cargo_toml
+        .lines()
+        .skip_while(|line| line.trim() != METADATA_SECTION)
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with('['))
+        .find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "layout").then(|| value.trim().to_string())
+        });
+
+    match layout {
+        None => bail!(
+            "{} was not generated by rsc: Cargo.toml has no {METADATA_SECTION} layout",
+            project_dir.display()
+        ),
+        Some(layout) if layout != PROJECT_LAYOUT.to_string() => bail!(
+            "{} has layout {layout}, but this rsc supports layout {PROJECT_LAYOUT}",
+            project_dir.display()
+        ),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Add `pub mod <module>;` among the module declarations, keeping them sorted
+fn declare_module(content: &str, module: &str) -> Result<String> {
+    fn declared(line: &str) -> Option<&str> {
+        line.strip_prefix("pub mod ")?.strip_suffix(';')
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.iter().any(|line| declared(line) == Some(module)) {
+        bail!("module `{module}` is already declared");
+    }
+
+    let Some(last) = lines.iter().rposition(|line| declared(line).is_some()) else {
+        bail!("no `pub mod` declarations to add `{module}` to");
+    };
+    let position = lines
+        .iter()
+        .position(|line| declared(line).is_some_and(|existing| existing > module))
+        .unwrap_or(last + 1);
+
+    let declaration = format!("pub mod {module};");
+    let mut result: Vec<&str> = lines;
+    result.insert(position, &declaration);
+    Ok(result.join("\n") + "\n")
+}
+
+/// Insert `line` above the `// <generate:<marker>>` line, at its indentation
+fn insert_above_marker(content: &str, marker: &str, line: &str) -> Result<String> {
+    let tag = format!("// <generate:{marker}>");
+    let Some(marker_line) = content.lines().find(|candidate| candidate.trim() == tag) else {
+        bail!("no `{tag}` marker; add it where rsc generate should register entities");
+    };
+    let indentation = &marker_line[..marker_line.len() - marker_line.trim_start().len()];
+
+    let mut result = String::with_capacity(content.len() + line.len());
+    for existing in content.lines() {
+        if existing == marker_line {
+            result.push_str(indentation);
+            result.push_str(line);
+            result.push('\n');
+        }
+        result.push_str(existing);
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::generator::tests::{assert_compiles, generate_service};
+
+    #[test]
+    fn test_declare_module_keeps_declarations_sorted() {
+        let content = "// Modules\n\npub mod admin;\npub mod models;\npub mod tasks;\n";
+
+        assert_eq!(
+            declare_module(content, "invoices").unwrap(),
+            "// Modules\n\npub mod admin;\npub mod invoices;\npub mod models;\npub mod tasks;\n"
+        );
+        assert_eq!(
+            declare_module(content, "users").unwrap(),
+            "// Modules\n\npub mod admin;\npub mod models;\npub mod tasks;\npub mod users;\n"
+        );
+
+        // Negative test: A module declared already, and a file without declarations
+        let error = declare_module(content, "models").unwrap_err();
+        assert!(error.to_string().contains("already declared"), "{error}");
+        assert!(declare_module("fn main() {}\n", "invoices").is_err());
+    }
+
+    #[test]
+    fn test_insert_above_marker_keeps_order_and_indentation() {
+        let content = "    let a = 1;\n    // <generate:routes>\n}\n";
+
+        let once = insert_above_marker(content, "routes", "let b = 2;").unwrap();
+        let twice = insert_above_marker(&once, "routes", "let c = 3;").unwrap();
+
+        assert_eq!(
+            twice,
+            "    let a = 1;\n    let b = 2;\n    let c = 3;\n    // <generate:routes>\n}\n"
+        );
+        // Negative test: The marker was removed
+        assert!(insert_above_marker(content, "paths", "x,").is_err());
+    }
+
+    #[test]
+    fn test_only_generated_services_are_accepted() {
+        // Negative test: No Cargo.toml, no marker, and a newer layout
+        let project = tempfile::tempdir().unwrap();
+        let entity = || EntityName::additional("invoice", None).unwrap();
+        let cargo_toml = project.path().join("Cargo.toml");
+
+        for (content, error) in [
+            (None, "No Cargo.toml"),
+            (
+                Some("[package]\nname = \"svc\"\n"),
+                "was not generated by rsc",
+            ),
+            (
+                Some("[package]\nname = \"svc\"\n\n[package.metadata.rsc]\nlayout = 99\n"),
+                "has layout 99",
+            ),
+        ] {
+            if let Some(content) = content {
+                fs::write(&cargo_toml, content).unwrap();
+            }
+            let Err(message) = EntityGenerator::new(project.path().to_path_buf(), entity()) else {
+                panic!("{content:?} should be rejected");
+            };
+            assert!(message.to_string().contains(error), "{message}");
+        }
+
+        fs::write(&cargo_toml, format!("[package]\n{}", project_metadata())).unwrap();
+        assert!(EntityGenerator::new(project.path().to_path_buf(), entity()).is_ok());
+    }
+
+    #[test]
+    fn test_generated_entity_compiles_and_is_not_overwritten() {
+        let (_output, project_dir) = generate_service("billing-svc", false, false, false, "task");
+        let generator = |name: &str| {
+            EntityGenerator::new(
+                project_dir.clone(),
+                EntityName::additional(name, None).unwrap(),
+            )
+            .unwrap()
+        };
+
+        // Act: Add an entity
+        let line_items = generator("line_item");
+        let generated = line_items.generate().unwrap();
+        line_items.format(&generated).unwrap();
+
+        // Assert: Every layer exists and is registered, and the service compiles
+        assert!(generated.created.iter().any(|path| path
+            .to_string_lossy()
+            .ends_with("_create_line_items_table.sql")));
+        let api = fs::read_to_string(project_dir.join("src/api/mod.rs")).unwrap();
+        assert!(api.contains("pub mod line_items;"), "{api}");
+        assert!(
+            api.contains("api_routes.merge(line_items::routes())"),
+            "{api}"
+        );
+        let docs = fs::read_to_string(project_dir.join(DOCS_FILE)).unwrap();
+        assert!(docs.contains("LineItemResponse"), "{docs}");
+        assert_compiles(&project_dir);
+
+        // Act: Add it again
+        let before = fs::read_to_string(project_dir.join("src/api/mod.rs")).unwrap();
+        let error = generator("line_item").generate().unwrap_err();
+
+        // Assert: Nothing is overwritten
+        assert!(error.to_string().contains("already exists"), "{error}");
+        assert_eq!(
+            fs::read_to_string(project_dir.join("src/api/mod.rs")).unwrap(),
+            before
+        );
+    }
+
+    #[test]
+    fn test_generated_entity_without_swagger_compiles() {
+        let (_output, project_dir) = generate_service("plain-svc", false, false, true, "order");
+
+        let generator = EntityGenerator::new(
+            project_dir.clone(),
+            EntityName::additional("invoice", None).unwrap(),
+        )
+        .unwrap();
+        generator.generate().unwrap();
+
+        let handlers =
+            fs::read_to_string(project_dir.join("src/api/invoices/handlers.rs")).unwrap();
+        assert!(!handlers.contains("utoipa"), "{handlers}");
+        assert_compiles(&project_dir);
+    }
+}
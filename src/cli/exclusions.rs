@@ -0,0 +1,222 @@
+//! Template paths left out of generated services besides the built-in ones
+//!
+//! A template lists them in an `.rscignore` file in its root, and `--exclude` and
+//! `--include` add to or override that list for one run. All patterns follow gitignore
+//! syntax and are relative to the template root.
+
+use anyhow::{Context, Result};
+use ignore::{
+    gitignore::{Gitignore, GitignoreBuilder},
+    Match,
+};
+use std::{fmt, path::Path};
+
+/// File in the template root listing paths to leave out
+pub const RSCIGNORE: &str = ".rscignore";
+
+/// Why a template path is left out of the service
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Exclusion {
+    /// Never part of a service, such as `.git`, `target` or the CLI itself
+    BuiltIn,
+    /// Matched by this pattern in `.rscignore`
+    Rscignore(String),
+    /// Matched by this `--exclude` pattern
+    Flag(String),
+}
+
+impl fmt::Display for Exclusion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BuiltIn => f.write_str("built in"),
+            Self::Rscignore(pattern) => write!(f, "{RSCIGNORE}: {pattern}"),
+            Self::Flag(pattern) => write!(f, "--exclude {pattern}"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Exclusions {
+    rscignore: Gitignore,
+    exclude: Gitignore,
+    include: Gitignore,
+    /// The `--exclude` and `--include` patterns, as given
+    flags: (Vec<String>, Vec<String>),
+}
+
+impl Exclusions {
+    /// The `.rscignore` of the template at `root`, if it has one, with `exclude` patterns
+    /// on top and `include` patterns overriding both
+    pub fn load(root: &Path, exclude: &[String], include: &[String]) -> Result<Self> {
+        let path = root.join(RSCIGNORE);
+        let mut rscignore = GitignoreBuilder::new(root);
+        if path.exists() {
+            if let Some(error) = rscignore.add(&path) {
+                return Err(error).with_context(|| format!("Invalid pattern in {:?}", path));
+            }
+        }
+
+        Ok(Self {
+            rscignore: rscignore
+                .build()
+                .with_context(|| format!("Invalid pattern in {:?}", path))?,
+            exclude: patterns(root, exclude, "--exclude")?,
+            include: patterns(root, include, "--include")?,
+            flags: (exclude.to_vec(), include.to_vec()),
+        })
+    }
+
+    /// The `--exclude` patterns, then the `--include` patterns, these exclusions were
+    /// loaded with
+    pub fn flags(&self) -> (&[String], &[String]) {
+        (&self.flags.0, &self.flags.1)
+    }
+
+    /// Why `relative`, a path in the template, is left out, or `None` if it is copied
+    ///
+    /// A path inside a directory a pattern matches is matched too.
+    pub fn exclusion(&self, relative: &Path, is_dir: bool) -> Option<Exclusion> {
+        let matched =
+            |ignore: &Gitignore| match ignore.matched_path_or_any_parents(relative, is_dir) {
+                Match::Ignore(glob) => Some(glob.original().to_string()),
+                Match::None | Match::Whitelist(_) => None,
+            };
+
+        if matched(&self.include).is_some() {
+            return None;
+        }
+        matched(&self.exclude)
+            .map(Exclusion::Flag)
+            .or_else(|| matched(&self.rscignore).map(Exclusion::Rscignore))
+    }
+}
+
+/// Patterns given with `flag`
+fn patterns(root: &Path, patterns: &[String], flag: &str) -> Result<Gitignore> {
+    let mut builder = GitignoreBuilder::new(root);
+    for pattern in patterns {
+        builder
+            .add_line(None, pattern)
+            .with_context(|| format!("Invalid {flag} pattern: {pattern}"))?;
+    }
+    builder
+        .build()
+        .with_context(|| format!("Invalid {flag} patterns"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn strings(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(|pattern| pattern.to_string()).collect()
+    }
+
+    /// A template root whose `.rscignore` holds `rscignore`
+    fn template(rscignore: &str) -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        fs::write(root.path().join(RSCIGNORE), rscignore).unwrap();
+        root
+    }
+
+    #[test]
+    fn test_rscignore_patterns_match_nested_paths() {
+        let root = template(
+            "# Internal notes\n\
+             docs/internal/\n\
+             *.scratch\n\
+             !keep.scratch\n\
+             /local\n\
+             src/**/fixtures/\n",
+        );
+        let exclusions = Exclusions::load(root.path(), &[], &[]).unwrap();
+        let exclusion = |path: &str| exclusions.exclusion(Path::new(path), false);
+        let rscignore = |pattern: &str| Some(Exclusion::Rscignore(pattern.to_string()));
+
+        assert_eq!(
+            exclusion("docs/internal/roadmap.md"),
+            rscignore("docs/internal/")
+        );
+        assert_eq!(
+            exclusion("docs/internal/2024/q1.md"),
+            rscignore("docs/internal/")
+        );
+        assert_eq!(exclusion("notes.scratch"), rscignore("*.scratch"));
+        assert_eq!(exclusion("src/api/notes.scratch"), rscignore("*.scratch"));
+        assert_eq!(exclusion("local/db.sqlite"), rscignore("/local"));
+        assert_eq!(
+            exclusion("src/domain/task/fixtures/task.json"),
+            rscignore("src/**/fixtures/")
+        );
+        // Negative test: similar paths elsewhere are copied
+        assert_eq!(exclusion("docs/guide.md"), None);
+        assert_eq!(exclusion("src/local/mod.rs"), None);
+        assert_eq!(exclusion("keep.scratch"), None);
+        assert_eq!(exclusion("tests/fixtures/task.json"), None);
+    }
+
+    #[test]
+    fn test_flags_compose_with_rscignore() {
+        let root = template("docs/internal/\n");
+        let exclusions = Exclusions::load(
+            root.path(),
+            &strings(&["*.md", "scripts/"]),
+            &strings(&["README.md", "docs/internal/public/"]),
+        )
+        .unwrap();
+        let exclusion = |path: &str| exclusions.exclusion(Path::new(path), false);
+
+        assert_eq!(
+            exclusion("AGENTS.md"),
+            Some(Exclusion::Flag("*.md".to_string()))
+        );
+        assert_eq!(
+            exclusion("scripts/git-hooks/pre-push"),
+            Some(Exclusion::Flag("scripts/".to_string()))
+        );
+        // --exclude is named before the .rscignore pattern it overlaps with
+        assert_eq!(
+            exclusion("docs/internal/roadmap.md"),
+            Some(Exclusion::Flag("*.md".to_string()))
+        );
+        assert_eq!(
+            exclusion("docs/internal/notes.txt"),
+            Some(Exclusion::Rscignore("docs/internal/".to_string()))
+        );
+        // --include wins over both
+        assert_eq!(exclusion("README.md"), None);
+        assert_eq!(exclusion("docs/internal/public/api.md"), None);
+    }
+
+    #[test]
+    fn test_template_without_rscignore_excludes_nothing() {
+        let root = tempfile::tempdir().unwrap();
+
+        let exclusions = Exclusions::load(root.path(), &[], &[]).unwrap();
+
+        assert_eq!(exclusions.exclusion(Path::new("src/lib.rs"), false), None);
+        assert_eq!(exclusions.exclusion(Path::new("docs"), true), None);
+    }
+
+    #[test]
+    fn test_invalid_patterns_are_rejected() {
+        // Negative test: a typo in a pattern must not silently copy everything
+        let error = Exclusions::load(
+            tempfile::tempdir().unwrap().path(),
+            &strings(&["docs/{internal"]),
+            &[],
+        )
+        .err()
+        .unwrap();
+        assert!(
+            error
+                .to_string()
+                .contains("Invalid --exclude pattern: docs/{internal"),
+            "{error}"
+        );
+
+        let root = template("src/{api\n");
+        assert!(Exclusions::load(root.path(), &[], &[]).is_err());
+    }
+}
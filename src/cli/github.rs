@@ -1,11 +1,113 @@
 use anyhow::{Context, Result};
-use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, USER_AGENT};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, AUTHORIZATION},
+    Method, RequestBuilder, Response, StatusCode,
+};
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{
+    config::HttpClientConfig,
+    infrastructure::{http::HttpClient, retry},
+};
+
+/// GitHub's REST API, which [`GitHubClient::new`] talks to
+pub const GITHUB_API_BASE: &str = "https://api.github.com";
+
+/// Sent with every request; GitHub rejects requests without a `User-Agent`
+const USER_AGENT_VALUE: &str = "rust-service-cli/1.0";
+
+/// How long a request may take before it fails, unless set with [`GitHubClient::with_timeout`]
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
 
 pub struct GitHubClient {
-    client: reqwest::Client,
+    client: HttpClient,
     token: String,
     api_base: String,
+    retry: RetryPolicy,
+    timeout: Duration,
+}
+
+/// How GitHub requests are retried after rate limiting and transient failures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts in total, the first included
+    pub attempts: u32,
+    /// Wait before the first retry, doubled for each retry after it
+    pub base_delay: Duration,
+    /// Longest wait; a rate limit resetting later than this fails instead
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 4,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// How long to wait before retrying after attempt number `attempt` got `status`, or
+    /// `None` to keep the response
+    ///
+    /// 429, 5xx and 403s that are rate limits are retried while attempts remain, after
+    /// `Retry-After`, the `X-RateLimit-Reset` of an exhausted limit or an exponential
+    /// backoff. Other statuses, such as 401 or 422, are final.
+    fn delay(
+        &self,
+        attempt: u32,
+        status: StatusCode,
+        headers: &HeaderMap,
+        now: SystemTime,
+    ) -> Option<Duration> {
+        if attempt >= self.attempts {
+            return None;
+        }
+
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+        let retry_after = header("retry-after").map(Duration::from_secs);
+        let exhausted = header("x-ratelimit-remaining") == Some(0);
+        let until_reset = header("x-ratelimit-reset")
+            .filter(|_| exhausted)
+            .map(|reset| {
+                let reset = UNIX_EPOCH + Duration::from_secs(reset);
+                // A second late, so the limit has surely been reset
+                reset.duration_since(now).unwrap_or_default() + Duration::from_secs(1)
+            });
+
+        let transient = matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::INTERNAL_SERVER_ERROR
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        );
+        let rate_limited = status == StatusCode::FORBIDDEN && (retry_after.is_some() || exhausted);
+        if !transient && !rate_limited {
+            return None;
+        }
+
+        let delay = retry_after
+            .or(until_reset)
+            .unwrap_or_else(|| self.backoff(attempt));
+        (delay <= self.max_delay).then_some(delay)
+    }
+
+    /// Exponential wait after attempt number `attempt`
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay)
+    }
 }
 
 #[derive(Serialize, Debug)]
@@ -17,6 +119,22 @@ struct CreateRepoRequest {
     auto_init: Option<bool>,
 }
 
+/// Topics `create` gives the repositories it makes
+pub const REPOSITORY_TOPICS: &[&str] = &["rust", "microservice"];
+
+#[derive(Serialize, Debug)]
+struct TopicsRequest<'a> {
+    names: &'a [&'a str],
+}
+
+#[derive(Serialize, Debug)]
+struct MergeSettingsRequest {
+    allow_squash_merge: bool,
+    allow_merge_commit: bool,
+    allow_rebase_merge: bool,
+    delete_branch_on_merge: bool,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct CreateRepoResponse {
     pub id: u64,
@@ -26,39 +144,108 @@ pub struct CreateRepoResponse {
     pub clone_url: String,
     pub ssh_url: String,
     pub private: bool,
+    pub owner: RepoOwner,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RepoOwner {
+    pub login: String,
+}
+
+/// Outcome of deleting a repository
+#[derive(Debug, PartialEq, Eq)]
+pub enum Deletion {
+    Deleted,
+    /// Refused with 403, usually because the token lacks the `delete_repo` scope
+    Forbidden,
+}
+
+/// The user a token belongs to
+#[derive(Debug)]
+pub struct AuthenticatedUser {
+    pub login: String,
+    /// Scopes of a classic token; `None` for fine-grained tokens, which have none
+    pub scopes: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct User {
+    login: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OrgMembership {
+    /// `active`, or `pending` until an invitation is accepted
+    pub state: String,
+    /// `admin` or `member`
+    pub role: String,
 }
 
 #[derive(Deserialize, Debug)]
-#[allow(dead_code)]
 struct GitHubError {
     message: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    errors: Option<Vec<serde_json::Value>>,
+    /// Details of a validation failure, such as a name that is already taken
+    #[serde(default)]
+    errors: Vec<GitHubErrorDetail>,
+}
+
+#[derive(Deserialize, Debug)]
+struct GitHubErrorDetail {
+    message: Option<String>,
 }
 
 impl GitHubClient {
     pub fn new(token: impl Into<String>) -> Result<Self> {
+        Self::with_api_base(token, GITHUB_API_BASE)
+    }
+
+    /// Client for the GitHub API at `api_base`, such as a GitHub Enterprise server or a
+    /// stand-in for tests
+    pub fn with_api_base(token: impl Into<String>, api_base: impl Into<String>) -> Result<Self> {
         let token = token.into();
         if token.is_empty() {
             anyhow::bail!("GitHub token cannot be empty");
         }
 
-        let mut headers = HeaderMap::new();
-        headers.insert(USER_AGENT, HeaderValue::from_static("rust-service-cli/1.0"));
-
-        let client = reqwest::Client::builder()
-            .default_headers(headers)
-            .timeout(std::time::Duration::from_secs(30))
+        // Retries are left to `send`, which knows GitHub's rate limits
+        let client = HttpClient::builder(&HttpClientConfig::default())
+            .user_agent(USER_AGENT_VALUE)
+            .retry(retry::RetryPolicy::NONE)
             .build()
             .context("Failed to create HTTP client")?;
 
         Ok(Self {
             client,
             token,
-            api_base: "https://api.github.com".to_string(),
+            api_base: api_base.into().trim_end_matches('/').to_string(),
+            retry: RetryPolicy::default(),
+            timeout: DEFAULT_TIMEOUT,
         })
     }
 
+    /// Retry requests as `retry` says instead of [`RetryPolicy::default`]
+    #[must_use]
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Fail each request not answered within `timeout`, instead of 30 seconds
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Endpoint creating a repository for `owner`: an organization when given as
+    /// `org/...`, otherwise the authenticated user
+    pub fn repositories_url(&self, owner: &str) -> String {
+        match owner.split_once('/') {
+            Some((org, _)) => format!("{}/orgs/{}/repos", self.api_base, org),
+            None => format!("{}/user/repos", self.api_base),
+        }
+    }
+
     pub async fn create_repository(
         &self,
         name: &str,
@@ -66,13 +253,7 @@ impl GitHubClient {
         private: bool,
         owner: &str,
     ) -> Result<CreateRepoResponse> {
-        let url = if owner.contains('/') {
-            let parts: Vec<&str> = owner.split('/').collect();
-            let org = parts[0];
-            format!("{}/orgs/{}/repos", self.api_base, org)
-        } else {
-            format!("{}/user/repos", self.api_base)
-        };
+        let url = self.repositories_url(owner);
 
         let request_body = CreateRepoRequest {
             name: name.to_string(),
@@ -82,83 +263,392 @@ impl GitHubClient {
         };
 
         let response = self
-            .client
-            .post(&url)
-            .header(
-                AUTHORIZATION,
-                HeaderValue::from_str(&format!("Bearer {}", self.token))
-                    .context("Invalid GitHub token format")?,
+            .send(self.request(Method::POST, &url)?.json(&request_body))
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+        response
+            .json()
+            .await
+            .context("Failed to parse GitHub API response")
+    }
+
+    /// Replace the topics of `owner/name` with `topics`
+    pub async fn set_topics(&self, owner: &str, name: &str, topics: &[&str]) -> Result<()> {
+        let url = format!("{}/repos/{}/{}/topics", self.api_base, owner, name);
+
+        let response = self
+            .send(
+                self.request(Method::PUT, &url)?
+                    .json(&TopicsRequest { names: topics }),
+            )
+            .await?;
+
+        success(response).await
+    }
+
+    /// Allow only squash merges into `owner/name` and delete branches once merged
+    pub async fn set_squash_merge_only(&self, owner: &str, name: &str) -> Result<()> {
+        let url = format!("{}/repos/{}/{}", self.api_base, owner, name);
+
+        let response = self
+            .send(
+                self.request(Method::PATCH, &url)?
+                    .json(&MergeSettingsRequest {
+                        allow_squash_merge: true,
+                        allow_merge_commit: false,
+                        allow_rebase_merge: false,
+                        delete_branch_on_merge: true,
+                    }),
             )
-            .header("Accept", "application/vnd.github.v3+json")
-            .json(&request_body)
-            .send()
+            .await?;
+
+        success(response).await
+    }
+
+    /// Require an approved pull request to change `branch` of `owner/name`, and forbid
+    /// force pushes to it and deleting it
+    ///
+    /// Admins may still bypass the rule. GitHub refuses protection for private
+    /// repositories on the free plan.
+    pub async fn protect_branch(&self, owner: &str, name: &str, branch: &str) -> Result<()> {
+        let url = format!(
+            "{}/repos/{}/{}/branches/{}/protection",
+            self.api_base, owner, name, branch
+        );
+        // Every key is required, even when null
+        let rule = serde_json::json!({
+            "required_status_checks": null,
+            "enforce_admins": false,
+            "required_pull_request_reviews": {
+                "dismiss_stale_reviews": true,
+                "required_approving_review_count": 1,
+            },
+            "restrictions": null,
+            "allow_force_pushes": false,
+            "allow_deletions": false,
+        });
+
+        let response = self
+            .send(self.request(Method::PUT, &url)?.json(&rule))
+            .await?;
+
+        success(response).await
+    }
+
+    /// Delete `owner/name`, such as a repository `create` could not push to
+    pub async fn delete_repository(&self, owner: &str, name: &str) -> Result<Deletion> {
+        let url = format!("{}/repos/{}/{}", self.api_base, owner, name);
+
+        let response = self.send(self.request(Method::DELETE, &url)?).await?;
+
+        match response.status() {
+            status if status.is_success() => Ok(Deletion::Deleted),
+            StatusCode::FORBIDDEN => Ok(Deletion::Forbidden),
+            _ => Err(api_error(response).await),
+        }
+    }
+
+    /// The user the token belongs to, with the scopes GitHub reports for it
+    pub async fn get_authenticated_user(&self) -> Result<AuthenticatedUser> {
+        let url = format!("{}/user", self.api_base);
+
+        let response = self.send(self.request(Method::GET, &url)?).await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+        let scopes = response
+            .headers()
+            .get("x-oauth-scopes")
+            .and_then(|scopes| scopes.to_str().ok())
+            .map(|scopes| {
+                scopes
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|scope| !scope.is_empty())
+                    .map(String::from)
+                    .collect()
+            });
+        let user: User = response
+            .json()
             .await
-            .context("Failed to send request to GitHub API")?;
+            .context("Failed to parse GitHub API response")?;
 
-        let status = response.status();
+        Ok(AuthenticatedUser {
+            login: user.login,
+            scopes,
+        })
+    }
+
+    /// Membership of `user` in `org`; `None` if there is none, or no such organization
+    pub async fn get_org_membership(&self, org: &str, user: &str) -> Result<Option<OrgMembership>> {
+        let url = format!("{}/orgs/{}/memberships/{}", self.api_base, org, user);
 
-        if status.is_success() {
-            let repo: CreateRepoResponse = response
+        let response = self.send(self.request(Method::GET, &url)?).await?;
+
+        match response.status() {
+            status if status.is_success() => response
                 .json()
                 .await
-                .context("Failed to parse GitHub API response")?;
-            Ok(repo)
-        } else {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            let error: GitHubError = serde_json::from_str(&error_text).unwrap_or(GitHubError {
-                message: error_text,
-                errors: None,
-            });
+                .map(Some)
+                .context("Failed to parse GitHub API response"),
+            StatusCode::NOT_FOUND => Ok(None),
+            _ => Err(api_error(response).await),
+        }
+    }
+
+    /// Whether `owner/name` exists and is visible to the token
+    pub async fn repository_exists(&self, owner: &str, name: &str) -> Result<bool> {
+        let url = format!("{}/repos/{}/{}", self.api_base, owner, name);
 
-            anyhow::bail!("GitHub API error ({}): {}", status.as_u16(), error.message);
+        let response = self.send(self.request(Method::GET, &url)?).await?;
+
+        match response.status() {
+            status if status.is_success() => Ok(true),
+            StatusCode::NOT_FOUND => Ok(false),
+            _ => Err(api_error(response).await),
         }
     }
 
-    pub async fn get_authenticated_user(&self) -> Result<serde_json::Value> {
-        let url = format!("{}/user", self.api_base);
+    /// Check that `create_repository(name, .., private, owner)` can succeed
+    ///
+    /// Each way it is known to fail gets its own error, before anything is generated.
+    /// Returns warnings about what else may not work.
+    pub async fn check_can_create(
+        &self,
+        owner: &str,
+        name: &str,
+        private: bool,
+    ) -> Result<Vec<String>> {
+        let mut warnings = Vec::new();
+        let user = self.get_authenticated_user().await.context(
+            "Failed to authenticate with the GitHub token; check it is valid and not expired",
+        )?;
 
-        let response = self
+        // Only classic tokens report scopes; fine-grained ones fail on creation instead
+        if let Some(scopes) = &user.scopes {
+            check_scopes(scopes, private)?;
+            if !scopes.iter().any(|scope| scope == "delete_repo") {
+                warnings.push(
+                    "The token lacks the `delete_repo` scope, so a repository that cannot be \
+                     pushed to will not be deleted again"
+                        .to_string(),
+                );
+            }
+        }
+
+        let repo_owner = match owner.split_once('/') {
+            Some((org, _)) => {
+                match self.get_org_membership(org, &user.login).await {
+                    Ok(Some(membership)) if membership.state == "active" => {}
+                    Ok(Some(_)) => anyhow::bail!(
+                        "'{}' is invited to the '{}' organization but has not joined: accept the \
+                         invitation at https://github.com/orgs/{}/invitation",
+                        user.login,
+                        org,
+                        org
+                    ),
+                    Ok(None) => anyhow::bail!(
+                        "'{}' is not a member of the '{}' organization, or it does not exist",
+                        user.login,
+                        org
+                    ),
+                    Err(error) => warnings.push(format!(
+                        "Could not check the membership of '{}' in '{}': {:#}",
+                        user.login, org, error
+                    )),
+                }
+                org
+            }
+            None if !owner.eq_ignore_ascii_case(&user.login) => anyhow::bail!(
+                "The token belongs to '{}', not '{}': use a token of '{}', or pass \
+                 `--github-user {}/` to create the repository in the '{}' organization",
+                user.login,
+                owner,
+                owner,
+                owner,
+                owner
+            ),
+            None => user.login.as_str(),
+        };
+
+        if self.repository_exists(repo_owner, name).await? {
+            anyhow::bail!(
+                "https://github.com/{}/{} already exists: choose another name or delete it first",
+                repo_owner,
+                name
+            );
+        }
+
+        Ok(warnings)
+    }
+
+    /// Send `request`, retrying rate limits and transient failures as the policy allows
+    ///
+    /// The last response is returned whatever its status.
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let mut attempt = 1;
+        loop {
+            let sent = self
+                .client
+                .send(
+                    request
+                        .try_clone()
+                        .context("GitHub request cannot be retried")?,
+                )
+                .await;
+            let (delay, cause) = match sent {
+                Ok(response) => {
+                    log_rate_limit(&response);
+                    let status = response.status();
+                    match self
+                        .retry
+                        .delay(attempt, status, response.headers(), SystemTime::now())
+                    {
+                        Some(delay) => (delay, status.to_string()),
+                        None => return Ok(response),
+                    }
+                }
+                // Nothing reached GitHub, so even creating a repository is safe to repeat
+                Err(error) if error.is_connect() && attempt < self.retry.attempts => {
+                    (self.retry.backoff(attempt), "connection failed".to_string())
+                }
+                // The request may have been handled, so it is not repeated
+                Err(error) if error.is_timeout() => {
+                    return Err(error).context(format!(
+                        "GitHub API did not respond within {}s",
+                        self.timeout.as_secs_f32()
+                    ))
+                }
+                Err(error) => return Err(error).context("Failed to send request to GitHub API"),
+            };
+
+            tracing::warn!(
+                "GitHub request failed ({}); retrying in {}s, attempt {} of {}",
+                cause,
+                delay.as_secs_f32(),
+                attempt + 1,
+                self.retry.attempts
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// `method` request to `url`, authenticated with the token
+    fn request(&self, method: Method, url: &str) -> Result<RequestBuilder> {
+        tracing::debug!("GitHub request: {} {}", method, url);
+        Ok(self
             .client
-            .get(&url)
+            .request(method, url)
+            .timeout(self.timeout)
             .header(
                 AUTHORIZATION,
                 HeaderValue::from_str(&format!("Bearer {}", self.token))
                     .context("Invalid GitHub token format")?,
             )
-            .header("Accept", "application/vnd.github.v3+json")
-            .send()
-            .await
-            .context("Failed to send request to GitHub API")?;
+            .header("Accept", "application/vnd.github.v3+json"))
+    }
+}
 
-        if response.status().is_success() {
-            let user: serde_json::Value = response
-                .json()
-                .await
-                .context("Failed to parse GitHub API response")?;
-            Ok(user)
-        } else {
-            let status = response.status();
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            anyhow::bail!("GitHub API error ({}): {}", status.as_u16(), error_text);
-        }
+/// Log the rate limit GitHub reports with a response, shown with `--verbose`
+fn log_rate_limit(response: &Response) {
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    };
+    if let (Some(remaining), Some(limit), Some(reset)) = (
+        header("x-ratelimit-remaining"),
+        header("x-ratelimit-limit"),
+        header("x-ratelimit-reset"),
+    ) {
+        tracing::debug!(
+            "GitHub rate limit: {} of {} requests left, reset at {} (Unix time)",
+            remaining,
+            limit,
+            reset
+        );
     }
 }
 
-pub fn get_github_token() -> Result<String> {
-    std::env::var("GITHUB_TOKEN").context(
-        "GITHUB_TOKEN environment variable not set. Please set it to your GitHub personal access token."
+/// Fail unless classic token `scopes` allow creating a repository of this visibility
+fn check_scopes(scopes: &[String], private: bool) -> Result<()> {
+    let has = |wanted: &str| scopes.iter().any(|scope| scope == wanted);
+    if has("repo") || (!private && has("public_repo")) {
+        return Ok(());
+    }
+
+    let (needed, visibility) = if private {
+        ("`repo`", "private")
+    } else {
+        ("`repo` or `public_repo`", "public")
+    };
+    anyhow::bail!(
+        "The token lacks the {} scope needed to create a {} repository: add it at \
+         https://github.com/settings/tokens",
+        needed,
+        visibility
     )
 }
 
+/// `Ok` for a successful response, whose body is not needed
+async fn success(response: Response) -> Result<()> {
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(api_error(response).await)
+    }
+}
+
+/// Error for an unsuccessful GitHub API response, with GitHub's message when it sent one
+async fn api_error(response: Response) -> anyhow::Error {
+    let status = response.status();
+    let error_text = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Unknown error".to_string());
+    let error: GitHubError = serde_json::from_str(&error_text).unwrap_or(GitHubError {
+        message: error_text,
+        errors: Vec::new(),
+    });
+
+    let details: Vec<_> = error
+        .errors
+        .iter()
+        .filter_map(|detail| detail.message.as_deref())
+        .collect();
+    if details.is_empty() {
+        anyhow::anyhow!("GitHub API error ({}): {}", status.as_u16(), error.message)
+    } else {
+        anyhow::anyhow!(
+            "GitHub API error ({}): {} ({})",
+            status.as_u16(),
+            error.message,
+            details.join("; ")
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::{
+        extract::Path,
+        http::Uri,
+        routing::{delete, get, post, put},
+        Json, Router,
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    };
+    use tokio::net::TcpListener;
+    use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
 
     #[test]
     fn test_github_client_creation() {
@@ -166,9 +656,641 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn test_repositories_url_distinguishes_users_and_organizations() {
+        let client = GitHubClient::new("test_token").unwrap();
+
+        assert_eq!(
+            client.repositories_url("myuser"),
+            "https://api.github.com/user/repos"
+        );
+        assert_eq!(
+            client.repositories_url("my-org/team"),
+            "https://api.github.com/orgs/my-org/repos"
+        );
+    }
+
     #[test]
     fn test_github_client_empty_token() {
         let client = GitHubClient::new("");
         assert!(client.is_err());
     }
+
+    /// A client talking to `app`, a local stand-in for the GitHub API
+    async fn stub(app: Router) -> GitHubClient {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        GitHubClient::with_api_base("test_token", format!("http://{address}")).unwrap()
+    }
+
+    /// A client whose repository deletions are answered with `status`
+    async fn client_answering(status: StatusCode) -> GitHubClient {
+        stub(Router::new().route(
+            "/repos/{owner}/{name}",
+            delete(
+                move |Path((owner, name)): Path<(String, String)>| async move {
+                    assert_eq!((owner.as_str(), name.as_str()), ("acme", "orders"));
+                    (status, r#"{"message": "Not Found"}"#)
+                },
+            ),
+        ))
+        .await
+    }
+
+    /// A client logged in as `octo` with classic token `scopes`, or a fine-grained token
+    ///
+    /// `octo` is a member of `acme`, invited to `pending-org`, and both `octo` and `acme`
+    /// have a repository named `taken`.
+    async fn client_of_octo(scopes: Option<&'static str>) -> GitHubClient {
+        stub(
+            Router::new()
+                .route(
+                    "/user",
+                    get(move || async move {
+                        let mut headers = HeaderMap::new();
+                        if let Some(scopes) = scopes {
+                            headers.insert("x-oauth-scopes", HeaderValue::from_static(scopes));
+                        }
+                        (headers, r#"{"login": "octo"}"#)
+                    }),
+                )
+                .route(
+                    "/orgs/{org}/memberships/{user}",
+                    get(|Path((org, user)): Path<(String, String)>| async move {
+                        match (org.as_str(), user.as_str()) {
+                            ("acme", "octo") => {
+                                (StatusCode::OK, r#"{"state": "active", "role": "member"}"#)
+                            }
+                            ("pending-org", "octo") => {
+                                (StatusCode::OK, r#"{"state": "pending", "role": "member"}"#)
+                            }
+                            _ => (StatusCode::NOT_FOUND, r#"{"message": "Not Found"}"#),
+                        }
+                    }),
+                )
+                .route(
+                    "/repos/{owner}/{name}",
+                    get(|Path((_, name)): Path<(String, String)>| async move {
+                        if name == "taken" {
+                            StatusCode::OK
+                        } else {
+                            StatusCode::NOT_FOUND
+                        }
+                    }),
+                ),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_delete_repository_reports_missing_scope() {
+        let client = client_answering(StatusCode::NO_CONTENT).await;
+        assert_eq!(
+            client.delete_repository("acme", "orders").await.unwrap(),
+            Deletion::Deleted
+        );
+
+        // Negative test: a token without `delete_repo` is an outcome, not an error
+        let client = client_answering(StatusCode::FORBIDDEN).await;
+        assert_eq!(
+            client.delete_repository("acme", "orders").await.unwrap(),
+            Deletion::Forbidden
+        );
+
+        let client = client_answering(StatusCode::NOT_FOUND).await;
+        let error = client
+            .delete_repository("acme", "orders")
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "GitHub API error (404): Not Found");
+    }
+
+    #[tokio::test]
+    async fn test_repository_settings_requests() {
+        // Objective: each setting is a single request with the body GitHub documents
+        // Arrange: a stand-in recording every request it receives
+        type Received = Arc<Mutex<Vec<(Method, String, serde_json::Value)>>>;
+        let received = Received::default();
+        let recorder = received.clone();
+        let client = stub(Router::new().fallback(
+            move |method: Method, uri: Uri, Json(body): Json<serde_json::Value>| async move {
+                recorder
+                    .lock()
+                    .unwrap()
+                    .push((method, uri.path().to_string(), body));
+                StatusCode::OK
+            },
+        ))
+        .await;
+
+        // Act
+        client
+            .set_topics("acme", "orders", REPOSITORY_TOPICS)
+            .await
+            .unwrap();
+        client
+            .set_squash_merge_only("acme", "orders")
+            .await
+            .unwrap();
+        client
+            .protect_branch("acme", "orders", "main")
+            .await
+            .unwrap();
+
+        // Assert
+        let received = received.lock().unwrap();
+        let (method, path, body) = &received[0];
+        assert_eq!(
+            (method, path.as_str()),
+            (&Method::PUT, "/repos/acme/orders/topics")
+        );
+        assert_eq!(body["names"], serde_json::json!(["rust", "microservice"]));
+
+        let (method, path, body) = &received[1];
+        assert_eq!(
+            (method, path.as_str()),
+            (&Method::PATCH, "/repos/acme/orders")
+        );
+        assert_eq!(body["allow_squash_merge"], true);
+        assert_eq!(body["allow_merge_commit"], false);
+        assert_eq!(body["allow_rebase_merge"], false);
+        assert_eq!(body["delete_branch_on_merge"], true);
+
+        let (method, path, body) = &received[2];
+        assert_eq!(
+            (method, path.as_str()),
+            (&Method::PUT, "/repos/acme/orders/branches/main/protection")
+        );
+        assert_eq!(
+            body["required_pull_request_reviews"]["required_approving_review_count"],
+            1
+        );
+        // GitHub rejects the rule unless the keys it does not use are sent as null
+        assert!(body["required_status_checks"].is_null());
+        assert!(body["restrictions"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_protect_branch_reports_github_refusal() {
+        // Negative test: a private repository on the free plan cannot be protected
+        let client = stub(Router::new().route(
+            "/repos/{owner}/{name}/branches/{branch}/protection",
+            put(|| async {
+                (
+                    StatusCode::FORBIDDEN,
+                    r#"{"message": "Upgrade to GitHub Pro or make this repository public to enable this feature."}"#,
+                )
+            }),
+        ))
+        .await;
+
+        let error = client
+            .protect_branch("acme", "orders", "main")
+            .await
+            .unwrap_err();
+
+        assert!(
+            error
+                .to_string()
+                .starts_with("GitHub API error (403): Upgrade"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_check_scopes_depends_on_visibility() {
+        let scopes = |scopes: &[&str]| scopes.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert!(check_scopes(&scopes(&["repo"]), true).is_ok());
+        assert!(check_scopes(&scopes(&["public_repo", "read:org"]), false).is_ok());
+        // Negative test: `public_repo` cannot create private repositories
+        let error = check_scopes(&scopes(&["public_repo"]), true).unwrap_err();
+        assert!(error.to_string().contains("`repo` scope"), "{error}");
+        assert!(check_scopes(&[], false).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_check_can_create_accepts_free_names() {
+        let client = client_of_octo(Some("repo, delete_repo")).await;
+
+        assert!(client
+            .check_can_create("octo", "orders", true)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(client
+            .check_can_create("Octo", "orders", false)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(client
+            .check_can_create("acme/platform", "orders", true)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let client = client_of_octo(Some("repo")).await;
+        let warnings = client
+            .check_can_create("octo", "orders", true)
+            .await
+            .unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("`delete_repo`"), "{warnings:?}");
+
+        // Fine-grained tokens report no scopes, so there is nothing to check
+        let client = client_of_octo(None).await;
+        assert!(client
+            .check_can_create("octo", "orders", true)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_check_can_create_explains_each_failure() {
+        // Negative test: every known cause gets its own error instead of a bare 403 or 422
+        let client = client_of_octo(Some("repo, delete_repo")).await;
+        let failure = |owner: &'static str, name: &'static str| {
+            let client = &client;
+            async move {
+                client
+                    .check_can_create(owner, name, true)
+                    .await
+                    .unwrap_err()
+                    .to_string()
+            }
+        };
+
+        assert!(failure("someone", "orders")
+            .await
+            .starts_with("The token belongs to 'octo', not 'someone'"));
+        assert!(failure("pending-org/team", "orders")
+            .await
+            .contains("https://github.com/orgs/pending-org/invitation"));
+        assert!(failure("nowhere/team", "orders")
+            .await
+            .contains("not a member of the 'nowhere' organization"));
+        assert!(failure("octo", "taken")
+            .await
+            .starts_with("https://github.com/octo/taken already exists"));
+        assert!(failure("acme/team", "taken")
+            .await
+            .starts_with("https://github.com/acme/taken already exists"));
+
+        let client = client_of_octo(Some("public_repo")).await;
+        let error = client
+            .check_can_create("octo", "orders", true)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("`repo` scope"), "{error}");
+    }
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|&(name, value)| (name.parse().unwrap(), HeaderValue::from_static(value)))
+            .collect()
+    }
+
+    #[test]
+    fn test_retry_policy_classifies_statuses() {
+        let policy = RetryPolicy::default();
+        let now = SystemTime::now();
+        let none = HeaderMap::new();
+
+        assert_eq!(
+            policy.delay(1, StatusCode::BAD_GATEWAY, &none, now),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            policy.delay(2, StatusCode::SERVICE_UNAVAILABLE, &none, now),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            policy.delay(3, StatusCode::TOO_MANY_REQUESTS, &none, now),
+            Some(Duration::from_secs(4))
+        );
+        // Negative test: the response to the last attempt is kept
+        assert_eq!(policy.delay(4, StatusCode::BAD_GATEWAY, &none, now), None);
+        // Negative test: retrying cannot fix bad credentials, permissions or input
+        for status in [
+            StatusCode::UNAUTHORIZED,
+            StatusCode::FORBIDDEN,
+            StatusCode::NOT_FOUND,
+            StatusCode::UNPROCESSABLE_ENTITY,
+        ] {
+            assert_eq!(policy.delay(1, status, &none, now), None, "{status}");
+        }
+    }
+
+    #[test]
+    fn test_retry_policy_honours_rate_limit_headers() {
+        let policy = RetryPolicy::default();
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let exhausted_until =
+            |reset| headers(&[("x-ratelimit-remaining", "0"), ("x-ratelimit-reset", reset)]);
+
+        // Secondary rate limit
+        assert_eq!(
+            policy.delay(
+                1,
+                StatusCode::FORBIDDEN,
+                &headers(&[("retry-after", "30")]),
+                now
+            ),
+            Some(Duration::from_secs(30))
+        );
+        // Primary rate limit, waited out until a second after its reset
+        assert_eq!(
+            policy.delay(
+                1,
+                StatusCode::FORBIDDEN,
+                &exhausted_until("1700000010"),
+                now
+            ),
+            Some(Duration::from_secs(11))
+        );
+        let mut both = exhausted_until("1700000010");
+        both.insert("retry-after", HeaderValue::from_static("5"));
+        assert_eq!(
+            policy.delay(1, StatusCode::TOO_MANY_REQUESTS, &both, now),
+            Some(Duration::from_secs(5))
+        );
+        // Negative test: a forbidden request with requests left is not rate limited
+        let remaining = headers(&[
+            ("x-ratelimit-remaining", "12"),
+            ("x-ratelimit-reset", "1700000010"),
+        ]);
+        assert_eq!(
+            policy.delay(1, StatusCode::FORBIDDEN, &remaining, now),
+            None
+        );
+        // Negative test: a limit resetting in an hour fails now rather than stall CI
+        assert_eq!(
+            policy.delay(
+                1,
+                StatusCode::FORBIDDEN,
+                &exhausted_until("1700003600"),
+                now
+            ),
+            None
+        );
+    }
+
+    /// A client creating repositories for `octo`, answered in turn by `responses`
+    ///
+    /// The last response repeats; the returned counter tracks the requests.
+    async fn client_creating(
+        responses: Vec<(StatusCode, HeaderMap)>,
+    ) -> (GitHubClient, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let app = Router::new().route(
+            "/user/repos",
+            post(move || {
+                let call = counter.fetch_add(1, Ordering::SeqCst);
+                let (status, headers) = responses[call.min(responses.len() - 1)].clone();
+                async move {
+                    let body = if status.is_success() {
+                        r#"{"id": 1, "name": "orders", "full_name": "octo/orders",
+                            "html_url": "https://github.com/octo/orders",
+                            "clone_url": "https://github.com/octo/orders.git",
+                            "ssh_url": "git@github.com:octo/orders.git",
+                            "private": true, "owner": {"login": "octo"}}"#
+                    } else {
+                        r#"{"message": "Repository creation failed."}"#
+                    };
+                    (status, headers, body)
+                }
+            }),
+        );
+
+        let client = stub(app).await.with_retry(RetryPolicy {
+            attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_secs(1),
+        });
+        (client, calls)
+    }
+
+    #[tokio::test]
+    async fn test_create_repository_retries_transient_failures() {
+        let (client, calls) = client_creating(vec![
+            (StatusCode::SERVICE_UNAVAILABLE, HeaderMap::new()),
+            (StatusCode::FORBIDDEN, headers(&[("retry-after", "0")])),
+            (StatusCode::CREATED, HeaderMap::new()),
+        ])
+        .await;
+
+        let repo = client
+            .create_repository("orders", None, true, "octo")
+            .await
+            .unwrap();
+
+        assert_eq!(repo.full_name, "octo/orders");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_create_repository_gives_up() {
+        // Negative test: validation errors fail on the first attempt
+        let (client, calls) =
+            client_creating(vec![(StatusCode::UNPROCESSABLE_ENTITY, HeaderMap::new())]).await;
+        let error = client
+            .create_repository("orders", None, true, "octo")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "GitHub API error (422): Repository creation failed."
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Negative test: an outage outlasting the attempts surfaces its status
+        let (client, calls) =
+            client_creating(vec![(StatusCode::BAD_GATEWAY, HeaderMap::new())]).await;
+        let error = client
+            .create_repository("orders", None, true, "octo")
+            .await
+            .unwrap_err();
+        assert!(
+            error.to_string().starts_with("GitHub API error (502)"),
+            "{error}"
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    /// A client for `server`, retrying quickly so failures surface fast
+    fn client_for(server: &MockServer) -> GitHubClient {
+        GitHubClient::with_api_base("test_token", server.uri())
+            .unwrap()
+            .with_retry(RetryPolicy {
+                attempts: 1,
+                ..RetryPolicy::default()
+            })
+    }
+
+    /// Body GitHub answers a created repository with
+    fn created_repository(owner: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": 1,
+            "name": "orders",
+            "full_name": format!("{owner}/orders"),
+            "html_url": format!("https://github.com/{owner}/orders"),
+            "clone_url": format!("https://github.com/{owner}/orders.git"),
+            "ssh_url": format!("git@github.com:{owner}/orders.git"),
+            "private": true,
+            "owner": { "login": owner },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_repository_for_a_user_sends_authenticated_request() {
+        // Objective: repositories of the token's user are created through /user/repos,
+        // authenticated and identified as GitHub requires
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/user/repos"))
+            .and(matchers::header("authorization", "Bearer test_token"))
+            .and(matchers::header("user-agent", "rust-service-cli/1.0"))
+            .and(matchers::header("accept", "application/vnd.github.v3+json"))
+            .and(matchers::body_partial_json(serde_json::json!({
+                "name": "orders",
+                "description": "Order service",
+                "private": true,
+                "auto_init": false,
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(created_repository("octo")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let repo = client_for(&server)
+            .create_repository("orders", Some("Order service"), true, "octo")
+            .await
+            .unwrap();
+
+        assert_eq!(repo.full_name, "octo/orders");
+        assert_eq!(repo.owner.login, "octo");
+    }
+
+    #[tokio::test]
+    async fn test_create_repository_for_an_organization() {
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/orgs/acme/repos"))
+            .and(matchers::header("authorization", "Bearer test_token"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(created_repository("acme")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let repo = client_for(&server)
+            .create_repository("orders", None, false, "acme/platform")
+            .await
+            .unwrap();
+
+        assert_eq!(repo.clone_url, "https://github.com/acme/orders.git");
+    }
+
+    /// Error `create_repository` returns when GitHub answers with `response`
+    async fn creation_error(response: ResponseTemplate) -> String {
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/user/repos"))
+            .respond_with(response)
+            .mount(&server)
+            .await;
+
+        format!(
+            "{:#}",
+            client_for(&server)
+                .create_repository("orders", None, true, "octo")
+                .await
+                .unwrap_err()
+        )
+    }
+
+    #[tokio::test]
+    async fn test_create_repository_reports_a_taken_name() {
+        // Negative test: GitHub's validation detail says why the name was refused
+        let error = creation_error(ResponseTemplate::new(422).set_body_json(serde_json::json!({
+            "message": "Repository creation failed.",
+            "errors": [{
+                "resource": "Repository",
+                "code": "custom",
+                "field": "name",
+                "message": "name already exists on this account",
+            }],
+            "documentation_url": "https://docs.github.com/rest/repos/repos#create-a-repository",
+        })))
+        .await;
+
+        assert_eq!(
+            error,
+            "GitHub API error (422): Repository creation failed. \
+             (name already exists on this account)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_repository_reports_bad_credentials() {
+        // Negative test: an invalid or expired token
+        let error = creation_error(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+            "message": "Bad credentials",
+            "documentation_url": "https://docs.github.com/rest",
+        })))
+        .await;
+
+        assert_eq!(error, "GitHub API error (401): Bad credentials");
+    }
+
+    #[tokio::test]
+    async fn test_create_repository_reports_malformed_bodies() {
+        // Negative test: an error body that is not GitHub's JSON is reported as sent
+        let error =
+            creation_error(ResponseTemplate::new(400).set_body_string("<html>Bad Request</html>"))
+                .await;
+        assert_eq!(error, "GitHub API error (400): <html>Bad Request</html>");
+
+        // Negative test: a success that cannot be read is not mistaken for a repository
+        let error =
+            creation_error(ResponseTemplate::new(201).set_body_string(r#"{"id": 1, "name":"#))
+                .await;
+        assert!(
+            error.starts_with("Failed to parse GitHub API response"),
+            "{error}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_repository_times_out() {
+        // Negative test: a request GitHub does not answer fails instead of hanging
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/user/repos"))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .set_body_json(created_repository("octo"))
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let error = client_for(&server)
+            .with_timeout(Duration::from_millis(100))
+            .create_repository("orders", None, true, "octo")
+            .await
+            .unwrap_err();
+
+        assert!(
+            error
+                .to_string()
+                .starts_with("GitHub API did not respond within 0.1s"),
+            "{error:#}"
+        );
+    }
 }
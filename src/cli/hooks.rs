@@ -0,0 +1,192 @@
+//! Commands run in a generated service after generation, before its initial commit
+//!
+//! A template lists them under `[hooks]` in an `rsc.toml` in its root, and `--post-hook`
+//! adds more for one run, after the template's. They run in order in the service
+//! directory, through the shell, with the project name and the selected features in
+//! `RSC_*` environment variables.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::{fs, path::Path, process::Command};
+use tracing::info;
+
+/// File in the template root configuring rsc
+pub const RSC_TOML: &str = "rsc.toml";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RscToml {
+    #[serde(default)]
+    hooks: HooksTable,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HooksTable {
+    /// Shell commands run once the service is generated
+    #[serde(default)]
+    post_generate: Vec<String>,
+}
+
+/// The `post_generate` hooks of the template at `root`, if it has an `rsc.toml`, followed
+/// by the `flags` given with `--post-hook`
+pub fn load(root: &Path, flags: &[String]) -> Result<Vec<String>> {
+    let path = root.join(RSC_TOML);
+    let mut hooks = if path.exists() {
+        let content =
+            fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))?;
+        toml::from_str::<RscToml>(&content)
+            .with_context(|| format!("Invalid {:?}", path))?
+            .hooks
+            .post_generate
+    } else {
+        Vec::new()
+    };
+
+    hooks.extend(flags.iter().cloned());
+    Ok(hooks)
+}
+
+/// Run `hooks` in order in `dir` with the extra environment `env`, stopping at the first
+/// that fails
+///
+/// Output is captured, and shown only in the error of a hook that fails.
+pub fn run(hooks: &[String], dir: &Path, env: &[(String, String)]) -> Result<()> {
+    for hook in hooks {
+        info!("Running hook: {hook}");
+        let output = shell(hook)
+            .current_dir(dir)
+            .envs(env.iter().map(|(key, value)| (key, value)))
+            .output()
+            .with_context(|| format!("Failed to run hook: {hook}"))?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Hook `{hook}` failed ({})\n--- stdout ---\n{}\n--- stderr ---\n{}",
+                output.status,
+                String::from_utf8_lossy(&output.stdout).trim_end(),
+                String::from_utf8_lossy(&output.stderr).trim_end()
+            );
+        }
+        info!("✓ Hook passed: {hook}");
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.args(["-c", command]);
+    shell
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.args(["/C", command]);
+    shell
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(hooks: &[&str]) -> Vec<String> {
+        hooks.iter().map(|hook| hook.to_string()).collect()
+    }
+
+    #[test]
+    fn test_template_hooks_run_before_flag_hooks() {
+        let root = tempfile::tempdir().unwrap();
+        fs::write(
+            root.path().join(RSC_TOML),
+            "[hooks]\npost_generate = [\"cargo fmt\", \"./scripts/register.sh\"]\n",
+        )
+        .unwrap();
+
+        let hooks = load(root.path(), &strings(&["touch CODEOWNERS"])).unwrap();
+
+        assert_eq!(
+            hooks,
+            ["cargo fmt", "./scripts/register.sh", "touch CODEOWNERS"]
+        );
+    }
+
+    #[test]
+    fn test_template_without_rsc_toml_has_only_flag_hooks() {
+        let root = tempfile::tempdir().unwrap();
+
+        assert!(load(root.path(), &[]).unwrap().is_empty());
+        assert_eq!(load(root.path(), &strings(&["true"])).unwrap(), ["true"]);
+    }
+
+    #[test]
+    fn test_invalid_rsc_toml_is_rejected() {
+        // Negative test: a misspelt key must not silently skip the template's hooks
+        let root = tempfile::tempdir().unwrap();
+        fs::write(
+            root.path().join(RSC_TOML),
+            "[hooks]\npost_generation = [\"cargo fmt\"]\n",
+        )
+        .unwrap();
+
+        let error = load(root.path(), &[]).unwrap_err();
+
+        assert!(
+            format!("{error:#}").contains("post_generation"),
+            "{error:#}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hooks_run_in_the_service_directory_with_its_settings() {
+        // Arrange
+        let dir = tempfile::tempdir().unwrap();
+        let env = [
+            ("RSC_PROJECT_NAME".to_string(), "billing-api".to_string()),
+            ("RSC_WITHOUT_KAFKA".to_string(), "true".to_string()),
+        ];
+
+        // Act
+        run(
+            &strings(&[
+                "echo \"$RSC_PROJECT_NAME $RSC_WITHOUT_KAFKA\" > marker",
+                "echo second >> marker",
+            ]),
+            dir.path(),
+            &env,
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(
+            fs::read_to_string(dir.path().join("marker")).unwrap(),
+            "billing-api true\nsecond\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_failing_hook_stops_with_its_output() {
+        // Negative test: a failed hook reports what it printed and later hooks never run
+        let dir = tempfile::tempdir().unwrap();
+
+        let error = run(
+            &strings(&[
+                "echo registering; echo 'catalog unreachable' >&2; exit 3",
+                "touch marker",
+            ]),
+            dir.path(),
+            &[],
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(error.contains("exit status: 3"), "{error}");
+        assert!(error.contains("registering"), "{error}");
+        assert!(error.contains("catalog unreachable"), "{error}");
+        assert!(!dir.path().join("marker").exists());
+    }
+}
@@ -1,17 +1,54 @@
 use anyhow::Result;
-use clap::Parser;
+use tracing::Level;
+use tracing_subscriber::{fmt::writer::MakeWriterExt, EnvFilter};
 
 use rust_service_template::cli::{
-    args::{Cli, Commands},
-    commands::{execute_create, execute_scaffold},
+    args::{Commands, OutputFormat},
+    commands::{execute_auth, execute_create, execute_generate, execute_scaffold, execute_upgrade},
+    prompt::{is_terminal, parse_cli},
 };
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let cli = parse_cli(std::env::args_os().collect(), is_terminal()).unwrap_or_else(|e| e.exit());
 
-    match cli.command {
-        Commands::Create(args) => execute_create(args).await,
-        Commands::Scaffold(args) => execute_scaffold(args),
+    let filter = if cli.quiet {
+        "error"
+    } else if cli.verbose {
+        "warn,rust_service_template=debug"
+    } else {
+        "warn,rust_service_template=info"
+    };
+    let output = tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::new(filter))
+        .without_time()
+        .with_target(false)
+        .with_level(false)
+        .with_ansi(false);
+    if cli.output_format == OutputFormat::Json {
+        // Stdout only carries the summary
+        output.with_writer(std::io::stderr).init();
+    } else {
+        // Progress on stdout; warnings, such as GitHub retries, and errors on stderr
+        output
+            .with_writer(
+                std::io::stderr
+                    .with_max_level(Level::WARN)
+                    .or_else(std::io::stdout),
+            )
+            .init();
     }
+
+    let summary = match cli.command {
+        Commands::Create(args) => execute_create(args).await?,
+        Commands::Scaffold(args) => execute_scaffold(args).await?,
+        Commands::Generate(args) => execute_generate(args).map(|()| None)?,
+        Commands::Upgrade(args) => execute_upgrade(args).map(|()| None)?,
+        Commands::Auth(args) => execute_auth(args).await.map(|()| None)?,
+    };
+    if let (OutputFormat::Json, Some(summary)) = (cli.output_format, summary) {
+        println!("{}", summary.to_json()?);
+    }
+
+    Ok(())
 }
@@ -0,0 +1,159 @@
+//! How a service was generated, recorded in the service as `.rsc-manifest.toml`
+//!
+//! `rsc upgrade` reads it to regenerate the service from the template commit it came
+//! from and from a newer one, with the same options, and merges the difference in.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+use crate::cli::{
+    args::{DatabaseBackend, ServiceKind},
+    template::Template,
+};
+
+/// File in the service root holding its [`Manifest`]
+pub const MANIFEST_FILE: &str = ".rsc-manifest.toml";
+
+const HEADER: &str = "# Written by rsc when the service was generated; `rsc upgrade` reads it to\n\
+                      # pull in template changes. Edit with care.\n\n";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Version of rsc that generated the service
+    pub rsc_version: String,
+    pub template: TemplateSource,
+    pub options: GenerationOptions,
+}
+
+/// Where the template was fetched from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateSource {
+    /// Git URL or local directory
+    pub url: String,
+    /// Tag, branch or commit asked for, if any
+    #[serde(rename = "ref", default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    /// Commit the template was at; unknown for a local directory outside git
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+impl TemplateSource {
+    pub fn of(template: &Template) -> Self {
+        Self {
+            url: template.url.clone(),
+            reference: template.reference.clone(),
+            commit: template.commit.clone(),
+        }
+    }
+}
+
+/// The options the service was generated with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenerationOptions {
+    pub name: String,
+    pub without_kafka: bool,
+    pub without_auth: bool,
+    pub without_swagger: bool,
+    pub with_k8s: bool,
+    pub with_helm: bool,
+    /// Api for services generated before `--kind` existed
+    #[serde(default)]
+    pub kind: ServiceKind,
+    pub database: DatabaseBackend,
+    pub entity: String,
+    pub entity_plural: String,
+    /// `--postgres-port`, if given; otherwise derived from the name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub postgres_port: Option<u16>,
+    /// `--kafka-port`, if given; otherwise derived from the name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kafka_port: Option<u16>,
+    /// `--exclude` patterns
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// `--include` patterns
+    #[serde(default)]
+    pub include: Vec<String>,
+}
+
+impl Manifest {
+    /// The manifest of the service in `dir`
+    pub fn read(dir: &Path) -> Result<Self> {
+        let path = dir.join(MANIFEST_FILE);
+        if !path.exists() {
+            anyhow::bail!(
+                "{} has no {MANIFEST_FILE}: run this in a service generated by rsc, from its \
+                 root. Services generated before rsc recorded manifests cannot be upgraded",
+                dir.display()
+            );
+        }
+        let content =
+            fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))?;
+        toml::from_str(&content).with_context(|| format!("Invalid {:?}", path))
+    }
+
+    /// Write the manifest into the service in `dir`
+    pub fn write(&self, dir: &Path) -> Result<()> {
+        let path = dir.join(MANIFEST_FILE);
+        let content = toml::to_string(self).context("Failed to serialize the manifest")?;
+        fs::write(&path, format!("{HEADER}{content}"))
+            .with_context(|| format!("Failed to write {:?}", path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_round_trips() {
+        let manifest = Manifest {
+            rsc_version: "0.3.0".to_string(),
+            template: TemplateSource {
+                url: "https://github.com/acme/service-template.git".to_string(),
+                reference: Some("v0.3.0".to_string()),
+                commit: Some("0123456789abcdef0123456789abcdef01234567".to_string()),
+            },
+            options: GenerationOptions {
+                name: "billing-api".to_string(),
+                without_kafka: true,
+                without_auth: false,
+                without_swagger: false,
+                with_k8s: true,
+                with_helm: false,
+                kind: ServiceKind::Worker,
+                database: DatabaseBackend::Sqlite,
+                entity: "person".to_string(),
+                entity_plural: "people".to_string(),
+                postgres_port: Some(5446),
+                kafka_port: None,
+                exclude: vec!["docs/".to_string()],
+                include: Vec::new(),
+            },
+        };
+        let dir = tempfile::tempdir().unwrap();
+
+        manifest.write(dir.path()).unwrap();
+
+        let content = fs::read_to_string(dir.path().join(MANIFEST_FILE)).unwrap();
+        assert!(content.starts_with("# Written by rsc"), "{content}");
+        assert!(content.contains("database = \"sqlite\""), "{content}");
+        assert!(content.contains("kind = \"worker\""), "{content}");
+        assert!(content.contains("postgres_port = 5446"), "{content}");
+        assert!(!content.contains("kafka_port"), "{content}");
+        assert!(content.contains("ref = \"v0.3.0\""), "{content}");
+        assert_eq!(Manifest::read(dir.path()).unwrap(), manifest);
+    }
+
+    #[test]
+    fn test_missing_manifest_is_explained() {
+        // Negative test: a service generated before manifests existed cannot be upgraded
+        let dir = tempfile::tempdir().unwrap();
+
+        let error = Manifest::read(dir.path()).unwrap_err();
+
+        assert!(error.to_string().contains(MANIFEST_FILE), "{error}");
+    }
+}
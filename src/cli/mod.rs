@@ -1,7 +1,19 @@
 pub mod args;
+pub mod auth;
 pub mod commands;
+pub mod entity;
+pub mod entity_generator;
+pub mod exclusions;
 pub mod generator;
 pub mod github;
+pub mod hooks;
+pub mod manifest;
+pub mod ports;
+pub mod prompt;
+pub mod summary;
+pub mod template;
+pub mod upgrade;
+pub mod verify;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,87 @@
+//! Host ports a generated service's `docker-compose.yaml` publishes its dependencies on
+//!
+//! The template publishes Postgres on 5445 and Kafka on 9092, so two services generated
+//! from it could not run side by side. Each service instead gets a block of
+//! [`PORT_BLOCK_SIZE`] ports derived from its name, the same on every machine and every
+//! run; `--postgres-port` and `--kafka-port` pick the two that matter by hand.
+
+use sha2::{Digest, Sha256};
+
+/// First port of the first block, above the well-known ports of common servers
+const PORT_BLOCK_START: u16 = 20000;
+
+/// Ports reserved per service
+const PORT_BLOCK_SIZE: u16 = 10;
+
+/// Number of blocks, ending below the ephemeral port range of Linux and Windows
+const PORT_BLOCKS: u16 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComposePorts {
+    pub postgres: u16,
+    pub kafka: u16,
+    /// JMX metrics of the Kafka broker
+    pub kafka_jmx: u16,
+    pub kafka_ui: u16,
+    pub zookeeper: u16,
+}
+
+impl ComposePorts {
+    /// The block of `crate_name`, with `postgres` and `kafka` overriding their ports
+    pub fn for_project(crate_name: &str, postgres: Option<u16>, kafka: Option<u16>) -> Self {
+        let digest = Sha256::digest(crate_name);
+        let block = u16::from_be_bytes([digest[0], digest[1]]) % PORT_BLOCKS;
+        let base = PORT_BLOCK_START + block * PORT_BLOCK_SIZE;
+
+        Self {
+            postgres: postgres.unwrap_or(base),
+            kafka: kafka.unwrap_or(base + 1),
+            kafka_jmx: base + 2,
+            kafka_ui: base + 3,
+            zookeeper: base + 4,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ports_are_derived_from_the_name() {
+        let billing = ComposePorts::for_project("billing_api", None, None);
+
+        // Objective: the same name always gets the same ports, in its own block
+        assert_eq!(
+            billing,
+            ComposePorts::for_project("billing_api", None, None)
+        );
+        assert_eq!(billing.postgres % PORT_BLOCK_SIZE, 0);
+        assert_eq!(billing.kafka, billing.postgres + 1);
+        assert!(
+            (PORT_BLOCK_START..PORT_BLOCK_START + PORT_BLOCKS * PORT_BLOCK_SIZE)
+                .contains(&billing.zookeeper)
+        );
+        // Negative test: another name gets another block
+        assert_ne!(
+            ComposePorts::for_project("orders_api", None, None).postgres,
+            billing.postgres
+        );
+    }
+
+    #[test]
+    fn test_flags_override_only_their_port() {
+        let derived = ComposePorts::for_project("billing_api", None, None);
+
+        let ports = ComposePorts::for_project("billing_api", Some(5446), Some(9093));
+
+        assert_eq!(
+            ports,
+            ComposePorts {
+                postgres: 5446,
+                kafka: 9093,
+                ..derived
+            }
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! Interactive `rsc create`
+//!
+//! Prompts only run on a terminal: scripts and CI keep failing on missing arguments with
+//! clap's errors instead of waiting for input that never comes.
+
+use anyhow::{Context, Result};
+use clap::{
+    error::{ContextKind, ContextValue, ErrorKind},
+    Parser, ValueEnum,
+};
+use dialoguer::{Confirm, Input, Select};
+use std::{ffi::OsString, io::IsTerminal};
+
+use crate::cli::{
+    args::{Cli, CreateArgs, ServiceKind},
+    generator::validate_service_name,
+};
+
+/// Whether prompts can be shown and answered
+pub fn is_terminal() -> bool {
+    std::io::stdin().is_terminal() && std::io::stderr().is_terminal()
+}
+
+/// Parse the command line
+///
+/// On a `terminal`, `create` without NAME or --github-user turns into `create --interactive`
+/// with the missing values left empty for the prompts, instead of failing.
+pub fn parse_cli(args: Vec<OsString>, terminal: bool) -> Result<Cli, clap::Error> {
+    let error = match Cli::try_parse_from(&args) {
+        Ok(cli) => return Ok(cli),
+        Err(error) => error,
+    };
+    let is_create = args.get(1).is_some_and(|command| command == "create");
+    if !terminal || !is_create || error.kind() != ErrorKind::MissingRequiredArgument {
+        return Err(error);
+    }
+
+    let mut args = args;
+    if let Some(ContextValue::Strings(missing)) = error.get(ContextKind::InvalidArg) {
+        for arg in missing {
+            if arg.starts_with("--github-user") {
+                args.extend(["--github-user".into(), OsString::new()]);
+            } else {
+                args.push(OsString::new());
+            }
+        }
+    }
+    args.push("--interactive".into());
+    Cli::try_parse_from(args)
+}
+
+/// Ask for every setting of `create`, offering the values given on the command line
+pub fn complete_create_args(args: &mut CreateArgs) -> Result<()> {
+    args.name = input("Service name", &args.name, false)
+        .validate_with(|name: &String| validate_service_name(name))
+        .interact_text()
+        .context("Failed to read the service name")?;
+    args.github_user = input("GitHub user or organization", &args.github_user, false)
+        .validate_with(|owner: &String| {
+            if owner.contains(char::is_whitespace) {
+                Err("Must not contain spaces")
+            } else {
+                Ok(())
+            }
+        })
+        .interact_text()
+        .context("Failed to read the GitHub owner")?;
+
+    let visibility = Select::new()
+        .with_prompt("Visibility")
+        .items(["public", "private"])
+        .default(usize::from(args.private))
+        .interact()
+        .context("Failed to read the visibility")?;
+    args.private = visibility == 1;
+
+    let description = input(
+        "Description (optional)",
+        args.description.as_deref().unwrap_or_default(),
+        true,
+    )
+    .interact_text()
+    .context("Failed to read the description")?;
+    args.description = Some(description.trim().to_string()).filter(|d| !d.is_empty());
+
+    let kinds = ServiceKind::value_variants();
+    let kind = Select::new()
+        .with_prompt("Kind")
+        .items(["HTTP API", "worker", "library"])
+        .default(
+            kinds
+                .iter()
+                .position(|kind| *kind == args.kind)
+                .unwrap_or(0),
+        )
+        .interact()
+        .context("Failed to read the kind")?;
+    args.kind = kinds[kind];
+
+    args.without_kafka = !confirm("Include Kafka event publishing?", !args.without_kafka)?;
+    // A worker has neither
+    if args.kind != ServiceKind::Worker {
+        args.without_auth = !confirm("Include JWT authentication?", !args.without_auth)?;
+        args.without_swagger = !confirm(
+            "Include OpenAPI docs and Swagger UI?",
+            !args.without_swagger,
+        )?;
+    }
+    args.with_k8s = confirm("Add Kubernetes manifests?", args.with_k8s)?;
+    args.with_helm = confirm("Add a Helm chart?", args.with_helm)?;
+
+    Ok(())
+}
+
+/// Ask a yes/no question
+pub fn confirm(prompt: &str, default: bool) -> Result<bool> {
+    Confirm::new()
+        .with_prompt(prompt)
+        .default(default)
+        .interact()
+        .context("Failed to read the answer")
+}
+
+/// Text prompt offering `current` as its default, unless it is empty
+fn input<'a>(prompt: &str, current: &str, allow_empty: bool) -> Input<'a, String> {
+    let input = Input::new().with_prompt(prompt).allow_empty(allow_empty);
+    if current.is_empty() {
+        input
+    } else {
+        input.default(current.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args::Commands;
+
+    fn args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    fn create(cli: Cli) -> CreateArgs {
+        let Commands::Create(args) = cli.command else {
+            panic!("Expected create command");
+        };
+        args
+    }
+
+    #[test]
+    fn test_missing_arguments_fail_without_a_terminal() {
+        // Negative test: CI must get clap's error rather than a prompt
+        for command in [&["rsc", "create"][..], &["rsc", "create", "svc"]] {
+            let error = parse_cli(args(command), false).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::MissingRequiredArgument);
+        }
+        let error = parse_cli(args(&["rsc", "scaffold"]), true).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn test_missing_arguments_switch_to_prompts_on_a_terminal() {
+        let cli = parse_cli(args(&["rsc", "create", "--private"]), true).unwrap();
+
+        let create_args = create(cli);
+        assert!(create_args.interactive);
+        assert!(create_args.private, "Given flags are kept");
+        assert_eq!(create_args.name, "");
+        assert_eq!(create_args.github_user, "");
+
+        let cli = parse_cli(args(&["rsc", "create", "svc", "--without-kafka"]), true).unwrap();
+
+        let create_args = create(cli);
+        assert!(create_args.interactive && create_args.without_kafka);
+        assert_eq!(create_args.name, "svc");
+        assert_eq!(create_args.github_user, "");
+    }
+
+    #[test]
+    fn test_complete_command_line_does_not_prompt() {
+        let cli = parse_cli(args(&["rsc", "create", "svc", "-g", "acme"]), true).unwrap();
+
+        assert!(!create(cli).interactive);
+    }
+}
@@ -0,0 +1,79 @@
+//! The result of `create` and `scaffold` for scripts, printed by `--output-format json`
+//!
+//! Scripts parse it, so its fields are a contract: add new ones, but never rename, remove
+//! or change the meaning of existing ones.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::{path::PathBuf, time::Duration};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Summary {
+    /// Web URL of the GitHub repository; `null` for `scaffold`
+    pub repo_url: Option<String>,
+    /// Absolute directory of the service; `null` for `create`, which pushes the service
+    /// from a scratch directory
+    pub path: Option<PathBuf>,
+    /// Files generated, or for a scaffold into an existing directory, written into it
+    pub files_created: usize,
+    /// Optional parts of the template the service has, such as `kafka` or `k8s`
+    pub features: Vec<String>,
+    /// Time the command took
+    pub duration_ms: u64,
+}
+
+impl Summary {
+    pub fn new(
+        repo_url: Option<String>,
+        path: Option<PathBuf>,
+        files_created: usize,
+        features: &[&str],
+        duration: Duration,
+    ) -> Self {
+        Self {
+            repo_url,
+            path,
+            files_created,
+            features: features.iter().map(|feature| feature.to_string()).collect(),
+            duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+        }
+    }
+
+    /// The summary on one line of JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).context("Failed to serialize the summary")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_contract() {
+        // Objective: a change to the fields scripts read fails here first
+        let scaffold = Summary::new(
+            None,
+            Some(PathBuf::from("/work/billing-api")),
+            142,
+            &["kafka", "auth", "swagger", "k8s"],
+            Duration::from_micros(1_234_567),
+        );
+        let create = Summary::new(
+            Some("https://github.com/acme/billing-api".to_string()),
+            None,
+            138,
+            &[],
+            Duration::from_secs(95),
+        );
+
+        assert_eq!(
+            scaffold.to_json().unwrap(),
+            r#"{"repo_url":null,"path":"/work/billing-api","files_created":142,"features":["kafka","auth","swagger","k8s"],"duration_ms":1234}"#
+        );
+        assert_eq!(
+            create.to_json().unwrap(),
+            r#"{"repo_url":"https://github.com/acme/billing-api","path":null,"files_created":138,"features":[],"duration_ms":95000}"#
+        );
+    }
+}
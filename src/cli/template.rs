@@ -0,0 +1,390 @@
+//! Where generated services are copied from
+//!
+//! By default the template is the release of the canonical repository matching this CLI,
+//! fetched once into the cache. `--template` points at a fork or a local checkout.
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use tempfile::TempDir;
+
+use crate::cli::args::TemplateArgs;
+
+/// Repository the CLI is released from
+pub const DEFAULT_TEMPLATE_URL: &str = concat!(env!("CARGO_PKG_REPOSITORY"), ".git");
+
+/// Release tag of the template matching this CLI
+pub const DEFAULT_TEMPLATE_REF: &str = concat!("v", env!("CARGO_PKG_VERSION"));
+
+/// Length of the hash that keeps cache entries of different URLs apart
+const CACHE_KEY_HASH_LENGTH: usize = 12;
+
+/// A checked-out template
+///
+/// Fetched with `--no-cache`, it is deleted when dropped.
+#[derive(Debug)]
+pub struct Template {
+    path: PathBuf,
+    /// What the template is, for the command output
+    pub source: String,
+    /// Git URL, or the resolved local directory
+    pub url: String,
+    /// Tag, branch or commit fetched; `None` for a local directory used in place
+    pub reference: Option<String>,
+    /// Commit checked out, if the template is a git checkout
+    pub commit: Option<String>,
+    _scratch: Option<TempDir>,
+}
+
+impl Template {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Find or fetch the template selected by `--template` and `--template-ref`
+///
+/// A local directory is used as it is unless a ref is asked for.
+pub fn resolve_template(args: &TemplateArgs) -> Result<Template> {
+    let url = args.template.as_deref().unwrap_or(DEFAULT_TEMPLATE_URL);
+
+    if args.template_ref.is_none() && Path::new(url).is_dir() {
+        let path = Path::new(url)
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve template directory {url}"))?;
+        return Ok(Template {
+            source: path.display().to_string(),
+            url: path.display().to_string(),
+            reference: None,
+            // Only for a repository root: a directory inside another checkout has no
+            // commit of its own to fetch again
+            commit: path
+                .join(".git")
+                .exists()
+                .then(|| head_commit(&path))
+                .flatten(),
+            path,
+            _scratch: None,
+        });
+    }
+
+    let reference = args.template_ref.as_deref().unwrap_or(DEFAULT_TEMPLATE_REF);
+    if args.offline && args.no_cache && !is_local(url) {
+        anyhow::bail!("--offline cannot fetch template {url} again for --no-cache");
+    }
+    if args.no_cache {
+        let scratch = TempDir::new().context("Failed to create temporary directory")?;
+        fetch(url, reference, scratch.path())?;
+        return Ok(Template {
+            path: scratch.path().to_path_buf(),
+            source: format!("{url} at {reference}"),
+            url: url.to_string(),
+            reference: Some(reference.to_string()),
+            commit: head_commit(scratch.path()),
+            _scratch: Some(scratch),
+        });
+    }
+
+    let cache_root = cache_dir()
+        .context("Cannot find a cache directory: set HOME or XDG_CACHE_HOME, or pass --no-cache")?;
+    if args.offline && !is_local(url) {
+        return cached(url, reference, &cache_root).with_context(|| {
+            format!(
+                "Template {url} at {reference} is not cached, and --offline forbids fetching \
+                 it. Run once with network access, or pass --template with a local checkout"
+            )
+        });
+    }
+    fetch_cached(url, reference, &cache_root)
+}
+
+/// Whether `url` is fetched without the network: a local directory or `file://` URL
+fn is_local(url: &str) -> bool {
+    url.starts_with("file://") || Path::new(url).is_dir()
+}
+
+/// `$XDG_CACHE_HOME/rsc`, or `~/.cache/rsc`
+fn cache_dir() -> Option<PathBuf> {
+    let non_empty = |name| env::var_os(name).filter(|value| !value.is_empty());
+    non_empty("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .map(|cache| cache.join("rsc"))
+}
+
+/// Readable and unique directory name for a template URL and ref
+fn cache_key(url: &str, reference: &str) -> String {
+    let name = url
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .rsplit(['/', ':'])
+        .next()
+        .unwrap_or_default();
+    let readable: String = format!("{name}-{reference}")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let hash: String = Sha256::digest(format!("{url}\n{reference}"))
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+
+    format!("{readable}-{}", &hash[..CACHE_KEY_HASH_LENGTH])
+}
+
+/// The cached checkout of `url` at `reference`, if it was fetched before
+fn cached(url: &str, reference: &str, cache_root: &Path) -> Option<Template> {
+    let path = cache_root.join(cache_key(url, reference));
+    path.exists().then(|| checkout(url, reference, path))
+}
+
+/// Reuse the cached checkout of `url` at `reference`, fetching it on first use
+///
+/// Branches are cached like tags; `--no-cache` picks up new commits.
+fn fetch_cached(url: &str, reference: &str, cache_root: &Path) -> Result<Template> {
+    let path = cache_root.join(cache_key(url, reference));
+
+    if !path.exists() {
+        fs::create_dir_all(cache_root)
+            .with_context(|| format!("Failed to create cache directory {:?}", cache_root))?;
+        // Fetched next to its final place and moved in once complete, so an interrupted
+        // fetch is never taken for a cached template
+        let staging = TempDir::new_in(cache_root).context("Failed to create cache entry")?;
+        fetch(url, reference, staging.path())?;
+        if let Err(e) = fs::rename(staging.path(), &path) {
+            // Another run cached the same template first
+            if !path.exists() {
+                return Err(e).with_context(|| format!("Failed to cache template in {:?}", path));
+            }
+        }
+    }
+
+    Ok(checkout(url, reference, path))
+}
+
+/// The cache entry at `path` holding `url` at `reference`
+fn checkout(url: &str, reference: &str, path: PathBuf) -> Template {
+    Template {
+        source: format!("{url} at {reference}"),
+        url: url.to_string(),
+        reference: Some(reference.to_string()),
+        commit: head_commit(&path),
+        path,
+        _scratch: None,
+    }
+}
+
+/// Shallow-fetch `reference`, a tag, branch or commit, of `url` into the empty `dir`
+fn fetch(url: &str, reference: &str, dir: &Path) -> Result<()> {
+    git(dir, &["init", "--quiet"])?;
+    git(dir, &["fetch", "--quiet", "--depth", "1", url, reference]).with_context(|| {
+        format!(
+            "Failed to fetch template {url} at {reference}. Check the URL, the ref and your \
+             network connection, or pass --template with a local checkout"
+        )
+    })?;
+    git(dir, &["checkout", "--quiet", "FETCH_HEAD"])
+}
+
+/// The commit checked out in `dir`, if it is a git checkout
+fn head_commit(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        // Fail instead of asking for credentials, e.g. for a mistyped GitHub URL
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .context("Failed to execute git")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Local repository with a `v1.0.0` tag and a newer `main` branch
+    fn template_repository() -> TempDir {
+        let repository = TempDir::new().unwrap();
+        let commit = |content: &str| {
+            fs::write(repository.path().join("Cargo.toml"), content).unwrap();
+            for args in [
+                &["add", "-A"][..],
+                &[
+                    "-c",
+                    "user.name=test",
+                    "-c",
+                    "user.email=test@localhost",
+                    "commit",
+                    "--quiet",
+                    "-m",
+                    content,
+                ],
+            ] {
+                git(repository.path(), args).unwrap();
+            }
+        };
+        git(
+            repository.path(),
+            &["init", "--quiet", "--initial-branch=main"],
+        )
+        .unwrap();
+        commit("released");
+        git(repository.path(), &["tag", "v1.0.0"]).unwrap();
+        commit("unreleased");
+        repository
+    }
+
+    #[test]
+    fn test_cache_key_is_readable_and_distinct() {
+        let key = cache_key("https://github.com/acme/service-template.git", "v1.2.0");
+
+        assert!(key.starts_with("service-template-v1.2.0-"), "{key}");
+        assert_eq!(
+            cache_key("git@github.com:acme/service-template.git", "feature/x")
+                .rsplit_once('-')
+                .unwrap()
+                .0,
+            "service-template-feature-x"
+        );
+        assert_ne!(
+            key,
+            cache_key("https://github.com/fork/service-template.git", "v1.2.0")
+        );
+    }
+
+    #[test]
+    fn test_fetch_cached_checks_out_the_ref_once() {
+        let repository = template_repository();
+        let url = format!("file://{}", repository.path().display());
+        let cache_root = TempDir::new().unwrap();
+
+        // Act: Fetch the tag, then the branch
+        let tagged = fetch_cached(&url, "v1.0.0", cache_root.path()).unwrap();
+        let branch = fetch_cached(&url, "main", cache_root.path()).unwrap();
+
+        // Assert: Each ref has its own checkout, at its own commit
+        assert_eq!(tagged.reference.as_deref(), Some("v1.0.0"));
+        assert_ne!(tagged.commit, branch.commit);
+        assert_eq!(tagged.commit.as_ref().map(String::len), Some(40));
+        let content =
+            |template: &Template| fs::read_to_string(template.path().join("Cargo.toml")).unwrap();
+        assert_eq!(content(&tagged), "released");
+        assert_eq!(content(&branch), "unreleased");
+        assert_eq!(fs::read_dir(cache_root.path()).unwrap().count(), 2);
+
+        // Act: Fetch the tag again after the source is gone
+        drop(repository);
+        let cached = fetch_cached(&url, "v1.0.0", cache_root.path()).unwrap();
+
+        // Assert: The cached checkout is reused
+        assert_eq!(cached.path(), tagged.path());
+        assert_eq!(content(&cached), "released");
+    }
+
+    #[test]
+    fn test_fetch_failure_explains_itself() {
+        // Negative test: A ref that does not exist, and a repository that does not
+        let repository = template_repository();
+        let url = format!("file://{}", repository.path().display());
+        let cache_root = TempDir::new().unwrap();
+
+        for (url, reference) in [(url.as_str(), "v9.9.9"), ("file:///nonexistent", "v1.0.0")] {
+            let error = fetch_cached(url, reference, cache_root.path()).unwrap_err();
+            let message = format!("{error:#}");
+            assert!(
+                message.contains(&format!("Failed to fetch template {url} at {reference}")),
+                "{message}"
+            );
+        }
+        // Assert: Failed fetches leave nothing behind in the cache
+        assert_eq!(fs::read_dir(cache_root.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_offline_uses_only_cached_templates() {
+        let repository = template_repository();
+        let url = "https://example.invalid/service-template.git";
+        let cache_root = TempDir::new().unwrap();
+        // Arrange: the checkout a previous online run left in the cache
+        let cached_path = cache_root.path().join(cache_key(url, "v1.0.0"));
+        fs::create_dir_all(&cached_path).unwrap();
+        let source = format!("file://{}", repository.path().display());
+        fetch(&source, "v1.0.0", &cached_path).unwrap();
+
+        // Act
+        let template = cached(url, "v1.0.0", cache_root.path()).unwrap();
+
+        // Assert
+        assert_eq!(template.path(), cached_path);
+        assert_eq!(template.commit.as_ref().map(String::len), Some(40));
+        // Negative test: a ref never fetched is not fetched now
+        assert!(cached(url, "main", cache_root.path()).is_none());
+        assert_eq!(fs::read_dir(cache_root.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_offline_refuses_to_fetch() {
+        // Negative test: --offline fails before running git for a remote template
+        let args = TemplateArgs {
+            template: Some("https://example.invalid/service-template.git".to_string()),
+            template_ref: Some("never-fetched".to_string()),
+            offline: true,
+            ..TemplateArgs::default()
+        };
+
+        let error = format!("{:#}", resolve_template(&args).unwrap_err());
+
+        assert!(
+            error.contains("is not cached, and --offline forbids fetching it"),
+            "{error}"
+        );
+        assert!(!error.contains("git fetch failed"), "{error}");
+    }
+
+    #[test]
+    fn test_local_directory_is_used_in_place() {
+        let repository = template_repository();
+        let args = TemplateArgs {
+            template: Some(repository.path().display().to_string()),
+            ..TemplateArgs::default()
+        };
+
+        let template = resolve_template(&args).unwrap();
+
+        assert_eq!(template.path(), repository.path().canonicalize().unwrap());
+        assert_eq!(template.reference, None);
+        assert_eq!(template.commit, head_commit(repository.path()));
+        assert!(template.commit.is_some());
+    }
+}
@@ -0,0 +1,155 @@
+use axum::{extract::State, http::StatusCode, Json};
+use std::sync::Arc;
+
+use crate::{
+    api::{
+        error::ApiErrorResponse,
+        extractors::{AppJson, AppPath, PathParam},
+        models::{{entities}}::{{{Entity}}Request, {{Entity}}Response},
+    },
+    config::AppState,
+    domain::{
+        errors::{DomainError, ExternalSystem},
+        interfaces::{{entity}}_repository::{{Entity}}Repository,
+        {{entity}}::{
+            models::{{{Entity}}, {{Entity}}Id},
+            operations::{
+                create_{{entity}}, delete_{{entity}}, get_{{entity}}, list_{{entities}}, update_{{entity}},
+            },
+        },
+    },
+    infrastructure::{{entity}}::Postgres{{Entity}}Repository,
+};
+
+/// {{Entities}} are stored in Postgres whichever backend serves the other entities
+fn repository(state: &AppState) -> Result<Arc<dyn {{Entity}}Repository>, ApiErrorResponse> {
+    let pool = state.db_pool.clone().ok_or_else(|| {
+        DomainError::external_error(ExternalSystem::Database, "{{Entities}} require Postgres")
+    })?;
+    Ok(Arc::new(Postgres{{Entity}}Repository::new(pool)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{{entities}}/{id}",
+    tag = "{{entities}}",
+    params(
+        ("id" = Uuid, Path, description = "{{Entity}} ID")
+    ),
+    responses(
+        (status = 200, description = "{{Entity}} found", body = {{Entity}}Response),
+        (status = 400, description = "{{Entity}} ID is not a valid UUID", body = ApiErrorResponse),
+        (status = 404, description = "{{Entity}} not found", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields({{entity}}_id = %id))]
+pub async fn get_{{entity}}_handler(
+    AppPath(PathParam(id)): AppPath<PathParam<{{Entity}}Id>>,
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<{{Entity}}Response>, ApiErrorResponse> {
+    let entity = get_{{entity}}(id, repository(&state)?).await?;
+
+    Ok(Json(entity.into()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{{entities}}",
+    tag = "{{entities}}",
+    responses(
+        (status = 200, description = "List of {{entities_words}}, newest first", body = Vec<{{Entity}}Response>),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub async fn list_{{entities}}_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<{{Entity}}Response>>, ApiErrorResponse> {
+    let entities = list_{{entities}}(repository(&state)?).await?;
+
+    Ok(Json(entities.into_iter().map(Into::into).collect()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{{entities}}",
+    tag = "{{entities}}",
+    request_body = {{Entity}}Request,
+    responses(
+        (status = 201, description = "{{Entity}} created", body = {{Entity}}Response),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+        (status = 415, description = "Missing JSON content type", body = ApiErrorResponse),
+        (status = 422, description = "Request body does not match the schema", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all)]
+pub async fn create_{{entity}}_handler(
+    State(state): State<Arc<AppState>>,
+    AppJson(request): AppJson<{{Entity}}Request>,
+) -> Result<(StatusCode, Json<{{Entity}}Response>), ApiErrorResponse> {
+    let entity = {{Entity}}::new(request.name, request.description)?;
+
+    let created = create_{{entity}}(entity, repository(&state)?).await?;
+
+    Ok((StatusCode::CREATED, Json(created.into())))
+}
+
+#[utoipa::path(
+    put,
+    path = "/{{entities}}/{id}",
+    tag = "{{entities}}",
+    params(
+        ("id" = Uuid, Path, description = "{{Entity}} ID")
+    ),
+    request_body = {{Entity}}Request,
+    responses(
+        (status = 200, description = "{{Entity}} updated", body = {{Entity}}Response),
+        (status = 400, description = "Invalid request", body = ApiErrorResponse),
+        (status = 404, description = "{{Entity}} not found", body = ApiErrorResponse),
+        (status = 415, description = "Missing JSON content type", body = ApiErrorResponse),
+        (status = 422, description = "Request body does not match the schema", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields({{entity}}_id = %id))]
+pub async fn update_{{entity}}_handler(
+    AppPath(PathParam(id)): AppPath<PathParam<{{Entity}}Id>>,
+    State(state): State<Arc<AppState>>,
+    AppJson(request): AppJson<{{Entity}}Request>,
+) -> Result<Json<{{Entity}}Response>, ApiErrorResponse> {
+    let updated = update_{{entity}}(
+        id,
+        request.name,
+        request.description,
+        repository(&state)?,
+    )
+    .await?;
+
+    Ok(Json(updated.into()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{{entities}}/{id}",
+    tag = "{{entities}}",
+    params(
+        ("id" = Uuid, Path, description = "{{Entity}} ID")
+    ),
+    responses(
+        (status = 204, description = "{{Entity}} deleted"),
+        (status = 400, description = "{{Entity}} ID is not a valid UUID", body = ApiErrorResponse),
+        (status = 404, description = "{{Entity}} not found", body = ApiErrorResponse),
+        (status = 500, description = "Internal server error", body = ApiErrorResponse)
+    )
+)]
+#[tracing::instrument(skip_all, fields({{entity}}_id = %id))]
+pub async fn delete_{{entity}}_handler(
+    AppPath(PathParam(id)): AppPath<PathParam<{{Entity}}Id>>,
+    State(state): State<Arc<AppState>>,
+) -> Result<StatusCode, ApiErrorResponse> {
+    delete_{{entity}}(id, repository(&state)?).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,22 @@
+pub mod handlers;
+
+use std::sync::Arc;
+
+use axum::{routing::get, Router};
+
+use crate::config::AppState;
+
+/// Routes of the {{entities_words}} API, merged into the application router
+pub fn routes() -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/{{entities}}",
+            get(handlers::list_{{entities}}_handler).post(handlers::create_{{entity}}_handler),
+        )
+        .route(
+            "/{{entities}}/{id}",
+            get(handlers::get_{{entity}}_handler)
+                .put(handlers::update_{{entity}}_handler)
+                .delete(handlers::delete_{{entity}}_handler),
+        )
+}
@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+// <feature:swagger>
+use utoipa::ToSchema;
+// </feature:swagger>
+
+use crate::domain::{{entity}}::models::{{Entity}};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct {{Entity}}Response {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// Serialized as RFC 3339 in UTC, e.g. `2024-01-15T09:30:00.123456Z`
+    #[schema(format = DateTime)]
+    pub created_at: DateTime<Utc>,
+    #[schema(format = DateTime)]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<{{Entity}}> for {{Entity}}Response {
+    fn from(entity: {{Entity}}) -> Self {
+        Self {
+            id: entity.id.to_string(),
+            name: entity.name,
+            description: entity.description,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        }
+    }
+}
+
+/// Body of both `POST /{{entities}}` and `PUT /{{entities}}/{id}`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct {{Entity}}Request {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
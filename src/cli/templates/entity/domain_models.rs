@@ -0,0 +1,116 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::errors::DomainError;
+
+/// Maximum length of a name in characters (Unicode scalar values), not bytes
+const MAX_NAME_LENGTH: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct {{Entity}}Id(Uuid);
+
+impl {{Entity}}Id {
+    #[must_use]
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> Uuid {
+        self.0
+    }
+}
+
+impl Default for {{Entity}}Id {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Uuid> for {{Entity}}Id {
+    fn from(id: Uuid) -> Self {
+        Self(id)
+    }
+}
+
+impl std::fmt::Display for {{Entity}}Id {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct {{Entity}} {
+    pub id: {{Entity}}Id,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl {{Entity}} {
+    pub fn new(name: String, description: Option<String>) -> Result<Self, DomainError> {
+        let now = Utc::now();
+        Ok(Self {
+            id: {{Entity}}Id::new(),
+            name: validate_name(&name)?,
+            description: normalize_description(description),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Replace the name and description, keeping the id and creation time
+    pub fn update(&mut self, name: String, description: Option<String>) -> Result<(), DomainError> {
+        self.name = validate_name(&name)?;
+        self.description = normalize_description(description);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+}
+
+/// Trim a user-supplied name and check its length
+fn validate_name(name: &str) -> Result<String, DomainError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(DomainError::field_validation_error(
+            "name",
+            "Name cannot be empty",
+        ));
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(DomainError::field_validation_error(
+            "name",
+            format!("Name cannot exceed {MAX_NAME_LENGTH} characters"),
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Trim a description, dropping it when nothing is left
+fn normalize_description(description: Option<String>) -> Option<String> {
+    description
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_trims_fields_and_rejects_invalid_names() {
+        let created = {{Entity}}::new("  Name ".to_string(), Some("  ".to_string())).unwrap();
+        assert_eq!(created.name, "Name");
+        assert_eq!(created.description, None);
+
+        // Negative test: Blank and overlong names
+        for name in [" ".to_string(), "x".repeat(MAX_NAME_LENGTH + 1)] {
+            assert!(matches!(
+                {{Entity}}::new(name, None),
+                Err(DomainError::ValidationError { .. })
+            ));
+        }
+    }
+}
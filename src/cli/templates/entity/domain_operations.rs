@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use super::models::{{{Entity}}, {{Entity}}Id};
+use crate::domain::{errors::DomainError, interfaces::{{entity}}_repository::{{Entity}}Repository};
+
+/// Retrieve a {{entity_words}} by ID
+///
+/// Returns an error if the {{entity_words}} is not found.
+pub async fn get_{{entity}}(
+    id: {{Entity}}Id,
+    repo: Arc<dyn {{Entity}}Repository>,
+) -> Result<{{Entity}}, DomainError> {
+    let result: Option<{{Entity}}> = repo.get(id).await?;
+    result.ok_or_else(|| DomainError::not_found("{{Entity}}", id.to_string()))
+}
+
+/// List all {{entities_words}}, newest first
+pub async fn list_{{entities}}(
+    repo: Arc<dyn {{Entity}}Repository>,
+) -> Result<Vec<{{Entity}}>, DomainError> {
+    repo.list().await
+}
+
+/// Store a new {{entity_words}}, validated by [`{{Entity}}::new`]
+pub async fn create_{{entity}}(
+    entity: {{Entity}},
+    repo: Arc<dyn {{Entity}}Repository>,
+) -> Result<{{Entity}}, DomainError> {
+    repo.create(entity).await
+}
+
+/// Replace the name and description of an existing {{entity_words}}
+pub async fn update_{{entity}}(
+    id: {{Entity}}Id,
+    name: String,
+    description: Option<String>,
+    repo: Arc<dyn {{Entity}}Repository>,
+) -> Result<{{Entity}}, DomainError> {
+    let mut entity = get_{{entity}}(id, repo.clone()).await?;
+    entity.update(name, description)?;
+    repo.update(&entity).await?;
+    Ok(entity)
+}
+
+/// Delete a {{entity_words}}
+///
+/// Returns an error if the {{entity_words}} is not found.
+pub async fn delete_{{entity}}(
+    id: {{Entity}}Id,
+    repo: Arc<dyn {{Entity}}Repository>,
+) -> Result<(), DomainError> {
+    repo.delete(id).await
+}
@@ -0,0 +1,9 @@
+CREATE TABLE {{entities}} (
+    id UUID PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_{{entities}}_created_at ON {{entities}}(created_at);
@@ -0,0 +1,134 @@
+use async_trait::async_trait;
+use sqlx::{postgres::PgQueryResult, PgPool};
+use uuid::Uuid;
+
+use crate::domain::{
+    errors::DomainError,
+    interfaces::{{entity}}_repository::{{Entity}}Repository,
+    {{entity}}::models::{{{Entity}}, {{Entity}}Id},
+};
+
+/// [`{{Entity}}Repository`] backed by the `{{entities}}` table
+#[derive(Debug, Clone)]
+pub struct Postgres{{Entity}}Repository {
+    pool: PgPool,
+}
+
+impl Postgres{{Entity}}Repository {
+    #[must_use]
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl {{Entity}}Repository for Postgres{{Entity}}Repository {
+    #[tracing::instrument(skip_all, fields({{entity}}_id = %entity.id))]
+    async fn create(&self, entity: {{Entity}}) -> Result<{{Entity}}, DomainError> {
+        let row = sqlx::query_as::<_, {{Entity}}Row>(
+            r#"
+            INSERT INTO {{entities}} (id, name, description, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, name, description, created_at, updated_at
+            "#,
+        )
+        .bind(entity.id.into_inner())
+        .bind(&entity.name)
+        .bind(&entity.description)
+        .bind(entity.created_at)
+        .bind(entity.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.into())
+    }
+
+    #[tracing::instrument(skip(self), fields({{entity}}_id = %id))]
+    async fn get(&self, id: {{Entity}}Id) -> Result<Option<{{Entity}}>, DomainError> {
+        let row = sqlx::query_as::<_, {{Entity}}Row>(
+            r#"
+            SELECT id, name, description, created_at, updated_at
+            FROM {{entities}}
+            WHERE id = $1
+            "#,
+        )
+        .bind(id.into_inner())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list(&self) -> Result<Vec<{{Entity}}>, DomainError> {
+        let rows = sqlx::query_as::<_, {{Entity}}Row>(
+            r#"
+            SELECT id, name, description, created_at, updated_at
+            FROM {{entities}}
+            ORDER BY created_at DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    #[tracing::instrument(skip_all, fields({{entity}}_id = %entity.id))]
+    async fn update(&self, entity: &{{Entity}}) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE {{entities}}
+            SET name = $2, description = $3, updated_at = $4
+            WHERE id = $1
+            "#,
+        )
+        .bind(entity.id.into_inner())
+        .bind(&entity.name)
+        .bind(&entity.description)
+        .bind(entity.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        expect_affected(&result, entity.id)
+    }
+
+    #[tracing::instrument(skip(self), fields({{entity}}_id = %id))]
+    async fn delete(&self, id: {{Entity}}Id) -> Result<(), DomainError> {
+        let result = sqlx::query("DELETE FROM {{entities}} WHERE id = $1")
+            .bind(id.into_inner())
+            .execute(&self.pool)
+            .await?;
+
+        expect_affected(&result, id)
+    }
+}
+
+/// Map an update or delete that matched no row to `DomainError::NotFound`
+fn expect_affected(result: &PgQueryResult, id: {{Entity}}Id) -> Result<(), DomainError> {
+    if result.rows_affected() == 0 {
+        return Err(DomainError::not_found("{{Entity}}", id.to_string()));
+    }
+    Ok(())
+}
+
+#[derive(sqlx::FromRow)]
+struct {{Entity}}Row {
+    id: Uuid,
+    name: String,
+    description: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<{{Entity}}Row> for {{Entity}} {
+    fn from(row: {{Entity}}Row) -> Self {
+        Self {
+            id: {{Entity}}Id::from(row.id),
+            name: row.name,
+            description: row.description,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
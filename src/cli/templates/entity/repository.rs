@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use std::fmt::Debug;
+
+use crate::domain::{
+    errors::DomainError,
+    {{entity}}::models::{{{Entity}}, {{Entity}}Id},
+};
+
+#[async_trait]
+pub trait {{Entity}}Repository: Send + Sync + Debug {
+    async fn create(&self, entity: {{Entity}}) -> Result<{{Entity}}, DomainError>;
+    /// Returns `Ok(None)` when no {{entity_words}} has this id
+    async fn get(&self, id: {{Entity}}Id) -> Result<Option<{{Entity}}>, DomainError>;
+    /// Every {{entity_words}}, newest first
+    async fn list(&self) -> Result<Vec<{{Entity}}>, DomainError>;
+    /// Overwrite the stored {{entity_words}} with `entity`
+    ///
+    /// Returns `DomainError::NotFound` when no {{entity_words}} has `entity.id`.
+    async fn update(&self, entity: &{{Entity}}) -> Result<(), DomainError>;
+    /// Returns `DomainError::NotFound` when no {{entity_words}} has this id
+    async fn delete(&self, id: {{Entity}}Id) -> Result<(), DomainError>;
+}
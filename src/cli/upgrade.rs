@@ -0,0 +1,371 @@
+//! Pulling template changes into a generated service
+//!
+//! The service is regenerated twice with the options in its manifest: from the template
+//! commit it came from, the base, and from the newer template. Whatever changed between
+//! the two is merged into the service three ways with `git merge-file`, so local edits
+//! are kept and overlapping ones end up between conflict markers.
+
+use anyhow::{Context, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use tempfile::TempDir;
+
+use crate::cli::{
+    entity::EntityName,
+    exclusions::Exclusions,
+    generator::{read_if_exists, relative_files, unified_diff, ProjectGenerator},
+    manifest::{GenerationOptions, TemplateSource, MANIFEST_FILE},
+    template::Template,
+};
+
+/// What an upgrade does to a file of the service
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// New in the template
+    Added,
+    /// Unchanged locally, so the template's version is taken
+    Updated,
+    /// Changed both locally and in the template, without overlapping
+    Merged,
+    /// Changed both locally and in the template in the same places, written with
+    /// conflict markers
+    Conflicted,
+    /// Unchanged locally and removed from the template
+    Removed,
+    /// Changed in the template, but left alone, as it was removed or is binary and was
+    /// changed locally
+    Kept,
+}
+
+impl Outcome {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Updated => "updated",
+            Self::Merged => "merged",
+            Self::Conflicted => "conflict",
+            Self::Removed => "removed",
+            Self::Kept => "kept, changed locally",
+        }
+    }
+}
+
+/// A file the upgrade changes, or would but for local changes
+#[derive(Debug)]
+pub struct Change {
+    /// Relative to the service root
+    pub path: PathBuf,
+    pub outcome: Outcome,
+    /// What the service has now
+    current: Option<Vec<u8>>,
+    /// What the upgrade leaves; `None` removes the file
+    upgraded: Option<Vec<u8>>,
+}
+
+/// Generate the service described by `options` from `template` into `dir`
+pub fn regenerate(
+    options: &GenerationOptions,
+    template: &Template,
+    dir: &Path,
+) -> Result<ProjectGenerator> {
+    let exclusions = Exclusions::load(template.path(), &options.exclude, &options.include)?;
+    let generator = ProjectGenerator::new(
+        template.path().to_path_buf(),
+        dir.to_path_buf(),
+        options.without_kafka,
+        options.without_auth,
+        options.without_swagger,
+        options.database,
+        options.name.clone(),
+    )?
+    .with_entity(EntityName::new(
+        &options.entity,
+        Some(options.entity_plural.as_str()),
+    )?)
+    .with_exclusions(exclusions)
+    .with_k8s_manifests(options.with_k8s)
+    .with_helm_chart(options.with_helm)
+    .with_kind(options.kind)
+    .with_ports(options.postgres_port, options.kafka_port)
+    .with_template_source(TemplateSource::of(template));
+    generator
+        .generate()
+        .with_context(|| format!("Failed to generate the service from {}", template.source))?;
+    Ok(generator)
+}
+
+/// The changes that bring the template's changes from `base` to `upgraded`, two
+/// generations of the service, into the `service`
+///
+/// Files the template never had, such as the service's own modules, are left alone.
+/// The manifest is left out; the upgrade writes a new one.
+pub fn plan(service: &Path, base: &Path, upgraded: &Path) -> Result<Vec<Change>> {
+    let mut paths = relative_files(base, |_| false)?;
+    paths.extend(relative_files(upgraded, |_| false)?);
+    paths.sort();
+    paths.dedup();
+
+    let mut changes = Vec::new();
+    for path in paths {
+        if path == Path::new(MANIFEST_FILE) {
+            continue;
+        }
+        let base_content = read_if_exists(&base.join(&path))?;
+        let upgraded_content = read_if_exists(&upgraded.join(&path))?;
+        let current = read_if_exists(&service.join(&path))?;
+        if base_content == upgraded_content || current == upgraded_content {
+            continue;
+        }
+
+        let (outcome, result) = if current == base_content {
+            let outcome = match (&base_content, &upgraded_content) {
+                (None, _) => Outcome::Added,
+                (_, None) => Outcome::Removed,
+                _ => Outcome::Updated,
+            };
+            (outcome, upgraded_content)
+        } else {
+            match (&current, &upgraded_content) {
+                (Some(ours), Some(theirs)) => {
+                    match merge(
+                        &path,
+                        ours,
+                        base_content.as_deref().unwrap_or_default(),
+                        theirs,
+                    )? {
+                        Some((merged, false)) => (Outcome::Merged, Some(merged)),
+                        Some((merged, true)) => (Outcome::Conflicted, Some(merged)),
+                        None => (Outcome::Kept, current.clone()),
+                    }
+                }
+                // Removed on one side and changed on the other: the local side stays
+                _ => (Outcome::Kept, current.clone()),
+            }
+        };
+
+        changes.push(Change {
+            path,
+            outcome,
+            current,
+            upgraded: result,
+        });
+    }
+
+    Ok(changes)
+}
+
+/// Three-way merge of `ours` and `theirs` from `base`, with whether it conflicted, or
+/// `None` for binary files
+fn merge(path: &Path, ours: &[u8], base: &[u8], theirs: &[u8]) -> Result<Option<(Vec<u8>, bool)>> {
+    let is_text = |content: &[u8]| std::str::from_utf8(content).is_ok() && !content.contains(&0);
+    if !(is_text(ours) && is_text(base) && is_text(theirs)) {
+        return Ok(None);
+    }
+
+    let scratch = TempDir::new().context("Failed to create temporary directory")?;
+    let files = [("ours", ours), ("base", base), ("theirs", theirs)].map(|(name, content)| {
+        let file = scratch.path().join(name);
+        fs::write(&file, content).map(|()| file)
+    });
+    let [ours, base, theirs] = files;
+    let name = path.display().to_string();
+    let output = Command::new("git")
+        .arg("merge-file")
+        .arg("--stdout")
+        .args(["-L", &name, "-L", "base", "-L", "template"])
+        .args([ours?, base?, theirs?])
+        .output()
+        .context("Failed to execute git merge-file")?;
+
+    // The exit code is the number of conflicts, or negative on errors
+    match output.status.code() {
+        Some(conflicts @ 0..=127) => Ok(Some((output.stdout, conflicts > 0))),
+        _ => anyhow::bail!(
+            "git merge-file failed for {name}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+    }
+}
+
+/// Write `changes` into the `service`
+pub fn apply(service: &Path, changes: &[Change]) -> Result<()> {
+    for change in changes {
+        let path = service.join(&change.path);
+        match (&change.upgraded, change.outcome) {
+            (_, Outcome::Kept) => {}
+            (Some(content), _) => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)
+                        .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+                }
+                fs::write(&path, content).with_context(|| format!("Failed to write {:?}", path))?;
+            }
+            (None, _) => {
+                fs::remove_file(&path).with_context(|| format!("Failed to remove {:?}", path))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Unified diff of what `changes` do to the service
+pub fn diff(changes: &[Change]) -> String {
+    changes
+        .iter()
+        .filter(|change| change.outcome != Outcome::Kept)
+        .map(|change| {
+            unified_diff(
+                &change.path,
+                change.current.as_deref(),
+                change.upgraded.as_deref(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A directory holding `files`, each a path and its content
+    fn tree(files: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for (path, content) in files {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    fn outcomes(changes: &[Change]) -> Vec<(String, Outcome)> {
+        changes
+            .iter()
+            .map(|change| (change.path.display().to_string(), change.outcome))
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_merges_template_changes_with_local_ones() {
+        // Arrange: a service edited since generation, and a template changed since then
+        let base = tree(&[
+            ("Cargo.toml", "[package]\nname = \"svc\"\n"),
+            (
+                "src/lib.rs",
+                "pub mod api;\n\npub mod config;\n\npub mod domain;\n",
+            ),
+            ("src/config.rs", "port = 3000\n"),
+            ("src/old.rs", "// removed upstream\n"),
+            ("src/kept.rs", "// removed upstream, edited locally\n"),
+            ("README.md", "# svc\n"),
+        ]);
+        let upgraded = tree(&[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"svc\"\nedition = \"2024\"\n",
+            ),
+            (
+                "src/lib.rs",
+                "pub mod api;\n\npub mod config;\n\npub mod domain;\n\npub mod telemetry;\n",
+            ),
+            ("src/config.rs", "port = 8080\n"),
+            ("src/telemetry.rs", "// new upstream\n"),
+            ("README.md", "# svc\n"),
+        ]);
+        let service = tree(&[
+            ("Cargo.toml", "[package]\nname = \"svc\"\n"),
+            (
+                "src/lib.rs",
+                "pub mod api;\n\npub mod billing;\n\npub mod config;\n\npub mod domain;\n",
+            ),
+            ("src/config.rs", "port = 4000\n"),
+            ("src/old.rs", "// removed upstream\n"),
+            (
+                "src/kept.rs",
+                "// removed upstream, edited locally\n// mine\n",
+            ),
+            ("src/billing.rs", "// the service's own\n"),
+            ("README.md", "# svc, the billing service\n"),
+        ]);
+
+        // Act
+        let changes = plan(service.path(), base.path(), upgraded.path()).unwrap();
+
+        // Assert: files only the service has, or the template left alone, are not listed
+        assert_eq!(
+            outcomes(&changes),
+            [
+                ("Cargo.toml".to_string(), Outcome::Updated),
+                ("src/config.rs".to_string(), Outcome::Conflicted),
+                ("src/kept.rs".to_string(), Outcome::Kept),
+                ("src/lib.rs".to_string(), Outcome::Merged),
+                ("src/old.rs".to_string(), Outcome::Removed),
+                ("src/telemetry.rs".to_string(), Outcome::Added),
+            ]
+        );
+
+        // Act
+        apply(service.path(), &changes).unwrap();
+
+        // Assert
+        let read = |path: &str| fs::read_to_string(service.path().join(path)).unwrap();
+        assert!(read("Cargo.toml").contains("edition = \"2024\""));
+        assert_eq!(
+            read("src/lib.rs"),
+            "pub mod api;\n\npub mod billing;\n\npub mod config;\n\npub mod domain;\n\npub mod telemetry;\n"
+        );
+        let config = read("src/config.rs");
+        assert!(
+            config.contains(
+                "<<<<<<< src/config.rs\nport = 4000\n=======\nport = 8080\n>>>>>>> template\n"
+            ),
+            "{config}"
+        );
+        assert_eq!(
+            read("src/kept.rs"),
+            "// removed upstream, edited locally\n// mine\n"
+        );
+        assert!(!service.path().join("src/old.rs").exists());
+        assert_eq!(read("src/telemetry.rs"), "// new upstream\n");
+        assert_eq!(read("README.md"), "# svc, the billing service\n");
+    }
+
+    #[test]
+    fn test_diff_shows_what_the_upgrade_writes_without_writing() {
+        let base = tree(&[("src/config.rs", "port = 3000\n")]);
+        let upgraded = tree(&[("src/config.rs", "port = 8080\n")]);
+        let service = tree(&[("src/config.rs", "port = 3000\n")]);
+
+        let changes = plan(service.path(), base.path(), upgraded.path()).unwrap();
+        let diff = diff(&changes);
+
+        assert!(
+            diff.contains("--- a/src/config.rs\n+++ b/src/config.rs\n"),
+            "{diff}"
+        );
+        assert!(diff.contains("-port = 3000\n+port = 8080\n"), "{diff}");
+        assert_eq!(
+            fs::read_to_string(service.path().join("src/config.rs")).unwrap(),
+            "port = 3000\n"
+        );
+    }
+
+    #[test]
+    fn test_binary_files_changed_on_both_sides_are_kept() {
+        // Negative test: binary files are never merged line by line
+        let base = tree(&[("logo.png", "\0base")]);
+        let upgraded = tree(&[("logo.png", "\0template")]);
+        let service = tree(&[("logo.png", "\0local")]);
+
+        let changes = plan(service.path(), base.path(), upgraded.path()).unwrap();
+
+        assert_eq!(
+            outcomes(&changes),
+            [("logo.png".to_string(), Outcome::Kept)]
+        );
+        assert!(diff(&changes).is_empty());
+    }
+}
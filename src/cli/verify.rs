@@ -0,0 +1,339 @@
+//! Checks that a generated service builds, before it is committed or pushed
+//!
+//! The generator edits the template as text, so a change to the template can leave a
+//! service that no longer compiles. Verification catches that while there is still
+//! nothing on GitHub to clean up.
+
+use anyhow::{anyhow, Context, Result};
+use std::{
+    collections::BTreeSet,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, BufReader},
+    process::Command,
+};
+use tracing::info;
+
+/// Longest a check may run; the first `cargo check` of a service builds every dependency
+pub const CHECK_TIMEOUT: Duration = Duration::from_secs(20 * 60);
+
+/// A cargo command run in the generated service
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    /// `cargo check`
+    Compile,
+    /// `cargo fmt --check`
+    Format,
+}
+
+impl Check {
+    /// Uncoloured, since rustfmt colours diffs even into a pipe and the output is parsed
+    const fn args(self) -> &'static [&'static str] {
+        match self {
+            Self::Compile => &["check", "--color", "never"],
+            Self::Format => &["fmt", "--check", "--", "--color", "never"],
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Compile => "cargo check",
+            Self::Format => "cargo fmt --check",
+        }
+    }
+}
+
+/// A check that failed, with the files its output points at
+#[derive(Debug)]
+pub struct Failure {
+    pub check: Check,
+    /// Relative to the service root
+    pub files: BTreeSet<PathBuf>,
+}
+
+pub struct Verifier {
+    dir: PathBuf,
+    checks: Vec<Check>,
+    timeout: Duration,
+    envs: Vec<(String, OsString)>,
+}
+
+impl Verifier {
+    /// `cargo check` the service in `dir`, then `cargo fmt --check` it if `format` is set
+    pub fn new(dir: PathBuf, format: bool) -> Self {
+        let mut checks = vec![Check::Compile];
+        if format {
+            checks.push(Check::Format);
+        }
+
+        Self {
+            dir,
+            checks,
+            timeout: CHECK_TIMEOUT,
+            envs: Vec::new(),
+        }
+    }
+
+    /// Stop each check after `timeout` instead of [`CHECK_TIMEOUT`]
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Run cargo with an extra environment variable, such as `CARGO_TARGET_DIR`
+    #[must_use]
+    pub fn with_env(mut self, key: &str, value: impl Into<OsString>) -> Self {
+        self.envs.push((key.to_string(), value.into()));
+        self
+    }
+
+    /// Run the checks in order, streaming their output, and stop at the first that fails
+    ///
+    /// Errors are for checks that could not run or did not finish in time.
+    pub async fn run(&self) -> Result<Option<Failure>> {
+        let dir = self
+            .dir
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {:?}", self.dir))?;
+
+        for &check in &self.checks {
+            info!("Running {}...", check.label());
+            if let Some(failure) = self.run_check(check, &dir).await? {
+                return Ok(Some(failure));
+            }
+            info!("✓ {} passed", check.label());
+        }
+
+        Ok(None)
+    }
+
+    async fn run_check(&self, check: Check, dir: &Path) -> Result<Option<Failure>> {
+        let mut child = Command::new("cargo")
+            .args(check.args())
+            .current_dir(dir)
+            .envs(self.envs.iter().map(|(key, value)| (key, value)))
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            // Also what stops it when the timeout drops the future waiting on it
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to run {}", check.label()))?;
+        let stdout = child
+            .stdout
+            .take()
+            .context("cargo stdout was not captured")?;
+        let stderr = child
+            .stderr
+            .take()
+            .context("cargo stderr was not captured")?;
+
+        let finished = async {
+            let (mut files, stderr_files) =
+                tokio::try_join!(relay(stdout, dir), relay(stderr, dir))?;
+            files.extend(stderr_files);
+            let status = child.wait().await?;
+            Ok::<_, std::io::Error>((status, files))
+        };
+        let (status, files) = tokio::time::timeout(self.timeout, finished)
+            .await
+            .map_err(|_| {
+                anyhow!(
+                    "{} did not finish within {} seconds",
+                    check.label(),
+                    self.timeout.as_secs()
+                )
+            })?
+            .with_context(|| format!("Failed to read the output of {}", check.label()))?;
+
+        Ok((!status.success()).then_some(Failure { check, files }))
+    }
+}
+
+/// Echo a cargo output stream line by line, collecting the files errors point at
+async fn relay(stream: impl AsyncRead + Unpin, dir: &Path) -> std::io::Result<BTreeSet<PathBuf>> {
+    let mut lines = BufReader::new(stream).lines();
+    let mut files = BTreeSet::new();
+    // Warnings point at files too, but do not fail the check
+    let mut in_error = false;
+
+    while let Some(line) = lines.next_line().await? {
+        info!("{line}");
+
+        if line.starts_with("error") {
+            in_error = true;
+        } else if line.starts_with("warning") {
+            in_error = false;
+        }
+        if let Some(location) = line.trim_start().strip_prefix("--> ") {
+            if in_error {
+                files.extend(service_file(location, dir));
+            }
+        } else if let Some(location) = line.strip_prefix("Diff in ") {
+            files.extend(service_file(location, dir));
+        }
+    }
+
+    Ok(files)
+}
+
+/// The file of a `path:line:column` location, relative to `dir`, unless it lies outside
+fn service_file(location: &str, dir: &Path) -> Option<PathBuf> {
+    // Older rustfmt reports `Diff in <path> at line <n>:`
+    let location = location.split(" at line ").next()?;
+    let path = Path::new(location.trim_end_matches(|c: char| c.is_ascii_digit() || c == ':'));
+
+    if path.is_absolute() {
+        path.strip_prefix(dir).ok().map(Path::to_path_buf)
+    } else {
+        Some(path.to_path_buf())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{
+        args::DatabaseBackend,
+        entity::EntityName,
+        generator::{tests::generate_service, ProjectGenerator},
+    };
+    use std::fs;
+
+    #[test]
+    fn test_service_file_reads_compiler_and_rustfmt_locations() {
+        let dir = Path::new("/work/svc");
+
+        assert_eq!(
+            service_file("src/api/mod.rs:97:30", dir),
+            Some(PathBuf::from("src/api/mod.rs"))
+        );
+        assert_eq!(
+            service_file("/work/svc/src/config.rs:646:", dir),
+            Some(PathBuf::from("src/config.rs"))
+        );
+        assert_eq!(
+            service_file("/work/svc/src/lib.rs at line 6:", dir),
+            Some(PathBuf::from("src/lib.rs"))
+        );
+        // Negative test: errors inside dependencies are not the generator's doing
+        assert_eq!(
+            service_file(
+                "/home/me/.cargo/registry/src/axum-0.8.4/src/lib.rs:1:1",
+                dir
+            ),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_relay_collects_files_of_errors_only() {
+        let output = "\
+warning: unused import: `Arc`
+ --> src/state.rs:3:5
+error[E0308]: mismatched types
+  --> src/lib.rs:12:26
+   |
+error: could not compile `svc` (lib) due to 1 previous error
+";
+
+        let files = relay(output.as_bytes(), Path::new("/work/svc"))
+            .await
+            .unwrap();
+
+        assert_eq!(files, BTreeSet::from([PathBuf::from("src/lib.rs")]));
+    }
+
+    #[tokio::test]
+    async fn test_verification_catches_a_broken_transform() {
+        // Objective: a transform that leaves invalid code is caught and traced back to it
+        // Arrange: a generated service whose lib.rs gained broken code, as a faulty
+        // `modify_lib_rs` would leave it
+        let (_output, target_dir) = generate_service("broken-svc", true, true, true, "invoice");
+        let lib_path = target_dir.join("src/lib.rs");
+        let lib = fs::read_to_string(&lib_path).unwrap();
+        fs::write(
+            &lib_path,
+            lib + "\npub fn port() -> u16 {\n    \"8080\"\n}\n",
+        )
+        .unwrap();
+        let source_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+        // Act
+        let failure = Verifier::new(target_dir.clone(), true)
+            .with_env("CARGO_NET_OFFLINE", "true")
+            // Kept between runs so only the generated crate is rechecked
+            .with_env("CARGO_TARGET_DIR", source_dir.join("target/generated"))
+            .run()
+            .await
+            .unwrap()
+            .expect("Broken service passed verification");
+
+        // Assert: the format check never runs once compilation fails
+        assert_eq!(failure.check, Check::Compile);
+        assert_eq!(failure.files, BTreeSet::from([PathBuf::from("src/lib.rs")]));
+
+        let generator = ProjectGenerator::new(
+            source_dir,
+            target_dir,
+            true,
+            true,
+            true,
+            DatabaseBackend::Postgres,
+            "broken-svc".to_string(),
+        )
+        .unwrap()
+        .with_entity(EntityName::new("invoice", None).unwrap());
+        let trace = generator
+            .trace(&[
+                PathBuf::from("src/lib.rs"),
+                PathBuf::from("src/domain/invoice/operations.rs"),
+                PathBuf::from("src/cli/main.rs"),
+            ])
+            .unwrap();
+        let steps: Vec<_> = trace.into_iter().map(|(_, steps)| steps).collect();
+        assert_eq!(
+            steps,
+            [
+                Some(vec!["modify_lib_rs", "apply_features"]),
+                // Found under its template path for the steps before the rename
+                Some(vec!["rename_entity"]),
+                // Negative test: never generated, so no step is to blame
+                None,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verification_times_out() {
+        // Negative test: a check that hangs must not hold up `create` forever
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"slow\"\nversion = \"0.1.0\"\nedition = \"2021\"\nbuild = \"build.rs\"\n",
+        )
+        .unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "").unwrap();
+        fs::write(
+            dir.path().join("build.rs"),
+            "fn main() { std::thread::sleep(std::time::Duration::from_secs(30)); }\n",
+        )
+        .unwrap();
+
+        let error = Verifier::new(dir.path().to_path_buf(), false)
+            .with_env("CARGO_TARGET_DIR", dir.path().join("target"))
+            .with_timeout(Duration::from_secs(5))
+            .run()
+            .await
+            .unwrap_err();
+
+        assert!(error
+            .to_string()
+            .contains("did not finish within 5 seconds"));
+    }
+}
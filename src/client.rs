@@ -0,0 +1,343 @@
+//! Typed HTTP client for the task API
+//!
+//! Uses the request and response models of [`crate::api::models`] and
+//! [`ApiErrorResponse`], so the client and the handlers cannot drift apart.
+
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use reqwest::{
+    header::{HeaderMap, HeaderValue, AUTHORIZATION},
+    Method, RequestBuilder, Response, StatusCode,
+};
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        error::{ApiErrorResponse, ErrorCode, FieldErrorResponse},
+        models::{
+            admin::LogLevel,
+            tasks::{CreateTaskRequest, TaskResponse, TaskStatsResponse},
+            users::{CreateUserRequest, UserResponse},
+            webhooks::{
+                CreateWebhookRequest, UpdateWebhookRequest, WebhookDeliveryResponse,
+                WebhookResponse,
+            },
+        },
+        tenant::TENANT_HEADER,
+    },
+    common::{TenantId, UserId},
+    infrastructure::telemetry,
+};
+
+/// Timeout of a whole request unless changed with [`TaskApiClient::with_timeout`]
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("Invalid base URL {url}: {message}")]
+    InvalidBaseUrl { url: String, message: String },
+
+    #[error("Invalid bearer token: {0}")]
+    InvalidToken(String),
+
+    #[error("Request timed out")]
+    Timeout(#[source] reqwest::Error),
+
+    #[error("Request failed: {0}")]
+    Transport(#[source] reqwest::Error),
+
+    /// The service answered with its JSON error envelope
+    #[error("API error {status}: {code:?}")]
+    Api {
+        status: StatusCode,
+        code: ErrorCode,
+        message: Option<String>,
+        /// Every invalid field, on validation errors naming fields
+        errors: Vec<FieldErrorResponse>,
+    },
+
+    /// A response that is neither the expected body nor the error envelope
+    #[error("Unexpected response {status}: {body}")]
+    UnexpectedResponse { status: StatusCode, body: String },
+}
+
+impl ClientError {
+    /// The API error code, when the service returned one
+    pub const fn code(&self) -> Option<&ErrorCode> {
+        match self {
+            Self::Api { code, .. } => Some(code),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            Self::Timeout(error)
+        } else {
+            Self::Transport(error)
+        }
+    }
+}
+
+/// Client for one deployment of the task service
+///
+/// Cheap to clone; clones share the connection pool.
+#[derive(Debug, Clone)]
+pub struct TaskApiClient {
+    client: reqwest::Client,
+    base_url: String,
+    timeout: Duration,
+    bearer_token: Option<HeaderValue>,
+    tenant: Option<TenantId>,
+}
+
+impl TaskApiClient {
+    /// Client for the service at `base_url`, e.g. `https://tasks.example.com`
+    pub fn new(base_url: impl Into<String>) -> Result<Self, ClientError> {
+        let base_url = base_url.into();
+        let parsed = url::Url::parse(&base_url).map_err(|e| ClientError::InvalidBaseUrl {
+            url: base_url.clone(),
+            message: e.to_string(),
+        })?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(ClientError::InvalidBaseUrl {
+                url: base_url,
+                message: "scheme must be http or https".to_string(),
+            });
+        }
+
+        let client = reqwest::Client::builder()
+            .user_agent(concat!(
+                env!("CARGO_PKG_NAME"),
+                "-client/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .build()
+            .map_err(ClientError::Transport)?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            timeout: DEFAULT_TIMEOUT,
+            bearer_token: None,
+            tenant: None,
+        })
+    }
+
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Send `Authorization: Bearer <token>` with every request
+    pub fn with_bearer_token(mut self, token: &str) -> Result<Self, ClientError> {
+        let mut value = HeaderValue::from_str(&format!("Bearer {token}"))
+            .map_err(|e| ClientError::InvalidToken(e.to_string()))?;
+        value.set_sensitive(true);
+        self.bearer_token = Some(value);
+        Ok(self)
+    }
+
+    /// Send `X-Tenant-Id: <tenant>` with every request, for services in multi-tenant mode
+    #[must_use]
+    pub const fn with_tenant(mut self, tenant: TenantId) -> Self {
+        self.tenant = Some(tenant);
+        self
+    }
+
+    /// `GET /health`
+    pub async fn health(&self) -> Result<(), ClientError> {
+        self.send_for_status(self.request(Method::GET, "/health"))
+            .await
+    }
+
+    /// `GET /ready`
+    pub async fn ready(&self) -> Result<(), ClientError> {
+        self.send_for_status(self.request(Method::GET, "/ready"))
+            .await
+    }
+
+    /// `POST /tasks`
+    pub async fn create_task(
+        &self,
+        request: &CreateTaskRequest,
+    ) -> Result<TaskResponse, ClientError> {
+        self.send(self.request(Method::POST, "/tasks").json(request))
+            .await
+    }
+
+    /// `GET /tasks/{id}`
+    pub async fn get_task(&self, id: Uuid) -> Result<TaskResponse, ClientError> {
+        self.send(self.request(Method::GET, &format!("/tasks/{id}")))
+            .await
+    }
+
+    /// `GET /users/{user_id}/tasks`, newest first; needs a bearer token for that user or an
+    /// admin
+    pub async fn list_tasks(&self, user_id: UserId) -> Result<Vec<TaskResponse>, ClientError> {
+        self.send(self.request(Method::GET, &format!("/users/{user_id}/tasks")))
+            .await
+    }
+
+    /// `GET /users/{user_id}/stats`, counting days from `from` to `to` in UTC unless `tz`
+    /// names another zone; needs a bearer token for that user or an admin
+    pub async fn task_stats(
+        &self,
+        user_id: UserId,
+        from: NaiveDate,
+        to: NaiveDate,
+        tz: Option<&str>,
+    ) -> Result<TaskStatsResponse, ClientError> {
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        query
+            .append_pair("from", &from.to_string())
+            .append_pair("to", &to.to_string());
+        if let Some(tz) = tz {
+            query.append_pair("tz", tz);
+        }
+        self.send(self.request(
+            Method::GET,
+            &format!("/users/{user_id}/stats?{}", query.finish()),
+        ))
+        .await
+    }
+
+    /// `POST /users`
+    pub async fn create_user(
+        &self,
+        request: &CreateUserRequest,
+    ) -> Result<UserResponse, ClientError> {
+        self.send(self.request(Method::POST, "/users").json(request))
+            .await
+    }
+
+    /// `GET /users/{id}`
+    pub async fn get_user(&self, id: UserId) -> Result<UserResponse, ClientError> {
+        self.send(self.request(Method::GET, &format!("/users/{id}")))
+            .await
+    }
+
+    /// `POST /webhooks`, the only response that includes the secret; needs an admin token
+    pub async fn create_webhook(
+        &self,
+        request: &CreateWebhookRequest,
+    ) -> Result<WebhookResponse, ClientError> {
+        self.send(self.request(Method::POST, "/webhooks").json(request))
+            .await
+    }
+
+    /// `GET /webhooks`; needs an admin token
+    pub async fn list_webhooks(&self) -> Result<Vec<WebhookResponse>, ClientError> {
+        self.send(self.request(Method::GET, "/webhooks")).await
+    }
+
+    /// `GET /webhooks/{id}`; needs an admin token
+    pub async fn get_webhook(&self, id: Uuid) -> Result<WebhookResponse, ClientError> {
+        self.send(self.request(Method::GET, &format!("/webhooks/{id}")))
+            .await
+    }
+
+    /// `PUT /webhooks/{id}`; needs an admin token
+    pub async fn update_webhook(
+        &self,
+        id: Uuid,
+        request: &UpdateWebhookRequest,
+    ) -> Result<WebhookResponse, ClientError> {
+        self.send(
+            self.request(Method::PUT, &format!("/webhooks/{id}"))
+                .json(request),
+        )
+        .await
+    }
+
+    /// `DELETE /webhooks/{id}`; needs an admin token
+    pub async fn delete_webhook(&self, id: Uuid) -> Result<(), ClientError> {
+        self.send_for_status(self.request(Method::DELETE, &format!("/webhooks/{id}")))
+            .await
+    }
+
+    /// `GET /webhooks/{id}/deliveries`, newest first; needs an admin token
+    pub async fn list_webhook_deliveries(
+        &self,
+        id: Uuid,
+    ) -> Result<Vec<WebhookDeliveryResponse>, ClientError> {
+        self.send(self.request(Method::GET, &format!("/webhooks/{id}/deliveries")))
+            .await
+    }
+
+    /// `GET /admin/log-level`; needs a bearer token
+    pub async fn get_log_level(&self) -> Result<LogLevel, ClientError> {
+        self.send(self.request(Method::GET, "/admin/log-level"))
+            .await
+    }
+
+    /// `PUT /admin/log-level`; needs a bearer token
+    pub async fn set_log_level(&self, level: &LogLevel) -> Result<LogLevel, ClientError> {
+        self.send(self.request(Method::PUT, "/admin/log-level").json(level))
+            .await
+    }
+
+    /// `GET /admin/config`, with secrets redacted; needs a bearer token
+    pub async fn get_config(&self) -> Result<serde_json::Value, ClientError> {
+        self.send(self.request(Method::GET, "/admin/config")).await
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let mut headers = HeaderMap::new();
+        // Joins the caller's trace when telemetry is enabled
+        telemetry::inject_current_context(&mut headers);
+        if let Some(token) = &self.bearer_token {
+            headers.insert(AUTHORIZATION, token.clone());
+        }
+        if let Some(tenant) = self.tenant {
+            headers.insert(
+                TENANT_HEADER,
+                HeaderValue::from_str(&tenant.to_string()).expect("A UUID is a valid header value"),
+            );
+        }
+
+        self.client
+            .request(method, format!("{}{path}", self.base_url))
+            .timeout(self.timeout)
+            .headers(headers)
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, ClientError> {
+        let response = successful(request.send().await?).await?;
+        let status = response.status();
+        let body = response.bytes().await?;
+
+        serde_json::from_slice(&body).map_err(|_| ClientError::UnexpectedResponse {
+            status,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        })
+    }
+
+    async fn send_for_status(&self, request: RequestBuilder) -> Result<(), ClientError> {
+        successful(request.send().await?).await.map(drop)
+    }
+}
+
+/// Pass successful responses through; turn the rest into a [`ClientError`]
+async fn successful(response: Response) -> Result<Response, ClientError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let body = response.text().await?;
+    Err(match serde_json::from_str::<ApiErrorResponse>(&body) {
+        Ok(error) => ClientError::Api {
+            status,
+            code: error.code,
+            message: error.message,
+            errors: error.errors.unwrap_or_default(),
+        },
+        Err(_) => ClientError::UnexpectedResponse { status, body },
+    })
+}
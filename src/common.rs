@@ -54,3 +54,80 @@ impl From<UserId> for Uuid {
         id.0
     }
 }
+
+/// Tenant that owns a row; every repository query is scoped to one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TenantId(Uuid);
+
+impl TenantId {
+    /// The only tenant in single-tenant mode, and the owner of rows stored before
+    /// multi-tenancy
+    pub const DEFAULT: Self = Self(Uuid::nil());
+
+    /// Create a new random tenant ID
+    #[must_use]
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    /// Get the inner UUID value
+    #[must_use]
+    pub fn into_inner(self) -> Uuid {
+        self.0
+    }
+
+    /// Get a reference to the inner UUID value
+    #[must_use]
+    pub fn as_uuid(&self) -> &Uuid {
+        &self.0
+    }
+}
+
+impl Default for TenantId {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl std::fmt::Display for TenantId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<Uuid> for TenantId {
+    fn from(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+}
+
+impl From<TenantId> for Uuid {
+    fn from(id: TenantId) -> Self {
+        id.0
+    }
+}
+
+/// Resolve once the process is asked to stop, by Ctrl+C or, on Unix, SIGTERM
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {},
+        () = terminate => {},
+    }
+}
@@ -0,0 +1,219 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{
+    errors::{DomainError, FieldError},
+    task::models::TaskId,
+};
+
+/// Longest filename accepted, in characters, the limit of most file systems
+const MAX_FILENAME_LENGTH: usize = 255;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AttachmentId(Uuid);
+
+impl AttachmentId {
+    #[must_use]
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> Uuid {
+        self.0
+    }
+}
+
+impl Default for AttachmentId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Uuid> for AttachmentId {
+    fn from(id: Uuid) -> Self {
+        Self(id)
+    }
+}
+
+impl std::fmt::Display for AttachmentId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Which files may be attached: their media types and largest size
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachmentLimits {
+    /// Largest file, in bytes
+    pub max_size: u64,
+    /// Media types accepted, e.g. `application/pdf`, or a whole type such as `image/*`
+    pub allowed_content_types: Vec<String>,
+}
+
+impl AttachmentLimits {
+    /// Whether `content_type`, already lowercased and without parameters, is accepted
+    #[must_use]
+    pub fn allows(&self, content_type: &str) -> bool {
+        self.allowed_content_types.iter().any(|allowed| {
+            let allowed = allowed.trim().to_ascii_lowercase();
+            match allowed.strip_suffix("/*") {
+                Some(top_level) => content_type
+                    .split_once('/')
+                    .is_some_and(|(kind, _)| kind == top_level),
+                None => allowed == content_type,
+            }
+        })
+    }
+}
+
+/// Metadata of a file attached to a task
+///
+/// The file itself is uploaded straight to the object store under `object_key`, so the
+/// service never handles its bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: AttachmentId,
+    pub task_id: TaskId,
+    pub filename: String,
+    /// Media type without parameters, lowercased, e.g. `image/png`
+    pub content_type: String,
+    /// Size in bytes the upload must have
+    pub size: u64,
+    pub object_key: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Attachment {
+    /// Describe a file about to be uploaded for `task_id`, reporting every invalid field
+    /// at once
+    pub fn new(
+        task_id: TaskId,
+        filename: &str,
+        content_type: &str,
+        size: u64,
+        limits: &AttachmentLimits,
+    ) -> Result<Self, DomainError> {
+        let mut errors = Vec::new();
+
+        let filename = filename.trim();
+        if filename.is_empty() {
+            errors.push(FieldError::new(
+                "filename",
+                "required",
+                "Filename cannot be empty",
+            ));
+        } else if filename.chars().count() > MAX_FILENAME_LENGTH {
+            errors.push(FieldError::new(
+                "filename",
+                "too_long",
+                format!("Filename cannot exceed {MAX_FILENAME_LENGTH} characters"),
+            ));
+        } else if filename
+            .chars()
+            .any(|c| c == '/' || c == '\\' || c.is_control())
+        {
+            errors.push(FieldError::new(
+                "filename",
+                "invalid_characters",
+                "Filename cannot contain slashes or control characters",
+            ));
+        }
+
+        let content_type = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        if !limits.allows(&content_type) {
+            errors.push(FieldError::new(
+                "content_type",
+                "not_allowed",
+                format!(
+                    "Content type must be one of {}",
+                    limits.allowed_content_types.join(", ")
+                ),
+            ));
+        }
+
+        if !(1..=limits.max_size).contains(&size) {
+            errors.push(FieldError::new(
+                "size",
+                "out_of_range",
+                format!("Size must be between 1 and {} bytes", limits.max_size),
+            ));
+        }
+        DomainError::check_fields(errors)?;
+
+        let id = AttachmentId::new();
+        Ok(Self {
+            id,
+            task_id,
+            filename: filename.to_string(),
+            content_type,
+            size,
+            object_key: format!("tasks/{task_id}/{id}"),
+            created_at: Utc::now(),
+        })
+    }
+}
+
+/// URL a client uploads a file to with a single PUT, without going through the service
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresignedUpload {
+    pub url: String,
+    /// Headers the PUT must send with exactly these values, or the store rejects it
+    pub headers: Vec<(String, String)>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> AttachmentLimits {
+        AttachmentLimits {
+            max_size: 1024,
+            allowed_content_types: vec!["application/pdf".to_string(), "image/*".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_new_normalizes_content_type_and_keys_object_by_task() {
+        let task_id = TaskId::new();
+
+        let attachment = Attachment::new(
+            task_id,
+            " scan.PNG ",
+            "Image/PNG; charset=binary",
+            512,
+            &limits(),
+        )
+        .unwrap();
+
+        assert_eq!(attachment.filename, "scan.PNG");
+        assert_eq!(attachment.content_type, "image/png");
+        assert_eq!(
+            attachment.object_key,
+            format!("tasks/{task_id}/{}", attachment.id)
+        );
+    }
+
+    #[test]
+    fn test_new_reports_every_invalid_field() {
+        let Err(DomainError::InvalidFields { errors }) = Attachment::new(
+            TaskId::new(),
+            "../etc/passwd",
+            "application/x-msdownload",
+            1025,
+            &limits(),
+        ) else {
+            panic!("Attachment should be rejected");
+        };
+
+        let fields: Vec<_> = errors.iter().map(|error| error.field.as_str()).collect();
+        assert_eq!(fields, ["filename", "content_type", "size"]);
+    }
+}
@@ -0,0 +1,87 @@
+use std::{sync::Arc, time::Duration};
+
+use super::models::{Attachment, AttachmentId, PresignedUpload};
+use crate::domain::{
+    errors::DomainError,
+    interfaces::{attachment_repository::AttachmentRepository, object_store::ObjectStore},
+    task::models::TaskId,
+};
+
+/// Store an attachment validated by [`Attachment::new`] and presign the upload of its file
+///
+/// The metadata is stored before the client uploads, so an attachment whose upload never
+/// happens is listed until it is deleted.
+pub async fn request_upload(
+    attachment: Attachment,
+    expires_in: Duration,
+    repo: Arc<dyn AttachmentRepository>,
+    store: Arc<dyn ObjectStore>,
+) -> Result<(Attachment, PresignedUpload), DomainError> {
+    let upload = store.presign_put(
+        &attachment.object_key,
+        &attachment.content_type,
+        attachment.size,
+        expires_in,
+    )?;
+    let attachment = repo.create(attachment).await?;
+    Ok((attachment, upload))
+}
+
+/// The task's attachments, oldest first
+pub async fn list_attachments(
+    task_id: TaskId,
+    repo: Arc<dyn AttachmentRepository>,
+) -> Result<Vec<Attachment>, DomainError> {
+    repo.list(task_id).await
+}
+
+/// Delete an attachment and its object
+///
+/// When the store cannot delete the object now, the attachment is still deleted and the
+/// object stays recorded as orphaned for [`delete_orphaned_objects`] to retry.
+pub async fn delete_attachment(
+    task_id: TaskId,
+    id: AttachmentId,
+    repo: Arc<dyn AttachmentRepository>,
+    store: Arc<dyn ObjectStore>,
+) -> Result<Attachment, DomainError> {
+    let attachment = repo.delete(task_id, id).await?;
+    match store.delete(&attachment.object_key).await {
+        Ok(()) => {
+            repo.forget_orphans(std::slice::from_ref(&attachment.object_key))
+                .await?;
+        }
+        Err(error) => tracing::warn!(
+            attachment_id = %attachment.id,
+            object_key = %attachment.object_key,
+            store = store.name(),
+            error = %error,
+            "Failed to delete the object of a deleted attachment, leaving it for the next sweep"
+        ),
+    }
+    Ok(attachment)
+}
+
+/// Delete up to `limit` objects left behind by deleted attachments, returning how many
+///
+/// Objects the store fails to delete stay recorded and are retried on the next call.
+pub async fn delete_orphaned_objects(
+    limit: u32,
+    repo: Arc<dyn AttachmentRepository>,
+    store: Arc<dyn ObjectStore>,
+) -> Result<u64, DomainError> {
+    let mut deleted = Vec::new();
+    for object_key in repo.orphaned_objects(limit).await? {
+        match store.delete(&object_key).await {
+            Ok(()) => deleted.push(object_key),
+            Err(error) => tracing::warn!(
+                object_key = %object_key,
+                store = store.name(),
+                error = %error,
+                "Failed to delete an orphaned object"
+            ),
+        }
+    }
+    repo.forget_orphans(&deleted).await?;
+    Ok(deleted.len() as u64)
+}
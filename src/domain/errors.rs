@@ -1,5 +1,74 @@
+use std::fmt;
+
 use thiserror::Error;
 
+/// SQLSTATE `query_canceled`, raised when `statement_timeout` cancels a statement
+const QUERY_CANCELED: &str = "57014";
+
+/// External dependency an [`DomainError::ExternalError`] originated from
+///
+/// The API layer picks the response code from this rather than from the error message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalSystem {
+    Database,
+    Kafka,
+    Http,
+    ObjectStore,
+    Other,
+}
+
+impl fmt::Display for ExternalSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Database => "Database",
+            Self::Kafka => "Kafka",
+            Self::Http => "HTTP",
+            Self::ObjectStore => "Object store",
+            Self::Other => "Other",
+        };
+        f.write_str(name)
+    }
+}
+
+/// One invalid field of a request, reported together with the others found in the same pass
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: String,
+    /// Machine-readable reason, such as `required` or `too_long`
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+impl From<FieldError> for DomainError {
+    fn from(error: FieldError) -> Self {
+        Self::field_validation_error(error.field, error.message)
+    }
+}
+
+fn join_field_errors(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 /// Domain errors representing business logic failures
 ///
 /// These errors are converted to API responses via `From<DomainError> for ApiErrorResponse`
@@ -16,13 +85,26 @@ pub enum DomainError {
         field: Option<String>,
     },
 
-    /// Domain logic violations (already exists, invalid transitions, limits)
+    /// Every invalid field of an input, checked in one pass rather than stopping at the first
+    #[error("Validation errors: {}", join_field_errors(errors))]
+    InvalidFields { errors: Vec<FieldError> },
+
+    /// Domain logic violations (invalid transitions, limits)
     #[error("Business rule violation: {message}")]
     BusinessRuleViolation { message: String, rule: String },
 
+    /// The resource already exists or was changed concurrently
+    #[error("Conflict: {message}")]
+    Conflict {
+        message: String,
+        /// Violated unique constraint, when known
+        constraint: Option<String>,
+    },
+
     /// External system failures (database, external APIs)
     #[error("External system error: {message}")]
     ExternalError {
+        system: ExternalSystem,
         message: String,
         #[source]
         source: Option<anyhow::Error>,
@@ -31,13 +113,42 @@ pub enum DomainError {
     /// Access control violations
     #[error("Unauthorized access: {message}")]
     Unauthorized { message: String },
+
+    /// An operation did not finish within its time limit
+    #[error("Timed out: {operation}")]
+    Timeout { operation: String },
+
+    /// A write would go past a hard quota
+    #[error("Quota exceeded: {quota} is limited to {limit}")]
+    QuotaExceeded { quota: String, limit: u64 },
 }
 
 impl From<sqlx::Error> for DomainError {
     fn from(error: sqlx::Error) -> Self {
-        Self::ExternalError {
-            message: format!("Database error: {error}"),
-            source: Some(error.into()),
+        match &error {
+            sqlx::Error::RowNotFound => Self::not_found("Record", "unknown"),
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => Self::Conflict {
+                message: db_error.message().to_string(),
+                constraint: db_error.constraint().map(str::to_string),
+            },
+            // The row points at one that does not exist, such as a task owned by an
+            // unregistered user
+            sqlx::Error::Database(db_error) if db_error.is_foreign_key_violation() => {
+                Self::not_found(
+                    "Referenced record",
+                    db_error.constraint().unwrap_or("unknown"),
+                )
+            }
+            sqlx::Error::Database(db_error)
+                if db_error.code().as_deref() == Some(QUERY_CANCELED) =>
+            {
+                Self::timeout("Database statement")
+            }
+            _ => Self::ExternalError {
+                system: ExternalSystem::Database,
+                message: format!("Database error: {error}"),
+                source: Some(error.into()),
+            },
         }
     }
 }
@@ -67,6 +178,15 @@ impl DomainError {
         }
     }
 
+    /// Create a validation error from the failures of every field; `Ok` when there are none
+    pub fn check_fields(errors: Vec<FieldError>) -> Result<(), Self> {
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Self::InvalidFields { errors })
+        }
+    }
+
     /// Create a business rule violation error
     pub fn business_rule_violation(rule: impl Into<String>, message: impl Into<String>) -> Self {
         Self::BusinessRuleViolation {
@@ -75,18 +195,57 @@ impl DomainError {
         }
     }
 
+    /// Create a conflict error without a known constraint
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::Conflict {
+            message: message.into(),
+            constraint: None,
+        }
+    }
+
     /// Create an external system error
-    pub fn external_error(message: impl Into<String>) -> Self {
+    pub fn external_error(system: ExternalSystem, message: impl Into<String>) -> Self {
         Self::ExternalError {
+            system,
             message: message.into(),
             source: None,
         }
     }
 
+    /// Create a timeout error
+    pub fn timeout(operation: impl Into<String>) -> Self {
+        Self::Timeout {
+            operation: operation.into(),
+        }
+    }
+
+    /// Create a quota exceeded error for the hard `limit` of `quota`
+    pub fn quota_exceeded(quota: impl fmt::Display, limit: u64) -> Self {
+        Self::QuotaExceeded {
+            quota: quota.to_string(),
+            limit,
+        }
+    }
+
     /// Create an unauthorized error
     pub fn unauthorized(message: impl Into<String>) -> Self {
         Self::Unauthorized {
             message: message.into(),
         }
     }
+
+    /// Short name of the variant, e.g. `not_found`, for metric labels
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::NotFound { .. } => "not_found",
+            Self::ValidationError { .. } | Self::InvalidFields { .. } => "validation",
+            Self::BusinessRuleViolation { .. } => "business_rule_violation",
+            Self::Conflict { .. } => "conflict",
+            Self::ExternalError { .. } => "external",
+            Self::Unauthorized { .. } => "unauthorized",
+            Self::Timeout { .. } => "timeout",
+            Self::QuotaExceeded { .. } => "quota_exceeded",
+        }
+    }
 }
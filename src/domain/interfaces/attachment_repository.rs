@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use std::fmt::Debug;
+
+use crate::domain::{
+    attachment::models::{Attachment, AttachmentId},
+    errors::DomainError,
+    task::models::TaskId,
+};
+
+#[async_trait]
+pub trait AttachmentRepository: Send + Sync + Debug {
+    async fn create(&self, entity: Attachment) -> Result<Attachment, DomainError>;
+    /// The task's attachments, oldest first
+    async fn list(&self, task_id: TaskId) -> Result<Vec<Attachment>, DomainError>;
+    /// Delete an attachment of `task_id`, returning it
+    ///
+    /// Its object key is recorded as orphaned until [`Self::forget_orphans`] is called
+    /// with it. Returns `DomainError::NotFound` when the task has no attachment with this id.
+    async fn delete(&self, task_id: TaskId, id: AttachmentId) -> Result<Attachment, DomainError>;
+    /// Object keys of deleted attachments whose objects may still be stored, oldest first
+    async fn orphaned_objects(&self, limit: u32) -> Result<Vec<String>, DomainError>;
+    /// Stop tracking orphaned object keys whose objects were deleted
+    async fn forget_orphans(&self, object_keys: &[String]) -> Result<(), DomainError>;
+}
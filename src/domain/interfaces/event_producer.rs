@@ -5,4 +5,9 @@ use crate::domain::{errors::DomainError, task::models::events::TaskEvent};
 #[async_trait]
 pub trait EventProducer: Send + Sync {
     async fn publish_task_event(&self, event: TaskEvent) -> Result<(), DomainError>;
+
+    /// Short name of the sink events go to, used in logs and metrics
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
 }
@@ -0,0 +1,71 @@
+use std::{collections::BTreeMap, fmt::Debug};
+
+/// Behaviours that can be switched on or off per environment without a redeploy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    /// Refuse a new task whose title one of the user's tasks already has
+    RejectDuplicateTitles,
+    /// Apply the configured task and webhook quotas
+    EnforceQuotas,
+    /// Warn about a new task whose title one of the user's open tasks has, ignoring case
+    WarnDuplicateTitles,
+}
+
+impl Feature {
+    pub const ALL: [Self; 3] = [
+        Self::RejectDuplicateTitles,
+        Self::EnforceQuotas,
+        Self::WarnDuplicateTitles,
+    ];
+
+    /// Name of the flag in configuration, e.g. `reject_duplicate_titles`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::RejectDuplicateTitles => "reject_duplicate_titles",
+            Self::EnforceQuotas => "enforce_quotas",
+            Self::WarnDuplicateTitles => "warn_duplicate_titles",
+        }
+    }
+
+    /// Value used when the flag is not set
+    pub fn default_enabled(self) -> bool {
+        match self {
+            Self::RejectDuplicateTitles => false,
+            Self::EnforceQuotas => true,
+            Self::WarnDuplicateTitles => false,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|feature| feature.name() == name)
+    }
+}
+
+/// Source of feature flag values, evaluated on every check
+///
+/// The service reads flags from its configuration; a client of a flag service such as
+/// LaunchDarkly or Unleash can implement this trait instead.
+pub trait FeatureFlags: Send + Sync + Debug {
+    /// Whether `feature` is on, its default when the source does not set it
+    fn is_enabled(&self, feature: Feature) -> bool;
+
+    /// Every feature with its current value, by name
+    fn snapshot(&self) -> BTreeMap<&'static str, bool> {
+        Feature::ALL
+            .into_iter()
+            .map(|feature| (feature.name(), self.is_enabled(feature)))
+            .collect()
+    }
+
+    fn rejects_duplicate_titles(&self) -> bool {
+        self.is_enabled(Feature::RejectDuplicateTitles)
+    }
+
+    fn enforces_quotas(&self) -> bool {
+        self.is_enabled(Feature::EnforceQuotas)
+    }
+
+    fn warns_on_duplicate_titles(&self) -> bool {
+        self.is_enabled(Feature::WarnDuplicateTitles)
+    }
+}
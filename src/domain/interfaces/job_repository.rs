@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use std::fmt::Debug;
+
+use crate::domain::{
+    errors::DomainError,
+    job::models::{Job, JobFilter, JobId, JobPage},
+};
+
+/// Inspection and repair of the background job queue
+#[async_trait]
+pub trait JobRepository: Send + Sync + Debug {
+    /// Returns `Ok(None)` when no job has this id
+    async fn get(&self, id: JobId) -> Result<Option<Job>, DomainError>;
+    /// Jobs matching `filter`, oldest first, skipping the first `offset`
+    async fn list(
+        &self,
+        filter: &JobFilter,
+        limit: u32,
+        offset: u64,
+    ) -> Result<JobPage, DomainError>;
+    /// Make a dead job due now, with its attempts starting over
+    ///
+    /// Returns `false`, changing nothing, when the job is not dead.
+    async fn retry(&self, id: JobId) -> Result<bool, DomainError>;
+    /// Delete a job that no worker is running
+    ///
+    /// Returns `false`, changing nothing, when the job is gone or running.
+    async fn delete(&self, id: JobId) -> Result<bool, DomainError>;
+}
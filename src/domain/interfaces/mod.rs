@@ -1,6 +1,10 @@
 // Repository and service trait definitions go here
-// Example:
-// pub mod user_repository;
 
+pub mod attachment_repository;
 pub mod event_producer;
+pub mod feature_flags;
+pub mod job_repository;
+pub mod object_store;
 pub mod task_repository;
+pub mod user_repository;
+pub mod webhook_repository;
@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use std::{fmt::Debug, time::Duration};
+
+use crate::domain::{attachment::models::PresignedUpload, errors::DomainError};
+
+/// Blob storage for attachments, such as an S3 bucket, which clients upload to directly
+#[async_trait]
+pub trait ObjectStore: Send + Sync + Debug {
+    /// Short name used in logs, e.g. `s3`
+    fn name(&self) -> &'static str;
+    /// A URL accepting one PUT of `size` bytes of `content_type` to `object_key` within
+    /// `expires_in`
+    fn presign_put(
+        &self,
+        object_key: &str,
+        content_type: &str,
+        size: u64,
+        expires_in: Duration,
+    ) -> Result<PresignedUpload, DomainError>;
+    /// Delete the object; deleting one that was never uploaded succeeds
+    async fn delete(&self, object_key: &str) -> Result<(), DomainError>;
+}
@@ -1,20 +1,100 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use std::fmt::Debug;
 
 use crate::{
-    common::UserId,
+    common::{TenantId, UserId},
     domain::{
         errors::DomainError,
-        task::models::{Task, TaskId},
+        job::models::{JobId, NewJob},
+        quota::models::TaskUsage,
+        task::models::{
+            DailyTaskCount, PriorityChange, StatsRange, Task, TaskId, TaskPage, TaskSearch,
+        },
     },
 };
 
+/// Storage for tasks, partitioned by tenant
+///
+/// Every read and delete names the tenant it acts for, and writes act for
+/// `entity.tenant_id`: a task of another tenant is reported exactly like a missing one.
 #[async_trait]
 pub trait TaskRepository: Send + Sync + Debug {
+    /// Store `entity` in its tenant, `entity.tenant_id`
     async fn create(&self, entity: Task) -> Result<Task, DomainError>;
-    async fn get(&self, id: TaskId) -> Result<Option<Task>, DomainError>;
-    async fn get_by_user(&self, user_id: UserId) -> Result<Vec<Task>, DomainError>;
+    /// Returns `Ok(None)` when `tenant` has no task with this id
+    async fn get(&self, tenant: TenantId, id: TaskId) -> Result<Option<Task>, DomainError>;
+    async fn get_by_user(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+    ) -> Result<Vec<Task>, DomainError>;
+    /// Tasks of `user_id` created and completed on each day of `range`, oldest day first
+    ///
+    /// Every day of the range has an entry, with zero counts when nothing happened.
+    async fn daily_counts(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        range: &StatsRange,
+    ) -> Result<Vec<DailyTaskCount>, DomainError>;
+    /// Open tasks of `user_id`, and the tasks they created from `since` on, for quotas
+    async fn usage(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<TaskUsage, DomainError>;
+    /// A page of the tasks of every user in `tenant` matching `search`, with the number of
+    /// matches across all pages
+    async fn search_all(
+        &self,
+        tenant: TenantId,
+        search: &TaskSearch,
+    ) -> Result<TaskPage, DomainError>;
+    /// Overwrite the stored task with `entity`
+    ///
+    /// Returns `DomainError::NotFound` when `entity.tenant_id` has no task with `entity.id`.
     async fn update(&self, entity: &Task) -> Result<(), DomainError>;
-    async fn delete(&self, id: TaskId) -> Result<(), DomainError>;
+    /// Returns `DomainError::NotFound` when `tenant` has no task with this id
+    async fn delete(&self, tenant: TenantId, id: TaskId) -> Result<(), DomainError>;
     async fn health_check(&self) -> Result<(), DomainError>;
+    /// Start a unit of work whose writes commit or roll back together
+    async fn begin(&self) -> Result<Box<dyn TaskUnitOfWork>, DomainError>;
+}
+
+/// Task writes grouped into a single transaction
+///
+/// Nothing is visible outside the unit of work until [`commit`](Self::commit) succeeds.
+/// Dropping it without committing rolls every write back.
+#[async_trait]
+pub trait TaskUnitOfWork: Send {
+    async fn create(&mut self, entity: Task) -> Result<Task, DomainError>;
+    /// Returns `Ok(None)` when `tenant` has no task with this id
+    ///
+    /// The SQL backends lock the task until the unit of work ends, so another unit of work
+    /// reading it waits and sees the committed result rather than a stale copy.
+    async fn get(&mut self, tenant: TenantId, id: TaskId) -> Result<Option<Task>, DomainError>;
+    /// Open tasks of `user_id`, and the tasks they created from `since` on, for quotas
+    ///
+    /// Takes the user's quota lock until the unit of work ends, so another unit of work
+    /// counting the same user waits and then sees the tasks this one created.
+    async fn lock_usage(
+        &mut self,
+        tenant: TenantId,
+        user_id: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<TaskUsage, DomainError>;
+    /// Returns `DomainError::NotFound` when `entity.tenant_id` has no task with `entity.id`
+    async fn update(&mut self, entity: &Task) -> Result<(), DomainError>;
+    /// Returns `DomainError::NotFound` when `tenant` has no task with this id
+    async fn delete(&mut self, tenant: TenantId, id: TaskId) -> Result<(), DomainError>;
+    /// Enqueue a background job that workers only see once this unit of work commits
+    ///
+    /// Returns `DomainError::ExternalError` on backends without a job queue.
+    async fn enqueue(&mut self, job: NewJob) -> Result<JobId, DomainError>;
+    /// Add `change` to the audit trail of its task's priority
+    async fn record_priority_change(&mut self, change: &PriorityChange) -> Result<(), DomainError>;
+    async fn commit(self: Box<Self>) -> Result<(), DomainError>;
+    async fn rollback(self: Box<Self>) -> Result<(), DomainError>;
 }
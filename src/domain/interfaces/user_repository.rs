@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+use std::fmt::Debug;
+
+use crate::{
+    common::UserId,
+    domain::{errors::DomainError, user::models::User},
+};
+
+#[async_trait]
+pub trait UserRepository: Send + Sync + Debug {
+    /// Returns `DomainError::Conflict` when the email is already registered
+    async fn create(&self, entity: User) -> Result<User, DomainError>;
+    /// Returns `Ok(None)` when no user has this id
+    async fn get(&self, id: UserId) -> Result<Option<User>, DomainError>;
+}
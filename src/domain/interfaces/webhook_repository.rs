@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use std::fmt::Debug;
+
+use crate::{
+    common::TenantId,
+    domain::{
+        errors::DomainError,
+        task::models::TaskEventType,
+        webhook::models::{Webhook, WebhookDelivery, WebhookId},
+    },
+};
+
+#[async_trait]
+pub trait WebhookRepository: Send + Sync + Debug {
+    async fn create(&self, entity: Webhook) -> Result<Webhook, DomainError>;
+    /// Returns `Ok(None)` when no webhook of `tenant` has this id
+    async fn get(&self, tenant: TenantId, id: WebhookId) -> Result<Option<Webhook>, DomainError>;
+    /// Every webhook of `tenant`, oldest first
+    async fn list(&self, tenant: TenantId) -> Result<Vec<Webhook>, DomainError>;
+    /// Number of webhooks of `tenant`, active or not
+    async fn count(&self, tenant: TenantId) -> Result<u64, DomainError>;
+    /// Active webhooks of `tenant` subscribed to `event_type`
+    async fn subscribed_to(
+        &self,
+        tenant: TenantId,
+        event_type: TaskEventType,
+    ) -> Result<Vec<Webhook>, DomainError>;
+    /// Overwrite the stored webhook with `entity`
+    ///
+    /// Returns `DomainError::NotFound` when no webhook of `entity.tenant_id` has `entity.id`.
+    async fn update(&self, entity: &Webhook) -> Result<(), DomainError>;
+    /// Delete the webhook and its delivery history
+    ///
+    /// Returns `DomainError::NotFound` when no webhook of `tenant` has this id.
+    async fn delete(&self, tenant: TenantId, id: WebhookId) -> Result<(), DomainError>;
+    /// Returns `DomainError::NotFound` when the webhook was deleted in the meantime
+    async fn record_delivery(&self, delivery: &WebhookDelivery) -> Result<(), DomainError>;
+    /// The webhook's most recent delivery attempts, newest first
+    async fn deliveries(
+        &self,
+        id: WebhookId,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, DomainError>;
+    /// Start the webhook's failure count over after an event was delivered
+    async fn record_success(&self, id: WebhookId) -> Result<(), DomainError>;
+    /// Count an event that could not be delivered, deactivating the webhook once
+    /// `disable_after` events in a row have failed
+    ///
+    /// Returns whether the webhook is active afterwards; `DomainError::NotFound` when no
+    /// webhook has this id.
+    async fn record_failure(&self, id: WebhookId, disable_after: u32) -> Result<bool, DomainError>;
+}
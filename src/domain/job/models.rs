@@ -0,0 +1,212 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::errors::DomainError;
+
+/// Longest job kind accepted, in characters
+const MAX_KIND_LENGTH: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct JobId(Uuid);
+
+impl JobId {
+    #[must_use]
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> Uuid {
+        self.0
+    }
+}
+
+impl Default for JobId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Uuid> for JobId {
+    fn from(id: Uuid) -> Self {
+        Self(id)
+    }
+}
+
+impl std::fmt::Display for JobId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Where a job is in its lifecycle; a job that succeeded is deleted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    /// Waiting for `run_at`, either new or scheduled for a retry
+    Pending,
+    /// Claimed by a worker
+    Running,
+    /// Failed on every attempt; kept until someone retries or discards it
+    Dead,
+}
+
+impl JobStatus {
+    pub const ALL: [Self; 3] = [Self::Pending, Self::Running, Self::Dead];
+
+    /// Name of the status as stored, e.g. `pending`
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::Dead => "dead",
+        }
+    }
+}
+
+impl FromStr for JobStatus {
+    type Err = DomainError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|status| status.as_str() == name)
+            .ok_or_else(|| DomainError::validation_error(format!("Unknown job status `{name}`")))
+    }
+}
+
+/// A job to enqueue, run by the handler registered for its `kind`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewJob {
+    pub id: JobId,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    /// Not run before this time
+    pub run_at: DateTime<Utc>,
+}
+
+impl NewJob {
+    /// A job of `kind` carrying `payload`, due at once
+    pub fn new(kind: &str, payload: &impl Serialize) -> Result<Self, DomainError> {
+        let kind = kind.trim();
+        if kind.is_empty() {
+            return Err(DomainError::field_validation_error(
+                "kind",
+                "Job kind cannot be empty",
+            ));
+        }
+        if kind.chars().count() > MAX_KIND_LENGTH {
+            return Err(DomainError::field_validation_error(
+                "kind",
+                format!("Job kind cannot exceed {MAX_KIND_LENGTH} characters"),
+            ));
+        }
+        let payload = serde_json::to_value(payload).map_err(|e| {
+            DomainError::field_validation_error("payload", format!("Invalid job payload: {e}"))
+        })?;
+
+        Ok(Self {
+            id: JobId::new(),
+            kind: kind.to_string(),
+            payload,
+            run_at: Utc::now(),
+        })
+    }
+
+    /// Delay the job until `run_at`
+    #[must_use]
+    pub const fn run_at(mut self, run_at: DateTime<Utc>) -> Self {
+        self.run_at = run_at;
+        self
+    }
+}
+
+/// A stored job
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Job {
+    pub id: JobId,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub status: JobStatus,
+    pub run_at: DateTime<Utc>,
+    /// Attempts started so far, including one in progress
+    pub attempts: u32,
+    /// Why the latest attempt failed, when it did
+    pub last_error: Option<String>,
+    /// Worker holding the job while it is running
+    pub locked_by: Option<String>,
+    pub locked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Job {
+    /// The payload as `T`
+    ///
+    /// # Errors
+    /// Returns `DomainError::ValidationError` when the payload does not match `T`
+    pub fn payload<T: serde::de::DeserializeOwned>(&self) -> Result<T, DomainError> {
+        T::deserialize(&self.payload).map_err(|e| {
+            DomainError::validation_error(format!("Invalid payload for {} job: {e}", self.kind))
+        })
+    }
+}
+
+/// Which jobs to list; every job when nothing is set
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JobFilter {
+    pub status: Option<JobStatus>,
+    /// Only jobs whose latest attempt failed, whether waiting for a retry or dead
+    pub failed: bool,
+    pub kind: Option<String>,
+}
+
+/// One page of a job listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobPage {
+    pub jobs: Vec<Job>,
+    /// Jobs matching the filter, on every page
+    pub total: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_job_trims_kind_and_serializes_payload() {
+        let job = NewJob::new(
+            " purge_tasks ",
+            &serde_json::json!({ "older_than_days": 30 }),
+        )
+        .unwrap();
+
+        assert_eq!(job.kind, "purge_tasks");
+        assert_eq!(job.payload["older_than_days"], 30);
+    }
+
+    #[test]
+    fn test_new_job_rejects_blank_and_overlong_kinds() {
+        for kind in ["", "   ", &"k".repeat(MAX_KIND_LENGTH + 1)] {
+            assert!(
+                matches!(
+                    NewJob::new(kind, &()),
+                    Err(DomainError::ValidationError { .. })
+                ),
+                "{kind:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_status_round_trips_through_its_stored_name() {
+        for status in JobStatus::ALL {
+            assert_eq!(status.as_str().parse::<JobStatus>().unwrap(), status);
+        }
+        assert!("failed".parse::<JobStatus>().is_err());
+    }
+}
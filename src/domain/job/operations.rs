@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use super::models::{Job, JobFilter, JobId, JobPage, JobStatus};
+use crate::domain::{errors::DomainError, interfaces::job_repository::JobRepository};
+
+/// Jobs listed per page unless the caller asks for another number
+pub const DEFAULT_JOB_PAGE_SIZE: u32 = 50;
+/// Most jobs listed per page
+pub const MAX_JOB_PAGE_SIZE: u32 = 100;
+
+/// Retrieve a job by ID
+///
+/// Returns an error if the job is not found, which includes jobs that succeeded.
+pub async fn get_job(id: JobId, repo: Arc<dyn JobRepository>) -> Result<Job, DomainError> {
+    let result: Option<Job> = repo.get(id).await?;
+    result.ok_or_else(|| DomainError::not_found("Job", id.to_string()))
+}
+
+/// A page of at most `limit` jobs matching `filter`, oldest first
+pub async fn list_jobs(
+    filter: &JobFilter,
+    limit: u32,
+    offset: u64,
+    repo: Arc<dyn JobRepository>,
+) -> Result<JobPage, DomainError> {
+    if !(1..=MAX_JOB_PAGE_SIZE).contains(&limit) {
+        return Err(DomainError::field_validation_error(
+            "limit",
+            format!("Limit must be between 1 and {MAX_JOB_PAGE_SIZE}"),
+        ));
+    }
+    repo.list(filter, limit, offset).await
+}
+
+/// Run a dead job again, with all its attempts
+///
+/// Returns `DomainError::Conflict` for a job that is not dead, since it is still retried
+/// or running on its own.
+pub async fn retry_job(id: JobId, repo: Arc<dyn JobRepository>) -> Result<Job, DomainError> {
+    let job = get_job(id, repo.clone()).await?;
+    if job.status != JobStatus::Dead {
+        return Err(DomainError::conflict(format!(
+            "Only dead jobs can be retried; job {id} is {}",
+            job.status.as_str()
+        )));
+    }
+    if !repo.retry(id).await? {
+        return Err(DomainError::conflict(format!(
+            "Job {id} was retried or discarded in the meantime"
+        )));
+    }
+    get_job(id, repo).await
+}
+
+/// Delete a job so it never runs again, returning it as it was
+///
+/// Returns `DomainError::Conflict` for a running job, which its worker would finish anyway.
+pub async fn discard_job(id: JobId, repo: Arc<dyn JobRepository>) -> Result<Job, DomainError> {
+    let job = get_job(id, repo.clone()).await?;
+    if job.status == JobStatus::Running || !repo.delete(id).await? {
+        return Err(DomainError::conflict(format!(
+            "Job {id} is running and cannot be discarded"
+        )));
+    }
+    Ok(job)
+}
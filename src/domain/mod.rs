@@ -1,3 +1,9 @@
+pub mod attachment;
 pub mod errors;
 pub mod interfaces;
+pub mod job;
+pub mod quota;
 pub mod task;
+pub mod user;
+pub mod warnings;
+pub mod webhook;
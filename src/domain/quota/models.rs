@@ -0,0 +1,193 @@
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::domain::{
+    errors::DomainError,
+    task::models::{Task, TaskStatus},
+};
+
+/// What a [`Quota`] limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaMetric {
+    /// Pending and in-progress tasks of a user
+    OpenTasks,
+    /// Tasks a user created since the start of the calendar month, in UTC
+    MonthlyTasks,
+    /// Webhook subscriptions of a tenant
+    Webhooks,
+}
+
+impl QuotaMetric {
+    /// Name used in `X-Quota-Warning` headers and error messages
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::OpenTasks => "open_tasks",
+            Self::MonthlyTasks => "tasks_this_month",
+            Self::Webhooks => "webhooks",
+        }
+    }
+}
+
+impl fmt::Display for QuotaMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Soft and hard limits on one metric, either of which may be unset
+///
+/// Going past the soft limit is allowed with a warning; the hard limit is not. Task quotas
+/// are counted under the user's quota lock, so concurrent creates cannot exceed it together;
+/// the webhook quota is counted without one, and concurrent creates may.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Quota {
+    #[serde(default)]
+    pub soft: Option<u64>,
+    #[serde(default)]
+    pub hard: Option<u64>,
+}
+
+impl Quota {
+    /// Neither limit is set
+    #[must_use]
+    pub const fn is_unlimited(self) -> bool {
+        self.soft.is_none() && self.hard.is_none()
+    }
+
+    /// Check that `metric` may grow from `used` by one
+    ///
+    /// Returns `DomainError::QuotaExceeded` when that would go past the hard limit, and a
+    /// warning when it goes past the soft one.
+    pub fn check_one_more(
+        self,
+        metric: QuotaMetric,
+        used: u64,
+    ) -> Result<Option<QuotaWarning>, DomainError> {
+        let after = used.saturating_add(1);
+        if let Some(hard) = self.hard.filter(|hard| after > *hard) {
+            return Err(DomainError::quota_exceeded(metric, hard));
+        }
+        Ok(self
+            .soft
+            .filter(|soft| after > *soft)
+            .map(|soft| QuotaWarning {
+                metric,
+                used: after,
+                soft,
+            }))
+    }
+}
+
+/// A soft quota gone past by a write that was allowed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotaWarning {
+    pub metric: QuotaMetric,
+    /// Usage including the write
+    pub used: u64,
+    pub soft: u64,
+}
+
+impl fmt::Display for QuotaWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}; used={}; soft={}", self.metric, self.used, self.soft)
+    }
+}
+
+/// Counts of a user's tasks that quotas apply to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskUsage {
+    /// Pending and in-progress tasks
+    pub open: u64,
+    /// Tasks created since the start of the counted period
+    pub created: u64,
+}
+
+impl TaskUsage {
+    /// Count `tasks`, those created from `since` on, for backends that cannot count in the
+    /// database
+    pub fn tally<'a>(since: DateTime<Utc>, tasks: impl IntoIterator<Item = &'a Task>) -> Self {
+        tasks.into_iter().fold(Self::default(), |mut usage, task| {
+            if matches!(task.status, TaskStatus::Pending | TaskStatus::InProgress) {
+                usage.open += 1;
+            }
+            if task.created_at >= since {
+                usage.created += 1;
+            }
+            usage
+        })
+    }
+
+    /// The usage once one more task is created
+    #[must_use]
+    pub const fn with_one_more(self) -> Self {
+        Self {
+            open: self.open + 1,
+            created: self.created + 1,
+        }
+    }
+}
+
+/// A user's usage of every quota, as reported by `GET /usage`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+    pub tasks: TaskUsage,
+    pub webhooks: u64,
+}
+
+/// Start of the calendar month of `now` in UTC, from which monthly quotas count
+#[must_use]
+pub fn month_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .single()
+        .expect("Midnight UTC on the first of a month exists")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quota_warns_past_soft_and_refuses_past_hard() {
+        let quota = Quota {
+            soft: Some(2),
+            hard: Some(3),
+        };
+
+        assert_eq!(
+            quota.check_one_more(QuotaMetric::OpenTasks, 1).unwrap(),
+            None
+        );
+        assert_eq!(
+            quota.check_one_more(QuotaMetric::OpenTasks, 2).unwrap(),
+            Some(QuotaWarning {
+                metric: QuotaMetric::OpenTasks,
+                used: 3,
+                soft: 2,
+            })
+        );
+        // Negative test: The hard limit is reached, one more is refused
+        assert!(matches!(
+            quota.check_one_more(QuotaMetric::OpenTasks, 3),
+            Err(DomainError::QuotaExceeded { limit: 3, .. })
+        ));
+        // An unset quota never limits
+        assert_eq!(
+            Quota::default()
+                .check_one_more(QuotaMetric::Webhooks, u64::MAX)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_month_start_is_midnight_utc_on_the_first() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 31, 23, 59, 59).unwrap();
+
+        assert_eq!(
+            month_start(now),
+            Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap()
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use super::models::{Quota, QuotaMetric, QuotaWarning, TaskUsage};
+use crate::{
+    common::TenantId,
+    domain::{
+        errors::DomainError,
+        interfaces::{feature_flags::FeatureFlags, webhook_repository::WebhookRepository},
+    },
+};
+
+/// Check that a user with `usage` may create one more task
+///
+/// Returns `DomainError::QuotaExceeded` when the task would go past a hard quota, and the
+/// soft quotas it goes past otherwise.
+pub fn check_task_quotas(
+    usage: TaskUsage,
+    open_tasks: Quota,
+    monthly_tasks: Quota,
+) -> Result<Vec<QuotaWarning>, DomainError> {
+    let mut warnings = Vec::new();
+    warnings.extend(open_tasks.check_one_more(QuotaMetric::OpenTasks, usage.open)?);
+    warnings.extend(monthly_tasks.check_one_more(QuotaMetric::MonthlyTasks, usage.created)?);
+    Ok(warnings)
+}
+
+/// Number of webhooks of `tenant`, which the webhook quota applies to
+pub async fn count_webhooks(
+    tenant: TenantId,
+    repo: Arc<dyn WebhookRepository>,
+) -> Result<u64, DomainError> {
+    repo.count(tenant).await
+}
+
+/// Check that `tenant` may have one more webhook under `quota`, counting its webhooks only
+/// when the quota is set and the `enforce_quotas` feature is on
+///
+/// Returns `DomainError::QuotaExceeded` past the hard quota, and a warning past the soft one.
+pub async fn check_webhook_quota(
+    tenant: TenantId,
+    quota: Quota,
+    features: &dyn FeatureFlags,
+    repo: Arc<dyn WebhookRepository>,
+) -> Result<Option<QuotaWarning>, DomainError> {
+    if quota.is_unlimited() || !features.enforces_quotas() {
+        return Ok(None);
+    }
+    quota.check_one_more(QuotaMetric::Webhooks, count_webhooks(tenant, repo).await?)
+}
@@ -1,10 +1,15 @@
+use std::str::FromStr;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{
-    common::UserId,
-    domain::task::models::{TaskId, TaskPriority, TaskStatus},
+    common::{TenantId, UserId},
+    domain::{
+        errors::DomainError,
+        task::models::{Task, TaskId, TaskPriority, TaskStatus},
+    },
 };
 
 /// Event types for task lifecycle events
@@ -15,6 +20,34 @@ pub enum TaskEventType {
     Deleted,
 }
 
+impl TaskEventType {
+    /// Every event type, in lifecycle order
+    pub const ALL: [Self; 3] = [Self::Created, Self::Updated, Self::Deleted];
+
+    /// Name of the event type as serialized, e.g. `Created`
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Created => "Created",
+            Self::Updated => "Updated",
+            Self::Deleted => "Deleted",
+        }
+    }
+}
+
+impl FromStr for TaskEventType {
+    type Err = DomainError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|event_type| event_type.as_str() == name)
+            .ok_or_else(|| {
+                DomainError::validation_error(format!("Unknown task event type `{name}`"))
+            })
+    }
+}
+
 /// Data payload for task events containing task fields
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskEventData {
@@ -23,18 +56,44 @@ pub struct TaskEventData {
     pub description: Option<String>,
     pub status: TaskStatus,
     pub priority: TaskPriority,
+    /// Absent from events published before tasks had tenants
+    #[serde(default)]
+    pub tenant_id: TenantId,
     pub user_id: UserId,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
 }
 
+impl From<&Task> for TaskEventData {
+    fn from(task: &Task) -> Self {
+        Self {
+            id: task.id,
+            title: task.title.value().to_string(),
+            description: task.description.clone(),
+            status: task.status,
+            priority: task.priority,
+            tenant_id: task.tenant_id,
+            user_id: task.user_id,
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            completed_at: task.completed_at,
+        }
+    }
+}
+
 /// Metadata for event tracking and correlation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventMetadata {
     pub source_service: String,
     pub correlation_id: String,
+    /// Tenant of the task; only this tenant's webhooks receive the event
+    #[serde(default)]
+    pub tenant_id: TenantId,
     pub user_id: UserId,
+    /// Why the change was made, when the user gave a reason
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
 }
 
 /// Complete task event structure for publishing to Kafka
@@ -54,7 +113,7 @@ impl TaskEvent {
 
     /// Create a new task created event
     pub fn new_created(data: TaskEventData, correlation_id: String) -> Self {
-        let user_id = data.user_id;
+        let (tenant_id, user_id) = (data.tenant_id, data.user_id);
         Self {
             event_type: TaskEventType::Created,
             event_id: Uuid::new_v4(),
@@ -65,7 +124,9 @@ impl TaskEvent {
             metadata: EventMetadata {
                 source_service: "rust-service-template".to_string(),
                 correlation_id,
+                tenant_id,
                 user_id,
+                reason: None,
             },
         }
     }
@@ -76,7 +137,7 @@ impl TaskEvent {
         old_data: TaskEventData,
         correlation_id: String,
     ) -> Self {
-        let user_id = data.user_id;
+        let (tenant_id, user_id) = (data.tenant_id, data.user_id);
         Self {
             event_type: TaskEventType::Updated,
             event_id: Uuid::new_v4(),
@@ -87,14 +148,16 @@ impl TaskEvent {
             metadata: EventMetadata {
                 source_service: "rust-service-template".to_string(),
                 correlation_id,
+                tenant_id,
                 user_id,
+                reason: None,
             },
         }
     }
 
     /// Create a new task deleted event
     pub fn new_deleted(data: TaskEventData, correlation_id: String) -> Self {
-        let user_id = data.user_id;
+        let (tenant_id, user_id) = (data.tenant_id, data.user_id);
         Self {
             event_type: TaskEventType::Deleted,
             event_id: Uuid::new_v4(),
@@ -105,8 +168,28 @@ impl TaskEvent {
             metadata: EventMetadata {
                 source_service: "rust-service-template".to_string(),
                 correlation_id,
+                tenant_id,
                 user_id,
+                reason: None,
             },
         }
     }
+
+    /// Attach the reason the user gave for the change
+    #[must_use]
+    pub fn with_reason(mut self, reason: Option<String>) -> Self {
+        self.metadata.reason = reason;
+        self
+    }
+}
+
+/// A committed change to a stored task, as notified by the database
+///
+/// Carries ids only; subscribers fetch the task if they need its contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskChange {
+    pub event_type: TaskEventType,
+    pub task_id: TaskId,
+    pub tenant_id: TenantId,
+    pub user_id: UserId,
 }
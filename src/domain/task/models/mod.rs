@@ -2,12 +2,23 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{common::UserId, domain::errors::DomainError};
+use crate::{
+    common::{TenantId, UserId},
+    domain::errors::{DomainError, FieldError},
+};
 
 pub mod events;
+pub mod priority;
+pub mod search;
+pub mod stats;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod strategies;
 
 // Re-export event types for convenience
-pub use events::{EventMetadata, TaskEvent, TaskEventData, TaskEventType};
+pub use events::{EventMetadata, TaskChange, TaskEvent, TaskEventData, TaskEventType};
+pub use priority::{ChangedPriority, PriorityChange};
+pub use search::{SortDirection, TaskFilter, TaskPage, TaskSearch, TaskSort, TaskSortField};
+pub use stats::{DailyTaskCount, StatsRange, TaskStats};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TaskId(Uuid);
@@ -47,6 +58,14 @@ impl std::fmt::Display for TaskId {
     }
 }
 
+/// A task named by its id, or the newest task of a user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskRef {
+    Id(TaskId),
+    /// The task the user created last
+    Latest(UserId),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum TaskStatus {
     #[default]
@@ -56,7 +75,8 @@ pub enum TaskStatus {
     Cancelled,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+/// Ordered from `Low` to `Critical`, as in the database enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default)]
 pub enum TaskPriority {
     Low,
     #[default]
@@ -68,25 +88,66 @@ pub enum TaskPriority {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Title(String);
 
+/// Invisible characters removed from titles
+///
+/// Zero-width (non-)joiners are kept: emoji sequences and several scripts depend on them.
+const STRIPPED_TITLE_CHARACTERS: [char; 3] = ['\u{200B}', '\u{2060}', '\u{FEFF}'];
+
 impl Title {
     const MIN_LENGTH: usize = 1;
+    /// Maximum length in characters (Unicode scalar values), not bytes
     const MAX_LENGTH: usize = 200;
 
+    /// Validate and normalize a user-supplied title
+    ///
+    /// Zero-width spaces are removed and runs of whitespace (including tabs and newlines)
+    /// collapse to a single space before the length is checked. Other control characters
+    /// are rejected.
     pub fn new(value: String) -> Result<Self, DomainError> {
-        let trimmed = value.trim().to_string();
-        if trimmed.len() < Self::MIN_LENGTH {
-            return Err(DomainError::field_validation_error(
+        Self::parse(value).map_err(DomainError::from)
+    }
+
+    fn parse(value: String) -> Result<Self, FieldError> {
+        let normalized = Self::normalize(&value);
+        if normalized.chars().count() < Self::MIN_LENGTH {
+            return Err(FieldError::new(
                 "title",
+                "required",
                 "Title cannot be empty",
             ));
         }
-        if trimmed.len() > Self::MAX_LENGTH {
-            return Err(DomainError::field_validation_error(
+        if normalized.chars().any(char::is_control) {
+            return Err(FieldError::new(
                 "title",
+                "invalid_characters",
+                "Title cannot contain control characters",
+            ));
+        }
+        if normalized.chars().count() > Self::MAX_LENGTH {
+            return Err(FieldError::new(
+                "title",
+                "too_long",
                 format!("Title cannot exceed {} characters", Self::MAX_LENGTH),
             ));
         }
-        Ok(Self(trimmed))
+        Ok(Self(normalized))
+    }
+
+    /// Wrap a title read back from storage without validating it
+    ///
+    /// Rows written under older validation rules must stay readable, so stored titles are
+    /// returned exactly as persisted.
+    #[must_use]
+    pub fn from_storage(value: String) -> Self {
+        Self(value)
+    }
+
+    fn normalize(value: &str) -> String {
+        let visible: String = value
+            .chars()
+            .filter(|c| !STRIPPED_TITLE_CHARACTERS.contains(c))
+            .collect();
+        visible.split_whitespace().collect::<Vec<_>>().join(" ")
     }
 
     #[must_use]
@@ -103,6 +164,8 @@ impl Title {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
     pub id: TaskId,
+    /// Set once at creation; repositories only read and write tasks within one tenant
+    pub tenant_id: TenantId,
     pub user_id: UserId,
     pub title: Title,
     pub description: Option<String>,
@@ -114,20 +177,32 @@ pub struct Task {
 }
 
 impl Task {
+    /// Maximum description length in characters, after trimming
+    pub const MAX_DESCRIPTION_LENGTH: usize = 2000;
+
+    /// Validate a new task, reporting every invalid field at once as
+    /// `DomainError::InvalidFields`
     pub fn new(
+        tenant_id: TenantId,
         user_id: UserId,
         title: String,
         description: Option<String>,
         priority: TaskPriority,
     ) -> Result<Self, DomainError> {
+        let (title, description) = match (Title::parse(title), Self::description(description)) {
+            (Ok(title), Ok(description)) => (title, description),
+            (title, description) => {
+                let errors = title.err().into_iter().chain(description.err()).collect();
+                return Err(DomainError::InvalidFields { errors });
+            }
+        };
         let now = Utc::now();
         Ok(Self {
             id: TaskId::new(),
+            tenant_id,
             user_id,
-            title: Title::new(title)?,
-            description: description
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty()),
+            title,
+            description,
             status: TaskStatus::Pending,
             priority,
             created_at: now,
@@ -135,4 +210,192 @@ impl Task {
             completed_at: None,
         })
     }
+
+    /// Trim a description, dropping it when blank
+    fn description(description: Option<String>) -> Result<Option<String>, FieldError> {
+        let description = description
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        if description
+            .as_ref()
+            .is_some_and(|s| s.chars().count() > Self::MAX_DESCRIPTION_LENGTH)
+        {
+            return Err(FieldError::new(
+                "description",
+                "too_long",
+                format!(
+                    "Description cannot exceed {} characters",
+                    Self::MAX_DESCRIPTION_LENGTH
+                ),
+            ));
+        }
+        Ok(description)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prelude::*, sample::select};
+
+    use super::{strategies::any_title, *};
+
+    fn validation_message(result: Result<Title, DomainError>) -> String {
+        match result {
+            Err(DomainError::ValidationError { message, .. }) => message,
+            other => panic!("Expected a validation error, got {other:?}"),
+        }
+    }
+
+    /// Characters that stay in a title as they are
+    fn visible() -> impl Strategy<Value = char> {
+        any::<char>().prop_filter("visible", |c| {
+            !c.is_whitespace() && !c.is_control() && !STRIPPED_TITLE_CHARACTERS.contains(c)
+        })
+    }
+
+    /// Runs of whitespace, possibly mixed with stripped invisible characters
+    fn spacing(min: usize) -> impl Strategy<Value = String> {
+        let whitespace = vec(
+            select(vec![' ', '\t', '\n', '\r', '\u{A0}', '\u{3000}']),
+            min..4,
+        );
+        let invisible = vec(select(STRIPPED_TITLE_CHARACTERS.to_vec()), 0..2);
+        (whitespace, invisible)
+            .prop_map(|(whitespace, invisible)| whitespace.into_iter().chain(invisible).collect())
+    }
+
+    /// A raw title and the normalized form it should be stored as
+    fn title_input() -> impl Strategy<Value = (String, String)> {
+        let words = vec(vec(visible(), 1..8).prop_map(String::from_iter), 1..60);
+        (spacing(0), words, spacing(0))
+            .prop_flat_map(|(leading, words, trailing)| {
+                let separators = vec(spacing(1), words.len() - 1);
+                (Just((leading, words, trailing)), separators)
+            })
+            .prop_map(|((leading, words, trailing), separators)| {
+                let mut raw = leading;
+                for (index, word) in words.iter().enumerate() {
+                    if index > 0 {
+                        raw.push_str(&separators[index - 1]);
+                    }
+                    raw.push_str(word);
+                }
+                raw.push_str(&trailing);
+                (raw, words.join(" "))
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn accepted_titles_are_trimmed_and_within_the_limit(raw in any_title()) {
+            match Title::new(raw) {
+                Ok(title) => {
+                    let length = title.value().chars().count();
+                    prop_assert!((1..=200).contains(&length), "{} characters", length);
+                    prop_assert_eq!(title.value(), title.value().trim());
+                    prop_assert!(!title.value().chars().any(char::is_control));
+                }
+                Err(DomainError::ValidationError { field, .. }) => {
+                    prop_assert_eq!(field.as_deref(), Some("title"));
+                }
+                Err(other) => prop_assert!(false, "Expected a validation error, got {:?}", other),
+            }
+        }
+
+        #[test]
+        fn title_is_stored_normalized_when_within_the_limit((raw, expected) in title_input()) {
+            let result = Title::new(raw);
+
+            if expected.chars().count() <= 200 {
+                prop_assert_eq!(result.unwrap().into_inner(), expected);
+            } else {
+                prop_assert_eq!(
+                    validation_message(result),
+                    "Title cannot exceed 200 characters"
+                );
+            }
+        }
+
+        #[test]
+        fn title_length_is_counted_in_characters(
+            title in vec(visible(), 195..=205).prop_map(String::from_iter)
+        ) {
+            let length = title.chars().count();
+
+            prop_assert_eq!(Title::new(title).is_ok(), length <= 200, "{} characters", length);
+        }
+
+        #[test]
+        fn normalizing_a_title_twice_changes_nothing((raw, _) in title_input()) {
+            if let Ok(title) = Title::new(raw) {
+                let again = Title::new(title.value().to_string()).unwrap();
+                prop_assert_eq!(again, title);
+            }
+        }
+
+        #[test]
+        fn titles_with_control_characters_are_rejected(
+            (raw, _) in title_input(),
+            control in any::<char>().prop_filter("control", |c| {
+                c.is_control() && !c.is_whitespace()
+            }),
+            position in any::<prop::sample::Index>(),
+        ) {
+            let mut chars: Vec<char> = raw.chars().collect();
+            chars.insert(position.index(chars.len() + 1), control);
+
+            prop_assert_eq!(
+                validation_message(Title::new(chars.into_iter().collect())),
+                "Title cannot contain control characters"
+            );
+        }
+
+        #[test]
+        fn titles_of_only_whitespace_and_invisible_characters_are_empty(raw in spacing(0)) {
+            prop_assert_eq!(validation_message(Title::new(raw)), "Title cannot be empty");
+        }
+    }
+
+    #[test]
+    fn test_title_length_counts_characters_not_bytes() {
+        // Regression test for multi-byte titles being rejected by byte length
+        let japanese = "日本語".repeat(40);
+        assert_eq!(japanese.len(), 360);
+
+        let title = Title::new(japanese.clone()).expect("120-character title should be valid");
+        assert_eq!(title.value(), japanese);
+        assert_eq!(
+            validation_message(Title::new("😀".repeat(201))),
+            "Title cannot exceed 200 characters"
+        );
+    }
+
+    #[test]
+    fn test_title_strips_zero_width_spaces_and_collapses_whitespace() {
+        let title = Title::new("\u{FEFF}Plan\u{200B}  the \t\n launch\u{2060} ".to_string())
+            .expect("Title should be valid");
+        assert_eq!(title.value(), "Plan the launch");
+    }
+
+    #[test]
+    fn test_new_task_reports_every_invalid_field() {
+        let result = Task::new(
+            TenantId::DEFAULT,
+            UserId::new(),
+            "  ".to_string(),
+            Some("x".repeat(Task::MAX_DESCRIPTION_LENGTH + 1)),
+            TaskPriority::Low,
+        );
+
+        match result {
+            Err(DomainError::InvalidFields { errors }) => assert_eq!(
+                errors
+                    .iter()
+                    .map(|error| (error.field.as_str(), error.code))
+                    .collect::<Vec<_>>(),
+                [("title", "required"), ("description", "too_long")]
+            ),
+            other => panic!("Expected invalid fields, got {other:?}"),
+        }
+    }
 }
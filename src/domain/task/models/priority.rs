@@ -0,0 +1,198 @@
+use chrono::{DateTime, Utc};
+
+use super::{Task, TaskId, TaskPriority};
+use crate::{
+    common::{TenantId, UserId},
+    domain::errors::{DomainError, FieldError},
+};
+
+/// Rule broken by a change that leaves the priority as it was
+pub const PRIORITY_MUST_CHANGE_RULE: &str = "priority_must_change";
+
+/// A change of a task's priority, kept in the audit trail with the reason given for it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriorityChange {
+    pub task_id: TaskId,
+    pub tenant_id: TenantId,
+    pub from: TaskPriority,
+    pub to: TaskPriority,
+    /// Why the priority changed; always present when escalating to `Critical`
+    pub reason: Option<String>,
+    pub changed_by: UserId,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl PriorityChange {
+    /// Maximum reason length in characters, after trimming
+    pub const MAX_REASON_LENGTH: usize = 500;
+
+    /// Validate a change of `task` to `to` requested by `changed_by`
+    ///
+    /// A change to the current priority is a business rule violation, so every entry in
+    /// the audit trail records an actual change. Escalating to `Critical` needs a reason;
+    /// a blank reason counts as none.
+    pub fn new(
+        task: &Task,
+        to: TaskPriority,
+        reason: Option<String>,
+        changed_by: UserId,
+    ) -> Result<Self, DomainError> {
+        if task.priority == to {
+            return Err(DomainError::business_rule_violation(
+                PRIORITY_MUST_CHANGE_RULE,
+                format!("Task priority is already {to:?}"),
+            ));
+        }
+        let reason = Self::reason(reason, to > task.priority && to == TaskPriority::Critical)
+            .map_err(|error| DomainError::InvalidFields {
+                errors: vec![error],
+            })?;
+
+        Ok(Self {
+            task_id: task.id,
+            tenant_id: task.tenant_id,
+            from: task.priority,
+            to,
+            reason,
+            changed_by,
+            changed_at: Utc::now(),
+        })
+    }
+
+    /// Whether the change raises the priority
+    #[must_use]
+    pub fn is_escalation(&self) -> bool {
+        self.to > self.from
+    }
+
+    /// Trim a reason, dropping it when blank
+    fn reason(reason: Option<String>, required: bool) -> Result<Option<String>, FieldError> {
+        let reason = reason
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        match &reason {
+            None if required => Err(FieldError::new(
+                "reason",
+                "required",
+                "A reason is required when escalating to Critical",
+            )),
+            Some(reason) if reason.chars().count() > Self::MAX_REASON_LENGTH => {
+                Err(FieldError::new(
+                    "reason",
+                    "too_long",
+                    format!(
+                        "Reason cannot exceed {} characters",
+                        Self::MAX_REASON_LENGTH
+                    ),
+                ))
+            }
+            _ => Ok(reason),
+        }
+    }
+}
+
+/// A task before and after a [`PriorityChange`]
+#[derive(Debug, Clone)]
+pub struct ChangedPriority {
+    pub before: Task,
+    pub after: Task,
+    pub change: PriorityChange,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(priority: TaskPriority) -> Task {
+        Task::new(
+            TenantId::default(),
+            UserId::new(),
+            "Ship it".to_string(),
+            None,
+            priority,
+        )
+        .unwrap()
+    }
+
+    fn reason_error(result: Result<PriorityChange, DomainError>) -> &'static str {
+        match result {
+            Err(DomainError::InvalidFields { errors }) if errors[0].field == "reason" => {
+                errors[0].code
+            }
+            other => panic!("Expected a reason validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_escalating_to_critical_requires_a_reason() {
+        let task = task(TaskPriority::High);
+
+        let blank = PriorityChange::new(
+            &task,
+            TaskPriority::Critical,
+            Some("  ".to_string()),
+            UserId::new(),
+        );
+        let change = PriorityChange::new(
+            &task,
+            TaskPriority::Critical,
+            Some(" Customer outage ".to_string()),
+            UserId::new(),
+        )
+        .unwrap();
+
+        assert_eq!(reason_error(blank), "required");
+        assert_eq!(change.reason.as_deref(), Some("Customer outage"));
+        assert!(change.is_escalation());
+    }
+
+    #[test]
+    fn test_other_changes_do_not_require_a_reason() {
+        let escalation = PriorityChange::new(
+            &task(TaskPriority::Low),
+            TaskPriority::High,
+            None,
+            UserId::new(),
+        );
+        let de_escalation = PriorityChange::new(
+            &task(TaskPriority::Critical),
+            TaskPriority::Low,
+            None,
+            UserId::new(),
+        )
+        .unwrap();
+
+        assert!(escalation.is_ok());
+        assert!(!de_escalation.is_escalation());
+        assert_eq!(de_escalation.from, TaskPriority::Critical);
+    }
+
+    #[test]
+    fn test_reason_length_is_limited() {
+        let reason = "a".repeat(PriorityChange::MAX_REASON_LENGTH + 1);
+
+        let result = PriorityChange::new(
+            &task(TaskPriority::Low),
+            TaskPriority::Medium,
+            Some(reason),
+            UserId::new(),
+        );
+
+        assert_eq!(reason_error(result), "too_long");
+    }
+
+    #[test]
+    fn test_unchanged_priority_is_a_business_rule_violation() {
+        let result = PriorityChange::new(
+            &task(TaskPriority::High),
+            TaskPriority::High,
+            Some("Still urgent".to_string()),
+            UserId::new(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(DomainError::BusinessRuleViolation { rule, .. }) if rule == PRIORITY_MUST_CHANGE_RULE
+        ));
+    }
+}
@@ -0,0 +1,275 @@
+use std::cmp::Ordering;
+
+use chrono::{DateTime, Utc};
+
+use super::{Task, TaskPriority, TaskStatus};
+use crate::{
+    common::UserId,
+    domain::errors::{DomainError, FieldError},
+};
+
+/// Tasks returned per page unless the caller asks for another number
+pub const DEFAULT_TASK_PAGE_SIZE: u32 = 50;
+/// Most tasks returned per page
+pub const MAX_TASK_PAGE_SIZE: u32 = 100;
+
+/// Which tasks of a tenant a search returns; every task when nothing is set
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskFilter {
+    pub user_id: Option<UserId>,
+    pub status: Option<TaskStatus>,
+    pub priority: Option<TaskPriority>,
+    /// Created at or after this time
+    pub created_from: Option<DateTime<Utc>>,
+    /// Created before this time
+    pub created_before: Option<DateTime<Utc>>,
+    /// Part of the title, matched ignoring case
+    pub title: Option<String>,
+    /// Part of the id in its hyphenated lowercase form, e.g. `5b3c8f4e`
+    pub id_fragment: Option<String>,
+}
+
+impl TaskFilter {
+    /// Whether `task` passes the filter, for backends that cannot filter in the database
+    #[must_use]
+    pub fn matches(&self, task: &Task) -> bool {
+        self.user_id.is_none_or(|user_id| task.user_id == user_id)
+            && self.status.is_none_or(|status| task.status == status)
+            && self
+                .priority
+                .is_none_or(|priority| task.priority == priority)
+            && self.created_from.is_none_or(|from| task.created_at >= from)
+            && self
+                .created_before
+                .is_none_or(|before| task.created_at < before)
+            && self.title.as_deref().is_none_or(|title| {
+                task.title
+                    .value()
+                    .to_lowercase()
+                    .contains(&title.to_lowercase())
+            })
+            && self
+                .id_fragment
+                .as_deref()
+                .is_none_or(|fragment| task.id.to_string().contains(fragment))
+    }
+}
+
+/// Field search results are ordered by; ties are broken by id
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TaskSortField {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    /// From `Low` to `Critical`
+    Priority,
+    /// By code point, so `Z` comes before `a`
+    Title,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Order of search results, newest first by default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskSort {
+    pub field: TaskSortField,
+    pub direction: SortDirection,
+}
+
+impl TaskSort {
+    /// Order two tasks the way the database does, ids ascending on ties
+    #[must_use]
+    pub fn compare(&self, a: &Task, b: &Task) -> Ordering {
+        let by_field = match self.field {
+            TaskSortField::CreatedAt => a.created_at.cmp(&b.created_at),
+            TaskSortField::UpdatedAt => a.updated_at.cmp(&b.updated_at),
+            TaskSortField::Priority => a.priority.cmp(&b.priority),
+            TaskSortField::Title => a.title.value().cmp(b.title.value()),
+        };
+        let by_field = match self.direction {
+            SortDirection::Asc => by_field,
+            SortDirection::Desc => by_field.reverse(),
+        };
+        by_field.then_with(|| a.id.into_inner().cmp(&b.id.into_inner()))
+    }
+}
+
+/// A validated search: which tasks, in which order, and which page of them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskSearch {
+    filter: TaskFilter,
+    sort: TaskSort,
+    limit: u32,
+    offset: u64,
+}
+
+impl TaskSearch {
+    /// Validate a user-supplied search, reporting every invalid field at once
+    ///
+    /// Blank titles and id fragments are dropped, and id fragments are lowercased.
+    pub fn new(
+        mut filter: TaskFilter,
+        sort: TaskSort,
+        limit: u32,
+        offset: u64,
+    ) -> Result<Self, DomainError> {
+        let mut errors = Vec::new();
+        if !(1..=MAX_TASK_PAGE_SIZE).contains(&limit) {
+            errors.push(FieldError::new(
+                "limit",
+                "out_of_range",
+                format!("Limit must be between 1 and {MAX_TASK_PAGE_SIZE}"),
+            ));
+        }
+        if let (Some(from), Some(before)) = (filter.created_from, filter.created_before) {
+            if from >= before {
+                errors.push(FieldError::new(
+                    "created_from",
+                    "invalid_range",
+                    "`created_from` must be before `created_before`",
+                ));
+            }
+        }
+        filter.title = filter
+            .title
+            .map(|title| title.trim().to_string())
+            .filter(|title| !title.is_empty());
+        filter.id_fragment = filter
+            .id_fragment
+            .map(|fragment| fragment.trim().to_ascii_lowercase())
+            .filter(|fragment| !fragment.is_empty());
+        if filter
+            .id_fragment
+            .as_deref()
+            .is_some_and(|fragment| !fragment.chars().all(|c| c.is_ascii_hexdigit() || c == '-'))
+        {
+            errors.push(FieldError::new(
+                "id",
+                "invalid_characters",
+                "An id fragment may only have hex digits and hyphens",
+            ));
+        }
+        DomainError::check_fields(errors)?;
+
+        Ok(Self {
+            filter,
+            sort,
+            limit,
+            offset,
+        })
+    }
+
+    #[must_use]
+    pub const fn filter(&self) -> &TaskFilter {
+        &self.filter
+    }
+
+    #[must_use]
+    pub const fn sort(&self) -> TaskSort {
+        self.sort
+    }
+
+    #[must_use]
+    pub const fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    #[must_use]
+    pub const fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Filter, sort and page `tasks`, for backends that cannot search in the database
+    pub fn page<'a>(&self, tasks: impl IntoIterator<Item = &'a Task>) -> TaskPage {
+        let mut matching: Vec<&Task> = tasks
+            .into_iter()
+            .filter(|task| self.filter.matches(task))
+            .collect();
+        matching.sort_by(|a, b| self.sort.compare(a, b));
+
+        TaskPage {
+            total: matching.len() as u64,
+            tasks: matching
+                .into_iter()
+                .skip(usize::try_from(self.offset).unwrap_or(usize::MAX))
+                .take(self.limit as usize)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// One page of a task search
+#[derive(Debug, Clone)]
+pub struct TaskPage {
+    pub tasks: Vec<Task>,
+    /// Tasks matching the filter, on every page
+    pub total: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::TenantId;
+
+    fn task(title: &str, priority: TaskPriority) -> Task {
+        Task::new(
+            TenantId::DEFAULT,
+            UserId::new(),
+            title.to_string(),
+            None,
+            priority,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_search_reports_every_invalid_field() {
+        let now = Utc::now();
+        let filter = TaskFilter {
+            created_from: Some(now),
+            created_before: Some(now),
+            id_fragment: Some("5b3c'--".to_string()),
+            ..TaskFilter::default()
+        };
+
+        let Err(DomainError::InvalidFields { errors }) =
+            TaskSearch::new(filter, TaskSort::default(), 0, 0)
+        else {
+            panic!("Search should be rejected");
+        };
+
+        let fields: Vec<_> = errors.iter().map(|error| error.field.as_str()).collect();
+        assert_eq!(fields, ["limit", "created_from", "id"]);
+    }
+
+    #[test]
+    fn test_page_filters_sorts_and_counts_every_match() {
+        let tasks = [
+            task("Report draft", TaskPriority::Low),
+            task("Quarterly REPORT", TaskPriority::Critical),
+            task("Groceries", TaskPriority::High),
+            task("Report review", TaskPriority::Medium),
+        ];
+        let filter = TaskFilter {
+            title: Some("  report ".to_string()),
+            ..TaskFilter::default()
+        };
+        let sort = TaskSort {
+            field: TaskSortField::Priority,
+            direction: SortDirection::Desc,
+        };
+        let search = TaskSearch::new(filter, sort, 2, 0).unwrap();
+
+        let page = search.page(&tasks);
+
+        assert_eq!(page.total, 3, "Total should count matches beyond the page");
+        let titles: Vec<_> = page.tasks.iter().map(|task| task.title.value()).collect();
+        assert_eq!(titles, ["Quarterly REPORT", "Report review"]);
+    }
+}
@@ -0,0 +1,188 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+
+use super::Task;
+use crate::domain::errors::DomainError;
+
+/// Days a [`StatsRange`] may span, both ends included: a leap year
+pub const MAX_STATS_RANGE_DAYS: i64 = 366;
+
+/// Calendar days to count tasks over, in the time zone the days start and end in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatsRange {
+    from: NaiveDate,
+    to: NaiveDate,
+    time_zone: Tz,
+}
+
+impl StatsRange {
+    /// Validate a user-supplied range; `time_zone` is an IANA name such as
+    /// `Europe/Warsaw`, UTC when absent
+    pub fn new(
+        from: NaiveDate,
+        to: NaiveDate,
+        time_zone: Option<&str>,
+    ) -> Result<Self, DomainError> {
+        let time_zone = match time_zone {
+            Some(name) => name.parse::<Tz>().map_err(|_| {
+                DomainError::field_validation_error("tz", format!("Unknown time zone `{name}`"))
+            })?,
+            None => Tz::UTC,
+        };
+        if from > to {
+            return Err(DomainError::field_validation_error(
+                "from",
+                "`from` cannot be after `to`",
+            ));
+        }
+        if (to - from).num_days() + 1 > MAX_STATS_RANGE_DAYS {
+            return Err(DomainError::field_validation_error(
+                "to",
+                format!("The range cannot exceed {MAX_STATS_RANGE_DAYS} days"),
+            ));
+        }
+
+        Ok(Self {
+            from,
+            to,
+            time_zone,
+        })
+    }
+
+    #[must_use]
+    pub const fn from(&self) -> NaiveDate {
+        self.from
+    }
+
+    #[must_use]
+    pub const fn to(&self) -> NaiveDate {
+        self.to
+    }
+
+    #[must_use]
+    pub const fn time_zone(&self) -> Tz {
+        self.time_zone
+    }
+
+    /// The day `instant` falls on in this range's time zone, if it is in the range
+    #[must_use]
+    pub fn day_of(&self, instant: DateTime<Utc>) -> Option<NaiveDate> {
+        let day = instant.with_timezone(&self.time_zone).date_naive();
+        (self.from..=self.to).contains(&day).then_some(day)
+    }
+
+    /// Every day of the range, in order
+    pub fn days(&self) -> impl Iterator<Item = NaiveDate> {
+        let to = self.to;
+        self.from.iter_days().take_while(move |day| *day <= to)
+    }
+}
+
+/// Tasks created and completed on one day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DailyTaskCount {
+    pub date: NaiveDate,
+    pub created: i64,
+    pub completed: i64,
+}
+
+impl DailyTaskCount {
+    /// Count `tasks` per day of `range`, for backends that cannot group in the database
+    ///
+    /// Returns one entry per day, days without tasks included, as the SQL backends do.
+    pub fn tally<'a>(range: &StatsRange, tasks: impl IntoIterator<Item = &'a Task>) -> Vec<Self> {
+        let mut days: Vec<Self> = range
+            .days()
+            .map(|date| Self {
+                date,
+                created: 0,
+                completed: 0,
+            })
+            .collect();
+        let index = |day: NaiveDate| usize::try_from((day - range.from).num_days()).ok();
+
+        for task in tasks {
+            if let Some(i) = range.day_of(task.created_at).and_then(index) {
+                days[i].created += 1;
+            }
+            if let Some(i) = task
+                .completed_at
+                .and_then(|completed_at| range.day_of(completed_at))
+                .and_then(index)
+            {
+                days[i].completed += 1;
+            }
+        }
+        days
+    }
+}
+
+/// A user's daily task counts over a range, for burn-down charts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskStats {
+    pub range: StatsRange,
+    /// One entry per day of the range, oldest first
+    pub days: Vec<DailyTaskCount>,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::common::{TenantId, UserId};
+
+    fn date(day: &str) -> NaiveDate {
+        day.parse().unwrap()
+    }
+
+    #[test]
+    fn test_range_accepts_a_leap_year_and_rejects_longer_or_reversed_ones() {
+        let range = StatsRange::new(date("2024-01-01"), date("2024-12-31"), None).unwrap();
+        assert_eq!(range.days().count(), 366);
+        assert_eq!(range.time_zone(), Tz::UTC);
+
+        // Negative test: One day too many, `from` after `to`, and an unknown zone
+        for (from, to, tz, field) in [
+            ("2024-01-01", "2025-01-01", None, "to"),
+            ("2024-02-02", "2024-02-01", None, "from"),
+            ("2024-02-01", "2024-02-01", Some("Mars/Olympus_Mons"), "tz"),
+        ] {
+            assert!(
+                matches!(
+                    StatsRange::new(date(from), date(to), tz),
+                    Err(DomainError::ValidationError { field: Some(f), .. }) if f == field
+                ),
+                "{from}..{to} in {tz:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_tally_counts_days_in_the_ranges_time_zone() {
+        // Arrange: A task created late on 1 March UTC, already 2 March in Tokyo, and
+        // completed on 3 March
+        let range =
+            StatsRange::new(date("2025-03-01"), date("2025-03-03"), Some("Asia/Tokyo")).unwrap();
+        let mut task = Task::new(
+            TenantId::DEFAULT,
+            UserId::new(),
+            "Ship stats".to_string(),
+            None,
+            Default::default(),
+        )
+        .unwrap();
+        task.created_at = Utc.with_ymd_and_hms(2025, 3, 1, 20, 0, 0).unwrap();
+        task.completed_at = Some(Utc.with_ymd_and_hms(2025, 3, 3, 1, 0, 0).unwrap());
+
+        // Act
+        let days = DailyTaskCount::tally(&range, [&task]);
+
+        // Assert: Every day is listed, the task counted on Tokyo's days
+        let counts: Vec<_> = days
+            .iter()
+            .map(|day| (day.created, day.completed))
+            .collect();
+        assert_eq!(counts, [(0, 0), (1, 0), (0, 1)]);
+    }
+}
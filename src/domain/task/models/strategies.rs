@@ -0,0 +1,18 @@
+//! Property-test strategies for task models, shared by the unit and integration tests
+
+use proptest::{collection::vec, prelude::*, sample::select};
+
+/// Any string a client could send: ASCII, multi-byte characters, control characters
+/// and every kind of whitespace and invisible character, up to well past the limit
+pub fn any_title() -> impl Strategy<Value = String> {
+    let character = prop_oneof![
+        4 => proptest::char::range(' ', '~'),
+        3 => any::<char>().prop_filter("multi-byte", |c| c.len_utf8() > 1),
+        1 => any::<char>().prop_filter("control", |c| c.is_control()),
+        2 => select(vec![
+            ' ', '\t', '\n', '\r', '\u{0B}', '\u{0C}', '\u{85}', '\u{A0}', '\u{2028}',
+            '\u{3000}', '\u{200B}', '\u{200D}', '\u{2060}', '\u{FEFF}',
+        ]),
+    ];
+    vec(character, 0..260).prop_map(String::from_iter)
+}
@@ -1,41 +1,229 @@
 use std::sync::Arc;
 
-use super::models::{Task, TaskId};
+use super::models::{
+    ChangedPriority, PriorityChange, StatsRange, Task, TaskFilter, TaskId, TaskPage, TaskPriority,
+    TaskRef, TaskSearch, TaskSort, TaskStats, TaskStatus,
+};
 use crate::{
-    common::UserId,
-    domain::{errors::DomainError, interfaces::task_repository::TaskRepository},
+    common::{TenantId, UserId},
+    domain::{
+        errors::DomainError,
+        interfaces::{feature_flags::FeatureFlags, task_repository::TaskRepository},
+        quota::{
+            models::{month_start, Quota, QuotaWarning},
+            operations::check_task_quotas,
+        },
+        warnings::DomainWarning,
+    },
 };
 
-/// Retrieve a task by ID
+/// Retrieve a task of `tenant` by ID
 ///
 /// Returns an error if the task is not found.
-pub async fn get_task(id: TaskId, repo: Arc<dyn TaskRepository>) -> Result<Task, DomainError> {
-    let result: Option<Task> = repo.get(id).await?;
+pub async fn get_task(
+    tenant: TenantId,
+    id: TaskId,
+    repo: Arc<dyn TaskRepository>,
+) -> Result<Task, DomainError> {
+    let result: Option<Task> = repo.get(tenant, id).await?;
     result.ok_or_else(|| DomainError::not_found("Task", id.to_string()))
 }
 
-/// List all tasks for a user
+/// Retrieve the task of `tenant` that `task` refers to
+///
+/// Returns an error if there is no such task, including when the user of
+/// `TaskRef::Latest` has none.
+pub async fn find_task(
+    tenant: TenantId,
+    task: TaskRef,
+    repo: Arc<dyn TaskRepository>,
+) -> Result<Task, DomainError> {
+    match task {
+        TaskRef::Id(id) => get_task(tenant, id, repo).await,
+        TaskRef::Latest(user_id) => {
+            let filter = TaskFilter {
+                user_id: Some(user_id),
+                ..TaskFilter::default()
+            };
+            // The default sort puts the newest task first
+            let search = TaskSearch::new(filter, TaskSort::default(), 1, 0)?;
+            repo.search_all(tenant, &search)
+                .await?
+                .tasks
+                .into_iter()
+                .next()
+                .ok_or_else(|| DomainError::not_found("Task", format!("latest of user {user_id}")))
+        }
+    }
+}
+
+/// List all tasks for a user of `tenant`
 ///
 /// Returns tasks ordered by creation date (newest first).
 pub async fn list_tasks_by_user(
+    tenant: TenantId,
     user_id: UserId,
     repo: Arc<dyn TaskRepository>,
 ) -> Result<Vec<Task>, DomainError> {
-    repo.get_by_user(user_id).await
+    repo.get_by_user(tenant, user_id).await
+}
+
+/// Count the tasks a user created and completed on each day of `range`
+pub async fn task_stats(
+    tenant: TenantId,
+    user_id: UserId,
+    range: StatsRange,
+    repo: Arc<dyn TaskRepository>,
+) -> Result<TaskStats, DomainError> {
+    let days = repo.daily_counts(tenant, user_id, &range).await?;
+    Ok(TaskStats { range, days })
+}
+
+/// A page of the tasks of every user in `tenant` matching `search`, for administrators
+pub async fn search_tasks(
+    tenant: TenantId,
+    search: &TaskSearch,
+    repo: Arc<dyn TaskRepository>,
+) -> Result<TaskPage, DomainError> {
+    repo.search_all(tenant, search).await
 }
 
 /// Create a new task
 ///
 /// Validates business rules:
 /// - Task title must be valid (enforced by Title value object)
-/// - No duplicate task validation (can be added if needed)
-pub async fn create_task(task: Task, repo: Arc<dyn TaskRepository>) -> Result<Task, DomainError> {
-    // Business rule: Task creation is validated through the Task::new constructor
-    // which ensures title is valid and other invariants are met.
-    // Additional business rules can be added here:
-    // - Check for duplicate tasks (same title for same user)
-    // - Enforce maximum tasks per user
-    // - Validate user permissions
-
-    repo.create(task).await
+/// - With the `reject_duplicate_titles` feature on, no open task of the same user may
+///   have the same title
+///
+/// With the `warn_duplicate_titles` feature on, an open task of the same user whose title
+/// differs only in case is reported as a [`DomainWarning::PossibleDuplicateTitle`].
+pub async fn create_task(
+    task: Task,
+    features: &dyn FeatureFlags,
+    repo: Arc<dyn TaskRepository>,
+) -> Result<(Task, Vec<DomainWarning>), DomainError> {
+    let warnings = duplicate_title_warnings(&task, features, repo.as_ref()).await?;
+
+    Ok((repo.create(task).await?, warnings))
+}
+
+/// Create a new task, as [`create_task`] does, unless it would go past a hard quota of its
+/// user
+///
+/// The user's usage is counted and the task inserted in one unit of work holding the user's
+/// quota lock, so concurrent creates, on this replica or another, are checked one after
+/// another and never go past the hard limit together. Returns the soft quotas the task goes
+/// past next to its warnings.
+pub async fn create_task_within_quotas(
+    task: Task,
+    open_tasks: Quota,
+    monthly_tasks: Quota,
+    features: &dyn FeatureFlags,
+    repo: Arc<dyn TaskRepository>,
+) -> Result<(Task, Vec<DomainWarning>, Vec<QuotaWarning>), DomainError> {
+    let warnings = duplicate_title_warnings(&task, features, repo.as_ref()).await?;
+
+    let mut uow = repo.begin().await?;
+    let usage = uow
+        .lock_usage(task.tenant_id, task.user_id, month_start(task.created_at))
+        .await?;
+    // Returning early drops the unit of work, which releases the lock
+    let quota_warnings = check_task_quotas(usage, open_tasks, monthly_tasks)?;
+    let created = uow.create(task).await?;
+    uow.commit().await?;
+
+    Ok((created, warnings, quota_warnings))
+}
+
+/// Apply the duplicate-title rules of `features` to `task` among the user's open tasks
+async fn duplicate_title_warnings(
+    task: &Task,
+    features: &dyn FeatureFlags,
+    repo: &dyn TaskRepository,
+) -> Result<Vec<DomainWarning>, DomainError> {
+    let rejects = features.rejects_duplicate_titles();
+    let warns = features.warns_on_duplicate_titles();
+    let mut warnings = Vec::new();
+    if !(rejects || warns) {
+        return Ok(warnings);
+    }
+    let existing = repo.get_by_user(task.tenant_id, task.user_id).await?;
+    let open: Vec<&Task> = existing
+        .iter()
+        .filter(|other| matches!(other.status, TaskStatus::Pending | TaskStatus::InProgress))
+        .collect();
+    let title = task.title.value();
+    if rejects && open.iter().any(|other| other.title.value() == title) {
+        return Err(DomainError::conflict(format!(
+            "An open task titled {title:?} already exists"
+        )));
+    }
+    if warns {
+        let title = title.to_lowercase();
+        if let Some(other) = open
+            .iter()
+            .find(|other| other.title.value().to_lowercase() == title)
+        {
+            warnings.push(DomainWarning::PossibleDuplicateTitle { existing: other.id });
+        }
+    }
+    Ok(warnings)
+}
+
+/// Create several tasks atomically
+///
+/// All tasks are inserted in one unit of work: if any insert fails, none of them are stored.
+pub async fn create_tasks(
+    tasks: Vec<Task>,
+    repo: Arc<dyn TaskRepository>,
+) -> Result<Vec<Task>, DomainError> {
+    let mut uow = repo.begin().await?;
+    let mut created = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        // Returning early drops the unit of work, which rolls back earlier inserts
+        created.push(uow.create(task).await?);
+    }
+    uow.commit().await?;
+
+    Ok(created)
+}
+
+/// Change the priority of a task of `tenant` on behalf of `changed_by`
+///
+/// Validates business rules:
+/// - The new priority must differ from the current one
+/// - Escalating to `Critical` needs a reason of at most
+///   [`PriorityChange::MAX_REASON_LENGTH`] characters
+///
+/// The task update and its audit trail entry commit together.
+pub async fn change_priority(
+    tenant: TenantId,
+    id: TaskId,
+    priority: TaskPriority,
+    reason: Option<String>,
+    changed_by: UserId,
+    repo: Arc<dyn TaskRepository>,
+) -> Result<ChangedPriority, DomainError> {
+    let mut uow = repo.begin().await?;
+    let before = uow
+        .get(tenant, id)
+        .await?
+        .ok_or_else(|| DomainError::not_found("Task", id.to_string()))?;
+    let change = PriorityChange::new(&before, priority, reason, changed_by)?;
+
+    let after = Task {
+        priority,
+        updated_at: change.changed_at,
+        ..before.clone()
+    };
+    // Returning early drops the unit of work, which rolls back the update
+    uow.update(&after).await?;
+    uow.record_priority_change(&change).await?;
+    uow.commit().await?;
+
+    Ok(ChangedPriority {
+        before,
+        after,
+        change,
+    })
 }
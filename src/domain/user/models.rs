@@ -0,0 +1,215 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{common::UserId, domain::errors::DomainError};
+
+/// Maximum length of a display name in characters (Unicode scalar values), not bytes
+const MAX_DISPLAY_NAME_LENGTH: usize = 100;
+
+/// An email address, stored lowercase so uniqueness ignores case
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Email(String);
+
+impl Email {
+    /// Longest address SMTP can deliver to (RFC 5321: a 256-octet path minus the brackets)
+    const MAX_LENGTH: usize = 254;
+    const MAX_LOCAL_PART_LENGTH: usize = 64;
+
+    /// Validate and normalize a user-supplied address
+    ///
+    /// Only the shape is checked: one `@` between a non-empty local part and a domain of
+    /// at least two dot-separated labels, with no whitespace. Whether the mailbox exists is
+    /// not.
+    pub fn new(value: String) -> Result<Self, DomainError> {
+        fn invalid(message: impl Into<String>) -> Result<Email, DomainError> {
+            Err(DomainError::field_validation_error("email", message))
+        }
+
+        let normalized = value.trim().to_lowercase();
+
+        if normalized.is_empty() {
+            return invalid("Email cannot be empty");
+        }
+        if normalized.chars().count() > Self::MAX_LENGTH {
+            return invalid(format!(
+                "Email cannot exceed {} characters",
+                Self::MAX_LENGTH
+            ));
+        }
+        if normalized
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control())
+        {
+            return invalid("Email cannot contain whitespace");
+        }
+        let Some((local, domain)) = normalized.split_once('@') else {
+            return invalid("Email must contain @");
+        };
+        if local.is_empty() || local.chars().count() > Self::MAX_LOCAL_PART_LENGTH {
+            return invalid(format!(
+                "Email must have 1 to {} characters before @",
+                Self::MAX_LOCAL_PART_LENGTH
+            ));
+        }
+        if domain.contains('@') {
+            return invalid("Email must contain a single @");
+        }
+        let labels: Vec<&str> = domain.split('.').collect();
+        if labels.len() < 2 || labels.iter().any(|label| label.is_empty()) {
+            return invalid("Email domain must have dot-separated labels, e.g. example.com");
+        }
+        Ok(Self(normalized))
+    }
+
+    /// Wrap an address read back from storage without validating it
+    #[must_use]
+    pub fn from_storage(value: String) -> Self {
+        Self(value)
+    }
+
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl std::fmt::Display for Email {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A registered user, the owner of tasks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: UserId,
+    pub email: Email,
+    pub display_name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl User {
+    pub fn new(email: String, display_name: String) -> Result<Self, DomainError> {
+        Ok(Self {
+            id: UserId::new(),
+            email: Email::new(email)?,
+            display_name: validate_display_name(&display_name)?,
+            created_at: Utc::now(),
+        })
+    }
+}
+
+/// Trim a user-supplied display name and check its length
+fn validate_display_name(display_name: &str) -> Result<String, DomainError> {
+    let display_name = display_name.trim();
+    if display_name.is_empty() {
+        return Err(DomainError::field_validation_error(
+            "display_name",
+            "Display name cannot be empty",
+        ));
+    }
+    if display_name.chars().any(char::is_control) {
+        return Err(DomainError::field_validation_error(
+            "display_name",
+            "Display name cannot contain control characters",
+        ));
+    }
+    if display_name.chars().count() > MAX_DISPLAY_NAME_LENGTH {
+        return Err(DomainError::field_validation_error(
+            "display_name",
+            format!("Display name cannot exceed {MAX_DISPLAY_NAME_LENGTH} characters"),
+        ));
+    }
+    Ok(display_name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validation_message(result: Result<Email, DomainError>) -> String {
+        match result {
+            Err(DomainError::ValidationError { message, field }) => {
+                assert_eq!(field.as_deref(), Some("email"));
+                message
+            }
+            other => panic!("Expected a validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_email_is_trimmed_and_lowercased() {
+        let email = Email::new("  Ada.Lovelace+Tasks@Example.COM ".to_string()).unwrap();
+        assert_eq!(email.value(), "ada.lovelace+tasks@example.com");
+
+        let unicode = Email::new("Jürgen@Bücher.de".to_string()).unwrap();
+        assert_eq!(unicode.value(), "jürgen@bücher.de");
+    }
+
+    #[test]
+    fn test_malformed_emails_are_rejected() {
+        // Negative test: Each shape problem is reported with its own message
+        for (email, message) in [
+            ("   ", "Email cannot be empty"),
+            (
+                "ada lovelace@example.com",
+                "Email cannot contain whitespace",
+            ),
+            ("ada.example.com", "Email must contain @"),
+            (
+                "@example.com",
+                "Email must have 1 to 64 characters before @",
+            ),
+            ("ada@lovelace@example.com", "Email must contain a single @"),
+            (
+                "ada@localhost",
+                "Email domain must have dot-separated labels",
+            ),
+            (
+                "ada@example..com",
+                "Email domain must have dot-separated labels",
+            ),
+            (
+                "ada@example.com.",
+                "Email domain must have dot-separated labels",
+            ),
+        ] {
+            let error = validation_message(Email::new(email.to_string()));
+            assert!(error.starts_with(message), "{email:?}: {error}");
+        }
+
+        let long_local = format!("{}@example.com", "a".repeat(65));
+        assert_eq!(
+            validation_message(Email::new(long_local)),
+            "Email must have 1 to 64 characters before @"
+        );
+        let long_domain = format!("ada@{}.com", "a".repeat(250));
+        assert_eq!(
+            validation_message(Email::new(long_domain)),
+            "Email cannot exceed 254 characters"
+        );
+    }
+
+    #[test]
+    fn test_new_user_trims_display_name_and_rejects_invalid_ones() {
+        let user = User::new("ada@example.com".to_string(), "  Ada  ".to_string()).unwrap();
+        assert_eq!(user.display_name, "Ada");
+
+        // Negative test: Blank, overlong and control-character names
+        for display_name in [
+            " ".to_string(),
+            "x".repeat(MAX_DISPLAY_NAME_LENGTH + 1),
+            "Ada\u{0}".to_string(),
+        ] {
+            assert!(matches!(
+                User::new("ada@example.com".to_string(), display_name),
+                Err(DomainError::ValidationError { field: Some(field), .. }) if field == "display_name"
+            ));
+        }
+    }
+}
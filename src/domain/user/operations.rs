@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use super::models::User;
+use crate::{
+    common::UserId,
+    domain::{errors::DomainError, interfaces::user_repository::UserRepository},
+};
+
+/// Retrieve a user by ID
+///
+/// Returns an error if the user is not found.
+pub async fn get_user(id: UserId, repo: Arc<dyn UserRepository>) -> Result<User, DomainError> {
+    let result: Option<User> = repo.get(id).await?;
+    result.ok_or_else(|| DomainError::not_found("User", id.to_string()))
+}
+
+/// Register a new user, validated by [`User::new`]
+///
+/// Returns `DomainError::Conflict` when the email is already registered.
+pub async fn register_user(user: User, repo: Arc<dyn UserRepository>) -> Result<User, DomainError> {
+    repo.create(user).await
+}
+
+/// Check that `id` belongs to a registered user, before something is stored under it
+///
+/// Returns `DomainError::NotFound` otherwise.
+pub async fn ensure_user_exists(
+    id: UserId,
+    repo: Arc<dyn UserRepository>,
+) -> Result<(), DomainError> {
+    get_user(id, repo).await?;
+    Ok(())
+}
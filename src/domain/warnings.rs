@@ -0,0 +1,33 @@
+use std::fmt;
+
+use crate::domain::task::models::TaskId;
+
+/// A concern about a write that went through anyway
+///
+/// Rules that should warn rather than block return these next to their result, e.g. as
+/// `(Task, Vec<DomainWarning>)`, and the API reports them without failing the request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DomainWarning {
+    /// An open task of the same user has the same title, ignoring case
+    PossibleDuplicateTitle { existing: TaskId },
+}
+
+impl DomainWarning {
+    /// Machine-readable name of the warning, e.g. `PossibleDuplicateTitle`
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::PossibleDuplicateTitle { .. } => "PossibleDuplicateTitle",
+        }
+    }
+}
+
+impl fmt::Display for DomainWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PossibleDuplicateTitle { existing } => {
+                write!(f, "Open task {existing} has the same title")
+            }
+        }
+    }
+}
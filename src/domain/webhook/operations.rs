@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use super::models::{Webhook, WebhookDelivery, WebhookId};
+use crate::{
+    common::TenantId,
+    domain::{
+        errors::DomainError, interfaces::webhook_repository::WebhookRepository,
+        task::models::TaskEventType,
+    },
+};
+
+/// Most delivery attempts listed for one webhook
+pub const DELIVERY_HISTORY_LIMIT: u32 = 100;
+
+/// Retrieve a webhook of `tenant` by ID
+///
+/// Returns an error if the webhook is not found, or belongs to another tenant.
+pub async fn get_webhook(
+    tenant: TenantId,
+    id: WebhookId,
+    repo: Arc<dyn WebhookRepository>,
+) -> Result<Webhook, DomainError> {
+    let result: Option<Webhook> = repo.get(tenant, id).await?;
+    result.ok_or_else(|| DomainError::not_found("Webhook", id.to_string()))
+}
+
+pub async fn list_webhooks(
+    tenant: TenantId,
+    repo: Arc<dyn WebhookRepository>,
+) -> Result<Vec<Webhook>, DomainError> {
+    repo.list(tenant).await
+}
+
+/// Store a subscription validated by [`Webhook::new`]
+pub async fn create_webhook(
+    webhook: Webhook,
+    repo: Arc<dyn WebhookRepository>,
+) -> Result<Webhook, DomainError> {
+    repo.create(webhook).await
+}
+
+/// Replace a webhook's URL, event types and active flag, as [`Webhook::update`] does
+pub async fn update_webhook(
+    tenant: TenantId,
+    id: WebhookId,
+    url: String,
+    event_types: Vec<TaskEventType>,
+    active: bool,
+    repo: Arc<dyn WebhookRepository>,
+) -> Result<Webhook, DomainError> {
+    let mut webhook = get_webhook(tenant, id, repo.clone()).await?;
+    webhook.update(url, event_types, active)?;
+    repo.update(&webhook).await?;
+    Ok(webhook)
+}
+
+pub async fn delete_webhook(
+    tenant: TenantId,
+    id: WebhookId,
+    repo: Arc<dyn WebhookRepository>,
+) -> Result<(), DomainError> {
+    repo.delete(tenant, id).await
+}
+
+/// The webhook's last [`DELIVERY_HISTORY_LIMIT`] delivery attempts, newest first
+///
+/// Returns `DomainError::NotFound` for an unknown webhook, or another tenant's, rather
+/// than an empty history.
+pub async fn list_deliveries(
+    tenant: TenantId,
+    id: WebhookId,
+    repo: Arc<dyn WebhookRepository>,
+) -> Result<Vec<WebhookDelivery>, DomainError> {
+    get_webhook(tenant, id, repo.clone()).await?;
+    repo.deliveries(id, DELIVERY_HISTORY_LIMIT).await
+}
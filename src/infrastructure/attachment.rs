@@ -0,0 +1,134 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{
+    attachment::models::{Attachment, AttachmentId},
+    errors::DomainError,
+    interfaces::attachment_repository::AttachmentRepository,
+    task::models::TaskId,
+};
+
+/// [`AttachmentRepository`] backed by the `attachments` and `orphaned_objects` tables
+#[derive(Debug, Clone)]
+pub struct PostgresAttachmentRepository {
+    pool: PgPool,
+}
+
+impl PostgresAttachmentRepository {
+    #[must_use]
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AttachmentRepository for PostgresAttachmentRepository {
+    #[tracing::instrument(skip_all, fields(attachment_id = %entity.id, task_id = %entity.task_id))]
+    async fn create(&self, entity: Attachment) -> Result<Attachment, DomainError> {
+        let row = sqlx::query_as::<_, AttachmentRow>(
+            r#"
+            INSERT INTO attachments (id, task_id, filename, content_type, size, object_key, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id, task_id, filename, content_type, size, object_key, created_at
+            "#,
+        )
+        .bind(entity.id.into_inner())
+        .bind(entity.task_id.into_inner())
+        .bind(&entity.filename)
+        .bind(&entity.content_type)
+        .bind(i64::try_from(entity.size).unwrap_or(i64::MAX))
+        .bind(&entity.object_key)
+        .bind(entity.created_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.into())
+    }
+
+    #[tracing::instrument(skip(self), fields(task_id = %task_id))]
+    async fn list(&self, task_id: TaskId) -> Result<Vec<Attachment>, DomainError> {
+        let rows = sqlx::query_as::<_, AttachmentRow>(
+            r#"
+            SELECT id, task_id, filename, content_type, size, object_key, created_at
+            FROM attachments
+            WHERE task_id = $1
+            ORDER BY created_at, id
+            "#,
+        )
+        .bind(task_id.into_inner())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    #[tracing::instrument(skip(self), fields(task_id = %task_id, attachment_id = %id))]
+    async fn delete(&self, task_id: TaskId, id: AttachmentId) -> Result<Attachment, DomainError> {
+        // The delete trigger records the object key in `orphaned_objects`
+        let row = sqlx::query_as::<_, AttachmentRow>(
+            r#"
+            DELETE FROM attachments
+            WHERE id = $1 AND task_id = $2
+            RETURNING id, task_id, filename, content_type, size, object_key, created_at
+            "#,
+        )
+        .bind(id.into_inner())
+        .bind(task_id.into_inner())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(Into::into)
+            .ok_or_else(|| DomainError::not_found("Attachment", id.to_string()))
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn orphaned_objects(&self, limit: u32) -> Result<Vec<String>, DomainError> {
+        let keys = sqlx::query_scalar(
+            "SELECT object_key FROM orphaned_objects ORDER BY orphaned_at, object_key LIMIT $1",
+        )
+        .bind(i64::from(limit))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(keys)
+    }
+
+    #[tracing::instrument(skip_all, fields(count = object_keys.len()))]
+    async fn forget_orphans(&self, object_keys: &[String]) -> Result<(), DomainError> {
+        if object_keys.is_empty() {
+            return Ok(());
+        }
+        sqlx::query("DELETE FROM orphaned_objects WHERE object_key = ANY($1)")
+            .bind(object_keys)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct AttachmentRow {
+    id: Uuid,
+    task_id: Uuid,
+    filename: String,
+    content_type: String,
+    size: i64,
+    object_key: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<AttachmentRow> for Attachment {
+    fn from(row: AttachmentRow) -> Self {
+        Self {
+            id: AttachmentId::from(row.id),
+            task_id: TaskId::from(row.task_id),
+            filename: row.filename,
+            content_type: row.content_type,
+            size: u64::try_from(row.size).unwrap_or_default(),
+            object_key: row.object_key,
+            created_at: row.created_at,
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use moka::future::Cache;
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use crate::{
+    common::{TenantId, UserId},
+    config::CacheConfig,
+    domain::{
+        errors::DomainError,
+        interfaces::task_repository::{TaskRepository, TaskUnitOfWork},
+        job::models::{JobId, NewJob},
+        quota::models::TaskUsage,
+        task::models::{
+            DailyTaskCount, PriorityChange, StatsRange, Task, TaskId, TaskPage, TaskSearch,
+        },
+    },
+};
+
+/// Storage behind [`CachedTaskRepository`]
+///
+/// Implemented in-process by [`MokaTaskCache`]; a shared cache such as Redis can implement
+/// it too. Failures should be swallowed (and logged) by the implementation: the cache is an
+/// optimization and must never fail a read that the database could serve.
+#[async_trait]
+pub trait TaskCache: Send + Sync + Debug {
+    async fn get(&self, id: TaskId) -> Option<Task>;
+    async fn insert(&self, task: Task);
+    async fn invalidate(&self, id: TaskId);
+}
+
+/// In-process [`TaskCache`] with a time-to-live and a bounded number of entries
+#[derive(Clone)]
+pub struct MokaTaskCache {
+    cache: Cache<TaskId, Task>,
+}
+
+impl Debug for MokaTaskCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MokaTaskCache")
+            .field("entry_count", &self.cache.entry_count())
+            .finish()
+    }
+}
+
+impl MokaTaskCache {
+    pub fn new(max_capacity: u64, ttl: Duration) -> Self {
+        Self {
+            cache: Cache::builder()
+                .max_capacity(max_capacity)
+                .time_to_live(ttl)
+                .build(),
+        }
+    }
+
+    pub fn from_config(config: &CacheConfig) -> Self {
+        Self::new(config.max_capacity, Duration::from_secs(config.ttl))
+    }
+}
+
+#[async_trait]
+impl TaskCache for MokaTaskCache {
+    async fn get(&self, id: TaskId) -> Option<Task> {
+        self.cache.get(&id).await
+    }
+
+    async fn insert(&self, task: Task) {
+        self.cache.insert(task.id, task).await;
+    }
+
+    async fn invalidate(&self, id: TaskId) {
+        self.cache.invalidate(&id).await;
+    }
+}
+
+/// [`TaskRepository`] decorator serving [`get`](TaskRepository::get) from a [`TaskCache`]
+///
+/// Updates and deletes (directly or through a committed unit of work) invalidate the
+/// cached task once the inner repository accepted them, so a read that starts after a
+/// write returns in this process never sees the old version. Listing by user always goes
+/// to the inner repository, and missing tasks are not cached. Cached tasks are keyed by id
+/// alone, so a hit for another tenant is treated as a miss and left to the inner
+/// repository to refuse.
+#[derive(Debug, Clone)]
+pub struct CachedTaskRepository {
+    inner: Arc<dyn TaskRepository>,
+    cache: Arc<dyn TaskCache>,
+    /// Bumped on every write, so a read racing with a write can tell its result may be stale
+    writes: Arc<AtomicU64>,
+}
+
+impl CachedTaskRepository {
+    pub fn new(inner: Arc<dyn TaskRepository>, cache: Arc<dyn TaskCache>) -> Self {
+        Self {
+            inner,
+            cache,
+            writes: Arc::new(AtomicU64::new(0)),
+        }
+    }
+}
+
+/// Drop the cached copies of tasks that were just written
+async fn invalidate(
+    cache: &dyn TaskCache,
+    writes: &AtomicU64,
+    ids: impl IntoIterator<Item = TaskId>,
+) {
+    writes.fetch_add(1, Ordering::SeqCst);
+    for id in ids {
+        cache.invalidate(id).await;
+    }
+}
+
+#[async_trait]
+impl TaskRepository for CachedTaskRepository {
+    async fn create(&self, entity: Task) -> Result<Task, DomainError> {
+        self.inner.create(entity).await
+    }
+
+    async fn get(&self, tenant: TenantId, id: TaskId) -> Result<Option<Task>, DomainError> {
+        let cached = self.cache.get(id).await;
+        if let Some(task) = cached.filter(|task| task.tenant_id == tenant) {
+            metrics::counter!("task_cache_requests_total", "result" => "hit").increment(1);
+            tracing::debug!(task_id = %id, "Task cache hit");
+            return Ok(Some(task));
+        }
+        metrics::counter!("task_cache_requests_total", "result" => "miss").increment(1);
+        tracing::debug!(task_id = %id, "Task cache miss");
+
+        let writes_before = self.writes.load(Ordering::SeqCst);
+        let task = self.inner.get(tenant, id).await?;
+
+        if let Some(task) = &task {
+            self.cache.insert(task.clone()).await;
+            // A write that finished while we were reading may have invalidated the cache
+            // before our possibly stale insert; undo it. A write still in flight
+            // invalidates after this check.
+            if self.writes.load(Ordering::SeqCst) != writes_before {
+                self.cache.invalidate(id).await;
+            }
+        }
+        Ok(task)
+    }
+
+    async fn get_by_user(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+    ) -> Result<Vec<Task>, DomainError> {
+        self.inner.get_by_user(tenant, user_id).await
+    }
+
+    async fn daily_counts(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        range: &StatsRange,
+    ) -> Result<Vec<DailyTaskCount>, DomainError> {
+        self.inner.daily_counts(tenant, user_id, range).await
+    }
+
+    async fn usage(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<TaskUsage, DomainError> {
+        self.inner.usage(tenant, user_id, since).await
+    }
+
+    async fn search_all(
+        &self,
+        tenant: TenantId,
+        search: &TaskSearch,
+    ) -> Result<TaskPage, DomainError> {
+        self.inner.search_all(tenant, search).await
+    }
+
+    async fn update(&self, entity: &Task) -> Result<(), DomainError> {
+        let result = self.inner.update(entity).await;
+        // Also on failure: a timed-out update may still have been applied
+        invalidate(self.cache.as_ref(), &self.writes, [entity.id]).await;
+        result
+    }
+
+    async fn delete(&self, tenant: TenantId, id: TaskId) -> Result<(), DomainError> {
+        let result = self.inner.delete(tenant, id).await;
+        invalidate(self.cache.as_ref(), &self.writes, [id]).await;
+        result
+    }
+
+    async fn health_check(&self) -> Result<(), DomainError> {
+        self.inner.health_check().await
+    }
+
+    async fn begin(&self) -> Result<Box<dyn TaskUnitOfWork>, DomainError> {
+        Ok(Box::new(CachedTaskUnitOfWork {
+            inner: self.inner.begin().await?,
+            cache: Arc::clone(&self.cache),
+            writes: Arc::clone(&self.writes),
+            written: Vec::new(),
+        }))
+    }
+}
+
+/// Unit of work that invalidates every task it wrote once it commits
+///
+/// Reads inside the unit of work bypass the cache so they see its own writes.
+pub struct CachedTaskUnitOfWork {
+    inner: Box<dyn TaskUnitOfWork>,
+    cache: Arc<dyn TaskCache>,
+    writes: Arc<AtomicU64>,
+    written: Vec<TaskId>,
+}
+
+impl Debug for CachedTaskUnitOfWork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedTaskUnitOfWork")
+            .field("written", &self.written)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl TaskUnitOfWork for CachedTaskUnitOfWork {
+    async fn create(&mut self, entity: Task) -> Result<Task, DomainError> {
+        self.inner.create(entity).await
+    }
+
+    async fn get(&mut self, tenant: TenantId, id: TaskId) -> Result<Option<Task>, DomainError> {
+        self.inner.get(tenant, id).await
+    }
+
+    async fn lock_usage(
+        &mut self,
+        tenant: TenantId,
+        user_id: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<TaskUsage, DomainError> {
+        self.inner.lock_usage(tenant, user_id, since).await
+    }
+
+    async fn update(&mut self, entity: &Task) -> Result<(), DomainError> {
+        self.written.push(entity.id);
+        self.inner.update(entity).await
+    }
+
+    async fn delete(&mut self, tenant: TenantId, id: TaskId) -> Result<(), DomainError> {
+        self.written.push(id);
+        self.inner.delete(tenant, id).await
+    }
+
+    async fn enqueue(&mut self, job: NewJob) -> Result<JobId, DomainError> {
+        self.inner.enqueue(job).await
+    }
+
+    async fn record_priority_change(&mut self, change: &PriorityChange) -> Result<(), DomainError> {
+        self.inner.record_priority_change(change).await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), DomainError> {
+        let result = self.inner.commit().await;
+        invalidate(self.cache.as_ref(), &self.writes, self.written).await;
+        result
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), DomainError> {
+        self.inner.rollback().await
+    }
+}
@@ -0,0 +1,448 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::config::CircuitBreakerConfig;
+
+/// Source of the current time, replaced by a fake clock in tests
+pub trait Clock: Send + Sync + std::fmt::Debug {
+    fn now(&self) -> Instant;
+}
+
+#[derive(Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    /// Calls go through while the failure rate stays below the threshold
+    Closed,
+    /// Calls fail immediately until the cool-down ends
+    Open,
+    /// One probe call is in flight; its outcome closes or reopens the breaker
+    HalfOpen,
+}
+
+impl BreakerState {
+    /// Value of the `circuit_breaker_state` gauge
+    fn gauge(self) -> f64 {
+        match self {
+            Self::Closed => 0.0,
+            Self::HalfOpen => 1.0,
+            Self::Open => 2.0,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+/// Returned instead of a permit while the breaker is open
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Circuit breaker {name} is open")]
+pub struct BreakerOpen {
+    pub name: String,
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: BreakerState,
+    /// Outcomes of the most recent calls while closed, `true` for a failure
+    outcomes: VecDeque<bool>,
+    opened_at: Option<Instant>,
+}
+
+/// Fails calls to a dependency fast once it keeps failing, instead of waiting on each one
+///
+/// Each call asks for a [`Permit`] with [`CircuitBreaker::try_call`] and reports its outcome
+/// through it. State changes are logged and exported as the `circuit_breaker_state` gauge
+/// (0 closed, 1 half-open, 2 open) and the `circuit_breaker_transitions_total` counter.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    name: String,
+    config: CircuitBreakerConfig,
+    clock: Arc<dyn Clock>,
+    inner: Mutex<Inner>,
+}
+
+impl CircuitBreaker {
+    #[must_use]
+    pub fn new(name: impl Into<String>, config: &CircuitBreakerConfig) -> Self {
+        Self::with_clock(name, config, Arc::new(SystemClock))
+    }
+
+    #[must_use]
+    pub fn with_clock(
+        name: impl Into<String>,
+        config: &CircuitBreakerConfig,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        let breaker = Self {
+            name: name.into(),
+            config: config.clone(),
+            clock,
+            inner: Mutex::new(Inner {
+                state: BreakerState::Closed,
+                outcomes: VecDeque::new(),
+                opened_at: None,
+            }),
+        };
+        metrics::gauge!("circuit_breaker_state", "breaker" => breaker.name.clone())
+            .set(BreakerState::Closed.gauge());
+        breaker
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn state(&self) -> BreakerState {
+        self.lock().state
+    }
+
+    /// Permission to make one call, or [`BreakerOpen`] to fail it without trying
+    ///
+    /// Once the cool-down of an open breaker has passed, the next caller gets the one probe
+    /// permit of the half-open state; callers after it are refused until it reports.
+    ///
+    /// # Errors
+    /// Returns [`BreakerOpen`] while the breaker is open or probing
+    pub fn try_call(&self) -> Result<Permit<'_>, BreakerOpen> {
+        let mut inner = self.lock();
+        match inner.state {
+            BreakerState::Closed => {}
+            BreakerState::Open => {
+                let cool_down = Duration::from_millis(self.config.cool_down_ms);
+                let cooled = inner
+                    .opened_at
+                    .is_none_or(|opened_at| self.clock.now() >= opened_at + cool_down);
+                if !cooled {
+                    return Err(self.open_error());
+                }
+                self.transition(&mut inner, BreakerState::HalfOpen);
+            }
+            BreakerState::HalfOpen => return Err(self.open_error()),
+        }
+        Ok(Permit {
+            breaker: self,
+            probe: inner.state == BreakerState::HalfOpen,
+            succeeded: false,
+        })
+    }
+
+    fn record(&self, probe: bool, succeeded: bool) {
+        let mut inner = self.lock();
+        match inner.state {
+            BreakerState::HalfOpen if probe => {
+                if succeeded {
+                    inner.outcomes.clear();
+                    self.transition(&mut inner, BreakerState::Closed);
+                } else {
+                    self.open(&mut inner);
+                }
+            }
+            BreakerState::Closed => {
+                inner.outcomes.push_back(!succeeded);
+                let window = self.config.window_size.max(1) as usize;
+                while inner.outcomes.len() > window {
+                    inner.outcomes.pop_front();
+                }
+                let calls = inner.outcomes.len();
+                let failures = inner.outcomes.iter().filter(|failed| **failed).count();
+                #[allow(clippy::cast_precision_loss)]
+                let rate = failures as f64 / calls as f64;
+                if calls >= self.config.minimum_calls.max(1) as usize
+                    && failures > 0
+                    && rate >= self.config.failure_rate_threshold
+                {
+                    self.open(&mut inner);
+                }
+            }
+            // A call let through before the breaker opened, finishing late
+            BreakerState::Open | BreakerState::HalfOpen => {}
+        }
+    }
+
+    fn open(&self, inner: &mut Inner) {
+        inner.outcomes.clear();
+        inner.opened_at = Some(self.clock.now());
+        self.transition(inner, BreakerState::Open);
+    }
+
+    fn transition(&self, inner: &mut Inner, state: BreakerState) {
+        let previous = std::mem::replace(&mut inner.state, state);
+        match state {
+            BreakerState::Open => tracing::warn!(
+                breaker = %self.name,
+                from = previous.as_str(),
+                "Circuit breaker opened, failing calls for {}ms",
+                self.config.cool_down_ms
+            ),
+            BreakerState::HalfOpen | BreakerState::Closed => tracing::info!(
+                breaker = %self.name,
+                from = previous.as_str(),
+                to = state.as_str(),
+                "Circuit breaker state changed"
+            ),
+        }
+        metrics::gauge!("circuit_breaker_state", "breaker" => self.name.clone()).set(state.gauge());
+        metrics::counter!(
+            "circuit_breaker_transitions_total",
+            "breaker" => self.name.clone(),
+            "state" => state.as_str()
+        )
+        .increment(1);
+    }
+
+    fn open_error(&self) -> BreakerOpen {
+        BreakerOpen {
+            name: self.name.clone(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// One call allowed by a [`CircuitBreaker`]
+///
+/// Counts as a failure unless [`Permit::succeeded`] is called, so a call abandoned halfway
+/// cannot leave a half-open breaker waiting forever for its probe.
+#[derive(Debug)]
+pub struct Permit<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+    succeeded: bool,
+}
+
+impl Permit<'_> {
+    pub fn succeeded(mut self) {
+        self.succeeded = true;
+    }
+
+    pub fn failed(self) {}
+
+    /// Report `result` and pass it on
+    pub fn record<T, E>(self, result: Result<T, E>) -> Result<T, E> {
+        if result.is_ok() {
+            self.succeeded();
+        }
+        result
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        self.breaker.record(self.probe, self.succeeded);
+    }
+}
+
+/// The service's circuit breakers by name, shared by the code using them and the health
+/// endpoint reporting them
+#[derive(Debug, Clone)]
+pub struct CircuitBreakers {
+    config: CircuitBreakerConfig,
+    clock: Arc<dyn Clock>,
+    breakers: Arc<Mutex<BTreeMap<String, Arc<CircuitBreaker>>>>,
+}
+
+impl CircuitBreakers {
+    #[must_use]
+    pub fn new(config: &CircuitBreakerConfig) -> Self {
+        Self::with_clock(config, Arc::new(SystemClock))
+    }
+
+    #[must_use]
+    pub fn with_clock(config: &CircuitBreakerConfig, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            config: config.clone(),
+            clock,
+            breakers: Arc::default(),
+        }
+    }
+
+    /// The breaker named `name`, created on first use; `None` when breakers are disabled
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<Arc<CircuitBreaker>> {
+        if !self.config.enabled {
+            return None;
+        }
+        let mut breakers = self
+            .breakers
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let breaker = breakers.entry(name.to_string()).or_insert_with(|| {
+            Arc::new(CircuitBreaker::with_clock(
+                name,
+                &self.config,
+                self.clock.clone(),
+            ))
+        });
+        Some(breaker.clone())
+    }
+
+    /// Current state of every breaker created so far
+    #[must_use]
+    pub fn states(&self) -> BTreeMap<String, BreakerState> {
+        self.breakers
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .map(|(name, breaker)| (name.clone(), breaker.state()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct FakeClock(Mutex<Instant>);
+
+    impl FakeClock {
+        fn advance(&self, by: Duration) {
+            *self.0.lock().unwrap() += by;
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> Instant {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    fn breaker() -> (CircuitBreaker, Arc<FakeClock>) {
+        let clock = Arc::new(FakeClock(Mutex::new(Instant::now())));
+        let config = CircuitBreakerConfig {
+            enabled: true,
+            failure_rate_threshold: 0.5,
+            window_size: 4,
+            minimum_calls: 3,
+            cool_down_ms: 1_000,
+        };
+        (
+            CircuitBreaker::with_clock("kafka", &config, clock.clone()),
+            clock,
+        )
+    }
+
+    fn fail(breaker: &CircuitBreaker) {
+        breaker.try_call().unwrap().failed();
+    }
+
+    fn succeed(breaker: &CircuitBreaker) {
+        breaker.try_call().unwrap().succeeded();
+    }
+
+    fn open(breaker: &CircuitBreaker) {
+        for _ in 0..3 {
+            fail(breaker);
+        }
+        assert_eq!(breaker.state(), BreakerState::Open);
+    }
+
+    #[test]
+    fn test_breaker_opens_at_the_failure_rate_and_fails_fast() {
+        let (breaker, _clock) = breaker();
+
+        // Negative test: a failure is below the minimum number of calls, and then below
+        // the failure rate
+        fail(&breaker);
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        succeed(&breaker);
+        succeed(&breaker);
+        succeed(&breaker);
+        assert_eq!(breaker.state(), BreakerState::Closed);
+
+        // The oldest calls leave the window: two failures in the last four calls
+        fail(&breaker);
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        succeed(&breaker);
+        fail(&breaker);
+        assert_eq!(breaker.state(), BreakerState::Open);
+
+        assert_eq!(
+            breaker.try_call().unwrap_err(),
+            BreakerOpen {
+                name: "kafka".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_successful_probe_closes_the_breaker() {
+        let (breaker, clock) = breaker();
+        open(&breaker);
+
+        // Negative test: still open until the cool-down has fully passed
+        clock.advance(Duration::from_millis(999));
+        assert!(breaker.try_call().is_err());
+
+        clock.advance(Duration::from_millis(1));
+        let probe = breaker.try_call().unwrap();
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+        // Only the probe goes through while half-open
+        assert!(breaker.try_call().is_err());
+        probe.succeeded();
+
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        // The failures from before opening are forgotten
+        fail(&breaker);
+        assert_eq!(breaker.state(), BreakerState::Closed);
+    }
+
+    #[test]
+    fn test_failed_probe_reopens_the_breaker_for_another_cool_down() {
+        let (breaker, clock) = breaker();
+        open(&breaker);
+        clock.advance(Duration::from_secs(1));
+
+        // An abandoned probe counts as a failure
+        drop(breaker.try_call().unwrap());
+
+        assert_eq!(breaker.state(), BreakerState::Open);
+        clock.advance(Duration::from_millis(500));
+        assert!(breaker.try_call().is_err());
+        clock.advance(Duration::from_millis(500));
+        assert!(breaker.try_call().is_ok());
+    }
+
+    #[test]
+    fn test_registry_shares_breakers_by_name_unless_disabled() {
+        let breakers = CircuitBreakers::new(&CircuitBreakerConfig::default());
+        let kafka = breakers.get("kafka").unwrap();
+        assert!(Arc::ptr_eq(&kafka, &breakers.get("kafka").unwrap()));
+        assert_eq!(
+            breakers.states(),
+            BTreeMap::from([("kafka".to_string(), BreakerState::Closed)])
+        );
+
+        let disabled = CircuitBreakers::new(&CircuitBreakerConfig {
+            enabled: false,
+            ..CircuitBreakerConfig::default()
+        });
+        assert!(disabled.get("kafka").is_none());
+    }
+}
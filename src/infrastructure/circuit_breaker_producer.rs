@@ -0,0 +1,136 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::circuit_breaker::CircuitBreaker;
+use crate::domain::{
+    errors::{DomainError, ExternalSystem},
+    interfaces::event_producer::EventProducer,
+    task::models::events::TaskEvent,
+};
+
+/// Publishes through `inner` while its circuit breaker is closed
+///
+/// Once the sink keeps failing, publishing fails immediately with an error from `system`
+/// instead of waiting for the sink's own timeout on every event.
+pub struct CircuitBreakerProducer {
+    inner: Arc<dyn EventProducer>,
+    breaker: Arc<CircuitBreaker>,
+    system: ExternalSystem,
+}
+
+impl std::fmt::Debug for CircuitBreakerProducer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircuitBreakerProducer")
+            .field("inner", &self.inner.name())
+            .field("breaker", &self.breaker.name())
+            .finish_non_exhaustive()
+    }
+}
+
+impl CircuitBreakerProducer {
+    #[must_use]
+    pub fn new(
+        inner: Arc<dyn EventProducer>,
+        breaker: Arc<CircuitBreaker>,
+        system: ExternalSystem,
+    ) -> Self {
+        Self {
+            inner,
+            breaker,
+            system,
+        }
+    }
+}
+
+#[async_trait]
+impl EventProducer for CircuitBreakerProducer {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    async fn publish_task_event(&self, event: TaskEvent) -> Result<(), DomainError> {
+        let permit = self
+            .breaker
+            .try_call()
+            .map_err(|e| DomainError::external_error(self.system, e.to_string()))?;
+        permit.record(self.inner.publish_task_event(event).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{
+        common::{TenantId, UserId},
+        config::CircuitBreakerConfig,
+        domain::task::models::{events::TaskEventData, Task, TaskPriority},
+    };
+
+    /// Sink that is down, counting the events it was asked to publish
+    #[derive(Debug, Default)]
+    struct DownSink(AtomicUsize);
+
+    #[async_trait]
+    impl EventProducer for DownSink {
+        async fn publish_task_event(&self, _event: TaskEvent) -> Result<(), DomainError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Err(DomainError::external_error(
+                ExternalSystem::Kafka,
+                "Message timed out",
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_open_breaker_stops_calling_the_sink() {
+        let sink = Arc::new(DownSink::default());
+        let config = CircuitBreakerConfig {
+            minimum_calls: 2,
+            ..CircuitBreakerConfig::default()
+        };
+        let producer = CircuitBreakerProducer::new(
+            sink.clone(),
+            Arc::new(CircuitBreaker::new("kafka", &config)),
+            ExternalSystem::Kafka,
+        );
+        let event = || {
+            let task = Task::new(
+                TenantId::DEFAULT,
+                UserId::new(),
+                "Write quarterly report".to_string(),
+                None,
+                TaskPriority::Medium,
+            )
+            .unwrap();
+            TaskEvent::new_created(
+                TaskEventData {
+                    id: task.id,
+                    title: task.title.value().to_string(),
+                    description: task.description,
+                    status: task.status,
+                    priority: task.priority,
+                    tenant_id: task.tenant_id,
+                    user_id: task.user_id,
+                    created_at: task.created_at,
+                    updated_at: task.updated_at,
+                    completed_at: task.completed_at,
+                },
+                uuid::Uuid::new_v4().to_string(),
+            )
+        };
+
+        for _ in 0..4 {
+            assert!(producer.publish_task_event(event()).await.is_err());
+        }
+
+        assert_eq!(sink.0.load(Ordering::SeqCst), 2);
+        let error = producer.publish_task_event(event()).await.unwrap_err();
+        assert!(
+            error.to_string().contains("Circuit breaker kafka is open"),
+            "{error}"
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::task::JoinSet;
+use tracing::warn;
+
+use crate::{
+    config::PublishPolicy,
+    domain::{
+        errors::{DomainError, ExternalSystem},
+        interfaces::event_producer::EventProducer,
+        task::models::events::TaskEvent,
+    },
+};
+
+/// Publishes every event to several sinks, such as Kafka and webhooks
+///
+/// With [`PublishPolicy::BestEffort`] all sinks are published to concurrently and a
+/// failing sink is logged without affecting the others; publishing only fails when no
+/// sink accepted the event. With [`PublishPolicy::FailFast`] sinks are published to in
+/// order and the first failure is returned, skipping the sinks after it.
+#[derive(Clone)]
+pub struct CompositeEventProducer {
+    producers: Vec<Arc<dyn EventProducer>>,
+    policy: PublishPolicy,
+}
+
+impl std::fmt::Debug for CompositeEventProducer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = self
+            .producers
+            .iter()
+            .map(|producer| producer.name())
+            .collect();
+        f.debug_struct("CompositeEventProducer")
+            .field("producers", &names)
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+impl CompositeEventProducer {
+    #[must_use]
+    pub fn new(producers: Vec<Arc<dyn EventProducer>>, policy: PublishPolicy) -> Self {
+        Self { producers, policy }
+    }
+
+    async fn publish_fail_fast(&self, event: TaskEvent) -> Result<(), DomainError> {
+        for producer in &self.producers {
+            if let Err(e) = producer.publish_task_event(event.clone()).await {
+                report_failure(producer.name(), &event, &e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn publish_best_effort(&self, event: TaskEvent) -> Result<(), DomainError> {
+        let mut publishing = JoinSet::new();
+        for producer in &self.producers {
+            let producer = producer.clone();
+            let event = event.clone();
+            publishing.spawn(async move {
+                let result = producer.publish_task_event(event).await;
+                (producer.name(), result)
+            });
+        }
+
+        let mut delivered = false;
+        let mut first_error = None;
+        while let Some(joined) = publishing.join_next().await {
+            let (sink, result) = match joined {
+                Ok(outcome) => outcome,
+                Err(e) => (
+                    "unknown",
+                    Err(DomainError::external_error(
+                        ExternalSystem::Other,
+                        format!("Event publishing task failed: {e}"),
+                    )),
+                ),
+            };
+            match result {
+                Ok(()) => delivered = true,
+                Err(e) => {
+                    report_failure(sink, &event, &e);
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) if !delivered => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn report_failure(sink: &'static str, event: &TaskEvent, error: &DomainError) {
+    warn!(
+        sink,
+        event_id = %event.event_id,
+        event_type = event.event_type.as_str(),
+        error = %error,
+        "Failed to publish task event"
+    );
+    metrics::counter!("event_publish_failures_total", "sink" => sink).increment(1);
+}
+
+#[async_trait]
+impl EventProducer for CompositeEventProducer {
+    async fn publish_task_event(&self, event: TaskEvent) -> Result<(), DomainError> {
+        match self.policy {
+            PublishPolicy::FailFast => self.publish_fail_fast(event).await,
+            PublishPolicy::BestEffort => self.publish_best_effort(event).await,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "composite"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::{
+        common::{TenantId, UserId},
+        domain::task::models::{events::TaskEventData, Task, TaskPriority},
+    };
+
+    /// Counts the events it receives, failing every one when `fails` is set
+    #[derive(Default)]
+    struct MockProducer {
+        fails: bool,
+        published: AtomicUsize,
+    }
+
+    impl MockProducer {
+        fn failing() -> Self {
+            Self {
+                fails: true,
+                ..Self::default()
+            }
+        }
+
+        fn published(&self) -> usize {
+            self.published.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl EventProducer for MockProducer {
+        async fn publish_task_event(&self, _event: TaskEvent) -> Result<(), DomainError> {
+            self.published.fetch_add(1, Ordering::SeqCst);
+            if self.fails {
+                return Err(DomainError::external_error(
+                    ExternalSystem::Kafka,
+                    "broker unavailable",
+                ));
+            }
+            Ok(())
+        }
+    }
+
+    fn event() -> TaskEvent {
+        let task = Task::new(
+            TenantId::DEFAULT,
+            UserId::new(),
+            "Write quarterly report".to_string(),
+            None,
+            TaskPriority::Medium,
+        )
+        .unwrap();
+        TaskEvent::new_created(
+            TaskEventData {
+                id: task.id,
+                title: task.title.value().to_string(),
+                description: task.description,
+                status: task.status,
+                priority: task.priority,
+                tenant_id: task.tenant_id,
+                user_id: task.user_id,
+                created_at: task.created_at,
+                updated_at: task.updated_at,
+                completed_at: task.completed_at,
+            },
+            Uuid::new_v4().to_string(),
+        )
+    }
+
+    fn composite(
+        first: &Arc<MockProducer>,
+        second: &Arc<MockProducer>,
+        policy: PublishPolicy,
+    ) -> CompositeEventProducer {
+        CompositeEventProducer::new(vec![first.clone(), second.clone()], policy)
+    }
+
+    #[tokio::test]
+    async fn test_best_effort_publishes_to_every_sink_despite_a_failure() {
+        let failing = Arc::new(MockProducer::failing());
+        let working = Arc::new(MockProducer::default());
+
+        let result = composite(&failing, &working, PublishPolicy::BestEffort)
+            .publish_task_event(event())
+            .await;
+
+        assert!(result.is_ok(), "One sink accepted the event: {result:?}");
+        assert_eq!(failing.published(), 1);
+        assert_eq!(working.published(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_best_effort_fails_when_no_sink_accepts_the_event() {
+        let first = Arc::new(MockProducer::failing());
+        let second = Arc::new(MockProducer::failing());
+
+        let result = composite(&first, &second, PublishPolicy::BestEffort)
+            .publish_task_event(event())
+            .await;
+
+        assert!(matches!(result, Err(DomainError::ExternalError { .. })));
+        assert_eq!(second.published(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast_stops_at_the_first_failing_sink() {
+        let failing = Arc::new(MockProducer::failing());
+        let working = Arc::new(MockProducer::default());
+
+        let result = composite(&failing, &working, PublishPolicy::FailFast)
+            .publish_task_event(event())
+            .await;
+
+        assert!(matches!(result, Err(DomainError::ExternalError { .. })));
+        assert_eq!(working.published(), 0, "Sinks after a failure are skipped");
+
+        let result = composite(&working, &failing, PublishPolicy::FailFast)
+            .publish_task_event(event())
+            .await;
+        assert!(result.is_err());
+        assert_eq!(working.published(), 1);
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    future::Future,
+    sync::{Arc, OnceLock},
+};
+
+use crate::config::ErrorReportingConfig;
+
+/// Request metadata attached to every error report
+#[derive(Debug, Default)]
+pub struct RequestContext {
+    pub request_id: Option<String>,
+    /// Route template, e.g. `/tasks/{id}`
+    pub route: Option<String>,
+    /// Set once the request is authenticated
+    pub user_id: OnceLock<String>,
+}
+
+/// Destination for unexpected server-side errors (5xx responses and panics)
+pub trait ErrorReporter: Send + Sync {
+    fn report(&self, error_type: &str, message: &str, context: &RequestContext);
+}
+
+/// Reporter used when error reporting is not configured
+#[derive(Debug, Default)]
+pub struct NoopErrorReporter;
+
+impl ErrorReporter for NoopErrorReporter {
+    fn report(&self, _error_type: &str, _message: &str, _context: &RequestContext) {}
+}
+
+static REPORTER: OnceLock<Box<dyn ErrorReporter>> = OnceLock::new();
+
+tokio::task_local! {
+    static REQUEST_CONTEXT: Arc<RequestContext>;
+}
+
+/// Install the process-wide error reporter
+///
+/// Can only be done once; returns `false` if a reporter was already installed.
+pub fn set_error_reporter(reporter: impl ErrorReporter + 'static) -> bool {
+    REPORTER.set(Box::new(reporter)).is_ok()
+}
+
+/// Whether a reporter has been installed
+pub fn is_enabled() -> bool {
+    REPORTER.get().is_some()
+}
+
+/// Send an error to the installed reporter with the current request's context
+///
+/// Does nothing when no reporter is installed.
+pub fn report_error(error_type: &str, message: &str) {
+    let Some(reporter) = REPORTER.get() else {
+        return;
+    };
+
+    match REQUEST_CONTEXT.try_with(Arc::clone) {
+        Ok(context) => reporter.report(error_type, message, &context),
+        Err(_) => reporter.report(error_type, message, &RequestContext::default()),
+    }
+}
+
+/// Run `future` with `context` available to [`report_error`]
+pub async fn with_request_context<F: Future>(context: RequestContext, future: F) -> F::Output {
+    REQUEST_CONTEXT.scope(Arc::new(context), future).await
+}
+
+/// Keep the current request's context, if any, for `future` once it is spawned
+///
+/// Task-locals do not cross `tokio::spawn`, so work started by a request and finished in
+/// the background would otherwise lose the request id.
+pub fn in_current_request<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    let context = REQUEST_CONTEXT.try_with(Arc::clone).ok();
+    async move {
+        match context {
+            Some(context) => REQUEST_CONTEXT.scope(context, future).await,
+            None => future.await,
+        }
+    }
+}
+
+/// Id of the request being handled, for passing on to the services it calls
+pub fn current_request_id() -> Option<String> {
+    REQUEST_CONTEXT
+        .try_with(|context| context.request_id.clone())
+        .ok()
+        .flatten()
+}
+
+/// Attach the authenticated user to the current request's context
+pub fn set_user_id(user_id: &str) {
+    let _ = REQUEST_CONTEXT.try_with(|context| context.user_id.set(user_id.to_string()));
+}
+
+/// Keeps the configured reporter alive; dropping it flushes pending reports
+pub struct ErrorReportingGuard {
+    #[cfg(feature = "sentry")]
+    _sentry: sentry::ClientInitGuard,
+}
+
+/// Initialize error reporting from config
+///
+/// Returns `None` without initializing anything when no DSN is configured.
+pub fn init(config: &ErrorReportingConfig) -> Option<ErrorReportingGuard> {
+    let dsn = config.dsn.as_deref()?;
+    init_reporter(dsn, config)
+}
+
+#[cfg(feature = "sentry")]
+fn init_reporter(dsn: &str, config: &ErrorReportingConfig) -> Option<ErrorReportingGuard> {
+    let guard = sentry::init((
+        dsn,
+        sentry::ClientOptions {
+            environment: Some(config.environment.clone().into()),
+            release: sentry::release_name!(),
+            ..Default::default()
+        },
+    ));
+    set_error_reporter(SentryErrorReporter);
+
+    Some(ErrorReportingGuard { _sentry: guard })
+}
+
+#[cfg(not(feature = "sentry"))]
+fn init_reporter(_dsn: &str, _config: &ErrorReportingConfig) -> Option<ErrorReportingGuard> {
+    tracing::warn!("Error reporting DSN is set but the `sentry` feature is not enabled");
+    None
+}
+
+/// Reports errors to Sentry as events tagged with the request context
+#[cfg(feature = "sentry")]
+#[derive(Debug, Default)]
+pub struct SentryErrorReporter;
+
+#[cfg(feature = "sentry")]
+impl ErrorReporter for SentryErrorReporter {
+    fn report(&self, error_type: &str, message: &str, context: &RequestContext) {
+        sentry::with_scope(
+            |scope| {
+                scope.set_tag("error_type", error_type);
+                if let Some(request_id) = &context.request_id {
+                    scope.set_tag("request_id", request_id);
+                }
+                if let Some(route) = &context.route {
+                    scope.set_tag("route", route);
+                }
+                if let Some(user_id) = context.user_id.get() {
+                    scope.set_user(Some(sentry::User {
+                        id: Some(user_id.clone()),
+                        ..Default::default()
+                    }));
+                }
+            },
+            || sentry::capture_message(message, sentry::Level::Error),
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+use crate::domain::interfaces::feature_flags::{Feature, FeatureFlags};
+
+/// Feature flags set in the configuration's `features` section, fixed until restart
+#[derive(Debug, Clone, Default)]
+pub struct StaticFeatureFlags {
+    flags: HashMap<String, bool>,
+}
+
+impl StaticFeatureFlags {
+    #[must_use]
+    pub fn new(flags: HashMap<String, bool>) -> Self {
+        Self { flags }
+    }
+}
+
+impl FeatureFlags for StaticFeatureFlags {
+    fn is_enabled(&self, feature: Feature) -> bool {
+        self.flags
+            .get(feature.name())
+            .copied()
+            .unwrap_or_else(|| feature.default_enabled())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unset_flags_keep_their_default() {
+        let flags = StaticFeatureFlags::new(HashMap::from([(
+            "reject_duplicate_titles".to_string(),
+            true,
+        )]));
+
+        assert!(flags.rejects_duplicate_titles());
+        assert!(flags.enforces_quotas());
+        assert!(!StaticFeatureFlags::default().rejects_duplicate_titles());
+        assert_eq!(
+            flags.snapshot().into_iter().collect::<Vec<_>>(),
+            vec![
+                ("enforce_quotas", true),
+                ("reject_duplicate_titles", true),
+                ("warn_duplicate_titles", false)
+            ]
+        );
+    }
+}
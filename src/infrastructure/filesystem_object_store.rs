@@ -0,0 +1,265 @@
+use std::{
+    path::{Component, Path, PathBuf},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use sha2::Sha256;
+
+use crate::domain::{
+    attachment::models::PresignedUpload,
+    errors::{DomainError, ExternalSystem},
+    interfaces::object_store::ObjectStore,
+};
+
+/// Path the service receives uploads for the filesystem store under
+pub const UPLOADS_PATH: &str = "/uploads";
+
+/// Query parameters of an upload URL, checked by [`FilesystemObjectStore::receive`]
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct UploadGrant {
+    pub content_type: String,
+    pub size: u64,
+    /// Unix timestamp, in seconds, after which the URL is refused
+    pub expires: i64,
+    pub signature: String,
+}
+
+/// [`ObjectStore`] keeping files in a local directory, for development
+///
+/// Without an S3 endpoint to upload to, the service receives the uploads itself: upload
+/// URLs point at `PUT /uploads/{key}` and carry an HMAC signature of the key, content
+/// type, size, and expiry, keyed with a secret generated at startup. A restart therefore
+/// invalidates upload URLs handed out before it.
+pub struct FilesystemObjectStore {
+    root: PathBuf,
+    base_url: String,
+    signing_key: [u8; 32],
+}
+
+impl std::fmt::Debug for FilesystemObjectStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FilesystemObjectStore")
+            .field("root", &self.root)
+            .field("base_url", &self.base_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FilesystemObjectStore {
+    /// Store keeping files under `root`, handing out upload URLs under `base_url`, the
+    /// address clients reach the service at
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>, base_url: &str) -> Self {
+        Self {
+            root: root.into(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            signing_key: rand::rng().random(),
+        }
+    }
+
+    /// Where the object `object_key` is kept, or `None` for a key escaping the root
+    #[must_use]
+    pub fn path(&self, object_key: &str) -> Option<PathBuf> {
+        let key = Path::new(object_key);
+        key.components()
+            .all(|component| matches!(component, Component::Normal(_)))
+            .then(|| self.root.join(key))
+    }
+
+    /// Store the body of a `PUT` to an upload URL, once its grant and body are checked
+    ///
+    /// Returns `DomainError::Unauthorized` for a forged or expired URL and
+    /// `DomainError::ValidationError` for a body of another type or size than signed.
+    pub async fn receive(
+        &self,
+        object_key: &str,
+        grant: &UploadGrant,
+        content_type: Option<&str>,
+        body: &[u8],
+    ) -> Result<(), DomainError> {
+        let expected = self.sign(object_key, &grant.content_type, grant.size, grant.expires);
+        if !constant_time_eq(expected.as_bytes(), grant.signature.as_bytes()) {
+            return Err(DomainError::unauthorized("Invalid upload signature"));
+        }
+        if Utc::now().timestamp() > grant.expires {
+            return Err(DomainError::unauthorized("Upload URL has expired"));
+        }
+        if content_type != Some(grant.content_type.as_str()) {
+            return Err(DomainError::validation_error(format!(
+                "Content-Type must be {}",
+                grant.content_type
+            )));
+        }
+        if body.len() as u64 != grant.size {
+            return Err(DomainError::validation_error(format!(
+                "Body must be exactly {} bytes",
+                grant.size
+            )));
+        }
+        let path = self
+            .path(object_key)
+            .ok_or_else(|| DomainError::validation_error("Invalid object key"))?;
+
+        // Write next to the target and rename, so a reader never sees a partial file
+        let partial = path.with_extension("partial");
+        let write = async {
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::write(&partial, body).await?;
+            tokio::fs::rename(&partial, &path).await
+        };
+        write.await.map_err(|e| {
+            DomainError::external_error(
+                ExternalSystem::ObjectStore,
+                format!("Failed to write object {object_key}: {e}"),
+            )
+        })
+    }
+
+    fn sign(&self, object_key: &str, content_type: &str, size: u64, expires: i64) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.signing_key)
+            .expect("HMAC accepts keys of any size");
+        mac.update(format!("{object_key}\n{content_type}\n{size}\n{expires}").as_bytes());
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[async_trait]
+impl ObjectStore for FilesystemObjectStore {
+    fn name(&self) -> &'static str {
+        "filesystem"
+    }
+
+    fn presign_put(
+        &self,
+        object_key: &str,
+        content_type: &str,
+        size: u64,
+        expires_in: Duration,
+    ) -> Result<PresignedUpload, DomainError> {
+        let expires_at = Utc::now() + expires_in;
+        let expires = expires_at.timestamp();
+        let signature = self.sign(object_key, content_type, size, expires);
+        let mut url = url::Url::parse(&format!("{}{UPLOADS_PATH}/{object_key}", self.base_url))
+            .map_err(|e| {
+                DomainError::external_error(
+                    ExternalSystem::ObjectStore,
+                    format!("Invalid upload URL base {}: {e}", self.base_url),
+                )
+            })?;
+        url.query_pairs_mut()
+            .append_pair("content_type", content_type)
+            .append_pair("size", &size.to_string())
+            .append_pair("expires", &expires.to_string())
+            .append_pair("signature", &signature);
+
+        Ok(PresignedUpload {
+            url: url.into(),
+            headers: vec![
+                ("Content-Length".to_string(), size.to_string()),
+                ("Content-Type".to_string(), content_type.to_string()),
+            ],
+            expires_at,
+        })
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn delete(&self, object_key: &str) -> Result<(), DomainError> {
+        let Some(path) = self.path(object_key) else {
+            return Ok(());
+        };
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(DomainError::external_error(
+                ExternalSystem::ObjectStore,
+                format!("Failed to delete object {object_key}: {e}"),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grant_from(url: &str) -> UploadGrant {
+        let url = url::Url::parse(url).unwrap();
+        serde_json::from_value(serde_json::Value::Object(
+            url.query_pairs()
+                .map(|(name, value)| {
+                    let value = match name.as_ref() {
+                        "size" | "expires" => serde_json::json!(value.parse::<i64>().unwrap()),
+                        _ => serde_json::json!(value),
+                    };
+                    (name.into_owned(), value)
+                })
+                .collect(),
+        ))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_receive_stores_a_signed_upload_and_delete_removes_it() {
+        let root = tempfile::tempdir().unwrap();
+        let store = FilesystemObjectStore::new(root.path(), "http://localhost:8080/");
+        let upload = store
+            .presign_put("tasks/1/2", "text/plain", 5, Duration::from_secs(60))
+            .unwrap();
+        assert!(upload
+            .url
+            .starts_with("http://localhost:8080/uploads/tasks/1/2?"));
+        let grant = grant_from(&upload.url);
+
+        store
+            .receive("tasks/1/2", &grant, Some("text/plain"), b"hello")
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::read(root.path().join("tasks/1/2")).unwrap(),
+            b"hello"
+        );
+
+        store.delete("tasks/1/2").await.unwrap();
+        assert!(!root.path().join("tasks/1/2").exists());
+        // Negative test: deleting an object that is gone succeeds
+        store.delete("tasks/1/2").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_receive_rejects_other_keys_sizes_and_escaping_paths() {
+        let root = tempfile::tempdir().unwrap();
+        let store = FilesystemObjectStore::new(root.path(), "http://localhost:8080");
+        let upload = store
+            .presign_put("tasks/1/2", "text/plain", 5, Duration::from_secs(60))
+            .unwrap();
+        let grant = grant_from(&upload.url);
+
+        let other_key = store
+            .receive("tasks/1/3", &grant, Some("text/plain"), b"hello")
+            .await;
+        let other_size = store
+            .receive("tasks/1/2", &grant, Some("text/plain"), b"hello!")
+            .await;
+
+        assert!(matches!(other_key, Err(DomainError::Unauthorized { .. })));
+        assert!(matches!(
+            other_size,
+            Err(DomainError::ValidationError { .. })
+        ));
+        assert_eq!(store.path("../escape"), None);
+    }
+}
@@ -0,0 +1,170 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::config::HealthHistoryConfig;
+
+/// Outcome of one readiness probe
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeResult {
+    pub at: DateTime<Utc>,
+    pub latency: Duration,
+    /// Why the probe failed; `None` when it succeeded
+    pub error: Option<String>,
+}
+
+impl ProbeResult {
+    #[must_use]
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// The most recent probes, oldest first
+    probes: VecDeque<ProbeResult>,
+    /// Whether the success rate was below the threshold after the previous probe
+    flapping: bool,
+}
+
+/// The last readiness probe results, kept so a flapping pod can be explained afterwards
+///
+/// Holds at most `capacity` results, dropping the oldest. Once it holds `min_probes`, a
+/// success rate falling below `min_success_rate` is logged as a warning, and its recovery
+/// as info; each once per crossing rather than on every probe.
+#[derive(Debug, Clone)]
+pub struct HealthHistory {
+    config: HealthHistoryConfig,
+    inner: Arc<RwLock<Inner>>,
+}
+
+impl HealthHistory {
+    #[must_use]
+    pub fn new(config: &HealthHistoryConfig) -> Self {
+        Self {
+            config: config.clone(),
+            inner: Arc::new(RwLock::new(Inner {
+                probes: VecDeque::with_capacity(config.capacity),
+                flapping: false,
+            })),
+        }
+    }
+
+    /// Add the outcome of a probe, dropping the oldest once full
+    pub fn record(&self, probe: ProbeResult) {
+        let mut inner = self.write();
+        if inner.probes.len() == self.config.capacity {
+            inner.probes.pop_front();
+        }
+        inner.probes.push_back(probe);
+
+        if inner.probes.len() < self.config.min_probes {
+            return;
+        }
+        let success_rate = success_rate(&inner.probes);
+        let flapping = success_rate < self.config.min_success_rate;
+        if flapping && !inner.flapping {
+            tracing::warn!(
+                success_rate,
+                min_success_rate = self.config.min_success_rate,
+                probes = inner.probes.len(),
+                "Readiness is flapping"
+            );
+        } else if !flapping && inner.flapping {
+            tracing::info!(success_rate, "Readiness stopped flapping");
+        }
+        inner.flapping = flapping;
+    }
+
+    /// The recorded probes, newest first
+    #[must_use]
+    pub fn probes(&self) -> Vec<ProbeResult> {
+        let inner = self.read();
+        inner.probes.iter().rev().cloned().collect()
+    }
+
+    /// Share of the recorded probes that succeeded, `None` before the first one
+    #[must_use]
+    pub fn success_rate(&self) -> Option<f64> {
+        let inner = self.read();
+        (!inner.probes.is_empty()).then(|| success_rate(&inner.probes))
+    }
+
+    /// Whether the success rate is below the threshold
+    #[must_use]
+    pub fn is_flapping(&self) -> bool {
+        self.read().flapping
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Inner> {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Inner> {
+        self.inner.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn success_rate(probes: &VecDeque<ProbeResult>) -> f64 {
+    let succeeded = probes.iter().filter(|probe| probe.succeeded()).count();
+    succeeded as f64 / probes.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probe(ok: bool) -> ProbeResult {
+        ProbeResult {
+            at: Utc::now(),
+            latency: Duration::from_millis(1),
+            error: (!ok).then(|| "connection refused".to_string()),
+        }
+    }
+
+    fn history(capacity: usize) -> HealthHistory {
+        HealthHistory::new(&HealthHistoryConfig {
+            capacity,
+            min_success_rate: 0.75,
+            min_probes: 4,
+        })
+    }
+
+    #[test]
+    fn test_history_keeps_only_the_most_recent_probes() {
+        let history = history(3);
+
+        for ok in [false, true, true, false] {
+            history.record(probe(ok));
+        }
+
+        let probes = history.probes();
+        assert_eq!(probes.len(), 3);
+        assert!(!probes[0].succeeded(), "newest first");
+        assert_eq!(history.success_rate(), Some(2.0 / 3.0));
+    }
+
+    #[test]
+    fn test_flapping_needs_enough_probes_and_clears_on_recovery() {
+        let history = history(8);
+
+        for ok in [false, true, false] {
+            history.record(probe(ok));
+        }
+        assert!(!history.is_flapping(), "too few probes to judge");
+
+        history.record(probe(true));
+        assert!(history.is_flapping());
+
+        for _ in 0..8 {
+            history.record(probe(true));
+        }
+        assert!(!history.is_flapping());
+    }
+}
@@ -0,0 +1,369 @@
+use std::time::{Duration, Instant};
+
+use opentelemetry::{global, propagation::Injector};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT},
+    IntoUrl, Method, RequestBuilder, Response, StatusCode,
+};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use super::{error_reporting, retry::RetryPolicy};
+use crate::{
+    config::HttpClientConfig,
+    domain::errors::{DomainError, ExternalSystem},
+};
+
+/// Header carrying the id of the request that caused an outgoing call
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Client for the service's outgoing HTTP calls
+///
+/// Wraps a `reqwest::Client` configured from [`HttpClientConfig`]: timeouts, proxy and
+/// user agent. Every request sent through [`HttpClient::send`] carries the current
+/// request's id and `traceparent` headers, and is recorded in the
+/// `http_client_request_duration_seconds` histogram by host, method and status.
+///
+/// Idempotent requests (`GET`, `HEAD`, `PUT`, `DELETE`, `OPTIONS`) are retried with
+/// exponential backoff when the connection fails, they time out, or the server answers
+/// 429, 502, 503 or 504. Other methods are sent once, since the server may have acted on
+/// them before failing.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    retry: RetryPolicy,
+}
+
+/// Options of an [`HttpClient`] that differ from the configuration
+#[derive(Debug)]
+pub struct HttpClientBuilder<'a> {
+    config: &'a HttpClientConfig,
+    user_agent: Option<String>,
+    timeout: Duration,
+    retry: RetryPolicy,
+    follow_redirects: bool,
+}
+
+impl HttpClientBuilder<'_> {
+    /// Send `user_agent` instead of the configured one
+    #[must_use]
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Fail each attempt not done within `timeout`, instead of the configured timeout
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Retry idempotent requests as `retry` says; [`RetryPolicy::NONE`] for callers that
+    /// retry on their own terms
+    #[must_use]
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Return redirects to the caller instead of following them
+    #[must_use]
+    pub fn no_redirects(mut self) -> Self {
+        self.follow_redirects = false;
+        self
+    }
+
+    /// # Errors
+    /// Returns `DomainError::ExternalError` for an invalid proxy or user agent, or when
+    /// TLS cannot be initialized
+    pub fn build(self) -> Result<HttpClient, DomainError> {
+        let error = |message: String| DomainError::external_error(ExternalSystem::Http, message);
+
+        let user_agent = self
+            .user_agent
+            .unwrap_or_else(|| self.config.user_agent.clone());
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            USER_AGENT,
+            HeaderValue::from_str(&user_agent)
+                .map_err(|e| error(format!("Invalid user agent {user_agent:?}: {e}")))?,
+        );
+
+        let mut builder = reqwest::Client::builder()
+            .default_headers(headers)
+            .connect_timeout(Duration::from_millis(self.config.connect_timeout_ms))
+            .timeout(self.timeout);
+        if !self.follow_redirects {
+            builder = builder.redirect(reqwest::redirect::Policy::none());
+        }
+        if let Some(proxy) = &self.config.proxy {
+            let proxy = reqwest::Proxy::all(proxy)
+                .map_err(|e| error(format!("Invalid HTTP proxy: {e}")))?;
+            builder = builder.proxy(proxy);
+        }
+        let client = builder
+            .build()
+            .map_err(|e| error(format!("Failed to create HTTP client: {e}")))?;
+
+        Ok(HttpClient {
+            client,
+            retry: self.retry,
+        })
+    }
+}
+
+impl HttpClient {
+    /// # Errors
+    /// See [`HttpClientBuilder::build`]
+    pub fn new(config: &HttpClientConfig) -> Result<Self, DomainError> {
+        Self::builder(config).build()
+    }
+
+    /// Client configured from `config`, with options to override per caller
+    #[must_use]
+    pub fn builder(config: &HttpClientConfig) -> HttpClientBuilder<'_> {
+        HttpClientBuilder {
+            config,
+            user_agent: None,
+            timeout: Duration::from_millis(config.request_timeout_ms),
+            retry: RetryPolicy {
+                max_attempts: config.max_attempts.max(1),
+                base_delay: Duration::from_millis(config.retry_base_delay_ms),
+                max_delay: Duration::from_millis(config.retry_max_delay_ms),
+            },
+            follow_redirects: true,
+        }
+    }
+
+    /// `method` request to `url`, to finish and pass to [`HttpClient::send`]
+    pub fn request(&self, method: Method, url: impl IntoUrl) -> RequestBuilder {
+        self.client.request(method, url)
+    }
+
+    /// Send `request` with the propagation headers, retrying it if it is idempotent
+    ///
+    /// The last response is returned whatever its status; only failures to get one are
+    /// errors.
+    ///
+    /// # Errors
+    /// Returns the `reqwest` error of an invalid request or of the last failed attempt
+    pub async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let mut request = request.build()?;
+        propagate_context(request.headers_mut());
+        let retryable = is_idempotent(request.method());
+
+        let mut attempt = 1;
+        loop {
+            // A streaming body cannot be sent twice, so that request gets one attempt
+            let next = (retryable && attempt < self.retry.max_attempts)
+                .then(|| request.try_clone())
+                .flatten();
+            let host = request.url().host_str().unwrap_or_default().to_string();
+            let method = request.method().clone();
+
+            let started = Instant::now();
+            let sent = self.client.execute(request).await;
+            metrics::histogram!(
+                "http_client_request_duration_seconds",
+                "host" => host.clone(),
+                "method" => method.to_string(),
+                "status" => match &sent {
+                    Ok(response) => response.status().as_u16().to_string(),
+                    Err(_) => "error".to_string(),
+                }
+            )
+            .record(started.elapsed().as_secs_f64());
+
+            let cause = match &sent {
+                Ok(response) if is_transient(response.status()) => response.status().to_string(),
+                Err(error) if error.is_connect() => "connection failed".to_string(),
+                Err(error) if error.is_timeout() => "timed out".to_string(),
+                _ => return sent,
+            };
+            let Some(retry) = next else {
+                return sent;
+            };
+
+            let delay = self.retry.delay(attempt);
+            tracing::warn!(
+                %host,
+                %method,
+                "HTTP request failed ({}); retrying in {}s, attempt {} of {}",
+                cause,
+                delay.as_secs_f32(),
+                attempt + 1,
+                self.retry.max_attempts
+            );
+            tokio::time::sleep(delay).await;
+            request = retry;
+            attempt += 1;
+        }
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+    )
+}
+
+fn is_transient(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Add the current request's id and `traceparent` to `headers`, keeping any set already
+fn propagate_context(headers: &mut HeaderMap) {
+    if !headers.contains_key(REQUEST_ID_HEADER) {
+        if let Some(value) = error_reporting::current_request_id()
+            .and_then(|request_id| HeaderValue::from_str(&request_id).ok())
+        {
+            headers.insert(REQUEST_ID_HEADER, value);
+        }
+    }
+
+    let context = tracing::Span::current().context();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut HeaderInjector(headers));
+    });
+}
+
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::{error_reporting::RequestContext, telemetry};
+    use opentelemetry_sdk::{propagation::TraceContextPropagator, trace::SdkTracerProvider};
+    use tracing::Instrument;
+    use tracing_subscriber::layer::SubscriberExt;
+    use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+    /// A client retrying three times without waiting
+    fn client() -> HttpClient {
+        let config = HttpClientConfig {
+            max_attempts: 3,
+            retry_base_delay_ms: 0,
+            retry_max_delay_ms: 0,
+            user_agent: "tests/1.0".to_string(),
+            ..HttpClientConfig::default()
+        };
+        HttpClient::new(&config).unwrap()
+    }
+
+    /// Answer `method` requests with 503 once, then with 200
+    async fn flaky(server: &MockServer, method: &str) {
+        Mock::given(matchers::method(method))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(server)
+            .await;
+        Mock::given(matchers::method(method))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_requests_are_retried() {
+        let server = MockServer::start().await;
+        flaky(&server, "GET").await;
+        let client = client();
+
+        let response = client
+            .send(client.request(Method::GET, server.uri()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_post_is_not_retried() {
+        // Negative test: the server may have acted on the first POST before failing
+        let server = MockServer::start().await;
+        flaky(&server, "POST").await;
+        let client = client();
+
+        let response = client
+            .send(client.request(Method::POST, server.uri()).body("{}"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retries_stop_after_max_attempts() {
+        // Negative test: a server that keeps failing gets its last answer returned
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("GET"))
+            .respond_with(ResponseTemplate::new(502))
+            .expect(3)
+            .mount(&server)
+            .await;
+        let client = client();
+
+        let response = client
+            .send(client.request(Method::GET, server.uri()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    }
+
+    #[tokio::test]
+    async fn test_request_id_trace_context_and_user_agent_are_sent() {
+        let server = MockServer::start().await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::header(REQUEST_ID_HEADER, "req-42"))
+            .and(matchers::header("user-agent", "tests/1.0"))
+            .and(matchers::header_exists("traceparent"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry().with(telemetry::layer(
+            &provider,
+            &crate::config::TelemetryConfig::default(),
+        ));
+        let _default = tracing::subscriber::set_default(subscriber);
+        let client = client();
+
+        let context = RequestContext {
+            request_id: Some("req-42".to_string()),
+            ..RequestContext::default()
+        };
+        let response = error_reporting::with_request_context(
+            context,
+            client
+                .send(client.request(Method::GET, server.uri()))
+                .instrument(tracing::info_span!("request")),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,317 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+use crate::{
+    common::{TenantId, UserId},
+    domain::{
+        errors::{DomainError, ExternalSystem},
+        interfaces::task_repository::{TaskRepository, TaskUnitOfWork},
+        job::models::{JobId, NewJob},
+        quota::models::TaskUsage,
+        task::models::{
+            DailyTaskCount, PriorityChange, StatsRange, Task, TaskId, TaskPage, TaskSearch,
+        },
+    },
+};
+
+/// Constraint reported on duplicate ids, matching the Postgres primary key name
+const PRIMARY_KEY_CONSTRAINT: &str = "tasks_pkey";
+
+type TaskMap = HashMap<TaskId, Task>;
+
+/// Process-local [`TaskRepository`] for tests and examples
+///
+/// Follows the same contract as [`PostgresTaskRepository`](super::task::PostgresTaskRepository):
+/// duplicate ids are a `Conflict` (across tenants too), tasks of other tenants are
+/// invisible, update/delete of a missing task is `NotFound`, updates keep the owner and
+/// creation time, and `get_by_user` returns the newest tasks first. Clones share the same storage.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryTaskRepository {
+    tasks: Arc<RwLock<TaskMap>>,
+    /// Committed priority changes, oldest first
+    priority_changes: Arc<RwLock<Vec<PriorityChange>>>,
+    /// Held by the unit of work counting quota usage, for every user at once
+    quota_lock: Arc<Mutex<()>>,
+}
+
+impl InMemoryTaskRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The audit trail of a task's priority in `tenant`, oldest change first
+    pub fn priority_changes(&self, tenant: TenantId, id: TaskId) -> Vec<PriorityChange> {
+        self.priority_changes
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter(|change| change.tenant_id == tenant && change.task_id == id)
+            .cloned()
+            .collect()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, TaskMap> {
+        // A panic while holding the lock cannot leave a half-written task behind
+        self.tasks.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, TaskMap> {
+        self.tasks.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn duplicate_id() -> DomainError {
+    DomainError::Conflict {
+        message: format!(
+            "duplicate key value violates unique constraint \"{PRIMARY_KEY_CONSTRAINT}\""
+        ),
+        constraint: Some(PRIMARY_KEY_CONSTRAINT.to_string()),
+    }
+}
+
+/// `task` if it belongs to `tenant`, as a tenant-scoped query would find it
+fn in_tenant(task: Option<&Task>, tenant: TenantId) -> Option<&Task> {
+    task.filter(|task| task.tenant_id == tenant)
+}
+
+/// Apply an update the way the SQL backends do: owner and creation time never change
+fn apply_update(stored: &Task, update: &Task) -> Task {
+    Task {
+        user_id: stored.user_id,
+        created_at: stored.created_at,
+        ..update.clone()
+    }
+}
+
+#[async_trait]
+impl TaskRepository for InMemoryTaskRepository {
+    async fn create(&self, entity: Task) -> Result<Task, DomainError> {
+        let mut tasks = self.write();
+        if tasks.contains_key(&entity.id) {
+            return Err(duplicate_id());
+        }
+        tasks.insert(entity.id, entity.clone());
+        Ok(entity)
+    }
+
+    async fn get(&self, tenant: TenantId, id: TaskId) -> Result<Option<Task>, DomainError> {
+        Ok(in_tenant(self.read().get(&id), tenant).cloned())
+    }
+
+    async fn get_by_user(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+    ) -> Result<Vec<Task>, DomainError> {
+        let mut tasks: Vec<Task> = self
+            .read()
+            .values()
+            .filter(|task| task.tenant_id == tenant && task.user_id == user_id)
+            .cloned()
+            .collect();
+        tasks.sort_by_key(|task| Reverse(task.created_at));
+        Ok(tasks)
+    }
+
+    async fn daily_counts(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        range: &StatsRange,
+    ) -> Result<Vec<DailyTaskCount>, DomainError> {
+        Ok(DailyTaskCount::tally(
+            range,
+            self.read()
+                .values()
+                .filter(|task| task.tenant_id == tenant && task.user_id == user_id),
+        ))
+    }
+
+    async fn usage(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<TaskUsage, DomainError> {
+        Ok(TaskUsage::tally(
+            since,
+            self.read()
+                .values()
+                .filter(|task| task.tenant_id == tenant && task.user_id == user_id),
+        ))
+    }
+
+    async fn search_all(
+        &self,
+        tenant: TenantId,
+        search: &TaskSearch,
+    ) -> Result<TaskPage, DomainError> {
+        Ok(search.page(self.read().values().filter(|task| task.tenant_id == tenant)))
+    }
+
+    async fn update(&self, entity: &Task) -> Result<(), DomainError> {
+        match self
+            .write()
+            .get_mut(&entity.id)
+            .filter(|task| task.tenant_id == entity.tenant_id)
+        {
+            Some(task) => {
+                *task = apply_update(task, entity);
+                Ok(())
+            }
+            None => Err(DomainError::not_found("Task", entity.id.to_string())),
+        }
+    }
+
+    async fn delete(&self, tenant: TenantId, id: TaskId) -> Result<(), DomainError> {
+        let mut tasks = self.write();
+        if in_tenant(tasks.get(&id), tenant).is_none() {
+            return Err(DomainError::not_found("Task", id.to_string()));
+        }
+        tasks.remove(&id);
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn begin(&self) -> Result<Box<dyn TaskUnitOfWork>, DomainError> {
+        Ok(Box::new(InMemoryTaskUnitOfWork {
+            repository: self.clone(),
+            pending: HashMap::new(),
+            priority_changes: Vec::new(),
+            quota_guard: None,
+        }))
+    }
+}
+
+/// Unit of work that buffers writes until commit
+///
+/// Writes are applied under a single lock on commit. Unlike Postgres there is no
+/// conflict detection between concurrent units of work: the last commit wins, and there is
+/// no job queue to enqueue to. Only counting quota usage locks, and then for every user.
+#[derive(Debug)]
+pub struct InMemoryTaskUnitOfWork {
+    repository: InMemoryTaskRepository,
+    /// Buffered writes; `None` marks a delete
+    pending: HashMap<TaskId, Option<Task>>,
+    priority_changes: Vec<PriorityChange>,
+    /// Quota lock taken by [`lock_usage`](TaskUnitOfWork::lock_usage), released when the
+    /// unit of work ends
+    quota_guard: Option<OwnedMutexGuard<()>>,
+}
+
+impl InMemoryTaskUnitOfWork {
+    /// The task as this unit of work sees it, including its own uncommitted writes, in
+    /// any tenant
+    fn current(&self, id: TaskId) -> Option<Task> {
+        match self.pending.get(&id) {
+            Some(pending) => pending.clone(),
+            None => self.repository.read().get(&id).cloned(),
+        }
+    }
+
+    /// [`current`](Self::current), if it belongs to `tenant`
+    fn current_in(&self, tenant: TenantId, id: TaskId) -> Option<Task> {
+        self.current(id).filter(|task| task.tenant_id == tenant)
+    }
+}
+
+#[async_trait]
+impl TaskUnitOfWork for InMemoryTaskUnitOfWork {
+    async fn create(&mut self, entity: Task) -> Result<Task, DomainError> {
+        if self.current(entity.id).is_some() {
+            return Err(duplicate_id());
+        }
+        self.pending.insert(entity.id, Some(entity.clone()));
+        Ok(entity)
+    }
+
+    async fn get(&mut self, tenant: TenantId, id: TaskId) -> Result<Option<Task>, DomainError> {
+        Ok(self.current_in(tenant, id))
+    }
+
+    async fn lock_usage(
+        &mut self,
+        tenant: TenantId,
+        user_id: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<TaskUsage, DomainError> {
+        if self.quota_guard.is_none() {
+            self.quota_guard = Some(Arc::clone(&self.repository.quota_lock).lock_owned().await);
+        }
+        let mut tasks = self.repository.read().clone();
+        for (id, task) in &self.pending {
+            match task {
+                Some(task) => tasks.insert(*id, task.clone()),
+                None => tasks.remove(id),
+            };
+        }
+        Ok(TaskUsage::tally(
+            since,
+            tasks
+                .values()
+                .filter(|task| task.tenant_id == tenant && task.user_id == user_id),
+        ))
+    }
+
+    async fn update(&mut self, entity: &Task) -> Result<(), DomainError> {
+        let Some(stored) = self.current_in(entity.tenant_id, entity.id) else {
+            return Err(DomainError::not_found("Task", entity.id.to_string()));
+        };
+        self.pending
+            .insert(entity.id, Some(apply_update(&stored, entity)));
+        Ok(())
+    }
+
+    async fn delete(&mut self, tenant: TenantId, id: TaskId) -> Result<(), DomainError> {
+        if self.current_in(tenant, id).is_none() {
+            return Err(DomainError::not_found("Task", id.to_string()));
+        }
+        self.pending.insert(id, None);
+        Ok(())
+    }
+
+    async fn enqueue(&mut self, _job: NewJob) -> Result<JobId, DomainError> {
+        Err(DomainError::external_error(
+            ExternalSystem::Database,
+            "Background jobs need the Postgres job queue",
+        ))
+    }
+
+    async fn record_priority_change(&mut self, change: &PriorityChange) -> Result<(), DomainError> {
+        if self.current_in(change.tenant_id, change.task_id).is_none() {
+            return Err(DomainError::not_found("Task", change.task_id.to_string()));
+        }
+        self.priority_changes.push(change.clone());
+        Ok(())
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), DomainError> {
+        let this = *self;
+        let mut tasks = this.repository.write();
+        for (id, task) in this.pending {
+            match task {
+                Some(task) => tasks.insert(id, task),
+                None => tasks.remove(&id),
+            };
+        }
+        this.repository
+            .priority_changes
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .extend(this.priority_changes);
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), DomainError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use std::{
+    collections::HashMap,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use crate::{
+    common::UserId,
+    domain::{
+        errors::DomainError, interfaces::user_repository::UserRepository, user::models::User,
+    },
+};
+
+/// Constraint reported on duplicate emails, matching the Postgres unique constraint name
+const EMAIL_CONSTRAINT: &str = "users_email_key";
+
+/// Process-local [`UserRepository`] for tests and examples
+///
+/// Follows the same contract as [`PostgresUserRepository`](super::user::PostgresUserRepository):
+/// a duplicate id or email is a `Conflict`. Clones share the same storage.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryUserRepository {
+    users: Arc<RwLock<HashMap<UserId, User>>>,
+}
+
+impl InMemoryUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn create(&self, entity: User) -> Result<User, DomainError> {
+        // A panic while holding the lock cannot leave a half-written user behind
+        let mut users = self.users.write().unwrap_or_else(PoisonError::into_inner);
+        if users.contains_key(&entity.id) {
+            return Err(DomainError::conflict("User already exists"));
+        }
+        if users.values().any(|user| user.email == entity.email) {
+            return Err(DomainError::Conflict {
+                message: format!(
+                    "duplicate key value violates unique constraint \"{EMAIL_CONSTRAINT}\""
+                ),
+                constraint: Some(EMAIL_CONSTRAINT.to_string()),
+            });
+        }
+        users.insert(entity.id, entity.clone());
+        Ok(entity)
+    }
+
+    async fn get(&self, id: UserId) -> Result<Option<User>, DomainError> {
+        Ok(self
+            .users
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&id)
+            .cloned())
+    }
+}
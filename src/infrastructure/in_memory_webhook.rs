@@ -0,0 +1,185 @@
+use async_trait::async_trait;
+use std::{
+    collections::HashMap,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use crate::{
+    common::TenantId,
+    domain::{
+        errors::DomainError,
+        interfaces::webhook_repository::WebhookRepository,
+        task::models::TaskEventType,
+        webhook::models::{Webhook, WebhookDelivery, WebhookId},
+    },
+};
+
+#[derive(Debug, Default)]
+struct Storage {
+    webhooks: HashMap<WebhookId, Webhook>,
+    /// Every delivery attempt, oldest first
+    deliveries: Vec<WebhookDelivery>,
+}
+
+/// Process-local [`WebhookRepository`] for tests and examples
+///
+/// Follows the same contract as
+/// [`PostgresWebhookRepository`](super::webhook::PostgresWebhookRepository): deleting a
+/// webhook deletes its deliveries, and recording a delivery for an unknown webhook is a
+/// `NotFound`. Clones share the same storage.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryWebhookRepository {
+    storage: Arc<RwLock<Storage>>,
+}
+
+impl InMemoryWebhookRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn sorted(webhooks: impl Iterator<Item = Webhook>) -> Vec<Webhook> {
+        let mut webhooks: Vec<Webhook> = webhooks.collect();
+        webhooks.sort_by_key(|webhook| (webhook.created_at, webhook.id.into_inner()));
+        webhooks
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for InMemoryWebhookRepository {
+    async fn create(&self, entity: Webhook) -> Result<Webhook, DomainError> {
+        // A panic while holding the lock cannot leave a half-written webhook behind
+        let mut storage = self.storage.write().unwrap_or_else(PoisonError::into_inner);
+        if storage.webhooks.contains_key(&entity.id) {
+            return Err(DomainError::conflict("Webhook already exists"));
+        }
+        storage.webhooks.insert(entity.id, entity.clone());
+        Ok(entity)
+    }
+
+    async fn get(&self, tenant: TenantId, id: WebhookId) -> Result<Option<Webhook>, DomainError> {
+        let storage = self.storage.read().unwrap_or_else(PoisonError::into_inner);
+        Ok(storage
+            .webhooks
+            .get(&id)
+            .filter(|webhook| webhook.tenant_id == tenant)
+            .cloned())
+    }
+
+    async fn list(&self, tenant: TenantId) -> Result<Vec<Webhook>, DomainError> {
+        let storage = self.storage.read().unwrap_or_else(PoisonError::into_inner);
+        Ok(Self::sorted(
+            storage
+                .webhooks
+                .values()
+                .filter(|webhook| webhook.tenant_id == tenant)
+                .cloned(),
+        ))
+    }
+
+    async fn count(&self, tenant: TenantId) -> Result<u64, DomainError> {
+        let storage = self.storage.read().unwrap_or_else(PoisonError::into_inner);
+        Ok(storage
+            .webhooks
+            .values()
+            .filter(|webhook| webhook.tenant_id == tenant)
+            .count() as u64)
+    }
+
+    async fn subscribed_to(
+        &self,
+        tenant: TenantId,
+        event_type: TaskEventType,
+    ) -> Result<Vec<Webhook>, DomainError> {
+        let storage = self.storage.read().unwrap_or_else(PoisonError::into_inner);
+        Ok(Self::sorted(
+            storage
+                .webhooks
+                .values()
+                .filter(|webhook| webhook.tenant_id == tenant && webhook.subscribes_to(event_type))
+                .cloned(),
+        ))
+    }
+
+    async fn update(&self, entity: &Webhook) -> Result<(), DomainError> {
+        let mut storage = self.storage.write().unwrap_or_else(PoisonError::into_inner);
+        match storage
+            .webhooks
+            .get_mut(&entity.id)
+            .filter(|webhook| webhook.tenant_id == entity.tenant_id)
+        {
+            Some(webhook) => {
+                // The secret is fixed at creation, as in the SQL repositories
+                *webhook = Webhook {
+                    secret: webhook.secret.clone(),
+                    created_at: webhook.created_at,
+                    ..entity.clone()
+                };
+                Ok(())
+            }
+            None => Err(DomainError::not_found("Webhook", entity.id.to_string())),
+        }
+    }
+
+    async fn delete(&self, tenant: TenantId, id: WebhookId) -> Result<(), DomainError> {
+        let mut storage = self.storage.write().unwrap_or_else(PoisonError::into_inner);
+        if !storage
+            .webhooks
+            .get(&id)
+            .is_some_and(|webhook| webhook.tenant_id == tenant)
+        {
+            return Err(DomainError::not_found("Webhook", id.to_string()));
+        }
+        storage.webhooks.remove(&id);
+        storage
+            .deliveries
+            .retain(|delivery| delivery.webhook_id != id);
+        Ok(())
+    }
+
+    async fn record_delivery(&self, delivery: &WebhookDelivery) -> Result<(), DomainError> {
+        let mut storage = self.storage.write().unwrap_or_else(PoisonError::into_inner);
+        if !storage.webhooks.contains_key(&delivery.webhook_id) {
+            return Err(DomainError::not_found(
+                "Webhook",
+                delivery.webhook_id.to_string(),
+            ));
+        }
+        storage.deliveries.push(delivery.clone());
+        Ok(())
+    }
+
+    async fn deliveries(
+        &self,
+        id: WebhookId,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, DomainError> {
+        let storage = self.storage.read().unwrap_or_else(PoisonError::into_inner);
+        Ok(storage
+            .deliveries
+            .iter()
+            .rev()
+            .filter(|delivery| delivery.webhook_id == id)
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn record_success(&self, id: WebhookId) -> Result<(), DomainError> {
+        let mut storage = self.storage.write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(webhook) = storage.webhooks.get_mut(&id) {
+            webhook.consecutive_failures = 0;
+        }
+        Ok(())
+    }
+
+    async fn record_failure(&self, id: WebhookId, disable_after: u32) -> Result<bool, DomainError> {
+        let mut storage = self.storage.write().unwrap_or_else(PoisonError::into_inner);
+        let webhook = storage
+            .webhooks
+            .get_mut(&id)
+            .ok_or_else(|| DomainError::not_found("Webhook", id.to_string()))?;
+        webhook.consecutive_failures = webhook.consecutive_failures.saturating_add(1);
+        webhook.active = webhook.active && webhook.consecutive_failures < disable_after;
+        Ok(webhook.active)
+    }
+}
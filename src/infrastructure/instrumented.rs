@@ -0,0 +1,381 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::{fmt::Debug, future::Future, sync::Arc, time::Instant};
+use tracing::Instrument;
+
+use crate::{
+    common::{TenantId, UserId},
+    domain::{
+        errors::DomainError,
+        interfaces::task_repository::{TaskRepository, TaskUnitOfWork},
+        job::models::{JobId, NewJob},
+        quota::models::TaskUsage,
+        task::models::{
+            DailyTaskCount, PriorityChange, StatsRange, Task, TaskId, TaskPage, TaskSearch,
+        },
+    },
+};
+
+/// Repository decorator timing every call of the repository it wraps
+///
+/// Each call runs in a `repository_call` span naming the repository and the method, its
+/// latency is recorded in `repository_call_duration_seconds` and a failure is counted in
+/// `repository_errors_total` with the [`kind`](DomainError::kind) of the error. The
+/// backend's own query metrics still apply inside; these measure what callers wait for,
+/// retries included.
+///
+/// Any repository trait can be wrapped: implement it for `Instrumented<dyn Trait>`,
+/// passing each call through [`observe`](Self::observe), as done for [`TaskRepository`].
+#[derive(Debug)]
+pub struct Instrumented<R: ?Sized> {
+    inner: Arc<R>,
+    /// `repository` label of the metrics, e.g. `task`
+    repository: &'static str,
+}
+
+/// [`TaskRepository`] recording the metrics and span of every call, labelled `task`
+pub type InstrumentedTaskRepository = Instrumented<dyn TaskRepository>;
+
+impl<R: ?Sized> Instrumented<R> {
+    pub fn new(inner: Arc<R>, repository: &'static str) -> Self {
+        Self { inner, repository }
+    }
+
+    /// Run `call`, the `method` of the inner repository, recording its span and metrics
+    pub async fn observe<T>(
+        &self,
+        method: &'static str,
+        call: impl Future<Output = Result<T, DomainError>>,
+    ) -> Result<T, DomainError> {
+        observe(self.repository, method, call).await
+    }
+}
+
+impl InstrumentedTaskRepository {
+    /// Instrument the task repository `inner`
+    pub fn task(inner: Arc<dyn TaskRepository>) -> Self {
+        Self::new(inner, "task")
+    }
+}
+
+async fn observe<T>(
+    repository: &'static str,
+    method: &'static str,
+    call: impl Future<Output = Result<T, DomainError>>,
+) -> Result<T, DomainError> {
+    let started = Instant::now();
+    let result = call
+        .instrument(tracing::info_span!("repository_call", repository, method))
+        .await;
+
+    metrics::histogram!(
+        "repository_call_duration_seconds",
+        "repository" => repository,
+        "method" => method
+    )
+    .record(started.elapsed().as_secs_f64());
+    if let Err(error) = &result {
+        metrics::counter!(
+            "repository_errors_total",
+            "repository" => repository,
+            "method" => method,
+            "kind" => error.kind()
+        )
+        .increment(1);
+    }
+
+    result
+}
+
+#[async_trait]
+impl TaskRepository for InstrumentedTaskRepository {
+    async fn create(&self, entity: Task) -> Result<Task, DomainError> {
+        self.observe("create", self.inner.create(entity)).await
+    }
+
+    async fn get(&self, tenant: TenantId, id: TaskId) -> Result<Option<Task>, DomainError> {
+        self.observe("get", self.inner.get(tenant, id)).await
+    }
+
+    async fn get_by_user(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+    ) -> Result<Vec<Task>, DomainError> {
+        self.observe("get_by_user", self.inner.get_by_user(tenant, user_id))
+            .await
+    }
+
+    async fn daily_counts(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        range: &StatsRange,
+    ) -> Result<Vec<DailyTaskCount>, DomainError> {
+        self.observe(
+            "daily_counts",
+            self.inner.daily_counts(tenant, user_id, range),
+        )
+        .await
+    }
+
+    async fn usage(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<TaskUsage, DomainError> {
+        self.observe("usage", self.inner.usage(tenant, user_id, since))
+            .await
+    }
+
+    async fn search_all(
+        &self,
+        tenant: TenantId,
+        search: &TaskSearch,
+    ) -> Result<TaskPage, DomainError> {
+        self.observe("search_all", self.inner.search_all(tenant, search))
+            .await
+    }
+
+    async fn update(&self, entity: &Task) -> Result<(), DomainError> {
+        self.observe("update", self.inner.update(entity)).await
+    }
+
+    async fn delete(&self, tenant: TenantId, id: TaskId) -> Result<(), DomainError> {
+        self.observe("delete", self.inner.delete(tenant, id)).await
+    }
+
+    async fn health_check(&self) -> Result<(), DomainError> {
+        self.observe("health_check", self.inner.health_check())
+            .await
+    }
+
+    async fn begin(&self) -> Result<Box<dyn TaskUnitOfWork>, DomainError> {
+        let inner = self.observe("begin", self.inner.begin()).await?;
+        Ok(Box::new(InstrumentedTaskUnitOfWork {
+            inner,
+            repository: self.repository,
+        }))
+    }
+}
+
+/// Unit of work of an [`InstrumentedTaskRepository`], recording each of its calls too
+///
+/// Methods are labelled with a `unit_of_work_` prefix, e.g. `unit_of_work_commit`, apart
+/// from the direct calls of the same name.
+pub struct InstrumentedTaskUnitOfWork {
+    inner: Box<dyn TaskUnitOfWork>,
+    repository: &'static str,
+}
+
+impl Debug for InstrumentedTaskUnitOfWork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InstrumentedTaskUnitOfWork")
+            .field("repository", &self.repository)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl TaskUnitOfWork for InstrumentedTaskUnitOfWork {
+    async fn create(&mut self, entity: Task) -> Result<Task, DomainError> {
+        observe(
+            self.repository,
+            "unit_of_work_create",
+            self.inner.create(entity),
+        )
+        .await
+    }
+
+    async fn get(&mut self, tenant: TenantId, id: TaskId) -> Result<Option<Task>, DomainError> {
+        observe(
+            self.repository,
+            "unit_of_work_get",
+            self.inner.get(tenant, id),
+        )
+        .await
+    }
+
+    async fn lock_usage(
+        &mut self,
+        tenant: TenantId,
+        user_id: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<TaskUsage, DomainError> {
+        observe(
+            self.repository,
+            "unit_of_work_lock_usage",
+            self.inner.lock_usage(tenant, user_id, since),
+        )
+        .await
+    }
+
+    async fn update(&mut self, entity: &Task) -> Result<(), DomainError> {
+        observe(
+            self.repository,
+            "unit_of_work_update",
+            self.inner.update(entity),
+        )
+        .await
+    }
+
+    async fn delete(&mut self, tenant: TenantId, id: TaskId) -> Result<(), DomainError> {
+        observe(
+            self.repository,
+            "unit_of_work_delete",
+            self.inner.delete(tenant, id),
+        )
+        .await
+    }
+
+    async fn enqueue(&mut self, job: NewJob) -> Result<JobId, DomainError> {
+        observe(
+            self.repository,
+            "unit_of_work_enqueue",
+            self.inner.enqueue(job),
+        )
+        .await
+    }
+
+    async fn record_priority_change(&mut self, change: &PriorityChange) -> Result<(), DomainError> {
+        observe(
+            self.repository,
+            "unit_of_work_record_priority_change",
+            self.inner.record_priority_change(change),
+        )
+        .await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), DomainError> {
+        observe(self.repository, "unit_of_work_commit", self.inner.commit()).await
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), DomainError> {
+        observe(
+            self.repository,
+            "unit_of_work_rollback",
+            self.inner.rollback(),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder, Snapshotter};
+
+    use super::*;
+    use crate::{
+        domain::task::models::TaskPriority, infrastructure::in_memory_task::InMemoryTaskRepository,
+    };
+
+    /// Recorded metrics as `(name, method label, kind label, value)`
+    fn snapshot(snapshotter: &Snapshotter) -> Vec<(String, String, Option<String>, DebugValue)> {
+        snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| {
+                let label = |name: &str| {
+                    key.key()
+                        .labels()
+                        .find(|label| label.key() == name)
+                        .map(|label| label.value().to_string())
+                };
+                (
+                    key.key().name().to_string(),
+                    label("method").unwrap_or_default(),
+                    label("kind"),
+                    value,
+                )
+            })
+            .collect()
+    }
+
+    fn task() -> Task {
+        Task::new(
+            TenantId::default(),
+            UserId::new(),
+            "Measure it".to_string(),
+            None,
+            TaskPriority::Medium,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_calls_record_their_latency_by_method() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+        let repository = InstrumentedTaskRepository::task(Arc::new(InMemoryTaskRepository::new()));
+
+        let created = repository.create(task()).await.unwrap();
+        let found = repository.get(created.tenant_id, created.id).await.unwrap();
+
+        assert_eq!(found.map(|task| task.id), Some(created.id));
+        let metrics = snapshot(&snapshotter);
+        for method in ["create", "get"] {
+            assert!(
+                metrics.iter().any(|(name, label, _, value)| {
+                    name == "repository_call_duration_seconds"
+                        && label == method
+                        && matches!(value, DebugValue::Histogram(values) if values.len() == 1)
+                }),
+                "Latency of {method} should be recorded: {metrics:?}"
+            );
+        }
+        assert!(
+            !metrics
+                .iter()
+                .any(|(name, ..)| name == "repository_errors_total"),
+            "Successful calls should not count errors"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_calls_are_counted_by_error_kind() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+        let repository = InstrumentedTaskRepository::task(Arc::new(InMemoryTaskRepository::new()));
+
+        let result = repository.update(&task()).await;
+
+        assert!(matches!(result, Err(DomainError::NotFound { .. })));
+        assert!(
+            snapshot(&snapshotter).contains(&(
+                "repository_errors_total".to_string(),
+                "update".to_string(),
+                Some("not_found".to_string()),
+                DebugValue::Counter(1)
+            )),
+            "The missing task should be counted as a not_found error"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unit_of_work_calls_are_recorded() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+        let repository = InstrumentedTaskRepository::task(Arc::new(InMemoryTaskRepository::new()));
+
+        let mut unit_of_work = repository.begin().await.unwrap();
+        unit_of_work.create(task()).await.unwrap();
+        unit_of_work.commit().await.unwrap();
+
+        let methods: Vec<String> = snapshot(&snapshotter)
+            .into_iter()
+            .filter(|(name, ..)| name == "repository_call_duration_seconds")
+            .map(|(_, method, ..)| method)
+            .collect();
+        for method in ["begin", "unit_of_work_create", "unit_of_work_commit"] {
+            assert!(
+                methods.iter().any(|m| m == method),
+                "{method} in {methods:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,504 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json, PgExecutor, PgPool};
+use tokio::task::{JoinHandle, JoinSet};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use super::retry::RetryPolicy;
+use crate::{
+    config::JobsConfig,
+    domain::{
+        errors::DomainError,
+        interfaces::job_repository::JobRepository,
+        job::models::{Job, JobFilter, JobId, JobPage, JobStatus, NewJob},
+    },
+};
+
+/// Longest error message kept on a failed job
+const MAX_ERROR_CHARS: usize = 1024;
+
+/// Runs the jobs of one kind
+///
+/// Register handlers with [`JobRunner::with_handler`]. A job may run more than once, e.g.
+/// when its worker dies before recording the outcome, so handlers must be idempotent.
+#[async_trait]
+pub trait JobHandler: Send + Sync {
+    /// Kind of the jobs this handler runs, as passed to [`NewJob::new`]
+    fn kind(&self) -> &'static str;
+
+    /// Run `job`; an error schedules a retry, or marks the job dead after its last attempt
+    async fn handle(&self, job: &Job) -> Result<(), DomainError>;
+}
+
+/// Insert `job`, as part of whatever transaction `executor` belongs to
+pub(super) async fn insert_job(
+    executor: impl PgExecutor<'_>,
+    job: &NewJob,
+) -> Result<JobId, sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO jobs (id, kind, payload, run_at)
+        VALUES ($1, $2, $3, $4)
+        "#,
+    )
+    .bind(job.id.into_inner())
+    .bind(&job.kind)
+    .bind(Json(&job.payload))
+    .bind(job.run_at)
+    .execute(executor)
+    .await?;
+
+    Ok(job.id)
+}
+
+/// The `jobs` table: enqueueing, claiming, and recording how attempts ended
+///
+/// Outcomes are only recorded while the worker still holds the job, so a worker whose
+/// lock timed out cannot overwrite what the worker that took over recorded.
+#[derive(Debug, Clone)]
+pub struct PostgresJobQueue {
+    pool: PgPool,
+}
+
+impl PostgresJobQueue {
+    #[must_use]
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Enqueue `job` on its own; use [`TaskUnitOfWork::enqueue`] to enqueue it together
+    /// with task writes
+    ///
+    /// [`TaskUnitOfWork::enqueue`]: crate::domain::interfaces::task_repository::TaskUnitOfWork::enqueue
+    #[tracing::instrument(skip_all, fields(job_id = %job.id, kind = %job.kind))]
+    pub async fn enqueue(&self, job: NewJob) -> Result<JobId, DomainError> {
+        Ok(insert_job(&self.pool, &job).await?)
+    }
+
+    /// Lock up to `limit` due jobs of `kinds` for `worker_id`, counting an attempt for each
+    ///
+    /// Jobs are due once their `run_at` has passed, or when the worker running them has
+    /// held them longer than `lock_timeout`. Rows locked by a concurrent claim are
+    /// skipped, so workers never wait for each other or claim the same job.
+    #[tracing::instrument(skip(self, kinds))]
+    pub async fn claim(
+        &self,
+        worker_id: &str,
+        kinds: &[&str],
+        limit: u32,
+        lock_timeout: Duration,
+    ) -> Result<Vec<Job>, DomainError> {
+        let rows = sqlx::query_as::<_, JobRow>(
+            r#"
+            UPDATE jobs
+            SET status = 'running', locked_by = $1, locked_at = NOW(),
+                attempts = attempts + 1, updated_at = NOW()
+            WHERE id IN (
+                SELECT id
+                FROM jobs
+                WHERE kind = ANY($2)
+                  AND ((status = 'pending' AND run_at <= NOW())
+                    OR (status = 'running' AND locked_at < NOW() - make_interval(secs => $4)))
+                ORDER BY run_at
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, kind, payload, status, run_at, attempts, last_error, locked_by, locked_at, created_at, updated_at
+            "#,
+        )
+        .bind(worker_id)
+        .bind(kinds)
+        .bind(i64::from(limit))
+        .bind(lock_timeout.as_secs_f64())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(Job::try_from).collect()
+    }
+
+    /// Delete a job that succeeded
+    #[tracing::instrument(skip(self), fields(job_id = %id))]
+    pub async fn complete(&self, id: JobId, worker_id: &str) -> Result<(), DomainError> {
+        sqlx::query("DELETE FROM jobs WHERE id = $1 AND locked_by = $2")
+            .bind(id.into_inner())
+            .bind(worker_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Release a job whose attempt failed, to run again at `retry_at` or, without one,
+    /// to stay dead
+    #[tracing::instrument(skip(self, error), fields(job_id = %id))]
+    pub async fn fail(
+        &self,
+        id: JobId,
+        worker_id: &str,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), DomainError> {
+        let error: String = error.chars().take(MAX_ERROR_CHARS).collect();
+        let status = if retry_at.is_some() {
+            JobStatus::Pending
+        } else {
+            JobStatus::Dead
+        };
+        sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = $3, run_at = COALESCE($4, run_at), last_error = $5,
+                locked_by = NULL, locked_at = NULL, updated_at = NOW()
+            WHERE id = $1 AND locked_by = $2
+            "#,
+        )
+        .bind(id.into_inner())
+        .bind(worker_id)
+        .bind(status.as_str())
+        .bind(retry_at)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Delete dead jobs that last failed before `older_than`, returning how many
+    #[tracing::instrument(skip(self))]
+    pub async fn purge_dead(&self, older_than: DateTime<Utc>) -> Result<u64, DomainError> {
+        let result = sqlx::query("DELETE FROM jobs WHERE status = $1 AND updated_at < $2")
+            .bind(JobStatus::Dead.as_str())
+            .bind(older_than)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl JobRepository for PostgresJobQueue {
+    #[tracing::instrument(skip(self), fields(job_id = %id))]
+    async fn get(&self, id: JobId) -> Result<Option<Job>, DomainError> {
+        let row = sqlx::query_as::<_, JobRow>(
+            r#"
+            SELECT id, kind, payload, status, run_at, attempts, last_error, locked_by, locked_at, created_at, updated_at
+            FROM jobs
+            WHERE id = $1
+            "#,
+        )
+        .bind(id.into_inner())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(Job::try_from).transpose()
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list(
+        &self,
+        filter: &JobFilter,
+        limit: u32,
+        offset: u64,
+    ) -> Result<JobPage, DomainError> {
+        // A job failed when its latest attempt did, unless it is being attempted again
+        const MATCHES: &str = r#"
+            ($1::TEXT IS NULL OR status = $1)
+            AND (NOT $2 OR (last_error IS NOT NULL AND status <> 'running'))
+            AND ($3::TEXT IS NULL OR kind = $3)
+        "#;
+        let status = filter.status.map(JobStatus::as_str);
+        let offset = i64::try_from(offset).unwrap_or(i64::MAX);
+
+        let rows = sqlx::query_as::<_, JobRow>(&format!(
+            r#"
+            SELECT id, kind, payload, status, run_at, attempts, last_error, locked_by, locked_at, created_at, updated_at
+            FROM jobs
+            WHERE {MATCHES}
+            ORDER BY created_at, id
+            LIMIT $4 OFFSET $5
+            "#
+        ))
+        .bind(status)
+        .bind(filter.failed)
+        .bind(filter.kind.as_deref())
+        .bind(i64::from(limit))
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+        let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM jobs WHERE {MATCHES}"))
+            .bind(status)
+            .bind(filter.failed)
+            .bind(filter.kind.as_deref())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(JobPage {
+            jobs: rows
+                .into_iter()
+                .map(Job::try_from)
+                .collect::<Result<_, _>>()?,
+            total: u64::try_from(total).unwrap_or_default(),
+        })
+    }
+
+    #[tracing::instrument(skip(self), fields(job_id = %id))]
+    async fn retry(&self, id: JobId) -> Result<bool, DomainError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = 'pending', run_at = NOW(), attempts = 0,
+                locked_by = NULL, locked_at = NULL, updated_at = NOW()
+            WHERE id = $1 AND status = 'dead'
+            "#,
+        )
+        .bind(id.into_inner())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(skip(self), fields(job_id = %id))]
+    async fn delete(&self, id: JobId) -> Result<bool, DomainError> {
+        let result = sqlx::query("DELETE FROM jobs WHERE id = $1 AND status <> 'running'")
+            .bind(id.into_inner())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// Claims due jobs and runs each with the [`JobHandler`] registered for its kind
+///
+/// Jobs are claimed in batches and a batch runs concurrently. A failed attempt is retried
+/// with exponential backoff until the job has used up `max_attempts`, when it is marked
+/// dead and kept for inspection. Jobs of kinds without a handler are left for other
+/// workers. Any number of runners, in any number of processes, can share the table.
+pub struct JobRunner {
+    queue: PostgresJobQueue,
+    handlers: HashMap<&'static str, Arc<dyn JobHandler>>,
+    worker_id: String,
+    batch_size: u32,
+    poll_interval: Duration,
+    lock_timeout: Duration,
+    retry_policy: RetryPolicy,
+}
+
+impl std::fmt::Debug for JobRunner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobRunner")
+            .field("kinds", &self.kinds())
+            .field("worker_id", &self.worker_id)
+            .field("batch_size", &self.batch_size)
+            .field("poll_interval", &self.poll_interval)
+            .field("lock_timeout", &self.lock_timeout)
+            .field("retry_policy", &self.retry_policy)
+            .finish_non_exhaustive()
+    }
+}
+
+impl JobRunner {
+    #[must_use]
+    pub fn new(pool: PgPool, config: &JobsConfig) -> Self {
+        Self {
+            queue: PostgresJobQueue::new(pool),
+            handlers: HashMap::new(),
+            // Unique per runner, so two runners in one process do not share locks
+            worker_id: format!("{}-{}", std::process::id(), Uuid::new_v4().simple()),
+            batch_size: config.batch_size.max(1),
+            poll_interval: Duration::from_millis(config.poll_interval_ms),
+            lock_timeout: Duration::from_secs(config.lock_timeout_secs),
+            retry_policy: RetryPolicy {
+                max_attempts: config.max_attempts.max(1),
+                base_delay: Duration::from_millis(config.retry_base_delay_ms),
+                max_delay: Duration::from_millis(config.retry_max_delay_ms),
+            },
+        }
+    }
+
+    /// Run the jobs of `handler.kind()` with `handler`, replacing any earlier handler
+    #[must_use]
+    pub fn with_handler(mut self, handler: Arc<dyn JobHandler>) -> Self {
+        self.handlers.insert(handler.kind(), handler);
+        self
+    }
+
+    #[must_use]
+    pub fn worker_id(&self) -> &str {
+        &self.worker_id
+    }
+
+    fn kinds(&self) -> Vec<&'static str> {
+        let mut kinds: Vec<&'static str> = self.handlers.keys().copied().collect();
+        kinds.sort_unstable();
+        kinds
+    }
+
+    /// Claim one batch of due jobs and run it to completion, returning how many ran
+    pub async fn run_once(&self) -> Result<usize, DomainError> {
+        if self.handlers.is_empty() {
+            return Ok(0);
+        }
+        let jobs = self
+            .queue
+            .claim(
+                &self.worker_id,
+                &self.kinds(),
+                self.batch_size,
+                self.lock_timeout,
+            )
+            .await?;
+        let claimed = jobs.len();
+
+        let mut running = JoinSet::new();
+        for job in jobs {
+            let Some(handler) = self.handlers.get(job.kind.as_str()).cloned() else {
+                continue;
+            };
+            let queue = self.queue.clone();
+            let worker_id = self.worker_id.clone();
+            let retry_policy = self.retry_policy;
+            running.spawn(async move {
+                execute(&queue, &worker_id, handler.as_ref(), retry_policy, job).await;
+            });
+        }
+        while let Some(joined) = running.join_next().await {
+            if let Err(e) = joined {
+                // The job stays running until its lock times out and it is claimed again
+                error!(error = %e, "Job handler panicked");
+            }
+        }
+
+        Ok(claimed)
+    }
+
+    /// Run jobs until `shutdown` is cancelled
+    ///
+    /// Jobs already claimed when that happens run to completion first, so awaiting this
+    /// (or the handle from [`spawn`](Self::spawn)) drains the runner.
+    pub async fn run(self, shutdown: CancellationToken) {
+        info!(
+            worker_id = %self.worker_id,
+            kinds = ?self.kinds(),
+            "Running background jobs"
+        );
+        while !shutdown.is_cancelled() {
+            let idle = match self.run_once().await {
+                Ok(ran) => ran < self.batch_size as usize,
+                Err(e) => {
+                    warn!(error = %e, "Failed to claim jobs");
+                    true
+                }
+            };
+            if idle {
+                tokio::select! {
+                    () = shutdown.cancelled() => {}
+                    () = tokio::time::sleep(self.poll_interval) => {}
+                }
+            }
+        }
+        info!(worker_id = %self.worker_id, "Background jobs stopped");
+    }
+
+    /// Run jobs in a background task until `shutdown` is cancelled
+    #[must_use]
+    pub fn spawn(self, shutdown: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(self.run(shutdown))
+    }
+}
+
+/// Run one claimed job and record how it ended
+async fn execute(
+    queue: &PostgresJobQueue,
+    worker_id: &str,
+    handler: &dyn JobHandler,
+    retry_policy: RetryPolicy,
+    job: Job,
+) {
+    let result = handler.handle(&job).await;
+    let (outcome, recorded) = match result {
+        Ok(()) => {
+            debug!(job_id = %job.id, kind = %job.kind, attempt = job.attempts, "Job succeeded");
+            ("success", queue.complete(job.id, worker_id).await)
+        }
+        Err(e) if job.attempts >= retry_policy.max_attempts => {
+            error!(
+                job_id = %job.id,
+                kind = %job.kind,
+                attempts = job.attempts,
+                error = %e,
+                "Job failed on its last attempt and is now dead"
+            );
+            (
+                "dead",
+                queue.fail(job.id, worker_id, &e.to_string(), None).await,
+            )
+        }
+        Err(e) => {
+            let delay = retry_policy.delay(job.attempts);
+            let retry_at = Utc::now() + chrono::Duration::from_std(delay).unwrap_or_default();
+            warn!(
+                job_id = %job.id,
+                kind = %job.kind,
+                attempt = job.attempts,
+                error = %e,
+                retry_in_ms = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX),
+                "Job failed, retrying"
+            );
+            (
+                "retry",
+                queue
+                    .fail(job.id, worker_id, &e.to_string(), Some(retry_at))
+                    .await,
+            )
+        }
+    };
+    metrics::counter!("jobs_processed_total", "kind" => job.kind.clone(), "outcome" => outcome)
+        .increment(1);
+
+    if let Err(e) = recorded {
+        // The lock times out and the job runs again
+        warn!(job_id = %job.id, error = %e, "Failed to record job outcome");
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct JobRow {
+    id: Uuid,
+    kind: String,
+    payload: Json<serde_json::Value>,
+    status: String,
+    run_at: DateTime<Utc>,
+    attempts: i32,
+    last_error: Option<String>,
+    locked_by: Option<String>,
+    locked_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<JobRow> for Job {
+    type Error = DomainError;
+
+    fn try_from(row: JobRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: JobId::from(row.id),
+            kind: row.kind,
+            payload: row.payload.0,
+            status: row.status.parse()?,
+            run_at: row.run_at,
+            attempts: u32::try_from(row.attempts).unwrap_or_default(),
+            last_error: row.last_error,
+            locked_by: row.locked_by,
+            locked_at: row.locked_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
@@ -10,7 +10,8 @@ use tracing::{debug, error, info};
 use crate::{
     config::KafkaConfig,
     domain::{
-        errors::DomainError, interfaces::event_producer::EventProducer,
+        errors::{DomainError, ExternalSystem},
+        interfaces::event_producer::EventProducer,
         task::models::events::TaskEvent,
     },
 };
@@ -32,17 +33,29 @@ impl KafkaEventService {
             config.bootstrap_servers
         );
 
-        let producer: FutureProducer = ClientConfig::new()
+        let mut client_config = ClientConfig::new();
+        client_config
             .set("bootstrap.servers", &config.bootstrap_servers)
             .set("client.id", &config.client_id)
             .set("message.timeout.ms", "10000")
             .set("acks", "all")
             .set("retries", "3")
-            .set("retry.backoff.ms", "1000")
-            .create()
-            .map_err(|e| {
-                DomainError::external_error(format!("Failed to create Kafka producer: {e}"))
-            })?;
+            .set("retry.backoff.ms", "1000");
+
+        if let (Some(username), Some(password)) = (&config.sasl_username, &config.sasl_password) {
+            client_config
+                .set("security.protocol", "SASL_SSL")
+                .set("sasl.mechanisms", "PLAIN")
+                .set("sasl.username", username)
+                .set("sasl.password", password);
+        }
+
+        let producer: FutureProducer = client_config.create().map_err(|e| {
+            DomainError::external_error(
+                ExternalSystem::Kafka,
+                format!("Failed to create Kafka producer: {e}"),
+            )
+        })?;
 
         info!(
             "Kafka producer initialized successfully for topic: {}",
@@ -58,9 +71,16 @@ impl KafkaEventService {
 
 #[async_trait]
 impl EventProducer for KafkaEventService {
+    fn name(&self) -> &'static str {
+        "kafka"
+    }
+
     async fn publish_task_event(&self, event: TaskEvent) -> Result<(), DomainError> {
         let event_json = serde_json::to_string(&event).map_err(|e| {
-            DomainError::external_error(format!("Failed to serialize task event: {e}"))
+            DomainError::external_error(
+                ExternalSystem::Kafka,
+                format!("Failed to serialize task event: {e}"),
+            )
         })?;
 
         let event_id = event.event_id.to_string();
@@ -96,9 +116,10 @@ impl EventProducer for KafkaEventService {
                     "Failed to publish task event to Kafka: event_id={}, error={}",
                     event_id, e
                 );
-                Err(DomainError::external_error(format!(
-                    "Failed to publish event to Kafka: {e}"
-                )))
+                Err(DomainError::external_error(
+                    ExternalSystem::Kafka,
+                    format!("Failed to publish event to Kafka: {e}"),
+                ))
             }
         }
     }
@@ -0,0 +1,44 @@
+use tracing_subscriber::{filter::ParseError, reload, EnvFilter, Registry};
+
+/// `EnvFilter` layer whose directives can be swapped through a [`LogLevelHandle`]
+pub type ReloadableFilter = reload::Layer<EnvFilter, Registry>;
+
+/// Errors returned when changing the active log filter
+#[derive(Debug, thiserror::Error)]
+pub enum LogLevelError {
+    #[error("Invalid log directive: {0}")]
+    InvalidDirective(#[from] ParseError),
+    #[error("Failed to reload log filter: {0}")]
+    Reload(#[from] reload::Error),
+}
+
+/// Reads and replaces the `EnvFilter` installed with [`reloadable_filter`]
+#[derive(Debug, Clone)]
+pub struct LogLevelHandle(reload::Handle<EnvFilter, Registry>);
+
+/// Wrap `filter` so its directives can be changed at runtime
+///
+/// The layer must be the first one added to the `Registry`; the handle stops working once the
+/// layer is dropped.
+pub fn reloadable_filter(filter: EnvFilter) -> (ReloadableFilter, LogLevelHandle) {
+    let (layer, handle) = reload::Layer::new(filter);
+    (layer, LogLevelHandle(handle))
+}
+
+impl LogLevelHandle {
+    /// The active filter directives, e.g. `rust_service_template=debug,sqlx=info`
+    pub fn current(&self) -> Result<String, LogLevelError> {
+        Ok(self.0.with_current(ToString::to_string)?)
+    }
+
+    /// Replace the active filter, returning the directives it replaced
+    ///
+    /// The new directives are parsed before anything changes, so an invalid value leaves the
+    /// current filter in place.
+    pub fn set(&self, directives: &str) -> Result<String, LogLevelError> {
+        let filter = EnvFilter::try_new(directives)?;
+        let previous = self.current()?;
+        self.0.reload(filter)?;
+        Ok(previous)
+    }
+}
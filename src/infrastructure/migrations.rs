@@ -0,0 +1,247 @@
+use sqlx::{
+    migrate::{Migration, MigrationType, Migrator},
+    PgPool,
+};
+
+use crate::config::UserIdMode;
+
+/// Postgres migrations embedded from `migrations/`
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+/// Foreign key from `tasks.user_id` to `users.id`, present only with `user_ids = registered`
+pub const TASK_OWNER_CONSTRAINT: &str = "tasks_user_id_fkey";
+
+/// Key of the advisory lock serializing [`apply_user_id_mode`] across replicas starting
+/// together
+const USER_ID_MODE_LOCK: i64 = 0x7573_6572_5f69_6473;
+
+/// Where a migration stands in a database compared to this binary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    /// Applied, but the file changed since
+    ChecksumMismatch,
+    /// Started but did not finish; the schema may be half-migrated
+    Failed,
+    /// Applied by a binary that knows a migration this one does not
+    Unknown,
+}
+
+impl MigrationState {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Applied => "applied",
+            Self::Pending => "pending",
+            Self::ChecksumMismatch => "checksum mismatch",
+            Self::Failed => "failed",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub state: MigrationState,
+}
+
+/// A row of sqlx's `_sqlx_migrations` table
+#[derive(Debug, sqlx::FromRow)]
+struct AppliedMigration {
+    version: i64,
+    description: String,
+    checksum: Vec<u8>,
+    success: bool,
+}
+
+/// Apply every pending migration
+pub async fn run(pool: &PgPool) -> Result<(), sqlx::migrate::MigrateError> {
+    MIGRATOR.run(pool).await
+}
+
+/// Add or drop the foreign key from tasks to their owners to match `mode`
+///
+/// Run after the migrations, wherever they run: a migration cannot depend on configuration.
+/// The key is added `NOT VALID`, so tasks stored while user ids were external keep owners
+/// that were never registered; only rows written from then on are checked.
+pub async fn apply_user_id_mode(pool: &PgPool, mode: UserIdMode) -> sqlx::Result<()> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(USER_ID_MODE_LOCK)
+        .execute(&mut *tx)
+        .await?;
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS (
+             SELECT 1 FROM pg_constraint WHERE conrelid = 'tasks'::regclass AND conname = $1
+         )",
+    )
+    .bind(TASK_OWNER_CONSTRAINT)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let statement = match (mode, exists) {
+        (UserIdMode::Registered, false) => format!(
+            "ALTER TABLE tasks ADD CONSTRAINT {TASK_OWNER_CONSTRAINT} \
+             FOREIGN KEY (user_id) REFERENCES users (id) NOT VALID"
+        ),
+        (UserIdMode::External, true) => {
+            format!("ALTER TABLE tasks DROP CONSTRAINT {TASK_OWNER_CONSTRAINT}")
+        }
+        _ => return tx.commit().await,
+    };
+    tracing::info!(?mode, "Applying user id mode: {statement}");
+    sqlx::query(&statement).execute(&mut *tx).await?;
+    tx.commit().await
+}
+
+/// Compare the database's migration history with the migrations embedded in this binary
+///
+/// Read-only: a database that was never migrated reports every migration as pending
+/// rather than creating sqlx's bookkeeping table.
+pub async fn status(pool: &PgPool) -> sqlx::Result<Vec<MigrationStatus>> {
+    let has_history: bool =
+        sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+            .fetch_one(pool)
+            .await?;
+
+    let applied = if has_history {
+        sqlx::query_as::<_, AppliedMigration>(
+            "SELECT version, description, checksum, success FROM _sqlx_migrations ORDER BY version",
+        )
+        .fetch_all(pool)
+        .await?
+    } else {
+        Vec::new()
+    };
+
+    Ok(compare(&MIGRATOR.migrations, &applied))
+}
+
+/// Whether every migration is applied and matches this binary
+pub fn is_up_to_date(statuses: &[MigrationStatus]) -> bool {
+    statuses
+        .iter()
+        .all(|status| status.state == MigrationState::Applied)
+}
+
+fn compare(migrations: &[Migration], applied: &[AppliedMigration]) -> Vec<MigrationStatus> {
+    let mut statuses: Vec<MigrationStatus> = migrations
+        .iter()
+        // Down migrations share the version of their up migration
+        .filter(|migration| !matches!(migration.migration_type, MigrationType::ReversibleDown))
+        .map(|migration| {
+            let state = match applied.iter().find(|row| row.version == migration.version) {
+                None => MigrationState::Pending,
+                Some(row) if !row.success => MigrationState::Failed,
+                Some(row) if row.checksum != *migration.checksum => {
+                    MigrationState::ChecksumMismatch
+                }
+                Some(_) => MigrationState::Applied,
+            };
+            MigrationStatus {
+                version: migration.version,
+                description: migration.description.to_string(),
+                state,
+            }
+        })
+        .collect();
+
+    statuses.extend(
+        applied
+            .iter()
+            .filter(|row| {
+                !migrations
+                    .iter()
+                    .any(|migration| migration.version == row.version)
+            })
+            .map(|row| MigrationStatus {
+                version: row.version,
+                description: row.description.clone(),
+                state: MigrationState::Unknown,
+            }),
+    );
+    statuses.sort_by_key(|status| status.version);
+    statuses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::borrow::Cow;
+
+    fn migration(version: i64, sql: &'static str) -> Migration {
+        Migration::new(
+            version,
+            Cow::Borrowed("test migration"),
+            MigrationType::Simple,
+            Cow::Borrowed(sql),
+            false,
+        )
+    }
+
+    fn applied(migration: &Migration, success: bool) -> AppliedMigration {
+        AppliedMigration {
+            version: migration.version,
+            description: migration.description.to_string(),
+            checksum: migration.checksum.to_vec(),
+            success,
+        }
+    }
+
+    fn states(statuses: &[MigrationStatus]) -> Vec<(i64, MigrationState)> {
+        statuses
+            .iter()
+            .map(|status| (status.version, status.state))
+            .collect()
+    }
+
+    #[test]
+    fn test_compare_reports_applied_and_pending() {
+        let first = migration(1, "CREATE TABLE a ()");
+        let second = migration(2, "CREATE TABLE b ()");
+
+        let statuses = compare(&[first.clone(), second], &[applied(&first, true)]);
+
+        assert_eq!(
+            states(&statuses),
+            [(1, MigrationState::Applied), (2, MigrationState::Pending)]
+        );
+        assert!(!is_up_to_date(&statuses));
+    }
+
+    #[test]
+    fn test_compare_detects_drift() {
+        let edited = migration(1, "CREATE TABLE a (id INT)");
+        let failed = migration(2, "CREATE TABLE b ()");
+        let original = applied(&migration(1, "CREATE TABLE a ()"), true);
+        let removed = applied(&migration(3, "CREATE TABLE c ()"), true);
+
+        let statuses = compare(
+            &[edited, failed.clone()],
+            &[original, applied(&failed, false), removed],
+        );
+
+        assert_eq!(
+            states(&statuses),
+            [
+                (1, MigrationState::ChecksumMismatch),
+                (2, MigrationState::Failed),
+                (3, MigrationState::Unknown)
+            ]
+        );
+        assert!(!is_up_to_date(&statuses));
+    }
+
+    #[test]
+    fn test_embedded_migrations_are_pending_on_an_empty_database() {
+        let statuses = compare(&MIGRATOR.migrations, &[]);
+
+        assert!(!statuses.is_empty());
+        assert!(statuses
+            .iter()
+            .all(|status| status.state == MigrationState::Pending));
+    }
+}
@@ -1,6 +1,55 @@
 // Infrastructure implementations go here
-// Example:
-// pub mod postgres_user_repository;
 
+// <feature:postgres>
+pub mod attachment;
+// </feature:postgres>
+pub mod cached_task;
+pub mod circuit_breaker;
+pub mod circuit_breaker_producer;
+pub mod composite_event_producer;
+pub mod error_reporting;
+pub mod feature_flags;
+pub mod filesystem_object_store;
+pub mod health_history;
+pub mod http;
+pub mod in_memory_task;
+pub mod in_memory_user;
+pub mod in_memory_webhook;
+pub mod instrumented;
+// <feature:postgres>
+pub mod jobs;
+// </feature:postgres>
+// <feature:kafka>
 pub mod kafka_producer;
+// </feature:kafka>
+pub mod log_level;
+// <feature:postgres>
+pub mod migrations;
+// </feature:postgres>
+pub mod noop_event_producer;
+// <feature:postgres>
+pub mod pool_monitor;
+// </feature:postgres>
+pub mod retry;
+pub mod s3_object_store;
+pub mod scheduler;
+mod sql;
+// <feature:sqlite>
+#[cfg(feature = "sqlite")]
+pub mod sqlite_task;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_user;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_webhook;
+// </feature:sqlite>
+// <feature:postgres>
 pub mod task;
+pub mod task_notifications;
+// </feature:postgres>
+pub mod telemetry;
+pub mod usage_cache;
+// <feature:postgres>
+pub mod user;
+pub mod webhook;
+// </feature:postgres>
+pub mod webhook_dispatcher;
@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use tracing::debug;
+
+use crate::domain::{
+    errors::DomainError, interfaces::event_producer::EventProducer, task::models::events::TaskEvent,
+};
+
+/// Event producer that drops every event, used when no event sink is enabled
+#[derive(Debug, Default)]
+pub struct NoopEventProducer;
+
+#[async_trait]
+impl EventProducer for NoopEventProducer {
+    fn name(&self) -> &'static str {
+        "noop"
+    }
+
+    async fn publish_task_event(&self, event: TaskEvent) -> Result<(), DomainError> {
+        debug!(
+            "No event sink enabled, dropping task event: event_id={}, event_type={:?}",
+            event.event_id, event.event_type
+        );
+        Ok(())
+    }
+}
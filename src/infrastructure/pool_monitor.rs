@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+
+use crate::config::DatabasePoolConfig;
+
+/// One observation of the database pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolSample {
+    /// Open connections, idle or in use
+    pub size: u32,
+    /// Open connections not currently checked out
+    pub idle: usize,
+}
+
+/// Spawn a background task that samples the pool every `monitor_interval` seconds
+///
+/// Each sample is published as `db_pool_*` gauges and logged; a warning is emitted when the
+/// pool is exhausted. Slow acquisitions are logged by the pool itself, see
+/// `acquire_wait_limit_ms`.
+pub fn spawn_pool_monitor(pool: PgPool, config: &DatabasePoolConfig) -> JoinHandle<()> {
+    let config = config.clone();
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(config.monitor_interval));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            sample_pool(&pool, &config);
+        }
+    })
+}
+
+/// Take a single pool sample, publish it as metrics and log it
+///
+/// Only reads the pool's counters: checking out a connection would wait while the pool is
+/// exhausted, and take one away from requests when they need it most.
+pub fn sample_pool(pool: &PgPool, config: &DatabasePoolConfig) -> PoolSample {
+    let size = pool.size();
+    let idle = pool.num_idle();
+
+    metrics::gauge!("db_pool_connections").set(f64::from(size));
+    metrics::gauge!("db_pool_idle_connections").set(idle as f64);
+
+    if size >= config.max_connections && idle == 0 {
+        tracing::warn!(
+            size,
+            idle,
+            max_connections = config.max_connections,
+            "Database pool exhausted"
+        );
+    } else {
+        tracing::debug!(size, idle, "Database pool sample");
+    }
+
+    PoolSample { size, idle }
+}
@@ -0,0 +1,201 @@
+use rand::Rng;
+use std::{future::Future, time::Duration};
+
+use crate::config::DatabasePoolConfig;
+
+/// SQLSTATE `admin_shutdown`, sent to every session when the server or a failover stops it
+const ADMIN_SHUTDOWN: &str = "57P01";
+/// SQLSTATE `serialization_failure`, which the server expects clients to retry
+const SERIALIZATION_FAILURE: &str = "40001";
+
+/// How often and how patiently a transient database error is retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first; `1` disables retrying
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Never retry
+    pub const NONE: Self = Self {
+        max_attempts: 1,
+        base_delay: Duration::ZERO,
+        max_delay: Duration::ZERO,
+    };
+
+    pub fn from_config(config: &DatabasePoolConfig) -> Self {
+        Self {
+            max_attempts: config.retry_max_attempts.max(1),
+            base_delay: Duration::from_millis(config.retry_base_delay_ms),
+            max_delay: Duration::from_millis(config.retry_max_delay_ms),
+        }
+    }
+
+    /// Delay before retry number `retry` (starting at 1)
+    ///
+    /// Exponential backoff capped at `max_delay`, with "equal jitter": half of the delay is
+    /// fixed and the other half random, so a burst of failing requests does not retry in
+    /// lockstep.
+    pub(crate) fn delay(&self, retry: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)));
+        let capped = exponential.min(self.max_delay);
+        let half = capped / 2;
+
+        half + half.mul_f64(rand::rng().random::<f64>())
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::from_config(&DatabasePoolConfig::default())
+    }
+}
+
+/// Whether `error` is likely to succeed on an immediate retry
+///
+/// Covers dropped connections, pool exhaustion during a failover, sessions terminated by
+/// the server, and serialization failures.
+pub fn is_transient(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut => true,
+        sqlx::Error::Database(db_error) => matches!(
+            db_error.code().as_deref(),
+            Some(ADMIN_SHUTDOWN | SERIALIZATION_FAILURE)
+        ),
+        _ => false,
+    }
+}
+
+/// Run `operation` until it succeeds, fails permanently or runs out of attempts
+///
+/// Only use this for statements that are safe to repeat: reads, or writes that are
+/// idempotent. A retried non-idempotent write may apply twice if the first attempt
+/// committed before its connection dropped.
+pub async fn retry<T, F, Fut>(
+    policy: RetryPolicy,
+    query: &'static str,
+    mut operation: F,
+) -> Result<T, sqlx::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, sqlx::Error>>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Err(error) if attempt < policy.max_attempts && is_transient(&error) => {
+                let delay = policy.delay(attempt);
+                metrics::counter!("db_query_retries_total", "query" => query).increment(1);
+                tracing::warn!(
+                    query,
+                    attempt,
+                    max_attempts = policy.max_attempts,
+                    delay_ms = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX),
+                    error = %error,
+                    "Retrying transient database error"
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    const FAST: RetryPolicy = RetryPolicy {
+        max_attempts: 3,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(2),
+    };
+
+    fn connection_reset() -> sqlx::Error {
+        sqlx::Error::Io(std::io::Error::new(
+            std::io::ErrorKind::ConnectionReset,
+            "connection closed",
+        ))
+    }
+
+    /// Run `retry` against an operation that fails with `errors` in order, then succeeds
+    async fn attempts_until_done(
+        policy: RetryPolicy,
+        errors: Vec<sqlx::Error>,
+    ) -> (Result<u32, sqlx::Error>, u32) {
+        let attempts = AtomicU32::new(0);
+        let errors = std::sync::Mutex::new(errors.into_iter());
+
+        let result = retry(policy, "test_query", || async {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            match errors.lock().unwrap().next() {
+                Some(error) => Err(error),
+                None => Ok(attempt),
+            }
+        })
+        .await;
+
+        (result, attempts.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn test_retry_recovers_from_transient_errors() {
+        let (result, attempts) =
+            attempts_until_done(FAST, vec![connection_reset(), sqlx::Error::PoolTimedOut]).await;
+
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_after_max_attempts() {
+        let errors = (0..5).map(|_| connection_reset()).collect();
+
+        let (result, attempts) = attempts_until_done(FAST, errors).await;
+
+        assert!(matches!(result, Err(sqlx::Error::Io(_))));
+        assert_eq!(attempts, FAST.max_attempts);
+    }
+
+    #[tokio::test]
+    async fn test_retry_does_not_repeat_permanent_errors() {
+        let (result, attempts) = attempts_until_done(FAST, vec![sqlx::Error::RowNotFound]).await;
+
+        assert!(matches!(result, Err(sqlx::Error::RowNotFound)));
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_policy_none_makes_a_single_attempt() {
+        let (result, attempts) =
+            attempts_until_done(RetryPolicy::NONE, vec![connection_reset()]).await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_delay_grows_exponentially_up_to_the_cap() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+        };
+
+        for (retry, full) in [(1, 100), (2, 200), (3, 400), (4, 800), (5, 1000), (9, 1000)] {
+            let delay = policy.delay(retry);
+            let full = Duration::from_millis(full);
+            assert!(
+                delay >= full / 2 && delay <= full,
+                "retry {retry}: {delay:?} should be within [{:?}, {full:?}]",
+                full / 2
+            );
+        }
+    }
+}
@@ -0,0 +1,348 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    str::FromStr,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use anyhow::{bail, Context};
+use chrono::{DateTime, Utc};
+use cron::Schedule;
+use tokio::task::{JoinHandle, JoinSet};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+use crate::domain::errors::DomainError;
+
+type RunFuture = Pin<Box<dyn Future<Output = Result<(), DomainError>> + Send>>;
+type RunFn = Arc<dyn Fn() -> RunFuture + Send + Sync>;
+
+/// How the latest run of a scheduled job went
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastRun {
+    pub started_at: DateTime<Utc>,
+    /// `None` while the run is in progress
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Why the run failed, when it did
+    pub error: Option<String>,
+}
+
+impl LastRun {
+    /// Whether the run finished without an error
+    #[must_use]
+    pub const fn succeeded(&self) -> bool {
+        self.finished_at.is_some() && self.error.is_none()
+    }
+}
+
+/// The latest run of every scheduled job, kept up to date while the scheduler runs
+#[derive(Debug, Clone, Default)]
+pub struct SchedulerStatus(Arc<Mutex<HashMap<&'static str, LastRun>>>);
+
+impl SchedulerStatus {
+    /// The latest run of the job named `name`; `None` until it first runs
+    #[must_use]
+    pub fn last_run(&self, name: &str) -> Option<LastRun> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .cloned()
+    }
+
+    fn record(&self, name: &'static str, run: LastRun) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name, run);
+    }
+}
+
+struct ScheduledJob {
+    name: &'static str,
+    schedule: Schedule,
+    run: RunFn,
+}
+
+/// Runs recurring jobs declared in code, each on its own cron schedule
+///
+/// Cron expressions have six fields, starting with seconds (`sec min hour day month
+/// weekday`), and are evaluated in UTC. A run that is due while the previous run of the
+/// same job is still going is skipped. Unlike background jobs, scheduled jobs run in
+/// every process that runs the scheduler, so they should be safe to run concurrently
+/// across replicas.
+#[derive(Default)]
+pub struct Scheduler {
+    jobs: Vec<ScheduledJob>,
+    status: SchedulerStatus,
+}
+
+impl std::fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("jobs", &self.names())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Scheduler {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `run` whenever `expression` is due, as the job `name`
+    ///
+    /// # Errors
+    /// Returns an error when `expression` is not a valid cron expression, or a job named
+    /// `name` is already registered
+    pub fn register<F, Fut>(
+        mut self,
+        name: &'static str,
+        expression: &str,
+        run: F,
+    ) -> anyhow::Result<Self>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), DomainError>> + Send + 'static,
+    {
+        let schedule = Schedule::from_str(expression).with_context(|| {
+            format!("Invalid cron expression `{expression}` for scheduled job `{name}`")
+        })?;
+        if self.jobs.iter().any(|job| job.name == name) {
+            bail!("Scheduled job `{name}` is registered twice");
+        }
+        self.jobs.push(ScheduledJob {
+            name,
+            schedule,
+            run: Arc::new(move || Box::pin(run()) as RunFuture),
+        });
+        Ok(self)
+    }
+
+    /// Names of the registered jobs, in registration order
+    #[must_use]
+    pub fn names(&self) -> Vec<&'static str> {
+        self.jobs.iter().map(|job| job.name).collect()
+    }
+
+    /// A handle on the latest run of each job, which stays current once the scheduler runs
+    #[must_use]
+    pub fn status(&self) -> SchedulerStatus {
+        self.status.clone()
+    }
+
+    /// Run the jobs on their schedules until `shutdown` is cancelled
+    ///
+    /// Runs in progress when that happens finish first, so awaiting this (or the handle
+    /// from [`spawn`](Self::spawn)) drains the scheduler.
+    pub async fn run(self, shutdown: CancellationToken) {
+        if self.jobs.is_empty() {
+            return;
+        }
+        info!(jobs = ?self.names(), "Running scheduled jobs");
+        let mut schedules = JoinSet::new();
+        for job in self.jobs {
+            schedules.spawn(run_schedule(job, self.status.clone(), shutdown.clone()));
+        }
+        while let Some(finished) = schedules.join_next().await {
+            if let Err(e) = finished {
+                error!(error = %e, "Scheduled job loop stopped abnormally");
+            }
+        }
+        info!("Scheduled jobs stopped");
+    }
+
+    /// Run the jobs in a background task until `shutdown` is cancelled
+    #[must_use]
+    pub fn spawn(self, shutdown: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(self.run(shutdown))
+    }
+}
+
+/// Start `job` every time its schedule is due, skipping runs while one is in progress
+async fn run_schedule(job: ScheduledJob, status: SchedulerStatus, shutdown: CancellationToken) {
+    let mut running: Option<JoinHandle<()>> = None;
+    let mut last_due = Utc::now();
+    loop {
+        // Never before the previous due time, in case the wall clock is behind the timer
+        let Some(due) = job.schedule.after(&Utc::now().max(last_due)).next() else {
+            info!(job = job.name, "Schedule has no further runs");
+            break;
+        };
+        last_due = due;
+        let wait = (due - Utc::now()).to_std().unwrap_or_default();
+        tokio::select! {
+            () = shutdown.cancelled() => break,
+            () = tokio::time::sleep(wait) => {}
+        }
+
+        if running.as_ref().is_some_and(|run| !run.is_finished()) {
+            warn!(
+                job = job.name,
+                "Skipping scheduled run; the previous run is still in progress"
+            );
+            metrics::counter!("scheduled_job_runs_total", "job" => job.name, "outcome" => "skipped")
+                .increment(1);
+            continue;
+        }
+        running = Some(tokio::spawn(execute(
+            job.name,
+            Arc::clone(&job.run),
+            status.clone(),
+        )));
+    }
+
+    if let Some(run) = running {
+        if !run.is_finished() {
+            info!(
+                job = job.name,
+                "Waiting for the scheduled run in progress to finish..."
+            );
+        }
+        if let Err(e) = run.await {
+            error!(job = job.name, error = %e, "Scheduled run stopped abnormally");
+        }
+    }
+}
+
+/// Run a job once and record how it went
+async fn execute(name: &'static str, run: RunFn, status: SchedulerStatus) {
+    let started_at = Utc::now();
+    status.record(
+        name,
+        LastRun {
+            started_at,
+            finished_at: None,
+            error: None,
+        },
+    );
+    debug!(job = name, "Scheduled run started");
+
+    // Run on a task of its own, so a panic is recorded as a failed run
+    let error = match tokio::spawn(run()).await {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => Some(e.to_string()),
+        Err(e) => Some(format!("Scheduled run panicked: {e}")),
+    };
+    let finished_at = Utc::now();
+
+    let outcome = if let Some(error) = &error {
+        warn!(job = name, error = %error, "Scheduled run failed");
+        "failure"
+    } else {
+        debug!(job = name, "Scheduled run succeeded");
+        "success"
+    };
+    metrics::counter!("scheduled_job_runs_total", "job" => name, "outcome" => outcome).increment(1);
+    metrics::gauge!("scheduled_job_last_run_timestamp_seconds", "job" => name)
+        .set(finished_at.timestamp() as f64);
+    metrics::gauge!("scheduled_job_last_run_success", "job" => name).set(if error.is_none() {
+        1.0
+    } else {
+        0.0
+    });
+
+    status.record(
+        name,
+        LastRun {
+            started_at,
+            finished_at: Some(finished_at),
+            error,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use super::*;
+    use crate::domain::errors::ExternalSystem;
+
+    const EVERY_SECOND: &str = "* * * * * *";
+
+    #[test]
+    fn test_register_rejects_invalid_expressions_and_duplicate_names() {
+        let error = Scheduler::new()
+            .register("purge", "every night", || async { Ok(()) })
+            .unwrap_err();
+        assert!(error.to_string().contains("`every night`"), "{error:#}");
+
+        let error = Scheduler::new()
+            .register("purge", EVERY_SECOND, || async { Ok(()) })
+            .unwrap()
+            .register("purge", EVERY_SECOND, || async { Ok(()) })
+            .unwrap_err();
+        assert!(error.to_string().contains("twice"), "{error:#}");
+    }
+
+    #[tokio::test]
+    async fn test_runs_are_recorded_with_their_outcome() {
+        let scheduler = Scheduler::new()
+            .register("succeeds", EVERY_SECOND, || async { Ok(()) })
+            .unwrap()
+            .register("fails", EVERY_SECOND, || async {
+                Err(DomainError::external_error(
+                    ExternalSystem::Database,
+                    "database unavailable",
+                ))
+            })
+            .unwrap();
+        let status = scheduler.status();
+        let shutdown = CancellationToken::new();
+        let running = scheduler.spawn(shutdown.clone());
+
+        tokio::time::sleep(Duration::from_millis(2_200)).await;
+        shutdown.cancel();
+        running.await.unwrap();
+
+        assert!(status.last_run("succeeds").unwrap().succeeded());
+        let failed = status.last_run("fails").unwrap();
+        assert!(failed.finished_at.is_some());
+        assert!(
+            failed
+                .error
+                .as_deref()
+                .is_some_and(|error| error.contains("database unavailable")),
+            "{failed:?}"
+        );
+        assert_eq!(status.last_run("unknown"), None);
+    }
+
+    #[tokio::test]
+    async fn test_overlapping_runs_are_skipped_and_shutdown_waits_for_the_run() {
+        let started = Arc::new(AtomicUsize::new(0));
+        let finished = Arc::new(AtomicUsize::new(0));
+        let scheduler = Scheduler::new()
+            .register("slow", EVERY_SECOND, {
+                let (started, finished) = (Arc::clone(&started), Arc::clone(&finished));
+                move || {
+                    let (started, finished) = (Arc::clone(&started), Arc::clone(&finished));
+                    async move {
+                        started.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_secs(3)).await;
+                        finished.fetch_add(1, Ordering::SeqCst);
+                        Ok(())
+                    }
+                }
+            })
+            .unwrap();
+        let status = scheduler.status();
+        let shutdown = CancellationToken::new();
+        let running = scheduler.spawn(shutdown.clone());
+
+        // Due at least twice while the first run is still going
+        tokio::time::sleep(Duration::from_millis(2_500)).await;
+        shutdown.cancel();
+        running.await.unwrap();
+
+        assert_eq!(started.load(Ordering::SeqCst), 1);
+        assert_eq!(finished.load(Ordering::SeqCst), 1);
+        assert!(status.last_run("slow").unwrap().succeeded());
+    }
+}
@@ -0,0 +1,275 @@
+//! Rows and helpers shared by the Postgres and SQLite repositories
+//!
+//! Both schemas store the same columns, so a row reads the same from either backend.
+
+use sqlx::types::Json;
+use std::{
+    convert::TryFrom,
+    future::Future,
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+use crate::{
+    common::{TenantId, UserId},
+    domain::{
+        errors::DomainError,
+        task::models::{Task, TaskEventType, TaskId, TaskPriority, TaskStatus},
+        user::models::{Email, User},
+        webhook::models::{Webhook, WebhookDelivery, WebhookId, WebhookUrl},
+    },
+};
+
+/// Default slow-query threshold, matching `DatabasePoolConfig`'s default
+pub(super) const DEFAULT_SLOW_QUERY_THRESHOLD: Duration = Duration::from_millis(500);
+
+/// Run a query, recording its duration on the current span and in the
+/// `db_query_duration_seconds` histogram, and warning when it exceeds `threshold`
+pub(super) async fn timed<T, E>(
+    threshold: Duration,
+    query: &'static str,
+    future: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let started = Instant::now();
+    let result = future.await;
+    let elapsed = started.elapsed();
+    let duration_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+
+    tracing::Span::current().record("duration_ms", duration_ms);
+    metrics::histogram!("db_query_duration_seconds", "query" => query)
+        .record(elapsed.as_secs_f64());
+
+    if elapsed > threshold {
+        metrics::counter!("db_slow_queries_total", "query" => query).increment(1);
+        tracing::warn!(
+            query,
+            duration_ms,
+            threshold_ms = u64::try_from(threshold.as_millis()).unwrap_or(u64::MAX),
+            "Slow database query"
+        );
+    }
+
+    result
+}
+
+// Infrastructure-specific enum types for database mapping
+#[derive(Debug, Clone, Copy, sqlx::Type)]
+#[sqlx(type_name = "task_status", rename_all = "SCREAMING_SNAKE_CASE")]
+pub(super) enum TaskStatusDb {
+    Pending,
+    InProgress,
+    Completed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, sqlx::Type)]
+#[sqlx(type_name = "task_priority", rename_all = "SCREAMING_SNAKE_CASE")]
+pub(super) enum TaskPriorityDb {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl From<TaskStatusDb> for TaskStatus {
+    fn from(status: TaskStatusDb) -> Self {
+        match status {
+            TaskStatusDb::Pending => TaskStatus::Pending,
+            TaskStatusDb::InProgress => TaskStatus::InProgress,
+            TaskStatusDb::Completed => TaskStatus::Completed,
+            TaskStatusDb::Cancelled => TaskStatus::Cancelled,
+        }
+    }
+}
+
+impl From<TaskStatus> for TaskStatusDb {
+    fn from(status: TaskStatus) -> Self {
+        match status {
+            TaskStatus::Pending => TaskStatusDb::Pending,
+            TaskStatus::InProgress => TaskStatusDb::InProgress,
+            TaskStatus::Completed => TaskStatusDb::Completed,
+            TaskStatus::Cancelled => TaskStatusDb::Cancelled,
+        }
+    }
+}
+
+impl From<TaskPriorityDb> for TaskPriority {
+    fn from(priority: TaskPriorityDb) -> Self {
+        match priority {
+            TaskPriorityDb::Low => TaskPriority::Low,
+            TaskPriorityDb::Medium => TaskPriority::Medium,
+            TaskPriorityDb::High => TaskPriority::High,
+            TaskPriorityDb::Critical => TaskPriority::Critical,
+        }
+    }
+}
+
+impl From<TaskPriority> for TaskPriorityDb {
+    fn from(priority: TaskPriority) -> Self {
+        match priority {
+            TaskPriority::Low => TaskPriorityDb::Low,
+            TaskPriority::Medium => TaskPriorityDb::Medium,
+            TaskPriority::High => TaskPriorityDb::High,
+            TaskPriority::Critical => TaskPriorityDb::Critical,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct TaskRow {
+    id: Uuid,
+    tenant_id: Uuid,
+    user_id: Uuid,
+    title: String,
+    description: Option<String>,
+    status: TaskStatusDb,
+    priority: TaskPriorityDb,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+    completed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl TryFrom<TaskRow> for Task {
+    type Error = DomainError;
+
+    fn try_from(row: TaskRow) -> Result<Self, Self::Error> {
+        use crate::domain::task::models::Title;
+
+        Ok(Self {
+            id: TaskId::from(row.id),
+            tenant_id: TenantId::from(row.tenant_id),
+            user_id: UserId::from(row.user_id),
+            title: Title::from_storage(row.title),
+            description: row.description,
+            status: row.status.into(),
+            priority: row.priority.into(),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            completed_at: row.completed_at,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct UserRow {
+    id: Uuid,
+    email: String,
+    display_name: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<UserRow> for User {
+    fn from(row: UserRow) -> Self {
+        Self {
+            id: UserId::from(row.id),
+            email: Email::from_storage(row.email),
+            display_name: row.display_name,
+            created_at: row.created_at,
+        }
+    }
+}
+
+/// Counters are `u32` in the domain and `INTEGER` in both schemas
+pub(super) fn to_db_count(count: u32) -> i32 {
+    i32::try_from(count).unwrap_or(i32::MAX)
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct WebhookRow {
+    id: Uuid,
+    tenant_id: Uuid,
+    url: String,
+    secret: String,
+    event_types: Json<Vec<TaskEventType>>,
+    active: bool,
+    consecutive_failures: i32,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<WebhookRow> for Webhook {
+    fn from(row: WebhookRow) -> Self {
+        Self {
+            id: WebhookId::from(row.id),
+            tenant_id: TenantId::from(row.tenant_id),
+            url: WebhookUrl::from_storage(row.url),
+            secret: row.secret,
+            event_types: row.event_types.0,
+            active: row.active,
+            consecutive_failures: u32::try_from(row.consecutive_failures).unwrap_or_default(),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub(super) struct WebhookDeliveryRow {
+    id: Uuid,
+    webhook_id: Uuid,
+    event_id: Uuid,
+    event_type: String,
+    attempt: i32,
+    status_code: Option<i32>,
+    error: Option<String>,
+    succeeded: bool,
+    duration_ms: i64,
+    attempted_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<WebhookDeliveryRow> for WebhookDelivery {
+    type Error = DomainError;
+
+    fn try_from(row: WebhookDeliveryRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            webhook_id: WebhookId::from(row.webhook_id),
+            event_id: row.event_id,
+            event_type: row.event_type.parse()?,
+            attempt: u32::try_from(row.attempt).unwrap_or_default(),
+            status_code: row.status_code.and_then(|code| u16::try_from(code).ok()),
+            error: row.error,
+            succeeded: row.succeeded,
+            duration_ms: u64::try_from(row.duration_ms).unwrap_or_default(),
+            attempted_at: row.attempted_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// The row `task` is stored as
+    fn row_of(task: &Task) -> TaskRow {
+        TaskRow {
+            id: task.id.into_inner(),
+            tenant_id: task.tenant_id.into_inner(),
+            user_id: task.user_id.into_inner(),
+            title: task.title.value().to_string(),
+            description: task.description.clone(),
+            status: task.status.into(),
+            priority: task.priority.into(),
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            completed_at: task.completed_at,
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn validated_titles_survive_a_round_trip_through_task_row(raw in "\\PC{0,250}") {
+            let Ok(task) = Task::new(TenantId::new(), UserId::new(), raw, None, TaskPriority::Medium) else {
+                return Ok(());
+            };
+
+            let read = Task::try_from(row_of(&task)).unwrap();
+
+            prop_assert_eq!(&read.title, &task.title);
+            prop_assert_eq!(read.id, task.id);
+            prop_assert_eq!(read.tenant_id, task.tenant_id);
+        }
+    }
+}
@@ -0,0 +1,490 @@
+use anyhow::Context;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions, SqliteQueryResult},
+    Sqlite, SqliteExecutor, SqlitePool, Transaction,
+};
+use std::{fmt::Debug, str::FromStr, time::Duration};
+
+use super::sql::{timed, TaskPriorityDb, TaskRow, TaskStatusDb, DEFAULT_SLOW_QUERY_THRESHOLD};
+use crate::{
+    common::{TenantId, UserId},
+    config::DatabasePoolConfig,
+    domain::{
+        errors::{DomainError, ExternalSystem},
+        interfaces::task_repository::{TaskRepository, TaskUnitOfWork},
+        job::models::{JobId, NewJob},
+        quota::models::TaskUsage,
+        task::models::{
+            DailyTaskCount, PriorityChange, StatsRange, Task, TaskId, TaskPage, TaskSearch,
+        },
+    },
+};
+
+/// [`TaskRepository`] backed by SQLite, selected with `database_kind = sqlite`
+///
+/// Mirrors [`PostgresTaskRepository`](super::task::PostgresTaskRepository) statement for
+/// statement, against the schema in `migrations_sqlite/`.
+#[derive(Clone)]
+pub struct SqliteTaskRepository {
+    pool: SqlitePool,
+    slow_query_threshold: Duration,
+}
+
+impl Debug for SqliteTaskRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqliteTaskRepository")
+            .field("pool", &"SqlitePool")
+            .field("slow_query_threshold", &self.slow_query_threshold)
+            .finish()
+    }
+}
+
+impl SqliteTaskRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            pool,
+            slow_query_threshold: DEFAULT_SLOW_QUERY_THRESHOLD,
+        }
+    }
+
+    /// Open (creating if needed) the database at `database_url` and run the SQLite migrations
+    pub async fn connect(database_url: &str, config: &DatabasePoolConfig) -> anyhow::Result<Self> {
+        let options = SqliteConnectOptions::from_str(database_url)
+            .context("Invalid SQLite database URL")?
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(config.max_connections)
+            .min_connections(config.min_connections)
+            .acquire_timeout(Duration::from_secs(config.acquire_timeout))
+            .connect_with(options)
+            .await
+            .context("Failed to open SQLite database")?;
+
+        sqlx::migrate!("./migrations_sqlite")
+            .run(&pool)
+            .await
+            .context("Failed to run SQLite migrations")?;
+
+        Ok(Self::new(pool)
+            .with_slow_query_threshold(Duration::from_millis(config.slow_query_threshold_ms)))
+    }
+
+    /// The database's pool, for repositories of other entities stored alongside tasks
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    /// Log queries slower than `threshold` at warn level
+    #[must_use]
+    pub const fn with_slow_query_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_query_threshold = threshold;
+        self
+    }
+}
+
+#[async_trait]
+impl TaskRepository for SqliteTaskRepository {
+    #[tracing::instrument(skip_all, fields(query = "insert_task", task_id = %entity.id, duration_ms = tracing::field::Empty))]
+    async fn create(&self, entity: Task) -> Result<Task, DomainError> {
+        timed(
+            self.slow_query_threshold,
+            "insert_task",
+            insert_task(&self.pool, &entity),
+        )
+        .await
+        .map_err(DomainError::from)
+        .and_then(Task::try_from)
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "select_task", tenant_id = %tenant, task_id = %id, duration_ms = tracing::field::Empty))]
+    async fn get(&self, tenant: TenantId, id: TaskId) -> Result<Option<Task>, DomainError> {
+        timed(
+            self.slow_query_threshold,
+            "select_task",
+            select_task(&self.pool, tenant, id),
+        )
+        .await
+        .map_err(DomainError::from)
+        .and_then(|row| row.map(Task::try_from).transpose())
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "select_tasks_by_user", tenant_id = %tenant, user_id = %user_id, duration_ms = tracing::field::Empty))]
+    async fn get_by_user(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+    ) -> Result<Vec<Task>, DomainError> {
+        timed(
+            self.slow_query_threshold,
+            "select_tasks_by_user",
+            select_tasks_by_user(&self.pool, tenant, user_id),
+        )
+        .await
+        .map_err(DomainError::from)
+        .and_then(|rows| {
+            rows.into_iter()
+                .map(Task::try_from)
+                .collect::<Result<Vec<_>, _>>()
+        })
+    }
+
+    /// SQLite has no time zone data, so the user's tasks are counted per local day here
+    /// rather than grouped in SQL
+    async fn daily_counts(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        range: &StatsRange,
+    ) -> Result<Vec<DailyTaskCount>, DomainError> {
+        let tasks = self.get_by_user(tenant, user_id).await?;
+        Ok(DailyTaskCount::tally(range, &tasks))
+    }
+
+    /// `created_at` may be stored in more than one text format, so the user's tasks are
+    /// counted here rather than compared as strings in SQL
+    async fn usage(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<TaskUsage, DomainError> {
+        let tasks = self.get_by_user(tenant, user_id).await?;
+        Ok(TaskUsage::tally(since, &tasks))
+    }
+
+    /// Ids are stored as blobs and `created_at` in more than one text format, so the
+    /// tenant's tasks are searched here rather than matched as strings in SQL
+    #[tracing::instrument(skip_all, fields(query = "select_tenant_tasks", tenant_id = %tenant, duration_ms = tracing::field::Empty))]
+    async fn search_all(
+        &self,
+        tenant: TenantId,
+        search: &TaskSearch,
+    ) -> Result<TaskPage, DomainError> {
+        let query = sqlx::query_as::<_, TaskRow>(
+            r#"
+            SELECT id, tenant_id, user_id, title, description, status, priority, created_at, updated_at, completed_at
+            FROM tasks
+            WHERE tenant_id = ?
+            "#,
+        )
+        .bind(tenant.into_inner())
+        .fetch_all(&self.pool);
+
+        let tasks = timed(self.slow_query_threshold, "select_tenant_tasks", query)
+            .await
+            .map_err(DomainError::from)?
+            .into_iter()
+            .map(Task::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(search.page(&tasks))
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "update_task", task_id = %entity.id, duration_ms = tracing::field::Empty))]
+    async fn update(&self, entity: &Task) -> Result<(), DomainError> {
+        let result = timed(
+            self.slow_query_threshold,
+            "update_task",
+            update_task(&self.pool, entity),
+        )
+        .await
+        .map_err(DomainError::from)?;
+        expect_affected(result, entity.id)
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "delete_task", tenant_id = %tenant, task_id = %id, duration_ms = tracing::field::Empty))]
+    async fn delete(&self, tenant: TenantId, id: TaskId) -> Result<(), DomainError> {
+        let result = timed(
+            self.slow_query_threshold,
+            "delete_task",
+            delete_task(&self.pool, tenant, id),
+        )
+        .await
+        .map_err(DomainError::from)?;
+        expect_affected(result, id)
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "health_check", duration_ms = tracing::field::Empty))]
+    async fn health_check(&self) -> Result<(), DomainError> {
+        timed(
+            self.slow_query_threshold,
+            "health_check",
+            sqlx::query("SELECT 1").execute(&self.pool),
+        )
+        .await
+        .map_err(DomainError::from)?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "begin", duration_ms = tracing::field::Empty))]
+    async fn begin(&self) -> Result<Box<dyn TaskUnitOfWork>, DomainError> {
+        // Take the write lock up front: SQLite has no row locks, and a deferred transaction
+        // could read a task another one is about to change
+        let tx = timed(
+            self.slow_query_threshold,
+            "begin",
+            self.pool.begin_with("BEGIN IMMEDIATE"),
+        )
+        .await
+        .map_err(DomainError::from)?;
+
+        Ok(Box::new(SqliteTaskUnitOfWork {
+            tx,
+            slow_query_threshold: self.slow_query_threshold,
+        }))
+    }
+}
+
+/// Unit of work over a single SQLite transaction, which holds the database's write lock
+/// from the start, so units of work run one after another
+///
+/// Jobs cannot be enqueued: the job queue relies on Postgres row locking.
+pub struct SqliteTaskUnitOfWork {
+    tx: Transaction<'static, Sqlite>,
+    slow_query_threshold: Duration,
+}
+
+impl Debug for SqliteTaskUnitOfWork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqliteTaskUnitOfWork")
+            .field("tx", &"Transaction")
+            .field("slow_query_threshold", &self.slow_query_threshold)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl TaskUnitOfWork for SqliteTaskUnitOfWork {
+    #[tracing::instrument(skip_all, fields(query = "insert_task", task_id = %entity.id, duration_ms = tracing::field::Empty))]
+    async fn create(&mut self, entity: Task) -> Result<Task, DomainError> {
+        timed(
+            self.slow_query_threshold,
+            "insert_task",
+            insert_task(&mut *self.tx, &entity),
+        )
+        .await
+        .map_err(DomainError::from)
+        .and_then(Task::try_from)
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "select_task", tenant_id = %tenant, task_id = %id, duration_ms = tracing::field::Empty))]
+    async fn get(&mut self, tenant: TenantId, id: TaskId) -> Result<Option<Task>, DomainError> {
+        timed(
+            self.slow_query_threshold,
+            "select_task",
+            select_task(&mut *self.tx, tenant, id),
+        )
+        .await
+        .map_err(DomainError::from)
+        .and_then(|row| row.map(Task::try_from).transpose())
+    }
+
+    /// The unit of work already holds the database's write lock, which keeps other units of
+    /// work from creating tasks until it ends
+    #[tracing::instrument(skip_all, fields(query = "select_tasks_by_user", tenant_id = %tenant, user_id = %user_id, duration_ms = tracing::field::Empty))]
+    async fn lock_usage(
+        &mut self,
+        tenant: TenantId,
+        user_id: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<TaskUsage, DomainError> {
+        let rows = timed(
+            self.slow_query_threshold,
+            "select_tasks_by_user",
+            select_tasks_by_user(&mut *self.tx, tenant, user_id),
+        )
+        .await
+        .map_err(DomainError::from)?;
+        let tasks = rows
+            .into_iter()
+            .map(Task::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(TaskUsage::tally(since, &tasks))
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "update_task", task_id = %entity.id, duration_ms = tracing::field::Empty))]
+    async fn update(&mut self, entity: &Task) -> Result<(), DomainError> {
+        let result = timed(
+            self.slow_query_threshold,
+            "update_task",
+            update_task(&mut *self.tx, entity),
+        )
+        .await
+        .map_err(DomainError::from)?;
+        expect_affected(result, entity.id)
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "delete_task", tenant_id = %tenant, task_id = %id, duration_ms = tracing::field::Empty))]
+    async fn delete(&mut self, tenant: TenantId, id: TaskId) -> Result<(), DomainError> {
+        let result = timed(
+            self.slow_query_threshold,
+            "delete_task",
+            delete_task(&mut *self.tx, tenant, id),
+        )
+        .await
+        .map_err(DomainError::from)?;
+        expect_affected(result, id)
+    }
+
+    async fn enqueue(&mut self, _job: NewJob) -> Result<JobId, DomainError> {
+        Err(DomainError::external_error(
+            ExternalSystem::Database,
+            "Background jobs need the Postgres job queue",
+        ))
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "insert_priority_change", task_id = %change.task_id, duration_ms = tracing::field::Empty))]
+    async fn record_priority_change(&mut self, change: &PriorityChange) -> Result<(), DomainError> {
+        timed(
+            self.slow_query_threshold,
+            "insert_priority_change",
+            insert_priority_change(&mut *self.tx, change),
+        )
+        .await
+        .map(|_| ())
+        .map_err(DomainError::from)
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "commit", duration_ms = tracing::field::Empty))]
+    async fn commit(self: Box<Self>) -> Result<(), DomainError> {
+        timed(self.slow_query_threshold, "commit", self.tx.commit())
+            .await
+            .map_err(DomainError::from)
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "rollback", duration_ms = tracing::field::Empty))]
+    async fn rollback(self: Box<Self>) -> Result<(), DomainError> {
+        timed(self.slow_query_threshold, "rollback", self.tx.rollback())
+            .await
+            .map_err(DomainError::from)
+    }
+}
+
+/// Map an update or delete that matched no row to `DomainError::NotFound`
+fn expect_affected(result: SqliteQueryResult, id: TaskId) -> Result<(), DomainError> {
+    if result.rows_affected() == 0 {
+        return Err(DomainError::not_found("Task", id.to_string()));
+    }
+    Ok(())
+}
+
+// Statements shared by the pool-backed repository and the unit of work
+
+async fn insert_task<'e>(
+    executor: impl SqliteExecutor<'e>,
+    entity: &Task,
+) -> sqlx::Result<TaskRow> {
+    sqlx::query_as::<_, TaskRow>(
+        r#"
+        INSERT INTO tasks (id, tenant_id, user_id, title, description, status, priority, created_at, updated_at, completed_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        RETURNING id, tenant_id, user_id, title, description, status, priority, created_at, updated_at, completed_at
+        "#,
+    )
+    .bind(entity.id.into_inner())
+    .bind(entity.tenant_id.into_inner())
+    .bind(entity.user_id.into_inner())
+    .bind(entity.title.value())
+    .bind(&entity.description)
+    .bind(TaskStatusDb::from(entity.status))
+    .bind(TaskPriorityDb::from(entity.priority))
+    .bind(entity.created_at)
+    .bind(entity.updated_at)
+    .bind(entity.completed_at)
+    .fetch_one(executor)
+    .await
+}
+
+async fn insert_priority_change<'e>(
+    executor: impl SqliteExecutor<'e>,
+    change: &PriorityChange,
+) -> sqlx::Result<SqliteQueryResult> {
+    sqlx::query(
+        r#"
+        INSERT INTO priority_changes (task_id, tenant_id, from_priority, to_priority, reason, changed_by, changed_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(change.task_id.into_inner())
+    .bind(change.tenant_id.into_inner())
+    .bind(TaskPriorityDb::from(change.from))
+    .bind(TaskPriorityDb::from(change.to))
+    .bind(&change.reason)
+    .bind(change.changed_by.into_inner())
+    .bind(change.changed_at)
+    .execute(executor)
+    .await
+}
+
+async fn select_task<'e>(
+    executor: impl SqliteExecutor<'e>,
+    tenant: TenantId,
+    id: TaskId,
+) -> sqlx::Result<Option<TaskRow>> {
+    sqlx::query_as::<_, TaskRow>(
+        r#"
+        SELECT id, tenant_id, user_id, title, description, status, priority, created_at, updated_at, completed_at
+        FROM tasks
+        WHERE id = ? AND tenant_id = ?
+        "#,
+    )
+    .bind(id.into_inner())
+    .bind(tenant.into_inner())
+    .fetch_optional(executor)
+    .await
+}
+
+async fn select_tasks_by_user<'e>(
+    executor: impl SqliteExecutor<'e>,
+    tenant: TenantId,
+    user_id: UserId,
+) -> sqlx::Result<Vec<TaskRow>> {
+    sqlx::query_as::<_, TaskRow>(
+        r#"
+        SELECT id, tenant_id, user_id, title, description, status, priority, created_at, updated_at, completed_at
+        FROM tasks
+        WHERE tenant_id = ? AND user_id = ?
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(tenant.into_inner())
+    .bind(user_id.into_inner())
+    .fetch_all(executor)
+    .await
+}
+
+async fn update_task<'e>(
+    executor: impl SqliteExecutor<'e>,
+    entity: &Task,
+) -> sqlx::Result<SqliteQueryResult> {
+    sqlx::query(
+        r#"
+        UPDATE tasks
+        SET title = ?, description = ?, status = ?, priority = ?, updated_at = ?, completed_at = ?
+        WHERE id = ? AND tenant_id = ?
+        "#,
+    )
+    .bind(entity.title.value())
+    .bind(&entity.description)
+    .bind(TaskStatusDb::from(entity.status))
+    .bind(TaskPriorityDb::from(entity.priority))
+    .bind(entity.updated_at)
+    .bind(entity.completed_at)
+    .bind(entity.id.into_inner())
+    .bind(entity.tenant_id.into_inner())
+    .execute(executor)
+    .await
+}
+
+async fn delete_task<'e>(
+    executor: impl SqliteExecutor<'e>,
+    tenant: TenantId,
+    id: TaskId,
+) -> sqlx::Result<SqliteQueryResult> {
+    sqlx::query("DELETE FROM tasks WHERE id = ? AND tenant_id = ?")
+        .bind(id.into_inner())
+        .bind(tenant.into_inner())
+        .execute(executor)
+        .await
+}
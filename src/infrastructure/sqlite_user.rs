@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use std::fmt::Debug;
+
+use super::sql::UserRow;
+use crate::{
+    common::UserId,
+    domain::{
+        errors::DomainError, interfaces::user_repository::UserRepository, user::models::User,
+    },
+};
+
+/// [`UserRepository`] backed by SQLite, sharing the database of
+/// [`SqliteTaskRepository`](super::sqlite_task::SqliteTaskRepository)
+#[derive(Clone)]
+pub struct SqliteUserRepository {
+    pool: SqlitePool,
+}
+
+impl Debug for SqliteUserRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqliteUserRepository")
+            .field("pool", &"SqlitePool")
+            .finish()
+    }
+}
+
+impl SqliteUserRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UserRepository for SqliteUserRepository {
+    #[tracing::instrument(skip_all, fields(user_id = %entity.id))]
+    async fn create(&self, entity: User) -> Result<User, DomainError> {
+        let row = sqlx::query_as::<_, UserRow>(
+            r#"
+            INSERT INTO users (id, email, display_name, created_at)
+            VALUES (?, ?, ?, ?)
+            RETURNING id, email, display_name, created_at
+            "#,
+        )
+        .bind(entity.id.into_inner())
+        .bind(entity.email.value())
+        .bind(&entity.display_name)
+        .bind(entity.created_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.into())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %id))]
+    async fn get(&self, id: UserId) -> Result<Option<User>, DomainError> {
+        let row = sqlx::query_as::<_, UserRow>(
+            r#"
+            SELECT id, email, display_name, created_at
+            FROM users
+            WHERE id = ?
+            "#,
+        )
+        .bind(id.into_inner())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+}
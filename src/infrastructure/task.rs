@@ -1,92 +1,153 @@
 use async_trait::async_trait;
-use sqlx::PgPool;
-use std::{convert::TryFrom, fmt::Debug};
-use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgQueryResult, PgExecutor, PgPool, Postgres, QueryBuilder, Transaction};
+use std::{convert::TryFrom, fmt::Debug, future::Future, time::Duration};
 
+use super::{
+    jobs::insert_job,
+    retry::{retry, RetryPolicy},
+    sql::{timed, TaskPriorityDb, TaskRow, TaskStatusDb, DEFAULT_SLOW_QUERY_THRESHOLD},
+};
 use crate::{
-    common::UserId,
+    common::{TenantId, UserId},
     domain::{
         errors::DomainError,
-        interfaces::task_repository::TaskRepository,
-        task::models::{Task, TaskId, TaskPriority, TaskStatus},
+        interfaces::task_repository::{TaskRepository, TaskUnitOfWork},
+        job::models::{JobId, NewJob},
+        quota::models::TaskUsage,
+        task::models::{
+            DailyTaskCount, PriorityChange, SortDirection, StatsRange, Task, TaskFilter, TaskId,
+            TaskPage, TaskSearch, TaskSortField,
+        },
     },
 };
 
+/// Extra time the client waits beyond `statement_timeout`, so the server cancels first
+/// and the connection stays usable
+const STATEMENT_TIMEOUT_GRACE: Duration = Duration::from_secs(1);
+
 #[derive(Clone)]
 pub struct PostgresTaskRepository {
     pool: PgPool,
+    slow_query_threshold: Duration,
+    retry_policy: RetryPolicy,
+    query_timeout: Option<Duration>,
 }
 
 impl Debug for PostgresTaskRepository {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PostgresTaskRepository")
             .field("pool", &"PgPool")
+            .field("slow_query_threshold", &self.slow_query_threshold)
+            .field("retry_policy", &self.retry_policy)
+            .field("query_timeout", &self.query_timeout)
             .finish()
     }
 }
 
 impl PostgresTaskRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            slow_query_threshold: DEFAULT_SLOW_QUERY_THRESHOLD,
+            retry_policy: RetryPolicy::default(),
+            query_timeout: None,
+        }
+    }
+
+    /// Log queries slower than `threshold` at warn level
+    #[must_use]
+    pub const fn with_slow_query_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_query_threshold = threshold;
+        self
+    }
+
+    /// Retry read-only queries that fail with a transient error
+    ///
+    /// Writes are never retried: a dropped connection does not tell whether they committed.
+    #[must_use]
+    pub const fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Give up on queries running past the pool's `statement_timeout`
+    ///
+    /// `timeout` should match the `statement_timeout` set on the pool's connections. The
+    /// server is expected to cancel the statement first; the client-side limit (slightly
+    /// longer) covers queries stuck anywhere else, such as on a dead connection. Both
+    /// surface as `DomainError::Timeout`. `None` disables the client-side limit.
+    #[must_use]
+    pub fn with_statement_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.query_timeout = timeout.map(|timeout| timeout + STATEMENT_TIMEOUT_GRACE);
+        self
     }
 }
 
 #[async_trait]
 impl TaskRepository for PostgresTaskRepository {
+    #[tracing::instrument(skip_all, fields(query = "insert_task", task_id = %entity.id, duration_ms = tracing::field::Empty))]
     async fn create(&self, entity: Task) -> Result<Task, DomainError> {
-        sqlx::query_as::<_, TaskRow>(
-            r#"
-            INSERT INTO tasks (id, user_id, title, description, status, priority, created_at, updated_at, completed_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
-            RETURNING id, user_id, title, description, status, priority, created_at, updated_at, completed_at
-            "#,
-        )
-        .bind(entity.id.into_inner())
-        .bind(entity.user_id.into_inner())
-        .bind(entity.title.into_inner())
-        .bind(&entity.description)
-        .bind(TaskStatusDb::from(entity.status))
-        .bind(TaskPriorityDb::from(entity.priority))
-        .bind(entity.created_at)
-        .bind(entity.updated_at)
-        .bind(entity.completed_at)
-        .fetch_one(&self.pool)
+        timed(
+            self.slow_query_threshold,
+            "insert_task",
+            bounded(
+                self.query_timeout,
+                "insert_task",
+                insert_task(&self.pool, &entity),
+            ),
+        )
         .await
-        .map_err(DomainError::from)
-        .and_then( Task::try_from)
+        .and_then(Task::try_from)
     }
 
-    async fn get(&self, id: TaskId) -> Result<Option<Task>, DomainError> {
-        sqlx::query_as::<_, TaskRow>(
-            r#"
-            SELECT id, user_id, title, description, status, priority, created_at, updated_at, completed_at
-            FROM tasks
-            WHERE id = $1
-            "#,
+    #[tracing::instrument(skip_all, fields(query = "select_task", tenant_id = %tenant, task_id = %id, duration_ms = tracing::field::Empty))]
+    async fn get(&self, tenant: TenantId, id: TaskId) -> Result<Option<Task>, DomainError> {
+        timed(
+            self.slow_query_threshold,
+            "select_task",
+            bounded(
+                self.query_timeout,
+                "select_task",
+                retry(self.retry_policy, "select_task", || {
+                    select_task(&self.pool, tenant, id)
+                }),
+            ),
         )
-        .bind(id.into_inner())
-        .fetch_optional(&self.pool)
         .await
-        .map_err(DomainError::from)
-        .and_then(|row| {
-            row.map(Task::try_from)
-                .transpose()
-        })
+        .and_then(|row| row.map(Task::try_from).transpose())
     }
 
-    async fn get_by_user(&self, user_id: UserId) -> Result<Vec<Task>, DomainError> {
-        sqlx::query_as::<_, TaskRow>(
-            r#"
-            SELECT id, user_id, title, description, status, priority, created_at, updated_at, completed_at
-            FROM tasks
-            WHERE user_id = $1
-            ORDER BY created_at DESC
-            "#,
+    #[tracing::instrument(skip_all, fields(query = "select_tasks_by_user", tenant_id = %tenant, user_id = %user_id, duration_ms = tracing::field::Empty))]
+    async fn get_by_user(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+    ) -> Result<Vec<Task>, DomainError> {
+        let query = || {
+            sqlx::query_as::<_, TaskRow>(
+                r#"
+                SELECT id, tenant_id, user_id, title, description, status, priority, created_at, updated_at, completed_at
+                FROM tasks
+                WHERE tenant_id = $1 AND user_id = $2
+                ORDER BY created_at DESC
+                "#,
+            )
+            .bind(tenant.into_inner())
+            .bind(user_id.into_inner())
+            .fetch_all(&self.pool)
+        };
+
+        timed(
+            self.slow_query_threshold,
+            "select_tasks_by_user",
+            bounded(
+                self.query_timeout,
+                "select_tasks_by_user",
+                retry(self.retry_policy, "select_tasks_by_user", query),
+            ),
         )
-        .bind(user_id.into_inner())
-        .fetch_all(&self.pool)
         .await
-        .map_err(DomainError::from)
         .and_then(|rows| {
             rows.into_iter()
                 .map(Task::try_from)
@@ -94,142 +155,653 @@ impl TaskRepository for PostgresTaskRepository {
         })
     }
 
-    async fn update(&self, entity: &Task) -> Result<(), DomainError> {
-        sqlx::query(
-            r#"
-            UPDATE tasks
-            SET title = $2, description = $3, status = $4, priority = $5, updated_at = $6, completed_at = $7
-            WHERE id = $1
-            "#,
-        )
-        .bind(entity.id.into_inner())
-        .bind(entity.title.value())
-        .bind(&entity.description)
-        .bind(TaskStatusDb::from(entity.status))
-        .bind(TaskPriorityDb::from(entity.priority))
-        .bind(entity.updated_at)
-        .bind(entity.completed_at)
-        .execute(&self.pool)
+    #[tracing::instrument(skip_all, fields(query = "select_daily_task_counts", tenant_id = %tenant, user_id = %user_id, duration_ms = tracing::field::Empty))]
+    async fn daily_counts(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        range: &StatsRange,
+    ) -> Result<Vec<DailyTaskCount>, DomainError> {
+        // Days start at midnight in the range's time zone: the bounds are converted from
+        // local midnights, and each timestamp is truncated to its local day
+        let query = || {
+            sqlx::query_as::<_, DailyCountRow>(
+                r#"
+                WITH days AS (
+                    SELECT day::date AS day
+                    FROM generate_series($2::date, $3::date, INTERVAL '1 day') AS day
+                ),
+                created AS (
+                    SELECT date_trunc('day', created_at AT TIME ZONE $4)::date AS day, COUNT(*) AS count
+                    FROM tasks
+                    WHERE tenant_id = $5 AND user_id = $1
+                      AND created_at >= $2::timestamp AT TIME ZONE $4
+                      AND created_at < ($3::date + 1)::timestamp AT TIME ZONE $4
+                    GROUP BY 1
+                ),
+                completed AS (
+                    SELECT date_trunc('day', completed_at AT TIME ZONE $4)::date AS day, COUNT(*) AS count
+                    FROM tasks
+                    WHERE tenant_id = $5 AND user_id = $1
+                      AND completed_at >= $2::timestamp AT TIME ZONE $4
+                      AND completed_at < ($3::date + 1)::timestamp AT TIME ZONE $4
+                    GROUP BY 1
+                )
+                SELECT days.day,
+                       COALESCE(created.count, 0) AS created,
+                       COALESCE(completed.count, 0) AS completed
+                FROM days
+                LEFT JOIN created USING (day)
+                LEFT JOIN completed USING (day)
+                ORDER BY days.day
+                "#,
+            )
+            .bind(user_id.into_inner())
+            .bind(range.from())
+            .bind(range.to())
+            .bind(range.time_zone().name())
+            .bind(tenant.into_inner())
+            .fetch_all(&self.pool)
+        };
+
+        timed(
+            self.slow_query_threshold,
+            "select_daily_task_counts",
+            bounded(
+                self.query_timeout,
+                "select_daily_task_counts",
+                retry(self.retry_policy, "select_daily_task_counts", query),
+            ),
+        )
         .await
-        .map_err(DomainError::from)?;
-        Ok(())
+        .map(|rows| rows.into_iter().map(DailyTaskCount::from).collect())
     }
 
-    async fn delete(&self, id: TaskId) -> Result<(), DomainError> {
-        sqlx::query("DELETE FROM tasks WHERE id = $1")
-            .bind(id.into_inner())
-            .execute(&self.pool)
-            .await
-            .map_err(DomainError::from)?;
-        Ok(())
+    #[tracing::instrument(skip_all, fields(query = "select_task_usage", tenant_id = %tenant, user_id = %user_id, duration_ms = tracing::field::Empty))]
+    async fn usage(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<TaskUsage, DomainError> {
+        timed(
+            self.slow_query_threshold,
+            "select_task_usage",
+            bounded(
+                self.query_timeout,
+                "select_task_usage",
+                retry(self.retry_policy, "select_task_usage", || {
+                    select_usage(&self.pool, tenant, user_id, since)
+                }),
+            ),
+        )
+        .await
+        .map(TaskUsage::from)
     }
 
+    #[tracing::instrument(skip_all, fields(query = "search_tasks", tenant_id = %tenant, duration_ms = tracing::field::Empty))]
+    async fn search_all(
+        &self,
+        tenant: TenantId,
+        search: &TaskSearch,
+    ) -> Result<TaskPage, DomainError> {
+        let page = || async move {
+            let mut query = search_query(SELECT_TASKS, tenant, search.filter());
+            push_order_and_page(&mut query, search);
+            query
+                .build_query_as::<TaskRow>()
+                .fetch_all(&self.pool)
+                .await
+        };
+        let count = || async move {
+            search_query("SELECT COUNT(*) FROM tasks", tenant, search.filter())
+                .build_query_scalar::<i64>()
+                .fetch_one(&self.pool)
+                .await
+        };
+
+        let rows = timed(
+            self.slow_query_threshold,
+            "search_tasks",
+            bounded(
+                self.query_timeout,
+                "search_tasks",
+                retry(self.retry_policy, "search_tasks", page),
+            ),
+        )
+        .await?;
+        let total = timed(
+            self.slow_query_threshold,
+            "count_searched_tasks",
+            bounded(
+                self.query_timeout,
+                "count_searched_tasks",
+                retry(self.retry_policy, "count_searched_tasks", count),
+            ),
+        )
+        .await?;
+
+        Ok(TaskPage {
+            tasks: rows
+                .into_iter()
+                .map(Task::try_from)
+                .collect::<Result<_, _>>()?,
+            total: u64::try_from(total).unwrap_or_default(),
+        })
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "update_task", task_id = %entity.id, duration_ms = tracing::field::Empty))]
+    async fn update(&self, entity: &Task) -> Result<(), DomainError> {
+        let result = timed(
+            self.slow_query_threshold,
+            "update_task",
+            bounded(
+                self.query_timeout,
+                "update_task",
+                update_task(&self.pool, entity),
+            ),
+        )
+        .await?;
+        expect_affected(result, entity.id)
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "delete_task", tenant_id = %tenant, task_id = %id, duration_ms = tracing::field::Empty))]
+    async fn delete(&self, tenant: TenantId, id: TaskId) -> Result<(), DomainError> {
+        let result = timed(
+            self.slow_query_threshold,
+            "delete_task",
+            bounded(
+                self.query_timeout,
+                "delete_task",
+                delete_task(&self.pool, tenant, id),
+            ),
+        )
+        .await?;
+        expect_affected(result, id)
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "health_check", duration_ms = tracing::field::Empty))]
     async fn health_check(&self) -> Result<(), DomainError> {
-        sqlx::query("SELECT 1")
-            .execute(&self.pool)
-            .await
-            .map_err(DomainError::from)?;
+        timed(
+            self.slow_query_threshold,
+            "health_check",
+            bounded(
+                self.query_timeout,
+                "health_check",
+                retry(self.retry_policy, "health_check", || {
+                    sqlx::query("SELECT 1").execute(&self.pool)
+                }),
+            ),
+        )
+        .await?;
         Ok(())
     }
+
+    #[tracing::instrument(skip_all, fields(query = "begin", duration_ms = tracing::field::Empty))]
+    async fn begin(&self) -> Result<Box<dyn TaskUnitOfWork>, DomainError> {
+        let tx = timed(
+            self.slow_query_threshold,
+            "begin",
+            bounded(self.query_timeout, "begin", self.pool.begin()),
+        )
+        .await?;
+
+        Ok(Box::new(PostgresTaskUnitOfWork {
+            tx,
+            slow_query_threshold: self.slow_query_threshold,
+            query_timeout: self.query_timeout,
+        }))
+    }
 }
 
-// Infrastructure-specific enum types for database mapping
-#[derive(Debug, Clone, Copy, sqlx::Type)]
-#[sqlx(type_name = "task_status", rename_all = "SCREAMING_SNAKE_CASE")]
-enum TaskStatusDb {
-    Pending,
-    InProgress,
-    Completed,
-    Cancelled,
+/// Unit of work over a single Postgres transaction
+///
+/// Created by [`PostgresTaskRepository::begin`]. sqlx rolls the transaction back if it is
+/// dropped before [`commit`](TaskUnitOfWork::commit).
+pub struct PostgresTaskUnitOfWork {
+    tx: Transaction<'static, Postgres>,
+    slow_query_threshold: Duration,
+    query_timeout: Option<Duration>,
 }
 
-#[derive(Debug, Clone, Copy, sqlx::Type)]
-#[sqlx(type_name = "task_priority", rename_all = "SCREAMING_SNAKE_CASE")]
-enum TaskPriorityDb {
-    Low,
-    Medium,
-    High,
-    Critical,
+impl Debug for PostgresTaskUnitOfWork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PostgresTaskUnitOfWork")
+            .field("tx", &"Transaction")
+            .field("slow_query_threshold", &self.slow_query_threshold)
+            .field("query_timeout", &self.query_timeout)
+            .finish()
+    }
 }
 
-impl From<TaskStatusDb> for TaskStatus {
-    fn from(status: TaskStatusDb) -> Self {
-        match status {
-            TaskStatusDb::Pending => TaskStatus::Pending,
-            TaskStatusDb::InProgress => TaskStatus::InProgress,
-            TaskStatusDb::Completed => TaskStatus::Completed,
-            TaskStatusDb::Cancelled => TaskStatus::Cancelled,
-        }
+#[async_trait]
+impl TaskUnitOfWork for PostgresTaskUnitOfWork {
+    #[tracing::instrument(skip_all, fields(query = "insert_task", task_id = %entity.id, duration_ms = tracing::field::Empty))]
+    async fn create(&mut self, entity: Task) -> Result<Task, DomainError> {
+        timed(
+            self.slow_query_threshold,
+            "insert_task",
+            bounded(
+                self.query_timeout,
+                "insert_task",
+                insert_task(&mut *self.tx, &entity),
+            ),
+        )
+        .await
+        .and_then(Task::try_from)
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "lock_task", tenant_id = %tenant, task_id = %id, duration_ms = tracing::field::Empty))]
+    async fn get(&mut self, tenant: TenantId, id: TaskId) -> Result<Option<Task>, DomainError> {
+        timed(
+            self.slow_query_threshold,
+            "lock_task",
+            bounded(
+                self.query_timeout,
+                "lock_task",
+                lock_task(&mut *self.tx, tenant, id),
+            ),
+        )
+        .await
+        .and_then(|row| row.map(Task::try_from).transpose())
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "lock_task_usage", tenant_id = %tenant, user_id = %user_id, duration_ms = tracing::field::Empty))]
+    async fn lock_usage(
+        &mut self,
+        tenant: TenantId,
+        user_id: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<TaskUsage, DomainError> {
+        timed(
+            self.slow_query_threshold,
+            "lock_task_usage",
+            bounded(self.query_timeout, "lock_task_usage", async {
+                lock_user_quota(&mut *self.tx, tenant, user_id).await?;
+                select_usage(&mut *self.tx, tenant, user_id, since).await
+            }),
+        )
+        .await
+        .map(TaskUsage::from)
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "update_task", task_id = %entity.id, duration_ms = tracing::field::Empty))]
+    async fn update(&mut self, entity: &Task) -> Result<(), DomainError> {
+        let result = timed(
+            self.slow_query_threshold,
+            "update_task",
+            bounded(
+                self.query_timeout,
+                "update_task",
+                update_task(&mut *self.tx, entity),
+            ),
+        )
+        .await?;
+        expect_affected(result, entity.id)
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "delete_task", tenant_id = %tenant, task_id = %id, duration_ms = tracing::field::Empty))]
+    async fn delete(&mut self, tenant: TenantId, id: TaskId) -> Result<(), DomainError> {
+        let result = timed(
+            self.slow_query_threshold,
+            "delete_task",
+            bounded(
+                self.query_timeout,
+                "delete_task",
+                delete_task(&mut *self.tx, tenant, id),
+            ),
+        )
+        .await?;
+        expect_affected(result, id)
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "insert_job", job_id = %job.id, kind = %job.kind, duration_ms = tracing::field::Empty))]
+    async fn enqueue(&mut self, job: NewJob) -> Result<JobId, DomainError> {
+        timed(
+            self.slow_query_threshold,
+            "insert_job",
+            bounded(
+                self.query_timeout,
+                "insert_job",
+                insert_job(&mut *self.tx, &job),
+            ),
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "insert_priority_change", task_id = %change.task_id, duration_ms = tracing::field::Empty))]
+    async fn record_priority_change(&mut self, change: &PriorityChange) -> Result<(), DomainError> {
+        timed(
+            self.slow_query_threshold,
+            "insert_priority_change",
+            bounded(
+                self.query_timeout,
+                "insert_priority_change",
+                insert_priority_change(&mut *self.tx, change),
+            ),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "commit", duration_ms = tracing::field::Empty))]
+    async fn commit(self: Box<Self>) -> Result<(), DomainError> {
+        timed(
+            self.slow_query_threshold,
+            "commit",
+            bounded(self.query_timeout, "commit", self.tx.commit()),
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip_all, fields(query = "rollback", duration_ms = tracing::field::Empty))]
+    async fn rollback(self: Box<Self>) -> Result<(), DomainError> {
+        timed(
+            self.slow_query_threshold,
+            "rollback",
+            bounded(self.query_timeout, "rollback", self.tx.rollback()),
+        )
+        .await
     }
 }
 
-impl From<TaskStatus> for TaskStatusDb {
-    fn from(status: TaskStatus) -> Self {
-        match status {
-            TaskStatus::Pending => TaskStatusDb::Pending,
-            TaskStatus::InProgress => TaskStatusDb::InProgress,
-            TaskStatus::Completed => TaskStatusDb::Completed,
-            TaskStatus::Cancelled => TaskStatusDb::Cancelled,
+/// Run a query under an optional client-side time limit, converting its error to a
+/// `DomainError`
+async fn bounded<T>(
+    timeout: Option<Duration>,
+    query: &'static str,
+    future: impl Future<Output = sqlx::Result<T>>,
+) -> Result<T, DomainError> {
+    let Some(timeout) = timeout else {
+        return future.await.map_err(DomainError::from);
+    };
+
+    match tokio::time::timeout(timeout, future).await {
+        Ok(result) => result.map_err(DomainError::from),
+        Err(_) => {
+            metrics::counter!("db_query_timeouts_total", "query" => query).increment(1);
+            tracing::warn!(
+                query,
+                timeout_ms = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX),
+                "Database query timed out"
+            );
+            Err(DomainError::timeout(format!("Database query {query}")))
         }
     }
 }
 
-impl From<TaskPriorityDb> for TaskPriority {
-    fn from(priority: TaskPriorityDb) -> Self {
-        match priority {
-            TaskPriorityDb::Low => TaskPriority::Low,
-            TaskPriorityDb::Medium => TaskPriority::Medium,
-            TaskPriorityDb::High => TaskPriority::High,
-            TaskPriorityDb::Critical => TaskPriority::Critical,
-        }
+/// Map an update or delete that matched no row to `DomainError::NotFound`
+fn expect_affected(result: PgQueryResult, id: TaskId) -> Result<(), DomainError> {
+    if result.rows_affected() == 0 {
+        return Err(DomainError::not_found("Task", id.to_string()));
     }
+    Ok(())
+}
+
+// Statements shared by the pool-backed repository and the unit of work
+
+async fn insert_task<'e>(executor: impl PgExecutor<'e>, entity: &Task) -> sqlx::Result<TaskRow> {
+    sqlx::query_as::<_, TaskRow>(
+        r#"
+        INSERT INTO tasks (id, tenant_id, user_id, title, description, status, priority, created_at, updated_at, completed_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        RETURNING id, tenant_id, user_id, title, description, status, priority, created_at, updated_at, completed_at
+        "#,
+    )
+    .bind(entity.id.into_inner())
+    .bind(entity.tenant_id.into_inner())
+    .bind(entity.user_id.into_inner())
+    .bind(entity.title.value())
+    .bind(&entity.description)
+    .bind(TaskStatusDb::from(entity.status))
+    .bind(TaskPriorityDb::from(entity.priority))
+    .bind(entity.created_at)
+    .bind(entity.updated_at)
+    .bind(entity.completed_at)
+    .fetch_one(executor)
+    .await
+}
+
+async fn select_task<'e>(
+    executor: impl PgExecutor<'e>,
+    tenant: TenantId,
+    id: TaskId,
+) -> sqlx::Result<Option<TaskRow>> {
+    sqlx::query_as::<_, TaskRow>(
+        r#"
+        SELECT id, tenant_id, user_id, title, description, status, priority, created_at, updated_at, completed_at
+        FROM tasks
+        WHERE id = $1 AND tenant_id = $2
+        "#,
+    )
+    .bind(id.into_inner())
+    .bind(tenant.into_inner())
+    .fetch_optional(executor)
+    .await
 }
 
-impl From<TaskPriority> for TaskPriorityDb {
-    fn from(priority: TaskPriority) -> Self {
-        match priority {
-            TaskPriority::Low => TaskPriorityDb::Low,
-            TaskPriority::Medium => TaskPriorityDb::Medium,
-            TaskPriority::High => TaskPriorityDb::High,
-            TaskPriority::Critical => TaskPriorityDb::Critical,
+/// [`select_task`], locking the row until the transaction ends so that concurrent
+/// read-modify-write cycles on the task run one after another
+async fn lock_task<'e>(
+    executor: impl PgExecutor<'e>,
+    tenant: TenantId,
+    id: TaskId,
+) -> sqlx::Result<Option<TaskRow>> {
+    sqlx::query_as::<_, TaskRow>(
+        r#"
+        SELECT id, tenant_id, user_id, title, description, status, priority, created_at, updated_at, completed_at
+        FROM tasks
+        WHERE id = $1 AND tenant_id = $2
+        FOR UPDATE
+        "#,
+    )
+    .bind(id.into_inner())
+    .bind(tenant.into_inner())
+    .fetch_optional(executor)
+    .await
+}
+
+/// Take the transaction-scoped advisory lock guarding the quotas of `user_id` in `tenant`,
+/// waiting while another transaction holds it
+async fn lock_user_quota<'e>(
+    executor: impl PgExecutor<'e>,
+    tenant: TenantId,
+    user_id: UserId,
+) -> sqlx::Result<()> {
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1::text || '/' || $2::text, 0))")
+        .bind(tenant.into_inner())
+        .bind(user_id.into_inner())
+        .execute(executor)
+        .await
+        .map(|_| ())
+}
+
+/// Open tasks of `user_id`, and the tasks they created from `since` on
+async fn select_usage<'e>(
+    executor: impl PgExecutor<'e>,
+    tenant: TenantId,
+    user_id: UserId,
+    since: DateTime<Utc>,
+) -> sqlx::Result<UsageRow> {
+    // Both counts are answered from idx_tasks_tenant_id_user_id, which includes the status
+    sqlx::query_as::<_, UsageRow>(
+        r#"
+        SELECT COUNT(*) FILTER (WHERE status IN ('PENDING', 'IN_PROGRESS')) AS open,
+               COUNT(*) FILTER (WHERE created_at >= $3) AS created
+        FROM tasks
+        WHERE tenant_id = $1 AND user_id = $2
+        "#,
+    )
+    .bind(tenant.into_inner())
+    .bind(user_id.into_inner())
+    .bind(since)
+    .fetch_one(executor)
+    .await
+}
+
+async fn update_task<'e>(
+    executor: impl PgExecutor<'e>,
+    entity: &Task,
+) -> sqlx::Result<PgQueryResult> {
+    sqlx::query(
+        r#"
+        UPDATE tasks
+        SET title = $3, description = $4, status = $5, priority = $6, updated_at = $7, completed_at = $8
+        WHERE id = $1 AND tenant_id = $2
+        "#,
+    )
+    .bind(entity.id.into_inner())
+    .bind(entity.tenant_id.into_inner())
+    .bind(entity.title.value())
+    .bind(&entity.description)
+    .bind(TaskStatusDb::from(entity.status))
+    .bind(TaskPriorityDb::from(entity.priority))
+    .bind(entity.updated_at)
+    .bind(entity.completed_at)
+    .execute(executor)
+    .await
+}
+
+async fn insert_priority_change<'e>(
+    executor: impl PgExecutor<'e>,
+    change: &PriorityChange,
+) -> sqlx::Result<PgQueryResult> {
+    sqlx::query(
+        r#"
+        INSERT INTO priority_changes (task_id, tenant_id, from_priority, to_priority, reason, changed_by, changed_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(change.task_id.into_inner())
+    .bind(change.tenant_id.into_inner())
+    .bind(TaskPriorityDb::from(change.from))
+    .bind(TaskPriorityDb::from(change.to))
+    .bind(&change.reason)
+    .bind(change.changed_by.into_inner())
+    .bind(change.changed_at)
+    .execute(executor)
+    .await
+}
+
+async fn delete_task<'e>(
+    executor: impl PgExecutor<'e>,
+    tenant: TenantId,
+    id: TaskId,
+) -> sqlx::Result<PgQueryResult> {
+    sqlx::query("DELETE FROM tasks WHERE id = $1 AND tenant_id = $2")
+        .bind(id.into_inner())
+        .bind(tenant.into_inner())
+        .execute(executor)
+        .await
+}
+
+const SELECT_TASKS: &str = "SELECT id, tenant_id, user_id, title, description, status, priority, created_at, updated_at, completed_at FROM tasks";
+
+/// `select` restricted to the tasks of `tenant` matching `filter`
+///
+/// Conditions are only added for the filter's typed fields, and every value is bound as a
+/// parameter, so no user input becomes part of the SQL text.
+fn search_query(
+    select: &'static str,
+    tenant: TenantId,
+    filter: &TaskFilter,
+) -> QueryBuilder<'static, Postgres> {
+    let mut query = QueryBuilder::new(select);
+    query
+        .push(" WHERE tenant_id = ")
+        .push_bind(tenant.into_inner());
+    if let Some(user_id) = filter.user_id {
+        query
+            .push(" AND user_id = ")
+            .push_bind(user_id.into_inner());
+    }
+    if let Some(status) = filter.status {
+        query
+            .push(" AND status = ")
+            .push_bind(TaskStatusDb::from(status));
+    }
+    if let Some(priority) = filter.priority {
+        query
+            .push(" AND priority = ")
+            .push_bind(TaskPriorityDb::from(priority));
+    }
+    if let Some(from) = filter.created_from {
+        query.push(" AND created_at >= ").push_bind(from);
+    }
+    if let Some(before) = filter.created_before {
+        query.push(" AND created_at < ").push_bind(before);
+    }
+    if let Some(title) = &filter.title {
+        query
+            .push(" AND title ILIKE ")
+            .push_bind(format!("%{}%", escape_like(title)));
+    }
+    if let Some(fragment) = &filter.id_fragment {
+        query
+            .push(" AND id::text LIKE ")
+            .push_bind(format!("%{}%", escape_like(fragment)));
+    }
+    query
+}
+
+/// Append the sort order, with ids ascending on ties, and the page bounds of `search`
+fn push_order_and_page(query: &mut QueryBuilder<'static, Postgres>, search: &TaskSearch) {
+    let sort = search.sort();
+    // The C collation compares code points, as `TaskSort::compare` does
+    let column = match sort.field {
+        TaskSortField::CreatedAt => "created_at",
+        TaskSortField::UpdatedAt => "updated_at",
+        TaskSortField::Priority => "priority",
+        TaskSortField::Title => "title COLLATE \"C\"",
+    };
+    let direction = match sort.direction {
+        SortDirection::Asc => "ASC",
+        SortDirection::Desc => "DESC",
+    };
+    query
+        .push(format_args!(" ORDER BY {column} {direction}, id LIMIT "))
+        .push_bind(i64::from(search.limit()))
+        .push(" OFFSET ")
+        .push_bind(i64::try_from(search.offset()).unwrap_or(i64::MAX));
+}
+
+/// `value` with the `LIKE` wildcards and the escape character matched literally
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[derive(sqlx::FromRow)]
+struct DailyCountRow {
+    day: chrono::NaiveDate,
+    created: i64,
+    completed: i64,
+}
+
+impl From<DailyCountRow> for DailyTaskCount {
+    fn from(row: DailyCountRow) -> Self {
+        Self {
+            date: row.day,
+            created: row.created,
+            completed: row.completed,
         }
     }
 }
 
 #[derive(sqlx::FromRow)]
-struct TaskRow {
-    id: Uuid,
-    user_id: Uuid,
-    title: String,
-    description: Option<String>,
-    status: TaskStatusDb,
-    priority: TaskPriorityDb,
-    created_at: chrono::DateTime<chrono::Utc>,
-    updated_at: chrono::DateTime<chrono::Utc>,
-    completed_at: Option<chrono::DateTime<chrono::Utc>>,
-}
-
-impl TryFrom<TaskRow> for Task {
-    type Error = DomainError;
-
-    fn try_from(row: TaskRow) -> Result<Self, Self::Error> {
-        use crate::domain::task::models::Title;
-
-        Ok(Self {
-            id: TaskId::from(row.id),
-            user_id: UserId::from(row.user_id),
-            title: Title::new(row.title).map_err(|e| {
-                DomainError::external_error(format!(
-                    "Invalid title data in database: {}. This indicates data corruption or migration issue.",
-                    e
-                ))
-            })?,
-            description: row.description,
-            status: row.status.into(),
-            priority: row.priority.into(),
-            created_at: row.created_at,
-            updated_at: row.updated_at,
-            completed_at: row.completed_at,
-        })
+struct UsageRow {
+    open: i64,
+    created: i64,
+}
+
+impl From<UsageRow> for TaskUsage {
+    fn from(row: UsageRow) -> Self {
+        // Counts are never negative
+        Self {
+            open: row.open.try_into().unwrap_or_default(),
+            created: row.created.try_into().unwrap_or_default(),
+        }
     }
 }
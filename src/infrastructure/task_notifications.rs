@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use sqlx::{
+    postgres::{PgListener, PgNotification},
+    PgPool,
+};
+use tokio::{sync::broadcast, task::JoinHandle};
+
+use crate::domain::task::models::TaskChange;
+
+/// Channel the `notify_task_change` trigger publishes on
+pub const TASK_CHANGES_CHANNEL: &str = "task_changes";
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Spawn a background task forwarding `task_changes` notifications to `sender`
+///
+/// The listener holds its own connection outside the pool. When that connection drops it
+/// reconnects with exponential backoff; notifications sent while disconnected are lost.
+pub fn spawn_task_change_listener(
+    pool: PgPool,
+    sender: broadcast::Sender<TaskChange>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut backoff = INITIAL_BACKOFF;
+
+        loop {
+            let delay = match listen(&pool, &sender).await {
+                // The connection worked, so start over from the shortest delay
+                Ok(()) => {
+                    backoff = INITIAL_BACKOFF;
+                    backoff
+                }
+                Err(e) => {
+                    metrics::counter!("task_change_listener_errors_total").increment(1);
+                    tracing::warn!(
+                        error_message = %e,
+                        retry_in_ms = u64::try_from(backoff.as_millis()).unwrap_or(u64::MAX),
+                        "Task change listener failed, reconnecting"
+                    );
+                    let delay = backoff;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                    delay
+                }
+            };
+            tokio::time::sleep(delay).await;
+        }
+    })
+}
+
+/// Listen until the connection is lost (`Ok`) or cannot be (re)established (`Err`)
+async fn listen(pool: &PgPool, sender: &broadcast::Sender<TaskChange>) -> sqlx::Result<()> {
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(TASK_CHANGES_CHANNEL).await?;
+    tracing::info!(channel = TASK_CHANGES_CHANNEL, "Listening for task changes");
+
+    // `try_recv` reports a lost connection as `None` instead of silently reconnecting, so
+    // reconnection goes through the backoff above
+    while let Some(notification) = listener.try_recv().await? {
+        forward(&notification, sender);
+    }
+
+    tracing::warn!(
+        channel = TASK_CHANGES_CHANNEL,
+        "Task change listener lost its connection"
+    );
+    Ok(())
+}
+
+fn forward(notification: &PgNotification, sender: &broadcast::Sender<TaskChange>) {
+    match serde_json::from_str::<TaskChange>(notification.payload()) {
+        // Sending only fails when nobody is subscribed, which is fine
+        Ok(change) => {
+            let _ = sender.send(change);
+        }
+        Err(e) => tracing::error!(
+            error_message = %e,
+            payload = notification.payload(),
+            "Malformed task change notification"
+        ),
+    }
+}
@@ -0,0 +1,126 @@
+// <feature:api>
+use axum::http::HeaderMap;
+// </feature:api>
+use opentelemetry::{global, trace::TracerProvider};
+// <feature:api>
+use opentelemetry_http::{HeaderExtractor, HeaderInjector};
+// </feature:api>
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    propagation::TraceContextPropagator,
+    trace::{Sampler, SdkTracer, SdkTracerProvider},
+    Resource,
+};
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+// <feature:api>
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+// </feature:api>
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::config::TelemetryConfig;
+
+/// Build the OTLP tracer provider when an endpoint is configured
+///
+/// Also installs the W3C `traceparent` propagator globally so incoming and outgoing
+/// requests join the same trace. Returns `None` when export is disabled.
+pub fn init_tracer_provider(config: &TelemetryConfig) -> anyhow::Result<Option<SdkTracerProvider>> {
+    let Some(endpoint) = config.otlp_endpoint.as_deref() else {
+        return Ok(None);
+    };
+
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()?;
+
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            config.sample_ratio,
+        ))))
+        .with_resource(
+            Resource::builder()
+                .with_service_name(config.service_name.clone())
+                .build(),
+        )
+        .build();
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_tracer_provider(provider.clone());
+
+    Ok(Some(provider))
+}
+
+/// `tracing` layer that turns spans into OpenTelemetry spans exported by `provider`
+pub fn layer<S>(
+    provider: &SdkTracerProvider,
+    config: &TelemetryConfig,
+) -> OpenTelemetryLayer<S, SdkTracer>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer(config.service_name.clone()))
+}
+
+// <feature:api>
+/// Make `span` a child of the trace described by the incoming `traceparent` headers
+pub fn set_parent_from_headers(span: &tracing::Span, headers: &HeaderMap) {
+    let parent =
+        global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)));
+    // Without an OpenTelemetry layer there is no span to attach the parent to
+    let _ = span.set_parent(parent);
+}
+
+/// Add `traceparent` headers for the current span to an outgoing request
+pub fn inject_current_context(headers: &mut HeaderMap) {
+    let context = tracing::Span::current().context();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut HeaderInjector(headers));
+    });
+}
+// </feature:api>
+
+// <feature:api>
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_incoming_traceparent_is_propagated_to_outgoing_headers() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber =
+            tracing_subscriber::registry().with(layer(&provider, &TelemetryConfig::default()));
+
+        let mut incoming = HeaderMap::new();
+        incoming.insert(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+                .parse()
+                .unwrap(),
+        );
+
+        let outgoing = tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("request");
+            set_parent_from_headers(&span, &incoming);
+            let _guard = span.enter();
+
+            let mut outgoing = HeaderMap::new();
+            inject_current_context(&mut outgoing);
+            outgoing
+        });
+
+        let traceparent = outgoing["traceparent"].to_str().unwrap();
+        assert!(
+            traceparent.starts_with("00-4bf92f3577b34da6a3ce929d0e0e4736-"),
+            "Outgoing request should continue the incoming trace, got {traceparent}"
+        );
+        assert!(
+            !traceparent.contains("00f067aa0ba902b7"),
+            "Outgoing request should carry the local span id, not the remote parent"
+        );
+    }
+}
+// </feature:api>
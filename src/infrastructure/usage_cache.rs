@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use moka::{future::Cache, Expiry};
+use std::{
+    fmt::Debug,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    common::{TenantId, UserId},
+    config::QuotaConfig,
+    domain::{
+        errors::DomainError,
+        interfaces::task_repository::TaskRepository,
+        quota::models::{month_start, TaskUsage},
+    },
+};
+
+/// A user in a tenant, and the start of the month their created tasks are counted from
+type UsageKey = (TenantId, UserId, DateTime<Utc>);
+
+/// Recently counted task usage, so quota checks do not count a user's tasks before every
+/// create
+///
+/// A count is reused until `ttl` after it was taken. Tasks created through this process are
+/// added to it without pushing that deadline back, so a user creating tasks in a burst is
+/// counted again regularly; tasks created elsewhere, and status changes, show once it passes.
+#[derive(Clone)]
+pub struct UsageCache {
+    cache: Cache<UsageKey, TaskUsage>,
+}
+
+impl Debug for UsageCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UsageCache")
+            .field("entry_count", &self.cache.entry_count())
+            .finish()
+    }
+}
+
+/// Expires an entry `ttl` after it was counted, whatever was added to it since
+struct CountedFor(Duration);
+
+impl Expiry<UsageKey, TaskUsage> for CountedFor {
+    fn expire_after_create(
+        &self,
+        _key: &UsageKey,
+        _value: &TaskUsage,
+        _created_at: Instant,
+    ) -> Option<Duration> {
+        Some(self.0)
+    }
+}
+
+impl UsageCache {
+    pub fn new(max_capacity: u64, ttl: Duration) -> Self {
+        Self {
+            cache: Cache::builder()
+                .max_capacity(max_capacity)
+                .expire_after(CountedFor(ttl))
+                .build(),
+        }
+    }
+
+    pub fn from_config(config: &QuotaConfig) -> Self {
+        Self::new(
+            config.usage_cache_max_capacity,
+            Duration::from_millis(config.usage_cache_ttl_ms),
+        )
+    }
+
+    /// Usage of `user_id` in `tenant` in the month of `now`, counted by `repo` unless
+    /// counted recently
+    pub async fn task_usage(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        now: DateTime<Utc>,
+        repo: &dyn TaskRepository,
+    ) -> Result<TaskUsage, DomainError> {
+        let key = (tenant, user_id, month_start(now));
+        if let Some(usage) = self.cache.get(&key).await {
+            return Ok(usage);
+        }
+        let usage = repo.usage(tenant, user_id, key.2).await?;
+        self.cache.insert(key, usage).await;
+        Ok(usage)
+    }
+
+    /// Add a task `user_id` just created to their cached usage, when there is one
+    pub async fn record_created(&self, tenant: TenantId, user_id: UserId, now: DateTime<Utc>) {
+        let key = (tenant, user_id, month_start(now));
+        if let Some(usage) = self.cache.get(&key).await {
+            self.cache.insert(key, usage.with_one_more()).await;
+        }
+    }
+}
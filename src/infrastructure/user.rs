@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use super::sql::UserRow;
+use crate::{
+    common::UserId,
+    domain::{
+        errors::DomainError, interfaces::user_repository::UserRepository, user::models::User,
+    },
+};
+
+/// [`UserRepository`] backed by the `users` table
+#[derive(Debug, Clone)]
+pub struct PostgresUserRepository {
+    pool: PgPool,
+}
+
+impl PostgresUserRepository {
+    #[must_use]
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UserRepository for PostgresUserRepository {
+    #[tracing::instrument(skip_all, fields(user_id = %entity.id))]
+    async fn create(&self, entity: User) -> Result<User, DomainError> {
+        let row = sqlx::query_as::<_, UserRow>(
+            r#"
+            INSERT INTO users (id, email, display_name, created_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, email, display_name, created_at
+            "#,
+        )
+        .bind(entity.id.into_inner())
+        .bind(entity.email.value())
+        .bind(&entity.display_name)
+        .bind(entity.created_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.into())
+    }
+
+    #[tracing::instrument(skip(self), fields(user_id = %id))]
+    async fn get(&self, id: UserId) -> Result<Option<User>, DomainError> {
+        let row = sqlx::query_as::<_, UserRow>(
+            r#"
+            SELECT id, email, display_name, created_at
+            FROM users
+            WHERE id = $1
+            "#,
+        )
+        .bind(id.into_inner())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+}
@@ -0,0 +1,249 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json, PgPool};
+
+use super::sql::{to_db_count, WebhookDeliveryRow, WebhookRow};
+use crate::{
+    common::TenantId,
+    domain::{
+        errors::DomainError,
+        interfaces::webhook_repository::WebhookRepository,
+        task::models::TaskEventType,
+        webhook::models::{Webhook, WebhookDelivery, WebhookId},
+    },
+};
+
+/// [`WebhookRepository`] backed by the `webhooks` and `webhook_deliveries` tables
+#[derive(Debug, Clone)]
+pub struct PostgresWebhookRepository {
+    pool: PgPool,
+}
+
+impl PostgresWebhookRepository {
+    #[must_use]
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Delete delivery attempts made before `older_than`, returning how many
+    #[tracing::instrument(skip(self))]
+    pub async fn purge_deliveries(&self, older_than: DateTime<Utc>) -> Result<u64, DomainError> {
+        let result = sqlx::query("DELETE FROM webhook_deliveries WHERE attempted_at < $1")
+            .bind(older_than)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for PostgresWebhookRepository {
+    #[tracing::instrument(skip_all, fields(webhook_id = %entity.id))]
+    async fn create(&self, entity: Webhook) -> Result<Webhook, DomainError> {
+        let row = sqlx::query_as::<_, WebhookRow>(
+            r#"
+            INSERT INTO webhooks (id, tenant_id, url, secret, event_types, active, consecutive_failures, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id, tenant_id, url, secret, event_types, active, consecutive_failures, created_at, updated_at
+            "#,
+        )
+        .bind(entity.id.into_inner())
+        .bind(entity.tenant_id.into_inner())
+        .bind(entity.url.value())
+        .bind(&entity.secret)
+        .bind(Json(&entity.event_types))
+        .bind(entity.active)
+        .bind(to_db_count(entity.consecutive_failures))
+        .bind(entity.created_at)
+        .bind(entity.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.into())
+    }
+
+    #[tracing::instrument(skip(self), fields(tenant_id = %tenant, webhook_id = %id))]
+    async fn get(&self, tenant: TenantId, id: WebhookId) -> Result<Option<Webhook>, DomainError> {
+        let row = sqlx::query_as::<_, WebhookRow>(
+            r#"
+            SELECT id, tenant_id, url, secret, event_types, active, consecutive_failures, created_at, updated_at
+            FROM webhooks
+            WHERE id = $1 AND tenant_id = $2
+            "#,
+        )
+        .bind(id.into_inner())
+        .bind(tenant.into_inner())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(Into::into))
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list(&self, tenant: TenantId) -> Result<Vec<Webhook>, DomainError> {
+        let rows = sqlx::query_as::<_, WebhookRow>(
+            r#"
+            SELECT id, tenant_id, url, secret, event_types, active, consecutive_failures, created_at, updated_at
+            FROM webhooks
+            WHERE tenant_id = $1
+            ORDER BY created_at, id
+            "#,
+        )
+        .bind(tenant.into_inner())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn count(&self, tenant: TenantId) -> Result<u64, DomainError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM webhooks WHERE tenant_id = $1")
+            .bind(tenant.into_inner())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count.try_into().unwrap_or_default())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn subscribed_to(
+        &self,
+        tenant: TenantId,
+        event_type: TaskEventType,
+    ) -> Result<Vec<Webhook>, DomainError> {
+        let rows = sqlx::query_as::<_, WebhookRow>(
+            r#"
+            SELECT id, tenant_id, url, secret, event_types, active, consecutive_failures, created_at, updated_at
+            FROM webhooks
+            WHERE tenant_id = $1 AND active AND event_types ? $2
+            ORDER BY created_at, id
+            "#,
+        )
+        .bind(tenant.into_inner())
+        .bind(event_type.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    #[tracing::instrument(skip_all, fields(webhook_id = %entity.id))]
+    async fn update(&self, entity: &Webhook) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE webhooks
+            SET url = $2, event_types = $3, active = $4, consecutive_failures = $5, updated_at = $6
+            WHERE id = $1 AND tenant_id = $7
+            "#,
+        )
+        .bind(entity.id.into_inner())
+        .bind(entity.url.value())
+        .bind(Json(&entity.event_types))
+        .bind(entity.active)
+        .bind(to_db_count(entity.consecutive_failures))
+        .bind(entity.updated_at)
+        .bind(entity.tenant_id.into_inner())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::not_found("Webhook", entity.id.to_string()));
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(tenant_id = %tenant, webhook_id = %id))]
+    async fn delete(&self, tenant: TenantId, id: WebhookId) -> Result<(), DomainError> {
+        let result = sqlx::query("DELETE FROM webhooks WHERE id = $1 AND tenant_id = $2")
+            .bind(id.into_inner())
+            .bind(tenant.into_inner())
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::not_found("Webhook", id.to_string()));
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(webhook_id = %delivery.webhook_id, attempt = delivery.attempt))]
+    async fn record_delivery(&self, delivery: &WebhookDelivery) -> Result<(), DomainError> {
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_deliveries
+                (id, webhook_id, event_id, event_type, attempt, status_code, error, succeeded, duration_ms, attempted_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+        )
+        .bind(delivery.id)
+        .bind(delivery.webhook_id.into_inner())
+        .bind(delivery.event_id)
+        .bind(delivery.event_type.as_str())
+        .bind(to_db_count(delivery.attempt))
+        .bind(delivery.status_code.map(i32::from))
+        .bind(&delivery.error)
+        .bind(delivery.succeeded)
+        .bind(i64::try_from(delivery.duration_ms).unwrap_or(i64::MAX))
+        .bind(delivery.attempted_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(webhook_id = %id))]
+    async fn deliveries(
+        &self,
+        id: WebhookId,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, DomainError> {
+        let rows = sqlx::query_as::<_, WebhookDeliveryRow>(
+            r#"
+            SELECT id, webhook_id, event_id, event_type, attempt, status_code, error, succeeded, duration_ms, attempted_at
+            FROM webhook_deliveries
+            WHERE webhook_id = $1
+            ORDER BY attempted_at DESC, attempt DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(id.into_inner())
+        .bind(i64::from(limit))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(WebhookDelivery::try_from).collect()
+    }
+
+    #[tracing::instrument(skip(self), fields(webhook_id = %id))]
+    async fn record_success(&self, id: WebhookId) -> Result<(), DomainError> {
+        sqlx::query("UPDATE webhooks SET consecutive_failures = 0 WHERE id = $1")
+            .bind(id.into_inner())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), fields(webhook_id = %id))]
+    async fn record_failure(&self, id: WebhookId, disable_after: u32) -> Result<bool, DomainError> {
+        // Both expressions read the count from before this update, so counting and
+        // deactivating happen in one statement without a race between deliveries
+        let active: Option<bool> = sqlx::query_scalar(
+            r#"
+            UPDATE webhooks
+            SET consecutive_failures = consecutive_failures + 1,
+                active = active AND consecutive_failures + 1 < $2
+            WHERE id = $1
+            RETURNING active
+            "#,
+        )
+        .bind(id.into_inner())
+        .bind(i64::from(disable_after))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        active.ok_or_else(|| DomainError::not_found("Webhook", id.to_string()))
+    }
+}
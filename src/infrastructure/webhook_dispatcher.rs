@@ -0,0 +1,309 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tracing::{debug, warn, Instrument};
+use uuid::Uuid;
+
+use super::{
+    circuit_breaker::{BreakerOpen, CircuitBreaker, CircuitBreakers},
+    error_reporting,
+    http::HttpClient,
+    retry::RetryPolicy,
+};
+use crate::{
+    config::{HttpClientConfig, WebhookConfig},
+    domain::{
+        errors::{DomainError, ExternalSystem},
+        interfaces::{event_producer::EventProducer, webhook_repository::WebhookRepository},
+        task::models::events::TaskEvent,
+        webhook::models::{Webhook, WebhookDelivery},
+    },
+};
+
+/// `sha256=` followed by the hex HMAC-SHA256 of the request body, keyed with the secret
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+/// Event type of the delivered event, e.g. `Created`
+pub const EVENT_HEADER: &str = "X-Webhook-Event";
+/// Event id, identical for every attempt so receivers can drop duplicates
+pub const DELIVERY_HEADER: &str = "X-Webhook-Delivery";
+
+/// Longest response excerpt kept in a failed delivery's error
+const MAX_ERROR_BODY_CHARS: usize = 256;
+
+/// Signature sent in [`SIGNATURE_HEADER`] for `body`
+#[must_use]
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    let hex: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("sha256={hex}")
+}
+
+/// [`EventProducer`] that POSTs each task event to the webhooks of its tenant subscribed
+/// to its type
+///
+/// Every webhook is delivered to in its own background task, so publishing never waits
+/// for a slow receiver. A failed attempt is retried with exponential backoff; once an
+/// event has used up its attempts it counts as failed, and a webhook whose events fail
+/// `disable_after_failures` times in a row is deactivated. Every attempt is recorded.
+///
+/// Each webhook has its own circuit breaker, named `webhook:{id}`: while it is open,
+/// attempts are recorded as failed without sending anything, so an endpoint that is down
+/// does not cost a timeout per attempt.
+#[derive(Debug, Clone)]
+pub struct WebhookDispatcher {
+    repository: Arc<dyn WebhookRepository>,
+    client: HttpClient,
+    breakers: CircuitBreakers,
+    retry_policy: RetryPolicy,
+    disable_after_failures: u32,
+}
+
+impl WebhookDispatcher {
+    /// Dispatcher sending through a client configured from `http`, with the webhook
+    /// timeout of `config`
+    ///
+    /// # Errors
+    /// Returns `DomainError::ExternalError` if the HTTP client cannot be built
+    pub fn new(
+        repository: Arc<dyn WebhookRepository>,
+        http: &HttpClientConfig,
+        config: &WebhookConfig,
+        breakers: CircuitBreakers,
+    ) -> Result<Self, DomainError> {
+        // Attempts are retried and recorded by `deliver`, and a redirect would resend the
+        // signed body to a URL nobody subscribed
+        let client = HttpClient::builder(http)
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .retry(RetryPolicy::NONE)
+            .no_redirects()
+            .build()?;
+
+        Ok(Self {
+            repository,
+            client,
+            breakers,
+            retry_policy: RetryPolicy {
+                max_attempts: config.max_attempts.max(1),
+                base_delay: Duration::from_millis(config.retry_base_delay_ms),
+                max_delay: Duration::from_millis(config.retry_max_delay_ms),
+            },
+            disable_after_failures: config.disable_after_failures.max(1),
+        })
+    }
+
+    /// Deliver `body` to `webhook`, retrying until it succeeds or runs out of attempts
+    async fn deliver(&self, webhook: Webhook, event: &TaskEvent, body: Arc<Vec<u8>>) {
+        let signature = sign(&webhook.secret, &body);
+
+        for attempt in 1..=self.retry_policy.max_attempts {
+            if attempt > 1 {
+                tokio::time::sleep(self.retry_policy.delay(attempt - 1)).await;
+            }
+
+            let breaker = self.breakers.get(&format!("webhook:{}", webhook.id));
+            let (delivery, result) = match breaker.as_deref().map(CircuitBreaker::try_call) {
+                Some(Err(open)) => (
+                    skipped_delivery(&webhook, event, attempt, &open),
+                    "circuit_open",
+                ),
+                permit => {
+                    let delivery = self
+                        .attempt(&webhook, event, &body, &signature, attempt)
+                        .await;
+                    if let Some(Ok(permit)) = permit {
+                        if delivery.succeeded {
+                            permit.succeeded();
+                        }
+                    }
+                    let result = if delivery.succeeded {
+                        "success"
+                    } else {
+                        "failure"
+                    };
+                    (delivery, result)
+                }
+            };
+            metrics::counter!("webhook_deliveries_total", "result" => result).increment(1);
+            let succeeded = delivery.succeeded;
+            if let Err(e) = self.repository.record_delivery(&delivery).await {
+                // The webhook was deleted while the event was in flight
+                if matches!(e, DomainError::NotFound { .. }) {
+                    return;
+                }
+                warn!(webhook_id = %webhook.id, error = %e, "Failed to record webhook delivery");
+            }
+
+            if succeeded {
+                if let Err(e) = self.repository.record_success(webhook.id).await {
+                    warn!(webhook_id = %webhook.id, error = %e, "Failed to reset webhook failures");
+                }
+                return;
+            }
+        }
+
+        match self
+            .repository
+            .record_failure(webhook.id, self.disable_after_failures)
+            .await
+        {
+            Ok(true) => {}
+            Ok(false) => {
+                warn!(
+                    webhook_id = %webhook.id,
+                    url = webhook.url.value(),
+                    "Webhook deactivated after {} consecutive failed events",
+                    self.disable_after_failures
+                );
+                metrics::counter!("webhooks_disabled_total").increment(1);
+            }
+            Err(DomainError::NotFound { .. }) => {}
+            Err(e) => {
+                warn!(webhook_id = %webhook.id, error = %e, "Failed to record webhook failure");
+            }
+        }
+    }
+
+    async fn attempt(
+        &self,
+        webhook: &Webhook,
+        event: &TaskEvent,
+        body: &[u8],
+        signature: &str,
+        attempt: u32,
+    ) -> WebhookDelivery {
+        let attempted_at = Utc::now();
+        let started = Instant::now();
+        let request = self
+            .client
+            .request(reqwest::Method::POST, webhook.url.value())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, signature)
+            .header(EVENT_HEADER, event.event_type.as_str())
+            .header(DELIVERY_HEADER, event.event_id.to_string())
+            .body(body.to_vec());
+        let response = self.client.send(request).await;
+
+        let (status_code, error) = match response {
+            Ok(response) if response.status().is_success() => {
+                (Some(response.status().as_u16()), None)
+            }
+            Ok(response) => {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                let excerpt: String = body.chars().take(MAX_ERROR_BODY_CHARS).collect();
+                (
+                    Some(status.as_u16()),
+                    Some(format!("Endpoint answered {status}: {excerpt}")),
+                )
+            }
+            Err(e) => (None, Some(format!("Request failed: {e}"))),
+        };
+        let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        debug!(
+            webhook_id = %webhook.id,
+            attempt,
+            status_code,
+            duration_ms,
+            "Webhook delivery attempted"
+        );
+
+        WebhookDelivery {
+            id: Uuid::new_v4(),
+            webhook_id: webhook.id,
+            event_id: event.event_id,
+            event_type: event.event_type,
+            attempt,
+            succeeded: error.is_none(),
+            status_code,
+            error,
+            duration_ms,
+            attempted_at,
+        }
+    }
+}
+
+/// Attempt refused by the webhook's open circuit breaker, recorded without being sent
+fn skipped_delivery(
+    webhook: &Webhook,
+    event: &TaskEvent,
+    attempt: u32,
+    open: &BreakerOpen,
+) -> WebhookDelivery {
+    WebhookDelivery {
+        id: Uuid::new_v4(),
+        webhook_id: webhook.id,
+        event_id: event.event_id,
+        event_type: event.event_type,
+        attempt,
+        succeeded: false,
+        status_code: None,
+        error: Some(format!("Not sent: {open}")),
+        duration_ms: 0,
+        attempted_at: Utc::now(),
+    }
+}
+
+#[async_trait]
+impl EventProducer for WebhookDispatcher {
+    fn name(&self) -> &'static str {
+        "webhooks"
+    }
+
+    async fn publish_task_event(&self, event: TaskEvent) -> Result<(), DomainError> {
+        let webhooks = self
+            .repository
+            .subscribed_to(event.metadata.tenant_id, event.event_type)
+            .await?;
+        if webhooks.is_empty() {
+            return Ok(());
+        }
+
+        let body = serde_json::to_vec(&event).map_err(|e| {
+            DomainError::external_error(
+                ExternalSystem::Http,
+                format!("Failed to serialize task event: {e}"),
+            )
+        })?;
+        let body = Arc::new(body);
+        let event = Arc::new(event);
+
+        for webhook in webhooks {
+            let dispatcher = self.clone();
+            let event = event.clone();
+            let body = body.clone();
+            // Deliveries outlive the request, but stay in its trace and keep its request id
+            let delivery = async move { dispatcher.deliver(webhook, &event, body).await };
+            tokio::spawn(
+                error_reporting::in_current_request(delivery).instrument(tracing::Span::current()),
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_is_the_hex_hmac_sha256_of_the_body() {
+        // RFC 4231, test case 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
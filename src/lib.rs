@@ -1,6 +1,25 @@
+// <feature:api>
 pub mod api;
+// </feature:api>
+pub mod bootstrap;
+pub mod build_info;
 pub mod cli;
+// <feature:api>
+pub mod client;
+// </feature:api>
 pub mod common;
 pub mod config;
 pub mod domain;
 pub mod infrastructure;
+// <feature:postgres>
+pub mod migrate;
+// </feature:postgres>
+// <feature:swagger>
+pub mod openapi;
+// </feature:swagger>
+// <feature:binary>
+pub mod service;
+// </feature:binary>
+// <feature:worker>
+pub mod worker;
+// </feature:worker>
@@ -0,0 +1,119 @@
+use anyhow::{Context, Result};
+use clap::Subcommand;
+
+use crate::{
+    bootstrap::connect_pool,
+    config::{AppConfig, DatabaseKind},
+    infrastructure::migrations::{self, MigrationStatus},
+};
+
+/// `migrate` subcommands of the service binary
+///
+/// Let migrations run as their own deploy step, with `migrate_on_startup` turned off for
+/// the service itself.
+#[derive(Subcommand, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrateCommand {
+    /// Apply pending migrations
+    Run,
+    /// List applied and pending migrations
+    Status,
+    /// Exit non-zero if the database is behind this binary or its migrations have drifted
+    Check,
+}
+
+/// Run a `migrate` subcommand against the configured Postgres database
+pub async fn execute_migrate(command: MigrateCommand, config: &AppConfig) -> Result<()> {
+    if config.database_kind != DatabaseKind::Postgres {
+        anyhow::bail!(
+            "`migrate` only manages Postgres; SQLite migrations run when the database is opened"
+        );
+    }
+
+    // Migrations may legitimately run longer than the service's statement timeout
+    let mut pool_config = config.pool_config.clone();
+    pool_config.statement_timeout_ms = 0;
+    let pool = connect_pool(&config.database_url, &pool_config)
+        .await
+        .context("Failed to connect to the database")?;
+
+    let result = match command {
+        MigrateCommand::Run => run(&pool, config).await,
+        MigrateCommand::Status => migrations::status(&pool)
+            .await
+            .context("Failed to read migration status")
+            .map(|statuses| print_statuses(&statuses)),
+        MigrateCommand::Check => check(&pool).await,
+    };
+
+    pool.close().await;
+    result
+}
+
+async fn run(pool: &sqlx::PgPool, config: &AppConfig) -> Result<()> {
+    migrations::run(pool)
+        .await
+        .context("Failed to run migrations")?;
+    migrations::apply_user_id_mode(pool, config.user_ids)
+        .await
+        .context("Failed to apply the user id mode")?;
+    println!("✓ Migrations applied");
+    Ok(())
+}
+
+async fn check(pool: &sqlx::PgPool) -> Result<()> {
+    let statuses = migrations::status(pool)
+        .await
+        .context("Failed to read migration status")?;
+
+    if migrations::is_up_to_date(&statuses) {
+        println!("✓ Database schema is up to date");
+        return Ok(());
+    }
+
+    let outstanding: Vec<_> = statuses
+        .into_iter()
+        .filter(|status| status.state != migrations::MigrationState::Applied)
+        .collect();
+    print_statuses(&outstanding);
+    anyhow::bail!(
+        "Database schema does not match this binary ({} migration(s) need attention)",
+        outstanding.len()
+    )
+}
+
+fn print_statuses(statuses: &[MigrationStatus]) {
+    for status in statuses {
+        println!(
+            "{:<18} {:<14} {}",
+            status.state.as_str(),
+            status.version,
+            status.description
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser, Debug)]
+    struct TestCli {
+        #[command(subcommand)]
+        command: MigrateCommand,
+    }
+
+    #[test]
+    fn test_migrate_subcommands_parse() {
+        for (arg, expected) in [
+            ("run", MigrateCommand::Run),
+            ("status", MigrateCommand::Status),
+            ("check", MigrateCommand::Check),
+        ] {
+            let cli = TestCli::try_parse_from(["migrate", arg]).unwrap();
+            assert_eq!(cli.command, expected);
+        }
+
+        assert!(TestCli::try_parse_from(["migrate", "revert"]).is_err());
+    }
+}
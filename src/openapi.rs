@@ -0,0 +1,158 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use utoipa::OpenApi;
+
+use crate::api::docs::ApiDoc;
+
+/// Serialization of the exported spec
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenApiFormat {
+    Json,
+    Yaml,
+}
+
+impl OpenApiFormat {
+    /// Format implied by a file extension: `.yaml`/`.yml` is YAML, anything else JSON
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("yaml" | "yml") => Self::Yaml,
+            _ => Self::Json,
+        }
+    }
+}
+
+/// `openapi` subcommand of the service binary
+///
+/// Needs no configuration or database, so CI can export the spec for the API gateway.
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct OpenApiCommand {
+    /// File to write; prints to stdout when omitted
+    #[arg(long)]
+    pub out: Option<PathBuf>,
+
+    /// Output format; defaults to the extension of `--out`, or JSON
+    #[arg(long, value_enum)]
+    pub format: Option<OpenApiFormat>,
+}
+
+/// Render the spec served at `/api-docs/openapi.json`
+///
+/// Paths and schemas are kept in sorted maps, so the output only changes when the API does.
+pub fn render(format: OpenApiFormat) -> Result<String> {
+    let spec = ApiDoc::openapi();
+    let mut rendered = match format {
+        OpenApiFormat::Json => spec.to_pretty_json()?,
+        OpenApiFormat::Yaml => spec.to_yaml()?,
+    };
+    if !rendered.ends_with('\n') {
+        rendered.push('\n');
+    }
+    Ok(rendered)
+}
+
+/// Write the spec to `--out`, or to stdout
+pub fn execute_openapi(command: &OpenApiCommand) -> Result<()> {
+    let format = command.format.unwrap_or_else(|| {
+        command
+            .out
+            .as_deref()
+            .map_or(OpenApiFormat::Json, OpenApiFormat::from_path)
+    });
+    let rendered = render(format).context("Failed to render the OpenAPI spec")?;
+
+    match &command.out {
+        Some(path) => {
+            std::fs::write(path, rendered)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("✓ OpenAPI spec written to {}", path.display());
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// Committed spec the API gateway ingests
+    const EXPORTED_SPEC: &str = "openapi.json";
+
+    /// Shown with a failed snapshot, and kept in its header
+    const SNAPSHOT_DESCRIPTION: &str = "The API contract: clients and the API gateway depend \
+         on it. Review the difference; if intended, run `cargo insta accept` and regenerate \
+         openapi.json with `cargo run -- openapi --out openapi.json`";
+
+    #[derive(Parser, Debug)]
+    struct TestCli {
+        #[command(flatten)]
+        command: OpenApiCommand,
+    }
+
+    /// Spec without `info`, whose title and version follow the crate rather than the API
+    fn contract() -> serde_json::Value {
+        let mut value = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        value.as_object_mut().unwrap().remove("info");
+        value
+    }
+
+    #[test]
+    fn test_openapi_spec_matches_snapshot() {
+        insta::with_settings!({
+            description => SNAPSHOT_DESCRIPTION,
+            prepend_module_to_snapshot => false,
+            sort_maps => true,
+        }, {
+            insta::assert_json_snapshot!("openapi", contract());
+        });
+    }
+
+    #[test]
+    fn test_exported_spec_is_current() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(EXPORTED_SPEC);
+        let exported = std::fs::read_to_string(&path).unwrap();
+
+        assert!(
+            exported == render(OpenApiFormat::Json).unwrap(),
+            "{EXPORTED_SPEC} is out of date; regenerate it with \
+             `cargo run -- openapi --out {EXPORTED_SPEC}` and commit it"
+        );
+    }
+
+    #[test]
+    fn test_render_is_stable_and_supports_yaml() {
+        assert_eq!(
+            render(OpenApiFormat::Json).unwrap(),
+            render(OpenApiFormat::Json).unwrap()
+        );
+
+        let yaml = render(OpenApiFormat::Yaml).unwrap();
+        assert!(
+            yaml.starts_with("openapi: 3."),
+            "Unexpected YAML: {yaml:.40}"
+        );
+        assert!(yaml.contains("/tasks/{id}:"));
+    }
+
+    #[test]
+    fn test_format_follows_flag_then_extension() {
+        let cli = TestCli::try_parse_from(["openapi", "--out", "spec.yml"]).unwrap();
+        assert_eq!(
+            cli.command.out.as_deref().map(OpenApiFormat::from_path),
+            Some(OpenApiFormat::Yaml)
+        );
+
+        let cli =
+            TestCli::try_parse_from(["openapi", "--out", "spec.txt", "--format", "yaml"]).unwrap();
+        assert_eq!(cli.command.format, Some(OpenApiFormat::Yaml));
+
+        assert_eq!(
+            OpenApiFormat::from_path(Path::new("openapi.json")),
+            OpenApiFormat::Json
+        );
+        assert!(TestCli::try_parse_from(["openapi", "--format", "toml"]).is_err());
+    }
+}
@@ -0,0 +1,199 @@
+//! Startup and shutdown shared by the service's binaries
+//!
+//! Each binary passes what it runs until shut down, the HTTP API or the worker; the
+//! subcommands, logging, background jobs and tracer flush are the same for both.
+
+use std::{env, future::Future, sync::Arc};
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use tokio_util::sync::CancellationToken;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+// <feature:api>
+use crate::api::middleware::install_panic_hook;
+// </feature:api>
+// <feature:swagger>
+use crate::openapi::{execute_openapi, OpenApiCommand};
+// </feature:swagger>
+// <feature:postgres>
+use crate::{
+    bootstrap::job_runner,
+    infrastructure::{pool_monitor, task_notifications},
+    migrate::{execute_migrate, MigrateCommand},
+};
+// </feature:postgres>
+use crate::{
+    bootstrap::{bootstrap, scheduler},
+    build_info::BuildInfo,
+    config::{AppConfig, AppState, LogFormat},
+    infrastructure::{error_reporting, log_level, telemetry},
+};
+
+/// Task service; runs until shut down unless a subcommand is given
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    // <feature:postgres>
+    /// Manage database migrations without starting the server
+    #[command(subcommand)]
+    Migrate(MigrateCommand),
+    // </feature:postgres>
+    // <feature:swagger>
+    /// Export the `OpenAPI` spec as JSON or YAML
+    Openapi(OpenApiCommand),
+    // </feature:swagger>
+}
+
+/// Parse the command line and run its subcommand, or `serve` until shut down
+///
+/// `serve` gets the state once the background jobs are running; they are stopped, and
+/// waited for, after it returns.
+pub async fn run<S, F>(serve: S) -> Result<()>
+where
+    S: FnOnce(Arc<AppState>, AppConfig) -> F,
+    F: Future<Output = Result<()>>,
+{
+    let args = Args::parse();
+    env::set_var("RUST_BACKTRACE", "full");
+
+    let config = match args.command {
+        // <feature:swagger>
+        // Handled before loading config so the spec can be exported without any environment
+        Some(Command::Openapi(command)) => return execute_openapi(&command),
+        // </feature:swagger>
+        // <feature:postgres>
+        Some(Command::Migrate(command)) => {
+            if let Err(e) = execute_migrate(command, &load_config()?).await {
+                // A failed `check` is an expected outcome, not worth a backtrace
+                eprintln!("Error: {e:#}");
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        // </feature:postgres>
+        None => load_config()?,
+    };
+
+    let tracer_provider = telemetry::init_tracer_provider(&config.telemetry_config)
+        .map_err(|e| anyhow::anyhow!("Failed to initialize OpenTelemetry exporter: {e}"))?;
+
+    // Reloadable so the level can be changed through `/admin/log-level` without a restart
+    let (env_filter, log_level) = log_level::reloadable_filter(
+        tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+            "rust_service_template=debug,tower_http=debug,axum::rejection=trace,sqlx=info".into()
+        }),
+    );
+
+    tracing_subscriber::registry()
+        .with(env_filter)
+        .with((config.log_format == LogFormat::Text).then(tracing_subscriber::fmt::layer))
+        .with((config.log_format == LogFormat::Json).then(|| {
+            tracing_subscriber::fmt::layer()
+                .json()
+                .flatten_event(true)
+                .with_current_span(true)
+                .with_span_list(true)
+        }))
+        .with(
+            tracer_provider
+                .as_ref()
+                .map(|provider| telemetry::layer(provider, &config.telemetry_config)),
+        )
+        .init();
+
+    // <feature:api>
+    install_panic_hook();
+    // </feature:api>
+
+    // Held until exit so pending reports are flushed on shutdown
+    let _error_reporting = error_reporting::init(&config.error_reporting_config);
+
+    let build = BuildInfo::current();
+    tracing::info!(
+        version = %build.version,
+        git_sha = %build.git_sha,
+        git_branch = %build.git_branch,
+        build_timestamp = %build.build_timestamp,
+        rustc_version = %build.rustc_version,
+        "Starting rust-service-template"
+    );
+
+    if let Some(endpoint) = &config.telemetry_config.otlp_endpoint {
+        tracing::info!("Exporting traces to {}", endpoint);
+    }
+
+    let app_state = bootstrap(config.clone(), Some(log_level)).await?;
+
+    // <feature:postgres>
+    if let Some(db_pool) = &app_state.db_pool {
+        pool_monitor::spawn_pool_monitor(db_pool.clone(), &config.pool_config);
+        task_notifications::spawn_task_change_listener(
+            db_pool.clone(),
+            app_state.task_changes.clone(),
+        );
+    }
+    // </feature:postgres>
+
+    // Fails on an invalid cron expression, before anything is served
+    let scheduler = scheduler(
+        // <feature:postgres>
+        app_state.db_pool.as_ref(),
+        app_state.object_store.clone(),
+        &config.scheduler_config,
+        // </feature:postgres>
+    )?;
+
+    // Cancelled once the service has stopped, so claimed jobs and scheduled runs still finish
+    let shutdown = CancellationToken::new();
+    let scheduled_jobs = scheduler.spawn(shutdown.clone());
+    // <feature:postgres>
+    let jobs = match &app_state.db_pool {
+        Some(db_pool) if config.jobs_config.enabled => {
+            Some(job_runner(db_pool.clone(), &config.jobs_config).spawn(shutdown.clone()))
+        }
+        _ => None,
+    };
+    // </feature:postgres>
+
+    let result = serve(app_state, config).await;
+
+    shutdown.cancel();
+    if let Err(e) = scheduled_jobs.await {
+        tracing::error!("Scheduled jobs stopped abnormally: {e}");
+    }
+    // <feature:postgres>
+    if let Some(jobs) = jobs {
+        tracing::info!("Waiting for running jobs to finish...");
+        if let Err(e) = jobs.await {
+            tracing::error!("Background jobs stopped abnormally: {e}");
+        }
+    }
+    // </feature:postgres>
+
+    if let Some(provider) = tracer_provider {
+        tracing::info!("Flushing OpenTelemetry spans...");
+        if let Err(e) = provider.shutdown() {
+            tracing::error!("Failed to flush OpenTelemetry spans: {e}");
+        }
+    }
+
+    result
+}
+
+/// The configuration from the environment, exiting when it is invalid
+fn load_config() -> Result<AppConfig> {
+    let config = AppConfig::init().map_err(|e| anyhow::anyhow!("Configuration error: {e}"))?;
+    if let Err(e) = config.validate() {
+        // Printed directly: this runs before logging is set up and a backtrace adds nothing
+        eprintln!("Configuration error: {e}");
+        std::process::exit(1);
+    }
+    Ok(config)
+}
@@ -1,69 +1,287 @@
-use std::sync::Arc;
+//! Setup shared by the integration tests
+//!
+//! Tests need Postgres, found in this order:
+//! - `RUST_SERVICE_TEMPLATE__DATABASE_URL` or `DATABASE_URL`, as CI sets them
+//! - with `TEST_USE_CONTAINERS=1`, a container the run starts and removes (see
+//!   [`containers`]), so only Docker has to be running
+//! - otherwise the docker-compose database on `localhost:5445`
+//!
+// <feature:kafka>
+//! Tests publishing to Kafka get a broker from [`kafka_bootstrap_servers`] the same way.
+// </feature:kafka>
+
+use std::{ops::Deref, sync::Arc};
 
-use async_trait::async_trait;
 use axum::Router;
 use rust_service_template::{
     api::build_app_router,
+    bootstrap::{bootstrap, object_store},
     config::{AppConfig, AppState},
-    domain::{
-        errors::DomainError, interfaces::event_producer::EventProducer,
-        task::models::events::TaskEvent,
+    domain::interfaces::task_repository::TaskRepository,
+    infrastructure::{
+        circuit_breaker::CircuitBreakers, feature_flags::StaticFeatureFlags,
+        health_history::HealthHistory, in_memory_task::InMemoryTaskRepository,
+        in_memory_user::InMemoryUserRepository, in_memory_webhook::InMemoryWebhookRepository,
+        noop_event_producer::NoopEventProducer, usage_cache::UsageCache,
     },
-    infrastructure::task::PostgresTaskRepository,
 };
-use sqlx::postgres::PgPoolOptions;
+use sqlx::{Connection, Executor, PgConnection, PgPool};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use uuid::Uuid;
+
+// Also compiled as a test target of its own, where a plain `mod` would look in `tests/`
+#[path = "common/containers.rs"]
+mod containers;
+
+static INIT: std::sync::Once = std::sync::Once::new();
+
+/// Set to `memory` to run the suite against [`InMemoryTaskRepository`] instead of Postgres
+///
+/// Nothing connects to the database in that mode, so tests that assert through the returned
+/// pool (or seed data with it) still need Postgres and will fail.
+pub const TEST_REPOSITORY_ENV: &str = "TEST_TASK_REPOSITORY";
 
-/// Mock event producer for testing (does nothing)
-struct MockEventProducer;
+/// Secret the test configuration signs and verifies JWTs with
+pub const TEST_JWT_SECRET: &str = "this_is_a_very_long_secret_key_for_testing_purposes_only";
 
-#[async_trait]
-impl EventProducer for MockEventProducer {
-    async fn publish_task_event(&self, _event: TaskEvent) -> Result<(), DomainError> {
-        // Do nothing in tests - we don't actually publish events during testing
-        Ok(())
+/// Prefix of the schemas tests run in, for finding ones a killed run left behind
+pub const TEST_SCHEMA_PREFIX: &str = "test_";
+
+/// The schema of one test, holding its tables and nothing of any other test
+///
+/// Derefs to the pool connected to it, for assertions and seeding. The schema is dropped
+/// with this guard, so keep it alive until the test ends: bind it, e.g. to `_db`, as `_`
+/// drops it at once.
+pub struct TestDatabase {
+    pool: PgPool,
+    /// Schema name and the URL to drop it through; `None` without Postgres
+    schema: Option<(String, String)>,
+}
+
+impl Deref for TestDatabase {
+    type Target = PgPool;
+
+    fn deref(&self) -> &PgPool {
+        &self.pool
     }
 }
 
-static INIT: std::sync::Once = std::sync::Once::new();
+impl Drop for TestDatabase {
+    fn drop(&mut self) {
+        let Some((schema, database_url)) = self.schema.take() else {
+            return;
+        };
 
-/// Test app setup with database connection and migrations
+        // Drop runs on the test's runtime, which cannot block on a query itself
+        let dropped = std::thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?
+                .block_on(async {
+                    let mut connection = PgConnection::connect(&database_url).await?;
+                    // A transaction the test left open would hold its locks until the pool
+                    // rolls it back, which it cannot while this blocks the test's runtime
+                    sqlx::query(
+                        "SELECT pg_terminate_backend(pid) FROM pg_stat_activity
+                         WHERE application_name = $1",
+                    )
+                    .bind(&schema)
+                    .execute(&mut connection)
+                    .await?;
+                    connection
+                        .execute(format!("DROP SCHEMA IF EXISTS \"{schema}\" CASCADE").as_str())
+                        .await?;
+                    connection.close().await?;
+                    anyhow::Ok(())
+                })
+        })
+        .join();
+
+        match dropped {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => eprintln!("Failed to drop test schema: {e:#}"),
+            Err(_) => eprintln!("Failed to drop test schema: the cleanup thread panicked"),
+        }
+    }
+}
+
+/// Test app setup with a database schema of its own, migrated
 ///
-/// This function:
-/// - Initializes environment variables once (using Once)
-/// - Sets up test configuration
-/// - Creates database connection pool
-/// - Runs migrations
-/// - Initializes AppState with test repositories
-/// - Returns the application router and database pool
+/// Builds the router from [`app_state`]. Use `app_state` directly when a test needs to
+/// tweak configuration or swap a dependency before building the router.
 ///
 /// # Returns
 /// A tuple containing:
 /// - `Router`: The axum application router
-/// - `Arc<PgPool>`: The database connection pool for test assertions
+/// - `TestDatabase`: The test's schema, dereferencing to its pool for assertions
 ///
 /// # Example
 /// ```no_run
-/// let (app, pool) = app().await;
-/// // Make requests to app, use pool for DB assertions
+/// let (app, db) = app().await;
+/// // Make requests to app, use db as a pool for DB assertions
 /// ```
-pub async fn app() -> (Router, Arc<sqlx::PgPool>) {
+pub async fn app() -> (Router, TestDatabase) {
+    let (app_state, db) = app_state().await;
+
+    (build_app_router(Arc::new(app_state)).await, db)
+}
+
+/// Like [`app`], with `repository` serving every task request
+///
+/// For test doubles that fail or stall on purpose. The test still gets a schema of its
+/// own, for tests that also seed or assert through the pool.
+pub async fn app_with_repository(repository: Arc<dyn TaskRepository>) -> (Router, TestDatabase) {
+    let (mut app_state, db) = app_state().await;
+    app_state.task_repository = repository;
+
+    (build_app_router(Arc::new(app_state)).await, db)
+}
+
+/// Test application state on a fresh, migrated database schema
+///
+/// This function:
+/// - Initializes environment variables once (using Once)
+/// - Sets up test configuration, with Kafka disabled so events are dropped
+/// - Creates a schema for the test and points `database_url` at it through `search_path`,
+///   so tests counting or listing rows see only their own, whatever runs in parallel
+/// - Builds the state through the same `bootstrap` the binary uses (pool, migrations,
+///   repositories), or around an in-memory repository when [`TEST_REPOSITORY_ENV`] is
+///   `memory`
+///
+/// # Example
+/// ```no_run
+/// let (mut state, _db) = app_state().await;
+/// state.env.concurrency_config.max_concurrent_requests = 1;
+/// let app = build_app_router(Arc::new(state)).await;
+/// ```
+pub async fn app_state() -> (AppState, TestDatabase) {
+    app_state_with(|_| {}).await
+}
+
+/// Like [`app_state`], with `configure` applied to the configuration before bootstrapping
+///
+/// For settings bootstrap acts on, such as `user_ids`, which decides whether the schema gets
+/// the foreign key from tasks to users.
+///
+/// # Example
+/// ```no_run
+/// let (state, _db) = app_state_with(|config| config.user_ids = UserIdMode::Registered).await;
+/// ```
+pub async fn app_state_with(configure: impl FnOnce(&mut AppConfig)) -> (AppState, TestDatabase) {
+    init();
+
+    let mut config: AppConfig = AppConfig::init().expect("Failed to initialize config");
+    // Keep uploaded attachments out of the working tree
+    config.attachment_config.filesystem_root =
+        std::env::temp_dir().join("rust-service-template-attachments");
+    configure(&mut config);
+
+    if uses_memory_repository() {
+        let state = in_memory_state(config);
+        let db = TestDatabase {
+            pool: state
+                .db_pool
+                .clone()
+                .expect("In-memory state has a lazy pool"),
+            schema: None,
+        };
+        return (state, db);
+    }
+
+    let database_url = config.database_url.clone();
+    let schema = format!("{TEST_SCHEMA_PREFIX}{}", Uuid::new_v4().simple());
+    config.database_url = with_search_path(&database_url, &schema);
+
+    // Retry with exponential backoff for CI environments where the database might take time
+    // to be ready
+    let mut retries = 5;
+    let mut delay = std::time::Duration::from_secs(2);
+
+    loop {
+        let bootstrapped = async {
+            create_schema(&database_url, &schema).await?;
+            bootstrap(config.clone(), None).await
+        };
+        match bootstrapped.await {
+            Ok(state) => {
+                let db = TestDatabase {
+                    pool: state
+                        .db_pool
+                        .clone()
+                        .expect("Integration tests run against Postgres"),
+                    schema: Some((schema, database_url)),
+                };
+                return ((*state).clone(), db);
+            }
+            Err(e) => {
+                retries -= 1;
+                if retries == 0 {
+                    panic!("Failed to bootstrap application after retries: {e:#}");
+                }
+                tracing::warn!(
+                    "Failed to bootstrap application ({} retries left): {:#}",
+                    retries,
+                    e
+                );
+                tokio::time::sleep(delay).await;
+                delay *= 2; // Exponential backoff
+            }
+        }
+    }
+}
+
+/// A fresh schema with nothing in it, and the database URL whose connections use it
+///
+/// For tests that start the service binary, which migrates the schema itself as it would
+/// in production.
+pub async fn empty_database() -> (TestDatabase, String) {
+    init();
+
+    let database_url =
+        std::env::var("RUST_SERVICE_TEMPLATE__DATABASE_URL").expect("init sets the database URL");
+    let schema = format!("{TEST_SCHEMA_PREFIX}{}", Uuid::new_v4().simple());
+    create_schema(&database_url, &schema)
+        .await
+        .expect("Failed to create test schema");
+    let schema_url = with_search_path(&database_url, &schema);
+
+    let db = TestDatabase {
+        pool: PgPool::connect_lazy(&schema_url).expect("Failed to parse database URL"),
+        schema: Some((schema, database_url)),
+    };
+    (db, schema_url)
+}
+
+/// Set the test environment and logging up, once per run
+fn init() {
     INIT.call_once(|| {
         // Set JWT secret for tests
-        std::env::set_var(
-            "RUST_SERVICE_TEMPLATE__JWT_SECRET",
-            "this_is_a_very_long_secret_key_for_testing_purposes_only",
-        );
+        std::env::set_var("RUST_SERVICE_TEMPLATE__JWT_SECRET", TEST_JWT_SECRET);
 
         // Set server configuration for tests
         std::env::set_var("RUST_SERVICE_TEMPLATE__SERVER_HOST", "127.0.0.1");
         std::env::set_var("RUST_SERVICE_TEMPLATE__SERVER_PORT", "8080");
 
+        // Don't publish task events during testing
+        std::env::set_var("RUST_SERVICE_TEMPLATE__KAFKA_CONFIG__ENABLED", "false");
+
+        // Tasks are created for arbitrary user ids; tests of registered users opt back in
+        // with `app_state_with`
+        std::env::set_var("RUST_SERVICE_TEMPLATE__USER_IDS", "external");
+
+        // Every test has a pool of its own; idle connections would add up
+        std::env::set_var("RUST_SERVICE_TEMPLATE__POOL_CONFIG__MIN_CONNECTIONS", "0");
+
         // Use DATABASE_URL from environment (for CI) or fall back to local dev default
         if std::env::var("RUST_SERVICE_TEMPLATE__DATABASE_URL").is_err() {
             if let Ok(database_url) = std::env::var("DATABASE_URL") {
                 // Convert DATABASE_URL to RUST_SERVICE_TEMPLATE__DATABASE_URL format
                 std::env::set_var("RUST_SERVICE_TEMPLATE__DATABASE_URL", database_url);
+            } else if containers::enabled() && !uses_memory_repository() {
+                std::env::set_var(
+                    "RUST_SERVICE_TEMPLATE__DATABASE_URL",
+                    containers::start_postgres(),
+                );
             } else {
                 // Local development default
                 std::env::set_var(
@@ -73,10 +291,13 @@ pub async fn app() -> (Router, Arc<sqlx::PgPool>) {
             }
         }
 
-        std::env::set_var(
-            "RUST_LOG",
-            "rust_service_template=debug,sqlx=debug,tower_http=debug,axum::rejection=trace",
-        );
+        // Kept when set, so runs such as the load test can turn logging down
+        if std::env::var("RUST_LOG").is_err() {
+            std::env::set_var(
+                "RUST_LOG",
+                "rust_service_template=debug,sqlx=debug,tower_http=debug,axum::rejection=trace",
+            );
+        }
 
         tracing_subscriber::registry()
             .with(
@@ -88,61 +309,75 @@ pub async fn app() -> (Router, Arc<sqlx::PgPool>) {
             .with(tracing_subscriber::fmt::layer())
             .init();
     });
+}
 
-    let config: AppConfig = AppConfig::init().expect("Failed to initialize config");
-
-    // Use longer timeout for CI environments where database might take time to be ready
-    // Retry connection with exponential backoff for CI environments
-    let mut db_pool = None;
-    let mut retries = 5;
-    let mut delay = std::time::Duration::from_secs(2);
-
-    while db_pool.is_none() && retries > 0 {
-        match PgPoolOptions::new()
-            .max_connections(20)
-            .acquire_timeout(std::time::Duration::from_secs(30))
-            .connect(&config.database_url)
-            .await
-        {
-            Ok(pool) => {
-                db_pool = Some(pool);
-                break;
-            }
-            Err(e) => {
-                retries -= 1;
-                if retries > 0 {
-                    tracing::warn!(
-                        "Failed to connect to database ({} retries left): {}",
-                        retries,
-                        e
-                    );
-                    tokio::time::sleep(delay).await;
-                    delay *= 2; // Exponential backoff
-                } else {
-                    panic!("Failed to connect to database after retries: {}", e);
-                }
-            }
-        }
+// <feature:kafka>
+/// Bootstrap servers of a Kafka broker for tests that publish events, if one is available
+///
+/// A container with `TEST_USE_CONTAINERS=1`, otherwise the broker named by
+/// `RUST_SERVICE_TEMPLATE__KAFKA_CONFIG__BOOTSTRAP_SERVERS`. `None` means the test should be
+/// skipped, as the default suite runs without a broker.
+pub fn kafka_bootstrap_servers() -> Option<String> {
+    if containers::enabled() {
+        return Some(containers::kafka_bootstrap_servers().to_string());
     }
+    std::env::var("RUST_SERVICE_TEMPLATE__KAFKA_CONFIG__BOOTSTRAP_SERVERS").ok()
+}
+// </feature:kafka>
 
-    let db_pool = db_pool.expect("Failed to connect to database");
+fn uses_memory_repository() -> bool {
+    std::env::var(TEST_REPOSITORY_ENV).is_ok_and(|value| value == "memory")
+}
 
-    // Run migrations
-    sqlx::migrate!("./migrations")
-        .run(&db_pool)
-        .await
-        .expect("Failed to run migrations");
+/// `database_url` with `schema` as the only schema its connections search
+///
+/// The connections are also named after the schema, so [`TestDatabase`] can find them.
+fn with_search_path(database_url: &str, schema: &str) -> String {
+    let mut url = url::Url::parse(database_url).expect("Failed to parse database URL");
+    url.query_pairs_mut()
+        .append_pair("options", &format!("-c search_path={schema}"))
+        .append_pair("application_name", schema);
+    url.to_string()
+}
 
-    let db_arc = Arc::new(db_pool.clone());
-    let task_repo = Arc::new(PostgresTaskRepository::new(db_pool.clone()));
-    let event_producer = Arc::new(MockEventProducer) as Arc<dyn EventProducer>;
+async fn create_schema(database_url: &str, schema: &str) -> anyhow::Result<()> {
+    let mut connection = PgConnection::connect(database_url).await?;
+    connection
+        .execute(format!("CREATE SCHEMA IF NOT EXISTS \"{schema}\"").as_str())
+        .await?;
+    connection.close().await?;
+    Ok(())
+}
 
-    let app_state = Arc::new(AppState {
-        db_pool,
-        env: config,
-        task_repository: task_repo,
-        event_producer,
-    });
+/// State backed by the in-memory repositories, with a pool that fails fast if a test uses it
+fn in_memory_state(config: AppConfig) -> AppState {
+    let db_pool = sqlx::postgres::PgPoolOptions::new()
+        .acquire_timeout(std::time::Duration::from_secs(1))
+        .connect_lazy(&config.database_url)
+        .expect("Failed to parse database URL");
+    let usage_cache = UsageCache::from_config(&config.quota_config);
+    let (object_store, local_uploads) =
+        object_store(&config).expect("Failed to initialize the object store");
+    let circuit_breakers = CircuitBreakers::new(&config.circuit_breaker_config);
+    let health_history = HealthHistory::new(&config.health_history_config);
+    let feature_flags = Arc::new(StaticFeatureFlags::new(config.features.clone()));
 
-    (build_app_router(app_state).await, db_arc)
+    AppState {
+        db_pool: Some(db_pool),
+        env: config,
+        task_repository: Arc::new(InMemoryTaskRepository::new()),
+        user_repository: Arc::new(InMemoryUserRepository::new()),
+        webhook_repository: Arc::new(InMemoryWebhookRepository::new()),
+        job_repository: None,
+        attachment_repository: None,
+        object_store,
+        local_uploads,
+        event_producer: Arc::new(NoopEventProducer),
+        circuit_breakers,
+        health_history,
+        feature_flags,
+        task_changes: tokio::sync::broadcast::channel(1).0,
+        usage_cache,
+        log_level: None,
+    }
 }
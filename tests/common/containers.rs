@@ -0,0 +1,89 @@
+//! Services the tests need, in containers started by the test run itself
+//!
+//! With [`TEST_CONTAINERS_ENV`] set to `1` and no database URL configured, `cargo test`
+//! needs nothing but a running Docker daemon. Each container starts on first use, is shared
+//! by every test of the run, and is removed when the test process exits.
+
+// <feature:kafka>
+use std::sync::OnceLock;
+// </feature:kafka>
+use std::sync::{Mutex, Once, PoisonError};
+
+// <feature:kafka>
+use testcontainers_modules::kafka::{Kafka, KAFKA_PORT};
+// </feature:kafka>
+use testcontainers_modules::{
+    postgres::Postgres,
+    testcontainers::{runners::SyncRunner, Container, Image},
+};
+
+/// Set to `1` to start the services tests need in containers instead of using the
+/// docker-compose ones
+pub const TEST_CONTAINERS_ENV: &str = "TEST_USE_CONTAINERS";
+
+/// Port Postgres listens on inside its container
+const POSTGRES_PORT: u16 = 5432;
+
+/// Containers removed at exit, type-erased as only their drop matters
+static STARTED: Mutex<Vec<Box<dyn Send>>> = Mutex::new(Vec::new());
+
+static REMOVE_AT_EXIT: Once = Once::new();
+
+// <feature:kafka>
+static KAFKA: OnceLock<String> = OnceLock::new();
+// </feature:kafka>
+
+pub fn enabled() -> bool {
+    std::env::var(TEST_CONTAINERS_ENV).is_ok_and(|value| value == "1")
+}
+
+/// Start a Postgres container, returning the URL of its `postgres` database
+///
+/// Returns once Postgres accepts connections; migrations are left to `bootstrap`.
+pub fn start_postgres() -> String {
+    let (host, port) = start(Postgres::default(), POSTGRES_PORT);
+    format!("postgresql://postgres:postgres@{host}:{port}/postgres")
+}
+
+// <feature:kafka>
+/// Bootstrap servers of the run's Kafka container, started on the first call
+pub fn kafka_bootstrap_servers() -> &'static str {
+    KAFKA.get_or_init(|| {
+        let (host, port) = start(Kafka::default(), KAFKA_PORT.as_u16());
+        format!("{host}:{port}")
+    })
+}
+// </feature:kafka>
+
+/// Start `image` and return the host and port `container_port` is published on
+///
+/// The blocking runner drives its own runtime, so it runs on a thread of its own rather
+/// than inside the calling test's.
+fn start<I: Image + Send + 'static>(image: I, container_port: u16) -> (String, u16) {
+    let (container, host, port) = std::thread::spawn(move || {
+        let container: Container<I> = image.start()?;
+        let host = container.get_host()?.to_string();
+        let port = container.get_host_port_ipv4(container_port)?;
+        anyhow::Ok((container, host, port))
+    })
+    .join()
+    .expect("Starting the container panicked")
+    .unwrap_or_else(|e| panic!("Failed to start a test container, is Docker running? {e:#}"));
+
+    REMOVE_AT_EXIT.call_once(|| {
+        // SAFETY: `remove_containers` is a plain function that lives as long as the process
+        unsafe { libc::atexit(remove_containers) };
+    });
+    STARTED
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(Box::new(container));
+
+    (host, port)
+}
+
+/// Remove every container started by the run; dropping one stops and removes it
+extern "C" fn remove_containers() {
+    let started = std::mem::take(&mut *STARTED.lock().unwrap_or_else(PoisonError::into_inner));
+    drop(started);
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use rust_service_template::api::build_app_router;
+
+use super::{super::*, admin_request, token};
+
+#[tokio::test]
+async fn test_admin_config_requires_authentication() {
+    // Objective: Verify the loaded configuration is not exposed anonymously
+    let (mut state, _db) = common::app_state().await;
+    state.env.admin_endpoints = true;
+    let app = build_app_router(Arc::new(state)).await;
+
+    // Act: Request the config without a token
+    let (status, body) = admin_request(&app, "GET", "/admin/config", None, None).await;
+
+    // Assert: Verify the request is rejected
+    assert_eq!(status, 401, "Config should require a token");
+    assert_eq!(body["code"], "TokenNotFound");
+}
+
+#[tokio::test]
+async fn test_admin_config_returns_loaded_config_without_secrets() {
+    // Objective: Verify the endpoint shows the running configuration with secrets redacted
+    let (mut state, _db) = common::app_state().await;
+    state.env.admin_endpoints = true;
+    let jwt_secret = state.env.jwt_secret.clone();
+    let database_password = url::Url::parse(&state.env.database_url)
+        .unwrap()
+        .password()
+        .map(str::to_string);
+    let app = build_app_router(Arc::new(state)).await;
+
+    // Act: Request the config with a valid token
+    let (status, body) = admin_request(&app, "GET", "/admin/config", Some(&token()), None).await;
+
+    // Assert: Verify loaded values are shown and secrets are not
+    assert_eq!(status, 200, "Config should be returned");
+    assert_eq!(body["server_host"], "127.0.0.1");
+    assert_eq!(body["admin_endpoints"], true);
+    assert_eq!(body["jwt_secret"]["length"], jwt_secret.len());
+    let output = body.to_string();
+    assert!(!output.contains(&jwt_secret), "JWT secret leaked: {output}");
+    if let Some(password) = database_password {
+        assert!(
+            !output.contains(&format!(":{password}@")),
+            "Database password leaked: {output}"
+        );
+    }
+}
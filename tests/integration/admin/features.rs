@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use rust_service_template::{api::build_app_router, config::AppConfig};
+
+use super::{super::*, admin_request, token};
+
+#[tokio::test]
+async fn test_admin_features_lists_every_flag_with_its_value() {
+    // Objective: Verify configured flags are reported and unset ones show their default
+    let (mut state, _db) = common::app_state_with(|config: &mut AppConfig| {
+        config
+            .features
+            .insert("reject_duplicate_titles".to_string(), true);
+    })
+    .await;
+    state.env.admin_endpoints = true;
+    let app = build_app_router(Arc::new(state)).await;
+
+    // Act: Request the flags with a valid token
+    let (status, body) = admin_request(&app, "GET", "/admin/features", Some(&token()), None).await;
+
+    // Assert: Verify every flag is listed
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(
+        body["features"],
+        serde_json::json!({
+            "enforce_quotas": true,
+            "reject_duplicate_titles": true,
+            "warn_duplicate_titles": false
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_admin_features_requires_authentication() {
+    // Negative test: flags are not exposed anonymously
+    let (mut state, _db) = common::app_state().await;
+    state.env.admin_endpoints = true;
+    let app = build_app_router(Arc::new(state)).await;
+
+    // Act: Request the flags without a token
+    let (status, body) = admin_request(&app, "GET", "/admin/features", None, None).await;
+
+    // Assert: Verify the request is rejected
+    assert_eq!(status, 401);
+    assert_eq!(body["code"], "TokenNotFound");
+}
@@ -0,0 +1,270 @@
+use std::sync::Arc;
+
+use rust_service_template::{
+    api::build_app_router,
+    infrastructure::jobs::{JobRunner, PostgresJobQueue},
+};
+use sqlx::PgPool;
+
+use super::{
+    super::{
+        jobs::{fast_config, reminder, RecordingHandler, KIND},
+        *,
+    },
+    admin_request, token,
+};
+
+/// Router with the admin endpoints mounted, and the database behind it
+async fn admin_app() -> (Router, common::TestDatabase) {
+    let (mut state, db) = common::app_state().await;
+    state.env.admin_endpoints = true;
+    (build_app_router(Arc::new(state)).await, db)
+}
+
+/// Enqueue a job and let a failing handler use up its single attempt
+async fn dead_job(db: &PgPool) -> String {
+    let runner = JobRunner::new(db.clone(), &fast_config(1)).with_handler(Arc::new(
+        RecordingHandler::failing("reminder service unavailable"),
+    ));
+    let id = PostgresJobQueue::new(db.clone())
+        .enqueue(reminder(Uuid::new_v4()))
+        .await
+        .unwrap();
+    assert_eq!(runner.run_once().await.unwrap(), 1);
+    id.to_string()
+}
+
+#[tokio::test]
+async fn test_dead_job_is_listed_and_resurrected() {
+    // Objective: Verify a job that died is listed as failed and, once retried through the
+    // endpoint, runs again and succeeds
+    let (app, db) = admin_app().await;
+    let admin = issue_admin_token(UserId::new());
+    let id = dead_job(&db).await;
+
+    // Act: List failed jobs of the kind, then retry the dead one
+    let (status, page) = admin_request(
+        &app,
+        "GET",
+        &format!("/admin/jobs?status=failed&kind={KIND}"),
+        Some(&admin),
+        None,
+    )
+    .await;
+    let (retry_status, retried) = admin_request(
+        &app,
+        "POST",
+        &format!("/admin/jobs/{id}/retry"),
+        Some(&admin),
+        None,
+    )
+    .await;
+
+    // Assert: Verify the listing shows the attempts and last error
+    assert_eq!(status, 200, "{page}");
+    assert_eq!(page["total"], 1);
+    assert_eq!(page["items"][0]["id"], id);
+    assert_eq!(page["items"][0]["status"], "dead");
+    assert_eq!(page["items"][0]["attempts"], 1);
+    assert!(
+        page["items"][0]["last_error"]
+            .as_str()
+            .is_some_and(|error| error.contains("reminder service unavailable")),
+        "{page}"
+    );
+    // Assert: Verify the retried job is pending with its attempts reset
+    assert_eq!(retry_status, 200, "{retried}");
+    assert_eq!(retried["status"], "pending");
+    assert_eq!(retried["attempts"], 0);
+
+    // Act: Run the queue with a handler that now succeeds
+    let handler = Arc::new(RecordingHandler::default());
+    let runner = JobRunner::new((*db).clone(), &fast_config(1)).with_handler(handler.clone());
+    assert_eq!(runner.run_once().await.unwrap(), 1);
+
+    // Assert: Verify the job ran and is gone
+    assert_eq!(handler.ran()[0].id.to_string(), id);
+    let (status, _) = admin_request(
+        &app,
+        "POST",
+        &format!("/admin/jobs/{id}/retry"),
+        Some(&admin),
+        None,
+    )
+    .await;
+    assert_eq!(status, 404);
+}
+
+#[tokio::test]
+async fn test_list_jobs_filters_and_paginates() {
+    // Objective: Verify jobs are filtered by status and kind, and paged oldest first
+    let (app, db) = admin_app().await;
+    let admin = issue_admin_token(UserId::new());
+    let dead = dead_job(&db).await;
+    let queue = PostgresJobQueue::new((*db).clone());
+    let mut pending = Vec::new();
+    for _ in 0..3 {
+        pending.push(
+            queue
+                .enqueue(reminder(Uuid::new_v4()))
+                .await
+                .unwrap()
+                .to_string(),
+        );
+    }
+
+    // Act: Page through pending jobs two at a time
+    let (_, first) = admin_request(
+        &app,
+        "GET",
+        "/admin/jobs?status=pending&limit=2",
+        Some(&admin),
+        None,
+    )
+    .await;
+    let (_, second) = admin_request(
+        &app,
+        "GET",
+        "/admin/jobs?status=pending&limit=2&offset=2",
+        Some(&admin),
+        None,
+    )
+    .await;
+    let (_, other_kind) = admin_request(
+        &app,
+        "GET",
+        "/admin/jobs?kind=purge_tasks",
+        Some(&admin),
+        None,
+    )
+    .await;
+    let (_, everything) = admin_request(&app, "GET", "/admin/jobs", Some(&admin), None).await;
+
+    // Assert: Verify the pages, the filters and the totals
+    let ids = |page: &Value| -> Vec<String> {
+        page["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|job| job["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(ids(&first), pending[..2]);
+    assert_eq!(ids(&second), pending[2..]);
+    assert_eq!(
+        (&first["total"], &first["limit"], &second["offset"]),
+        (&3.into(), &2.into(), &2.into())
+    );
+    assert_eq!(first["next_offset"], 2);
+    assert!(second.get("next_offset").is_none(), "{second}");
+    assert_eq!(other_kind["total"], 0);
+    assert_eq!(everything["total"], 4);
+    assert!(ids(&everything).contains(&dead));
+}
+
+#[tokio::test]
+async fn test_list_jobs_rejects_unknown_status_and_oversized_pages() {
+    // Negative test: Filters the queue has no answer for are rejected
+    let (app, _db) = admin_app().await;
+    let admin = issue_admin_token(UserId::new());
+
+    for uri in [
+        "/admin/jobs?status=exploded",
+        "/admin/jobs?limit=101",
+        "/admin/jobs?limit=0",
+    ] {
+        // Act: List jobs
+        let (status, body) = admin_request(&app, "GET", uri, Some(&admin), None).await;
+
+        // Assert: Verify the request is rejected
+        assert_eq!(status, 400, "{uri}: {body}");
+    }
+}
+
+#[tokio::test]
+async fn test_discard_job_deletes_it_but_not_a_running_one() {
+    // Objective: Verify a dead job can be discarded, and a running one cannot
+    let (app, db) = admin_app().await;
+    let admin = issue_admin_token(UserId::new());
+    let dead = dead_job(&db).await;
+    let queue = PostgresJobQueue::new((*db).clone());
+    let running = queue.enqueue(reminder(Uuid::new_v4())).await.unwrap();
+    queue
+        .claim("elsewhere", &[KIND], 1, std::time::Duration::from_secs(60))
+        .await
+        .unwrap();
+
+    // Act: Discard both
+    let (dead_status, _) = admin_request(
+        &app,
+        "DELETE",
+        &format!("/admin/jobs/{dead}"),
+        Some(&admin),
+        None,
+    )
+    .await;
+    let (running_status, body) = admin_request(
+        &app,
+        "DELETE",
+        &format!("/admin/jobs/{running}"),
+        Some(&admin),
+        None,
+    )
+    .await;
+
+    // Assert: Verify the dead job is gone and the running one is kept
+    assert_eq!(dead_status, 204);
+    let (status, _) = admin_request(
+        &app,
+        "DELETE",
+        &format!("/admin/jobs/{dead}"),
+        Some(&admin),
+        None,
+    )
+    .await;
+    assert_eq!(status, 404);
+    assert_eq!(running_status, 409, "{body}");
+    assert_eq!(body["code"], "Conflict");
+}
+
+#[tokio::test]
+async fn test_retrying_a_job_that_is_not_dead_is_a_conflict() {
+    // Negative test: A pending job is already retried on its own
+    let (app, db) = admin_app().await;
+    let id = PostgresJobQueue::new((*db).clone())
+        .enqueue(reminder(Uuid::new_v4()))
+        .await
+        .unwrap();
+
+    // Act: Retry the pending job
+    let (status, body) = admin_request(
+        &app,
+        "POST",
+        &format!("/admin/jobs/{id}/retry"),
+        Some(&issue_admin_token(UserId::new())),
+        None,
+    )
+    .await;
+
+    // Assert: Verify the conflict
+    assert_eq!(status, 409, "{body}");
+}
+
+#[tokio::test]
+async fn test_job_endpoints_require_the_admin_role() {
+    // Negative test: Tokens without the admin role cannot see or change jobs
+    let (app, db) = admin_app().await;
+    let id = dead_job(&db).await;
+
+    for (method, uri) in [
+        ("GET", "/admin/jobs".to_string()),
+        ("POST", format!("/admin/jobs/{id}/retry")),
+        ("DELETE", format!("/admin/jobs/{id}")),
+    ] {
+        // Act: Call the endpoint with a plain user's token
+        let (status, body) = admin_request(&app, method, &uri, Some(&token()), None).await;
+
+        // Assert: Verify access is forbidden
+        assert_eq!(status, 403, "{method} {uri}: {body}");
+    }
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use axum::Router;
+use rust_service_template::{
+    api::build_app_router,
+    infrastructure::log_level::{reloadable_filter, ReloadableFilter},
+};
+use tracing_subscriber::EnvFilter;
+
+use super::{super::*, admin_request, token};
+use crate::common::TestDatabase;
+
+const LOG_LEVEL: &str = "/admin/log-level";
+
+/// Admin-enabled app with its own reloadable filter
+///
+/// The returned layer must be kept alive for the log level handle to keep working, and the
+/// database for the app to keep its schema.
+async fn admin_app(initial: &str) -> (Router, ReloadableFilter, TestDatabase) {
+    let (mut state, db) = common::app_state().await;
+    state.env.admin_endpoints = true;
+    let (layer, handle) = reloadable_filter(EnvFilter::new(initial));
+    state.log_level = Some(handle);
+
+    (build_app_router(Arc::new(state)).await, layer, db)
+}
+
+#[tokio::test]
+async fn test_log_level_endpoint_is_not_mounted_by_default() {
+    // Objective: Verify admin routes only exist when `admin_endpoints` is enabled
+    let (app, _db) = common::app().await;
+
+    // Act: Read the log level with a valid token
+    let (status, body) = admin_request(&app, "GET", LOG_LEVEL, Some(&token()), None).await;
+
+    // Assert: Verify the route is unknown
+    assert_eq!(status, 404, "Admin routes should not be mounted");
+    assert_eq!(body["code"], "NotFound");
+}
+
+#[tokio::test]
+async fn test_log_level_requires_authentication() {
+    // Objective: Verify the log level cannot be read or changed anonymously
+    let (app, _layer, _db) = admin_app("info").await;
+
+    // Act: Call both methods without a token
+    let (get_status, _) = admin_request(&app, "GET", LOG_LEVEL, None, None).await;
+    let (put_status, _) = admin_request(
+        &app,
+        "PUT",
+        LOG_LEVEL,
+        None,
+        Some(r#"{"directives": "debug"}"#),
+    )
+    .await;
+
+    // Assert: Verify both are rejected
+    assert_eq!(get_status, 401, "GET should require a token");
+    assert_eq!(put_status, 401, "PUT should require a token");
+}
+
+#[tokio::test]
+async fn test_log_level_can_be_read_and_updated() {
+    // Objective: Verify the active filter is reported and replaced at runtime
+    let (app, _layer, _db) = admin_app("info").await;
+    let token = token();
+
+    // Act: Read, update, then read again
+    let (initial_status, initial) = admin_request(&app, "GET", LOG_LEVEL, Some(&token), None).await;
+    let (update_status, updated) = admin_request(
+        &app,
+        "PUT",
+        LOG_LEVEL,
+        Some(&token),
+        Some(r#"{"directives": "rust_service_template=trace,sqlx=warn"}"#),
+    )
+    .await;
+    let (_, current) = admin_request(&app, "GET", LOG_LEVEL, Some(&token), None).await;
+
+    // Assert: Verify the new directives are active
+    assert_eq!(initial_status, 200);
+    assert_eq!(initial["directives"], "info");
+    assert_eq!(update_status, 200, "Valid directives should be accepted");
+    assert_eq!(updated, current, "PUT should return the active filter");
+    let directives = current["directives"].as_str().unwrap();
+    assert!(
+        directives.contains("rust_service_template=trace") && directives.contains("sqlx=warn"),
+        "Unexpected directives: {directives}"
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_log_directive_returns_400_and_keeps_filter() {
+    // Objective: Verify a malformed directive is rejected with the parse error
+    let (app, _layer, _db) = admin_app("info").await;
+    let token = token();
+
+    // Act: Submit a directive with an unknown level
+    let (status, body) = admin_request(
+        &app,
+        "PUT",
+        LOG_LEVEL,
+        Some(&token),
+        Some(r#"{"directives": "rust_service_template=loud"}"#),
+    )
+    .await;
+    let (_, current) = admin_request(&app, "GET", LOG_LEVEL, Some(&token), None).await;
+
+    // Assert: Verify the error explains the problem and nothing changed
+    assert_eq!(status, 400, "Invalid directives should be rejected");
+    assert_eq!(body["code"], "BadRequest");
+    assert!(
+        body["message"]
+            .as_str()
+            .is_some_and(|message| message.starts_with("Invalid log directive")),
+        "Parse error should be returned: {body}"
+    );
+    assert_eq!(current["directives"], "info", "Filter should be unchanged");
+}
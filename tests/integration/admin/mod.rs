@@ -0,0 +1,51 @@
+pub mod config;
+pub mod features;
+pub mod jobs;
+pub mod log_level;
+pub mod tasks;
+
+use axum::{body::Body, http::Request, Router};
+use http_body_util::BodyExt;
+use rust_service_template::common::UserId;
+use serde_json::Value;
+use tower::ServiceExt;
+
+use super::{issue_test_token, parse_json_response};
+
+/// Token signed with the test secret for a random user
+pub fn token() -> String {
+    issue_test_token(UserId::new(), chrono::Duration::hours(1))
+}
+
+/// Send a request to an admin route, optionally authenticated and with a JSON body
+///
+/// An empty response body comes back as `Value::Null`.
+pub async fn admin_request(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<&str>,
+) -> (u16, Value) {
+    let mut builder = Request::builder().method(method).uri(uri);
+    if let Some(token) = token {
+        builder = builder.header("Authorization", format!("Bearer {token}"));
+    }
+    let request = match body {
+        Some(body) => builder
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string())),
+        None => builder.body(Body::empty()),
+    }
+    .unwrap();
+
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status().as_u16();
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body = if body_bytes.is_empty() {
+        Value::Null
+    } else {
+        parse_json_response(&body_bytes)
+    };
+    (status, body)
+}
@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use rust_service_template::{
+    api::build_app_router, common::TenantId, domain::task::models::TaskStatus,
+};
+
+use super::{super::*, admin_request, token};
+
+/// Router with the admin endpoints mounted, and the database behind it
+async fn admin_app() -> (Router, common::TestDatabase) {
+    let (mut state, db) = common::app_state().await;
+    state.env.admin_endpoints = true;
+    (build_app_router(Arc::new(state)).await, db)
+}
+
+/// Titles of the tasks on a page, in order
+fn titles(page: &Value) -> Vec<&str> {
+    page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|task| task["title"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_search_tasks_filters_across_users_and_paginates() {
+    // Objective: Verify an admin sees matching tasks of every user, a page at a time,
+    // with the total across pages
+    let (app, db) = admin_app().await;
+    let admin = issue_admin_token(UserId::new());
+    for (title, priority) in [
+        ("Report draft", TaskPriority::Low),
+        ("Quarterly report", TaskPriority::Critical),
+        ("Report review", TaskPriority::High),
+    ] {
+        TaskFixture::new(UserId::new())
+            .title(title)
+            .priority(priority)
+            .insert(&db)
+            .await;
+    }
+    TaskFixture::new(UserId::new())
+        .title("Groceries")
+        .insert(&db)
+        .await;
+    TaskFixture::new(UserId::new())
+        .title("Report of another tenant")
+        .tenant(TenantId::new())
+        .insert(&db)
+        .await;
+
+    // Act: Search titles by priority, highest first, two per page
+    let uri = "/admin/tasks?title=REPORT&sort=priority&order=desc&limit=2";
+    let (first_status, first) = admin_request(&app, "GET", uri, Some(&admin), None).await;
+    let (second_status, second) =
+        admin_request(&app, "GET", &format!("{uri}&offset=2"), Some(&admin), None).await;
+
+    // Assert: Pages follow the order and both count every match in the tenant
+    assert_eq!(first_status, 200, "{first}");
+    assert_eq!(second_status, 200, "{second}");
+    assert_eq!(titles(&first), ["Quarterly report", "Report review"]);
+    assert_eq!(titles(&second), ["Report draft"]);
+    assert_eq!(first["total"], 3);
+    assert_eq!(second["total"], 3);
+    assert_eq!(first["limit"], 2);
+    assert_eq!(second["offset"], 2);
+}
+
+#[tokio::test]
+async fn test_search_tasks_by_owner_status_and_id() {
+    // Objective: Verify the owner, status and id fragment filters combine
+    let (app, db) = admin_app().await;
+    let admin = issue_admin_token(UserId::new());
+    let owner = UserId::new();
+    let done = TaskFixture::new(owner)
+        .status(TaskStatus::Completed)
+        .insert(&db)
+        .await;
+    TaskFixture::new(owner).insert(&db).await;
+    TaskFixture::new(UserId::new())
+        .status(TaskStatus::Completed)
+        .insert(&db)
+        .await;
+    let fragment = &done.id.to_string()[..8];
+
+    // Act: Search by owner and status, then by id fragment alone
+    let (status, by_owner) = admin_request(
+        &app,
+        "GET",
+        &format!("/admin/tasks?user_id={owner}&status=Completed"),
+        Some(&admin),
+        None,
+    )
+    .await;
+    let (_, by_id) = admin_request(
+        &app,
+        "GET",
+        &format!("/admin/tasks?id={fragment}"),
+        Some(&admin),
+        None,
+    )
+    .await;
+
+    // Assert: Each search finds only the completed task of the owner
+    assert_eq!(status, 200, "{by_owner}");
+    assert_eq!(by_owner["total"], 1);
+    assert_eq!(by_owner["items"][0]["id"], done.id.to_string());
+    assert_eq!(by_id["total"], 1);
+    assert_eq!(by_id["items"][0]["id"], done.id.to_string());
+}
+
+#[tokio::test]
+async fn test_search_tasks_requires_admin() {
+    // Negative test: Users without the admin role cannot read other users' tasks
+    let (app, _db) = admin_app().await;
+
+    // Act: Search anonymously and with a user token
+    let (anonymous, _) = admin_request(&app, "GET", "/admin/tasks", None, None).await;
+    let (user, body) = admin_request(&app, "GET", "/admin/tasks", Some(&token()), None).await;
+
+    // Assert: Verify both are rejected
+    assert_eq!(anonymous, 401, "Search should require a token");
+    assert_eq!(user, 403, "Search should require the admin role");
+    assert_eq!(body["code"], "Forbidden");
+}
+
+#[tokio::test]
+async fn test_search_tasks_rejects_invalid_filters() {
+    // Negative test: Malformed filters are rejected, with every invalid field reported
+    let (app, _db) = admin_app().await;
+    let admin = issue_admin_token(UserId::new());
+
+    for uri in [
+        "/admin/tasks?status=Exploded",
+        "/admin/tasks?sort=owner",
+        "/admin/tasks?user_id=not-a-uuid",
+        "/admin/tasks?created_from=yesterday",
+    ] {
+        // Act: Search
+        let (status, body) = admin_request(&app, "GET", uri, Some(&admin), None).await;
+
+        // Assert: Verify the request is rejected
+        assert_eq!(status, 400, "{uri}: {body}");
+    }
+
+    // Act: Search with several invalid values at once
+    let (status, body) = admin_request(
+        &app,
+        "GET",
+        "/admin/tasks?limit=101&created_from=2025-03-02T00:00:00Z&created_before=2025-03-01T00:00:00Z&id=abc'%20OR%201=1",
+        Some(&admin),
+        None,
+    )
+    .await;
+
+    // Assert: Verify each invalid field is listed
+    assert_eq!(status, 400, "{body}");
+    let fields: Vec<_> = body["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error| error["field"].as_str().unwrap())
+        .collect();
+    assert_eq!(fields, ["limit", "created_from", "id"]);
+}
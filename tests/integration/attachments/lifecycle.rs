@@ -0,0 +1,224 @@
+use std::sync::Arc;
+
+use rust_service_template::{api::build_app_router, config::AppState};
+
+use super::super::*;
+
+/// Router over `state`, keeping the state for reaching the filesystem store
+async fn attachments_app() -> (Router, Arc<AppState>, common::TestDatabase) {
+    let (state, db) = common::app_state().await;
+    let state = Arc::new(state);
+    (build_app_router(state.clone()).await, state, db)
+}
+
+/// Send an attachments API request as the owner of `token`, returning the parsed body
+async fn request(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: &str,
+    body: Option<Value>,
+) -> (u16, Value) {
+    let body = body.map(|body| create_json_body(&body.to_string()));
+    let (status, body_bytes) = make_authenticated_request(app, method, uri, body, token).await;
+    let body = if body_bytes.is_empty() {
+        Value::Null
+    } else {
+        parse_json_response(&body_bytes)
+    };
+    (status, body)
+}
+
+/// PUT `bytes` to the upload URL of a `POST .../attachments` response, as a client would
+async fn upload(app: &Router, created: &Value, bytes: &'static [u8]) -> u16 {
+    let url = url::Url::parse(created["upload"]["url"].as_str().unwrap()).unwrap();
+    let mut request =
+        Request::builder()
+            .method("PUT")
+            .uri(format!("{}?{}", url.path(), url.query().unwrap()));
+    for (name, value) in created["upload"]["headers"].as_object().unwrap() {
+        request = request.header(name, value.as_str().unwrap());
+    }
+    let response = app
+        .clone()
+        .oneshot(request.body(Body::from(bytes)).unwrap())
+        .await
+        .unwrap();
+    response.status().as_u16()
+}
+
+#[tokio::test]
+async fn test_attachment_is_uploaded_listed_and_deleted_with_its_file() {
+    // Objective: Verify the full lifecycle against the filesystem store: the presigned URL
+    // accepts the file, the attachment is listed, and deleting it removes the file
+    let (app, state, db) = attachments_app().await;
+    let owner = UserId::new();
+    let token = issue_test_token(owner, chrono::Duration::hours(1));
+    let task = TaskFixture::new(owner).insert(&db).await;
+    let uri = format!("/tasks/{}/attachments", task.id);
+
+    // Act: Request an upload, upload the file, then list the task's attachments
+    let (create_status, created) = request(
+        &app,
+        "POST",
+        &uri,
+        &token,
+        Some(serde_json::json!({
+            "filename": "notes.txt",
+            "content_type": "text/plain; charset=utf-8",
+            "size": 5
+        })),
+    )
+    .await;
+    let upload_status = upload(&app, &created, b"hello").await;
+    let (_, listed) = request(&app, "GET", &uri, &token, None).await;
+
+    // Assert: The upload is stored under the attachment's object key
+    assert_eq!(create_status, 201, "{created}");
+    assert_eq!(created["upload"]["method"], "PUT");
+    assert_eq!(created["attachment"]["content_type"], "text/plain");
+    assert_eq!(upload_status, 200);
+    let attachment_id = created["attachment"]["id"].as_str().unwrap();
+    let local = state.local_uploads.clone().unwrap();
+    let file = local
+        .path(&format!("tasks/{}/{attachment_id}", task.id))
+        .unwrap();
+    assert_eq!(std::fs::read(&file).unwrap(), b"hello");
+    assert_eq!(listed, serde_json::json!([created["attachment"]]));
+
+    // Act: Delete the attachment
+    let (delete_status, _) = request(
+        &app,
+        "DELETE",
+        &format!("{uri}/{attachment_id}"),
+        &token,
+        None,
+    )
+    .await;
+    let (_, listed) = request(&app, "GET", &uri, &token, None).await;
+
+    // Assert: The row and the file are gone, and nothing is left for the sweep
+    assert_eq!(delete_status, 204);
+    assert_eq!(listed, serde_json::json!([]));
+    assert!(!file.exists(), "File should be deleted");
+    let orphans: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM orphaned_objects")
+        .fetch_one(&*db)
+        .await
+        .unwrap();
+    assert_eq!(orphans, 0);
+}
+
+#[tokio::test]
+async fn test_create_attachment_reports_every_invalid_field() {
+    // Negative test: A disallowed type and an oversized file are rejected together,
+    // before anything is stored
+    let (app, _state, db) = attachments_app().await;
+    let owner = UserId::new();
+    let token = issue_test_token(owner, chrono::Duration::hours(1));
+    let task = TaskFixture::new(owner).insert(&db).await;
+    let uri = format!("/tasks/{}/attachments", task.id);
+
+    // Act: Request an upload of an executable over the size limit
+    let (status, body) = request(
+        &app,
+        "POST",
+        &uri,
+        &token,
+        Some(serde_json::json!({
+            "filename": "setup.exe",
+            "content_type": "application/x-msdownload",
+            "size": 1_u64 << 40
+        })),
+    )
+    .await;
+    let (_, listed) = request(&app, "GET", &uri, &token, None).await;
+
+    // Assert: Both fields are reported and no attachment exists
+    assert_eq!(status, 400, "{body}");
+    let fields: Vec<_> = body["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error| error["field"].as_str().unwrap())
+        .collect();
+    assert_eq!(fields, ["content_type", "size"]);
+    assert_eq!(listed, serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn test_attachments_of_unknown_or_foreign_tasks_are_refused() {
+    // Negative test: A missing task is 404, and another user's task is 403
+    let (app, _state, db) = attachments_app().await;
+    let token = issue_test_token(UserId::new(), chrono::Duration::hours(1));
+    let foreign = TaskFixture::new(UserId::new()).insert(&db).await;
+
+    // Act: List the attachments of both
+    let (missing_status, _) = request(
+        &app,
+        "GET",
+        &format!("/tasks/{}/attachments", Uuid::new_v4()),
+        &token,
+        None,
+    )
+    .await;
+    let (foreign_status, _) = request(
+        &app,
+        "GET",
+        &format!("/tasks/{}/attachments", foreign.id),
+        &token,
+        None,
+    )
+    .await;
+
+    // Assert: Verify the status codes
+    assert_eq!(missing_status, 404);
+    assert_eq!(foreign_status, 403);
+}
+
+#[tokio::test]
+async fn test_upload_url_only_accepts_the_declared_file() {
+    // Negative test: The signature covers the size, so neither a tampered URL nor a body
+    // of another size is stored
+    let (app, state, db) = attachments_app().await;
+    let owner = UserId::new();
+    let token = issue_test_token(owner, chrono::Duration::hours(1));
+    let task = TaskFixture::new(owner).insert(&db).await;
+    let (_, created) = request(
+        &app,
+        "POST",
+        &format!("/tasks/{}/attachments", task.id),
+        &token,
+        Some(serde_json::json!({
+            "filename": "notes.txt",
+            "content_type": "text/plain",
+            "size": 5
+        })),
+    )
+    .await;
+    let mut tampered = created.clone();
+    tampered["upload"]["url"] = created["upload"]["url"]
+        .as_str()
+        .unwrap()
+        .replace("size=5", "size=6")
+        .into();
+    tampered["upload"]["headers"]["Content-Length"] = "6".into();
+
+    // Act: Upload six bytes with the tampered URL, then with the original one
+    let tampered_status = upload(&app, &tampered, b"hello!").await;
+    let oversized_status = upload(&app, &created, b"hello!").await;
+
+    // Assert: Both are refused and no file is written
+    assert_eq!(tampered_status, 401);
+    assert_eq!(oversized_status, 400);
+    let file = state
+        .local_uploads
+        .clone()
+        .unwrap()
+        .path(&format!(
+            "tasks/{}/{}",
+            task.id,
+            created["attachment"]["id"].as_str().unwrap()
+        ))
+        .unwrap();
+    assert!(!file.exists());
+}
@@ -0,0 +1,137 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use rust_service_template::{
+    domain::{
+        attachment::{
+            models::{Attachment, AttachmentLimits, PresignedUpload},
+            operations::{delete_attachment, delete_orphaned_objects},
+        },
+        errors::{DomainError, ExternalSystem},
+        interfaces::{attachment_repository::AttachmentRepository, object_store::ObjectStore},
+    },
+    infrastructure::{
+        attachment::PostgresAttachmentRepository, filesystem_object_store::FilesystemObjectStore,
+    },
+};
+
+use super::super::*;
+
+/// Object store that is down: presigning works, every delete fails
+#[derive(Debug)]
+struct UnreachableStore;
+
+#[async_trait]
+impl ObjectStore for UnreachableStore {
+    fn name(&self) -> &'static str {
+        "unreachable"
+    }
+
+    fn presign_put(
+        &self,
+        _object_key: &str,
+        _content_type: &str,
+        _size: u64,
+        expires_in: Duration,
+    ) -> Result<PresignedUpload, DomainError> {
+        Ok(PresignedUpload {
+            url: "http://store.invalid/upload".to_string(),
+            headers: Vec::new(),
+            expires_at: chrono::Utc::now() + expires_in,
+        })
+    }
+
+    async fn delete(&self, _object_key: &str) -> Result<(), DomainError> {
+        Err(DomainError::external_error(
+            ExternalSystem::ObjectStore,
+            "Connection refused",
+        ))
+    }
+}
+
+/// An attachment of `task_id` stored through the repository, with its file in `store`
+async fn attachment_with_file(
+    repo: &Arc<dyn AttachmentRepository>,
+    store: &FilesystemObjectStore,
+    task_id: rust_service_template::domain::task::models::TaskId,
+) -> Attachment {
+    let limits = AttachmentLimits {
+        max_size: 1024,
+        allowed_content_types: vec!["text/plain".to_string()],
+    };
+    let attachment = Attachment::new(task_id, "notes.txt", "text/plain", 5, &limits).unwrap();
+    let file = store.path(&attachment.object_key).unwrap();
+    std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+    std::fs::write(&file, b"hello").unwrap();
+    repo.create(attachment).await.unwrap()
+}
+
+async fn orphaned_keys(db: &sqlx::PgPool) -> Vec<String> {
+    sqlx::query_scalar("SELECT object_key FROM orphaned_objects ORDER BY object_key")
+        .fetch_all(db)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_objects_of_a_deleted_task_are_swept() {
+    // Objective: Verify deleting a task records its attachments' objects as orphaned,
+    // and the sweep deletes them and forgets them
+    let (_state, db) = common::app_state().await;
+    let root = tempfile::tempdir().unwrap();
+    let store = Arc::new(FilesystemObjectStore::new(root.path(), "http://localhost"));
+    let repo: Arc<dyn AttachmentRepository> =
+        Arc::new(PostgresAttachmentRepository::new((*db).clone()));
+    let task = TaskFixture::new(UserId::new()).insert(&db).await;
+    let attachment = attachment_with_file(&repo, &store, task.id).await;
+    let file = store.path(&attachment.object_key).unwrap();
+
+    // Act: Delete the task, cascading to its attachment, then run the sweep
+    sqlx::query("DELETE FROM tasks WHERE id = $1")
+        .bind(task.id.into_inner())
+        .execute(&*db)
+        .await
+        .unwrap();
+    let orphaned = orphaned_keys(&db).await;
+    let deleted = delete_orphaned_objects(100, repo.clone(), store.clone())
+        .await
+        .unwrap();
+
+    // Assert: The object was recorded, deleted, then forgotten
+    assert_eq!(orphaned, vec![attachment.object_key.clone()]);
+    assert_eq!(deleted, 1);
+    assert!(!file.exists(), "File should be deleted");
+    assert!(orphaned_keys(&db).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_object_the_store_fails_to_delete_stays_orphaned() {
+    // Negative test: With the store down, deleting an attachment still succeeds and its
+    // object is left for the sweep, which keeps it until the store deletes it
+    let (_state, db) = common::app_state().await;
+    let root = tempfile::tempdir().unwrap();
+    let store = FilesystemObjectStore::new(root.path(), "http://localhost");
+    let repo: Arc<dyn AttachmentRepository> =
+        Arc::new(PostgresAttachmentRepository::new((*db).clone()));
+    let task = TaskFixture::new(UserId::new()).insert(&db).await;
+    let attachment = attachment_with_file(&repo, &store, task.id).await;
+
+    // Act: Delete the attachment and sweep while the store is unreachable
+    let deleted_attachment = delete_attachment(
+        task.id,
+        attachment.id,
+        repo.clone(),
+        Arc::new(UnreachableStore),
+    )
+    .await
+    .unwrap();
+    let swept = delete_orphaned_objects(100, repo.clone(), Arc::new(UnreachableStore))
+        .await
+        .unwrap();
+
+    // Assert: The attachment is gone but its object is still tracked
+    assert_eq!(deleted_attachment.id, attachment.id);
+    assert!(repo.list(task.id).await.unwrap().is_empty());
+    assert_eq!(swept, 0);
+    assert_eq!(orphaned_keys(&db).await, vec![attachment.object_key]);
+}
@@ -0,0 +1,89 @@
+use super::super::*;
+
+/// A user with one task, and the path listing their tasks
+async fn user_with_task(pool: &sqlx::PgPool) -> (UserId, String) {
+    let user_id = UserId::new();
+    TaskFixture::new(user_id)
+        .title(&generate_unique_title("owned"))
+        .insert(pool)
+        .await;
+    (user_id, format!("/users/{user_id}/tasks"))
+}
+
+#[tokio::test]
+async fn test_user_lists_own_tasks() {
+    // Objective: Verify a token's subject may list the tasks under their own path
+    let (app, db) = common::app().await;
+    let (user_id, path) = user_with_task(&db).await;
+    let token = issue_test_token(user_id, chrono::Duration::minutes(5));
+
+    // Act: List the user's tasks with their token
+    let (status, body_bytes) = make_authenticated_request(&app, "GET", &path, None, &token).await;
+
+    // Assert: Verify 200 OK with their task
+    assert_eq!(status, 200, "Owner should be allowed");
+    let tasks = parse_json_response(&body_bytes);
+    assert_eq!(tasks.as_array().unwrap().len(), 1);
+    assert_eq!(tasks[0]["user_id"], user_id.to_string());
+}
+
+#[tokio::test]
+async fn test_admin_lists_any_users_tasks() {
+    // Objective: Verify the admin role may list another user's tasks
+    let (app, db) = common::app().await;
+    let (_, path) = user_with_task(&db).await;
+    let token = issue_admin_token(UserId::new());
+
+    // Act: List the tasks as an admin who does not own them
+    let (status, body_bytes) = make_authenticated_request(&app, "GET", &path, None, &token).await;
+
+    // Assert: Verify 200 OK with the task
+    assert_eq!(status, 200, "Admin should be allowed");
+    assert_eq!(
+        parse_json_response(&body_bytes).as_array().unwrap().len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_other_users_tasks_return_403() {
+    // Negative test: A valid token for someone else is forbidden, not unauthenticated
+    let (app, db) = common::app().await;
+    let (_, path) = user_with_task(&db).await;
+    let token = issue_test_token(UserId::new(), chrono::Duration::minutes(5));
+
+    // Act: List the tasks with another user's token
+    let (status, body_bytes) = make_authenticated_request(&app, "GET", &path, None, &token).await;
+
+    // Assert: Verify 403 Forbidden
+    assert_eq!(status, 403, "Another user should be forbidden");
+    verify_error_response(&body_bytes, "Forbidden");
+}
+
+#[tokio::test]
+async fn test_listing_without_a_valid_token_returns_401() {
+    // Negative test: Missing and expired tokens are rejected, before the path is even parsed
+    let (app, db) = common::app().await;
+    let (user_id, path) = user_with_task(&db).await;
+
+    // Act: List without a token, with an expired one, and with one on a malformed path
+    let (missing, missing_body) = make_request(&app, "GET", &path, None).await;
+    let (expired, expired_body) =
+        make_authenticated_request(&app, "GET", &path, None, &issue_expired_token(user_id)).await;
+    let (malformed, malformed_body) = make_authenticated_request(
+        &app,
+        "GET",
+        "/users/not-a-uuid/tasks",
+        None,
+        &issue_expired_token(user_id),
+    )
+    .await;
+
+    // Assert: Verify 401 with the reason
+    assert_eq!(missing, 401, "A request without a token should be rejected");
+    verify_error_response(&missing_body, "TokenNotFound");
+    assert_eq!(expired, 401, "An expired token should be rejected");
+    verify_error_response(&expired_body, "InvalidToken");
+    assert_eq!(malformed, 401, "Authentication should come before the path");
+    verify_error_response(&malformed_body, "InvalidToken");
+}
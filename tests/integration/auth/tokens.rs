@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use rust_service_template::api::build_app_router;
+
+use super::super::*;
+
+/// Route every token is checked against; the admin routes are the ones behind JWTs
+const PROTECTED: &str = "/admin/config";
+
+async fn protected_app() -> (Router, common::TestDatabase) {
+    let (mut state, db) = common::app_state().await;
+    state.env.admin_endpoints = true;
+    (build_app_router(Arc::new(state)).await, db)
+}
+
+#[tokio::test]
+async fn test_valid_token_is_accepted() {
+    // Objective: Verify a token signed with the secret for this service is accepted
+    let (app, _db) = protected_app().await;
+    let token = issue_test_token(UserId::new(), chrono::Duration::minutes(5));
+
+    // Act: Call the protected route with the token
+    let (status, _) = make_authenticated_request(&app, "GET", PROTECTED, None, &token).await;
+
+    // Assert: Verify the request is served
+    assert_eq!(status, 200, "A valid token should be accepted");
+}
+
+#[tokio::test]
+async fn test_missing_token_returns_401() {
+    // Objective: Verify protected routes reject anonymous requests
+    // Negative test: No Authorization header
+    let (app, _db) = protected_app().await;
+
+    // Act: Call the protected route without a token
+    let (status, body_bytes) = make_request(&app, "GET", PROTECTED, None).await;
+
+    // Assert: Verify 401 Unauthorized naming the missing token
+    assert_eq!(status, 401, "A request without a token should be rejected");
+    verify_error_response(&body_bytes, "TokenNotFound");
+}
+
+#[tokio::test]
+async fn test_expired_token_returns_401() {
+    // Objective: Verify tokens are rejected once they expire
+    // Negative test: Correctly signed token past its expiry
+    let (app, _db) = protected_app().await;
+    let token = issue_expired_token(UserId::new());
+
+    // Act: Call the protected route with the expired token
+    let (status, body_bytes) =
+        make_authenticated_request(&app, "GET", PROTECTED, None, &token).await;
+
+    // Assert: Verify 401 Unauthorized
+    assert_eq!(status, 401, "An expired token should be rejected");
+    verify_error_response(&body_bytes, "InvalidToken");
+}
+
+#[tokio::test]
+async fn test_token_for_another_audience_returns_401() {
+    // Objective: Verify tokens issued for other services are not accepted here
+    // Negative test: Correctly signed, unexpired token with the wrong audience
+    let (app, _db) = protected_app().await;
+    let token = issue_wrong_audience_token(UserId::new());
+
+    // Act: Call the protected route with the token
+    let (status, body_bytes) =
+        make_authenticated_request(&app, "GET", PROTECTED, None, &token).await;
+
+    // Assert: Verify 401 Unauthorized
+    assert_eq!(
+        status, 401,
+        "A token for another audience should be rejected"
+    );
+    verify_error_response(&body_bytes, "InvalidToken");
+}
+
+#[tokio::test]
+async fn test_malformed_token_returns_401() {
+    // Objective: Verify a bearer value that is not a JWT is rejected
+    // Negative test: Arbitrary string in place of the token
+    let (app, _db) = protected_app().await;
+
+    // Act: Call the protected route with a malformed token
+    let (status, body_bytes) =
+        make_authenticated_request(&app, "GET", PROTECTED, None, "not-a-jwt").await;
+
+    // Assert: Verify 401 Unauthorized
+    assert_eq!(status, 401, "A malformed token should be rejected");
+    verify_error_response(&body_bytes, "InvalidToken");
+}
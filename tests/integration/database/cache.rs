@@ -0,0 +1,273 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_service_template::{
+    common::TenantId,
+    domain::{
+        errors::DomainError,
+        interfaces::task_repository::TaskUnitOfWork,
+        quota::models::TaskUsage,
+        task::models::{DailyTaskCount, StatsRange, TaskId, TaskPage, TaskSearch, TaskStatus},
+    },
+    infrastructure::{
+        cached_task::{CachedTaskRepository, MokaTaskCache},
+        in_memory_task::InMemoryTaskRepository,
+    },
+};
+use tokio::sync::Notify;
+
+use super::super::*;
+
+/// In-memory repository that counts reads and can pause one read after it hit storage
+#[derive(Debug, Default)]
+struct ObservedRepository {
+    inner: InMemoryTaskRepository,
+    reads: AtomicUsize,
+    pause_next_read: AtomicBool,
+    read_done: Notify,
+    resume: Notify,
+}
+
+#[async_trait]
+impl TaskRepository for ObservedRepository {
+    async fn create(&self, entity: Task) -> Result<Task, DomainError> {
+        self.inner.create(entity).await
+    }
+
+    async fn get(&self, tenant: TenantId, id: TaskId) -> Result<Option<Task>, DomainError> {
+        self.reads.fetch_add(1, Ordering::SeqCst);
+        let task = self.inner.get(tenant, id).await;
+        if self.pause_next_read.swap(false, Ordering::SeqCst) {
+            self.read_done.notify_one();
+            self.resume.notified().await;
+        }
+        task
+    }
+
+    async fn get_by_user(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+    ) -> Result<Vec<Task>, DomainError> {
+        self.inner.get_by_user(tenant, user_id).await
+    }
+
+    async fn daily_counts(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        range: &StatsRange,
+    ) -> Result<Vec<DailyTaskCount>, DomainError> {
+        self.inner.daily_counts(tenant, user_id, range).await
+    }
+
+    async fn usage(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<TaskUsage, DomainError> {
+        self.inner.usage(tenant, user_id, since).await
+    }
+
+    async fn search_all(
+        &self,
+        tenant: TenantId,
+        search: &TaskSearch,
+    ) -> Result<TaskPage, DomainError> {
+        self.inner.search_all(tenant, search).await
+    }
+
+    async fn update(&self, entity: &Task) -> Result<(), DomainError> {
+        self.inner.update(entity).await
+    }
+
+    async fn delete(&self, tenant: TenantId, id: TaskId) -> Result<(), DomainError> {
+        self.inner.delete(tenant, id).await
+    }
+
+    async fn health_check(&self) -> Result<(), DomainError> {
+        self.inner.health_check().await
+    }
+
+    async fn begin(&self) -> Result<Box<dyn TaskUnitOfWork>, DomainError> {
+        self.inner.begin().await
+    }
+}
+
+/// A cached repository over an observed in-memory one, with a task already stored
+async fn cached_repository_with_task(
+    ttl: Duration,
+) -> (CachedTaskRepository, Arc<ObservedRepository>, Task) {
+    let inner = Arc::new(ObservedRepository::default());
+    let task = Task::new(
+        TenantId::DEFAULT,
+        UserId::new(),
+        generate_unique_title("cached"),
+        None,
+        TaskPriority::Medium,
+    )
+    .unwrap();
+    inner.create(task.clone()).await.unwrap();
+
+    let repo = CachedTaskRepository::new(inner.clone(), Arc::new(MokaTaskCache::new(100, ttl)));
+    (repo, inner, task)
+}
+
+const LONG_TTL: Duration = Duration::from_secs(60);
+
+#[tokio::test]
+async fn test_repeated_get_is_served_from_cache() {
+    // Objective: Verify a cached task is not read from the inner repository again
+    let (repo, inner, task) = cached_repository_with_task(LONG_TTL).await;
+
+    // Act: Read the same task twice
+    let first = repo.get(task.tenant_id, task.id).await.unwrap();
+    let second = repo.get(task.tenant_id, task.id).await.unwrap();
+
+    // Assert: Verify only the first read reached the inner repository
+    assert_eq!(first.map(|t| t.id), Some(task.id));
+    assert_eq!(second.map(|t| t.id), Some(task.id));
+    assert_eq!(inner.reads.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_update_invalidates_cached_task() {
+    // Objective: Verify a read after an update never returns the cached old version
+    let (repo, _, mut task) = cached_repository_with_task(LONG_TTL).await;
+    repo.get(task.tenant_id, task.id).await.unwrap();
+
+    // Act: Change the status through the cached repository and read again
+    task.status = TaskStatus::Completed;
+    repo.update(&task).await.unwrap();
+    let stored = repo
+        .get(task.tenant_id, task.id)
+        .await
+        .unwrap()
+        .expect("Task should exist");
+
+    // Assert: Verify the new status is returned
+    assert_eq!(stored.status, TaskStatus::Completed);
+}
+
+#[tokio::test]
+async fn test_delete_invalidates_cached_task() {
+    // Objective: Verify a deleted task is not served from the cache
+    let (repo, _, task) = cached_repository_with_task(LONG_TTL).await;
+    repo.get(task.tenant_id, task.id).await.unwrap();
+
+    // Act: Delete the task and read it again
+    repo.delete(task.tenant_id, task.id).await.unwrap();
+    let stored = repo.get(task.tenant_id, task.id).await.unwrap();
+
+    // Assert: Verify the task is gone
+    assert!(stored.is_none(), "Deleted task should not be cached");
+}
+
+#[tokio::test]
+async fn test_committed_unit_of_work_invalidates_cached_tasks() {
+    // Objective: Verify writes through a unit of work invalidate the cache once committed
+    let (repo, _, mut task) = cached_repository_with_task(LONG_TTL).await;
+    repo.get(task.tenant_id, task.id).await.unwrap();
+
+    // Act: Update the task in a unit of work, reading before and after commit
+    task.status = TaskStatus::InProgress;
+    let mut uow = repo.begin().await.unwrap();
+    uow.update(&task).await.unwrap();
+    let before_commit = repo.get(task.tenant_id, task.id).await.unwrap().unwrap();
+    uow.commit().await.unwrap();
+    let after_commit = repo.get(task.tenant_id, task.id).await.unwrap().unwrap();
+
+    // Assert: Verify the uncommitted change was invisible and the committed one is visible
+    assert_eq!(before_commit.status, TaskStatus::Pending);
+    assert_eq!(after_commit.status, TaskStatus::InProgress);
+}
+
+#[tokio::test]
+async fn test_read_racing_an_update_does_not_cache_stale_task() {
+    // Objective: Verify a read that fetched the old version before an update cannot leave
+    // it in the cache after the update returned
+    let (repo, inner, mut task) = cached_repository_with_task(LONG_TTL).await;
+
+    // Arrange: Start a read that pauses after fetching the old version
+    inner.pause_next_read.store(true, Ordering::SeqCst);
+    let reader = tokio::spawn({
+        let repo = repo.clone();
+        async move { repo.get(task.tenant_id, task.id).await }
+    });
+    inner.read_done.notified().await;
+
+    // Act: Update while the read is in flight, then let the read finish
+    task.status = TaskStatus::Completed;
+    repo.update(&task).await.unwrap();
+    inner.resume.notify_one();
+    reader.await.unwrap().unwrap();
+    let stored = repo
+        .get(task.tenant_id, task.id)
+        .await
+        .unwrap()
+        .expect("Task should exist");
+
+    // Assert: Verify the update is visible
+    assert_eq!(stored.status, TaskStatus::Completed);
+}
+
+#[tokio::test]
+async fn test_list_bypasses_cache() {
+    // Objective: Verify listing by user always reflects the inner repository
+    let (repo, inner, mut task) = cached_repository_with_task(LONG_TTL).await;
+    repo.get(task.tenant_id, task.id).await.unwrap();
+
+    // Act: Change the task behind the cache's back and list the user's tasks
+    task.status = TaskStatus::Completed;
+    inner.update(&task).await.unwrap();
+    let tasks = repo
+        .get_by_user(task.tenant_id, task.user_id)
+        .await
+        .unwrap();
+
+    // Assert: Verify the list shows the change
+    assert_eq!(tasks[0].status, TaskStatus::Completed);
+}
+
+#[tokio::test]
+async fn test_cached_task_expires_after_ttl() {
+    // Objective: Verify a write from elsewhere becomes visible once the TTL expires
+    let (repo, inner, mut task) = cached_repository_with_task(Duration::from_millis(50)).await;
+    repo.get(task.tenant_id, task.id).await.unwrap();
+
+    // Act: Change the task behind the cache's back and wait past the TTL
+    task.status = TaskStatus::Completed;
+    inner.update(&task).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let stored = repo
+        .get(task.tenant_id, task.id)
+        .await
+        .unwrap()
+        .expect("Task should exist");
+
+    // Assert: Verify the change is visible
+    assert_eq!(stored.status, TaskStatus::Completed);
+}
+
+#[tokio::test]
+async fn test_cached_task_is_not_served_to_another_tenant() {
+    // Objective: Verify a cache hit is checked against the tenant asking for it
+    // Negative test: Tasks are cached by id alone, so another tenant can ask for a cached id
+    let (repo, inner, task) = cached_repository_with_task(LONG_TTL).await;
+    repo.get(task.tenant_id, task.id).await.unwrap();
+
+    // Act: Read the cached task as another tenant
+    let stored = repo.get(TenantId::new(), task.id).await.unwrap();
+
+    // Assert: Verify nothing is returned and the read went to the inner repository
+    assert!(stored.is_none(), "Another tenant should not see the task");
+    assert_eq!(inner.reads.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,813 @@
+//! `TaskRepository` contract, run against every implementation
+//!
+//! Each scenario takes a fresh repository handle and must pass for Postgres, the in-memory
+//! repository and (with the `sqlite` feature) SQLite. Scenarios use unique users, tenants and ids so
+//! they can share a database, although each Postgres run gets a schema of its own. The in-memory run needs no database, so it doubles as a fast
+//! check of the contract itself.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rust_service_template::{
+    common::TenantId,
+    domain::{
+        errors::DomainError,
+        quota::models::TaskUsage,
+        task::models::{
+            DailyTaskCount, SortDirection, StatsRange, TaskFilter, TaskId, TaskPage, TaskSearch,
+            TaskSort, TaskSortField, TaskStatus, Title,
+        },
+    },
+    infrastructure::in_memory_task::InMemoryTaskRepository,
+};
+
+use super::super::*;
+
+/// A whole-second timestamp, so it survives every backend's storage precision
+fn timestamp(minutes: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::minutes(minutes)
+}
+
+async fn postgres_repository() -> (Arc<dyn TaskRepository>, common::TestDatabase) {
+    let (_, db) = common::app().await;
+    (Arc::new(PostgresTaskRepository::new((*db).clone())), db)
+}
+
+fn in_memory_repository() -> (Arc<dyn TaskRepository>, ()) {
+    (Arc::new(InMemoryTaskRepository::new()), ())
+}
+
+fn new_task(user_id: UserId, prefix: &str) -> Task {
+    Task::new(
+        TenantId::DEFAULT,
+        user_id,
+        generate_unique_title(prefix),
+        None,
+        TaskPriority::Medium,
+    )
+    .unwrap()
+}
+
+pub async fn create_then_get_returns_task(repo: Arc<dyn TaskRepository>) {
+    let task = new_task(UserId::new(), "contract_roundtrip");
+
+    repo.create(task.clone()).await.unwrap();
+    let stored = repo
+        .get(task.tenant_id, task.id)
+        .await
+        .unwrap()
+        .expect("Task should exist");
+
+    assert_eq!(stored.id, task.id);
+    assert_eq!(stored.user_id, task.user_id);
+    assert_eq!(stored.title, task.title);
+    assert_eq!(stored.status, task.status);
+    assert_eq!(stored.priority, task.priority);
+}
+
+pub async fn create_duplicate_id_conflicts(repo: Arc<dyn TaskRepository>) {
+    let task = new_task(UserId::new(), "contract_duplicate");
+    repo.create(task.clone()).await.unwrap();
+
+    let result = repo.create(task).await;
+
+    assert!(
+        matches!(result, Err(DomainError::Conflict { .. })),
+        "Duplicate id should be a Conflict, got {result:?}"
+    );
+}
+
+pub async fn get_missing_returns_none(repo: Arc<dyn TaskRepository>) {
+    let task = new_task(UserId::new(), "contract_missing");
+
+    assert!(repo.get(task.tenant_id, task.id).await.unwrap().is_none());
+}
+
+pub async fn get_by_user_returns_newest_first(repo: Arc<dyn TaskRepository>) {
+    let user_id = UserId::new();
+    let base = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    for offset in [1, 3, 2] {
+        let mut task = new_task(user_id, "contract_order");
+        task.created_at = base + Duration::minutes(offset);
+        repo.create(task).await.unwrap();
+    }
+
+    let tasks = repo.get_by_user(TenantId::DEFAULT, user_id).await.unwrap();
+
+    let created: Vec<_> = tasks.iter().map(|task| task.created_at).collect();
+    assert_eq!(
+        created,
+        [3, 2, 1].map(|offset| base + Duration::minutes(offset)),
+        "Tasks should be ordered by created_at descending"
+    );
+}
+
+pub async fn get_by_user_excludes_other_users(repo: Arc<dyn TaskRepository>) {
+    let user_id = UserId::new();
+    let own = new_task(user_id, "contract_own");
+    repo.create(own.clone()).await.unwrap();
+    repo.create(new_task(UserId::new(), "contract_other"))
+        .await
+        .unwrap();
+
+    let tasks = repo.get_by_user(TenantId::DEFAULT, user_id).await.unwrap();
+
+    assert_eq!(tasks.len(), 1, "Only the user's own task should be listed");
+    assert_eq!(tasks[0].id, own.id);
+}
+
+pub async fn update_overwrites_task(repo: Arc<dyn TaskRepository>) {
+    let mut task = new_task(UserId::new(), "contract_update");
+    repo.create(task.clone()).await.unwrap();
+
+    task.status = TaskStatus::Completed;
+    repo.update(&task).await.unwrap();
+
+    let stored = repo
+        .get(task.tenant_id, task.id)
+        .await
+        .unwrap()
+        .expect("Task should exist");
+    assert_eq!(stored.status, TaskStatus::Completed);
+}
+
+pub async fn update_missing_is_not_found(repo: Arc<dyn TaskRepository>) {
+    let task = new_task(UserId::new(), "contract_update_missing");
+
+    let result = repo.update(&task).await;
+
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+    assert!(repo.get(task.tenant_id, task.id).await.unwrap().is_none());
+}
+
+pub async fn delete_removes_task(repo: Arc<dyn TaskRepository>) {
+    let task = new_task(UserId::new(), "contract_delete");
+    repo.create(task.clone()).await.unwrap();
+
+    repo.delete(task.tenant_id, task.id).await.unwrap();
+
+    assert!(repo.get(task.tenant_id, task.id).await.unwrap().is_none());
+}
+
+pub async fn delete_missing_is_not_found(repo: Arc<dyn TaskRepository>) {
+    let task = new_task(UserId::new(), "contract_delete_missing");
+
+    let result = repo.delete(task.tenant_id, task.id).await;
+
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+}
+
+pub async fn unit_of_work_commit_applies_writes(repo: Arc<dyn TaskRepository>) {
+    let task = new_task(UserId::new(), "contract_uow_commit");
+
+    let mut uow = repo.begin().await.unwrap();
+    uow.create(task.clone()).await.unwrap();
+    assert!(
+        repo.get(task.tenant_id, task.id).await.unwrap().is_none(),
+        "Uncommitted writes should not be visible outside the unit of work"
+    );
+    uow.commit().await.unwrap();
+
+    assert!(repo.get(task.tenant_id, task.id).await.unwrap().is_some());
+}
+
+pub async fn unit_of_work_drop_discards_writes(repo: Arc<dyn TaskRepository>) {
+    let task = new_task(UserId::new(), "contract_uow_drop");
+
+    let mut uow = repo.begin().await.unwrap();
+    uow.create(task.clone()).await.unwrap();
+    drop(uow);
+
+    assert!(repo.get(task.tenant_id, task.id).await.unwrap().is_none());
+}
+
+pub async fn create_returns_the_stored_task(repo: Arc<dyn TaskRepository>) {
+    let task = new_task(UserId::new(), "contract_create_returns");
+
+    let created = repo.create(task.clone()).await.unwrap();
+
+    assert_eq!(created.id, task.id);
+    assert_eq!(created.title, task.title);
+}
+
+pub async fn create_preserves_normalized_title(repo: Arc<dyn TaskRepository>) {
+    let suffix = generate_unique_title("contract_title");
+    let task = Task::new(
+        TenantId::DEFAULT,
+        UserId::new(),
+        format!("  Padded \t title   {suffix}  "),
+        Some("  padded description ".to_string()),
+        TaskPriority::Medium,
+    )
+    .unwrap();
+    repo.create(task.clone()).await.unwrap();
+
+    let stored = repo
+        .get(task.tenant_id, task.id)
+        .await
+        .unwrap()
+        .expect("Task should exist");
+
+    assert_eq!(stored.title.value(), format!("Padded title {suffix}"));
+    assert_eq!(stored.description.as_deref(), Some("padded description"));
+}
+
+pub async fn create_preserves_optional_fields(repo: Arc<dyn TaskRepository>) {
+    let mut with_fields = new_task(UserId::new(), "contract_optional_some");
+    with_fields.description = Some("details".to_string());
+    with_fields.status = TaskStatus::Completed;
+    with_fields.priority = TaskPriority::Critical;
+    with_fields.completed_at = Some(timestamp(5));
+    let without_fields = new_task(UserId::new(), "contract_optional_none");
+    repo.create(with_fields.clone()).await.unwrap();
+    repo.create(without_fields.clone()).await.unwrap();
+
+    let stored = repo
+        .get(with_fields.tenant_id, with_fields.id)
+        .await
+        .unwrap()
+        .expect("Task should exist");
+    assert_eq!(stored.description.as_deref(), Some("details"));
+    assert_eq!(stored.status, TaskStatus::Completed);
+    assert_eq!(stored.priority, TaskPriority::Critical);
+    assert_eq!(stored.completed_at, Some(timestamp(5)));
+
+    let stored = repo
+        .get(without_fields.tenant_id, without_fields.id)
+        .await
+        .unwrap()
+        .expect("Task should exist");
+    assert_eq!(stored.description, None);
+    assert_eq!(stored.completed_at, None);
+}
+
+pub async fn create_preserves_timestamps(repo: Arc<dyn TaskRepository>) {
+    let mut task = new_task(UserId::new(), "contract_timestamps");
+    task.created_at = timestamp(1);
+    task.updated_at = timestamp(2);
+    repo.create(task.clone()).await.unwrap();
+
+    let stored = repo
+        .get(task.tenant_id, task.id)
+        .await
+        .unwrap()
+        .expect("Task should exist");
+
+    assert_eq!(stored.created_at, timestamp(1));
+    assert_eq!(stored.updated_at, timestamp(2));
+}
+
+pub async fn concurrent_creates_are_all_stored(repo: Arc<dyn TaskRepository>) {
+    let user_id = UserId::new();
+    let mut creates = tokio::task::JoinSet::new();
+    for _ in 0..10 {
+        let repo = Arc::clone(&repo);
+        let task = new_task(user_id, "contract_concurrent");
+        creates.spawn(async move { repo.create(task).await.map(|task| task.id) });
+    }
+
+    let mut created: Vec<TaskId> = creates
+        .join_all()
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+
+    let mut listed: Vec<TaskId> = repo
+        .get_by_user(TenantId::DEFAULT, user_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|task| task.id)
+        .collect();
+    created.sort_by_key(ToString::to_string);
+    listed.sort_by_key(ToString::to_string);
+    assert_eq!(listed, created, "Every concurrent create should be stored");
+}
+
+pub async fn get_by_user_unknown_user_is_empty(repo: Arc<dyn TaskRepository>) {
+    assert!(repo
+        .get_by_user(TenantId::DEFAULT, UserId::new())
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+pub async fn get_by_user_excludes_deleted_tasks(repo: Arc<dyn TaskRepository>) {
+    let user_id = UserId::new();
+    let kept = new_task(user_id, "contract_kept");
+    let deleted = new_task(user_id, "contract_deleted");
+    repo.create(kept.clone()).await.unwrap();
+    repo.create(deleted.clone()).await.unwrap();
+
+    repo.delete(deleted.tenant_id, deleted.id).await.unwrap();
+
+    let ids: Vec<_> = repo
+        .get_by_user(TenantId::DEFAULT, user_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|task| task.id)
+        .collect();
+    assert_eq!(ids, [kept.id]);
+}
+
+pub async fn update_overwrites_mutable_fields(repo: Arc<dyn TaskRepository>) {
+    let mut task = new_task(UserId::new(), "contract_update_fields");
+    task.description = Some("before".to_string());
+    repo.create(task.clone()).await.unwrap();
+
+    task.title = Title::new(generate_unique_title("contract_renamed")).unwrap();
+    task.description = None;
+    task.priority = TaskPriority::High;
+    task.status = TaskStatus::Completed;
+    task.completed_at = Some(timestamp(10));
+    repo.update(&task).await.unwrap();
+
+    let stored = repo
+        .get(task.tenant_id, task.id)
+        .await
+        .unwrap()
+        .expect("Task should exist");
+    assert_eq!(stored.title, task.title);
+    assert_eq!(stored.description, None);
+    assert_eq!(stored.priority, TaskPriority::High);
+    assert_eq!(stored.completed_at, Some(timestamp(10)));
+}
+
+pub async fn update_keeps_creation_time_and_owner(repo: Arc<dyn TaskRepository>) {
+    let mut task = new_task(UserId::new(), "contract_update_immutable");
+    task.created_at = timestamp(1);
+    task.updated_at = timestamp(1);
+    repo.create(task.clone()).await.unwrap();
+
+    let mut changed = task.clone();
+    changed.user_id = UserId::new();
+    changed.created_at = timestamp(50);
+    changed.updated_at = timestamp(60);
+    repo.update(&changed).await.unwrap();
+
+    let stored = repo
+        .get(task.tenant_id, task.id)
+        .await
+        .unwrap()
+        .expect("Task should exist");
+    assert_eq!(stored.user_id, task.user_id, "Owner should not change");
+    assert_eq!(
+        stored.created_at,
+        timestamp(1),
+        "created_at should not change"
+    );
+    assert_eq!(
+        stored.updated_at,
+        timestamp(60),
+        "updated_at should be written"
+    );
+    assert!(stored.updated_at >= stored.created_at);
+}
+
+pub async fn delete_twice_is_not_found(repo: Arc<dyn TaskRepository>) {
+    let task = new_task(UserId::new(), "contract_delete_twice");
+    repo.create(task.clone()).await.unwrap();
+    repo.delete(task.tenant_id, task.id).await.unwrap();
+
+    let result = repo.delete(task.tenant_id, task.id).await;
+
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+}
+
+pub async fn unit_of_work_sees_own_writes(repo: Arc<dyn TaskRepository>) {
+    let mut task = new_task(UserId::new(), "contract_uow_own_writes");
+
+    let mut uow = repo.begin().await.unwrap();
+    uow.create(task.clone()).await.unwrap();
+    task.status = TaskStatus::InProgress;
+    uow.update(&task).await.unwrap();
+
+    let seen = uow
+        .get(task.tenant_id, task.id)
+        .await
+        .unwrap()
+        .expect("Task should exist");
+    assert_eq!(seen.status, TaskStatus::InProgress);
+    uow.rollback().await.unwrap();
+}
+
+pub async fn unit_of_work_rollback_discards_writes(repo: Arc<dyn TaskRepository>) {
+    let task = new_task(UserId::new(), "contract_uow_rollback");
+    repo.create(task.clone()).await.unwrap();
+
+    let mut uow = repo.begin().await.unwrap();
+    uow.delete(task.tenant_id, task.id).await.unwrap();
+    uow.rollback().await.unwrap();
+
+    assert!(
+        repo.get(task.tenant_id, task.id).await.unwrap().is_some(),
+        "A rolled back delete should leave the task in place"
+    );
+}
+
+pub async fn daily_counts_group_by_local_day(repo: Arc<dyn TaskRepository>) {
+    let user_id = UserId::new();
+    // 23:00 UTC on 1 January is 2 January in Tokyo; completed at 01:00 UTC on 3 January
+    let mut late = new_task(user_id, "contract_stats");
+    late.created_at = timestamp(23 * 60);
+    late.completed_at = Some(timestamp(49 * 60));
+    repo.create(late).await.unwrap();
+    let mut other = new_task(UserId::new(), "contract_stats");
+    other.created_at = timestamp(60);
+    repo.create(other).await.unwrap();
+    let day = |day: u32| chrono::NaiveDate::from_ymd_opt(2024, 1, day).unwrap();
+
+    let utc = repo
+        .daily_counts(
+            TenantId::DEFAULT,
+            user_id,
+            &StatsRange::new(day(1), day(3), None).unwrap(),
+        )
+        .await
+        .unwrap();
+    let tokyo = repo
+        .daily_counts(
+            TenantId::DEFAULT,
+            user_id,
+            &StatsRange::new(day(1), day(3), Some("Asia/Tokyo")).unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let counts = |days: &[DailyTaskCount]| {
+        days.iter()
+            .map(|day| (day.date, day.created, day.completed))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        counts(&utc),
+        [(day(1), 1, 0), (day(2), 0, 0), (day(3), 0, 1)]
+    );
+    assert_eq!(
+        counts(&tokyo),
+        [(day(1), 0, 0), (day(2), 1, 0), (day(3), 0, 1)]
+    );
+}
+
+pub async fn usage_counts_open_and_recent_tasks(repo: Arc<dyn TaskRepository>) {
+    let user_id = UserId::new();
+    let mut old_open = new_task(user_id, "contract_usage");
+    old_open.created_at = timestamp(0);
+    repo.create(old_open).await.unwrap();
+    let mut recent_open = new_task(user_id, "contract_usage");
+    recent_open.created_at = timestamp(2 * 24 * 60);
+    recent_open.status = TaskStatus::InProgress;
+    repo.create(recent_open).await.unwrap();
+    let mut recent_done = new_task(user_id, "contract_usage");
+    recent_done.created_at = timestamp(2 * 24 * 60);
+    recent_done.status = TaskStatus::Completed;
+    repo.create(recent_done).await.unwrap();
+    repo.create(new_task(UserId::new(), "contract_usage"))
+        .await
+        .unwrap();
+
+    let usage = repo
+        .usage(TenantId::DEFAULT, user_id, timestamp(24 * 60))
+        .await
+        .unwrap();
+    let other_tenant = repo
+        .usage(TenantId::new(), user_id, timestamp(0))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        usage,
+        TaskUsage {
+            open: 2,
+            created: 2
+        }
+    );
+    assert_eq!(other_tenant, TaskUsage::default());
+}
+
+pub async fn unit_of_work_lock_usage_counts_one_at_a_time(repo: Arc<dyn TaskRepository>) {
+    let user_id = UserId::new();
+    let mut creates = tokio::task::JoinSet::new();
+    for _ in 0..5 {
+        let repo = Arc::clone(&repo);
+        let task = new_task(user_id, "contract_quota");
+        creates.spawn(async move {
+            let mut uow = repo.begin().await.unwrap();
+            let usage = uow
+                .lock_usage(TenantId::DEFAULT, user_id, timestamp(0))
+                .await
+                .unwrap();
+            // Give the other units of work a chance to count before this one creates
+            tokio::task::yield_now().await;
+            // A hard limit of one open task
+            if usage.open == 0 {
+                uow.create(task).await.unwrap();
+            }
+            uow.commit().await.unwrap();
+        });
+    }
+    creates.join_all().await;
+
+    let stored = repo.get_by_user(TenantId::DEFAULT, user_id).await.unwrap();
+    assert_eq!(stored.len(), 1, "Each count should see the previous create");
+}
+
+pub async fn search_all_filters_sorts_and_pages(repo: Arc<dyn TaskRepository>) {
+    let tenant = TenantId::new();
+    let owner = UserId::new();
+    let mut stored = Vec::new();
+    for (minutes, user_id, title, priority, status) in [
+        (
+            0,
+            owner,
+            "Quarterly report",
+            TaskPriority::Low,
+            TaskStatus::Pending,
+        ),
+        (
+            60,
+            owner,
+            "REPORT review",
+            TaskPriority::Critical,
+            TaskStatus::Pending,
+        ),
+        (
+            120,
+            UserId::new(),
+            "Report draft",
+            TaskPriority::High,
+            TaskStatus::Pending,
+        ),
+        (
+            180,
+            owner,
+            "Report 100% done",
+            TaskPriority::Medium,
+            TaskStatus::Pending,
+        ),
+        (
+            240,
+            owner,
+            "Groceries",
+            TaskPriority::High,
+            TaskStatus::Pending,
+        ),
+        (
+            300,
+            owner,
+            "Report archive",
+            TaskPriority::High,
+            TaskStatus::Completed,
+        ),
+    ] {
+        let mut task = Task::new(tenant, user_id, title.to_string(), None, priority).unwrap();
+        task.created_at = timestamp(minutes);
+        task.updated_at = task.created_at;
+        task.status = status;
+        stored.push(repo.create(task).await.unwrap());
+    }
+    repo.create(new_task(owner, "contract_search"))
+        .await
+        .unwrap();
+    let search = |filter, field, limit, offset| {
+        let sort = TaskSort {
+            field,
+            direction: SortDirection::Asc,
+        };
+        TaskSearch::new(filter, sort, limit, offset).unwrap()
+    };
+    let titles = |page: &TaskPage| {
+        page.tasks
+            .iter()
+            .map(|task| task.title.value().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let reports = TaskFilter {
+        user_id: Some(owner),
+        status: Some(TaskStatus::Pending),
+        title: Some("report".to_string()),
+        ..TaskFilter::default()
+    };
+    let by_priority = repo
+        .search_all(
+            tenant,
+            &search(reports.clone(), TaskSortField::Priority, 2, 1),
+        )
+        .await
+        .unwrap();
+    let literal_percent = repo
+        .search_all(
+            tenant,
+            &search(
+                TaskFilter {
+                    title: Some("100%".to_string()),
+                    ..TaskFilter::default()
+                },
+                TaskSortField::Title,
+                10,
+                0,
+            ),
+        )
+        .await
+        .unwrap();
+    let created_range = repo
+        .search_all(
+            tenant,
+            &search(
+                TaskFilter {
+                    created_from: Some(timestamp(60)),
+                    created_before: Some(timestamp(180)),
+                    ..TaskFilter::default()
+                },
+                TaskSortField::CreatedAt,
+                10,
+                0,
+            ),
+        )
+        .await
+        .unwrap();
+    let fragment = stored[4].id.to_string()[..8].to_uppercase();
+    let by_id = repo
+        .search_all(
+            tenant,
+            &search(
+                TaskFilter {
+                    id_fragment: Some(fragment),
+                    ..TaskFilter::default()
+                },
+                TaskSortField::CreatedAt,
+                10,
+                0,
+            ),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(by_priority.total, 3, "Total should count every page");
+    assert_eq!(titles(&by_priority), ["Report 100% done", "REPORT review"]);
+    assert_eq!(titles(&literal_percent), ["Report 100% done"]);
+    assert_eq!(titles(&created_range), ["REPORT review", "Report draft"]);
+    assert_eq!(by_id.total, 1);
+    assert_eq!(by_id.tasks[0].id, stored[4].id);
+}
+
+/// A task stored for a fresh tenant, and another fresh tenant
+async fn task_of_another_tenant(repo: &Arc<dyn TaskRepository>, prefix: &str) -> (Task, TenantId) {
+    let mut task = new_task(UserId::new(), prefix);
+    task.tenant_id = TenantId::new();
+    task.created_at = timestamp(60);
+    repo.create(task.clone()).await.unwrap();
+    (task, TenantId::new())
+}
+
+pub async fn other_tenants_tasks_are_invisible(repo: Arc<dyn TaskRepository>) {
+    let (task, other) = task_of_another_tenant(&repo, "contract_tenant_reads").await;
+    let day = chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let range = StatsRange::new(day, day, None).unwrap();
+
+    assert!(repo.get(other, task.id).await.unwrap().is_none());
+    assert!(repo
+        .get_by_user(other, task.user_id)
+        .await
+        .unwrap()
+        .is_empty());
+    let counts = repo
+        .daily_counts(other, task.user_id, &range)
+        .await
+        .unwrap();
+    assert_eq!(
+        counts[0].created, 0,
+        "Another tenant's task should not count"
+    );
+
+    let own = repo
+        .daily_counts(task.tenant_id, task.user_id, &range)
+        .await
+        .unwrap();
+    assert_eq!(own[0].created, 1);
+    assert_eq!(
+        repo.get_by_user(task.tenant_id, task.user_id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+pub async fn other_tenants_tasks_cannot_be_changed(repo: Arc<dyn TaskRepository>) {
+    let (task, other) = task_of_another_tenant(&repo, "contract_tenant_writes").await;
+    let mut hijacked = task.clone();
+    hijacked.tenant_id = other;
+    hijacked.status = TaskStatus::Cancelled;
+
+    let updated = repo.update(&hijacked).await;
+    let deleted = repo.delete(other, task.id).await;
+
+    assert!(matches!(updated, Err(DomainError::NotFound { .. })));
+    assert!(matches!(deleted, Err(DomainError::NotFound { .. })));
+    let stored = repo
+        .get(task.tenant_id, task.id)
+        .await
+        .unwrap()
+        .expect("Task should be left in place");
+    assert_eq!(stored.status, TaskStatus::Pending);
+    assert_eq!(stored.tenant_id, task.tenant_id);
+}
+
+pub async fn create_duplicate_id_in_another_tenant_conflicts(repo: Arc<dyn TaskRepository>) {
+    let (task, other) = task_of_another_tenant(&repo, "contract_tenant_duplicate").await;
+    let mut copy = task.clone();
+    copy.tenant_id = other;
+
+    let result = repo.create(copy).await;
+
+    assert!(
+        matches!(result, Err(DomainError::Conflict { .. })),
+        "Ids are unique across tenants, got {result:?}"
+    );
+    assert!(repo.get(other, task.id).await.unwrap().is_none());
+}
+
+pub async fn unit_of_work_is_scoped_to_its_tenant(repo: Arc<dyn TaskRepository>) {
+    let (task, other) = task_of_another_tenant(&repo, "contract_tenant_uow").await;
+    let mut hijacked = task.clone();
+    hijacked.tenant_id = other;
+
+    let mut uow = repo.begin().await.unwrap();
+    let seen = uow.get(other, task.id).await.unwrap();
+    let updated = uow.update(&hijacked).await;
+    let deleted = uow.delete(other, task.id).await;
+    uow.commit().await.unwrap();
+
+    assert!(seen.is_none());
+    assert!(matches!(updated, Err(DomainError::NotFound { .. })));
+    assert!(matches!(deleted, Err(DomainError::NotFound { .. })));
+    assert!(repo.get(task.tenant_id, task.id).await.unwrap().is_some());
+}
+
+pub async fn health_check_succeeds(repo: Arc<dyn TaskRepository>) {
+    repo.health_check().await.unwrap();
+}
+
+/// Generate a module with one test per scenario
+///
+/// `$repository` must evaluate to `(Arc<dyn TaskRepository>, guard)`; the guard keeps any
+/// backing resource (such as a temporary database file) alive for the test.
+macro_rules! conformance_tests {
+    ($module:ident, $repository:expr) => {
+        $crate::integration::database::conformance::conformance_tests!(
+            @scenarios $module, $repository,
+            create_then_get_returns_task,
+            create_returns_the_stored_task,
+            create_duplicate_id_conflicts,
+            create_preserves_normalized_title,
+            create_preserves_optional_fields,
+            create_preserves_timestamps,
+            concurrent_creates_are_all_stored,
+            get_missing_returns_none,
+            get_by_user_returns_newest_first,
+            get_by_user_excludes_other_users,
+            get_by_user_unknown_user_is_empty,
+            get_by_user_excludes_deleted_tasks,
+            update_overwrites_task,
+            update_overwrites_mutable_fields,
+            update_keeps_creation_time_and_owner,
+            update_missing_is_not_found,
+            delete_removes_task,
+            delete_missing_is_not_found,
+            delete_twice_is_not_found,
+            unit_of_work_commit_applies_writes,
+            unit_of_work_sees_own_writes,
+            unit_of_work_rollback_discards_writes,
+            unit_of_work_drop_discards_writes,
+            daily_counts_group_by_local_day,
+            usage_counts_open_and_recent_tasks,
+            unit_of_work_lock_usage_counts_one_at_a_time,
+            search_all_filters_sorts_and_pages,
+            other_tenants_tasks_are_invisible,
+            other_tenants_tasks_cannot_be_changed,
+            create_duplicate_id_in_another_tenant_conflicts,
+            unit_of_work_is_scoped_to_its_tenant,
+            health_check_succeeds,
+        );
+    };
+    (@scenarios $module:ident, $repository:expr, $($scenario:ident),* $(,)?) => {
+        mod $module {
+            $(
+                #[tokio::test]
+                async fn $scenario() {
+                    let (repository, _guard) = $repository;
+                    $crate::integration::database::conformance::$scenario(repository).await;
+                }
+            )*
+        }
+    };
+}
+
+pub(crate) use conformance_tests;
+
+conformance_tests!(postgres, super::postgres_repository().await);
+conformance_tests!(in_memory, super::in_memory_repository());
@@ -0,0 +1,54 @@
+use super::super::*;
+
+async fn count_tasks(pool: &sqlx::PgPool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM tasks")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_each_test_sees_only_its_own_rows() {
+    // Objective: Verify tests running side by side cannot see each other's data
+    let (_, first) = common::app().await;
+    let (_, second) = common::app().await;
+
+    // Act: Seed a task through the first database only
+    create_test_task(
+        &first,
+        UserId::new(),
+        &generate_unique_title("isolation"),
+        None,
+        TaskPriority::Medium,
+    )
+    .await;
+
+    // Assert: Verify whole-table counts are exact
+    assert_eq!(count_tasks(&first).await, 1);
+    // Negative test: The other test's schema is untouched
+    assert_eq!(count_tasks(&second).await, 0);
+}
+
+#[tokio::test]
+async fn test_schema_is_dropped_with_its_guard() {
+    // Objective: Verify a test leaves no schema behind once it ends
+    let (_, db) = common::app().await;
+    let (_, observer) = common::app().await;
+    let schema: String = sqlx::query_scalar("SELECT current_schema()")
+        .fetch_one(&*db)
+        .await
+        .unwrap();
+    assert!(schema.starts_with(common::TEST_SCHEMA_PREFIX), "{schema}");
+
+    // Act: End the test's use of its database
+    drop(db);
+
+    // Assert: Verify the schema is gone
+    let exists: bool =
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_namespace WHERE nspname = $1)")
+            .bind(&schema)
+            .fetch_one(&*observer)
+            .await
+            .unwrap();
+    assert!(!exists, "Schema {schema} should have been dropped");
+}
@@ -0,0 +1,94 @@
+use rust_service_template::{
+    bootstrap::bootstrap,
+    config::UserIdMode,
+    infrastructure::migrations::{self, MigrationState},
+};
+
+use super::super::*;
+
+#[tokio::test]
+async fn test_status_reports_every_migration_applied_after_bootstrap() {
+    // Objective: Verify `migrate status` sees the migrations bootstrap applied
+    let (_, pool) = common::app().await;
+
+    // Act: Read the migration status
+    let statuses = migrations::status(&pool).await.unwrap();
+
+    // Assert: Verify nothing is pending or drifted
+    assert!(!statuses.is_empty(), "The binary embeds migrations");
+    assert!(
+        statuses
+            .iter()
+            .all(|status| status.state == MigrationState::Applied),
+        "Every migration should be applied: {statuses:?}"
+    );
+    assert!(migrations::is_up_to_date(&statuses));
+}
+
+#[tokio::test]
+async fn test_bootstrap_skips_migrations_when_disabled() {
+    // Objective: Verify the service starts without running migrations when told not to
+    let (state, _db) = common::app_state().await;
+    let mut config = state.env;
+    config.migrate_on_startup = false;
+
+    // Act: Bootstrap against the already migrated database
+    let state = bootstrap(config, None).await;
+
+    // Assert: Verify the state is usable
+    let state = state.expect("Bootstrap should succeed without migrating");
+    state.task_repository.health_check().await.unwrap();
+}
+
+/// Whether the foreign key from tasks to users is in place
+async fn has_task_owner_constraint(pool: &sqlx::PgPool) -> bool {
+    sqlx::query_scalar(
+        "SELECT EXISTS (
+             SELECT 1 FROM pg_constraint WHERE conrelid = 'tasks'::regclass AND conname = $1
+         )",
+    )
+    .bind(migrations::TASK_OWNER_CONSTRAINT)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_user_id_mode_adds_and_drops_the_task_owner_key() {
+    // Objective: Verify the foreign key follows the configured mode, and that switching to
+    // registered ids keeps tasks whose owners were never registered
+    let (_, pool) = common::app().await;
+    assert!(
+        !has_task_owner_constraint(&pool).await,
+        "External user ids should not add the key"
+    );
+    let legacy = create_test_task(
+        &pool,
+        UserId::new(),
+        &generate_unique_title("legacy_owner"),
+        None,
+        TaskPriority::Low,
+    )
+    .await;
+
+    // Act: Switch to registered ids, twice, then back
+    migrations::apply_user_id_mode(&pool, UserIdMode::Registered)
+        .await
+        .expect("Adding the key should not validate existing rows");
+    migrations::apply_user_id_mode(&pool, UserIdMode::Registered)
+        .await
+        .expect("Applying the same mode again should do nothing");
+    let registered = has_task_owner_constraint(&pool).await;
+    migrations::apply_user_id_mode(&pool, UserIdMode::External)
+        .await
+        .unwrap();
+
+    // Assert: Verify the key came and went, and the legacy task survived
+    assert!(registered, "Registered user ids should add the key");
+    assert!(!has_task_owner_constraint(&pool).await);
+    let stored = PostgresTaskRepository::new((*pool).clone())
+        .get(legacy.tenant_id, legacy.id)
+        .await
+        .unwrap();
+    assert!(stored.is_some(), "Legacy task should be kept");
+}
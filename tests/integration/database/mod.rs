@@ -0,0 +1,12 @@
+pub mod cache;
+pub mod conformance;
+pub mod isolation;
+pub mod migrations;
+pub mod observability;
+pub mod repository;
+// <feature:sqlite>
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+// </feature:sqlite>
+pub mod timeouts;
+pub mod transactions;
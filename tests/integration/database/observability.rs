@@ -0,0 +1,155 @@
+use std::time::Duration;
+
+use metrics_util::debugging::{DebugValue, DebuggingRecorder, Snapshotter};
+use rust_service_template::{
+    common::TenantId,
+    config::DatabasePoolConfig,
+    infrastructure::{pool_monitor::sample_pool, task::PostgresTaskRepository},
+};
+
+use super::super::*;
+
+/// Recorded metrics as `(name, query label, value)`
+///
+/// Taking a snapshot drains counters, so each test should snapshot once.
+fn snapshot(snapshotter: &Snapshotter) -> Vec<(String, Option<String>, DebugValue)> {
+    snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .map(|(key, _, _, value)| {
+            let query = key
+                .key()
+                .labels()
+                .find(|label| label.key() == "query")
+                .map(|label| label.value().to_string());
+            (key.key().name().to_string(), query, value)
+        })
+        .collect()
+}
+
+/// Find a metric by name and `query` label (if given)
+fn find_metric<'a>(
+    metrics: &'a [(String, Option<String>, DebugValue)],
+    name: &str,
+    query: Option<&str>,
+) -> Option<&'a DebugValue> {
+    metrics
+        .iter()
+        .find(|(metric, label, _)| {
+            metric == name && query.is_none_or(|query| label.as_deref() == Some(query))
+        })
+        .map(|(_, _, value)| value)
+}
+
+#[tokio::test]
+async fn test_queries_over_threshold_are_counted_as_slow() {
+    // Objective: Verify queries exceeding the slow-query threshold are recorded
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    let _guard = metrics::set_default_local_recorder(&recorder);
+    let (_, pool) = common::app().await;
+
+    // Arrange: A repository where every query counts as slow
+    let repository =
+        PostgresTaskRepository::new((*pool).clone()).with_slow_query_threshold(Duration::ZERO);
+
+    // Act: Run a lookup
+    let result = repository
+        .get(TenantId::DEFAULT, uuid::Uuid::new_v4().into())
+        .await;
+
+    // Assert: Verify the result is unaffected and the query was timed and counted
+    assert!(
+        result.unwrap().is_none(),
+        "Unknown task should not be found"
+    );
+    let metrics = snapshot(&snapshotter);
+    assert!(
+        matches!(
+            find_metric(&metrics, "db_query_duration_seconds", Some("select_task")),
+            Some(DebugValue::Histogram(values)) if !values.is_empty()
+        ),
+        "Query duration should be recorded"
+    );
+    assert_eq!(
+        find_metric(&metrics, "db_slow_queries_total", Some("select_task")),
+        Some(&DebugValue::Counter(1)),
+        "Slow query should be counted"
+    );
+}
+
+#[tokio::test]
+async fn test_fast_queries_are_not_counted_as_slow() {
+    // Objective: Verify queries under the threshold do not trigger slow-query reporting
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    let _guard = metrics::set_default_local_recorder(&recorder);
+    let (_, pool) = common::app().await;
+
+    // Arrange: A repository with a generous threshold
+    let repository = PostgresTaskRepository::new((*pool).clone())
+        .with_slow_query_threshold(Duration::from_secs(60));
+
+    // Act: Run a health check query
+    repository.health_check().await.unwrap();
+
+    // Assert: Verify nothing was counted as slow
+    assert!(
+        find_metric(&snapshot(&snapshotter), "db_slow_queries_total", None).is_none(),
+        "Fast queries should not be counted as slow"
+    );
+}
+
+#[tokio::test]
+async fn test_pool_sample_publishes_pool_gauges() {
+    // Objective: Verify a pool sample reports size and idle count
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    let _guard = metrics::set_default_local_recorder(&recorder);
+    let (_, pool) = common::app().await;
+
+    // Act: Take a single sample
+    let sample = sample_pool(&pool, &DatabasePoolConfig::default());
+
+    // Assert: Verify the sample and gauges are consistent
+    let metrics = snapshot(&snapshotter);
+    assert!(sample.size >= 1, "Pool should have at least one connection");
+    assert!(
+        sample.idle <= sample.size as usize,
+        "Idle connections cannot exceed pool size"
+    );
+    for gauge in ["db_pool_connections", "db_pool_idle_connections"] {
+        assert!(
+            matches!(
+                find_metric(&metrics, gauge, None),
+                Some(DebugValue::Gauge(_))
+            ),
+            "{gauge} should be published"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_pool_sample_reports_an_exhausted_pool_without_waiting() {
+    // Objective: Verify sampling reads counters instead of checking out a connection
+    let (_, pool) = common::app().await;
+    let config = DatabasePoolConfig {
+        max_connections: pool.options().get_max_connections(),
+        ..DatabasePoolConfig::default()
+    };
+
+    // Arrange: Check out every connection the pool may open
+    let mut held = Vec::new();
+    for _ in 0..config.max_connections {
+        held.push(pool.acquire().await.unwrap());
+    }
+
+    // Act: Sample while nothing is left to acquire
+    let sample = sample_pool(&pool, &config);
+
+    // Assert: Verify the sample reports the exhaustion without taking a connection
+    assert_eq!(sample.idle, 0, "No connection should be idle");
+    assert_eq!(sample.size, config.max_connections, "Pool should be full");
+    drop(held);
+}
@@ -0,0 +1,120 @@
+use rust_service_template::{
+    common::TenantId,
+    domain::{
+        errors::DomainError,
+        task::models::{TaskId, TaskStatus},
+    },
+};
+
+use super::super::*;
+
+#[tokio::test]
+async fn test_update_persists_changes_for_existing_task() {
+    // Objective: Verify update overwrites a stored task
+    // Positive test: Updating an existing task should succeed
+    let (_, pool) = common::app().await;
+    let repo = PostgresTaskRepository::new((*pool).clone());
+
+    // Arrange: Create a task and change its status
+    let mut task = create_test_task(
+        &pool,
+        UserId::new(),
+        &generate_unique_title("repo_update"),
+        None,
+        TaskPriority::Low,
+    )
+    .await;
+    task.status = TaskStatus::InProgress;
+
+    // Act: Update the task
+    let result = repo.update(&task).await;
+
+    // Assert: Verify the change was stored
+    assert!(result.is_ok(), "Update should succeed: {result:?}");
+    let stored = repo
+        .get(task.tenant_id, task.id)
+        .await
+        .unwrap()
+        .expect("Task should exist");
+    assert_eq!(
+        stored.status,
+        TaskStatus::InProgress,
+        "Status should change"
+    );
+}
+
+#[tokio::test]
+async fn test_update_returns_not_found_for_missing_task() {
+    // Objective: Verify update reports a task that does not exist
+    // Negative test: Updating an unknown id should not silently succeed
+    let (_, pool) = common::app().await;
+    let repo = PostgresTaskRepository::new((*pool).clone());
+
+    // Arrange: Build a task that was never stored
+    let task = Task::new(
+        TenantId::DEFAULT,
+        UserId::new(),
+        generate_unique_title("repo_update_missing"),
+        None,
+        TaskPriority::Low,
+    )
+    .unwrap();
+
+    // Act: Update the task
+    let result = repo.update(&task).await;
+
+    // Assert: Verify NotFound is returned and nothing was inserted
+    assert!(
+        matches!(result, Err(DomainError::NotFound { .. })),
+        "Update should return NotFound, got {result:?}"
+    );
+    assert!(
+        !task_exists_in_db(&pool, task.id.as_uuid()).await,
+        "Update must not create the task"
+    );
+}
+
+#[tokio::test]
+async fn test_delete_removes_existing_task() {
+    // Objective: Verify delete removes a stored task
+    // Positive test: Deleting an existing task should succeed
+    let (_, pool) = common::app().await;
+    let repo = PostgresTaskRepository::new((*pool).clone());
+
+    // Arrange: Create a task
+    let task = create_test_task(
+        &pool,
+        UserId::new(),
+        &generate_unique_title("repo_delete"),
+        None,
+        TaskPriority::Low,
+    )
+    .await;
+
+    // Act: Delete the task
+    let result = repo.delete(task.tenant_id, task.id).await;
+
+    // Assert: Verify the task is gone
+    assert!(result.is_ok(), "Delete should succeed: {result:?}");
+    assert!(
+        !task_exists_in_db(&pool, task.id.as_uuid()).await,
+        "Task should be removed"
+    );
+}
+
+#[tokio::test]
+async fn test_delete_returns_not_found_for_missing_task() {
+    // Objective: Verify delete reports a task that does not exist
+    // Negative test: Deleting an unknown id should not silently succeed
+    let (_, pool) = common::app().await;
+    let repo = PostgresTaskRepository::new((*pool).clone());
+
+    // Act: Delete a random id
+    let result = repo.delete(TenantId::DEFAULT, TaskId::new()).await;
+
+    // Assert: Verify NotFound is returned
+    assert!(
+        matches!(result, Err(DomainError::NotFound { .. })),
+        "Delete should return NotFound, got {result:?}"
+    );
+}
@@ -0,0 +1,108 @@
+//! Repository conformance for the SQLite backend (`--features sqlite`)
+
+use std::sync::Arc;
+
+use rust_service_template::{
+    config::DatabasePoolConfig,
+    domain::{
+        errors::DomainError,
+        interfaces::webhook_repository::WebhookRepository,
+        task::models::TaskEventType,
+        webhook::models::{Webhook, WebhookDelivery},
+    },
+    infrastructure::{sqlite_task::SqliteTaskRepository, sqlite_webhook::SqliteWebhookRepository},
+};
+use tempfile::TempDir;
+
+use super::{super::*, conformance::conformance_tests};
+
+/// Repository over a fresh database file, removed when the returned directory is dropped
+async fn sqlite_repository() -> (Arc<dyn TaskRepository>, TempDir) {
+    let dir = tempfile::tempdir().unwrap();
+    let url = format!("sqlite://{}", dir.path().join("tasks.db").display());
+    let repository = SqliteTaskRepository::connect(&url, &DatabasePoolConfig::default())
+        .await
+        .unwrap();
+
+    (Arc::new(repository), dir)
+}
+
+#[tokio::test]
+async fn test_sqlite_webhooks_filter_by_event_type_and_deactivate() {
+    // Objective: Verify the SQLite webhook queries that differ from Postgres: the JSON
+    // event type filter, the tenant scope, deactivation and the cascade to deliveries
+    let dir = tempfile::tempdir().unwrap();
+    let url = format!("sqlite://{}", dir.path().join("tasks.db").display());
+    let tasks = SqliteTaskRepository::connect(&url, &DatabasePoolConfig::default())
+        .await
+        .unwrap();
+    let repository = SqliteWebhookRepository::new(tasks.pool().clone());
+
+    // Arrange: A webhook subscribed to deleted tasks only, with one recorded delivery
+    let tenant = TenantId::from(Uuid::new_v4());
+    let webhook = Webhook::new(
+        tenant,
+        "https://hooks.example.com/tasks".to_string(),
+        None,
+        vec![TaskEventType::Deleted],
+    )
+    .unwrap();
+    repository.create(webhook.clone()).await.unwrap();
+    repository
+        .record_delivery(&WebhookDelivery {
+            id: Uuid::new_v4(),
+            webhook_id: webhook.id,
+            event_id: Uuid::new_v4(),
+            event_type: TaskEventType::Deleted,
+            attempt: 1,
+            status_code: Some(500),
+            error: Some("Endpoint answered 500".to_string()),
+            succeeded: false,
+            duration_ms: 3,
+            attempted_at: chrono::Utc::now(),
+        })
+        .await
+        .unwrap();
+
+    // Act: Look up subscribers, then fail twice with a limit of two
+    let deleted = repository
+        .subscribed_to(tenant, TaskEventType::Deleted)
+        .await
+        .unwrap();
+    let created = repository
+        .subscribed_to(tenant, TaskEventType::Created)
+        .await
+        .unwrap();
+    let other_tenant = repository
+        .subscribed_to(TenantId::DEFAULT, TaskEventType::Deleted)
+        .await
+        .unwrap();
+    let first = repository.record_failure(webhook.id, 2).await.unwrap();
+    let second = repository.record_failure(webhook.id, 2).await.unwrap();
+    let deliveries = repository.deliveries(webhook.id, 10).await.unwrap();
+    let deleted_elsewhere = repository.delete(TenantId::DEFAULT, webhook.id).await;
+    repository.delete(tenant, webhook.id).await.unwrap();
+
+    // Assert: Verify the filters, deactivation and cascade
+    assert_eq!(deleted.len(), 1);
+    assert!(created.is_empty());
+    assert!(
+        other_tenant.is_empty(),
+        "Another tenant's events should not match"
+    );
+    assert!(matches!(
+        deleted_elsewhere,
+        Err(DomainError::NotFound { .. })
+    ));
+    assert!(first, "One failure should keep the webhook active");
+    assert!(!second, "Two failures should deactivate it");
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].status_code, Some(500));
+    assert!(repository
+        .deliveries(webhook.id, 10)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+conformance_tests!(conformance, super::sqlite_repository().await);
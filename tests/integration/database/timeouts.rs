@@ -0,0 +1,100 @@
+use std::time::{Duration, Instant};
+
+use rust_service_template::{bootstrap::bootstrap, domain::errors::DomainError};
+
+use super::super::*;
+
+#[tokio::test]
+async fn test_statement_timeout_cancels_slow_query() {
+    // Objective: Verify the pool applies statement_timeout to every connection
+    // Negative test: A query running past the limit should be cancelled by the server
+    let (state, _db) = common::app_state().await;
+    let mut config = state.env;
+    config.pool_config.statement_timeout_ms = 100;
+    // The schema is migrated already; the migration lock is shared by parallel tests
+    config.migrate_on_startup = false;
+    let state = bootstrap(config, None)
+        .await
+        .expect("Bootstrap should succeed");
+    let pool = state.db_pool.clone().expect("Postgres pool");
+
+    // Act: Sleep well past the timeout
+    let started = Instant::now();
+    let error = sqlx::query("SELECT pg_sleep(5)")
+        .execute(&pool)
+        .await
+        .map_err(DomainError::from)
+        .unwrap_err();
+
+    // Assert: Verify the query was cancelled early and reported as a timeout
+    assert!(
+        matches!(error, DomainError::Timeout { .. }),
+        "Expected Timeout, got {error:?}"
+    );
+    assert!(
+        started.elapsed() < Duration::from_secs(2),
+        "Query should be cancelled near the 100ms limit, took {:?}",
+        started.elapsed()
+    );
+}
+
+#[tokio::test]
+async fn test_statement_timeout_is_disabled_by_zero() {
+    // Objective: Verify statement_timeout_ms = 0 leaves statements unbounded
+    let (state, _db) = common::app_state().await;
+    let mut config = state.env;
+    config.pool_config.statement_timeout_ms = 0;
+    // The schema is migrated already; the migration lock is shared by parallel tests
+    config.migrate_on_startup = false;
+    let state = bootstrap(config, None)
+        .await
+        .expect("Bootstrap should succeed");
+    let pool = state.db_pool.clone().expect("Postgres pool");
+
+    // Act: Read the session setting
+    let timeout: String = sqlx::query_scalar("SHOW statement_timeout")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    // Assert: Verify Postgres' default (no limit) is in effect
+    assert_eq!(timeout, "0");
+}
+
+#[tokio::test]
+async fn test_repository_gives_up_on_blocked_query() {
+    // Objective: Verify the client-side limit fires even when the server does not cancel
+    // Negative test: An update waiting on a row lock should fail with Timeout
+    let (_, pool) = common::app().await;
+    let task = create_test_task(
+        &pool,
+        UserId::new(),
+        &generate_unique_title("blocked_update"),
+        None,
+        TaskPriority::Low,
+    )
+    .await;
+
+    // Arrange: Hold the row lock in an open transaction
+    let locking = PostgresTaskRepository::new((*pool).clone());
+    let mut uow = locking.begin().await.unwrap();
+    uow.update(&task).await.unwrap();
+
+    // Act: Update the same row through a repository with a short timeout
+    let repo = PostgresTaskRepository::new((*pool).clone())
+        .with_statement_timeout(Some(Duration::from_millis(100)));
+    let started = Instant::now();
+    let result = repo.update(&task).await;
+    uow.rollback().await.unwrap();
+
+    // Assert: Verify the update gave up with a timeout
+    assert!(
+        matches!(result, Err(DomainError::Timeout { .. })),
+        "Expected Timeout, got {result:?}"
+    );
+    assert!(
+        started.elapsed() < Duration::from_secs(5),
+        "Update should give up shortly after the limit, took {:?}",
+        started.elapsed()
+    );
+}
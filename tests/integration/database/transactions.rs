@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use rust_service_template::{
+    common::TenantId,
+    domain::{
+        errors::DomainError,
+        task::{models::TaskStatus, operations::create_tasks},
+    },
+};
+
+use super::super::*;
+
+fn new_task(prefix: &str) -> Task {
+    Task::new(
+        TenantId::DEFAULT,
+        UserId::new(),
+        generate_unique_title(prefix),
+        None,
+        TaskPriority::Medium,
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_create_tasks_commits_whole_batch() {
+    // Objective: Verify a successful batch stores every task
+    // Positive test: All inserts in the unit of work should be committed
+    let (_, pool) = common::app().await;
+    let repo: Arc<dyn TaskRepository> = Arc::new(PostgresTaskRepository::new((*pool).clone()));
+
+    // Arrange: Build two distinct tasks
+    let tasks = vec![new_task("batch_ok_1"), new_task("batch_ok_2")];
+    let ids: Vec<Uuid> = tasks.iter().map(|task| *task.id.as_uuid()).collect();
+
+    // Act: Create them as one batch
+    let result = create_tasks(tasks, repo).await;
+
+    // Assert: Verify both tasks were stored
+    assert_eq!(result.unwrap().len(), 2, "Both tasks should be returned");
+    for id in &ids {
+        assert!(task_exists_in_db(&pool, id).await, "Task {id} should exist");
+    }
+}
+
+#[tokio::test]
+async fn test_create_tasks_rolls_back_when_second_insert_fails() {
+    // Objective: Verify a failing statement rolls back the earlier ones
+    // Negative test: A duplicate id in the batch must leave the tasks table untouched
+    let (_, pool) = common::app().await;
+    let repo: Arc<dyn TaskRepository> = Arc::new(PostgresTaskRepository::new((*pool).clone()));
+
+    // Arrange: Repeat the first task so the second insert violates the primary key
+    let first = new_task("batch_rollback");
+    let tasks = vec![first.clone(), first.clone()];
+
+    // Act: Create them as one batch
+    let result = create_tasks(tasks, repo).await;
+
+    // Assert: Verify the batch failed and the first insert was rolled back
+    assert!(
+        matches!(result, Err(DomainError::Conflict { .. })),
+        "Duplicate id should fail with Conflict, got {result:?}"
+    );
+    assert!(
+        !task_exists_in_db(&pool, first.id.as_uuid()).await,
+        "First insert should have been rolled back"
+    );
+}
+
+#[tokio::test]
+async fn test_unit_of_work_rollback_discards_writes() {
+    // Objective: Verify an explicit rollback discards updates and deletes
+    // Negative test: Writes inside a rolled back unit of work must not be visible
+    let (_, pool) = common::app().await;
+    let repo = PostgresTaskRepository::new((*pool).clone());
+
+    // Arrange: Store one task to modify and one to delete
+    let updated = create_test_task(
+        &pool,
+        UserId::new(),
+        &generate_unique_title("uow_update"),
+        None,
+        TaskPriority::Low,
+    )
+    .await;
+    let deleted = create_test_task(
+        &pool,
+        UserId::new(),
+        &generate_unique_title("uow_delete"),
+        None,
+        TaskPriority::Low,
+    )
+    .await;
+
+    // Act: Update and delete inside a unit of work, then roll back
+    let mut uow = repo.begin().await.unwrap();
+    let mut changed = updated.clone();
+    changed.status = TaskStatus::Completed;
+    uow.update(&changed).await.unwrap();
+    uow.delete(deleted.tenant_id, deleted.id).await.unwrap();
+    assert_eq!(
+        uow.get(updated.tenant_id, updated.id)
+            .await
+            .unwrap()
+            .map(|task| task.status),
+        Some(TaskStatus::Completed),
+        "Unit of work should see its own writes"
+    );
+    uow.rollback().await.unwrap();
+
+    // Assert: Verify the stored tasks are unchanged
+    let stored = repo
+        .get(updated.tenant_id, updated.id)
+        .await
+        .unwrap()
+        .expect("Task should exist");
+    assert_eq!(
+        stored.status,
+        TaskStatus::Pending,
+        "Update should be discarded"
+    );
+    assert!(
+        task_exists_in_db(&pool, deleted.id.as_uuid()).await,
+        "Delete should be discarded"
+    );
+}
@@ -0,0 +1,251 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_service_template::{
+    common::{TenantId, UserId},
+    domain::{
+        errors::DomainError,
+        interfaces::{
+            event_producer::EventProducer,
+            task_repository::{TaskRepository, TaskUnitOfWork},
+        },
+        quota::models::TaskUsage,
+        task::models::{DailyTaskCount, StatsRange, Task, TaskEvent, TaskId, TaskPage, TaskSearch},
+    },
+    infrastructure::in_memory_task::InMemoryTaskRepository,
+};
+
+/// A method of [`TaskRepository`], for scripting [`FailingTaskRepository`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RepositoryMethod {
+    Create,
+    Get,
+    GetByUser,
+    DailyCounts,
+    Usage,
+    SearchAll,
+    Update,
+    Delete,
+    HealthCheck,
+    Begin,
+}
+
+impl RepositoryMethod {
+    pub const ALL: [Self; 10] = [
+        Self::Create,
+        Self::Get,
+        Self::GetByUser,
+        Self::DailyCounts,
+        Self::Usage,
+        Self::SearchAll,
+        Self::Update,
+        Self::Delete,
+        Self::HealthCheck,
+        Self::Begin,
+    ];
+}
+
+/// What a scripted repository method does when called
+#[derive(Clone)]
+pub enum Behavior {
+    /// Delegate to the wrapped repository
+    Succeed,
+    /// Return the error the function builds; `DomainError` is not `Clone`
+    Fail(Arc<dyn Fn() -> DomainError + Send + Sync>),
+    /// Sleep, then delegate to the wrapped repository
+    Delay(Duration),
+    /// Panic, as a bug in the repository would
+    Panic,
+    /// Behave as each of these in turn, starting over after the last
+    Cycle(Arc<[Behavior]>),
+}
+
+impl Behavior {
+    pub fn fail(error: impl Fn() -> DomainError + Send + Sync + 'static) -> Self {
+        Self::Fail(Arc::new(error))
+    }
+}
+
+impl fmt::Debug for Behavior {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Succeed => f.write_str("Succeed"),
+            Self::Fail(error) => f.debug_tuple("Fail").field(&error()).finish(),
+            Self::Delay(delay) => f.debug_tuple("Delay").field(delay).finish(),
+            Self::Panic => f.write_str("Panic"),
+            Self::Cycle(behaviors) => f.debug_tuple("Cycle").field(behaviors).finish(),
+        }
+    }
+}
+
+/// Repository test double whose methods fail, stall or panic on demand
+///
+/// Methods without a script delegate to the wrapped repository, an empty
+/// [`InMemoryTaskRepository`] unless [`wrapping`](Self::wrapping) says otherwise.
+///
+/// # Example
+/// ```no_run
+/// let repository = FailingTaskRepository::new()
+///     .on(RepositoryMethod::Get, Behavior::Delay(Duration::from_secs(5)));
+/// let (app, _db) = common::app_with_repository(Arc::new(repository)).await;
+/// ```
+#[derive(Debug)]
+pub struct FailingTaskRepository {
+    inner: Arc<dyn TaskRepository>,
+    script: HashMap<RepositoryMethod, Behavior>,
+    /// Calls made so far to each method, for [`Behavior::Cycle`]
+    calls: Mutex<HashMap<RepositoryMethod, usize>>,
+}
+
+impl FailingTaskRepository {
+    pub fn new() -> Self {
+        Self::wrapping(Arc::new(InMemoryTaskRepository::new()))
+    }
+
+    /// Delegate unscripted calls to `inner`, such as the test's Postgres repository
+    pub fn wrapping(inner: Arc<dyn TaskRepository>) -> Self {
+        Self {
+            inner,
+            script: HashMap::new(),
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Make every call to `method` behave as `behavior`
+    #[must_use]
+    pub fn on(mut self, method: RepositoryMethod, behavior: Behavior) -> Self {
+        self.script.insert(method, behavior);
+        self
+    }
+
+    /// Make every method behave as `behavior`
+    #[must_use]
+    pub fn on_every_method(self, behavior: &Behavior) -> Self {
+        RepositoryMethod::ALL
+            .into_iter()
+            .fold(self, |repository, method| {
+                repository.on(method, behavior.clone())
+            })
+    }
+
+    /// Play the script for `method`; `Ok` means the call should go through to `inner`
+    async fn play(&self, method: RepositoryMethod) -> Result<(), DomainError> {
+        let behavior = match self.script.get(&method).unwrap_or(&Behavior::Succeed) {
+            Behavior::Cycle(behaviors) => {
+                let mut calls = self.calls.lock().unwrap();
+                let call = calls.entry(method).or_default();
+                *call += 1;
+                behaviors[(*call - 1) % behaviors.len()].clone()
+            }
+            behavior => behavior.clone(),
+        };
+        match behavior {
+            Behavior::Succeed => Ok(()),
+            Behavior::Fail(error) => Err(error()),
+            Behavior::Delay(delay) => {
+                tokio::time::sleep(delay).await;
+                Ok(())
+            }
+            Behavior::Panic => panic!("Injected panic in {method:?}"),
+            Behavior::Cycle(_) => panic!("Nested cycle scripted for {method:?}"),
+        }
+    }
+}
+
+#[async_trait]
+impl TaskRepository for FailingTaskRepository {
+    async fn create(&self, entity: Task) -> Result<Task, DomainError> {
+        self.play(RepositoryMethod::Create).await?;
+        self.inner.create(entity).await
+    }
+
+    async fn get(&self, tenant: TenantId, id: TaskId) -> Result<Option<Task>, DomainError> {
+        self.play(RepositoryMethod::Get).await?;
+        self.inner.get(tenant, id).await
+    }
+
+    async fn get_by_user(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+    ) -> Result<Vec<Task>, DomainError> {
+        self.play(RepositoryMethod::GetByUser).await?;
+        self.inner.get_by_user(tenant, user_id).await
+    }
+
+    async fn daily_counts(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        range: &StatsRange,
+    ) -> Result<Vec<DailyTaskCount>, DomainError> {
+        self.play(RepositoryMethod::DailyCounts).await?;
+        self.inner.daily_counts(tenant, user_id, range).await
+    }
+
+    async fn usage(
+        &self,
+        tenant: TenantId,
+        user_id: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<TaskUsage, DomainError> {
+        self.play(RepositoryMethod::Usage).await?;
+        self.inner.usage(tenant, user_id, since).await
+    }
+
+    async fn search_all(
+        &self,
+        tenant: TenantId,
+        search: &TaskSearch,
+    ) -> Result<TaskPage, DomainError> {
+        self.play(RepositoryMethod::SearchAll).await?;
+        self.inner.search_all(tenant, search).await
+    }
+
+    async fn update(&self, entity: &Task) -> Result<(), DomainError> {
+        self.play(RepositoryMethod::Update).await?;
+        self.inner.update(entity).await
+    }
+
+    async fn delete(&self, tenant: TenantId, id: TaskId) -> Result<(), DomainError> {
+        self.play(RepositoryMethod::Delete).await?;
+        self.inner.delete(tenant, id).await
+    }
+
+    async fn health_check(&self) -> Result<(), DomainError> {
+        self.play(RepositoryMethod::HealthCheck).await?;
+        self.inner.health_check().await
+    }
+
+    async fn begin(&self) -> Result<Box<dyn TaskUnitOfWork>, DomainError> {
+        self.play(RepositoryMethod::Begin).await?;
+        self.inner.begin().await
+    }
+}
+
+/// Event producer that keeps every event published to it, for asserting on them
+#[derive(Debug, Default)]
+pub struct RecordingEventProducer {
+    events: Mutex<Vec<TaskEvent>>,
+}
+
+impl RecordingEventProducer {
+    /// Events published so far, oldest first
+    pub fn events(&self) -> Vec<TaskEvent> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl EventProducer for RecordingEventProducer {
+    async fn publish_task_event(&self, event: TaskEvent) -> Result<(), DomainError> {
+        self.events.lock().unwrap().push(event);
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use rust_service_template::{
+    api::build_app_router,
+    domain::errors::{DomainError, ExternalSystem},
+};
+
+use super::super::*;
+
+/// Builds the error a scripted repository method returns
+type ErrorFactory = fn() -> DomainError;
+
+/// Every task endpoint, as `(repository method serving it, HTTP method, uri, body)`
+fn task_requests() -> Vec<(RepositoryMethod, &'static str, String, Option<&'static str>)> {
+    vec![
+        (
+            RepositoryMethod::Create,
+            "POST",
+            "/tasks".to_string(),
+            Some(r#"{"title": "Injected failure"}"#),
+        ),
+        (
+            RepositoryMethod::Get,
+            "GET",
+            format!("/tasks/{}", Uuid::new_v4()),
+            None,
+        ),
+        (
+            RepositoryMethod::GetByUser,
+            "GET",
+            format!("/tasks?user_id={}", Uuid::new_v4()),
+            None,
+        ),
+    ]
+}
+
+#[tokio::test]
+async fn test_repository_database_failures_return_500_database_error() {
+    // Objective: Verify database failures of any kind surface as a 500 DatabaseError from
+    // every task endpoint
+    let failures: [(&str, ErrorFactory); 3] = [
+        ("connection lost", || {
+            DomainError::external_error(ExternalSystem::Database, "Database connection lost")
+        }),
+        ("pool exhausted", || {
+            DomainError::from(sqlx::Error::PoolTimedOut)
+        }),
+        ("undecodable row", || {
+            DomainError::from(sqlx::Error::ColumnNotFound("priority".to_string()))
+        }),
+    ];
+
+    for (failure, error) in failures {
+        for (method, http_method, uri, body) in task_requests() {
+            // Arrange: Make only the method behind this endpoint fail
+            let repository = FailingTaskRepository::new().on(method, Behavior::fail(error));
+            let (app, _db) = common::app_with_repository(Arc::new(repository)).await;
+
+            // Act: Call the endpoint
+            let (status, body_bytes) =
+                make_request(&app, http_method, &uri, body.map(create_json_body)).await;
+
+            // Assert: Verify the database error envelope
+            assert_eq!(status, 500, "{http_method} {uri} with {failure}");
+            verify_error_response(&body_bytes, "DatabaseError");
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_stalled_repository_trips_the_request_timeout() {
+    // Objective: Verify a request stuck in the repository is abandoned with 504 once the
+    // request timeout passes, instead of holding the client and a concurrency slot
+    let (mut state, _db) = common::app_state().await;
+    state.env.concurrency_config.request_timeout_ms = 200;
+    state.task_repository = Arc::new(FailingTaskRepository::new().on(
+        RepositoryMethod::Get,
+        Behavior::Delay(Duration::from_secs(30)),
+    ));
+    let app = build_app_router(Arc::new(state)).await;
+
+    // Act: Fetch a task while the repository stalls
+    let started = Instant::now();
+    let (status, body_bytes) =
+        make_request(&app, "GET", &format!("/tasks/{}", Uuid::new_v4()), None).await;
+
+    // Assert: Verify a 504 GatewayTimeout, well before the stall would end
+    assert_eq!(status, 504, "Stalled request should time out");
+    verify_error_response(&body_bytes, "GatewayTimeout");
+    assert!(
+        started.elapsed() < Duration::from_secs(5),
+        "Request should be abandoned at the timeout, took {:?}",
+        started.elapsed()
+    );
+}
+
+#[tokio::test]
+async fn test_slow_repository_within_the_request_timeout_succeeds() {
+    // Negative test: a delay shorter than the request timeout must not fail the request
+    let (mut state, _db) = common::app_state().await;
+    state.env.concurrency_config.request_timeout_ms = 5_000;
+    state.task_repository = Arc::new(FailingTaskRepository::new().on(
+        RepositoryMethod::Create,
+        Behavior::Delay(Duration::from_millis(100)),
+    ));
+    let app = build_app_router(Arc::new(state)).await;
+
+    // Act: Create a task through the slow repository
+    let (status, _) = make_request(
+        &app,
+        "POST",
+        "/tasks",
+        Some(create_json_body(r#"{"title": "Slow but in time"}"#)),
+    )
+    .await;
+
+    // Assert: Verify the task was created
+    assert_eq!(status, 201, "Request within the timeout should succeed");
+}
+
+#[tokio::test]
+async fn test_repository_panic_is_caught_as_500() {
+    // Objective: Verify a panic inside the repository reaches the catch-panic handler and
+    // leaves the service answering
+    let repository = FailingTaskRepository::new().on(RepositoryMethod::Create, Behavior::Panic);
+    let (app, _db) = common::app_with_repository(Arc::new(repository)).await;
+
+    // Act: Create a task, which panics, then list tasks
+    let (status, body_bytes) = make_request(
+        &app,
+        "POST",
+        "/tasks",
+        Some(create_json_body(r#"{"title": "Panics"}"#)),
+    )
+    .await;
+    let (list_status, _) = make_request(
+        &app,
+        "GET",
+        &format!("/tasks?user_id={}", Uuid::new_v4()),
+        None,
+    )
+    .await;
+
+    // Assert: Verify the JSON 500 and that the next request is unaffected
+    assert_eq!(status, 500, "Panic should become a 500");
+    verify_error_response(&body_bytes, "InternalServerError");
+    assert_eq!(
+        list_status, 200,
+        "Service should keep serving after a panic"
+    );
+}
@@ -0,0 +1,166 @@
+use axum::response::IntoResponse;
+use rust_service_template::{
+    api::error::ApiErrorResponse,
+    config::UserIdMode,
+    domain::errors::{DomainError, ExternalSystem},
+    infrastructure::migrations,
+};
+
+use super::super::*;
+
+/// Status code and body `code` returned for a domain error
+async fn response_for(error: DomainError) -> (u16, String) {
+    let response = ApiErrorResponse::from(error).into_response();
+    let status = response.status().as_u16();
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body = parse_json_response(&body_bytes);
+    (status, body["code"].as_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn test_unique_violation_maps_to_conflict() {
+    // Objective: Verify inserting a duplicate primary key becomes a Conflict, not an external error
+    let (_, pool) = common::app().await;
+    let task = create_test_task(
+        &pool,
+        UserId::new(),
+        &generate_unique_title("duplicate"),
+        None,
+        TaskPriority::Low,
+    )
+    .await;
+
+    // Act: Insert the same task again
+    let error = PostgresTaskRepository::new((*pool).clone())
+        .create(task)
+        .await
+        .unwrap_err();
+
+    // Assert: Verify the conflict and its constraint are reported
+    match error {
+        DomainError::Conflict { constraint, .. } => {
+            assert_eq!(constraint.as_deref(), Some("tasks_pkey"));
+        }
+        other => panic!("Expected Conflict, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_foreign_key_violation_maps_to_not_found() {
+    // Objective: Verify a task for an unregistered owner, caught by the database rather than
+    // the handler, becomes a NotFound naming the foreign key
+    let (state, _db) =
+        common::app_state_with(|config| config.user_ids = UserIdMode::Registered).await;
+    let pool = state.db_pool.clone().unwrap();
+    let task = TaskFixture::new(UserId::new())
+        .title(&generate_unique_title("unknown_owner"))
+        .build();
+
+    // Act: Insert the task straight through the repository
+    let error = PostgresTaskRepository::new(pool)
+        .create(task)
+        .await
+        .unwrap_err();
+
+    // Assert: Verify the not found and its constraint are reported
+    match error {
+        DomainError::NotFound { id, .. } => {
+            assert_eq!(id, migrations::TASK_OWNER_CONSTRAINT);
+        }
+        other => panic!("Expected NotFound, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_row_not_found_maps_to_not_found() {
+    // Objective: Verify sqlx's RowNotFound becomes a domain NotFound
+    let (_, pool) = common::app().await;
+
+    // Act: Fetch exactly one row from an empty result
+    let error = sqlx::query("SELECT 1 WHERE false")
+        .fetch_one(&*pool)
+        .await
+        .map_err(DomainError::from)
+        .unwrap_err();
+
+    // Assert: Verify the mapping
+    assert!(
+        matches!(error, DomainError::NotFound { .. }),
+        "Expected NotFound, got {error:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_other_database_errors_stay_external() {
+    // Objective: Verify unrelated database failures are tagged as database errors
+    let (_, pool) = common::app().await;
+
+    // Act: Run invalid SQL
+    let error = sqlx::query("SELECT * FROM table_that_does_not_exist")
+        .execute(&*pool)
+        .await
+        .map_err(DomainError::from)
+        .unwrap_err();
+
+    // Assert: Verify the external system is the database
+    assert!(
+        matches!(
+            error,
+            DomainError::ExternalError {
+                system: ExternalSystem::Database,
+                ..
+            }
+        ),
+        "Expected a database ExternalError, got {error:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_api_error_code_follows_external_system_not_message() {
+    // Objective: Verify the response code depends on the error's system, whatever its wording
+    let database = DomainError::external_error(ExternalSystem::Database, "connection reset");
+    let kafka = DomainError::external_error(ExternalSystem::Kafka, "Database of offsets lost");
+
+    // Act: Convert both into responses
+    let database_response = response_for(database).await;
+    let kafka_response = response_for(kafka).await;
+
+    // Assert: Verify only the database error is reported as a DatabaseError
+    assert_eq!(database_response, (500, "DatabaseError".to_string()));
+    assert_eq!(kafka_response, (500, "InternalServerError".to_string()));
+}
+
+#[tokio::test]
+async fn test_conflict_and_not_found_map_to_409_and_404() {
+    // Objective: Verify the new domain errors get their own HTTP semantics
+
+    // Act: Convert both into responses
+    let conflict = response_for(DomainError::conflict("Task already exists")).await;
+    let not_found = response_for(DomainError::not_found("Task", "123")).await;
+
+    // Assert: Verify the status codes and error codes
+    assert_eq!(conflict, (409, "Conflict".to_string()));
+    assert_eq!(not_found, (404, "NotFound".to_string()));
+}
+
+#[tokio::test]
+async fn test_quota_exceeded_maps_to_403() {
+    // Objective: Verify a refused write over quota is told apart from a missing permission
+
+    // Act: Convert a quota error into a response
+    let exceeded = response_for(DomainError::quota_exceeded("open_tasks", 100)).await;
+
+    // Assert: Verify the forbidden status and its own code
+    assert_eq!(exceeded, (403, "QuotaExceeded".to_string()));
+}
+
+#[tokio::test]
+async fn test_timeout_maps_to_504() {
+    // Objective: Verify a timed-out operation is distinguishable from a generic failure
+
+    // Act: Convert a timeout into a response
+    let timeout = response_for(DomainError::timeout("Database query select_task")).await;
+
+    // Assert: Verify the gateway timeout status and code
+    assert_eq!(timeout, (504, "GatewayTimeout".to_string()));
+}
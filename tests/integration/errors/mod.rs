@@ -0,0 +1,3 @@
+pub mod injection;
+pub mod mapping;
+pub mod reporting;
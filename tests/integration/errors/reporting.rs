@@ -0,0 +1,101 @@
+use std::sync::{Arc, Mutex};
+
+use rust_service_template::{
+    domain::errors::{DomainError, ExternalSystem},
+    infrastructure::error_reporting::{self, ErrorReporter, RequestContext},
+};
+
+use super::super::*;
+
+/// Error report captured as `(error_type, request_id, route)`
+type Report = (String, Option<String>, Option<String>);
+
+static REPORTS: Mutex<Vec<Report>> = Mutex::new(Vec::new());
+
+struct RecordingReporter;
+
+impl ErrorReporter for RecordingReporter {
+    fn report(&self, error_type: &str, _message: &str, context: &RequestContext) {
+        REPORTS.lock().unwrap().push((
+            error_type.to_string(),
+            context.request_id.clone(),
+            context.route.clone(),
+        ));
+    }
+}
+
+/// Repository whose every call fails like a lost database connection
+fn failing_repository() -> FailingTaskRepository {
+    FailingTaskRepository::new().on_every_method(&Behavior::fail(|| {
+        DomainError::external_error(ExternalSystem::Database, "Database connection lost")
+    }))
+}
+
+/// Send a GET with an explicit request id
+async fn get_with_request_id(app: &Router, uri: &str, request_id: &str) -> u16 {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header("x-request-id", request_id)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    response.status().as_u16()
+}
+
+fn reports_for(request_id: &str) -> Vec<Report> {
+    REPORTS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(_, id, _)| id.as_deref() == Some(request_id))
+        .cloned()
+        .collect()
+}
+
+#[tokio::test]
+async fn test_server_errors_are_reported_with_request_context() {
+    // Objective: Verify 5xx domain errors reach the error reporter with request id and route
+    error_reporting::set_error_reporter(RecordingReporter);
+    let (app, _db) = common::app_with_repository(Arc::new(failing_repository())).await;
+    let request_id = Uuid::new_v4().to_string();
+
+    // Act: Request a task while the database is failing
+    let status =
+        get_with_request_id(&app, &format!("/tasks/{}", Uuid::new_v4()), &request_id).await;
+
+    // Assert: Verify a 500 and exactly one report carrying the route template
+    assert_eq!(status, 500, "Database failure should return 500");
+    assert_eq!(
+        reports_for(&request_id),
+        vec![(
+            "ExternalError".to_string(),
+            Some(request_id.clone()),
+            Some("/tasks/{id}".to_string()),
+        )],
+        "Error should be reported once with request context"
+    );
+}
+
+#[tokio::test]
+async fn test_client_errors_are_not_reported() {
+    // Objective: Verify 4xx responses do not produce error reports
+    error_reporting::set_error_reporter(RecordingReporter);
+    let (app, _db) = common::app().await;
+    let request_id = Uuid::new_v4().to_string();
+
+    // Act: Request a task that does not exist
+    let status =
+        get_with_request_id(&app, &format!("/tasks/{}", Uuid::new_v4()), &request_id).await;
+
+    // Assert: Verify a 404 and no report
+    assert_eq!(status, 404);
+    assert!(
+        reports_for(&request_id).is_empty(),
+        "Client errors should not be reported"
+    );
+}
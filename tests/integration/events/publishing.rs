@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use rdkafka::{
+    consumer::{Consumer, StreamConsumer},
+    ClientConfig, Message,
+};
+use rust_service_template::{
+    config::KafkaConfig,
+    domain::{
+        interfaces::event_producer::EventProducer,
+        task::models::events::{TaskEvent, TaskEventData},
+    },
+    infrastructure::kafka_producer::KafkaEventService,
+};
+
+use super::super::*;
+
+/// Time for a fresh topic to be created and its first message delivered
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[tokio::test]
+async fn test_published_task_event_reaches_consumers() {
+    // Objective: Verify task events are delivered through a real broker as published
+    let Some(bootstrap_servers) = common::kafka_bootstrap_servers() else {
+        eprintln!("Skipping: no Kafka broker; set TEST_USE_CONTAINERS=1 to start one");
+        return;
+    };
+    let config: KafkaConfig = serde_json::from_value(serde_json::json!({
+        "bootstrap_servers": bootstrap_servers,
+        "task_topic": format!("task-events-{}", Uuid::new_v4().simple()),
+    }))
+    .unwrap();
+
+    // Arrange: An event for a new task
+    let task = TaskFixture::new(UserId::new()).build();
+    let event = TaskEvent::new_created(
+        TaskEventData {
+            id: task.id,
+            title: task.title.value().to_string(),
+            description: task.description.clone(),
+            status: task.status,
+            priority: task.priority,
+            tenant_id: task.tenant_id,
+            user_id: task.user_id,
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            completed_at: task.completed_at,
+        },
+        Uuid::new_v4().to_string(),
+    );
+
+    // Act: Publish it, then read the topic from the beginning
+    KafkaEventService::new(&config)
+        .unwrap()
+        .publish_task_event(event.clone())
+        .await
+        .expect("Event should be published");
+    let consumer: StreamConsumer = ClientConfig::new()
+        .set("bootstrap.servers", &config.bootstrap_servers)
+        .set("group.id", Uuid::new_v4().to_string())
+        .set("auto.offset.reset", "earliest")
+        .create()
+        .unwrap();
+    consumer.subscribe(&[&config.task_topic]).unwrap();
+    let message = tokio::time::timeout(DELIVERY_TIMEOUT, consumer.recv())
+        .await
+        .expect("Event should be delivered")
+        .unwrap();
+
+    // Assert: Verify the consumer sees the same event, keyed by its task
+    assert_eq!(message.key(), Some(task.id.to_string().as_bytes()));
+    let received: TaskEvent = serde_json::from_slice(message.payload().unwrap()).unwrap();
+    assert_eq!(received.event_id, event.event_id);
+    assert_eq!(received.data.id, task.id);
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use rust_service_template::api::build_app_router;
+
+use super::super::*;
+
+#[tokio::test]
+async fn test_detailed_health_reports_an_open_circuit_breaker() {
+    // Objective: Verify a breaker opened by failing calls makes the service degraded
+    let (state, _db) = common::app_state().await;
+    let breaker = state.circuit_breakers.get("kafka").unwrap();
+    let minimum_calls = state.env.circuit_breaker_config.minimum_calls;
+    let app = build_app_router(Arc::new(state)).await;
+    let token = issue_admin_token(UserId::new());
+
+    // Act: Fail enough calls to open the breaker
+    for _ in 0..minimum_calls {
+        breaker.try_call().unwrap().failed();
+    }
+    let (status, body) =
+        make_authenticated_request(&app, "GET", "/health/detailed", None, &token).await;
+
+    // Assert: The breaker is open and the database is up
+    assert_eq!(status, 200);
+    let body = parse_json_response(&body);
+    assert_eq!(body["status"], "degraded");
+    assert_eq!(body["database"]["status"], "up");
+    assert_eq!(body["circuit_breakers"]["kafka"], "open");
+}
+
+#[tokio::test]
+async fn test_detailed_health_requires_the_admin_role() {
+    // Negative test: Dependency details are for operators only
+    let (app, _db) = common::app().await;
+    let token = issue_test_token(UserId::new(), chrono::Duration::hours(1));
+
+    let (status, _) =
+        make_authenticated_request(&app, "GET", "/health/detailed", None, &token).await;
+
+    assert_eq!(status, 403);
+}
@@ -0,0 +1,137 @@
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+use rust_service_template::{
+    api::build_app_router,
+    domain::errors::{DomainError, ExternalSystem},
+};
+use tracing_subscriber::fmt::MakeWriter;
+
+use super::super::{
+    doubles::{Behavior, FailingTaskRepository, RepositoryMethod},
+    *,
+};
+
+/// Log output written while a test runs, for asserting on warnings
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+impl CapturedLogs {
+    fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+    }
+}
+
+impl io::Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for CapturedLogs {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+/// App whose readiness probe alternates between failing and succeeding
+async fn flapping_app() -> (axum::Router, common::TestDatabase) {
+    let (mut state, db) = common::app_state_with(|config| {
+        config.health_history_config.capacity = 10;
+        config.health_history_config.min_probes = 4;
+        config.health_history_config.min_success_rate = 0.75;
+    })
+    .await;
+    let behaviors: Arc<[Behavior]> = Arc::new([
+        Behavior::fail(|| {
+            DomainError::external_error(ExternalSystem::Database, "connection refused")
+        }),
+        Behavior::Succeed,
+    ]);
+    state.task_repository = Arc::new(
+        FailingTaskRepository::new().on(RepositoryMethod::HealthCheck, Behavior::Cycle(behaviors)),
+    );
+
+    (build_app_router(Arc::new(state)).await, db)
+}
+
+#[tokio::test]
+async fn test_flapping_readiness_logs_a_warning() {
+    // Objective: Verify half the probes failing is reported once enough are recorded
+    let logs = CapturedLogs::default();
+    let _subscriber = tracing::subscriber::set_default(
+        tracing_subscriber::fmt()
+            .with_writer(logs.clone())
+            .with_max_level(tracing::Level::WARN)
+            .with_ansi(false)
+            .finish(),
+    );
+    let (app, _db) = flapping_app().await;
+
+    // Act: Probe below and then at the number of probes judged
+    for _ in 0..3 {
+        make_request(&app, "GET", "/ready", None).await;
+    }
+    assert!(
+        !logs.contents().contains("Readiness is flapping"),
+        "Too few probes to judge"
+    );
+    let (status, _) = make_request(&app, "GET", "/ready", None).await;
+
+    // Assert
+    assert_eq!(status, 200);
+    assert_eq!(
+        logs.contents().matches("Readiness is flapping").count(),
+        1,
+        "{}",
+        logs.contents()
+    );
+}
+
+#[tokio::test]
+async fn test_health_history_lists_recent_probes_newest_first() {
+    // Objective: Verify the history keeps each probe's outcome, bounded by its capacity
+    let (app, _db) = flapping_app().await;
+    let token = issue_admin_token(UserId::new());
+
+    // Act: More probes than the history holds
+    for _ in 0..12 {
+        make_request(&app, "GET", "/ready", None).await;
+    }
+    let (status, body) =
+        make_authenticated_request(&app, "GET", "/health/history", None, &token).await;
+
+    // Assert: The last of the 12 probes succeeded, and only 10 are kept
+    assert_eq!(status, 200);
+    let body = parse_json_response(&body);
+    let probes = body["probes"].as_array().unwrap();
+    assert_eq!(probes.len(), 10);
+    assert!(probes[0].get("error").is_none());
+    assert!(probes[1]["error"]
+        .as_str()
+        .unwrap()
+        .contains("connection refused"));
+    assert_eq!(body["success_rate"], 0.5);
+    assert_eq!(body["flapping"], true);
+}
+
+#[tokio::test]
+async fn test_health_history_requires_the_admin_role() {
+    // Negative test: Probe details are for operators only
+    let (app, _db) = common::app().await;
+    let token = issue_test_token(UserId::new(), chrono::Duration::hours(1));
+
+    let (status, _) =
+        make_authenticated_request(&app, "GET", "/health/history", None, &token).await;
+
+    assert_eq!(status, 403);
+}
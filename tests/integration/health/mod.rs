@@ -1 +1,4 @@
+pub mod detailed;
+pub mod history;
 pub mod readiness;
+pub mod version;
@@ -0,0 +1,41 @@
+use super::super::*;
+
+#[tokio::test]
+async fn test_version_reports_the_build() {
+    // Objective: Verify `/version` names the package and the commit it was built from
+    let (app, _db) = common::app().await;
+
+    let (status, body) = make_request(&app, "GET", "/version", None).await;
+
+    assert_eq!(status, 200);
+    let body = parse_json_response(&body);
+    assert_eq!(body["name"], env!("CARGO_PKG_NAME"));
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    for field in ["git_sha", "git_branch", "build_timestamp", "rustc_version"] {
+        assert!(
+            !body[field].as_str().unwrap().is_empty(),
+            "{field} should be set: {body}"
+        );
+    }
+    assert!(body["rustc_version"]
+        .as_str()
+        .unwrap()
+        .starts_with("rustc "));
+}
+
+#[tokio::test]
+async fn test_detailed_health_includes_the_build() {
+    // Objective: Verify operators see the same build information in detailed health
+    let (app, _db) = common::app().await;
+    let token = issue_admin_token(UserId::new());
+
+    let (_, version) = make_request(&app, "GET", "/version", None).await;
+    let (status, health) =
+        make_authenticated_request(&app, "GET", "/health/detailed", None, &token).await;
+
+    assert_eq!(status, 200);
+    assert_eq!(
+        parse_json_response(&health)["build"],
+        parse_json_response(&version)
+    );
+}
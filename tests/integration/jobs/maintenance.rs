@@ -0,0 +1,145 @@
+use chrono::{Duration, Utc};
+use rust_service_template::{
+    bootstrap::scheduler,
+    config::SchedulerConfig,
+    domain::{
+        interfaces::{job_repository::JobRepository, webhook_repository::WebhookRepository},
+        job::models::NewJob,
+        task::models::TaskEventType,
+        webhook::models::{Webhook, WebhookDelivery},
+    },
+    infrastructure::{jobs::PostgresJobQueue, webhook::PostgresWebhookRepository},
+};
+use sqlx::PgPool;
+
+use super::super::*;
+
+/// Enqueue a job and give it `status`, last updated `age` ago
+async fn job_with_status(db: &PgPool, status: &str, age: Duration) -> Uuid {
+    let job = NewJob::new("send_reminder", &()).unwrap();
+    let id = PostgresJobQueue::new(db.clone())
+        .enqueue(job)
+        .await
+        .unwrap()
+        .into_inner();
+    sqlx::query("UPDATE jobs SET status = $2, updated_at = $3 WHERE id = $1")
+        .bind(id)
+        .bind(status)
+        .bind(Utc::now() - age)
+        .execute(db)
+        .await
+        .unwrap();
+    id
+}
+
+fn delivery(webhook: &Webhook, age: Duration) -> WebhookDelivery {
+    WebhookDelivery {
+        id: Uuid::new_v4(),
+        webhook_id: webhook.id,
+        event_id: Uuid::new_v4(),
+        event_type: TaskEventType::Created,
+        attempt: 1,
+        status_code: Some(200),
+        error: None,
+        succeeded: true,
+        duration_ms: 12,
+        attempted_at: Utc::now() - age,
+    }
+}
+
+#[tokio::test]
+async fn test_purge_dead_jobs_keeps_recent_and_live_jobs() {
+    // Objective: Verify only dead jobs past the retention period are deleted
+    let (_state, db) = common::app_state().await;
+    let queue = PostgresJobQueue::new((*db).clone());
+    let old_dead = job_with_status(&db, "dead", Duration::days(40)).await;
+    let recent_dead = job_with_status(&db, "dead", Duration::days(1)).await;
+    let old_pending = job_with_status(&db, "pending", Duration::days(40)).await;
+
+    // Act: Purge dead jobs older than 30 days
+    let purged = queue
+        .purge_dead(Utc::now() - Duration::days(30))
+        .await
+        .unwrap();
+
+    // Assert: Verify only the old dead job is gone
+    assert!(purged >= 1);
+    assert_eq!(queue.get(old_dead.into()).await.unwrap(), None);
+    assert!(queue.get(recent_dead.into()).await.unwrap().is_some());
+    assert!(queue.get(old_pending.into()).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_purge_webhook_deliveries_keeps_recent_attempts() {
+    // Objective: Verify delivery attempts past the retention period are deleted
+    let (_state, db) = common::app_state().await;
+    let webhooks = PostgresWebhookRepository::new((*db).clone());
+    let webhook = webhooks
+        .create(
+            Webhook::new(
+                TenantId::DEFAULT,
+                "https://example.com/hooks".to_string(),
+                None,
+                vec![],
+            )
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    webhooks
+        .record_delivery(&delivery(&webhook, Duration::days(20)))
+        .await
+        .unwrap();
+    let recent = delivery(&webhook, Duration::hours(1));
+    webhooks.record_delivery(&recent).await.unwrap();
+
+    // Act: Purge deliveries older than 14 days
+    webhooks
+        .purge_deliveries(Utc::now() - Duration::days(14))
+        .await
+        .unwrap();
+
+    // Assert: Verify only the recent attempt is left
+    let left = webhooks.deliveries(webhook.id, 10).await.unwrap();
+    assert_eq!(
+        left.iter().map(|delivery| delivery.id).collect::<Vec<_>>(),
+        vec![recent.id]
+    );
+}
+
+#[tokio::test]
+async fn test_scheduler_registers_enabled_maintenance_jobs() {
+    // Objective: Verify each built-in job is registered only when its flag is on
+    let (state, db) = common::app_state().await;
+    let config = SchedulerConfig {
+        purge_webhook_deliveries: true,
+        ..SchedulerConfig::default()
+    };
+
+    // Act: Build the scheduler with and without a database
+    let with_database = scheduler(Some(&db), state.object_store.clone(), &config).unwrap();
+    let without_database = scheduler(None, state.object_store, &config).unwrap();
+
+    // Assert: Verify the registered jobs
+    assert_eq!(with_database.names(), vec!["purge_webhook_deliveries"]);
+    assert!(without_database.names().is_empty());
+}
+
+#[tokio::test]
+async fn test_scheduler_fails_on_an_invalid_cron_expression() {
+    // Negative test: A schedule that does not parse stops startup
+    let (state, db) = common::app_state().await;
+    let config = SchedulerConfig {
+        purge_dead_jobs: true,
+        purge_dead_jobs_cron: "0 3 * * *".to_string(),
+        ..SchedulerConfig::default()
+    };
+
+    // Act: Build the scheduler
+    let error = scheduler(Some(&db), state.object_store, &config).unwrap_err();
+
+    // Assert: Verify the error names the job and the expression
+    let message = format!("{error:#}");
+    assert!(message.contains("purge_dead_jobs"), "{message}");
+    assert!(message.contains("`0 3 * * *`"), "{message}");
+}
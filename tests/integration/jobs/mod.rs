@@ -0,0 +1,75 @@
+use std::sync::{Mutex, PoisonError};
+
+use async_trait::async_trait;
+use rust_service_template::{
+    config::JobsConfig,
+    domain::{
+        errors::{DomainError, ExternalSystem},
+        job::models::{Job, NewJob},
+    },
+    infrastructure::jobs::JobHandler,
+};
+use uuid::Uuid;
+
+pub mod maintenance;
+pub mod queue;
+
+pub const KIND: &str = "send_reminder";
+
+/// Runner settings with no backoff, so retries are due at once
+pub fn fast_config(max_attempts: u32) -> JobsConfig {
+    JobsConfig {
+        enabled: true,
+        poll_interval_ms: 10,
+        batch_size: 5,
+        max_attempts,
+        retry_base_delay_ms: 0,
+        retry_max_delay_ms: 0,
+        lock_timeout_secs: 60,
+    }
+}
+
+/// Records every job it runs, failing each one when `error` is set
+#[derive(Default)]
+pub struct RecordingHandler {
+    error: Option<&'static str>,
+    ran: Mutex<Vec<Job>>,
+}
+
+impl RecordingHandler {
+    pub fn failing(error: &'static str) -> Self {
+        Self {
+            error: Some(error),
+            ..Self::default()
+        }
+    }
+
+    pub fn ran(&self) -> Vec<Job> {
+        self.ran
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+#[async_trait]
+impl JobHandler for RecordingHandler {
+    fn kind(&self) -> &'static str {
+        KIND
+    }
+
+    async fn handle(&self, job: &Job) -> Result<(), DomainError> {
+        self.ran
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(job.clone());
+        match self.error {
+            Some(error) => Err(DomainError::external_error(ExternalSystem::Http, error)),
+            None => Ok(()),
+        }
+    }
+}
+
+pub fn reminder(task_id: Uuid) -> NewJob {
+    NewJob::new(KIND, &serde_json::json!({ "task_id": task_id })).unwrap()
+}
@@ -0,0 +1,154 @@
+use std::{sync::Arc, time::Duration};
+
+use rust_service_template::{
+    domain::{interfaces::job_repository::JobRepository, job::models::JobStatus},
+    infrastructure::jobs::{JobRunner, PostgresJobQueue},
+};
+use tokio_util::sync::CancellationToken;
+
+use super::{super::*, fast_config, reminder, RecordingHandler};
+
+#[tokio::test]
+async fn test_job_enqueued_with_a_task_is_run_and_removed() {
+    // Objective: Verify a job enqueued in the unit of work that creates a task is run once
+    // the unit of work commits, and deleted after it succeeds
+    let (state, db) = common::app_state().await;
+    let handler = Arc::new(RecordingHandler::default());
+    let runner = JobRunner::new((*db).clone(), &fast_config(3)).with_handler(handler.clone());
+    let task = TaskFixture::new(UserId::new()).build();
+
+    // Arrange: Create the task and its job together
+    let mut uow = state.task_repository.begin().await.unwrap();
+    let task = uow.create(task).await.unwrap();
+    let job_id = uow.enqueue(reminder(task.id.into_inner())).await.unwrap();
+    uow.commit().await.unwrap();
+
+    // Act: Run one batch
+    let ran = runner.run_once().await.unwrap();
+
+    // Assert: Verify the handler got the job's payload and the job is gone
+    assert_eq!(ran, 1);
+    let jobs = handler.ran();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].id, job_id);
+    assert_eq!(jobs[0].attempts, 1);
+    assert_eq!(jobs[0].locked_by.as_deref(), Some(runner.worker_id()));
+    assert_eq!(jobs[0].payload["task_id"], task.id.into_inner().to_string());
+    let queue = PostgresJobQueue::new((*db).clone());
+    assert_eq!(queue.get(job_id).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_job_of_a_rolled_back_unit_of_work_is_never_run() {
+    // Negative test: Nothing enqueued in a unit of work outlives its rollback
+    let (state, db) = common::app_state().await;
+    let handler = Arc::new(RecordingHandler::default());
+    let runner = JobRunner::new((*db).clone(), &fast_config(3)).with_handler(handler.clone());
+
+    // Arrange: Enqueue a job, then roll back
+    let mut uow = state.task_repository.begin().await.unwrap();
+    uow.enqueue(reminder(Uuid::new_v4())).await.unwrap();
+    uow.rollback().await.unwrap();
+
+    // Act: Run one batch
+    let ran = runner.run_once().await.unwrap();
+
+    // Assert: Verify there was nothing to run
+    assert_eq!(ran, 0);
+    assert!(handler.ran().is_empty());
+}
+
+#[tokio::test]
+async fn test_failing_job_is_retried_then_dead() {
+    // Objective: Verify a failing job runs max_attempts times, then is kept as dead with
+    // its last error and no longer claimed
+    let (_state, db) = common::app_state().await;
+    let handler = Arc::new(RecordingHandler::failing("reminder service unavailable"));
+    let runner = JobRunner::new((*db).clone(), &fast_config(2)).with_handler(handler.clone());
+    let queue = PostgresJobQueue::new((*db).clone());
+    let job_id = queue.enqueue(reminder(Uuid::new_v4())).await.unwrap();
+
+    // Act: Run three batches
+    let first = runner.run_once().await.unwrap();
+    let retried = queue.get(job_id).await.unwrap().unwrap();
+    let second = runner.run_once().await.unwrap();
+    let third = runner.run_once().await.unwrap();
+
+    // Assert: Verify the retry, then the dead job
+    assert_eq!((first, second, third), (1, 1, 0));
+    assert_eq!(retried.status, JobStatus::Pending);
+    assert_eq!(retried.attempts, 1);
+    assert_eq!(retried.locked_by, None);
+    let dead = queue.get(job_id).await.unwrap().unwrap();
+    assert_eq!(dead.status, JobStatus::Dead);
+    assert_eq!(dead.attempts, 2);
+    assert!(
+        dead.last_error
+            .as_deref()
+            .is_some_and(|error| error.contains("reminder service unavailable")),
+        "Last error should be kept: {dead:?}"
+    );
+    assert_eq!(handler.ran().len(), 2);
+}
+
+#[tokio::test]
+async fn test_concurrent_runners_never_run_a_job_twice() {
+    // Objective: Verify runners sharing the table split the jobs between them
+    let (_state, db) = common::app_state().await;
+    let queue = PostgresJobQueue::new((*db).clone());
+    for _ in 0..20 {
+        queue.enqueue(reminder(Uuid::new_v4())).await.unwrap();
+    }
+    let handlers: Vec<Arc<RecordingHandler>> = (0..3).map(|_| Arc::default()).collect();
+
+    // Act: Let three runners drain the queue at the same time
+    let mut draining = tokio::task::JoinSet::new();
+    for handler in &handlers {
+        let runner = JobRunner::new((*db).clone(), &fast_config(3)).with_handler(handler.clone());
+        draining.spawn(async move { while runner.run_once().await.unwrap() > 0 {} });
+    }
+    while let Some(drained) = draining.join_next().await {
+        drained.unwrap();
+    }
+
+    // Assert: Verify every job ran exactly once
+    let mut ran: Vec<Uuid> = handlers
+        .iter()
+        .flat_map(|handler| handler.ran())
+        .map(|job| job.id.into_inner())
+        .collect();
+    ran.sort_unstable();
+    ran.dedup();
+    let total: usize = handlers.iter().map(|handler| handler.ran().len()).sum();
+    assert_eq!(total, 20);
+    assert_eq!(ran.len(), 20, "A job ran more than once");
+}
+
+#[tokio::test]
+async fn test_runner_stops_when_shutdown_is_cancelled() {
+    // Objective: Verify a spawned runner picks up new jobs and returns once cancelled
+    let (_state, db) = common::app_state().await;
+    let handler = Arc::new(RecordingHandler::default());
+    let shutdown = CancellationToken::new();
+    let running = JobRunner::new((*db).clone(), &fast_config(3))
+        .with_handler(handler.clone())
+        .spawn(shutdown.clone());
+
+    // Act: Enqueue a job while the runner polls, then shut it down
+    PostgresJobQueue::new((*db).clone())
+        .enqueue(reminder(Uuid::new_v4()))
+        .await
+        .unwrap();
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+    while handler.ran().is_empty() {
+        assert!(tokio::time::Instant::now() < deadline, "Job was never run");
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    shutdown.cancel();
+
+    // Assert: Verify the runner stops promptly
+    tokio::time::timeout(Duration::from_secs(5), running)
+        .await
+        .expect("Runner should stop once cancelled")
+        .unwrap();
+}
@@ -0,0 +1,116 @@
+use std::{sync::Arc, time::Duration};
+
+use axum::http::header::RETRY_AFTER;
+use rust_service_template::api::build_app_router;
+
+use super::super::*;
+
+/// Repository holding every listing call open for `delay`, on top of the test's own
+fn slow_listing(inner: Arc<dyn TaskRepository>, delay: Duration) -> Arc<dyn TaskRepository> {
+    Arc::new(
+        FailingTaskRepository::wrapping(inner)
+            .on(RepositoryMethod::GetByUser, Behavior::Delay(delay)),
+    )
+}
+
+/// Send a bodyless GET and return the status and `Retry-After` header
+async fn get(app: &Router, uri: &str) -> (u16, Option<String>) {
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let retry_after = response
+        .headers()
+        .get(RETRY_AFTER)
+        .map(|value| value.to_str().unwrap().to_string());
+    (response.status().as_u16(), retry_after)
+}
+
+#[tokio::test]
+async fn test_requests_over_concurrency_limit_are_shed_with_503() {
+    // Objective: Verify requests beyond the concurrency limit are rejected immediately
+    // while health and readiness probes keep working
+    let (mut state, _db) = common::app_state().await;
+    state.env.concurrency_config.max_concurrent_requests = 2;
+    state.env.concurrency_config.retry_after = 3;
+    state.task_repository = slow_listing(state.task_repository.clone(), Duration::from_millis(500));
+    let app = build_app_router(Arc::new(state)).await;
+    let uri = format!("/tasks?user_id={}", Uuid::new_v4());
+
+    // Act: Fire more slow requests than the limit allows, plus probes, all at once
+    let (r1, r2, r3, r4, r5, r6, health, ready) = tokio::join!(
+        get(&app, &uri),
+        get(&app, &uri),
+        get(&app, &uri),
+        get(&app, &uri),
+        get(&app, &uri),
+        get(&app, &uri),
+        get(&app, "/health"),
+        get(&app, "/ready"),
+    );
+    let results = [r1, r2, r3, r4, r5, r6];
+
+    // Assert: Exactly the limit succeeded, the rest were shed with Retry-After
+    let succeeded = results.iter().filter(|(status, _)| *status == 200).count();
+    let shed: Vec<_> = results
+        .iter()
+        .filter(|(status, _)| *status == 503)
+        .collect();
+    assert_eq!(succeeded, 2, "Only the permitted requests should succeed");
+    assert_eq!(shed.len(), 4, "Requests over the limit should be shed");
+    assert!(
+        shed.iter()
+            .all(|(_, retry_after)| retry_after.as_deref() == Some("3")),
+        "Shed requests should carry the configured Retry-After"
+    );
+    assert_eq!(health.0, 200, "Health probe should bypass the limiter");
+    assert_eq!(ready.0, 200, "Readiness probe should bypass the limiter");
+}
+
+#[tokio::test]
+async fn test_shed_response_uses_json_envelope() {
+    // Objective: Verify a shed request returns the ServiceUnavailable error code
+    let (mut state, _db) = common::app_state().await;
+    state.env.concurrency_config.max_concurrent_requests = 1;
+    state.task_repository = slow_listing(state.task_repository.clone(), Duration::from_millis(300));
+    let app = build_app_router(Arc::new(state)).await;
+    let uri = format!("/tasks?user_id={}", Uuid::new_v4());
+
+    // Act: Occupy the only permit, then send a second request
+    let ((first_status, _), (second_status, second_body)) =
+        tokio::join!(make_request(&app, "GET", &uri, None), async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            make_request(&app, "GET", &uri, None).await
+        });
+
+    // Assert: Verify 503 ServiceUnavailable for the second request
+    assert_eq!(first_status, 200, "First request should hold the permit");
+    assert_eq!(second_status, 503, "Second request should be shed");
+    verify_error_response(&second_body, "ServiceUnavailable");
+}
+
+#[tokio::test]
+async fn test_concurrency_limit_is_shared_across_routes() {
+    // Objective: Verify the limit covers the whole API, not each route separately
+    let (mut state, _db) = common::app_state().await;
+    state.env.concurrency_config.max_concurrent_requests = 1;
+    state.task_repository = slow_listing(state.task_repository.clone(), Duration::from_millis(300));
+    let app = build_app_router(Arc::new(state)).await;
+    let list_uri = format!("/tasks?user_id={}", Uuid::new_v4());
+    let get_uri = format!("/tasks/{}", Uuid::new_v4());
+
+    // Act: Occupy the only permit with a listing, then fetch a task by id
+    let ((list_status, _), (get_status, retry_after)) = tokio::join!(get(&app, &list_uri), async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        get(&app, &get_uri).await
+    });
+
+    // Assert: Verify the other route was shed while the listing held the permit
+    assert_eq!(list_status, 200, "Listing should hold the permit");
+    assert_eq!(get_status, 503, "A different route should share the limit");
+    assert!(
+        retry_after.is_some(),
+        "Shed request should carry Retry-After"
+    );
+}
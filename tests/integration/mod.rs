@@ -1,5 +1,23 @@
+pub mod admin;
+pub mod attachments;
+// <feature:auth>
+pub mod auth;
+// </feature:auth>
+pub mod database;
+pub mod doubles;
+pub mod errors;
+// <feature:kafka>
+pub mod events;
+// </feature:kafka>
+pub mod fixtures;
 pub mod health;
+pub mod jobs;
+pub mod load;
+pub mod routing;
+pub mod server;
 pub mod tasks;
+pub mod users;
+pub mod webhooks;
 
 use axum::{body::Body, http::Request};
 use http_body_util::BodyExt;
@@ -9,8 +27,13 @@ use uuid::Uuid;
 
 use crate::common;
 use axum::Router;
+pub use doubles::{Behavior, FailingTaskRepository, RepositoryMethod};
+pub use fixtures::TaskFixture;
+// <feature:auth>
+use rust_service_template::api::auth::{JwtClaims, ADMIN_ROLE};
+// </feature:auth>
 use rust_service_template::{
-    common::UserId,
+    common::{TenantId, UserId},
     domain::{
         interfaces::task_repository::TaskRepository,
         task::models::{Task, TaskPriority},
@@ -38,8 +61,14 @@ pub async fn make_request(
     uri: &str,
     body: Option<Body>,
 ) -> (u16, Vec<u8>) {
-    let mut request_builder = Request::builder().method(method).uri(uri);
+    send_request(app, Request::builder().method(method).uri(uri), body).await
+}
 
+pub async fn send_request(
+    app: &Router,
+    mut request_builder: axum::http::request::Builder,
+    body: Option<Body>,
+) -> (u16, Vec<u8>) {
     if body.is_some() {
         request_builder = request_builder.header("Content-Type", "application/json");
     }
@@ -56,6 +85,111 @@ pub async fn make_request(
     (status, body_bytes.to_vec())
 }
 
+// <feature:auth>
+/// Helper function to make HTTP requests with a bearer token
+///
+/// Same as [`make_request`], with `token` sent in the `Authorization` header.
+///
+/// # Arguments
+/// - `app`: The axum Router to send the request to
+/// - `method`: HTTP method (e.g., "GET", "POST", "PUT", "DELETE")
+/// - `uri`: Request URI path (e.g., "/tasks", "/tasks/123")
+/// - `body`: Optional request body for POST/PUT requests
+/// - `token`: JWT, e.g. from [`issue_test_token`]
+///
+/// # Returns
+/// A tuple containing:
+/// - Status code as u16 (e.g., 200, 401, 404)
+/// - Response body as Vec<u8>
+pub async fn make_authenticated_request(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    body: Option<Body>,
+    token: &str,
+) -> (u16, Vec<u8>) {
+    let request_builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Authorization", format!("Bearer {token}"));
+    send_request(app, request_builder, body).await
+}
+
+/// Audience the service accepts tokens for
+pub const TEST_TOKEN_AUDIENCE: &str = "rust-service-template";
+
+/// Helper function to sign a JWT for a user, as the identity provider would
+///
+/// # Arguments
+/// - `user_id`: User the token is issued to, its `sub` claim
+/// - `ttl`: Time until the token expires; negative for one that has already expired
+///
+/// # Returns
+/// A token signed with [`common::TEST_JWT_SECRET`] for [`TEST_TOKEN_AUDIENCE`]
+pub fn issue_test_token(user_id: UserId, ttl: chrono::Duration) -> String {
+    sign_test_token(&test_claims(user_id, ttl))
+}
+
+/// Helper function to sign a JWT granting the admin role, valid for an hour
+pub fn issue_admin_token(user_id: UserId) -> String {
+    sign_test_token(&JwtClaims {
+        roles: vec![ADMIN_ROLE.to_string()],
+        ..test_claims(user_id, chrono::Duration::hours(1))
+    })
+}
+
+/// Helper function to sign a JWT for a user of `tenant`, valid for an hour
+pub fn issue_tenant_token(user_id: UserId, tenant: TenantId) -> String {
+    sign_test_token(&JwtClaims {
+        tenant_id: Some(tenant.into_inner()),
+        ..test_claims(user_id, chrono::Duration::hours(1))
+    })
+}
+
+/// Helper function to sign a JWT granting the admin role within `tenant`, valid for an hour
+pub fn issue_tenant_admin_token(user_id: UserId, tenant: TenantId) -> String {
+    sign_test_token(&JwtClaims {
+        roles: vec![ADMIN_ROLE.to_string()],
+        tenant_id: Some(tenant.into_inner()),
+        ..test_claims(user_id, chrono::Duration::hours(1))
+    })
+}
+
+fn test_claims(user_id: UserId, ttl: chrono::Duration) -> JwtClaims {
+    JwtClaims {
+        sub: Some(user_id.to_string()),
+        aud: Some(TEST_TOKEN_AUDIENCE.to_string()),
+        exp: usize::try_from((chrono::Utc::now() + ttl).timestamp()).unwrap(),
+        iss: None,
+        session_id: None,
+        roles: Vec::new(),
+        tenant_id: None,
+    }
+}
+
+/// Helper function to sign a JWT that expired an hour ago, well past the validation leeway
+pub fn issue_expired_token(user_id: UserId) -> String {
+    issue_test_token(user_id, -chrono::Duration::hours(1))
+}
+
+/// Helper function to sign an otherwise valid JWT issued for another service
+pub fn issue_wrong_audience_token(user_id: UserId) -> String {
+    sign_test_token(&JwtClaims {
+        aud: Some("another-service".to_string()),
+        ..test_claims(user_id, chrono::Duration::hours(1))
+    })
+}
+
+fn sign_test_token(claims: &JwtClaims) -> String {
+    jsonwebtoken::encode(
+        &jsonwebtoken::Header::default(),
+        claims,
+        &jsonwebtoken::EncodingKey::from_secret(common::TEST_JWT_SECRET.as_bytes()),
+    )
+    .unwrap()
+}
+// </feature:auth>
+
 /// Helper function to create a JSON request body from a string
 ///
 /// Converts a JSON string into a Body for HTTP requests.
@@ -71,29 +205,24 @@ pub fn create_json_body(data: &str) -> Body {
 
 /// Helper function to parse JSON response bytes into a Value
 ///
-/// Attempts to parse response bytes as JSON. If parsing fails,
-/// creates a default error response structure.
+/// Every response from the API, including routing and body rejections,
+/// uses a JSON body, so a parse failure is a test failure.
 ///
 /// # Arguments
 /// - `body_bytes`: Raw response body bytes
 ///
 /// # Returns
 /// A serde_json::Value containing the parsed response
+///
+/// # Panics
+/// If the response body is not valid JSON
 pub fn parse_json_response(body_bytes: &[u8]) -> Value {
-    serde_json::from_slice(body_bytes).unwrap_or_else(|_| {
-        // If we can't parse as JSON, check if it's a plain text error from Axum
-        let text = String::from_utf8_lossy(body_bytes);
-        if text.contains("missing field") || text.contains("Failed to deserialize") {
-            // This is an Axum JSON deserialization error, return BadRequest
-            serde_json::json!({
-                "code": "BadRequest"
-            })
-        } else {
-            // If we can't parse as JSON, create a default error response
-            serde_json::json!({
-                "code": "InvalidResponse"
-            })
-        }
+    serde_json::from_slice(body_bytes).unwrap_or_else(|e| {
+        panic!(
+            "Response should be valid JSON ({}): {}",
+            e,
+            String::from_utf8_lossy(body_bytes)
+        )
     })
 }
 
@@ -122,8 +251,8 @@ pub fn verify_error_response(body_bytes: &[u8], expected_code: &str) {
 
 /// Helper function to create a test task and insert it into the database
 ///
-/// Creates a Task domain object and persists it using the repository.
-/// This is useful for setting up test data without going through the API.
+/// Shorthand for the common case of [`TaskFixture`], which also sets status and
+/// timestamps.
 ///
 /// # Arguments
 /// - `pool`: Database connection pool
@@ -141,10 +270,13 @@ pub async fn create_test_task(
     description: Option<String>,
     priority: TaskPriority,
 ) -> Task {
-    let task = Task::new(user_id, title.to_string(), description, priority).unwrap();
-    let repo = PostgresTaskRepository::new(pool.clone());
-    repo.create(task.clone()).await.unwrap();
-    task
+    let fixture = TaskFixture::new(user_id).title(title).priority(priority);
+    match description {
+        Some(description) => fixture.description(&description),
+        None => fixture,
+    }
+    .insert(pool)
+    .await
 }
 
 /// Helper function to check if a task exists in the database
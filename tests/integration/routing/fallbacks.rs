@@ -0,0 +1,98 @@
+use axum::http::header::{ALLOW, CONTENT_TYPE};
+
+use super::super::*;
+
+/// Send a bodyless request and return the full response for header assertions
+async fn send(app: &Router, method: &str, uri: &str) -> axum::response::Response {
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_unknown_route_returns_json_404() {
+    // Objective: Verify unknown routes return the JSON error envelope
+    let (app, _db) = common::app().await;
+
+    // Act: Request a path with no registered route
+    let response = send(&app, "GET", "/does-not-exist").await;
+
+    // Assert: Verify 404 with JSON NotFound body
+    assert_eq!(response.status(), 404, "Should return 404 Not Found");
+    assert_eq!(
+        response.headers()[CONTENT_TYPE],
+        "application/json",
+        "404 should be JSON"
+    );
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    verify_error_response(&body, "NotFound");
+}
+
+#[tokio::test]
+async fn test_unsupported_method_returns_json_405_with_allow_header() {
+    // Objective: Verify an unsupported method on a known route returns 405 with Allow
+    let (app, _db) = common::app().await;
+
+    // Act: DELETE is not registered on /tasks
+    let response = send(&app, "DELETE", "/tasks").await;
+
+    // Assert: Verify 405 with Allow header and JSON MethodNotAllowed body
+    assert_eq!(
+        response.status(),
+        405,
+        "Should return 405 Method Not Allowed"
+    );
+    let allow = response.headers()[ALLOW].to_str().unwrap().to_string();
+    assert!(
+        allow.contains("GET"),
+        "Allow should list GET, got {}",
+        allow
+    );
+    assert!(
+        allow.contains("POST"),
+        "Allow should list POST, got {}",
+        allow
+    );
+    assert_eq!(
+        response.headers()[CONTENT_TYPE],
+        "application/json",
+        "405 should be JSON"
+    );
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    verify_error_response(&body, "MethodNotAllowed");
+}
+
+#[tokio::test]
+async fn test_missing_content_type_returns_json_415() {
+    // Objective: Verify body rejections use the JSON envelope
+    let (app, _db) = common::app().await;
+
+    // Act: POST a body without a JSON content type
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/tasks")
+                .body(Body::from(r#"{"title": "no content type"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // Assert: Verify 415 with JSON UnsupportedMediaType body
+    assert_eq!(
+        response.status(),
+        415,
+        "Should return 415 Unsupported Media Type"
+    );
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    verify_error_response(&body, "UnsupportedMediaType");
+}
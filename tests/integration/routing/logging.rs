@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use rust_service_template::api::build_app_router;
+
+use super::super::*;
+
+#[tokio::test]
+async fn test_body_logging_passes_request_and_response_through_unchanged() {
+    // Objective: Verify enabling body logging does not alter what handlers and clients see
+    let (mut state, _db) = common::app_state().await;
+    state.env.request_logging_config.log_bodies = true;
+    state.env.request_logging_config.max_body_bytes = 8;
+    let app = build_app_router(Arc::new(state)).await;
+    let title = generate_unique_title("body_logging");
+
+    // Act: Create a task with a body larger than the logging cap
+    let (status, body_bytes) = make_request(
+        &app,
+        "POST",
+        "/tasks",
+        Some(create_json_body(&format!(
+            r#"{{"title": "{}", "description": "longer than eight bytes"}}"#,
+            title
+        ))),
+    )
+    .await;
+
+    // Assert: Verify the full request reached the handler and the full response came back
+    assert_eq!(
+        status, 201,
+        "Task should be created with body logging enabled"
+    );
+    let body = parse_json_response(&body_bytes);
+    assert_eq!(body["title"], title, "Title should survive body buffering");
+    assert_eq!(
+        body["description"], "longer than eight bytes",
+        "Response body should not be truncated"
+    );
+}
+
+#[tokio::test]
+async fn test_errors_are_still_returned_with_body_logging_enabled() {
+    // Objective: Verify error responses pass through the body logging middleware intact
+    let (mut state, _db) = common::app_state().await;
+    state.env.request_logging_config.log_bodies = true;
+    let app = build_app_router(Arc::new(state)).await;
+
+    // Act: Request an unknown task
+    let (status, body_bytes) =
+        make_request(&app, "GET", &format!("/tasks/{}", Uuid::new_v4()), None).await;
+
+    // Assert: Verify the 404 envelope is unchanged
+    assert_eq!(status, 404);
+    verify_error_response(&body_bytes, "NotFound");
+}
+
+#[tokio::test]
+async fn test_body_logging_keeps_the_default_body_limit() {
+    // Objective: Verify logging does not read past the limit the extractors enforce
+    let (mut state, _db) = common::app_state().await;
+    state.env.request_logging_config.log_bodies = true;
+    let app = build_app_router(Arc::new(state)).await;
+    let description = "x".repeat(3 * 1024 * 1024);
+
+    // Act: Send a body larger than axum's 2 MB default
+    let (status, body_bytes) = make_request(
+        &app,
+        "POST",
+        "/tasks",
+        Some(create_json_body(&format!(
+            r#"{{"title": "too large", "description": "{description}"}}"#
+        ))),
+    )
+    .await;
+
+    // Assert: Verify the body is still rejected unread, not validated
+    assert_eq!(status, 400, "Oversized body should be rejected");
+    verify_error_response(&body_bytes, "BadRequest");
+}
+
+#[tokio::test]
+async fn test_event_stream_is_not_held_back_by_body_logging() {
+    // Objective: Verify an endless SSE response still reaches the client
+    let (mut state, _db) = common::app_state().await;
+    state.env.request_logging_config.log_bodies = true;
+    let app = build_app_router(Arc::new(state)).await;
+    let user_id = UserId::new();
+
+    // Act: Open the task stream
+    let response = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        app.oneshot(
+            Request::builder()
+                .uri(format!("/tasks/stream?user_id={user_id}"))
+                .header(
+                    "Authorization",
+                    format!(
+                        "Bearer {}",
+                        issue_test_token(user_id, chrono::Duration::minutes(5))
+                    ),
+                )
+                .body(Body::empty())
+                .unwrap(),
+        ),
+    )
+    .await
+    .expect("Stream should open without waiting for its end")
+    .unwrap();
+
+    // Assert: Verify the stream opened as Server-Sent Events
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+}
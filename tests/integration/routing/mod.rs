@@ -0,0 +1,2 @@
+pub mod fallbacks;
+pub mod logging;
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use rust_service_template::api::{bind, build_app_router, serve};
+
+use crate::common;
+
+#[tokio::test]
+async fn test_bind_on_port_zero_serves_real_tcp_traffic() {
+    // Objective: Verify port 0 binds an OS-assigned port that serves requests
+    let (mut state, _db) = common::app_state().await;
+    state.env.server_host = "127.0.0.1".to_string();
+    state.env.server_port = 0;
+
+    // Arrange: Bind an ephemeral port and serve the app on it
+    let (listener, addr) = bind(&state.env).await.expect("Failed to bind listener");
+    assert_ne!(addr.port(), 0, "Bound address should report the real port");
+    let app = build_app_router(Arc::new(state)).await;
+    let server = tokio::spawn(serve(listener, app));
+
+    // Act: Hit the health endpoint over the real TCP stack
+    let response = reqwest::get(format!("http://{}/health", addr))
+        .await
+        .expect("Request to live server failed");
+
+    // Assert: Verify the live server answered
+    assert_eq!(response.status(), 200, "Health check should return 200");
+    assert_eq!(response.text().await.unwrap(), "OK");
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_two_servers_on_port_zero_get_distinct_ports() {
+    // Objective: Verify multiple instances can run side by side with port 0
+    let (mut state, _db) = common::app_state().await;
+    state.env.server_host = "127.0.0.1".to_string();
+    state.env.server_port = 0;
+
+    // Act: Bind twice with the same configuration
+    let (_first, first_addr) = bind(&state.env).await.unwrap();
+    let (_second, second_addr) = bind(&state.env).await.unwrap();
+
+    // Assert: Verify each listener got its own port
+    assert_ne!(
+        first_addr.port(),
+        second_addr.port(),
+        "Each bind should get a distinct OS-assigned port"
+    );
+}
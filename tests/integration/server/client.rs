@@ -0,0 +1,259 @@
+use std::{sync::Arc, time::Duration};
+
+use rust_service_template::{
+    api::{
+        bind, build_app_router,
+        error::ErrorCode,
+        models::{tasks::CreateTaskRequest, users::CreateUserRequest},
+        serve,
+    },
+    client::{ClientError, TaskApiClient},
+    common::{TenantId, UserId},
+    config::{AppConfig, TenancyMode},
+    domain::task::models::TaskPriority,
+};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::{
+    common::{self, TestDatabase},
+    integration::{admin::token, issue_test_token},
+};
+
+/// Serve the app on an ephemeral port, with admin routes enabled, for as long as the
+/// returned database is kept
+async fn live_server() -> (String, JoinHandle<anyhow::Result<()>>, TestDatabase) {
+    live_server_with(|_| {}).await
+}
+
+/// Like [`live_server`], with `configure` applied to the configuration
+async fn live_server_with(
+    configure: impl FnOnce(&mut AppConfig),
+) -> (String, JoinHandle<anyhow::Result<()>>, TestDatabase) {
+    let (mut state, db) = common::app_state_with(configure).await;
+    state.env.server_host = "127.0.0.1".to_string();
+    state.env.server_port = 0;
+    state.env.admin_endpoints = true;
+
+    let (listener, addr) = bind(&state.env).await.expect("Failed to bind listener");
+    let app = build_app_router(Arc::new(state)).await;
+    (
+        format!("http://{addr}"),
+        tokio::spawn(serve(listener, app)),
+        db,
+    )
+}
+
+#[tokio::test]
+async fn test_client_round_trips_tasks() {
+    // Objective: Verify the client creates, fetches and lists tasks on a live server
+    let (base_url, server, _db) = live_server().await;
+    let user_id = UserId::new();
+    let client = TaskApiClient::new(base_url)
+        .unwrap()
+        .with_bearer_token(&issue_test_token(user_id, chrono::Duration::hours(1)))
+        .unwrap();
+    client.health().await.expect("Health check should succeed");
+
+    // Act: Create a task, then read it back by id and by owner
+    let created = client
+        .create_task(&CreateTaskRequest {
+            user_id: Some(user_id),
+            title: "Client round trip".to_string(),
+            description: Some("Created through TaskApiClient".to_string()),
+            priority: Some(TaskPriority::High),
+        })
+        .await
+        .expect("Create should succeed");
+    let fetched = client
+        .get_task(created.id.parse().unwrap())
+        .await
+        .expect("Get should succeed");
+    let listed = client
+        .list_tasks(user_id)
+        .await
+        .expect("List should succeed");
+
+    // Assert: Every call sees the same task
+    assert_eq!(created.title, "Client round trip");
+    assert_eq!(created.user_id, user_id.to_string());
+    assert_eq!(created.priority, TaskPriority::High);
+    assert_eq!(fetched.id, created.id);
+    assert_eq!(fetched.description, created.description);
+    assert_eq!(listed.len(), 1, "Only the created task should be listed");
+    assert_eq!(listed[0].id, created.id);
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_client_registers_users_who_own_tasks() {
+    // Objective: Verify the client registers a user, fetches them back and creates a task
+    // in their name
+    let (base_url, server, _db) = live_server().await;
+    let client = TaskApiClient::new(base_url).unwrap();
+
+    // Act: Register a user, fetch them, then create a task for them
+    let registered = client
+        .create_user(&CreateUserRequest {
+            email: "Client@Example.com".to_string(),
+            display_name: "Client".to_string(),
+        })
+        .await
+        .expect("Registration should succeed");
+    let user_id = UserId::from_uuid(registered.id.parse().unwrap());
+    let fetched = client.get_user(user_id).await.expect("Get should succeed");
+    let task = client
+        .create_task(&CreateTaskRequest {
+            user_id: Some(user_id),
+            title: "Owned through the client".to_string(),
+            description: None,
+            priority: None,
+        })
+        .await
+        .expect("Create should succeed");
+
+    // Assert: Verify the stored user and the task's owner
+    assert_eq!(registered.email, "client@example.com");
+    assert_eq!(fetched.id, registered.id);
+    assert_eq!(task.user_id, registered.id);
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_client_maps_error_envelope() {
+    // Objective: Verify error responses surface as typed API errors
+    // Negative test: Unknown task and invalid title
+    let (base_url, server, _db) = live_server().await;
+    let client = TaskApiClient::new(base_url).unwrap();
+
+    // Act: Fetch a task that does not exist
+    let error = client.get_task(Uuid::new_v4()).await.unwrap_err();
+
+    // Assert: 404 is mapped to NotFound
+    assert!(
+        matches!(error, ClientError::Api { status, code: ErrorCode::NotFound, .. } if status == 404),
+        "Unexpected error: {error:?}"
+    );
+
+    // Act: Create a task with an empty title
+    let error = client
+        .create_task(&CreateTaskRequest {
+            user_id: None,
+            title: String::new(),
+            description: None,
+            priority: None,
+        })
+        .await
+        .unwrap_err();
+
+    // Assert: The validation failure keeps its code and names the field
+    assert_eq!(error.code(), Some(&ErrorCode::ValidationError));
+    assert!(
+        matches!(&error, ClientError::Api { errors, .. } if errors.len() == 1 && errors[0].field == "title"),
+        "Unexpected error: {error:?}"
+    );
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_client_sends_tenant_header() {
+    // Objective: Verify a client bound to a tenant only sees that tenant's tasks
+    let (base_url, server, _db) =
+        live_server_with(|config| config.tenancy = TenancyMode::Multi).await;
+    let tenant_a = TaskApiClient::new(base_url.clone())
+        .unwrap()
+        .with_tenant(TenantId::new());
+    let tenant_b = TaskApiClient::new(base_url.clone())
+        .unwrap()
+        .with_tenant(TenantId::new());
+
+    // Act: Create a task as tenant A, then fetch it as each tenant and as neither
+    let created = tenant_a
+        .create_task(&CreateTaskRequest {
+            user_id: None,
+            title: "Tenant A's task".to_string(),
+            description: None,
+            priority: None,
+        })
+        .await
+        .expect("Create should succeed");
+    let id = created.id.parse().unwrap();
+    let own = tenant_a.get_task(id).await;
+    let other = tenant_b.get_task(id).await.unwrap_err();
+    let none = TaskApiClient::new(base_url)
+        .unwrap()
+        .get_task(id)
+        .await
+        .unwrap_err();
+
+    // Assert: Only tenant A finds it; a request naming no tenant is rejected
+    assert_eq!(own.expect("Tenant A should find its task").id, created.id);
+    assert_eq!(other.code(), Some(&ErrorCode::NotFound));
+    assert_eq!(none.code(), Some(&ErrorCode::BadRequest));
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_client_sends_bearer_token() {
+    // Objective: Verify the configured token authenticates admin calls
+    let (base_url, server, _db) = live_server().await;
+    let anonymous = TaskApiClient::new(&base_url).unwrap();
+    let authenticated = TaskApiClient::new(&base_url)
+        .unwrap()
+        .with_bearer_token(&token())
+        .unwrap();
+
+    // Act: Read the configuration with and without a token
+    let denied = anonymous.get_config().await.unwrap_err();
+    let config = authenticated
+        .get_config()
+        .await
+        .expect("Authenticated call should succeed");
+
+    // Assert: Only the authenticated client gets through
+    assert!(
+        matches!(denied, ClientError::Api { status, code: ErrorCode::TokenNotFound, .. } if status == 401),
+        "Unexpected error: {denied:?}"
+    );
+    assert_eq!(config["admin_endpoints"], true);
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_client_rejects_bad_base_url_and_times_out() {
+    // Objective: Verify configuration errors and timeouts are reported distinctly
+    // Negative test: Unsupported scheme, and a server that never answers
+    let error = TaskApiClient::new("ftp://tasks.example.com").unwrap_err();
+    assert!(matches!(error, ClientError::InvalidBaseUrl { .. }));
+    assert!(matches!(
+        TaskApiClient::new("not a url").unwrap_err(),
+        ClientError::InvalidBaseUrl { .. }
+    ));
+
+    // Arrange: Accept connections without ever responding
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let silent = tokio::spawn(async move {
+        let mut connections = Vec::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            connections.push(stream);
+        }
+    });
+    let client = TaskApiClient::new(format!("http://{addr}"))
+        .unwrap()
+        .with_timeout(Duration::from_millis(200));
+
+    // Act & Assert: The call gives up with a timeout
+    let error = client.health().await.unwrap_err();
+    assert!(
+        matches!(error, ClientError::Timeout(_)),
+        "Unexpected error: {error:?}"
+    );
+
+    silent.abort();
+}
@@ -0,0 +1,7 @@
+pub mod binding;
+pub mod bootstrap;
+pub mod client;
+pub mod ops;
+// <feature:binary>
+pub mod process;
+// </feature:binary>
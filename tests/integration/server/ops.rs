@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use rust_service_template::api::{
+    bind, bind_ops, build_app_router, build_ops_router, serve_with_ops,
+};
+
+use super::super::*;
+
+/// Serve the app with an ops listener, both on OS-assigned ports; returns their base URLs
+async fn start_with_ops_port() -> (
+    String,
+    String,
+    tokio::task::JoinHandle<()>,
+    common::TestDatabase,
+) {
+    let (mut state, db) = common::app_state_with(|config| config.admin_endpoints = true).await;
+    state.env.server_host = "127.0.0.1".to_string();
+    state.env.server_port = 0;
+    state.env.ops_port = Some(0);
+    let state = Arc::new(state);
+
+    let (listener, addr) = bind(&state.env).await.unwrap();
+    let (ops_listener, ops_addr) = bind_ops(&state.env).await.unwrap().unwrap();
+    let app = build_app_router(state.clone()).await;
+    let ops_app = build_ops_router(state);
+    let server = tokio::spawn(async move {
+        serve_with_ops((listener, app), (ops_listener, ops_app))
+            .await
+            .unwrap();
+    });
+
+    (
+        format!("http://{addr}"),
+        format!("http://{ops_addr}"),
+        server,
+        db,
+    )
+}
+
+async fn get_status(url: String, token: &str) -> u16 {
+    reqwest::Client::new()
+        .get(url)
+        .bearer_auth(token)
+        .send()
+        .await
+        .expect("Request to live server failed")
+        .status()
+        .as_u16()
+}
+
+#[tokio::test]
+async fn test_ops_port_moves_operator_routes_off_the_public_listener() {
+    // Objective: Verify `/admin/*` and detailed health answer only on the ops listener
+    let (public, ops, server, _db) = start_with_ops_port().await;
+    let token = issue_admin_token(UserId::new());
+
+    for path in [
+        "/admin/config",
+        "/admin/features",
+        "/health/detailed",
+        "/health/history",
+    ] {
+        // Assert: Served on the ops listener, not found on the public one
+        assert_eq!(
+            get_status(format!("{ops}{path}"), &token).await,
+            200,
+            "{path}"
+        );
+        assert_eq!(
+            get_status(format!("{public}{path}"), &token).await,
+            404,
+            "{path}"
+        );
+    }
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_ops_listener_serves_no_client_routes() {
+    // Negative test: The API and the probes stay on the public listener only
+    let (public, ops, server, _db) = start_with_ops_port().await;
+    let user_id = UserId::new();
+    let token = issue_test_token(user_id, chrono::Duration::hours(1));
+
+    for path in ["/health", "/ready", &format!("/users/{user_id}/tasks")] {
+        assert_eq!(
+            get_status(format!("{public}{path}"), &token).await,
+            200,
+            "{path}"
+        );
+        assert_eq!(
+            get_status(format!("{ops}{path}"), &token).await,
+            404,
+            "{path}"
+        );
+    }
+
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_operator_routes_stay_public_without_ops_port() {
+    // Objective: Verify the default single listener still serves the operator routes
+    let (app, _db) = common::app().await;
+    let token = issue_admin_token(UserId::new());
+
+    let (status, _) =
+        make_authenticated_request(&app, "GET", "/health/detailed", None, &token).await;
+
+    assert_eq!(status, 200);
+}
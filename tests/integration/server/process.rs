@@ -0,0 +1,182 @@
+use std::{
+    process::{ExitStatus, Stdio},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    process::{Child, Command},
+    sync::oneshot,
+    task::JoinHandle,
+};
+
+use crate::common;
+
+/// How long the binary may take to migrate and start listening
+const STARTUP_DEADLINE: Duration = Duration::from_secs(60);
+
+/// How long the binary may take to drain and exit after SIGTERM
+const SHUTDOWN_DEADLINE: Duration = Duration::from_secs(10);
+
+/// The HTTP API binary, running against its own schema
+///
+/// It serves the API under every feature set, including `--all-features`; the worker is the
+/// separate `worker` binary.
+struct ServiceProcess {
+    child: Child,
+    base_url: String,
+    /// Every log line so far, for assertions and failure messages
+    logs: Arc<Mutex<Vec<String>>>,
+    /// Tasks copying stdout and stderr into `logs`, done once the process exits
+    readers: Vec<JoinHandle<()>>,
+}
+
+impl ServiceProcess {
+    /// Start the binary on an OS-assigned port and wait until it logs the bound address
+    ///
+    /// Configuration comes from the environment, as in production; the test environment
+    /// set up by `common` is inherited.
+    async fn start(database_url: &str) -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_rust-service-template"))
+            .env("RUST_SERVICE_TEMPLATE__DATABASE_URL", database_url)
+            .env("RUST_SERVICE_TEMPLATE__SERVER_PORT", "0")
+            .env("RUST_SERVICE_TEMPLATE__LOG_FORMAT", "json")
+            .env("RUST_LOG", "info")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .expect("Failed to start the service binary");
+
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        let (address_sender, address) = oneshot::channel();
+        let mut address_sender = Some(address_sender);
+        let mut stdout = BufReader::new(child.stdout.take().unwrap()).lines();
+        let stdout_logs = logs.clone();
+        let stdout_reader = tokio::spawn(async move {
+            while let Ok(Some(line)) = stdout.next_line().await {
+                let message = serde_json::from_str::<serde_json::Value>(&line)
+                    .ok()
+                    .and_then(|event| event["message"].as_str().map(str::to_string));
+                if let Some(address) = message
+                    .as_deref()
+                    .and_then(|message| message.strip_prefix("Listening on "))
+                {
+                    if let Some(sender) = address_sender.take() {
+                        let _ = sender.send(address.to_string());
+                    }
+                }
+                stdout_logs.lock().unwrap().push(line);
+            }
+        });
+        let mut stderr = BufReader::new(child.stderr.take().unwrap()).lines();
+        let stderr_logs = logs.clone();
+        let stderr_reader = tokio::spawn(async move {
+            while let Ok(Some(line)) = stderr.next_line().await {
+                stderr_logs.lock().unwrap().push(line);
+            }
+        });
+
+        let address = tokio::time::timeout(STARTUP_DEADLINE, address).await;
+        let Ok(Ok(address)) = address else {
+            panic!(
+                "The binary never reported its address:\n{}",
+                logs.lock().unwrap().join("\n")
+            );
+        };
+        Self {
+            child,
+            base_url: format!("http://{address}"),
+            logs,
+            readers: vec![stdout_reader, stderr_reader],
+        }
+    }
+
+    fn logs(&self) -> String {
+        self.logs.lock().unwrap().join("\n")
+    }
+
+    /// Send SIGTERM, as Kubernetes does when stopping a pod
+    fn terminate(&self) {
+        let pid = self.child.id().expect("The binary has already exited");
+        // SAFETY: kill only sends a signal; the pid is our own child, which has not been reaped
+        let sent = unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) };
+        assert_eq!(sent, 0, "Failed to send SIGTERM");
+    }
+
+    /// Wait for the process to exit, at most `deadline`, and for its last log lines
+    async fn wait(&mut self, deadline: Duration) -> ExitStatus {
+        let status = tokio::time::timeout(deadline, self.child.wait())
+            .await
+            .unwrap_or_else(|_| panic!("The binary did not exit within {deadline:?}"))
+            .expect("Failed to wait for the binary");
+        for reader in self.readers.drain(..) {
+            reader.await.unwrap();
+        }
+        status
+    }
+}
+
+#[tokio::test]
+async fn test_binary_serves_tasks_and_shuts_down_on_sigterm() {
+    // Objective: Verify the real binary parses its environment, migrates an empty schema,
+    // serves tasks over HTTP and exits cleanly on SIGTERM
+    let (_db, database_url) = common::empty_database().await;
+    let mut service = ServiceProcess::start(&database_url).await;
+    let client = reqwest::Client::new();
+
+    // Act: Wait for readiness, then create a task and fetch it back
+    let ready = client
+        .get(format!("{}/ready", service.base_url))
+        .send()
+        .await
+        .expect("Readiness request failed");
+    assert_eq!(
+        ready.status(),
+        200,
+        "Service should be ready once listening"
+    );
+
+    let created = client
+        .post(format!("{}/tasks", service.base_url))
+        .json(&serde_json::json!({ "title": "Smoke test task", "priority": "High" }))
+        .send()
+        .await
+        .expect("Create request failed");
+    assert_eq!(created.status(), 201, "{}", service.logs());
+    let created: serde_json::Value = created.json().await.unwrap();
+
+    let fetched = client
+        .get(format!(
+            "{}/tasks/{}",
+            service.base_url,
+            created["id"].as_str().unwrap()
+        ))
+        .send()
+        .await
+        .expect("Get request failed");
+    assert_eq!(fetched.status(), 200, "{}", service.logs());
+    let fetched: serde_json::Value = fetched.json().await.unwrap();
+
+    // Assert: Verify the task round-tripped, then that SIGTERM stops the process cleanly
+    assert_eq!(
+        fetched, created,
+        "Fetched task should match the created one"
+    );
+    assert_eq!(fetched["title"], "Smoke test task");
+    assert_eq!(fetched["priority"], "High");
+
+    service.terminate();
+    let status = service.wait(SHUTDOWN_DEADLINE).await;
+    assert!(
+        status.success(),
+        "Unclean exit ({status}):\n{}",
+        service.logs()
+    );
+    assert!(
+        service.logs().contains("Shutdown signal received"),
+        "Shutdown should go through the graceful drain:\n{}",
+        service.logs()
+    );
+}
@@ -0,0 +1,136 @@
+use chrono::Utc;
+use rust_service_template::domain::task::models::TaskStatus;
+
+use super::super::*;
+
+/// Send a GET with `if_none_match`, if any, returning the status, `ETag` and body
+async fn conditional_get(
+    app: &Router,
+    uri: &str,
+    if_none_match: Option<&str>,
+) -> (u16, Option<String>, Vec<u8>) {
+    let mut request = Request::builder().uri(uri);
+    if let Some(if_none_match) = if_none_match {
+        request = request.header("If-None-Match", if_none_match);
+    }
+    let response = app
+        .clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status().as_u16();
+    let etag = response
+        .headers()
+        .get("ETag")
+        .map(|value| value.to_str().unwrap().to_string());
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, etag, body_bytes.to_vec())
+}
+
+#[tokio::test]
+async fn test_get_task_revalidates_until_the_task_changes() {
+    // Objective: Verify a matching If-None-Match yields an empty 304 until the task is
+    // written, after which the task comes back with a new ETag
+    let (app, db) = common::app().await;
+    let task = TaskFixture::new(UserId::new()).insert(&db).await;
+    let uri = format!("/tasks/{}", task.id);
+
+    // Arrange: Fetch the task once to learn its ETag
+    let (status, etag, _) = conditional_get(&app, &uri, None).await;
+    assert_eq!(status, 200, "Should find the task");
+    let etag = etag.expect("Task should carry an ETag");
+    assert!(etag.starts_with("W/\""), "ETag should be weak: {etag}");
+
+    // Act: Revalidate, then update the task and revalidate again
+    let (unchanged_status, unchanged_etag, unchanged_body) =
+        conditional_get(&app, &uri, Some(&etag)).await;
+    let mut updated = task.clone();
+    updated.status = TaskStatus::Completed;
+    updated.updated_at = Utc::now();
+    updated.completed_at = Some(updated.updated_at);
+    PostgresTaskRepository::new((*db).clone())
+        .update(&updated)
+        .await
+        .unwrap();
+    let (changed_status, changed_etag, changed_body) =
+        conditional_get(&app, &uri, Some(&etag)).await;
+
+    // Assert: 304 with no body while unchanged, 200 with a new ETag once updated
+    assert_eq!(unchanged_status, 304, "Unchanged task should not be resent");
+    assert!(unchanged_body.is_empty(), "304 should have no body");
+    assert_eq!(unchanged_etag.as_deref(), Some(etag.as_str()));
+    assert_eq!(changed_status, 200, "Updated task should be resent");
+    assert_ne!(
+        changed_etag.as_deref(),
+        Some(etag.as_str()),
+        "Update should change the ETag"
+    );
+    assert_eq!(parse_json_response(&changed_body)["status"], "Completed");
+}
+
+#[tokio::test]
+async fn test_list_tasks_revalidates_until_a_task_is_added() {
+    // Objective: Verify list responses are tagged and revalidated like single tasks
+    let (app, db) = common::app().await;
+    let user_id = UserId::new();
+    TaskFixture::new(user_id).insert(&db).await;
+    let uri = format!("/tasks?user_id={user_id}");
+    let (_, etag, _) = conditional_get(&app, &uri, None).await;
+    let etag = etag.expect("List should carry an ETag");
+
+    // Act: Revalidate, then add a task and revalidate again
+    let (unchanged_status, _, unchanged_body) = conditional_get(&app, &uri, Some(&etag)).await;
+    TaskFixture::new(user_id).insert(&db).await;
+    let (changed_status, changed_etag, changed_body) =
+        conditional_get(&app, &uri, Some(&etag)).await;
+
+    // Assert: 304 while unchanged, the longer list with a new ETag afterwards
+    assert_eq!(unchanged_status, 304, "Unchanged list should not be resent");
+    assert!(unchanged_body.is_empty(), "304 should have no body");
+    assert_eq!(changed_status, 200, "Changed list should be resent");
+    assert_ne!(changed_etag.as_deref(), Some(etag.as_str()));
+    assert_eq!(
+        parse_json_response(&changed_body).as_array().unwrap().len(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn test_etag_differs_between_formats_and_ignores_other_tags() {
+    // Negative test: A tag for another representation or another task is not a match
+    let (app, db) = common::app().await;
+    let task = TaskFixture::new(UserId::new()).insert(&db).await;
+    let other = TaskFixture::new(UserId::new()).insert(&db).await;
+    let uri = format!("/tasks/{}", task.id);
+    let (_, other_etag, _) = conditional_get(&app, &format!("/tasks/{}", other.id), None).await;
+
+    // Act: Fetch the task as MessagePack, and revalidate it with the other task's tag
+    let msgpack = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(&uri)
+                .header("Accept", "application/msgpack")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let (json_status, json_etag, _) = conditional_get(&app, &uri, other_etag.as_deref()).await;
+
+    // Assert: Each representation has its own tag, and a foreign tag gets the full body
+    assert_eq!(json_status, 200, "Another task's ETag should not match");
+    assert_ne!(
+        msgpack
+            .headers()
+            .get("ETag")
+            .map(|value| value.to_str().unwrap()),
+        json_etag.as_deref(),
+        "JSON and MessagePack should be tagged apart"
+    );
+    assert_eq!(
+        msgpack.headers().get("Vary").unwrap(),
+        "accept",
+        "Negotiated responses should vary on Accept"
+    );
+}
@@ -1,3 +1,7 @@
+use std::sync::Arc;
+
+use rust_service_template::api::build_app_router;
+
 use super::super::*;
 
 #[tokio::test]
@@ -35,9 +39,18 @@ async fn test_create_task_returns_201_with_valid_data() {
         body.get("user_id").is_some(),
         "Response should include user_id"
     );
+    for field in ["created_at", "updated_at"] {
+        let timestamp = body[field]
+            .as_str()
+            .unwrap_or_else(|| panic!("Response should include {field}"));
+        assert!(
+            chrono::DateTime::parse_from_rfc3339(timestamp).is_ok(),
+            "{field} should be RFC 3339: {timestamp}"
+        );
+    }
     assert!(
-        body.get("created_at").is_some(),
-        "Response should include created_at"
+        body["completed_at"].is_null(),
+        "New task should not be completed"
     );
 }
 
@@ -45,7 +58,7 @@ async fn test_create_task_returns_201_with_valid_data() {
 async fn test_create_task_returns_400_with_empty_title() {
     // Objective: Verify empty title is rejected
     // Negative test: Empty string should fail validation
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
 
     // Arrange: Create request with empty title
     let body = r#"{"title": "", "description": "Test description"}"#;
@@ -63,7 +76,7 @@ async fn test_create_task_returns_400_with_empty_title() {
 async fn test_create_task_returns_400_with_title_too_long() {
     // Objective: Verify title length limit is enforced
     // Negative test: Title > 200 characters should fail
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
 
     // Arrange: Create request with title > 200 characters
     let long_title = "a".repeat(201);
@@ -85,7 +98,7 @@ async fn test_create_task_returns_400_with_title_too_long() {
 async fn test_create_task_returns_400_with_whitespace_only_title() {
     // Objective: Verify whitespace-only title is rejected
     // Negative test: Title with only spaces should fail
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
 
     // Arrange: Create request with whitespace-only title
     let body = r#"{"title": "   ", "description": "Test description"}"#;
@@ -102,11 +115,70 @@ async fn test_create_task_returns_400_with_whitespace_only_title() {
     verify_error_response(&body_bytes, "ValidationError");
 }
 
+#[tokio::test]
+async fn test_create_task_lists_every_invalid_field() {
+    // Objective: Verify one response reports every invalid field, not only the first
+    // Negative test: Empty title and a description over the limit
+    let (app, _db) = common::app().await;
+    let body = serde_json::json!({
+        "title": "",
+        "description": "d".repeat(2001),
+    });
+
+    // Act: Send POST request
+    let (status, body_bytes) = make_request(
+        &app,
+        "POST",
+        "/tasks",
+        Some(create_json_body(&body.to_string())),
+    )
+    .await;
+
+    // Assert: Verify both fields are listed with their reasons
+    assert_eq!(status, 400);
+    let body = parse_json_response(&body_bytes);
+    assert_eq!(body["code"], "ValidationError");
+    assert_eq!(
+        body["errors"],
+        serde_json::json!([
+            { "field": "title", "code": "required", "message": "Title cannot be empty" },
+            {
+                "field": "description",
+                "code": "too_long",
+                "message": "Description cannot exceed 2000 characters"
+            }
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_create_task_lists_a_lone_invalid_field() {
+    // Objective: Verify a single failure still comes as a one-entry list
+    let (app, _db) = common::app().await;
+    let body = serde_json::json!({ "title": "a".repeat(201) });
+
+    // Act: Send POST request
+    let (status, body_bytes) = make_request(
+        &app,
+        "POST",
+        "/tasks",
+        Some(create_json_body(&body.to_string())),
+    )
+    .await;
+
+    // Assert: Verify the one entry
+    assert_eq!(status, 400);
+    let errors = parse_json_response(&body_bytes)["errors"].clone();
+    assert_eq!(errors.as_array().map(Vec::len), Some(1), "{errors}");
+    assert_eq!(errors[0]["field"], "title");
+    assert_eq!(errors[0]["code"], "too_long");
+}
+
 #[tokio::test]
 async fn test_create_task_returns_201_with_unicode_characters() {
     // Objective: Verify unicode characters are supported in title
     // Positive test: Unicode should be handled correctly
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
     let title = "Test tâsk with spëcial çharacters 日本語";
 
     // Arrange: Create request with unicode title
@@ -129,7 +201,7 @@ async fn test_create_task_returns_201_with_unicode_characters() {
 async fn test_create_task_returns_201_with_special_characters_in_description() {
     // Objective: Verify special characters in description are supported
     // Positive test: Special chars in description should work
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
     let title = generate_unique_title("special_chars");
 
     // Arrange: Create request with special characters in description
@@ -158,7 +230,7 @@ async fn test_create_task_returns_201_with_special_characters_in_description() {
 async fn test_create_task_with_low_priority() {
     // Objective: verify task creation with Low priority
     // Positive test: Low priority should be accepted
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
     let title = generate_unique_title("low_priority");
 
     // Arrange: Create request with Low priority
@@ -178,7 +250,7 @@ async fn test_create_task_with_low_priority() {
 async fn test_create_task_with_medium_priority() {
     // Objective: Verify task creation with Medium priority
     // Positive test: Medium priority should be accepted
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
     let title = generate_unique_title("medium_priority");
 
     // Arrange: Create request with Medium priority
@@ -198,7 +270,7 @@ async fn test_create_task_with_medium_priority() {
 async fn test_create_task_with_high_priority() {
     // Objective: Verify task creation with High priority
     // Positive test: High priority should be accepted
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
     let title = generate_unique_title("high_priority");
 
     // Arrange: Create request with High priority
@@ -218,7 +290,7 @@ async fn test_create_task_with_high_priority() {
 async fn test_create_task_with_critical_priority() {
     // Objective: Verify task creation with Critical priority
     // Positive test: Critical priority should be accepted
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
     let title = generate_unique_title("critical_priority");
 
     // Arrange: Create request with Critical priority
@@ -238,7 +310,7 @@ async fn test_create_task_with_critical_priority() {
 async fn test_create_task_with_default_priority() {
     // Objective: Verify default priority is Medium when not specified
     // Positive test: Missing priority should default to Medium
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
     let title = generate_unique_title("default_priority");
 
     // Arrange: Create request without priority field
@@ -261,7 +333,7 @@ async fn test_create_task_with_default_priority() {
 async fn test_create_task_with_missing_description() {
     // Objective: Verify task creation works without description
     // Positive test: Optional description field should work
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
     let title = generate_unique_title("no_description");
 
     // Arrange: Create request without description
@@ -285,51 +357,55 @@ async fn test_create_task_with_missing_description() {
 async fn test_create_task_returns_422_with_missing_title_field() {
     // Objective: Verify missing required field is rejected
     // Negative test: Missing title should return 422 (JSON deserialization error)
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
 
     // Arrange: Create request without title field
     let body = r#"{"description": "Test description"}"#;
 
     // Act: Send POST request
-    let (status, _) = make_request(&app, "POST", "/tasks", Some(create_json_body(body))).await;
+    let (status, body_bytes) =
+        make_request(&app, "POST", "/tasks", Some(create_json_body(body))).await;
 
     // Assert: Verify 422 Unprocessable Entity
     assert_eq!(
         status, 422,
         "Should return 422 Unprocessable Entity for missing title field"
     );
+    verify_error_response(&body_bytes, "UnprocessableEntity");
 }
 
 #[tokio::test]
 async fn test_create_task_returns_400_with_malformed_json() {
     // Objective: Verify malformed JSON is rejected
     // Negative test: Invalid JSON should return 400
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
 
     // Arrange: Create malformed JSON (missing closing brace)
     let body = r#"{"title": "test", "description": "desc""#;
 
     // Act: Send POST request
-    let (status, _) = make_request(&app, "POST", "/tasks", Some(create_json_body(body))).await;
+    let (status, body_bytes) =
+        make_request(&app, "POST", "/tasks", Some(create_json_body(body))).await;
 
     // Assert: Verify 400 Bad Request
     assert_eq!(
         status, 400,
         "Should return 400 Bad Request for malformed JSON"
     );
+    verify_error_response(&body_bytes, "BadRequest");
 }
 
 #[tokio::test]
 async fn test_create_task_returns_422_with_invalid_priority_type() {
     // Objective: Verify invalid priority value is rejected
     // Negative test: Invalid priority enum value should fail
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
 
     // Arrange: Create request with invalid priority value
     let body = r#"{"title": "Test", "priority": "InvalidPriority"}"#;
 
     // Act: Send POST request
-    let (status, _body_bytes) =
+    let (status, body_bytes) =
         make_request(&app, "POST", "/tasks", Some(create_json_body(body))).await;
 
     // Assert: Verify 422 Unprocessable Entity (JSON deserialization error)
@@ -337,6 +413,73 @@ async fn test_create_task_returns_422_with_invalid_priority_type() {
         status, 422,
         "Should return 422 Unprocessable Entity for invalid priority type"
     );
+    verify_error_response(&body_bytes, "UnprocessableEntity");
+}
+
+/// Router whose JSON bodies are parsed with `strict_json` set to `strict`
+async fn app_with_strict_json(strict: bool) -> (Router, common::TestDatabase) {
+    let (state, db) = common::app_state_with(|config| config.strict_json = strict).await;
+    (build_app_router(Arc::new(state)).await, db)
+}
+
+#[tokio::test]
+async fn test_unknown_fields_are_ignored_unless_json_is_strict() {
+    // Objective: Verify a misspelt field is ignored by default and rejected in strict mode
+    let body = serde_json::json!({
+        "title": generate_unique_title("strict"),
+        "priorty": "High",
+    })
+    .to_string();
+
+    for (strict, expected) in [(false, 201), (true, 422)] {
+        let (app, _db) = app_with_strict_json(strict).await;
+
+        // Act: Send the body with the misspelt field
+        let (status, body_bytes) =
+            make_request(&app, "POST", "/tasks", Some(create_json_body(&body))).await;
+
+        // Assert: Verify the status, and that strict mode names the field
+        assert_eq!(status, expected, "strict_json = {strict}");
+        if strict {
+            let error = parse_json_response(&body_bytes);
+            assert_eq!(error["code"], "UnprocessableEntity");
+            assert_eq!(error["message"], "Unknown field `priorty`");
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_strict_json_accepts_known_fields_and_keeps_other_rejections() {
+    // Objective: Verify strict mode changes nothing for bodies without unknown fields
+    let (app, _db) = app_with_strict_json(true).await;
+    let valid = serde_json::json!({
+        "title": generate_unique_title("strict"),
+        "description": null,
+        "priority": "Low",
+    })
+    .to_string();
+
+    // Act: Send a valid body, one missing the title and a malformed one
+    let (created, _) = make_request(&app, "POST", "/tasks", Some(create_json_body(&valid))).await;
+    let (missing, _) = make_request(
+        &app,
+        "POST",
+        "/tasks",
+        Some(create_json_body(
+            r#"{"description": "no title", "extra": 1}"#,
+        )),
+    )
+    .await;
+    let (malformed, _) = make_request(
+        &app,
+        "POST",
+        "/tasks",
+        Some(create_json_body(r#"{"title": "#)),
+    )
+    .await;
+
+    // Assert: Verify the same statuses as in lenient mode
+    assert_eq!((created, missing, malformed), (201, 422, 400));
 }
 
 #[tokio::test]
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use axum::http::HeaderMap;
+use rust_service_template::{api::build_app_router, config::AppConfig};
+
+use super::super::*;
+
+/// App with the feature flag `flag` set to `enabled`
+async fn app_with_flag(flag: &str, enabled: bool) -> (Router, common::TestDatabase) {
+    let (state, db) = common::app_state_with(|config: &mut AppConfig| {
+        config.features.insert(flag.to_string(), enabled);
+    })
+    .await;
+    (build_app_router(Arc::new(state)).await, db)
+}
+
+/// App with the `reject_duplicate_titles` flag set to `enabled`
+async fn app_rejecting_duplicates(enabled: bool) -> (Router, common::TestDatabase) {
+    app_with_flag("reject_duplicate_titles", enabled).await
+}
+
+/// Create a task, sending `Prefer: return=verbose` when `verbose`, and return the response
+/// headers too
+async fn create_preferring(
+    app: &Router,
+    user_id: UserId,
+    title: &str,
+    verbose: bool,
+) -> (u16, HeaderMap, Value) {
+    let body = serde_json::json!({ "title": title, "user_id": user_id });
+    let mut request = Request::builder()
+        .method("POST")
+        .uri("/tasks")
+        .header("Content-Type", "application/json");
+    if verbose {
+        request = request.header("Prefer", "return=verbose");
+    }
+    let response = app
+        .clone()
+        .oneshot(request.body(Body::from(body.to_string())).unwrap())
+        .await
+        .unwrap();
+    let status = response.status().as_u16();
+    let headers = response.headers().clone();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, headers, parse_json_response(&body))
+}
+
+async fn create(app: &Router, user_id: UserId, title: &str) -> (u16, Value) {
+    let body = serde_json::json!({ "title": title, "user_id": user_id });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/tasks")
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status().as_u16();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, parse_json_response(&body))
+}
+
+#[tokio::test]
+async fn test_duplicate_titles_are_rejected_with_the_feature_on() {
+    // Objective: Verify a user cannot open two tasks with the same title once the flag is on
+    let (app, _db) = app_rejecting_duplicates(true).await;
+    let (user_id, other_user) = (UserId::new(), UserId::new());
+    let title = generate_unique_title("duplicate");
+
+    // Act: Create the same title twice for one user, then once for another user
+    let (first, _) = create(&app, user_id, &title).await;
+    let (second, body) = create(&app, user_id, &title).await;
+    let (other, _) = create(&app, other_user, &title).await;
+
+    // Assert: Verify only the second task of the same user is refused
+    assert_eq!(first, 201);
+    assert_eq!(second, 409, "{body}");
+    assert_eq!(body["code"], "Conflict");
+    assert_eq!(other, 201);
+}
+
+#[tokio::test]
+async fn test_duplicate_titles_are_allowed_with_the_feature_off() {
+    // Negative test: the rule is dark-launched, so the default keeps accepting duplicates
+    let (app, _db) = app_rejecting_duplicates(false).await;
+    let user_id = UserId::new();
+    let title = generate_unique_title("duplicate");
+
+    // Act: Create the same title twice
+    let (first, _) = create(&app, user_id, &title).await;
+    let (second, _) = create(&app, user_id, &title).await;
+
+    // Assert: Verify both are created
+    assert_eq!((first, second), (201, 201));
+}
+
+#[tokio::test]
+async fn test_possible_duplicate_title_is_reported_as_a_warning() {
+    // Objective: Verify a same title in another case is created and flagged in X-Warnings
+    let (app, _db) = app_with_flag("warn_duplicate_titles", true).await;
+    let user_id = UserId::new();
+    let title = generate_unique_title("duplicate");
+
+    // Act: Create the title, then the same title in upper case
+    let (first, first_headers, _) = create_preferring(&app, user_id, &title, false).await;
+    let (second, headers, body) =
+        create_preferring(&app, user_id, &title.to_uppercase(), false).await;
+
+    // Assert: Only the second is flagged, and the body stays the default representation
+    assert_eq!((first, second), (201, 201), "{body}");
+    assert!(first_headers.get("x-warnings").is_none());
+    assert_eq!(headers["x-warnings"], "PossibleDuplicateTitle");
+    assert!(body.get("warnings").is_none(), "{body}");
+    assert!(headers.get("preference-applied").is_none());
+}
+
+#[tokio::test]
+async fn test_verbose_representation_lists_the_warnings() {
+    // Objective: Verify `Prefer: return=verbose` adds the warnings array, even when empty
+    let (app, _db) = app_with_flag("warn_duplicate_titles", true).await;
+    let user_id = UserId::new();
+    let title = generate_unique_title("duplicate");
+
+    // Act
+    let (_, first_headers, first) = create_preferring(&app, user_id, &title, true).await;
+    let (status, headers, body) = create_preferring(&app, user_id, &title, true).await;
+
+    // Assert: The first task has no warnings, the second names the first
+    assert_eq!(first["warnings"], serde_json::json!([]));
+    assert_eq!(first_headers["preference-applied"], "return=verbose");
+    assert_eq!(status, 201, "{body}");
+    assert_eq!(headers["preference-applied"], "return=verbose");
+    assert_eq!(body["warnings"][0]["code"], "PossibleDuplicateTitle");
+    assert_eq!(
+        body["warnings"][0]["message"],
+        format!(
+            "Open task {} has the same title",
+            first["id"].as_str().unwrap()
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_duplicate_title_warning_is_off_by_default() {
+    // Negative test: without the flag duplicates are created without a warning
+    let (app, _db) = app_with_flag("warn_duplicate_titles", false).await;
+    let user_id = UserId::new();
+    let title = generate_unique_title("duplicate");
+
+    // Act
+    create_preferring(&app, user_id, &title, false).await;
+    let (status, headers, body) = create_preferring(&app, user_id, &title, true).await;
+
+    // Assert
+    assert_eq!(status, 201);
+    assert!(headers.get("x-warnings").is_none());
+    assert_eq!(body["warnings"], serde_json::json!([]));
+}
@@ -1,25 +1,6 @@
 use super::super::*;
 use rust_service_template::domain::task::models::{TaskPriority, TaskStatus};
 
-// Helper functions to convert domain enums to database string representations
-fn status_to_db_string(status: TaskStatus) -> &'static str {
-    match status {
-        TaskStatus::Pending => "PENDING",
-        TaskStatus::InProgress => "IN_PROGRESS",
-        TaskStatus::Completed => "COMPLETED",
-        TaskStatus::Cancelled => "CANCELLED",
-    }
-}
-
-fn priority_to_db_string(priority: TaskPriority) -> &'static str {
-    match priority {
-        TaskPriority::Low => "LOW",
-        TaskPriority::Medium => "MEDIUM",
-        TaskPriority::High => "HIGH",
-        TaskPriority::Critical => "CRITICAL",
-    }
-}
-
 #[tokio::test]
 async fn test_list_tasks_returns_200_with_tasks() {
     // Objective: Verify listing tasks by user_id returns all user's tasks
@@ -27,24 +8,27 @@ async fn test_list_tasks_returns_200_with_tasks() {
     let (app, pool) = common::app().await;
     let user_id = UserId::new();
 
-    // Arrange: Create multiple tasks for the same user
-    let task1 = create_test_task(
-        &pool,
-        user_id,
-        "Task 1",
-        Some("Description 1".to_string()),
-        TaskPriority::High,
-    )
-    .await;
-    let task2 = create_test_task(
-        &pool,
-        user_id,
-        "Task 2",
-        Some("Description 2".to_string()),
-        TaskPriority::Low,
-    )
-    .await;
-    let task3 = create_test_task(&pool, user_id, "Task 3", None, TaskPriority::Medium).await;
+    // Arrange: Create multiple tasks for the same user, a minute apart
+    let now = chrono::Utc::now();
+    let task1 = TaskFixture::new(user_id)
+        .title("Task 1")
+        .description("Description 1")
+        .priority(TaskPriority::High)
+        .created_at(now - chrono::Duration::minutes(2))
+        .insert(&pool)
+        .await;
+    let task2 = TaskFixture::new(user_id)
+        .title("Task 2")
+        .description("Description 2")
+        .priority(TaskPriority::Low)
+        .created_at(now - chrono::Duration::minutes(1))
+        .insert(&pool)
+        .await;
+    let task3 = TaskFixture::new(user_id)
+        .title("Task 3")
+        .created_at(now)
+        .insert(&pool)
+        .await;
 
     // Act: Send GET request to list tasks
     let (status, body_bytes) =
@@ -67,7 +51,7 @@ async fn test_list_tasks_returns_200_with_tasks() {
 async fn test_list_tasks_returns_200_empty_for_new_user() {
     // Objective: Verify listing tasks for user with no tasks returns empty array
     // Positive test: Empty result should return 200 with empty array
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
     let user_id = UserId::new();
 
     // Arrange: Use a user_id with no tasks
@@ -88,7 +72,7 @@ async fn test_list_tasks_returns_200_empty_for_new_user() {
 async fn test_list_tasks_returns_400_missing_user_id() {
     // Objective: Verify missing user_id query parameter is rejected
     // Negative test: Required query parameter missing should return 400
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
 
     // Arrange: Send request without user_id query param
     // (No setup needed)
@@ -102,13 +86,20 @@ async fn test_list_tasks_returns_400_missing_user_id() {
         "Should return 400 Bad Request for missing user_id"
     );
     verify_error_response(&body_bytes, "BadRequest");
+    let body = parse_json_response(&body_bytes);
+    assert!(
+        body["message"]
+            .as_str()
+            .is_some_and(|message| message.contains("user_id")),
+        "Error message should name the missing parameter: {body}"
+    );
 }
 
 #[tokio::test]
 async fn test_list_tasks_returns_400_invalid_user_id_format() {
     // Objective: Verify invalid UUID format for user_id is rejected
     // Negative test: Malformed UUID should return 400
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
 
     // Arrange: Use invalid user_id format
     let invalid_user_id = "not-a-valid-uuid";
@@ -128,6 +119,10 @@ async fn test_list_tasks_returns_400_invalid_user_id_format() {
         "Should return 400 Bad Request for invalid user_id format"
     );
     verify_error_response(&body_bytes, "BadRequest");
+    assert!(
+        parse_json_response(&body_bytes)["message"].is_string(),
+        "Error should explain the parse failure"
+    );
 }
 
 #[tokio::test]
@@ -136,41 +131,22 @@ async fn test_list_tasks_with_different_statuses() {
     // Positive test: All status types should be included in results
     let (app, pool) = common::app().await;
     let user_id = UserId::new();
-    let now = chrono::Utc::now();
 
-    // Arrange: Create tasks with different statuses directly in DB
+    // Arrange: Create tasks with different statuses
     for (i, status) in [
         TaskStatus::Pending,
         TaskStatus::InProgress,
         TaskStatus::Completed,
         TaskStatus::Cancelled,
     ]
-    .iter()
+    .into_iter()
     .enumerate()
     {
-        let task_id = uuid::Uuid::new_v4();
-        sqlx::query(
-            r#"
-            INSERT INTO tasks (id, user_id, title, description, status, priority, created_at, updated_at, completed_at)
-            VALUES ($1, $2, $3, $4, $5::task_status, $6::task_priority, $7, $8, $9)
-            "#,
-        )
-        .bind(task_id)
-        .bind(user_id.into_inner())
-        .bind(format!("Task {}", i))
-        .bind::<Option<String>>(None)
-        .bind(status_to_db_string(*status))
-        .bind(priority_to_db_string(TaskPriority::Medium))
-        .bind(now)
-        .bind(now)
-        .bind(if *status == TaskStatus::Completed {
-            Some(now)
-        } else {
-            None
-        })
-        .execute(&*pool)
-        .await
-        .unwrap();
+        TaskFixture::new(user_id)
+            .title(&format!("Task {}", i))
+            .status(status)
+            .insert(&pool)
+            .await;
     }
 
     // Act: Send GET request to list tasks
@@ -352,3 +328,33 @@ async fn test_list_tasks_with_single_task() {
     assert_eq!(body.as_array().unwrap().len(), 1, "Should return 1 task");
     assert_eq!(body[0]["title"], "Single Task", "Task title should match");
 }
+
+#[tokio::test]
+async fn test_query_param_listing_is_gone_without_legacy_routes() {
+    // Objective: Verify turning legacy routes off retires `GET /tasks?user_id=` while
+    // creation on the same path keeps working
+    let (mut state, _db) = common::app_state().await;
+    state.env.legacy_routes = false;
+    let app = rust_service_template::api::build_app_router(std::sync::Arc::new(state)).await;
+
+    // Act: List by query parameter, then create a task
+    let (list_status, body_bytes) = make_request(
+        &app,
+        "GET",
+        &format!("/tasks?user_id={}", UserId::new()),
+        None,
+    )
+    .await;
+    let (create_status, _) = make_request(
+        &app,
+        "POST",
+        "/tasks",
+        Some(create_json_body(r#"{"title": "Still created"}"#)),
+    )
+    .await;
+
+    // Assert: Verify the listing is no longer served
+    assert_eq!(list_status, 405, "Legacy listing should be retired");
+    verify_error_response(&body_bytes, "MethodNotAllowed");
+    assert_eq!(create_status, 201, "Creation should be unaffected");
+}
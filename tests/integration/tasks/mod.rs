@@ -1,3 +1,13 @@
+pub mod caching;
 pub mod creation;
+pub mod duplicates;
 pub mod listing;
+pub mod negotiation;
+pub mod pages;
+pub mod priority;
+pub mod quota;
 pub mod retrieval;
+pub mod stats;
+pub mod stream;
+pub mod tenancy;
+pub mod title;
@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+
+use super::super::*;
+
+const MSGPACK: &str = "application/msgpack";
+
+/// Send a request with explicit `Content-Type` and `Accept` headers, returning the status,
+/// the response `Content-Type` and the body
+async fn negotiate(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    body: Option<(&str, Vec<u8>)>,
+    accept: &str,
+) -> (u16, String, Vec<u8>) {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Accept", accept);
+    let body = match body {
+        Some((content_type, bytes)) => {
+            request = request.header("Content-Type", content_type);
+            Body::from(bytes)
+        }
+        None => Body::empty(),
+    };
+    let response = app
+        .clone()
+        .oneshot(request.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status().as_u16();
+    let content_type = response
+        .headers()
+        .get("Content-Type")
+        .map(|value| value.to_str().unwrap().to_string())
+        .unwrap_or_default();
+    let body_bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, content_type, body_bytes.to_vec())
+}
+
+#[derive(Serialize)]
+struct NewTask<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_id: Option<&'a str>,
+    title: &'a str,
+    description: &'a str,
+    priority: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct DecodedTask {
+    id: String,
+    user_id: String,
+    title: String,
+    description: Option<String>,
+    priority: String,
+    status: String,
+}
+
+#[tokio::test]
+async fn test_task_round_trips_as_json_by_default() {
+    // Objective: Verify JSON stays the format of create and get when clients accept anything
+    let (app, _db) = common::app().await;
+    let title = generate_unique_title("json_round_trip");
+    let body = serde_json::to_vec(&NewTask {
+        user_id: None,
+        title: &title,
+        description: "Sent as JSON",
+        priority: "High",
+    })
+    .unwrap();
+
+    // Act: Create the task and read it back
+    let (create_status, create_type, created) = negotiate(
+        &app,
+        "POST",
+        "/tasks",
+        Some(("application/json", body)),
+        "*/*",
+    )
+    .await;
+    let created: DecodedTask = serde_json::from_slice(&created).unwrap();
+    let (get_status, get_type, fetched) =
+        negotiate(&app, "GET", &format!("/tasks/{}", created.id), None, "*/*").await;
+
+    // Assert: Both responses are JSON and carry the task as sent
+    assert_eq!(create_status, 201, "Should create the task");
+    assert_eq!(get_status, 200, "Should find the task");
+    assert_eq!(create_type, "application/json");
+    assert_eq!(get_type, "application/json");
+    let fetched: DecodedTask = serde_json::from_slice(&fetched).unwrap();
+    assert_eq!(fetched.id, created.id, "Should read back the created task");
+    assert_eq!(fetched.title, title);
+    assert_eq!(fetched.description.as_deref(), Some("Sent as JSON"));
+    assert_eq!(fetched.priority, "High");
+    assert_eq!(fetched.status, "Pending");
+}
+
+#[tokio::test]
+async fn test_task_round_trips_as_msgpack() {
+    // Objective: Verify create reads a MessagePack body and create and get answer in
+    // MessagePack when the client prefers it
+    let (app, _db) = common::app().await;
+    let title = generate_unique_title("msgpack_round_trip");
+    let user_id = UserId::new().to_string();
+    let body = rmp_serde::to_vec_named(&NewTask {
+        user_id: Some(&user_id),
+        title: &title,
+        description: "Sent as MessagePack",
+        priority: "Critical",
+    })
+    .unwrap();
+    let accept = "application/json;q=0.5, application/msgpack";
+
+    // Act: Create the task and read it back
+    let (create_status, create_type, created) =
+        negotiate(&app, "POST", "/tasks", Some((MSGPACK, body)), accept).await;
+    let created: DecodedTask = rmp_serde::from_slice(&created).unwrap();
+    let (get_status, get_type, fetched) =
+        negotiate(&app, "GET", &format!("/tasks/{}", created.id), None, accept).await;
+
+    // Assert: Both responses are MessagePack and carry the task as sent, ids as strings
+    assert_eq!(create_status, 201, "Should create the task");
+    assert_eq!(get_status, 200, "Should find the task");
+    assert_eq!(create_type, MSGPACK);
+    assert_eq!(get_type, MSGPACK);
+    let fetched: DecodedTask = rmp_serde::from_slice(&fetched).unwrap();
+    assert_eq!(fetched.id, created.id, "Should read back the created task");
+    assert_eq!(fetched.title, title);
+    assert_eq!(fetched.description.as_deref(), Some("Sent as MessagePack"));
+    assert_eq!(
+        fetched.user_id, user_id,
+        "Should read the owner as a string"
+    );
+    assert_eq!(fetched.priority, "Critical");
+    assert_eq!(fetched.status, "Pending");
+}
+
+#[tokio::test]
+async fn test_formats_can_be_mixed_between_request_and_response() {
+    // Objective: Verify the request body format does not decide the response format
+    let (app, _db) = common::app().await;
+    let title = generate_unique_title("mixed_formats");
+    let body = rmp_serde::to_vec_named(&NewTask {
+        user_id: None,
+        title: &title,
+        description: "In MessagePack, out JSON",
+        priority: "Low",
+    })
+    .unwrap();
+
+    // Act: Send MessagePack without asking for it back
+    let (status, content_type, created) = negotiate(
+        &app,
+        "POST",
+        "/tasks",
+        Some((MSGPACK, body)),
+        "application/json",
+    )
+    .await;
+
+    // Assert: The task is created and returned as JSON
+    assert_eq!(status, 201, "Should create the task");
+    assert_eq!(content_type, "application/json");
+    assert_eq!(parse_json_response(&created)["title"], title);
+}
+
+#[tokio::test]
+async fn test_create_task_rejects_unsupported_and_malformed_bodies() {
+    // Negative test: Bodies that are neither JSON nor valid MessagePack are rejected
+    let (app, _db) = common::app().await;
+
+    // Act: Send plain text, bytes that are not MessagePack, and MessagePack of the
+    // wrong shape
+    let (text_status, _, text_body) = negotiate(
+        &app,
+        "POST",
+        "/tasks",
+        Some(("text/plain", b"Buy milk".to_vec())),
+        MSGPACK,
+    )
+    .await;
+    let (garbage_status, _, garbage_body) =
+        negotiate(&app, "POST", "/tasks", Some((MSGPACK, vec![0xc1])), MSGPACK).await;
+    let (shape_status, _, shape_body) = negotiate(
+        &app,
+        "POST",
+        "/tasks",
+        Some((MSGPACK, rmp_serde::to_vec_named(&[1, 2, 3]).unwrap())),
+        MSGPACK,
+    )
+    .await;
+
+    // Assert: Errors keep the JSON envelope whatever the client accepts
+    assert_eq!(text_status, 415, "Should reject plain text");
+    verify_error_response(&text_body, "UnsupportedMediaType");
+    assert_eq!(
+        garbage_status, 400,
+        "Should reject bytes that do not decode"
+    );
+    verify_error_response(&garbage_body, "BadRequest");
+    assert_eq!(shape_status, 422, "Should reject a body of the wrong shape");
+    verify_error_response(&shape_body, "UnprocessableEntity");
+}
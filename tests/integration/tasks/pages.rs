@@ -0,0 +1,132 @@
+use super::super::*;
+use rust_service_template::domain::task::models::{TaskPriority, TaskStatus};
+
+/// GET `uri` as `user_id`, returning the status and JSON body
+async fn get_page(app: &Router, user_id: UserId, uri: &str) -> (u16, Value) {
+    let token = issue_test_token(user_id, chrono::Duration::hours(1));
+    let (status, body) = make_authenticated_request(app, "GET", uri, None, &token).await;
+    (status, parse_json_response(&body))
+}
+
+#[tokio::test]
+async fn test_v1_task_listing_returns_a_page() {
+    // Objective: Verify the versioned listing wraps the tasks in the page envelope
+    let (app, pool) = common::app().await;
+    let user_id = UserId::new();
+
+    // Arrange: Three tasks, a minute apart
+    let now = chrono::Utc::now();
+    let mut titles = Vec::new();
+    for minutes in [3, 2, 1] {
+        let task = TaskFixture::new(user_id)
+            .created_at(now - chrono::Duration::minutes(minutes))
+            .insert(&pool)
+            .await;
+        titles.push(task.title.value().to_string());
+    }
+
+    // Act: The first page of two, then the second
+    let uri = format!("/api/v1/users/{user_id}/tasks?limit=2");
+    let (status, first) = get_page(&app, user_id, &uri).await;
+    let (_, second) = get_page(&app, user_id, &format!("{uri}&offset=2")).await;
+
+    // Assert: Newest first, with the offset of the next page until the last one
+    assert_eq!(status, 200, "{first}");
+    assert_eq!(first["total"], 3);
+    assert_eq!(first["limit"], 2);
+    assert_eq!(first["offset"], 0);
+    assert_eq!(first["next_offset"], 2);
+    assert_eq!(first["items"][0]["title"], titles[2]);
+    assert_eq!(first["items"][1]["title"], titles[1]);
+    assert_eq!(second["items"].as_array().unwrap().len(), 1);
+    assert_eq!(second["items"][0]["title"], titles[0]);
+    assert!(second.get("next_offset").is_none(), "{second}");
+}
+
+#[tokio::test]
+async fn test_v1_task_listing_total_counts_only_filtered_tasks() {
+    // Objective: Verify `total` counts the tasks matching the filters, not the whole list
+    let (app, pool) = common::app().await;
+    let user_id = UserId::new();
+
+    // Arrange: Two high priority pending tasks, one completed, one low, and another user's
+    for _ in 0..2 {
+        TaskFixture::new(user_id)
+            .priority(TaskPriority::High)
+            .insert(&pool)
+            .await;
+    }
+    TaskFixture::new(user_id)
+        .priority(TaskPriority::High)
+        .status(TaskStatus::Completed)
+        .insert(&pool)
+        .await;
+    TaskFixture::new(user_id)
+        .priority(TaskPriority::Low)
+        .insert(&pool)
+        .await;
+    TaskFixture::new(UserId::new())
+        .priority(TaskPriority::High)
+        .insert(&pool)
+        .await;
+
+    // Act
+    let (status, page) = get_page(
+        &app,
+        user_id,
+        &format!("/api/v1/users/{user_id}/tasks?priority=High&status=Pending&limit=1"),
+    )
+    .await;
+    let (_, all) = get_page(&app, user_id, &format!("/api/v1/users/{user_id}/tasks")).await;
+
+    // Assert
+    assert_eq!(status, 200, "{page}");
+    assert_eq!(page["total"], 2);
+    assert_eq!(page["items"].as_array().unwrap().len(), 1);
+    assert_eq!(page["items"][0]["priority"], "High");
+    assert_eq!(page["next_offset"], 1);
+    assert_eq!(all["total"], 4);
+    assert_eq!(all["limit"], 50);
+}
+
+#[tokio::test]
+async fn test_unversioned_task_listing_keeps_the_array_shape() {
+    // Objective: Verify existing clients still get a bare array outside `/api/v1`
+    let (app, pool) = common::app().await;
+    let user_id = UserId::new();
+    TaskFixture::new(user_id).insert(&pool).await;
+
+    let (status, body) = get_page(&app, user_id, &format!("/users/{user_id}/tasks")).await;
+
+    assert_eq!(status, 200);
+    assert_eq!(body.as_array().map(Vec::len), Some(1), "{body}");
+}
+
+#[tokio::test]
+async fn test_v1_task_listing_rejects_an_out_of_range_limit() {
+    // Negative test: A page size over the maximum is a validation error
+    let (app, _db) = common::app().await;
+    let user_id = UserId::new();
+
+    let (status, body) = get_page(
+        &app,
+        user_id,
+        &format!("/api/v1/users/{user_id}/tasks?limit=101"),
+    )
+    .await;
+
+    assert_eq!(status, 400);
+    assert_eq!(body["code"], "ValidationError");
+    assert_eq!(body["errors"][0]["field"], "limit");
+}
+
+#[tokio::test]
+async fn test_v1_task_listing_of_another_user_is_forbidden() {
+    // Negative test: The versioned listing authorizes like the unversioned one
+    let (app, _db) = common::app().await;
+    let owner = UserId::new();
+
+    let (status, _) = get_page(&app, UserId::new(), &format!("/api/v1/users/{owner}/tasks")).await;
+
+    assert_eq!(status, 403);
+}
@@ -0,0 +1,340 @@
+use std::sync::Arc;
+
+use rust_service_template::{api::build_app_router, domain::task::models::TaskEventType};
+
+use super::super::{doubles::RecordingEventProducer, *};
+use crate::common::TestDatabase;
+
+/// App whose task events are recorded rather than published
+async fn app_recording_events() -> (Router, TestDatabase, Arc<RecordingEventProducer>) {
+    let (mut state, db) = common::app_state().await;
+    let events = Arc::new(RecordingEventProducer::default());
+    state.event_producer = events.clone();
+    (build_app_router(Arc::new(state)).await, db, events)
+}
+
+/// Change the priority of `task` as its owner
+async fn change_priority(app: &Router, task: &Task, body: Value) -> (u16, Value) {
+    let token = issue_test_token(task.user_id, chrono::Duration::hours(1));
+    let (status, body) = make_authenticated_request(
+        app,
+        "POST",
+        &format!("/tasks/{}/priority", task.id),
+        Some(Body::from(body.to_string())),
+        &token,
+    )
+    .await;
+    (status, parse_json_response(&body))
+}
+
+/// The audit trail of `task`'s priority: from, to, reason and who changed it
+async fn priority_changes(
+    pool: &sqlx::PgPool,
+    task: &Task,
+) -> Vec<(String, String, Option<String>, Uuid)> {
+    sqlx::query_as(
+        "SELECT from_priority::text, to_priority::text, reason, changed_by
+         FROM priority_changes WHERE task_id = $1 ORDER BY id",
+    )
+    .bind(task.id.into_inner())
+    .fetch_all(pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_escalation_to_critical_records_and_publishes_the_reason() {
+    // Objective: Verify the reason reaches both the audit trail and the Updated event
+    let (app, db, events) = app_recording_events().await;
+    let task = create_test_task(
+        &db,
+        UserId::new(),
+        &generate_unique_title("escalate"),
+        None,
+        TaskPriority::High,
+    )
+    .await;
+
+    // Act
+    let (status, body) = change_priority(
+        &app,
+        &task,
+        serde_json::json!({"priority": "Critical", "reason": "Blocks the release"}),
+    )
+    .await;
+
+    // Assert: The task is escalated and the change is audited
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(body["priority"], "Critical");
+    assert_eq!(
+        priority_changes(&db, &task).await,
+        vec![(
+            "HIGH".to_string(),
+            "CRITICAL".to_string(),
+            Some("Blocks the release".to_string()),
+            task.user_id.into_inner()
+        )]
+    );
+
+    // Assert: One Updated event, carrying the reason and the previous priority
+    let events = events.events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type, TaskEventType::Updated);
+    assert_eq!(
+        events[0].metadata.reason.as_deref(),
+        Some("Blocks the release")
+    );
+    assert_eq!(
+        events[0].old_data.as_ref().unwrap().priority,
+        TaskPriority::High
+    );
+    assert_eq!(events[0].data.priority, TaskPriority::Critical);
+}
+
+#[tokio::test]
+async fn test_escalation_to_critical_requires_a_reason() {
+    // Negative test: Without a reason nothing changes, is audited or is published
+    let (app, db, events) = app_recording_events().await;
+    let task = create_test_task(
+        &db,
+        UserId::new(),
+        &generate_unique_title("no_reason"),
+        None,
+        TaskPriority::Low,
+    )
+    .await;
+
+    // Act: No reason, then a blank one
+    let (status, body) =
+        change_priority(&app, &task, serde_json::json!({"priority": "Critical"})).await;
+    let (blank_status, _) = change_priority(
+        &app,
+        &task,
+        serde_json::json!({"priority": "Critical", "reason": "   "}),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(status, 400);
+    assert_eq!(body["code"], "ValidationError");
+    assert_eq!(body["errors"][0]["field"], "reason");
+    assert_eq!(body["errors"][0]["code"], "required");
+    assert_eq!(blank_status, 400);
+    assert!(priority_changes(&db, &task).await.is_empty());
+    assert!(events.events().is_empty());
+}
+
+#[tokio::test]
+async fn test_escalation_below_critical_needs_no_reason() {
+    // Objective: Verify only escalating to Critical asks for a reason
+    let (app, db, _events) = app_recording_events().await;
+    let task = create_test_task(
+        &db,
+        UserId::new(),
+        &generate_unique_title("escalate_high"),
+        None,
+        TaskPriority::Low,
+    )
+    .await;
+
+    let (status, body) =
+        change_priority(&app, &task, serde_json::json!({"priority": "High"})).await;
+
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(
+        priority_changes(&db, &task).await,
+        vec![(
+            "LOW".to_string(),
+            "HIGH".to_string(),
+            None,
+            task.user_id.into_inner()
+        )]
+    );
+}
+
+#[tokio::test]
+async fn test_de_escalation_needs_no_reason() {
+    // Objective: Verify lowering a Critical task is audited without a reason
+    let (app, db, events) = app_recording_events().await;
+    let task = create_test_task(
+        &db,
+        UserId::new(),
+        &generate_unique_title("de_escalate"),
+        None,
+        TaskPriority::Critical,
+    )
+    .await;
+
+    // Act
+    let (status, body) = change_priority(&app, &task, serde_json::json!({"priority": "Low"})).await;
+
+    // Assert
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(body["priority"], "Low");
+    let changes = priority_changes(&db, &task).await;
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].0, "CRITICAL");
+    assert_eq!(changes[0].2, None);
+    assert_eq!(events.events()[0].metadata.reason, None);
+}
+
+#[tokio::test]
+async fn test_unchanged_priority_is_rejected() {
+    // Negative test: A change to the current priority would only clutter the audit trail
+    let (app, db, events) = app_recording_events().await;
+    let task = create_test_task(
+        &db,
+        UserId::new(),
+        &generate_unique_title("unchanged"),
+        None,
+        TaskPriority::Critical,
+    )
+    .await;
+
+    let (status, body) = change_priority(
+        &app,
+        &task,
+        serde_json::json!({"priority": "Critical", "reason": "Still urgent"}),
+    )
+    .await;
+
+    assert_eq!(status, 400);
+    assert_eq!(body["code"], "BadRequest");
+    assert_eq!(body["message"], "Task priority is already Critical");
+    assert!(priority_changes(&db, &task).await.is_empty());
+    assert!(events.events().is_empty());
+}
+
+#[tokio::test]
+async fn test_concurrent_changes_are_audited_one_after_another() {
+    // Objective: Verify concurrent changes each start from the priority the previous one
+    // left, so the audit trail is a chain without stale `from` priorities
+    let (app, db, _events) = app_recording_events().await;
+    let task = create_test_task(
+        &db,
+        UserId::new(),
+        &generate_unique_title("concurrent"),
+        None,
+        TaskPriority::Low,
+    )
+    .await;
+
+    // Act: Three changes to different priorities at once
+    let (medium, high, critical) = tokio::join!(
+        change_priority(&app, &task, serde_json::json!({"priority": "Medium"})),
+        change_priority(&app, &task, serde_json::json!({"priority": "High"})),
+        change_priority(
+            &app,
+            &task,
+            serde_json::json!({"priority": "Critical", "reason": "Outage"})
+        ),
+    );
+
+    // Assert: All succeed, and each audited change starts where the previous one ended
+    for (status, body) in [medium, high, critical] {
+        assert_eq!(status, 200, "{body}");
+    }
+    let changes = priority_changes(&db, &task).await;
+    assert_eq!(changes.len(), 3);
+    assert_eq!(changes[0].0, "LOW");
+    for pair in changes.windows(2) {
+        assert_eq!(pair[1].0, pair[0].1, "Stale from priority in {changes:?}");
+    }
+}
+
+#[tokio::test]
+async fn test_concurrent_identical_changes_apply_once() {
+    // Negative test: Of two concurrent changes to the same priority, the second sees the
+    // first one's result and is rejected as unchanged rather than failing or double-auditing
+    let (app, db, events) = app_recording_events().await;
+    let task = create_test_task(
+        &db,
+        UserId::new(),
+        &generate_unique_title("identical"),
+        None,
+        TaskPriority::Low,
+    )
+    .await;
+    let body = serde_json::json!({"priority": "High"});
+
+    // Act
+    let (first, second) = tokio::join!(
+        change_priority(&app, &task, body.clone()),
+        change_priority(&app, &task, body.clone()),
+    );
+
+    // Assert: One change succeeds, the other is a 400 for the unchanged priority
+    let mut statuses = [first.0, second.0];
+    statuses.sort_unstable();
+    assert_eq!(statuses, [200, 400], "{first:?} {second:?}");
+    assert_eq!(
+        priority_changes(&db, &task).await,
+        vec![(
+            "LOW".to_string(),
+            "HIGH".to_string(),
+            None,
+            task.user_id.into_inner()
+        )]
+    );
+    assert_eq!(events.events().len(), 1);
+}
+
+#[tokio::test]
+async fn test_priority_of_another_users_task_cannot_be_changed() {
+    // Negative test: Only the owner, or an admin, may change a task's priority
+    let (app, db, _events) = app_recording_events().await;
+    let task = create_test_task(
+        &db,
+        UserId::new(),
+        &generate_unique_title("other_owner"),
+        None,
+        TaskPriority::Low,
+    )
+    .await;
+    let uri = format!("/tasks/{}/priority", task.id);
+    let body = serde_json::json!({"priority": "High"}).to_string();
+
+    // Act: As another user, then as an admin
+    let stranger = issue_test_token(UserId::new(), chrono::Duration::hours(1));
+    let (status, _) = make_authenticated_request(
+        &app,
+        "POST",
+        &uri,
+        Some(Body::from(body.clone())),
+        &stranger,
+    )
+    .await;
+    let admin = UserId::new();
+    let (admin_status, _) = make_authenticated_request(
+        &app,
+        "POST",
+        &uri,
+        Some(Body::from(body)),
+        &issue_admin_token(admin),
+    )
+    .await;
+
+    // Assert: The admin's change is attributed to the admin
+    assert_eq!(status, 403);
+    assert_eq!(admin_status, 200);
+    assert_eq!(priority_changes(&db, &task).await[0].3, admin.into_inner());
+}
+
+#[tokio::test]
+async fn test_priority_of_missing_task_is_not_found() {
+    // Negative test: An unknown task id
+    let (app, _db) = common::app().await;
+    let token = issue_test_token(UserId::new(), chrono::Duration::hours(1));
+
+    let (status, body) = make_authenticated_request(
+        &app,
+        "POST",
+        &format!("/tasks/{}/priority", Uuid::new_v4()),
+        Some(Body::from(r#"{"priority": "High"}"#)),
+        &token,
+    )
+    .await;
+
+    assert_eq!(status, 404);
+    verify_error_response(&body, "NotFound");
+}
@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use axum::http::HeaderMap;
+use rust_service_template::{
+    api::build_app_router,
+    config::{AppConfig, QuotaConfig},
+    domain::quota::models::Quota,
+};
+
+use super::super::*;
+use crate::common::TestDatabase;
+
+async fn app_with_quotas(quotas: QuotaConfig) -> (Router, TestDatabase) {
+    let (state, db) =
+        common::app_state_with(|config: &mut AppConfig| config.quota_config = quotas).await;
+    (build_app_router(Arc::new(state)).await, db)
+}
+
+/// Create a task for `user_id`, returning the status, the headers and the parsed body
+async fn create_task_for(app: &Router, user_id: UserId) -> (u16, HeaderMap, Value) {
+    let body = serde_json::json!({
+        "title": generate_unique_title("quota"),
+        "user_id": user_id,
+    });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/tasks")
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status().as_u16();
+    let headers = response.headers().clone();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, headers, parse_json_response(&body))
+}
+
+/// Fetch `GET /usage` as `user_id`
+async fn usage_of(app: &Router, user_id: UserId) -> (u16, Value) {
+    let token = issue_test_token(user_id, chrono::Duration::minutes(5));
+    let (status, body) = make_authenticated_request(app, "GET", "/usage", None, &token).await;
+    (status, parse_json_response(&body))
+}
+
+#[tokio::test]
+async fn test_task_creation_warns_past_soft_quota_and_stops_at_hard_quota() {
+    // Objective: Verify the soft quota adds a warning header and the hard quota refuses the
+    // task with QuotaExceeded
+    let (app, _db) = app_with_quotas(QuotaConfig {
+        open_tasks: Quota {
+            soft: Some(1),
+            hard: Some(2),
+        },
+        ..QuotaConfig::default()
+    })
+    .await;
+    let user_id = UserId::new();
+
+    // Act: Create three tasks for the same user
+    let (first, first_headers, _) = create_task_for(&app, user_id).await;
+    let (second, second_headers, _) = create_task_for(&app, user_id).await;
+    let (third, _, third_body) = create_task_for(&app, user_id).await;
+
+    // Assert: Verify only the second is warned about and the third is refused
+    assert_eq!((first, second), (201, 201));
+    assert!(first_headers.get("X-Quota-Warning").is_none());
+    assert_eq!(
+        second_headers["X-Quota-Warning"],
+        "open_tasks; used=2; soft=1"
+    );
+    assert_eq!(third, 403);
+    assert_eq!(third_body["code"], "QuotaExceeded");
+    assert_eq!(third_body["message"], "open_tasks is limited to 2");
+}
+
+#[tokio::test]
+async fn test_concurrent_creates_stop_at_hard_quota() {
+    // Negative test: Creates racing for the last task under a hard quota are counted one
+    // after another, so only one of them is created
+    let (app, _db) = app_with_quotas(QuotaConfig {
+        open_tasks: Quota {
+            soft: None,
+            hard: Some(1),
+        },
+        ..QuotaConfig::default()
+    })
+    .await;
+    let user_id = UserId::new();
+
+    // Act: Create five tasks for the same user at once
+    let mut creates = tokio::task::JoinSet::new();
+    for _ in 0..5 {
+        let app = app.clone();
+        creates.spawn(async move { create_task_for(&app, user_id).await.0 });
+    }
+    let mut statuses = creates.join_all().await;
+
+    // Assert: Verify one is created and the others are refused
+    statuses.sort_unstable();
+    assert_eq!(statuses, [201, 403, 403, 403, 403]);
+    let (_, usage) = usage_of(&app, user_id).await;
+    assert_eq!(usage["open_tasks"]["used"], 1, "{usage}");
+}
+
+#[tokio::test]
+async fn test_task_quotas_apply_per_user() {
+    // Objective: Verify one user's tasks do not count against another user's quota
+    let (app, _db) = app_with_quotas(QuotaConfig {
+        monthly_tasks: Quota {
+            soft: None,
+            hard: Some(1),
+        },
+        ..QuotaConfig::default()
+    })
+    .await;
+    let (user_a, user_b) = (UserId::new(), UserId::new());
+
+    // Act: Create a task for each user, then a second one for the first
+    let (a_status, _, _) = create_task_for(&app, user_a).await;
+    let (b_status, _, _) = create_task_for(&app, user_b).await;
+    let (again_status, _, _) = create_task_for(&app, user_a).await;
+
+    // Assert: Verify only the second task of the first user is refused
+    assert_eq!((a_status, b_status, again_status), (201, 201, 403));
+}
+
+#[tokio::test]
+async fn test_usage_reports_counts_and_limits() {
+    // Objective: Verify /usage reports the caller's counts next to the configured limits
+    let (app, _db) = app_with_quotas(QuotaConfig {
+        open_tasks: Quota {
+            soft: Some(80),
+            hard: Some(100),
+        },
+        ..QuotaConfig::default()
+    })
+    .await;
+    let user_id = UserId::new();
+    create_task_for(&app, user_id).await;
+    create_task_for(&app, user_id).await;
+    create_task_for(&app, UserId::new()).await;
+
+    // Act: Fetch the user's usage
+    let (status, usage) = usage_of(&app, user_id).await;
+
+    // Assert: Verify the counts and the limits
+    assert_eq!(status, 200, "{usage}");
+    assert_eq!(
+        usage["open_tasks"],
+        serde_json::json!({ "used": 2, "soft": 80, "hard": 100 })
+    );
+    assert_eq!(
+        usage["tasks_this_month"],
+        serde_json::json!({ "used": 2, "soft": null, "hard": null })
+    );
+    assert_eq!(usage["webhooks"]["used"], 0);
+}
+
+#[tokio::test]
+async fn test_usage_of_another_user_is_forbidden() {
+    // Negative test: A user cannot read another user's usage without the admin role
+    let (app, _db) = app_with_quotas(QuotaConfig::default()).await;
+    let token = issue_test_token(UserId::new(), chrono::Duration::minutes(5));
+    let uri = format!("/usage?user_id={}", UserId::new());
+
+    // Act: Ask for another user's usage, as that user's peer and as an admin
+    let (peer_status, body) = make_authenticated_request(&app, "GET", &uri, None, &token).await;
+    let admin_token = issue_admin_token(UserId::new());
+    let (admin_status, _) = make_authenticated_request(&app, "GET", &uri, None, &admin_token).await;
+
+    // Assert: Verify 403 Forbidden for the peer only
+    assert_eq!(peer_status, 403);
+    verify_error_response(&body, "Forbidden");
+    assert_eq!(admin_status, 200);
+}
+
+#[tokio::test]
+async fn test_quotas_are_not_enforced_with_the_feature_off() {
+    // Objective: Verify turning the enforce_quotas flag off lets tasks past the hard quota
+    let (state, _db) = common::app_state_with(|config: &mut AppConfig| {
+        config.quota_config.open_tasks = Quota {
+            soft: None,
+            hard: Some(1),
+        };
+        config.features.insert("enforce_quotas".to_string(), false);
+    })
+    .await;
+    let app = build_app_router(Arc::new(state)).await;
+    let user_id = UserId::new();
+
+    // Act: Create two tasks for the same user
+    let (first, _, _) = create_task_for(&app, user_id).await;
+    let (second, second_headers, _) = create_task_for(&app, user_id).await;
+
+    // Assert: Verify both are created without a warning
+    assert_eq!((first, second), (201, 201));
+    assert!(second_headers.get("X-Quota-Warning").is_none());
+}
@@ -1,25 +1,6 @@
 use super::super::*;
 use rust_service_template::domain::task::models::{TaskPriority, TaskStatus};
 
-// Helper functions to convert domain enums to database string representations
-fn status_to_db_string(status: TaskStatus) -> &'static str {
-    match status {
-        TaskStatus::Pending => "PENDING",
-        TaskStatus::InProgress => "IN_PROGRESS",
-        TaskStatus::Completed => "COMPLETED",
-        TaskStatus::Cancelled => "CANCELLED",
-    }
-}
-
-fn priority_to_db_string(priority: TaskPriority) -> &'static str {
-    match priority {
-        TaskPriority::Low => "LOW",
-        TaskPriority::Medium => "MEDIUM",
-        TaskPriority::High => "HIGH",
-        TaskPriority::Critical => "CRITICAL",
-    }
-}
-
 #[tokio::test]
 async fn test_get_task_returns_200_for_existing_task() {
     // Objective: Verify retrieving an existing task returns correct data
@@ -49,7 +30,7 @@ async fn test_get_task_returns_200_for_existing_task() {
 async fn test_get_task_returns_404_for_non_existent_task() {
     // Objective: Verify non-existent task returns 404
     // Negative test: GET request with invalid ID should fail
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
 
     // Arrange: Use a random UUID that doesn't exist in DB
     let fake_id = uuid::Uuid::new_v4();
@@ -70,7 +51,7 @@ async fn test_get_task_returns_404_for_non_existent_task() {
 async fn test_get_task_returns_400_for_invalid_uuid_format() {
     // Objective: Verify invalid UUID format is rejected
     // Negative test: Malformed UUID should return 400
-    let (app, _) = common::app().await;
+    let (app, _db) = common::app().await;
 
     // Arrange: Use invalid UUID format
     let invalid_id = "not-a-uuid";
@@ -85,6 +66,118 @@ async fn test_get_task_returns_400_for_invalid_uuid_format() {
         "Should return 400 Bad Request for invalid UUID"
     );
     verify_error_response(&body_bytes, "BadRequest");
+    assert_eq!(
+        parse_json_response(&body_bytes)["message"],
+        "Path parameter `id` must be a UUID or `latest`, got \"not-a-uuid\"",
+        "Error should name the parameter and echo the value"
+    );
+}
+
+#[tokio::test]
+async fn test_get_task_truncates_long_invalid_id_in_error() {
+    // Objective: Verify a long malformed ID is not echoed in full
+    // Negative test: The message repeats at most 40 characters of the value
+    let (app, _db) = common::app().await;
+
+    // Arrange: An ID far longer than the echoed prefix
+    let invalid_id = "x".repeat(100);
+
+    // Act
+    let (status, body_bytes) =
+        make_request(&app, "GET", &format!("/tasks/{invalid_id}"), None).await;
+
+    // Assert
+    assert_eq!(status, 400);
+    assert_eq!(
+        parse_json_response(&body_bytes)["message"],
+        format!(
+            "Path parameter `id` must be a UUID or `latest`, got \"{}…\"",
+            "x".repeat(40)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_get_latest_task_returns_newest_task_of_user() {
+    // Objective: Verify `latest` resolves to the user's most recently created task
+    let (app, pool) = common::app().await;
+    let user_id = UserId::new();
+
+    // Arrange: Two tasks of the user, and a newer one of someone else
+    create_test_task(
+        &pool,
+        user_id,
+        &generate_unique_title("older"),
+        None,
+        TaskPriority::Low,
+    )
+    .await;
+    let newest = create_test_task(
+        &pool,
+        user_id,
+        &generate_unique_title("newest"),
+        None,
+        TaskPriority::Low,
+    )
+    .await;
+    create_test_task(
+        &pool,
+        UserId::new(),
+        &generate_unique_title("other_user"),
+        None,
+        TaskPriority::Low,
+    )
+    .await;
+
+    // Act
+    let (status, body_bytes) = make_request(
+        &app,
+        "GET",
+        &format!("/tasks/latest?user_id={user_id}"),
+        None,
+    )
+    .await;
+
+    // Assert
+    assert_eq!(status, 200);
+    assert_eq!(
+        parse_json_response(&body_bytes)["id"],
+        newest.id.to_string()
+    );
+}
+
+#[tokio::test]
+async fn test_get_latest_task_returns_404_for_user_without_tasks() {
+    // Negative test: `latest` of a user with no tasks is not found
+    let (app, _db) = common::app().await;
+
+    // Act
+    let (status, body_bytes) = make_request(
+        &app,
+        "GET",
+        &format!("/tasks/latest?user_id={}", UserId::new()),
+        None,
+    )
+    .await;
+
+    // Assert
+    assert_eq!(status, 404);
+    verify_error_response(&body_bytes, "NotFound");
+}
+
+#[tokio::test]
+async fn test_get_latest_task_returns_400_without_user_id() {
+    // Negative test: `latest` needs the user it refers to
+    let (app, _db) = common::app().await;
+
+    // Act
+    let (status, body_bytes) = make_request(&app, "GET", "/tasks/latest", None).await;
+
+    // Assert
+    assert_eq!(status, 400);
+    let body = parse_json_response(&body_bytes);
+    assert_eq!(body["code"], "ValidationError");
+    assert_eq!(body["errors"][0]["field"], "user_id");
 }
 
 #[tokio::test]
@@ -152,39 +245,29 @@ async fn test_get_task_returns_200_for_completed_task() {
     let user_id = UserId::new();
     let title = generate_unique_title("completed_task");
 
-    // Arrange: Create a completed task directly in DB
-    let task_id = uuid::Uuid::new_v4();
+    // Arrange: Create a completed task
     let completed_at = chrono::Utc::now();
-    sqlx::query(
-        r#"
-        INSERT INTO tasks (id, user_id, title, description, status, priority, created_at, updated_at, completed_at)
-        VALUES ($1, $2, $3, $4, $5::task_status, $6::task_priority, $7, $8, $9)
-        "#,
-    )
-    .bind(task_id)
-    .bind(user_id.into_inner())
-    .bind(title)
-    .bind::<Option<String>>(None)
-    .bind(status_to_db_string(TaskStatus::Completed))
-    .bind(priority_to_db_string(TaskPriority::Medium))
-    .bind(completed_at)
-    .bind(completed_at)
-    .bind(completed_at)
-    .execute(&*pool)
-    .await
-    .unwrap();
+    let task = TaskFixture::new(user_id)
+        .title(&title)
+        .status(TaskStatus::Completed)
+        .priority(TaskPriority::Medium)
+        .completed_at(completed_at)
+        .insert(&pool)
+        .await;
 
     // Act: Send GET request
     let (status, body_bytes) =
-        make_request(&app, "GET", &format!("/tasks/{}", task_id), None).await;
+        make_request(&app, "GET", &format!("/tasks/{}", task.id), None).await;
 
     // Assert: Verify 200 OK with completed status and completed_at
     assert_eq!(status, 200, "Should return 200 OK for completed task");
     let body: Value = parse_json_response(&body_bytes);
     assert_eq!(body["status"], "Completed", "Status should be Completed");
     assert!(
-        body["completed_at"].is_string(),
-        "completed_at should be a string timestamp"
+        body["completed_at"]
+            .as_str()
+            .is_some_and(|timestamp| chrono::DateTime::parse_from_rfc3339(timestamp).is_ok()),
+        "completed_at should be an RFC 3339 timestamp"
     );
     assert!(
         body.get("completed_at").is_some(),
@@ -200,31 +283,17 @@ async fn test_get_task_returns_200_for_task_with_in_progress_status() {
     let user_id = UserId::new();
     let title = generate_unique_title("in_progress");
 
-    // Arrange: Create InProgress task directly in DB
-    let task_id = uuid::Uuid::new_v4();
-    let now = chrono::Utc::now();
-    sqlx::query(
-        r#"
-        INSERT INTO tasks (id, user_id, title, description, status, priority, created_at, updated_at, completed_at)
-        VALUES ($1, $2, $3, $4, $5::task_status, $6::task_priority, $7, $8, $9)
-        "#,
-    )
-    .bind(task_id)
-    .bind(user_id.into_inner())
-    .bind(title)
-    .bind::<Option<String>>(None)
-    .bind(status_to_db_string(TaskStatus::InProgress))
-    .bind(priority_to_db_string(TaskPriority::High))
-    .bind(now)
-    .bind(now)
-    .bind::<Option<chrono::DateTime<chrono::Utc>>>(None)
-    .execute(&*pool)
-    .await
-    .unwrap();
+    // Arrange: Create InProgress task
+    let task = TaskFixture::new(user_id)
+        .title(&title)
+        .status(TaskStatus::InProgress)
+        .priority(TaskPriority::High)
+        .insert(&pool)
+        .await;
 
     // Act: Send GET request
     let (status, body_bytes) =
-        make_request(&app, "GET", &format!("/tasks/{}", task_id), None).await;
+        make_request(&app, "GET", &format!("/tasks/{}", task.id), None).await;
 
     // Assert: Verify 200 OK with InProgress status
     assert_eq!(status, 200, "Should return 200 OK");
@@ -241,31 +310,17 @@ async fn test_get_task_returns_200_for_task_with_cancelled_status() {
     let user_id = UserId::new();
     let title = generate_unique_title("cancelled");
 
-    // Arrange: Create Cancelled task directly in DB
-    let task_id = uuid::Uuid::new_v4();
-    let now = chrono::Utc::now();
-    sqlx::query(
-        r#"
-        INSERT INTO tasks (id, user_id, title, description, status, priority, created_at, updated_at, completed_at)
-        VALUES ($1, $2, $3, $4, $5::task_status, $6::task_priority, $7, $8, $9)
-        "#,
-    )
-    .bind(task_id)
-    .bind(user_id.into_inner())
-    .bind(title)
-    .bind::<Option<String>>(None)
-    .bind(status_to_db_string(TaskStatus::Cancelled))
-    .bind(priority_to_db_string(TaskPriority::Low))
-    .bind(now)
-    .bind(now)
-    .bind::<Option<chrono::DateTime<chrono::Utc>>>(None)
-    .execute(&*pool)
-    .await
-    .unwrap();
+    // Arrange: Create Cancelled task
+    let task = TaskFixture::new(user_id)
+        .title(&title)
+        .status(TaskStatus::Cancelled)
+        .priority(TaskPriority::Low)
+        .insert(&pool)
+        .await;
 
     // Act: Send GET request
     let (status, body_bytes) =
-        make_request(&app, "GET", &format!("/tasks/{}", task_id), None).await;
+        make_request(&app, "GET", &format!("/tasks/{}", task.id), None).await;
 
     // Assert: Verify 200 OK with Cancelled status
     assert_eq!(status, 200, "Should return 200 OK");
@@ -0,0 +1,163 @@
+use chrono::{DateTime, TimeZone, Utc};
+use rust_service_template::domain::task::models::TaskStatus;
+
+use super::super::*;
+
+fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 3, day, hour, minute, 0).unwrap()
+}
+
+/// Fetch `user_id`'s stats with `query` appended, as that user
+async fn get_stats(app: &Router, user_id: UserId, query: &str) -> (u16, Value) {
+    let token = issue_test_token(user_id, chrono::Duration::minutes(5));
+    let (status, body_bytes) = make_authenticated_request(
+        app,
+        "GET",
+        &format!("/users/{user_id}/stats?{query}"),
+        None,
+        &token,
+    )
+    .await;
+    (status, parse_json_response(&body_bytes))
+}
+
+/// `(created, completed)` of every day in a stats response
+fn counts(body: &Value) -> Vec<(i64, i64)> {
+    body["days"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|day| {
+            (
+                day["created"].as_i64().unwrap(),
+                day["completed"].as_i64().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_stats_count_created_and_completed_tasks_per_day() {
+    // Objective: Verify each day of the range gets its counts, empty days included, and
+    // other users' tasks and days outside the range are left out
+    let (app, db) = common::app().await;
+    let user_id = UserId::new();
+
+    // Arrange: Two tasks created on 1 March, one completed on 3 March, plus noise
+    TaskFixture::new(user_id)
+        .created_at(at(1, 9, 0))
+        .insert(&db)
+        .await;
+    TaskFixture::new(user_id)
+        .created_at(at(1, 15, 0))
+        .status(TaskStatus::Completed)
+        .completed_at(at(3, 10, 0))
+        .insert(&db)
+        .await;
+    TaskFixture::new(user_id)
+        .created_at(at(5, 9, 0))
+        .insert(&db)
+        .await;
+    TaskFixture::new(UserId::new())
+        .created_at(at(2, 9, 0))
+        .insert(&db)
+        .await;
+
+    // Act: Ask for 1 to 3 March
+    let (status, body) = get_stats(&app, user_id, "from=2025-03-01&to=2025-03-03").await;
+
+    // Assert: Verify a row per day, counted in UTC
+    assert_eq!(status, 200, "Should return the stats: {body}");
+    assert_eq!(body["from"], "2025-03-01");
+    assert_eq!(body["to"], "2025-03-03");
+    assert_eq!(body["tz"], "UTC");
+    assert_eq!(body["days"][1]["date"], "2025-03-02");
+    assert_eq!(counts(&body), [(2, 0), (0, 0), (0, 1)]);
+}
+
+#[tokio::test]
+async fn test_stats_truncate_days_in_the_requested_time_zone() {
+    // Objective: Verify days start at local midnight: 23:30 UTC on 1 March is already
+    // 2 March in Warsaw, and 00:30 Warsaw on 1 March is still February in UTC
+    let (app, db) = common::app().await;
+    let user_id = UserId::new();
+
+    // Arrange
+    TaskFixture::new(user_id)
+        .created_at(at(1, 23, 30))
+        .insert(&db)
+        .await;
+    TaskFixture::new(user_id)
+        .created_at(Utc.with_ymd_and_hms(2025, 2, 28, 23, 30, 0).unwrap())
+        .insert(&db)
+        .await;
+
+    // Act: The same range in UTC and in Warsaw
+    let (_, utc) = get_stats(&app, user_id, "from=2025-03-01&to=2025-03-02").await;
+    let (status, warsaw) = get_stats(
+        &app,
+        user_id,
+        "from=2025-03-01&to=2025-03-02&tz=Europe/Warsaw",
+    )
+    .await;
+
+    // Assert
+    assert_eq!(status, 200, "Should return the stats: {warsaw}");
+    assert_eq!(counts(&utc), [(1, 0), (0, 0)]);
+    assert_eq!(warsaw["tz"], "Europe/Warsaw");
+    assert_eq!(counts(&warsaw), [(1, 0), (1, 0)]);
+}
+
+#[tokio::test]
+async fn test_stats_reject_invalid_ranges() {
+    // Negative test: Reversed, overlong and malformed ranges, and unknown zones
+    let (app, _db) = common::app().await;
+    let user_id = UserId::new();
+
+    for (query, code) in [
+        ("from=2025-03-02&to=2025-03-01", "ValidationError"),
+        ("from=2024-01-01&to=2025-01-01", "ValidationError"),
+        (
+            "from=2025-03-01&to=2025-03-02&tz=Mars/Olympus_Mons",
+            "ValidationError",
+        ),
+        ("from=2025-03-01", "BadRequest"),
+        ("from=yesterday&to=2025-03-01", "BadRequest"),
+    ] {
+        // Act
+        let (status, body) = get_stats(&app, user_id, query).await;
+
+        // Assert: Verify 400 Bad Request
+        assert_eq!(status, 400, "{query}: {body}");
+        assert_eq!(body["code"], code, "{query}");
+    }
+
+    // A leap year is the longest range accepted
+    let (status, body) = get_stats(&app, user_id, "from=2024-01-01&to=2024-12-31").await;
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(body["days"].as_array().unwrap().len(), 366);
+}
+
+#[tokio::test]
+async fn test_stats_of_another_user_return_403() {
+    // Negative test: Stats are as private as the tasks they count
+    let (app, _db) = common::app().await;
+    let token = issue_test_token(UserId::new(), chrono::Duration::minutes(5));
+
+    // Act
+    let (status, body_bytes) = make_authenticated_request(
+        &app,
+        "GET",
+        &format!(
+            "/users/{}/stats?from=2025-03-01&to=2025-03-01",
+            UserId::new()
+        ),
+        None,
+        &token,
+    )
+    .await;
+
+    // Assert
+    assert_eq!(status, 403);
+    verify_error_response(&body_bytes, "Forbidden");
+}
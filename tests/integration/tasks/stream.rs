@@ -0,0 +1,303 @@
+use std::{sync::Arc, time::Duration};
+
+use rust_service_template::{
+    api::build_app_router,
+    common::TenantId,
+    config::{AppConfig, TenancyMode},
+    domain::task::models::{TaskChange, TaskEventType},
+    infrastructure::task_notifications::spawn_task_change_listener,
+};
+use tokio::{sync::broadcast, task::JoinHandle};
+
+use super::super::*;
+use crate::common::TestDatabase;
+
+/// Tasks created before giving up on a change being delivered
+///
+/// The listener connects in the background, so the first changes may happen before it is
+/// listening; creating tasks until one arrives avoids a fixed sleep.
+const ATTEMPTS: usize = 50;
+const WAIT: Duration = Duration::from_millis(200);
+
+/// Router and database with a task change listener running for the router's state
+async fn streaming_app() -> (Router, TestDatabase, JoinHandle<()>) {
+    streaming_app_with(|_| {}).await
+}
+
+/// [`streaming_app`] with `configure` applied to the test configuration
+async fn streaming_app_with(
+    configure: impl FnOnce(&mut AppConfig),
+) -> (Router, TestDatabase, JoinHandle<()>) {
+    let (state, db) = common::app_state_with(configure).await;
+    let listener = spawn_task_change_listener(db.clone(), state.task_changes.clone());
+
+    (build_app_router(Arc::new(state)).await, db, listener)
+}
+
+/// Read `task_change` events from an SSE body until none arrives for [`WAIT`]
+async fn read_task_changes(body: &mut Body) -> Vec<Value> {
+    let mut changes = Vec::new();
+    while let Ok(Some(Ok(frame))) = tokio::time::timeout(WAIT, body.frame()).await {
+        let Ok(data) = frame.into_data() else {
+            continue;
+        };
+        let text = String::from_utf8(data.to_vec()).unwrap();
+        if text.contains("event: task_change") {
+            let data = text
+                .lines()
+                .find_map(|line| line.strip_prefix("data: "))
+                .expect("task_change event should carry data");
+            changes.push(serde_json::from_str(data).unwrap());
+        }
+    }
+    changes
+}
+
+/// Create tasks for a new user until `receiver` reports the creation of one of them
+async fn create_until_broadcast(
+    pool: &sqlx::PgPool,
+    receiver: &mut broadcast::Receiver<TaskChange>,
+) -> TaskChange {
+    let user_id = UserId::new();
+    for _ in 0..ATTEMPTS {
+        let task = create_test_task(
+            pool,
+            user_id,
+            &generate_unique_title("stream"),
+            None,
+            TaskPriority::Medium,
+        )
+        .await;
+
+        while let Ok(Ok(change)) = tokio::time::timeout(WAIT, receiver.recv()).await {
+            if change.task_id == task.id {
+                return change;
+            }
+        }
+    }
+    panic!("No task change was broadcast after {ATTEMPTS} attempts");
+}
+
+#[tokio::test]
+async fn test_stream_delivers_changes_to_own_tasks_only() {
+    // Objective: Verify the stream emits committed changes for the requested user only
+    // Positive test: The user's own task is delivered, another user's task is filtered out
+    let (app, pool, listener) = streaming_app().await;
+    let user_id = UserId::new();
+    let other_user_id = UserId::new();
+
+    // Arrange: Open the stream
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/tasks/stream?user_id={user_id}"))
+                .header(
+                    "Authorization",
+                    format!(
+                        "Bearer {}",
+                        issue_test_token(user_id, chrono::Duration::minutes(5))
+                    ),
+                )
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200, "Stream should open with 200 OK");
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/event-stream",
+        "Stream should be served as Server-Sent Events"
+    );
+    let mut body = response.into_body();
+
+    // Act: Create another user's task, then one of the user's own, until one is streamed
+    let mut delivered = None;
+    for _ in 0..ATTEMPTS {
+        create_test_task(
+            &pool,
+            other_user_id,
+            &generate_unique_title("stream_other"),
+            None,
+            TaskPriority::Low,
+        )
+        .await;
+        let task = create_test_task(
+            &pool,
+            user_id,
+            &generate_unique_title("stream_own"),
+            None,
+            TaskPriority::Medium,
+        )
+        .await;
+
+        let changes = read_task_changes(&mut body).await;
+        // Assert: Only the user's changes are streamed
+        for change in &changes {
+            assert_eq!(
+                change["user_id"],
+                user_id.to_string(),
+                "Stream should filter out other users' changes: {change}"
+            );
+        }
+        if let Some(change) = changes
+            .into_iter()
+            .find(|change| change["task_id"] == task.id.to_string())
+        {
+            delivered = Some(change);
+            break;
+        }
+    }
+    listener.abort();
+
+    // Assert: The creation was delivered with its type
+    let change = delivered.expect("The user's task change should be streamed");
+    assert_eq!(
+        change["event_type"], "Created",
+        "Event type should be Created"
+    );
+}
+
+#[tokio::test]
+async fn test_stream_delivers_changes_of_own_tenant_only() {
+    // Objective: Verify a stream opened for a tenant filters out the same user's tasks
+    // in other tenants
+    let (app, pool, listener) =
+        streaming_app_with(|config| config.tenancy = TenancyMode::Multi).await;
+    let (tenant, other_tenant) = (TenantId::new(), TenantId::new());
+    let user_id = UserId::new();
+
+    // Arrange: Open the stream as the tenant
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/tasks/stream?user_id={user_id}"))
+                .header("X-Tenant-Id", tenant.to_string())
+                .header(
+                    "Authorization",
+                    format!("Bearer {}", issue_tenant_token(user_id, tenant)),
+                )
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200, "Stream should open with 200 OK");
+    let mut body = response.into_body();
+
+    // Act: Create a task in the other tenant, then one in the tenant, until one is streamed
+    let mut delivered = false;
+    for _ in 0..ATTEMPTS {
+        let other = TaskFixture::new(user_id)
+            .tenant(other_tenant)
+            .insert(&pool)
+            .await;
+        let own = TaskFixture::new(user_id).tenant(tenant).insert(&pool).await;
+
+        let changes = read_task_changes(&mut body).await;
+        // Assert: The other tenant's change is never streamed
+        assert!(
+            changes
+                .iter()
+                .all(|change| change["task_id"] != other.id.to_string()),
+            "Stream should filter out other tenants' changes: {changes:?}"
+        );
+        if changes
+            .iter()
+            .any(|change| change["task_id"] == own.id.to_string())
+        {
+            delivered = true;
+            break;
+        }
+    }
+    listener.abort();
+
+    assert!(delivered, "The tenant's task change should be streamed");
+}
+
+#[tokio::test]
+async fn test_stream_returns_400_missing_user_id() {
+    // Objective: Verify a stream cannot be opened without naming the user
+    // Negative test: Missing user_id should return 400 before the stream starts
+    let (app, _db) = common::app().await;
+    let token = issue_test_token(UserId::new(), chrono::Duration::minutes(5));
+
+    // Act: Open the stream without user_id
+    let (status, body_bytes) =
+        make_authenticated_request(&app, "GET", "/tasks/stream", None, &token).await;
+
+    // Assert: Verify 400 Bad Request
+    assert_eq!(
+        status, 400,
+        "Should return 400 Bad Request for missing user_id"
+    );
+    verify_error_response(&body_bytes, "BadRequest");
+}
+
+#[tokio::test]
+async fn test_stream_of_another_user_is_forbidden() {
+    // Negative test: A user cannot follow another user's task changes, while an admin can
+    let (app, _db) = common::app().await;
+    let (user_a, user_b) = (UserId::new(), UserId::new());
+    let uri = format!("/tasks/stream?user_id={user_a}");
+
+    // Act: Open A's stream without a token, with B's token and with an admin's
+    let (anonymous_status, _) = make_request(&app, "GET", &uri, None).await;
+    let token = issue_test_token(user_b, chrono::Duration::minutes(5));
+    let (peer_status, body_bytes) =
+        make_authenticated_request(&app, "GET", &uri, None, &token).await;
+    let admin = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(&uri)
+                .header(
+                    "Authorization",
+                    format!("Bearer {}", issue_admin_token(UserId::new())),
+                )
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    // Assert: Verify 401 without a token, 403 for B and a stream for the admin
+    assert_eq!(anonymous_status, 401);
+    assert_eq!(peer_status, 403);
+    verify_error_response(&body_bytes, "Forbidden");
+    assert_eq!(admin.status(), 200);
+}
+
+#[tokio::test]
+async fn test_listener_reconnects_after_connection_loss() {
+    // Objective: Verify changes keep flowing after the listening connection is terminated
+    // Positive test: The listener reconnects on its own and broadcasts later changes
+    let (state, _db) = common::app_state().await;
+    let pool = state
+        .db_pool
+        .clone()
+        .expect("Integration tests run against Postgres");
+    let mut receiver = state.task_changes.subscribe();
+    let listener = spawn_task_change_listener(pool.clone(), state.task_changes.clone());
+
+    // Arrange: Wait until the listener delivers changes
+    let change = create_until_broadcast(&pool, &mut receiver).await;
+    assert_eq!(change.event_type, TaskEventType::Created);
+
+    // Act: Terminate every listening connection
+    let terminated: Vec<bool> = sqlx::query_scalar(
+        "SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE query LIKE 'LISTEN%'",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert!(
+        !terminated.is_empty(),
+        "The listener's connection should have been found"
+    );
+
+    // Assert: Changes are broadcast again once the listener has reconnected
+    let change = create_until_broadcast(&pool, &mut receiver).await;
+    assert_eq!(change.event_type, TaskEventType::Created);
+    listener.abort();
+}
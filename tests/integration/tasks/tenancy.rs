@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use rust_service_template::{
+    api::build_app_router,
+    common::TenantId,
+    config::{AppConfig, TenancyMode},
+};
+
+use super::super::*;
+use crate::common::TestDatabase;
+
+async fn app_with_tenancy(tenancy: TenancyMode) -> (Router, TestDatabase) {
+    let (state, db) =
+        common::app_state_with(|config: &mut AppConfig| config.tenancy = tenancy).await;
+    (build_app_router(Arc::new(state)).await, db)
+}
+
+/// Send a request naming `tenant` in the `X-Tenant-Id` header, when given
+async fn tenant_request(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    tenant: Option<&str>,
+    body: Option<Value>,
+) -> (u16, Value) {
+    let mut builder = Request::builder().method(method).uri(uri);
+    if let Some(tenant) = tenant {
+        builder = builder.header("X-Tenant-Id", tenant);
+    }
+    let (status, body) =
+        send_request(app, builder, body.map(|body| Body::from(body.to_string()))).await;
+    (status, parse_json_response(&body))
+}
+
+/// Create a task for `user_id` through the API, as `tenant`
+async fn create_task_as(app: &Router, tenant: TenantId, user_id: UserId) -> Value {
+    let (status, body) = tenant_request(
+        app,
+        "POST",
+        "/tasks",
+        Some(&tenant.to_string()),
+        Some(serde_json::json!({
+            "title": generate_unique_title("tenant"),
+            "user_id": user_id,
+        })),
+    )
+    .await;
+    assert_eq!(status, 201, "Task should be created: {body}");
+    body
+}
+
+/// List `user_id`'s tasks as that user of `tenant`, also named in the header
+async fn list_tasks_as(app: &Router, tenant: TenantId, user_id: UserId) -> Vec<Value> {
+    let token = issue_tenant_token(user_id, tenant);
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/users/{user_id}/tasks"))
+        .header("Authorization", format!("Bearer {token}"))
+        .header("X-Tenant-Id", tenant.to_string());
+    let (status, body) = send_request(app, request, None).await;
+    assert_eq!(status, 200);
+    parse_json_response(&body).as_array().unwrap().clone()
+}
+
+#[tokio::test]
+async fn test_task_of_another_tenant_is_not_found() {
+    // Objective: Verify a task created by tenant A is invisible to tenant B
+    // Negative test: Tenant B gets the same 404 as for a task that does not exist
+    let (app, _db) = app_with_tenancy(TenancyMode::Multi).await;
+    let (tenant_a, tenant_b) = (TenantId::new(), TenantId::new());
+    let user_id = UserId::new();
+    let task = create_task_as(&app, tenant_a, user_id).await;
+    let uri = format!("/tasks/{}", task["id"].as_str().unwrap());
+
+    // Act: Fetch the task and list the user's tasks as each tenant
+    let (own_status, _) =
+        tenant_request(&app, "GET", &uri, Some(&tenant_a.to_string()), None).await;
+    let (other_status, other_body) =
+        tenant_request(&app, "GET", &uri, Some(&tenant_b.to_string()), None).await;
+    let own_list = list_tasks_as(&app, tenant_a, user_id).await;
+    let other_list = list_tasks_as(&app, tenant_b, user_id).await;
+
+    // Assert: Verify only tenant A sees the task
+    assert_eq!(own_status, 200);
+    assert_eq!(other_status, 404, "Another tenant's task should be hidden");
+    assert_eq!(other_body["code"], "NotFound");
+    assert_eq!(own_list.len(), 1);
+    assert!(other_list.is_empty());
+}
+
+#[tokio::test]
+async fn test_tasks_are_stored_in_the_requesting_tenant() {
+    // Objective: Verify the stored row carries the tenant named by the request
+    let (app, db) = app_with_tenancy(TenancyMode::Multi).await;
+    let tenant = TenantId::new();
+
+    // Act: Create a task as the tenant
+    let task = create_task_as(&app, tenant, UserId::new()).await;
+
+    // Assert: Verify the row's tenant
+    let stored: Uuid = sqlx::query_scalar("SELECT tenant_id FROM tasks WHERE id = $1")
+        .bind(task["id"].as_str().unwrap().parse::<Uuid>().unwrap())
+        .fetch_one(&*db)
+        .await
+        .unwrap();
+    assert_eq!(stored, tenant.into_inner());
+}
+
+#[tokio::test]
+async fn test_missing_or_malformed_tenant_header_is_rejected() {
+    // Negative test: In multi-tenant mode a request must name a valid tenant
+    let (app, _db) = app_with_tenancy(TenancyMode::Multi).await;
+    let uri = format!("/tasks/{}", Uuid::new_v4());
+
+    // Act: Fetch a task without a tenant and with a malformed one
+    let (missing_status, missing) = tenant_request(&app, "GET", &uri, None, None).await;
+    let (malformed_status, malformed) =
+        tenant_request(&app, "GET", &uri, Some("tenant-a"), None).await;
+
+    // Assert: Verify both are rejected with a message naming the header
+    assert_eq!(missing_status, 400);
+    assert_eq!(missing["code"], "BadRequest");
+    assert!(missing["message"].as_str().unwrap().contains("x-tenant-id"));
+    assert_eq!(malformed_status, 400);
+    assert!(malformed["message"].as_str().unwrap().contains("UUID"));
+}
+
+#[tokio::test]
+async fn test_single_tenant_mode_ignores_tenant_header() {
+    // Objective: Verify single-tenant services keep serving every task from the default
+    // tenant, whatever tenant a request names
+    let (app, _db) = app_with_tenancy(TenancyMode::Single).await;
+    let task = create_task_as(&app, TenantId::new(), UserId::new()).await;
+    let uri = format!("/tasks/{}", task["id"].as_str().unwrap());
+
+    // Act: Fetch the task naming another tenant, and naming none
+    let (other_status, _) =
+        tenant_request(&app, "GET", &uri, Some(&TenantId::new().to_string()), None).await;
+    let (none_status, _) = tenant_request(&app, "GET", &uri, None, None).await;
+
+    // Assert: Verify both find it
+    assert_eq!(other_status, 200);
+    assert_eq!(none_status, 200);
+}
+
+#[tokio::test]
+async fn test_token_tenant_claim_takes_precedence_over_header() {
+    // Objective: Verify a token's tenant claim decides the tenant, and a header naming
+    // another tenant is refused
+    let (app, _db) = app_with_tenancy(TenancyMode::Multi).await;
+    let (tenant_a, tenant_b) = (TenantId::new(), TenantId::new());
+    let user_id = UserId::new();
+    create_task_as(&app, tenant_a, user_id).await;
+    let token = issue_tenant_token(user_id, tenant_a);
+    let uri = format!("/users/{user_id}/tasks");
+
+    // Act: List the user's tasks with the token alone, and with tenant B's header
+    let (status, body) = make_authenticated_request(&app, "GET", &uri, None, &token).await;
+    let request = Request::builder()
+        .method("GET")
+        .uri(&uri)
+        .header("Authorization", format!("Bearer {token}"))
+        .header("X-Tenant-Id", tenant_b.to_string());
+    let (mismatch_status, mismatch_body) = send_request(&app, request, None).await;
+
+    // Assert: Verify the claim's tenant is used and the mismatch is forbidden
+    assert_eq!(status, 200);
+    assert_eq!(parse_json_response(&body).as_array().unwrap().len(), 1);
+    assert_eq!(mismatch_status, 403);
+    verify_error_response(&mismatch_body, "Forbidden");
+}
+
+#[tokio::test]
+async fn test_token_without_tenant_claim_is_forbidden() {
+    // Objective: Verify a token without a tenant claim cannot pick its tenant with the header
+    // Negative test: The header is only trusted on requests without a token
+    let (app, _db) = app_with_tenancy(TenancyMode::Multi).await;
+    let foreign_tenant = TenantId::new();
+    let user_id = UserId::new();
+    create_task_as(&app, foreign_tenant, user_id).await;
+    let token = issue_test_token(user_id, chrono::Duration::minutes(5));
+    let uri = format!("/users/{user_id}/tasks");
+
+    // Act: List the user's tasks with the token and the foreign tenant's header, and alone
+    let request = Request::builder()
+        .method("GET")
+        .uri(&uri)
+        .header("Authorization", format!("Bearer {token}"))
+        .header("X-Tenant-Id", foreign_tenant.to_string());
+    let (status, body) = send_request(&app, request, None).await;
+    let (bare_status, _) = make_authenticated_request(&app, "GET", &uri, None, &token).await;
+
+    // Assert: Verify both are forbidden rather than served from the header's tenant
+    assert_eq!(status, 403);
+    verify_error_response(&body, "Forbidden");
+    assert_eq!(bare_status, 403);
+}
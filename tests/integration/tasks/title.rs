@@ -0,0 +1,74 @@
+use proptest::{
+    prelude::*,
+    test_runner::{FileFailurePersistence, TestRunner},
+};
+use rust_service_template::domain::task::models::{strategies::any_title, Title};
+
+use super::super::*;
+
+/// Failures found by [`test_create_endpoint_agrees_with_title_validation`], replayed first
+const REGRESSIONS: &str = "proptest-regressions/integration/tasks/title.txt";
+
+#[tokio::test]
+async fn test_legacy_rows_with_titles_invalid_under_new_rules_are_still_readable() {
+    // Objective: Verify rows stored before the stricter rules can still be read
+    let (app, pool) = common::app().await;
+    let legacy_title = "Legacy\u{0007}  title\u{200B}";
+
+    // Arrange: Store a title that Title::new would now reject
+    let task = TaskFixture::new(UserId::new())
+        .legacy_title(legacy_title)
+        .priority(TaskPriority::Low)
+        .insert(&pool)
+        .await;
+
+    // Act: Fetch it through the API
+    let (status, body_bytes) =
+        make_request(&app, "GET", &format!("/tasks/{}", task.id), None).await;
+
+    // Assert: Verify the title is returned exactly as stored
+    assert_eq!(status, 200, "Legacy row should still be readable");
+    assert_eq!(parse_json_response(&body_bytes)["title"], legacy_title);
+}
+
+#[test]
+fn test_create_endpoint_agrees_with_title_validation() {
+    // Objective: Verify POST /tasks accepts exactly the titles Title::new accepts, and
+    // stores them normalized
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let (app, _db) = runtime.block_on(common::app());
+    let mut runner = TestRunner::new(ProptestConfig {
+        cases: 64,
+        failure_persistence: Some(Box::new(FileFailurePersistence::Direct(REGRESSIONS))),
+        ..ProptestConfig::default()
+    });
+
+    let result = runner.run(&any_title(), |raw| {
+        // Act: Create a task with the generated title
+        let body = serde_json::json!({ "title": raw }).to_string();
+        let (status, body_bytes) = runtime.block_on(make_request(
+            &app,
+            "POST",
+            "/tasks",
+            Some(create_json_body(&body)),
+        ));
+
+        // Assert: Verify the response matches what the domain decides
+        let response = parse_json_response(&body_bytes);
+        match Title::new(raw) {
+            Ok(title) => {
+                prop_assert_eq!(status, 201);
+                prop_assert_eq!(response["title"].as_str(), Some(title.value()));
+            }
+            Err(_) => {
+                prop_assert_eq!(status, 400);
+                prop_assert_eq!(response["code"].as_str(), Some("ValidationError"));
+            }
+        }
+        Ok(())
+    });
+
+    if let Err(failure) = result {
+        panic!("{failure}");
+    }
+}
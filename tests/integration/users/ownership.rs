@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use rust_service_template::{api::build_app_router, common::TenantId, config::UserIdMode};
+
+use super::super::*;
+
+/// App that only accepts tasks for registered users
+async fn registered_app() -> (Router, common::TestDatabase) {
+    let (state, db) =
+        common::app_state_with(|config| config.user_ids = UserIdMode::Registered).await;
+    (build_app_router(Arc::new(state)).await, db)
+}
+
+/// Create a task through the API with the given JSON body
+async fn create_task(app: &Router, body: Value) -> (u16, Value) {
+    let (status, body_bytes) = make_request(
+        app,
+        "POST",
+        "/tasks",
+        Some(create_json_body(&body.to_string())),
+    )
+    .await;
+    (status, parse_json_response(&body_bytes))
+}
+
+#[tokio::test]
+async fn test_registered_user_can_own_tasks() {
+    // Objective: Verify a task created for a registered user is stored as theirs
+    let (app, _db) = registered_app().await;
+
+    // Arrange: Register the owner
+    let body = serde_json::json!({ "email": "owner@example.com", "display_name": "Owner" });
+    let (status, body_bytes) = make_request(
+        &app,
+        "POST",
+        "/users",
+        Some(create_json_body(&body.to_string())),
+    )
+    .await;
+    assert_eq!(status, 201);
+    let user_id = parse_json_response(&body_bytes)["id"].clone();
+
+    // Act: Create a task for them
+    let (status, task) = create_task(
+        &app,
+        serde_json::json!({ "title": generate_unique_title("owned"), "user_id": user_id }),
+    )
+    .await;
+
+    // Assert: Verify 201 Created with the owner set
+    assert_eq!(status, 201, "Should create the task: {task}");
+    assert_eq!(task["user_id"], user_id, "Task should belong to the user");
+}
+
+#[tokio::test]
+async fn test_task_for_unknown_user_returns_404() {
+    // Negative test: An owner nobody registered is rejected before anything is stored
+    let (app, db) = registered_app().await;
+    let unknown = UserId::new();
+
+    // Act: Create a task for an unregistered user
+    let (status, body) = create_task(
+        &app,
+        serde_json::json!({ "title": generate_unique_title("orphan"), "user_id": unknown }),
+    )
+    .await;
+
+    // Assert: Verify 404 and that no task was stored
+    assert_eq!(status, 404, "Unknown owner should be rejected: {body}");
+    assert_eq!(body["code"], "NotFound");
+    let tasks = PostgresTaskRepository::new((*db).clone())
+        .get_by_user(TenantId::DEFAULT, unknown)
+        .await
+        .unwrap();
+    assert!(tasks.is_empty(), "No task should be stored");
+}
+
+#[tokio::test]
+async fn test_task_without_user_id_returns_400_when_users_are_registered() {
+    // Negative test: Without an owner there is no one to check
+    let (app, _db) = registered_app().await;
+
+    // Act: Create a task with no user_id
+    let (status, body) = create_task(
+        &app,
+        serde_json::json!({ "title": generate_unique_title("ownerless") }),
+    )
+    .await;
+
+    // Assert: Verify 400 Bad Request
+    assert_eq!(status, 400, "Missing owner should be rejected: {body}");
+    assert_eq!(body["code"], "ValidationError");
+}
+
+#[tokio::test]
+async fn test_external_user_ids_are_accepted_unchecked() {
+    // Objective: Verify the escape hatch lets tasks name owners the service never saw
+    let (app, _db) = common::app().await;
+    let external = UserId::new();
+
+    // Act: Create a task for an id from another system
+    let (status, task) = create_task(
+        &app,
+        serde_json::json!({ "title": generate_unique_title("external"), "user_id": external }),
+    )
+    .await;
+
+    // Assert: Verify 201 Created with the id kept
+    assert_eq!(status, 201, "External ids should be accepted: {task}");
+    assert_eq!(task["user_id"], external.to_string());
+}
@@ -0,0 +1,105 @@
+use super::super::*;
+
+/// Register a user through the API, returning the response body
+async fn register(app: &Router, email: &str, display_name: &str) -> (u16, Value) {
+    let body = serde_json::json!({ "email": email, "display_name": display_name });
+    let (status, body_bytes) = make_request(
+        app,
+        "POST",
+        "/users",
+        Some(create_json_body(&body.to_string())),
+    )
+    .await;
+    (status, parse_json_response(&body_bytes))
+}
+
+#[tokio::test]
+async fn test_create_user_returns_201_with_normalized_email() {
+    // Objective: Verify registration stores the user with a trimmed, lowercase email
+    let (app, _db) = common::app().await;
+
+    // Act: Register with mixed case and surrounding whitespace
+    let (status, body) = register(&app, "  Ada.Lovelace@Example.COM ", " Ada Lovelace ").await;
+
+    // Assert: Verify 201 Created with the normalized fields
+    assert_eq!(status, 201, "Should return 201 Created: {body}");
+    assert_eq!(body["email"], "ada.lovelace@example.com");
+    assert_eq!(body["display_name"], "Ada Lovelace");
+    assert!(
+        Uuid::parse_str(body["id"].as_str().unwrap()).is_ok(),
+        "ID should be a UUID"
+    );
+    assert!(body["created_at"].is_string(), "Should have created_at");
+}
+
+#[tokio::test]
+async fn test_get_user_returns_the_registered_user() {
+    // Objective: Verify a registered user can be fetched by id
+    let (app, _db) = common::app().await;
+
+    // Arrange: Register a user
+    let (_, created) = register(&app, "grace@example.com", "Grace Hopper").await;
+
+    // Act: Fetch the user
+    let (status, body_bytes) = make_request(
+        &app,
+        "GET",
+        &format!("/users/{}", created["id"].as_str().unwrap()),
+        None,
+    )
+    .await;
+
+    // Assert: Verify 200 OK with the same user
+    assert_eq!(status, 200, "Should return 200 OK for a registered user");
+    assert_eq!(parse_json_response(&body_bytes), created);
+}
+
+#[tokio::test]
+async fn test_get_user_returns_404_for_unknown_user() {
+    // Negative test: An id nobody registered
+    let (app, _db) = common::app().await;
+
+    // Act: Fetch a random id
+    let (status, body_bytes) =
+        make_request(&app, "GET", &format!("/users/{}", Uuid::new_v4()), None).await;
+
+    // Assert: Verify 404 Not Found
+    assert_eq!(status, 404, "Should return 404 for an unknown user");
+    verify_error_response(&body_bytes, "NotFound");
+}
+
+#[tokio::test]
+async fn test_create_user_returns_409_for_email_taken_in_any_case() {
+    // Negative test: Emails are unique regardless of case
+    let (app, _db) = common::app().await;
+
+    // Arrange: Register the address once
+    let (status, _) = register(&app, "linus@example.com", "Linus").await;
+    assert_eq!(status, 201);
+
+    // Act: Register it again with different case
+    let (status, body) = register(&app, "Linus@Example.com", "Another Linus").await;
+
+    // Assert: Verify 409 Conflict
+    assert_eq!(status, 409, "Duplicate email should conflict: {body}");
+    assert_eq!(body["code"], "Conflict");
+}
+
+#[tokio::test]
+async fn test_create_user_returns_400_for_invalid_fields() {
+    // Negative test: Malformed emails and blank display names are rejected
+    let (app, _db) = common::app().await;
+
+    for (email, display_name) in [
+        ("not-an-email", "Ada"),
+        ("ada@localhost", "Ada"),
+        ("ada@example.com", "   "),
+    ] {
+        // Act: Register with the invalid field
+        let (status, body) = register(&app, email, display_name).await;
+
+        // Assert: Verify 400 Bad Request
+        assert_eq!(status, 400, "{email:?}/{display_name:?}: {body}");
+        assert_eq!(body["code"], "ValidationError");
+    }
+}
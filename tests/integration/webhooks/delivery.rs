@@ -0,0 +1,349 @@
+use std::{sync::Arc, time::Duration};
+
+use rust_service_template::{
+    api::build_app_router,
+    common::TenantId,
+    config::{AppConfig, CircuitBreakerConfig, TenancyMode, WebhookConfig},
+    domain::{
+        interfaces::event_producer::EventProducer,
+        task::models::events::{TaskEvent, TaskEventData},
+    },
+    infrastructure::{
+        circuit_breaker::{BreakerState, CircuitBreakers},
+        webhook_dispatcher::{
+            sign, WebhookDispatcher, DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER,
+        },
+    },
+};
+use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+use super::super::*;
+
+/// Time for background deliveries, including their retries, to be recorded
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delivery settings with short backoff, so retries finish within a test
+fn fast_config(max_attempts: u32, disable_after_failures: u32) -> WebhookConfig {
+    WebhookConfig {
+        enabled: true,
+        timeout_ms: 2_000,
+        max_attempts,
+        retry_base_delay_ms: 10,
+        retry_max_delay_ms: 50,
+        disable_after_failures,
+    }
+}
+
+/// The app, and a dispatcher delivering to the webhooks stored through it
+async fn app_and_dispatcher(
+    config: &WebhookConfig,
+) -> (Router, WebhookDispatcher, common::TestDatabase) {
+    let (state, db) = common::app_state().await;
+    let dispatcher = WebhookDispatcher::new(
+        state.webhook_repository.clone(),
+        &state.env.http_client_config,
+        config,
+        state.circuit_breakers.clone(),
+    )
+    .unwrap();
+    (build_app_router(Arc::new(state)).await, dispatcher, db)
+}
+
+/// Subscribe `url` to created events with a known secret, returning the webhook id
+async fn subscribe(app: &Router, url: &str, secret: &str) -> String {
+    let body = serde_json::json!({ "url": url, "secret": secret, "event_types": ["Created"] });
+    let (status, body_bytes) = make_authenticated_request(
+        app,
+        "POST",
+        "/webhooks",
+        Some(create_json_body(&body.to_string())),
+        &issue_admin_token(UserId::new()),
+    )
+    .await;
+    assert_eq!(status, 201, "Webhook should be created");
+    parse_json_response(&body_bytes)["id"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Subscribe `url` to created events as an admin of `tenant`
+async fn subscribe_as(app: &Router, tenant: TenantId, url: &str) {
+    let body = serde_json::json!({ "url": url, "event_types": ["Created"] });
+    let request = Request::builder()
+        .method("POST")
+        .uri("/webhooks")
+        .header("Content-Type", "application/json")
+        .header(
+            "Authorization",
+            format!("Bearer {}", issue_tenant_admin_token(UserId::new(), tenant)),
+        )
+        .header("X-Tenant-Id", tenant.to_string());
+    let (status, body_bytes) = send_request(app, request, Some(Body::from(body.to_string()))).await;
+    assert_eq!(
+        status,
+        201,
+        "Webhook should be created: {}",
+        parse_json_response(&body_bytes)
+    );
+}
+
+fn created_event() -> TaskEvent {
+    created_event_in(TenantId::DEFAULT)
+}
+
+fn created_event_in(tenant: TenantId) -> TaskEvent {
+    let task = TaskFixture::new(UserId::new()).tenant(tenant).build();
+    TaskEvent::new_created(
+        TaskEventData {
+            id: task.id,
+            title: task.title.value().to_string(),
+            description: task.description.clone(),
+            status: task.status,
+            priority: task.priority,
+            tenant_id: task.tenant_id,
+            user_id: task.user_id,
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            completed_at: task.completed_at,
+        },
+        Uuid::new_v4().to_string(),
+    )
+}
+
+async fn get_as_admin(app: &Router, uri: &str) -> Value {
+    let (status, body_bytes) =
+        make_authenticated_request(app, "GET", uri, None, &issue_admin_token(UserId::new())).await;
+    assert_eq!(status, 200, "GET {uri} should succeed");
+    parse_json_response(&body_bytes)
+}
+
+/// Poll the delivery history until it has `count` attempts, newest first
+async fn wait_for_deliveries(app: &Router, webhook_id: &str, count: usize) -> Vec<Value> {
+    let uri = format!("/webhooks/{webhook_id}/deliveries");
+    let deadline = tokio::time::Instant::now() + DELIVERY_TIMEOUT;
+    loop {
+        let deliveries = get_as_admin(app, &uri).await.as_array().unwrap().clone();
+        if deliveries.len() >= count {
+            return deliveries;
+        }
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "Expected {count} deliveries, got {deliveries:?}"
+        );
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+}
+
+#[tokio::test]
+async fn test_event_is_delivered_with_a_verifiable_signature() {
+    // Objective: Verify the receiver gets the event JSON, signed with the webhook's secret
+    let server = MockServer::start().await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::path("/hooks"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let (app, dispatcher, _db) = app_and_dispatcher(&fast_config(3, 10)).await;
+    let secret = "a-secret-of-sufficient-length";
+    let webhook_id = subscribe(&app, &format!("{}/hooks", server.uri()), secret).await;
+    let event = created_event();
+
+    // Act: Publish the event and wait for the delivery to be recorded
+    dispatcher.publish_task_event(event.clone()).await.unwrap();
+    let deliveries = wait_for_deliveries(&app, &webhook_id, 1).await;
+
+    // Assert: Verify the request carried the event and a matching signature
+    let requests = server.received_requests().await.unwrap();
+    let request = &requests[0];
+    let body: Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body["event_id"], event.event_id.to_string());
+    assert_eq!(
+        request.headers[SIGNATURE_HEADER].to_str().unwrap(),
+        sign(secret, &request.body)
+    );
+    assert_eq!(request.headers[EVENT_HEADER], "Created");
+    assert_eq!(
+        request.headers[DELIVERY_HEADER].to_str().unwrap(),
+        event.event_id.to_string()
+    );
+    assert_eq!(deliveries[0]["succeeded"], true);
+    assert_eq!(deliveries[0]["status_code"], 204);
+}
+
+#[tokio::test]
+async fn test_event_is_only_delivered_to_webhooks_of_its_tenant() {
+    // Objective: Verify tenant B's webhook never receives an event of tenant A's tasks
+    let (receiver_a, receiver_b) = (MockServer::start().await, MockServer::start().await);
+    for (server, expected) in [(&receiver_a, 1), (&receiver_b, 0)] {
+        Mock::given(matchers::method("POST"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(expected)
+            .mount(server)
+            .await;
+    }
+    let (state, _db) =
+        common::app_state_with(|config: &mut AppConfig| config.tenancy = TenancyMode::Multi).await;
+    let dispatcher = WebhookDispatcher::new(
+        state.webhook_repository.clone(),
+        &state.env.http_client_config,
+        &fast_config(1, 10),
+        state.circuit_breakers.clone(),
+    )
+    .unwrap();
+    let app = build_app_router(Arc::new(state)).await;
+    let (tenant_a, tenant_b) = (TenantId::new(), TenantId::new());
+
+    // Arrange: Each tenant subscribes its own receiver to created events
+    subscribe_as(&app, tenant_a, &receiver_a.uri()).await;
+    subscribe_as(&app, tenant_b, &receiver_b.uri()).await;
+
+    // Act: Publish an event of tenant A and wait for its delivery
+    dispatcher
+        .publish_task_event(created_event_in(tenant_a))
+        .await
+        .unwrap();
+    let deadline = tokio::time::Instant::now() + DELIVERY_TIMEOUT;
+    while receiver_a.received_requests().await.unwrap().is_empty() {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "Tenant A's webhook should receive the event"
+        );
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // Assert: Only tenant A's webhook was called
+    receiver_a.verify().await;
+    receiver_b.verify().await;
+}
+
+#[tokio::test]
+async fn test_failed_delivery_is_retried_and_every_attempt_recorded() {
+    // Objective: Verify a server error is retried and both attempts show in the history
+    let server = MockServer::start().await;
+    Mock::given(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("database down"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    let (app, dispatcher, _db) = app_and_dispatcher(&fast_config(3, 10)).await;
+    let webhook_id = subscribe(&app, &server.uri(), "a-secret-of-sufficient-length").await;
+
+    // Act: Publish an event the first attempt fails for
+    dispatcher
+        .publish_task_event(created_event())
+        .await
+        .unwrap();
+    let deliveries = wait_for_deliveries(&app, &webhook_id, 2).await;
+
+    // Assert: Verify the failure, then the successful retry of the same event
+    assert_eq!(deliveries[0]["attempt"], 2);
+    assert_eq!(deliveries[0]["succeeded"], true);
+    assert_eq!(deliveries[1]["attempt"], 1);
+    assert_eq!(deliveries[1]["succeeded"], false);
+    assert_eq!(deliveries[1]["status_code"], 500);
+    assert!(
+        deliveries[1]["error"]
+            .as_str()
+            .unwrap()
+            .contains("database down"),
+        "Error should quote the response: {}",
+        deliveries[1]
+    );
+    assert_eq!(deliveries[0]["event_id"], deliveries[1]["event_id"]);
+    let webhook = get_as_admin(&app, &format!("/webhooks/{webhook_id}")).await;
+    assert_eq!(webhook["consecutive_failures"], 0);
+}
+
+#[tokio::test]
+async fn test_webhook_is_deactivated_after_consecutive_failures() {
+    // Negative test: An endpoint that always fails stops receiving events
+    let server = MockServer::start().await;
+    Mock::given(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+    let (app, dispatcher, _db) = app_and_dispatcher(&fast_config(1, 2)).await;
+    let webhook_id = subscribe(&app, &server.uri(), "a-secret-of-sufficient-length").await;
+
+    // Act: Publish two events that fail, then a third
+    for count in 1..=2 {
+        dispatcher
+            .publish_task_event(created_event())
+            .await
+            .unwrap();
+        wait_for_deliveries(&app, &webhook_id, count).await;
+    }
+    let deadline = tokio::time::Instant::now() + DELIVERY_TIMEOUT;
+    let webhook = loop {
+        let webhook = get_as_admin(&app, &format!("/webhooks/{webhook_id}")).await;
+        if webhook["active"] == false || tokio::time::Instant::now() > deadline {
+            break webhook;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    };
+    dispatcher
+        .publish_task_event(created_event())
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // Assert: Verify the webhook was deactivated and got nothing more
+    assert_eq!(
+        webhook["active"], false,
+        "Webhook should be deactivated: {webhook}"
+    );
+    assert_eq!(webhook["consecutive_failures"], 2);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_open_circuit_breaker_stops_sending_to_a_failing_endpoint() {
+    // Negative test: Once the webhook's breaker opens, the remaining attempts are recorded
+    // as failed without reaching the endpoint
+    let server = MockServer::start().await;
+    Mock::given(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+    let (state, _db) = common::app_state().await;
+    let breakers = CircuitBreakers::new(&CircuitBreakerConfig {
+        minimum_calls: 2,
+        ..CircuitBreakerConfig::default()
+    });
+    let dispatcher = WebhookDispatcher::new(
+        state.webhook_repository.clone(),
+        &state.env.http_client_config,
+        &fast_config(4, 10),
+        breakers.clone(),
+    )
+    .unwrap();
+    let app = build_app_router(Arc::new(state)).await;
+    let webhook_id = subscribe(&app, &server.uri(), "a-secret-of-sufficient-length").await;
+
+    // Act: Publish an event whose four attempts all fail
+    dispatcher
+        .publish_task_event(created_event())
+        .await
+        .unwrap();
+    let deliveries = wait_for_deliveries(&app, &webhook_id, 4).await;
+
+    // Assert: Only the first two attempts were sent before the breaker opened
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    assert_eq!(deliveries[0]["status_code"], Value::Null);
+    assert_eq!(
+        deliveries[0]["error"],
+        format!("Not sent: Circuit breaker webhook:{webhook_id} is open")
+    );
+    assert_eq!(deliveries[2]["status_code"], 503);
+    assert_eq!(
+        breakers.states()[&format!("webhook:{webhook_id}")],
+        BreakerState::Open
+    );
+}
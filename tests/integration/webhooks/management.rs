@@ -0,0 +1,298 @@
+use std::sync::Arc;
+
+use rust_service_template::{
+    api::build_app_router,
+    common::TenantId,
+    config::{AppConfig, TenancyMode},
+    domain::quota::models::Quota,
+};
+
+use super::super::*;
+
+/// Send a webhook API request with an admin token, returning the parsed body
+async fn admin_request(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (u16, Value) {
+    let token = issue_admin_token(UserId::new());
+    let body = body.map(|body| create_json_body(&body.to_string()));
+    let (status, body_bytes) = make_authenticated_request(app, method, uri, body, &token).await;
+    (status, parse_body(&body_bytes))
+}
+
+/// [`admin_request`] as an admin of `tenant`, also named in the `X-Tenant-Id` header
+async fn tenant_admin_request(
+    app: &Router,
+    tenant: TenantId,
+    method: &str,
+    uri: &str,
+    body: Option<Value>,
+) -> (u16, Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .header(
+            "Authorization",
+            format!("Bearer {}", issue_tenant_admin_token(UserId::new(), tenant)),
+        )
+        .header("X-Tenant-Id", tenant.to_string());
+    let (status, body_bytes) =
+        send_request(app, request, body.map(|body| Body::from(body.to_string()))).await;
+    (status, parse_body(&body_bytes))
+}
+
+fn parse_body(body_bytes: &[u8]) -> Value {
+    if body_bytes.is_empty() {
+        Value::Null
+    } else {
+        parse_json_response(body_bytes)
+    }
+}
+
+#[tokio::test]
+async fn test_create_webhook_returns_its_secret_once() {
+    // Objective: Verify a created webhook subscribes to every event type and shows the
+    // generated secret only in the creation response
+    let (app, _db) = common::app().await;
+
+    // Act: Create a webhook without event types or secret, then fetch it
+    let (status, created) = admin_request(
+        &app,
+        "POST",
+        "/webhooks",
+        Some(serde_json::json!({ "url": "https://hooks.example.com/tasks" })),
+    )
+    .await;
+    let (_, fetched) = admin_request(
+        &app,
+        "GET",
+        &format!("/webhooks/{}", created["id"].as_str().unwrap()),
+        None,
+    )
+    .await;
+
+    // Assert: Verify 201 Created with a secret that is not shown again
+    assert_eq!(status, 201, "Should return 201 Created: {created}");
+    assert_eq!(
+        created["event_types"],
+        serde_json::json!(["Created", "Updated", "Deleted"])
+    );
+    assert_eq!(created["active"], true);
+    assert_eq!(created["secret"].as_str().unwrap().len(), 64);
+    assert!(fetched.get("secret").is_none(), "Secret leaked: {fetched}");
+    assert_eq!(fetched["url"], created["url"]);
+}
+
+#[tokio::test]
+async fn test_update_list_and_delete_webhook() {
+    // Objective: Verify a webhook can be changed, is listed, and is gone once deleted
+    let (app, _db) = common::app().await;
+
+    // Arrange: Create a webhook
+    let (_, created) = admin_request(
+        &app,
+        "POST",
+        "/webhooks",
+        Some(serde_json::json!({
+            "url": "https://hooks.example.com/tasks",
+            "secret": "a-secret-of-sufficient-length",
+            "event_types": ["Created"]
+        })),
+    )
+    .await;
+    let uri = format!("/webhooks/{}", created["id"].as_str().unwrap());
+
+    // Act: Deactivate it with other event types, list, then delete it
+    let (update_status, updated) = admin_request(
+        &app,
+        "PUT",
+        &uri,
+        Some(serde_json::json!({
+            "url": "https://hooks.example.com/v2/tasks",
+            "event_types": ["Deleted", "Updated"],
+            "active": false
+        })),
+    )
+    .await;
+    let (_, listed) = admin_request(&app, "GET", "/webhooks", None).await;
+    let (delete_status, _) = admin_request(&app, "DELETE", &uri, None).await;
+    let (get_status, _) = admin_request(&app, "GET", &uri, None).await;
+
+    // Assert: Verify each step
+    assert_eq!(update_status, 200, "Should return 200 OK: {updated}");
+    assert_eq!(updated["url"], "https://hooks.example.com/v2/tasks");
+    assert_eq!(
+        updated["event_types"],
+        serde_json::json!(["Updated", "Deleted"])
+    );
+    assert_eq!(updated["active"], false);
+    assert!(
+        listed
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|webhook| webhook["id"] == created["id"]),
+        "Webhook should be listed: {listed}"
+    );
+    assert_eq!(delete_status, 204, "Should return 204 No Content");
+    assert_eq!(get_status, 404, "Deleted webhook should be gone");
+}
+
+#[tokio::test]
+async fn test_create_webhook_rejects_invalid_url_and_secret() {
+    // Negative test: URLs that are not absolute HTTP(S) and secrets that are too short
+    let (app, _db) = common::app().await;
+
+    for body in [
+        serde_json::json!({ "url": "ftp://hooks.example.com/tasks" }),
+        serde_json::json!({ "url": "/tasks" }),
+        serde_json::json!({ "url": "https://hooks.example.com/tasks", "secret": "short" }),
+    ] {
+        // Act: Create the webhook
+        let (status, response) = admin_request(&app, "POST", "/webhooks", Some(body.clone())).await;
+
+        // Assert: Verify 400 with a validation error
+        assert_eq!(status, 400, "Should reject {body}");
+        assert_eq!(response["code"], "ValidationError");
+    }
+}
+
+#[tokio::test]
+async fn test_webhook_routes_require_the_admin_role() {
+    // Negative test: A valid token without the admin role
+    let (app, _db) = common::app().await;
+    let token = issue_test_token(UserId::new(), chrono::Duration::minutes(5));
+
+    for (method, uri) in [
+        ("GET", "/webhooks".to_string()),
+        ("GET", format!("/webhooks/{}", Uuid::new_v4())),
+        ("DELETE", format!("/webhooks/{}", Uuid::new_v4())),
+        ("GET", format!("/webhooks/{}/deliveries", Uuid::new_v4())),
+    ] {
+        // Act: Call the route as a regular user
+        let (status, body_bytes) =
+            make_authenticated_request(&app, method, &uri, None, &token).await;
+
+        // Assert: Verify 403 Forbidden
+        assert_eq!(status, 403, "{method} {uri} should be forbidden");
+        verify_error_response(&body_bytes, "Forbidden");
+    }
+}
+
+#[tokio::test]
+async fn test_unknown_webhook_returns_404() {
+    // Negative test: Routes addressing a webhook that does not exist
+    let (app, _db) = common::app().await;
+    let uri = format!("/webhooks/{}", Uuid::new_v4());
+    let update = serde_json::json!({
+        "url": "https://hooks.example.com/tasks",
+        "event_types": [],
+        "active": true
+    });
+
+    for (method, uri, body) in [
+        ("PUT", uri.clone(), Some(update)),
+        ("DELETE", uri.clone(), None),
+        ("GET", format!("{uri}/deliveries"), None),
+    ] {
+        // Act: Call the route
+        let (status, response) = admin_request(&app, method, &uri, body).await;
+
+        // Assert: Verify 404 Not Found
+        assert_eq!(status, 404, "{method} {uri}: {response}");
+        assert_eq!(response["code"], "NotFound");
+    }
+}
+
+#[tokio::test]
+async fn test_webhook_of_another_tenant_is_not_found() {
+    // Objective: Verify a webhook created by tenant A is invisible to tenant B
+    // Negative test: Tenant B gets the same 404 as for a webhook that does not exist
+    let (state, _db) =
+        common::app_state_with(|config: &mut AppConfig| config.tenancy = TenancyMode::Multi).await;
+    let app = build_app_router(Arc::new(state)).await;
+    let (tenant_a, tenant_b) = (TenantId::new(), TenantId::new());
+    let (status, created) = tenant_admin_request(
+        &app,
+        tenant_a,
+        "POST",
+        "/webhooks",
+        Some(serde_json::json!({ "url": "https://hooks.example.com/tasks" })),
+    )
+    .await;
+    assert_eq!(status, 201, "{created}");
+    let uri = format!("/webhooks/{}", created["id"].as_str().unwrap());
+
+    // Act: List and address the webhook as each tenant
+    let (_, own_list) = tenant_admin_request(&app, tenant_a, "GET", "/webhooks", None).await;
+    let (_, other_list) = tenant_admin_request(&app, tenant_b, "GET", "/webhooks", None).await;
+    let (own_get, _) = tenant_admin_request(&app, tenant_a, "GET", &uri, None).await;
+    let mut other_statuses = Vec::new();
+    for (method, uri) in [
+        ("GET", uri.clone()),
+        ("GET", format!("{uri}/deliveries")),
+        ("DELETE", uri.clone()),
+    ] {
+        let (status, _) = tenant_admin_request(&app, tenant_b, method, &uri, None).await;
+        other_statuses.push(status);
+    }
+
+    // Assert: Verify only tenant A sees the webhook, which tenant B could not delete
+    assert_eq!(own_list.as_array().map(Vec::len), Some(1), "{own_list}");
+    assert_eq!(other_list, serde_json::json!([]));
+    assert_eq!(own_get, 200);
+    assert_eq!(other_statuses, [404, 404, 404]);
+    let (still_there, _) = tenant_admin_request(&app, tenant_a, "GET", &uri, None).await;
+    assert_eq!(still_there, 200);
+}
+
+#[tokio::test]
+async fn test_webhook_creation_stops_at_hard_quota() {
+    // Objective: Verify webhooks past the hard quota are refused with QuotaExceeded
+    let (state, _db) = common::app_state_with(|config: &mut AppConfig| {
+        config.quota_config.webhooks = Quota {
+            soft: None,
+            hard: Some(1),
+        };
+    })
+    .await;
+    let app = build_app_router(Arc::new(state)).await;
+    let body = serde_json::json!({ "url": "https://hooks.example.com/tasks" });
+
+    // Act: Create two webhooks
+    let (first, _) = admin_request(&app, "POST", "/webhooks", Some(body.clone())).await;
+    let (second, response) = admin_request(&app, "POST", "/webhooks", Some(body)).await;
+
+    // Assert: Verify the second is refused
+    assert_eq!(first, 201);
+    assert_eq!(second, 403, "{response}");
+    assert_eq!(response["code"], "QuotaExceeded");
+    assert_eq!(response["message"], "webhooks is limited to 1");
+}
+
+#[tokio::test]
+async fn test_webhook_quota_is_counted_per_tenant() {
+    // Objective: Verify one tenant's webhooks do not use up another tenant's quota
+    let (state, _db) = common::app_state_with(|config: &mut AppConfig| {
+        config.tenancy = TenancyMode::Multi;
+        config.quota_config.webhooks = Quota {
+            soft: None,
+            hard: Some(1),
+        };
+    })
+    .await;
+    let app = build_app_router(Arc::new(state)).await;
+    let (tenant_a, tenant_b) = (TenantId::new(), TenantId::new());
+    let body = serde_json::json!({ "url": "https://hooks.example.com/tasks" });
+
+    // Act: Tenant A creates two webhooks, then tenant B one
+    let (first, _) =
+        tenant_admin_request(&app, tenant_a, "POST", "/webhooks", Some(body.clone())).await;
+    let (second, refused) =
+        tenant_admin_request(&app, tenant_a, "POST", "/webhooks", Some(body.clone())).await;
+    let (other, _) = tenant_admin_request(&app, tenant_b, "POST", "/webhooks", Some(body)).await;
+
+    // Assert: Only tenant A's second webhook goes past the quota
+    assert_eq!(first, 201);
+    assert_eq!(second, 403, "{refused}");
+    assert_eq!(refused["code"], "QuotaExceeded");
+    assert_eq!(other, 201, "Tenant B should have its own quota");
+}
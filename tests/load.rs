@@ -0,0 +1,342 @@
+//! Latency and throughput of the task endpoints under concurrent load, and the size of
+//! their JSON and MessagePack payloads
+//!
+//! Ignored by default, as seeding and driving the endpoints takes a while. The service
+//! listens on an ephemeral port and is driven over real HTTP, against the Postgres the
+//! integration tests use (see `tests/common.rs`). Build in release mode, or the numbers
+//! mostly measure the debug build:
+//!
+//! ```sh
+//! docker compose up -d postgres
+//! cargo test --release --test load -- --ignored --nocapture
+//! ```
+//!
+//! Tuned through environment variables:
+//! - `LOAD_TASKS`: tasks seeded before measuring, 100000 by default
+//! - `LOAD_REQUESTS`: requests sent to each endpoint, 5000 by default
+//! - `LOAD_CONCURRENCY`: requests in flight at once, 32 by default
+//! - `LOAD_P99_BUDGET_MS`: fail when an endpoint's p99 latency exceeds it; unset only reports
+
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use reqwest::StatusCode;
+use rust_service_template::api::build_app_router;
+use tokio::net::TcpListener;
+use uuid::Uuid;
+
+// Only the state setup is used here
+#[allow(dead_code)]
+mod common;
+#[path = "common/seed.rs"]
+mod seed;
+
+/// Settings of one run, read from the environment
+struct LoadConfig {
+    tasks: usize,
+    requests: usize,
+    concurrency: usize,
+    p99_budget: Option<Duration>,
+}
+
+impl LoadConfig {
+    fn from_env() -> Self {
+        let var = |name: &str, default: usize| {
+            std::env::var(name).map_or(default, |value| {
+                value
+                    .parse()
+                    .unwrap_or_else(|_| panic!("{name} must be a number, got {value:?}"))
+            })
+        };
+        Self {
+            tasks: var("LOAD_TASKS", 100_000),
+            requests: var("LOAD_REQUESTS", 5_000),
+            concurrency: var("LOAD_CONCURRENCY", 32),
+            p99_budget: std::env::var("LOAD_P99_BUDGET_MS")
+                .is_ok()
+                .then(|| Duration::from_millis(var("LOAD_P99_BUDGET_MS", 0) as u64)),
+        }
+    }
+}
+
+/// Measurements of one endpoint
+struct Summary {
+    name: &'static str,
+    /// Latencies of the successful requests, sorted
+    latencies: Vec<Duration>,
+    failures: usize,
+    elapsed: Duration,
+}
+
+impl Summary {
+    fn percentile(&self, percentile: f64) -> Duration {
+        let Some(last) = self.latencies.len().checked_sub(1) else {
+            return Duration::ZERO;
+        };
+        let index = (last as f64 * percentile / 100.0).round() as usize;
+        self.latencies[index]
+    }
+
+    fn throughput(&self) -> f64 {
+        self.latencies.len() as f64 / self.elapsed.as_secs_f64()
+    }
+
+    fn report(&self) {
+        println!(
+            "{:<16} {:>8} ok {:>6} failed {:>10.1} req/s   p50 {:>8.2?}   p99 {:>8.2?}   max {:>8.2?}",
+            self.name,
+            self.latencies.len(),
+            self.failures,
+            self.throughput(),
+            self.percentile(50.0),
+            self.percentile(99.0),
+            self.latencies.last().copied().unwrap_or_default(),
+        );
+    }
+}
+
+/// Send `requests` requests, `concurrency` at a time, timing each one
+///
+/// `send` gets the request's index and returns whether it succeeded.
+async fn drive<F, Fut>(name: &'static str, config: &LoadConfig, send: F) -> Summary
+where
+    F: Fn(usize) -> Fut + Clone + Send + 'static,
+    Fut: Future<Output = bool> + Send,
+{
+    let next = Arc::new(AtomicUsize::new(0));
+    let requests = config.requests;
+    let started = Instant::now();
+
+    let workers: Vec<_> = (0..config.concurrency)
+        .map(|_| {
+            let (next, send) = (next.clone(), send.clone());
+            tokio::spawn(async move {
+                let mut latencies = Vec::new();
+                let mut failures = 0;
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    if index >= requests {
+                        return (latencies, failures);
+                    }
+                    let sent = Instant::now();
+                    if send(index).await {
+                        latencies.push(sent.elapsed());
+                    } else {
+                        failures += 1;
+                    }
+                }
+            })
+        })
+        .collect();
+
+    let mut summary = Summary {
+        name,
+        latencies: Vec::with_capacity(requests),
+        failures: 0,
+        elapsed: Duration::ZERO,
+    };
+    for worker in workers {
+        let (latencies, failures) = worker.await.expect("Load worker panicked");
+        summary.latencies.extend(latencies);
+        summary.failures += failures;
+    }
+    summary.elapsed = started.elapsed();
+    summary.latencies.sort_unstable();
+    summary
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "load test; run with --ignored, see the module docs"]
+async fn test_task_endpoints_under_load() {
+    // Objective: Report p50/p99 latency and throughput of create, get and list on a large
+    // dataset, failing only when a p99 budget is set and exceeded
+    let config = LoadConfig::from_env();
+    if std::env::var("RUST_LOG").is_err() {
+        // Per-request debug logging would dominate the measurements
+        std::env::set_var("RUST_LOG", "warn");
+    }
+
+    // Arrange: Seed the dataset and serve the app on an ephemeral port
+    let (mut state, db) = common::app_state().await;
+    state.env.concurrency_config.max_concurrent_requests = config.concurrency.max(1) * 2;
+    let seeding = Instant::now();
+    let (users, tasks) = seed::seed_tasks(&db, config.tasks)
+        .await
+        .expect("Failed to seed tasks");
+    println!("Seeded {} tasks in {:.2?}", tasks.len(), seeding.elapsed());
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let app = build_app_router(Arc::new(state)).await;
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    let client = reqwest::Client::builder()
+        .pool_max_idle_per_host(config.concurrency)
+        .build()
+        .unwrap();
+    let (users, tasks) = (Arc::new(users), Arc::new(tasks));
+
+    // Act: Drive each endpoint in turn
+    let create = drive("POST /tasks", &config, {
+        let (client, base_url) = (client.clone(), base_url.clone());
+        move |index| {
+            let request = client
+                .post(format!("{base_url}/tasks"))
+                .json(&serde_json::json!({ "title": format!("Load task {index}") }));
+            async move { succeeded(request, StatusCode::CREATED).await }
+        }
+    })
+    .await;
+    let get = drive("GET /tasks/{id}", &config, {
+        let (client, base_url) = (client.clone(), base_url.clone());
+        move |index| {
+            let id = tasks[index % tasks.len()];
+            let request = client.get(format!("{base_url}/tasks/{id}"));
+            async move { succeeded(request, StatusCode::OK).await }
+        }
+    })
+    .await;
+    let list = drive("GET /tasks", &config, {
+        let (client, base_url) = (client.clone(), base_url.clone());
+        move |index| {
+            let user_id: Uuid = users[index % users.len()];
+            let request = client.get(format!("{base_url}/tasks?user_id={user_id}"));
+            async move { succeeded(request, StatusCode::OK).await }
+        }
+    })
+    .await;
+
+    // Assert: Report every endpoint, then hold them to the budget
+    println!(
+        "{} requests per endpoint, {} concurrent",
+        config.requests, config.concurrency
+    );
+    let summaries = [create, get, list];
+    for summary in &summaries {
+        summary.report();
+    }
+    for summary in &summaries {
+        assert_eq!(summary.failures, 0, "{} had failed requests", summary.name);
+        if let Some(budget) = config.p99_budget {
+            assert!(
+                summary.percentile(99.0) <= budget,
+                "{} p99 of {:.2?} exceeds the budget of {budget:?}",
+                summary.name,
+                summary.percentile(99.0)
+            );
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "load test; run with --ignored, see the module docs"]
+async fn test_task_payload_sizes_by_format() {
+    // Objective: Report the bytes per response and latency of get and list in JSON and in
+    // MessagePack, failing if MessagePack is not the smaller encoding
+    let config = LoadConfig::from_env();
+    if std::env::var("RUST_LOG").is_err() {
+        std::env::set_var("RUST_LOG", "warn");
+    }
+
+    // Arrange: Seed the dataset and serve the app on an ephemeral port
+    let (mut state, db) = common::app_state().await;
+    state.env.concurrency_config.max_concurrent_requests = config.concurrency.max(1) * 2;
+    let (users, tasks) = seed::seed_tasks(&db, config.tasks)
+        .await
+        .expect("Failed to seed tasks");
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let app = build_app_router(Arc::new(state)).await;
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    let client = reqwest::Client::builder()
+        .pool_max_idle_per_host(config.concurrency)
+        .build()
+        .unwrap();
+    let (users, tasks) = (Arc::new(users), Arc::new(tasks));
+
+    // Act: Drive get and list once per format, counting the response bytes
+    let mut results = Vec::new();
+    for (name, accept) in [
+        ("GET /tasks/{id}", "application/json"),
+        ("GET /tasks/{id}", "application/msgpack"),
+        ("GET /tasks", "application/json"),
+        ("GET /tasks", "application/msgpack"),
+    ] {
+        let bytes = Arc::new(AtomicUsize::new(0));
+        let summary = drive(name, &config, {
+            let (client, base_url, bytes) = (client.clone(), base_url.clone(), bytes.clone());
+            let (users, tasks) = (users.clone(), tasks.clone());
+            move |index| {
+                let uri = if name == "GET /tasks" {
+                    format!("{base_url}/tasks?user_id={}", users[index % users.len()])
+                } else {
+                    format!("{base_url}/tasks/{}", tasks[index % tasks.len()])
+                };
+                let request = client.get(uri).header(reqwest::header::ACCEPT, accept);
+                let bytes = bytes.clone();
+                async move { response_size(request, &bytes).await }
+            }
+        })
+        .await;
+        let per_response = bytes.load(Ordering::Relaxed) / summary.latencies.len().max(1);
+        results.push((summary, accept, per_response));
+    }
+
+    // Assert: Report every endpoint in both formats, then compare the sizes
+    for (summary, accept, per_response) in &results {
+        print!("{accept:<20} {per_response:>8} B/response   ");
+        summary.report();
+    }
+    for pair in results.chunks(2) {
+        let [(json, _, json_size), (_, _, msgpack_size)] = pair else {
+            unreachable!("Formats are driven in pairs");
+        };
+        assert_eq!(
+            json.failures + pair[1].0.failures,
+            0,
+            "{} had failed requests",
+            json.name
+        );
+        assert!(
+            msgpack_size < json_size,
+            "{} MessagePack responses ({msgpack_size} B) should be smaller than JSON ({json_size} B)",
+            json.name
+        );
+    }
+}
+
+/// Send `request`, adding the size of a 200 response's body to `bytes`
+async fn response_size(request: reqwest::RequestBuilder, bytes: &AtomicUsize) -> bool {
+    match request.send().await {
+        Ok(response) if response.status() == StatusCode::OK => match response.bytes().await {
+            Ok(body) => {
+                bytes.fetch_add(body.len(), Ordering::Relaxed);
+                true
+            }
+            Err(_) => false,
+        },
+        Ok(_) => false,
+        Err(e) => {
+            eprintln!("Request failed: {e}");
+            false
+        }
+    }
+}
+
+/// Send `request`, reporting whether it returned `expected`
+async fn succeeded(request: reqwest::RequestBuilder, expected: StatusCode) -> bool {
+    match request.send().await {
+        // Reading the body lets the connection go back to the pool
+        Ok(response) => response.status() == expected && response.bytes().await.is_ok(),
+        Err(e) => {
+            eprintln!("Request failed: {e}");
+            false
+        }
+    }
+}